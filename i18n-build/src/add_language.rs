@@ -0,0 +1,232 @@
+//! Adding a new language to a crate which is already being
+//! localized, used by the `cargo i18n add-language` command.
+
+use crate::error::PathError;
+use crate::fluent_impl::FluentLayout;
+use crate::usage::{message_usages, SourceLocation};
+use crate::{gettext_impl, util};
+
+use anyhow::{anyhow, Context, Result};
+use i18n_config::Crate;
+use log::info;
+use std::{
+    collections::BTreeMap,
+    fs::{read_to_string, write},
+    path::{Path, PathBuf},
+};
+use tr::tr;
+use unic_langid::LanguageIdentifier;
+
+/// Add the `language` to the crate `crt`:
+///
+/// + For the fluent system, create the language files in the
+///   `assets_dir` for the `language`, pre-populated with the
+///   messages of the `fallback_language` commented out, ready to be
+///   translated. Each message is preceded by a comment with the
+///   locations of the `fl!()` calls which use it (see
+///   [message_usages()]).
+/// + For the gettext system, append the `language` to the
+///   `target_languages` in the crate's i18n config file, and create
+///   its `po` file using `msginit` (if the `pot` file has already
+///   been created by a previous run of `cargo i18n`).
+///
+/// Returns the paths of the files which were created or modified.
+pub fn add_language(crt: &Crate, language: &str) -> Result<Vec<PathBuf>> {
+    let language: LanguageIdentifier = language.parse().with_context(|| {
+        tr!(
+            // {0} is the language identifier which was requested.
+            "\"{0}\" is not a valid language identifier.",
+            language
+        )
+    })?;
+    let config = crt.config_or_err()?;
+    if language == config.fallback_language {
+        return Err(anyhow!(tr!(
+            // {0} is the language identifier which was requested.
+            "\"{0}\" is already the fallback language.",
+            language
+        )));
+    }
+
+    let mut changed = Vec::new();
+
+    if let Some(fluent_config) = &config.fluent {
+        changed.extend(add_fluent_language(
+            &FluentLayout::new(crt, fluent_config)?,
+            &config.fallback_language,
+            &language,
+            &message_usages(crt)?,
+        )?);
+    }
+
+    if let Some(gettext_config) = &config.gettext {
+        if gettext_config
+            .target_languages
+            .iter()
+            .any(|target_language| target_language == &language.to_string())
+        {
+            return Err(anyhow!(tr!(
+                // {0} is the language identifier which was requested.
+                "\"{0}\" is already one of the target languages.",
+                language
+            )));
+        }
+
+        let config_file_path = crt.path.join(&crt.config_file_path);
+        append_target_language(&config_file_path, &language.to_string())?;
+        changed.push(config_file_path);
+
+        // Reload the crate so that it includes the new target language.
+        let crt = Crate::from(crt.path.clone(), None, crt.config_file_path.clone())?;
+        let gettext_config = crt.gettext_config_or_err()?;
+        let pot_dir = crt.path.join(gettext_config.pot_dir());
+        let po_dir = crt.path.join(gettext_config.po_dir());
+        if pot_dir
+            .join(crt.module_name())
+            .with_extension("pot")
+            .exists()
+        {
+            gettext_impl::run_msginit(&crt, &pot_dir, &po_dir)?;
+            changed.push(
+                po_dir
+                    .join(language.to_string())
+                    .join(crt.module_name())
+                    .with_extension("po"),
+            );
+        } else {
+            info!(
+                "The po file for \"{0}\" will be created the next time `cargo i18n` is run.",
+                language
+            );
+        }
+    }
+
+    Ok(changed)
+}
+
+/// Create the fluent files for the `language` in the assets
+/// directory, with the messages of the `fallback_language` commented
+/// out, and the locations of the messages in the `usages`.
+fn add_fluent_language(
+    layout: &FluentLayout,
+    fallback_language: &LanguageIdentifier,
+    language: &LanguageIdentifier,
+    usages: &BTreeMap<String, Vec<SourceLocation>>,
+) -> Result<Vec<PathBuf>> {
+    let domain_file_name = format!("{0}.ftl", layout.domain);
+
+    let main_file_path = layout.path(language, Path::new(&domain_file_name));
+    if main_file_path.exists() {
+        return Err(anyhow!(tr!(
+            // {0} is the path to the fluent file for the language.
+            "The language file \"{0}\" already exists.",
+            main_file_path.to_string_lossy()
+        )));
+    }
+
+    let source_paths = layout.file_paths(fallback_language)?;
+
+    let mut changed = Vec::new();
+    for source_path in source_paths {
+        let fallback_path = layout.path(fallback_language, &source_path);
+        let source = read_to_string(&fallback_path).with_context(|| {
+            tr!(
+                // {0} is the path to the fluent file of the fallback language.
+                "Unable to read the fallback language file \"{0}\".",
+                fallback_path.to_string_lossy()
+            )
+        })?;
+
+        let path = layout.path(language, &source_path);
+        if path.exists() {
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            util::create_dir_all_if_not_exists(parent)?;
+        }
+        write(&path, comment_out(&source, fallback_language, usages))
+            .map_err(|err| PathError::cannot_create_file(&path, err))?;
+        changed.push(path);
+    }
+    Ok(changed)
+}
+
+/// Comment out each of the messages in the fluent `source`, so that
+/// they can be translated and then uncommented. The messages are
+/// preceded by their locations in the `usages`.
+fn comment_out(
+    source: &str,
+    fallback_language: &LanguageIdentifier,
+    usages: &BTreeMap<String, Vec<SourceLocation>>,
+) -> String {
+    let mut commented = format!(
+        "# Copied from the \"{fallback_language}\" messages, uncomment each message once it has been translated.\n\n"
+    );
+    for line in source.lines() {
+        // The line starting a message, e.g. `hello-world = Hello World!`.
+        let message_id = line
+            .split_once('=')
+            .map(|(id, _)| id.trim_end())
+            .filter(|id| {
+                id.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && id
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            });
+        if let Some(locations) = message_id.and_then(|id| usages.get(id)) {
+            let locations: Vec<String> = locations.iter().map(ToString::to_string).collect();
+            commented.push_str(&format!("# Used in {0}\n", locations.join(", ")));
+        }
+        if !(line.trim().is_empty() || line.starts_with('#')) {
+            commented.push_str("# ");
+        }
+        commented.push_str(line);
+        commented.push('\n');
+    }
+    commented
+}
+
+/// Append the `language` to the `target_languages` array in the
+/// `[gettext]` section of the i18n config file at `config_file_path`,
+/// preserving its formatting.
+fn append_target_language(config_file_path: &Path, language: &str) -> Result<()> {
+    let config = read_to_string(config_file_path).with_context(|| {
+        tr!(
+            // {0} is the path to the i18n config file.
+            "Unable to read \"{0}\".",
+            config_file_path.to_string_lossy()
+        )
+    })?;
+
+    let not_found = || {
+        anyhow!(tr!(
+            // {0} is the path to the i18n config file.
+            "Unable to find the gettext `target_languages` in \"{0}\".",
+            config_file_path.to_string_lossy()
+        ))
+    };
+
+    let gettext_start = config.find("[gettext]").ok_or_else(not_found)?;
+    let key_start = gettext_start
+        + config[gettext_start..]
+            .find("target_languages")
+            .ok_or_else(not_found)?;
+    let array_start = key_start + config[key_start..].find('[').ok_or_else(not_found)?;
+    let array_end = array_start + config[array_start..].find(']').ok_or_else(not_found)?;
+
+    let items = config[array_start + 1..array_end].trim_end();
+    let insert = if items.trim().is_empty() {
+        format!("\"{language}\"")
+    } else if items.ends_with(',') {
+        format!(" \"{language}\"")
+    } else {
+        format!(", \"{language}\"")
+    };
+    let insert_at = array_start + 1 + items.len();
+
+    let mut patched = config;
+    patched.insert_str(insert_at, &insert);
+    write(config_file_path, patched)
+        .map_err(|err| PathError::cannot_create_file(config_file_path, err))?;
+    Ok(())
+}
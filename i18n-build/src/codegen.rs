@@ -0,0 +1,382 @@
+//! Generating a Rust module with a function for each message of the
+//! fluent files of a crate, used by the `cargo i18n codegen` command.
+//! This is an alternative to the `fl!()` macro which checks the
+//! message ids and arguments when the module is generated instead of
+//! at each call site, e.g. `messages::greeting("Alice")` instead of
+//! `fl!(loader, "greeting", name = "Alice")`.
+//!
+//! The functions obtain the messages using the global loader for the
+//! crate's domain, see `i18n_embed::fluent::set_global_loader()`. The
+//! module also has constants for the ids of the messages and of their
+//! attributes, which can be used with any loader.
+
+use crate::error::PathError;
+use crate::fluent_impl::{parse_fluent_file, FluentLayout};
+use crate::lint::pattern_variables;
+
+use anyhow::{anyhow, Result};
+use fluent_syntax::ast::{Entry, Expression, InlineExpression, Message, Pattern, PatternElement};
+use i18n_config::Crate;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    fs,
+    path::Path,
+};
+use tr::tr;
+
+/// The default path of the generated module, relative to the crate.
+pub const DEFAULT_OUTPUT: &str = "src/messages.rs";
+
+/// The keywords which can't be used as identifiers, except as raw
+/// identifiers.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// The keywords which can't be used as raw identifiers.
+const RESERVED: &[&str] = &["crate", "self", "super", "_"];
+
+/// A message or attribute for which a function is generated.
+struct Item {
+    /// The id of the message, including its namespace.
+    message_id: String,
+    attribute_id: Option<String>,
+    /// The text of the message in the fallback language.
+    text: String,
+    /// The names of the variables of the message.
+    variables: Vec<String>,
+}
+
+/// Generate the source code of a Rust module with a function for each
+/// message (and attribute) in the fluent files of the fallback
+/// language of the crate `crt`. A message `greeting = Hello { $name
+/// }!` becomes:
+///
+/// ```ignore
+/// pub fn greeting<'a>(name: impl Into<FluentValue<'a>>) -> String
+/// ```
+///
+/// The ids are converted into snake case (e.g. `hello-world` becomes
+/// `hello_world()`), the ids of namespaced messages and attributes
+/// are joined to the id of the namespace or message with a `_` (e.g.
+/// `settings.title` becomes `settings_title()`, and the `tooltip`
+/// attribute of `button` becomes `button_tooltip()`). The parameters
+/// for the variables of a message are in alphabetical order, so that
+/// reordering the text of the message does not change them. An error
+/// is returned if two messages have the same function name.
+pub fn generate(crt: &Crate) -> Result<String> {
+    let config = crt.config_or_err()?;
+    let fluent_config = config.fluent.as_ref().ok_or_else(|| {
+        anyhow!(tr!(
+            // {0} is the name of the crate.
+            "The crate \"{0}\" does not use the fluent localization system.",
+            crt.name
+        ))
+    })?;
+    let layout = FluentLayout::new(crt, fluent_config)?;
+    let fallback_language = &config.fallback_language;
+
+    let mut items = Vec::new();
+    for file_path in layout.file_paths(fallback_language)? {
+        let path = layout.path(fallback_language, &file_path);
+        let resource = match parse_fluent_file(&path)? {
+            Some(resource) => resource,
+            None => continue,
+        };
+        // Messages in namespace files are referenced as
+        // `{namespace}.{id}`, see `FluentLanguageLoader`.
+        let namespace = file_path
+            .strip_prefix(&layout.domain)
+            .ok()
+            .map(|namespace| {
+                namespace
+                    .with_extension("")
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(".")
+            });
+        for entry in &resource.body {
+            if let Entry::Message(message) = entry {
+                items.extend(message_items(namespace.as_deref(), message));
+            }
+        }
+    }
+
+    let mut functions: BTreeMap<String, &Item> = BTreeMap::new();
+    for item in &items {
+        let name = function_name(item);
+        if let Some(existing) = functions.insert(name.clone(), item) {
+            return Err(anyhow!(tr!(
+                // {0} and {1} are the ids of messages (or attributes), {2} is the name of the function generated for them.
+                "The messages \"{0}\" and \"{1}\" would both generate the function \"{2}\".",
+                item_id(existing),
+                item_id(item),
+                name
+            )));
+        }
+    }
+
+    let mut source = String::new();
+    writeln!(
+        source,
+        "// This file is generated by `cargo i18n codegen` from the fluent files\n\
+        // of the \"{fallback_language}\" language, do not edit it.\n\
+        \n\
+        #![allow(dead_code)]\n\
+        #![cfg_attr(rustfmt, rustfmt::skip)]\n\
+        \n\
+        use i18n_embed::fluent::{{global_loader, FluentValue}};\n\
+        \n\
+        /// The domain of the messages.\n\
+        pub const DOMAIN: &str = {domain:?};",
+        domain = layout.domain,
+    )?;
+
+    writeln!(source, "\n/// The ids of the messages.\npub mod ids {{")?;
+    let mut message_ids: Vec<&str> = items.iter().map(|item| item.message_id.as_str()).collect();
+    message_ids.dedup();
+    for message_id in message_ids {
+        writeln!(
+            source,
+            "    /// `{0}`\n    pub const {1}: &str = {0:?};",
+            message_id,
+            identifier(message_id).to_uppercase()
+        )?;
+    }
+    writeln!(source, "}}")?;
+
+    let mut attributes: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for item in &items {
+        if let Some(attribute_id) = &item.attribute_id {
+            attributes
+                .entry(&item.message_id)
+                .or_default()
+                .push(attribute_id);
+        }
+    }
+    writeln!(
+        source,
+        "\n/// The ids of the attributes of each message.\npub mod attrs {{"
+    )?;
+    for (message_id, attribute_ids) in &attributes {
+        writeln!(
+            source,
+            "    /// The attributes of `{0}`.\n    pub mod {1} {{",
+            message_id,
+            function_identifier(&identifier(message_id))
+        )?;
+        for attribute_id in attribute_ids {
+            writeln!(
+                source,
+                "        /// `{0}.{1}`\n        pub const {2}: &str = {1:?};",
+                message_id,
+                attribute_id,
+                identifier(attribute_id).to_uppercase()
+            )?;
+        }
+        writeln!(source, "    }}")?;
+    }
+    writeln!(source, "}}")?;
+
+    for item in &items {
+        write_function(&mut source, &function_name(item), item)?;
+    }
+    Ok(source)
+}
+
+/// Generate the module for the crate `crt` (see [generate()]) and
+/// write it to the `output` path, unless it is up to date. Returns
+/// whether the file was written.
+pub fn write(crt: &Crate, output: &Path) -> Result<bool> {
+    let source = generate(crt)?;
+    if fs::read_to_string(output).is_ok_and(|existing| existing == source) {
+        return Ok(false);
+    }
+    if let Some(parent) = output.parent() {
+        crate::util::create_dir_all_if_not_exists(parent)?;
+    }
+    fs::write(output, source).map_err(|err| PathError::cannot_create_file(output, err))?;
+    Ok(true)
+}
+
+/// The items of the `message` (in the `namespace`): the message
+/// itself if it has a value, and each of its attributes.
+fn message_items(namespace: Option<&str>, message: &Message<String>) -> Vec<Item> {
+    let message_id = match namespace {
+        Some(namespace) => format!("{namespace}.{}", message.id.name),
+        None => message.id.name.clone(),
+    };
+    let item = |attribute_id: Option<String>, pattern: &Pattern<String>| Item {
+        message_id: message_id.clone(),
+        attribute_id,
+        text: pattern_source(pattern),
+        variables: pattern_variables(pattern).into_iter().collect(),
+    };
+    message
+        .value
+        .iter()
+        .map(|value| item(None, value))
+        .chain(
+            message
+                .attributes
+                .iter()
+                .map(|attribute| item(Some(attribute.id.name.clone()), &attribute.value)),
+        )
+        .collect()
+}
+
+/// The id of the `item`, as it is shown in error messages.
+fn item_id(item: &Item) -> String {
+    match &item.attribute_id {
+        Some(attribute_id) => format!("{0}.{1}", item.message_id, attribute_id),
+        None => item.message_id.clone(),
+    }
+}
+
+/// The name of the function generated for the `item`.
+fn function_name(item: &Item) -> String {
+    function_identifier(&identifier(&item_id(item)))
+}
+
+/// Convert the fluent `id` into a snake case identifier.
+fn identifier(id: &str) -> String {
+    id.chars()
+        .map(|c| match c {
+            '-' | '.' => '_',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+/// The `identifier` with a `r#` prefix if it is a keyword, or with a
+/// `_` suffix if it can't be a raw identifier.
+fn function_identifier(identifier: &str) -> String {
+    if RESERVED.contains(&identifier) {
+        format!("{identifier}_")
+    } else if KEYWORDS.contains(&identifier) {
+        format!("r#{identifier}")
+    } else {
+        identifier.to_string()
+    }
+}
+
+/// Write the function for the `item` to the `source`.
+fn write_function(source: &mut String, name: &str, item: &Item) -> Result<()> {
+    writeln!(source, "\n/// `{0}`\n///\n/// ```text", item_id(item))?;
+    for line in item.text.lines() {
+        writeln!(source, "/// {line}")?;
+    }
+    writeln!(source, "/// ```")?;
+
+    let parameters: Vec<(String, &str)> = item
+        .variables
+        .iter()
+        .map(|variable| {
+            (
+                function_identifier(&identifier(variable)),
+                variable.as_str(),
+            )
+        })
+        .collect();
+    let mut unique = HashMap::new();
+    for (parameter, variable) in &parameters {
+        if let Some(other) = unique.insert(parameter, variable) {
+            return Err(anyhow!(tr!(
+                // {0} and {1} are the names of variables, {2} is the id of the message.
+                "The variables \"{0}\" and \"{1}\" of the message \"{2}\" have the same name in Rust.",
+                other,
+                variable,
+                item_id(item)
+            )));
+        }
+    }
+
+    let attribute = item
+        .attribute_id
+        .as_ref()
+        .map(|attribute_id| format!(", {attribute_id:?}"))
+        .unwrap_or_default();
+    let get = if item.attribute_id.is_some() {
+        "get_attr"
+    } else {
+        "get"
+    };
+    if parameters.is_empty() {
+        writeln!(
+            source,
+            "pub fn {name}() -> String {{\n    \
+                global_loader(DOMAIN).{get}({0:?}{attribute})\n\
+            }}",
+            item.message_id
+        )?;
+        return Ok(());
+    }
+
+    let signature = parameters
+        .iter()
+        .map(|(parameter, _)| format!("{parameter}: impl Into<FluentValue<'a>>"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    writeln!(
+        source,
+        "pub fn {name}<'a>({signature}) -> String {{\n    \
+            let mut args = std::collections::HashMap::new();"
+    )?;
+    for (parameter, variable) in &parameters {
+        writeln!(source, "    args.insert({variable:?}, {parameter}.into());")?;
+    }
+    writeln!(
+        source,
+        "    global_loader(DOMAIN).{get}_args_concrete({0:?}{attribute}, args)\n}}",
+        item.message_id
+    )?;
+    Ok(())
+}
+
+/// The source of the `pattern`, with its placeables simplified.
+fn pattern_source(pattern: &Pattern<String>) -> String {
+    let mut source = String::new();
+    for element in &pattern.elements {
+        match element {
+            PatternElement::TextElement { value } => source.push_str(value),
+            PatternElement::Placeable { expression } => {
+                source.push_str("{ ");
+                source.push_str(&expression_source(expression));
+                source.push_str(" }");
+            }
+        }
+    }
+    source
+}
+
+fn expression_source(expression: &Expression<String>) -> String {
+    match expression {
+        Expression::Inline(expression) => inline_expression_source(expression),
+        Expression::Select { selector, .. } => {
+            format!("{0} -> …", inline_expression_source(selector))
+        }
+    }
+}
+
+fn inline_expression_source(expression: &InlineExpression<String>) -> String {
+    match expression {
+        InlineExpression::StringLiteral { value } => format!("\"{value}\""),
+        InlineExpression::NumberLiteral { value } => value.clone(),
+        InlineExpression::FunctionReference { id, .. } => format!("{0}(…)", id.name),
+        InlineExpression::MessageReference { id, attribute } => match attribute {
+            Some(attribute) => format!("{0}.{1}", id.name, attribute.name),
+            None => id.name.clone(),
+        },
+        InlineExpression::TermReference { id, .. } => format!("-{0}", id.name),
+        InlineExpression::VariableReference { id } => format!("${0}", id.name),
+        InlineExpression::Placeable { expression } => {
+            format!("{{ {0} }}", expression_source(expression))
+        }
+    }
+}
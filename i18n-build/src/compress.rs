@@ -0,0 +1,99 @@
+//! Compressing the localization assets within a `build.rs` build
+//! script, so that they can be embedded in the binary with the
+//! `CompressedRustEmbedAssets` of the
+//! [i18n-embed](https://crates.io/crates/i18n-embed) crate (with its
+//! `compressed-assets` feature).
+//!
+//! ⚠️ *This module requires the following crate features to be activated: `compress-assets`.*
+
+use crate::error::PathError;
+use crate::util;
+
+use anyhow::{anyhow, Context, Result};
+use flate2::{write::GzEncoder, Compression};
+use log::debug;
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+use tr::tr;
+use walkdir::WalkDir;
+
+/// The extension which is appended to the path of the compressed
+/// files, which is expected by `CompressedRustEmbedAssets`.
+pub const COMPRESSED_EXTENSION: &str = "gz";
+
+/// Compress each of the files within the `src_dir` (e.g. the `i18n`
+/// directory of the crate) with gzip, into the same path within the
+/// `out_dir` with the [COMPRESSED_EXTENSION] appended (e.g.
+/// `de-DE/my_crate.ftl` becomes `de-DE/my_crate.ftl.gz`). Returns the
+/// paths of the compressed files.
+///
+/// The compressed files are reproducible, and they are only written
+/// when their contents have changed, so that `Cargo` does not need to
+/// rebuild the crate which embeds them.
+///
+/// ```no_run
+/// use std::path::Path;
+///
+/// i18n_build::compress::compress_assets(Path::new("i18n"), Path::new("i18n-compressed")).unwrap();
+/// ```
+pub fn compress_assets(src_dir: &Path, out_dir: &Path) -> Result<Vec<PathBuf>> {
+    util::check_path_exists(src_dir)?;
+
+    let mut compressed_paths = Vec::new();
+    for entry in WalkDir::new(src_dir).sort_by_file_name() {
+        let entry = entry.map_err(|err| {
+            anyhow!(tr!(
+                // {0} is the path to the directory, {1} is the error.
+                "Error walking the directory \"{0}\": {1}",
+                src_dir.to_string_lossy(),
+                err
+            ))
+        })?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative_path = entry.path().strip_prefix(src_dir)?;
+        let mut file_name = relative_path.as_os_str().to_owned();
+        file_name.push(".");
+        file_name.push(COMPRESSED_EXTENSION);
+        let compressed_path = out_dir.join(file_name);
+
+        let contents = fs::read(entry.path()).with_context(|| {
+            tr!(
+                // {0} is the path to the file.
+                "Unable to read \"{0}\".",
+                entry.path().to_string_lossy()
+            )
+        })?;
+        let compressed = compress(&contents)?;
+
+        if fs::read(&compressed_path).is_ok_and(|existing| existing == compressed) {
+            debug!("\"{0}\" is up to date", compressed_path.to_string_lossy());
+        } else {
+            if let Some(parent) = compressed_path.parent() {
+                util::create_dir_all_if_not_exists(parent)?;
+            }
+            debug!(
+                "Compressing \"{0}\" into \"{1}\"",
+                entry.path().to_string_lossy(),
+                compressed_path.to_string_lossy()
+            );
+            fs::write(&compressed_path, compressed)
+                .map_err(|e| PathError::cannot_create_file(&compressed_path, e))?;
+        }
+        compressed_paths.push(compressed_path);
+    }
+    Ok(compressed_paths)
+}
+
+/// Compress the `contents` with gzip at the best compression level.
+/// The gzip header has no modification time, so that the output is
+/// reproducible.
+fn compress(contents: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(contents)?;
+    Ok(encoder.finish()?)
+}
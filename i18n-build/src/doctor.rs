@@ -0,0 +1,143 @@
+//! Checking that the external tools used by the gettext system are
+//! installed, used by the `cargo i18n doctor` command. The
+//! executables which are checked can be configured in the
+//! `[gettext.tools]` table of the crate's i18n config file, see
+//! [GettextTools].
+
+use i18n_config::{Crate, GettextTools};
+use log::debug;
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use tr::tr;
+
+/// Whether a gettext tool is installed, and its version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolStatus {
+    /// The name of the tool, one of [GettextTools::NAMES].
+    pub name: &'static str,
+    /// The executable which is run for the tool, see
+    /// [GettextTools::program()].
+    pub program: PathBuf,
+    /// The first line of the output of the tool's `--version` option,
+    /// or `None` if the tool could not be run.
+    pub version: Option<String>,
+    /// Whether the tool is required by the crate, because it uses the
+    /// gettext system.
+    pub required: bool,
+    /// Whether a built-in replacement is used instead of the tool
+    /// (with the `builtin-gettext-tools` feature), in which case it
+    /// is not required.
+    pub builtin: bool,
+}
+
+impl ToolStatus {
+    /// Whether the tool is required, but could not be run.
+    pub fn is_missing(&self) -> bool {
+        self.required && self.version.is_none()
+    }
+}
+
+impl Display for ToolStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let program = self.program.to_string_lossy();
+        if self.builtin {
+            return write!(f, "✓ {0}: {1}", self.name, tr!("built-in"));
+        }
+        match (&self.version, self.required) {
+            (Some(version), _) => write!(f, "✓ {0} ({1}): {2}", self.name, program, version),
+            (None, true) => write!(
+                f,
+                "✗ {0} ({1}): {2}",
+                self.name,
+                program,
+                tr!("not found, or unable to run.")
+            ),
+            (None, false) => write!(
+                f,
+                "- {0} ({1}): {2}",
+                self.name,
+                program,
+                tr!("not found (not required).")
+            ),
+        }
+    }
+}
+
+/// Check each of the gettext tools ([GettextTools::NAMES]) which
+/// would be run for the crate `crt`, by running them with the
+/// `--version` option. The tools are required if the crate has a
+/// gettext config (`xtr` only if string extraction is enabled). When
+/// there is no crate, the default executables are checked.
+pub fn check_tools(crt: Option<&Crate>) -> Vec<ToolStatus> {
+    let gettext_config = crt
+        .and_then(|crt| crt.i18n_config.as_ref())
+        .and_then(|config| config.gettext.as_ref());
+    let default_tools = GettextTools::default();
+    let tools = gettext_config
+        .map(|gettext_config| &gettext_config.tools)
+        .unwrap_or(&default_tools);
+
+    GettextTools::NAMES
+        .iter()
+        .map(|&name| {
+            let program = tools.program(name);
+            let builtin = is_builtin(tools, name);
+            let required = !builtin
+                && gettext_config.is_some_and(|gettext_config| {
+                    name != "xtr" || gettext_config.xtr.unwrap_or(true)
+                });
+            ToolStatus {
+                name,
+                version: tool_version(&program),
+                program,
+                required,
+                builtin,
+            }
+        })
+        .collect()
+}
+
+/// Whether the built-in replacement for the tool with the `name` is
+/// used, because it is available and no executable is configured for
+/// the tool in the `tools`.
+fn is_builtin(tools: &GettextTools, name: &str) -> bool {
+    cfg!(feature = "builtin-gettext-tools")
+        && ["msgmerge", "msgfmt"].contains(&name)
+        && tools.configured(name).is_none()
+}
+
+/// The first non-empty line of the output of running the `program`
+/// with the `--version` option, or `None` if it could not be run.
+fn tool_version(program: &Path) -> Option<String> {
+    let output = match Command::new(program).arg("--version").output() {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            debug!(
+                "\"{0} --version\" exited with {1}",
+                program.to_string_lossy(),
+                output.status
+            );
+            return None;
+        }
+        Err(err) => {
+            debug!(
+                "Unable to run \"{0} --version\": {1}",
+                program.to_string_lossy(),
+                err
+            );
+            return None;
+        }
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Some(
+        stdout
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+            .to_string(),
+    )
+}
@@ -1,8 +1,12 @@
 //! Error types for use with the `i18n_build` library.
 
+use crate::system::Severity;
+
+use serde::Serialize;
 use std::fmt::Display;
 use std::io;
 use std::path::PathBuf;
+use std::process::ExitStatus;
 use thiserror::Error;
 use tr::tr;
 
@@ -194,3 +198,149 @@ impl Display for PathError {
         write!(f, "{message}")
     }
 }
+
+/// An error for when an external command (such as `msgfmt`) reported
+/// that it was unsuccessful, see
+/// [run_command_and_check_success()](crate::util::run_command_and_check_success()).
+#[derive(Error, Debug)]
+pub struct CommandError {
+    /// The name of the command.
+    pub command_name: String,
+    /// The exit status of the command.
+    pub status: ExitStatus,
+    /// The standard output of the command.
+    pub stdout: String,
+    /// The standard error output of the command.
+    pub stderr: String,
+    /// The problems with specific files reported in the output of the
+    /// command.
+    pub diagnostics: Vec<ToolDiagnostic>,
+}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&tr!(
+            // {0} is the name of the command, {1} is its exit status.
+            "The \"{0}\" command reported that it was unsuccessful ({1}).",
+            self.command_name,
+            self.status
+        ))?;
+        if self.diagnostics.is_empty() {
+            let output = self.stderr.trim();
+            if !output.is_empty() {
+                write!(f, "\n{output}")?;
+            }
+        } else {
+            for diagnostic in &self.diagnostics {
+                write!(f, "\n{diagnostic}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A problem with a specific file reported in the output of an
+/// external command, in the `file:line: message` or
+/// `file:line:column: message` format used by the gettext tools.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ToolDiagnostic {
+    /// The name of the command which reported the problem.
+    pub tool: String,
+    /// The file with the problem.
+    pub path: PathBuf,
+    /// The line of the file with the problem.
+    pub line: usize,
+    /// The column of the line with the problem, if it was reported.
+    pub column: Option<usize>,
+    /// [Severity::Warning] if the message starts with `warning:`.
+    pub severity: Severity,
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl ToolDiagnostic {
+    /// Parse the diagnostics in the `output` of the command named
+    /// `tool`, skipping the lines which don't refer to a file. The
+    /// lines may be prefixed with the name of the `tool`.
+    ///
+    /// ```
+    /// use i18n_build::{error::ToolDiagnostic, system::Severity};
+    /// use std::path::PathBuf;
+    ///
+    /// let diagnostics = ToolDiagnostic::parse(
+    ///     "msgfmt",
+    ///     "msgfmt: po/fr/app.po:12: missing 'msgstr' section\n\
+    ///     po/fr/app.po:3:7: warning: header field 'Language' missing\n\
+    ///     msgfmt: found 1 fatal error\n",
+    /// );
+    /// assert_eq!(2, diagnostics.len());
+    /// assert_eq!(PathBuf::from("po/fr/app.po"), diagnostics[0].path);
+    /// assert_eq!((12, None), (diagnostics[0].line, diagnostics[0].column));
+    /// assert_eq!("missing 'msgstr' section", diagnostics[0].message);
+    /// assert_eq!((3, Some(7)), (diagnostics[1].line, diagnostics[1].column));
+    /// assert_eq!(Severity::Warning, diagnostics[1].severity);
+    /// ```
+    pub fn parse(tool: &str, output: &str) -> Vec<ToolDiagnostic> {
+        let tool_prefix = format!("{tool}: ");
+        output
+            .lines()
+            .filter_map(|line| {
+                let line = line.strip_prefix(tool_prefix.as_str()).unwrap_or(line);
+                Self::parse_line(tool, line)
+            })
+            .collect()
+    }
+
+    fn parse_line(tool: &str, line: &str) -> Option<ToolDiagnostic> {
+        // The path is followed by the first `:{number}:`, so that
+        // paths containing `:` (such as `C:\...` on Windows) are
+        // supported.
+        let (path, rest) = line.match_indices(':').find_map(|(index, _)| {
+            let rest = &line[index + 1..];
+            let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+            (index > 0 && digits > 0 && rest[digits..].starts_with(':'))
+                .then(|| (&line[..index], rest))
+        })?;
+        let (line_number, rest) = split_number(rest)?;
+        let (column, rest) = match split_number(rest) {
+            Some((column, rest)) => (Some(column), rest),
+            None => (None, rest),
+        };
+        let message = rest.trim();
+        let (severity, message) = match message.strip_prefix("warning:") {
+            Some(message) => (Severity::Warning, message.trim()),
+            None => (Severity::Error, message),
+        };
+        Some(ToolDiagnostic {
+            tool: tool.to_string(),
+            path: PathBuf::from(path),
+            line: line_number,
+            column,
+            severity,
+            message: message.to_string(),
+        })
+    }
+}
+
+/// Split a number followed by a `:` from the start of the `text`.
+fn split_number(text: &str) -> Option<(usize, &str)> {
+    let (number, rest) = text.split_once(':')?;
+    Some((number.parse().ok()?, rest))
+}
+
+impl Display for ToolDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{0}:{1}", self.path.to_string_lossy(), self.line)?;
+        if let Some(column) = self.column {
+            write!(f, ":{column}")?;
+        }
+        f.write_str(": ")?;
+        if self.severity == Severity::Warning {
+            f.write_str(&tr!(
+                // The prefix of the problems reported by the gettext tools which are only warnings.
+                "warning: "
+            ))?;
+        }
+        f.write_str(&self.message)
+    }
+}
@@ -0,0 +1,429 @@
+//! Helpers for reading the fluent resources of a crate.
+
+use crate::{
+    error::PathError,
+    lint::{entry_patterns, pattern_terms},
+    util,
+};
+
+use anyhow::{Context, Result};
+use fluent_syntax::{
+    ast::{Comment, Entry, Resource},
+    parser, serializer,
+};
+use i18n_config::{Crate, FluentConfig, ObsoletePolicy};
+use i18n_embed::PathTemplate;
+use log::{info, warn};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fs::{read_dir, read_to_string, write},
+    path::{Path, PathBuf},
+};
+use tr::tr;
+use unic_langid::LanguageIdentifier;
+use walkdir::WalkDir;
+
+/// The name of the file containing the terms which are shared by all
+/// the fluent files of a language, the same as
+/// `i18n_embed::fluent::TERMS_FILE`.
+const TERMS_FILE: &str = "_terms.ftl";
+
+/// The layout of the fluent files of a crate within its assets
+/// directory, given by the `path_template` in its [FluentConfig].
+///
+/// The fluent files of a language are identified by their path
+/// relative to the language's directory in the default
+/// `{language}/{domain}.ftl` layout: `{domain}.ftl` for the main
+/// file, and `{domain}/{namespace}.ftl` for the namespace files.
+pub(crate) struct FluentLayout {
+    pub(crate) assets_dir: PathBuf,
+    pub(crate) domain: String,
+    path_template: PathTemplate,
+}
+
+impl FluentLayout {
+    pub(crate) fn new(crt: &Crate, fluent_config: &FluentConfig) -> Result<Self> {
+        let domain = fluent_config
+            .domain
+            .clone()
+            .unwrap_or_else(|| crt.module_name());
+        let path_template = match &fluent_config.path_template {
+            Some(path_template) => PathTemplate::new(path_template.clone())?,
+            None => PathTemplate::with_file_name(&format!("{domain}.ftl")),
+        };
+        Ok(Self {
+            assets_dir: crt.path.join(&fluent_config.assets_dir),
+            domain,
+            path_template,
+        })
+    }
+
+    /// The path of the fluent `file` (see [FluentLayout]) for the
+    /// `language`.
+    pub(crate) fn path(&self, language: &LanguageIdentifier, file: &Path) -> PathBuf {
+        let namespace_dir = self.path_template.directory(language, &self.domain);
+        match (file.strip_prefix(&self.domain), namespace_dir) {
+            (Ok(namespace_file), Some(namespace_dir)) => {
+                self.assets_dir.join(namespace_dir).join(namespace_file)
+            }
+            _ => self
+                .assets_dir
+                .join(self.path_template.path(language, &self.domain)),
+        }
+    }
+
+    /// The path of the file containing the shared terms of the
+    /// `language` (see [TERMS_FILE]), or `None` if the layout has no
+    /// directory for each language.
+    pub(crate) fn terms_path(&self, language: &LanguageIdentifier) -> Option<PathBuf> {
+        self.path_template
+            .sibling_path(language, &self.domain, TERMS_FILE)
+            .map(|path| self.assets_dir.join(path))
+    }
+
+    /// The fluent files (see [FluentLayout]) of the `language`: the
+    /// main `{domain}.ftl` file, and the files for each namespace.
+    pub(crate) fn file_paths(&self, language: &LanguageIdentifier) -> Result<Vec<PathBuf>> {
+        let mut paths = vec![PathBuf::from(format!("{0}.ftl", self.domain))];
+        let namespace_dir = match self.path_template.directory(language, &self.domain) {
+            Some(namespace_dir) => self.assets_dir.join(namespace_dir),
+            None => return Ok(paths),
+        };
+        if namespace_dir.is_dir() {
+            for entry in WalkDir::new(&namespace_dir).sort_by_file_name() {
+                let entry = entry?;
+                if entry.file_type().is_file()
+                    && entry.path().extension().is_some_and(|ext| ext == "ftl")
+                {
+                    paths.push(
+                        Path::new(&self.domain).join(
+                            entry
+                                .path()
+                                .strip_prefix(&namespace_dir)
+                                .expect("expected entry to be inside the namespace directory"),
+                        ),
+                    );
+                }
+            }
+        }
+        Ok(paths)
+    }
+
+    /// The languages which have fluent files in the assets directory.
+    pub(crate) fn languages(&self) -> Result<Vec<LanguageIdentifier>> {
+        let mut languages: Vec<LanguageIdentifier> = Vec::new();
+        for entry in WalkDir::new(&self.assets_dir) {
+            let entry = entry.with_context(|| {
+                tr!(
+                    // {0} is the path to the fluent assets directory.
+                    "Unable to read \"{0}\".",
+                    self.assets_dir.to_string_lossy()
+                )
+            })?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let path = entry
+                .path()
+                .strip_prefix(&self.assets_dir)
+                .expect("expected entry to be inside the assets directory")
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            if let Some(language) = self.path_template.language(&path, &self.domain) {
+                if let Ok(language) = language.parse() {
+                    if !languages.contains(&language) {
+                        languages.push(language);
+                    }
+                }
+            }
+        }
+        languages.sort_by_key(ToString::to_string);
+        Ok(languages)
+    }
+}
+
+/// The languages which have a directory in the `assets_dir`.
+pub(crate) fn fluent_languages(assets_dir: &Path) -> Result<Vec<LanguageIdentifier>> {
+    let mut languages = Vec::new();
+    for entry in read_dir(assets_dir).with_context(|| {
+        tr!(
+            // {0} is the path to the fluent assets directory.
+            "Unable to read \"{0}\".",
+            assets_dir.to_string_lossy()
+        )
+    })? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            if let Ok(language) = entry.file_name().to_string_lossy().parse() {
+                languages.push(language);
+            }
+        }
+    }
+    languages.sort_by_key(ToString::to_string);
+    Ok(languages)
+}
+
+/// Parse the fluent file at `path`, or return `None` if it does not
+/// exist. Syntax errors are logged, and the valid entries returned.
+pub(crate) fn parse_fluent_file(path: &Path) -> Result<Option<Resource<String>>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let source = read_to_string(path).with_context(|| {
+        tr!(
+            // {0} is the path to the fluent file.
+            "Unable to read \"{0}\".",
+            path.to_string_lossy()
+        )
+    })?;
+    Ok(Some(parser::parse(source).unwrap_or_else(
+        |(resource, errors)| {
+            warn!(
+                "Ignoring {0} syntax errors in \"{1}\".",
+                errors.len(),
+                path.to_string_lossy()
+            );
+            resource
+        },
+    )))
+}
+
+/// The ids of the messages in the `resource`.
+pub(crate) fn message_ids(resource: &Resource<String>) -> HashSet<String> {
+    resource
+        .body
+        .iter()
+        .filter_map(|entry| match entry {
+            Entry::Message(message) => Some(message.id.name.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The ids of the terms in the `resource`, with their leading `-`.
+fn term_ids(resource: &Resource<String>) -> HashSet<String> {
+    resource
+        .body
+        .iter()
+        .filter_map(|entry| match entry {
+            Entry::Term(term) => Some(format!("-{}", term.id.name)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The ids of the terms referenced by the messages and terms in the
+/// fluent files of the `language` (including its [TERMS_FILE]) which
+/// are neither defined in the same file, nor in the terms file, for
+/// each file with undefined terms.
+pub(crate) fn undefined_terms(
+    layout: &FluentLayout,
+    language: &LanguageIdentifier,
+) -> Result<Vec<(PathBuf, Vec<String>)>> {
+    let terms_path = layout.terms_path(language);
+    let shared_terms = match &terms_path {
+        Some(terms_path) => parse_fluent_file(terms_path)?
+            .map(|resource| term_ids(&resource))
+            .unwrap_or_default(),
+        None => HashSet::new(),
+    };
+
+    let mut undefined = Vec::new();
+    let paths = layout
+        .file_paths(language)?
+        .into_iter()
+        .map(|file_path| layout.path(language, &file_path))
+        .chain(terms_path);
+    for path in paths {
+        let resource = match parse_fluent_file(&path)? {
+            Some(resource) => resource,
+            None => continue,
+        };
+        let defined_terms = term_ids(&resource);
+        let undefined_ids: BTreeSet<String> = entry_patterns(&resource.body)
+            .into_iter()
+            .flat_map(|(_, pattern)| pattern_terms(pattern))
+            .filter(|id| !defined_terms.contains(id) && !shared_terms.contains(id))
+            .collect();
+        if !undefined_ids.is_empty() {
+            undefined.push((path, undefined_ids.into_iter().collect()));
+        }
+    }
+    Ok(undefined)
+}
+
+/// Remove the messages of the languages other than the
+/// `fallback_language` which are not in the corresponding file of the
+/// fallback language, moving them to the attic files (see
+/// [util::attic_file_path()]) if the `obsolete_policy` is
+/// [ObsoletePolicy::Attic]. Nothing is removed if it is
+/// [ObsoletePolicy::Keep], or from files with syntax errors. Returns
+/// the paths of the files which were changed.
+pub(crate) fn remove_obsolete_messages(
+    layout: &FluentLayout,
+    fallback_language: &LanguageIdentifier,
+    obsolete_policy: ObsoletePolicy,
+) -> Result<Vec<PathBuf>> {
+    let mut changed = Vec::new();
+    if obsolete_policy == ObsoletePolicy::Keep {
+        return Ok(changed);
+    }
+    for language in layout
+        .languages()?
+        .into_iter()
+        .filter(|language| language != fallback_language)
+    {
+        for file_path in layout.file_paths(&language)? {
+            let fallback_ids = match parse_fluent_file(&layout.path(fallback_language, &file_path))?
+            {
+                Some(fallback_resource) => message_ids(&fallback_resource),
+                None => continue,
+            };
+            let path = layout.path(&language, &file_path);
+            if !path.exists() {
+                continue;
+            }
+            let source = read_to_string(&path).with_context(|| {
+                tr!(
+                    // {0} is the path to the fluent file.
+                    "Unable to read \"{0}\".",
+                    path.to_string_lossy()
+                )
+            })?;
+            let resource = match parser::parse(source) {
+                Ok(resource) => resource,
+                Err(_) => {
+                    warn!(
+                        "Not removing the obsolete messages from \"{0}\", which has syntax errors.",
+                        path.to_string_lossy()
+                    );
+                    continue;
+                }
+            };
+            let (obsolete, current): (Vec<_>, Vec<_>) =
+                resource.body.into_iter().partition(|entry| {
+                    matches!(entry, Entry::Message(message) if !fallback_ids.contains(&message.id.name))
+                });
+            if obsolete.is_empty() {
+                continue;
+            }
+            info!(
+                "Removing {0} obsolete messages from \"{1}\"",
+                obsolete.len(),
+                path.to_string_lossy()
+            );
+            if obsolete_policy == ObsoletePolicy::Attic {
+                util::append_to_attic(&path, &serializer::serialize(&Resource { body: obsolete }))?;
+            }
+            write(&path, serializer::serialize(&Resource { body: current }))
+                .map_err(|err| PathError::cannot_create_file(&path, err))?;
+            changed.push(path);
+        }
+    }
+    Ok(changed)
+}
+
+/// Replace the comments of the messages in the fluent files of the
+/// `fallback_language` with their `comments` from the source code
+/// (see [message_comments()](crate::usage::message_comments())).
+/// Messages without comments in the source code, and files with
+/// syntax errors, are left unchanged. Returns the paths of the files
+/// which were changed.
+pub(crate) fn update_source_comments(
+    layout: &FluentLayout,
+    fallback_language: &LanguageIdentifier,
+    comments: &BTreeMap<String, Vec<String>>,
+) -> Result<Vec<PathBuf>> {
+    let mut changed = Vec::new();
+    for file_path in layout.file_paths(fallback_language)? {
+        let path = layout.path(fallback_language, &file_path);
+        if !path.exists() {
+            continue;
+        }
+        let source = read_to_string(&path).with_context(|| {
+            tr!(
+                // {0} is the path to the fluent file.
+                "Unable to read \"{0}\".",
+                path.to_string_lossy()
+            )
+        })?;
+        let mut resource = match parser::parse(source) {
+            Ok(resource) => resource,
+            Err(_) => {
+                warn!(
+                    "Not copying the comments from the source code to \"{0}\", which has syntax errors.",
+                    path.to_string_lossy()
+                );
+                continue;
+            }
+        };
+
+        let mut file_changed = false;
+        for entry in &mut resource.body {
+            let message = match entry {
+                Entry::Message(message) => message,
+                _ => continue,
+            };
+            let content: Vec<String> = match comments.get(&message.id.name) {
+                Some(message_comments) => message_comments
+                    .iter()
+                    .flat_map(|comment| comment.lines().map(ToString::to_string))
+                    .collect(),
+                None => continue,
+            };
+            if message.comment.as_ref().map(|comment| &comment.content) != Some(&content) {
+                message.comment = Some(Comment { content });
+                file_changed = true;
+            }
+        }
+        if file_changed {
+            info!(
+                "Copying the comments from the source code to \"{0}\"",
+                path.to_string_lossy()
+            );
+            write(&path, serializer::serialize(&resource))
+                .map_err(|err| PathError::cannot_create_file(&path, err))?;
+            changed.push(path);
+        }
+    }
+    Ok(changed)
+}
+
+/// The ids of the messages of the `fallback_language` which are
+/// missing from the fluent files of the `language`, in the order of
+/// the fallback language's files, for each file of the `language`
+/// with missing messages.
+pub(crate) fn missing_messages(
+    layout: &FluentLayout,
+    fallback_language: &LanguageIdentifier,
+    language: &LanguageIdentifier,
+) -> Result<Vec<(PathBuf, Vec<String>)>> {
+    let mut missing = Vec::new();
+    for file_path in layout.file_paths(fallback_language)? {
+        let fallback_resource =
+            match parse_fluent_file(&layout.path(fallback_language, &file_path))? {
+                Some(resource) => resource,
+                None => continue,
+            };
+        let path = layout.path(language, &file_path);
+        let existing_ids = parse_fluent_file(&path)?
+            .map(|resource| message_ids(&resource))
+            .unwrap_or_default();
+        let missing_ids: Vec<String> = fallback_resource
+            .body
+            .into_iter()
+            .filter_map(|entry| match entry {
+                Entry::Message(message) if !existing_ids.contains(&message.id.name) => {
+                    Some(message.id.name)
+                }
+                _ => None,
+            })
+            .collect();
+        if !missing_ids.is_empty() {
+            missing.push((path, missing_ids));
+        }
+    }
+    Ok(missing)
+}
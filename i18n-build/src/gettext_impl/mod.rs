@@ -2,25 +2,37 @@
 //! `gettext` localization system.
 
 use crate::error::{PathError, PathType};
+use crate::progress::{self, Progress};
+use crate::summary::{RunSummary, Stage};
 use crate::util;
-use i18n_config::{Crate, GettextConfig, I18nConfigError};
+use i18n_config::{Crate, GettextConfig, GettextSortOutput, GettextTools, ObsoletePolicy};
+
+#[cfg(feature = "builtin-gettext-tools")]
+pub mod msgmerge;
+mod pipeline;
+pub(crate) mod po;
+#[cfg(feature = "builtin-gettext-tools")]
+pub mod po2mo;
+pub(crate) mod pot_header;
+
+pub use pipeline::{GettextPipeline, PipelineContext};
 
 use std::ffi::OsStr;
-use std::fs::{create_dir_all, File};
+use std::fs::{self, create_dir_all, File};
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{Context, Result};
 use log::{debug, info};
 use subprocess::Exec;
 use tr::tr;
-use walkdir::WalkDir;
 
 /// Run the `xtr` command (<https://crates.io/crates/xtr/>) in order
 /// to extract the translateable strings from the crate.
 ///
-/// `src_dir` is the directory where the Rust source code is located
-/// relative to the crate path.
+/// `src_dirs` are the directories where the Rust source code is
+/// located (see [GettextConfig::src_dirs()]).
 ///
 /// `pot_dir` is the directory where the output `pot` files will be
 /// stored.
@@ -28,12 +40,16 @@ use walkdir::WalkDir;
 /// `prepend_crate_path` is whether or not to prepend the path of the
 /// crate to directory where the intermediate `pot` files will be
 /// stored within the `pot_dir`.
+///
+/// `jobs` is the maximum number of `xtr` processes to run in
+/// parallel, see [util::default_jobs()].
 pub fn run_xtr(
     crt: &Crate,
     gettext_config: &GettextConfig,
-    src_dir: &Path,
+    src_dirs: &[PathBuf],
     pot_dir: &Path,
     prepend_crate_path: bool,
+    jobs: NonZeroUsize,
 ) -> Result<()> {
     info!(
         "Performing string extraction with `xtr` for crate \"{0}\"",
@@ -41,47 +57,71 @@ pub fn run_xtr(
     );
     let mut rs_files: Vec<Box<Path>> = Vec::new();
 
-    for result in WalkDir::new(src_dir) {
-        match result {
-            Ok(entry) => {
-                let path = entry.path();
-
-                if let Some(extension) = path.extension() {
-                    if extension.to_str() == Some("rs") {
-                        rs_files.push(Box::from(path))
-                    }
-                }
+    // The files are sorted so that the messages of the pot file are in
+    // the same order regardless of the order of the directory entries
+    // in the file system.
+    for src_dir in src_dirs {
+        for path in util::rust_source_files(crt, src_dir, &gettext_config.ignore)? {
+            // The `exclude_dirs` and `include_globs` are relative to
+            // the crate.
+            let crate_relative_path = path.strip_prefix(&crt.path).unwrap_or(&path);
+            if gettext_config.is_extracted(crate_relative_path) {
+                rs_files.push(Box::from(path))
+            } else {
+                debug!(
+                    "Skipping string extraction for \"{0}\"",
+                    path.to_string_lossy()
+                );
             }
-            Err(err) => return Err(anyhow!("error walking directory {}/src: {}", crt.name, err)),
         }
     }
 
-    let mut pot_paths = Vec::new();
+    let combined_pot_file_path = crate_module_pot_file_path(crt, pot_dir);
+
+    // Crates without any source code (e.g. a crate which only
+    // re-exports its dependencies) have nothing to extract.
+    if rs_files.is_empty() {
+        info!(
+            "Skipping string extraction for crate \"{0}\", it has no rust source files",
+            crt.path.to_string_lossy()
+        );
+        util::remove_file_if_exists(&combined_pot_file_path)?;
+        return Ok(());
+    }
 
-    let pot_src_dir = if prepend_crate_path {
-        pot_dir.join(&crt.path).join("src")
+    // The intermediate pot files are stored at the same path
+    // relative to the `pot_crate_dir` as the source files relative
+    // to the crate, e.g. `pot/src/main.pot`.
+    let pot_crate_dir = if prepend_crate_path {
+        pot_dir.join(&crt.path)
     } else {
-        pot_dir.join("src")
+        pot_dir.to_path_buf()
     };
 
     // create pot and pot/tmp if they don't exist
-    util::create_dir_all_if_not_exists(&pot_src_dir)?;
+    util::create_dir_all_if_not_exists(&pot_crate_dir)?;
+
+    let mut extractions: Vec<(Box<Path>, PathBuf)> = Vec::new();
 
     for rs_file_path in rs_files {
         let parent_dir = rs_file_path.parent().context(format!(
             "the rs file {0} is not inside a directory",
             rs_file_path.to_string_lossy()
         ))?;
-        let src_dir_relative = parent_dir.strip_prefix(src_dir).map_err(|_| {
-            PathError::not_inside_dir(parent_dir, format!("crate {0}/src", crt.name), src_dir)
+        let crate_relative = parent_dir.strip_prefix(&crt.path).map_err(|_| {
+            PathError::not_inside_dir(
+                parent_dir,
+                format!("crate {0}", crt.name),
+                crt.path.as_path(),
+            )
         })?;
         let file_stem = rs_file_path.file_stem().context(format!(
             "expected rs file path {0} would have a filename",
             rs_file_path.to_string_lossy()
         ))?;
 
-        let pot_file_path = pot_src_dir
-            .join(src_dir_relative)
+        let pot_file_path = pot_crate_dir
+            .join(crate_relative)
             .join(file_stem)
             .with_extension("pot");
 
@@ -92,57 +132,68 @@ pub fn run_xtr(
             )
         })?)?;
 
-        // ======= Run the `xtr` command to extract translatable strings =======
-        let xtr_command_name = "xtr";
-        let mut xtr = Command::new(xtr_command_name);
+        extractions.push((rs_file_path, pot_file_path));
+    }
+
+    debug!(
+        "Extracting strings from {0} files using up to {1} jobs",
+        extractions.len(),
+        jobs
+    );
+
+    let pot_paths: Vec<PathBuf> =
+        util::run_parallel(jobs, &extractions, |(rs_file_path, pot_file_path)| {
+            // ======= Run the `xtr` command to extract translatable strings =======
+            let xtr_command_name = "xtr";
+            let mut xtr = Command::new(gettext_config.tools.program(xtr_command_name));
 
-        match &gettext_config.copyright_holder {
-            Some(copyright_holder) => {
+            if let Some(copyright_holder) = &gettext_config.copyright_holder {
                 xtr.args(["--copyright-holder", copyright_holder.as_str()]);
             }
-            None => {}
-        }
 
-        match &gettext_config.msgid_bugs_address {
-            Some(msgid_bugs_address) => {
+            if let Some(msgid_bugs_address) = &gettext_config.msgid_bugs_address {
                 xtr.args(["--msgid-bugs-address", msgid_bugs_address.as_str()]);
             }
-            None => {}
-        }
-
-        xtr.args([
-            "--package-name",
-            crt.name.as_str(),
-            "--package-version",
-            crt.version.as_str(),
-            "--default-domain",
-            crt.module_name().as_str(),
-            "--add-location",
-            gettext_config.add_location.to_str(),
-            "-o",
-            pot_file_path.to_str().ok_or_else(|| {
-                PathError::not_valid_utf8(pot_file_path.clone(), "pot", PathType::File)
-            })?,
-            rs_file_path.to_str().ok_or_else(|| {
-                PathError::not_valid_utf8(rs_file_path.clone(), "rs", PathType::File)
-            })?,
-        ]);
 
-        util::run_command_and_check_success(xtr_command_name, xtr)?;
+            for keyword in &gettext_config.xtr_keywords {
+                xtr.args(["--keywords", keyword.as_str()]);
+            }
 
-        pot_paths.push(pot_file_path.to_owned());
-    }
+            if let Some(add_comments) = &gettext_config.add_comments {
+                xtr.args(["--add-comments", add_comments.as_str()]);
+            }
 
-    let mut msgcat_args: Vec<Box<OsStr>> = Vec::new();
+            xtr.args([
+                "--package-name",
+                crt.name.as_str(),
+                "--package-version",
+                crt.version.as_str(),
+                "--default-domain",
+                crt.module_name().as_str(),
+                "--add-location",
+                gettext_config.add_location.to_str(),
+                "-o",
+                pot_file_path.to_str().ok_or_else(|| {
+                    PathError::not_valid_utf8(pot_file_path.clone(), "pot", PathType::File)
+                })?,
+                rs_file_path.to_str().ok_or_else(|| {
+                    PathError::not_valid_utf8(rs_file_path.to_path_buf(), "rs", PathType::File)
+                })?,
+            ]);
 
-    for path in &pot_paths {
-        msgcat_args.push(Box::from(path.as_os_str()));
-    }
+            util::run_command_and_check_success(xtr_command_name, xtr)?;
 
-    let combined_pot_file_path = crate_module_pot_file_path(crt, pot_dir);
+            Ok(pot_file_path.clone())
+        })?;
 
-    run_msgcat(&pot_paths, &combined_pot_file_path)
-        .context("There was a problem while trying to run the \"msgcat\" command.")?;
+    run_msgcat_batched(
+        &gettext_config.tools,
+        gettext_config.sort_output,
+        jobs,
+        &pot_paths,
+        &combined_pot_file_path,
+    )
+    .context("There was a problem while trying to run the \"msgcat\" command.")?;
 
     Ok(())
 }
@@ -154,9 +205,86 @@ fn crate_module_pot_file_path<P: AsRef<Path>>(crt: &Crate<'_>, pot_dir: P) -> Pa
         .with_extension("pot")
 }
 
-/// Run the gettext utils `msgcat` command to concatinate pot files
-/// into a single pot file.
+/// The maximum number of pot files passed to a single invocation of
+/// `msgcat`, to avoid exceeding the command line length limit of the
+/// operating system for crates with many source files.
+const MSGCAT_BATCH_SIZE: usize = 256;
+
+/// Concatinate the pot files at `input_pot_paths` into
+/// `output_pot_path` using `msgcat`. When there are more than
+/// [MSGCAT_BATCH_SIZE] input files, they are first concatinated in
+/// batches (using up to `jobs` parallel invocations) into
+/// intermediate files, which are then concatinated into the output.
+/// The output is sorted according to `sort_output`.
+fn run_msgcat_batched(
+    tools: &GettextTools,
+    sort_output: GettextSortOutput,
+    jobs: NonZeroUsize,
+    input_pot_paths: &[PathBuf],
+    output_pot_path: &Path,
+) -> Result<()> {
+    if input_pot_paths.len() <= MSGCAT_BATCH_SIZE {
+        return run_msgcat_sorted(
+            tools,
+            sort_output,
+            input_pot_paths.iter().map(PathBuf::as_path),
+            output_pot_path,
+        );
+    }
+
+    let batches: Vec<(PathBuf, &[PathBuf])> = input_pot_paths
+        .chunks(MSGCAT_BATCH_SIZE)
+        .enumerate()
+        .map(|(i, batch)| {
+            let batch_pot_path = output_pot_path.with_extension(format!("batch-{i}.pot.tmp"));
+            (batch_pot_path, batch)
+        })
+        .collect();
+
+    let batch_pot_paths = util::run_parallel(jobs, &batches, |(batch_pot_path, batch)| {
+        run_msgcat(
+            tools,
+            batch.iter().map(PathBuf::as_path),
+            batch_pot_path.as_path(),
+        )?;
+        Ok(batch_pot_path.clone())
+    })?;
+
+    run_msgcat_sorted(
+        tools,
+        sort_output,
+        batch_pot_paths.iter().map(PathBuf::as_path),
+        output_pot_path,
+    )?;
+
+    for batch_pot_path in batch_pot_paths {
+        util::remove_file_or_error(batch_pot_path)?;
+    }
+
+    Ok(())
+}
+
+/// Run the gettext utils `msgcat` command (the executable configured
+/// in the `tools`) to concatinate pot files into a single pot file.
 pub fn run_msgcat<P: AsRef<Path>, I: IntoIterator<Item = P>>(
+    tools: &GettextTools,
+    input_pot_paths: I,
+    output_pot_path: P,
+) -> Result<()> {
+    run_msgcat_sorted(
+        tools,
+        GettextSortOutput::None,
+        input_pot_paths,
+        output_pot_path,
+    )
+}
+
+/// Run the gettext utils `msgcat` command like [run_msgcat()], sorting
+/// the messages of the output pot file according to `sort_output`
+/// (see [GettextConfig::sort_output]).
+pub fn run_msgcat_sorted<P: AsRef<Path>, I: IntoIterator<Item = P>>(
+    tools: &GettextTools,
+    sort_output: GettextSortOutput,
     input_pot_paths: I,
     output_pot_path: P,
 ) -> Result<()> {
@@ -165,6 +293,10 @@ pub fn run_msgcat<P: AsRef<Path>, I: IntoIterator<Item = P>>(
     let mut input_pot_paths_strings: Vec<String> = Vec::new();
     let mut msgcat_args: Vec<Box<OsStr>> = Vec::new();
 
+    if let Some(option) = sort_output.msgcat_option() {
+        msgcat_args.push(Box::from(OsStr::new(option)));
+    }
+
     let mut output_in_input = false;
     for input_path in input_pot_paths_iter {
         let input_path_ref = input_path.as_ref();
@@ -197,7 +329,7 @@ pub fn run_msgcat<P: AsRef<Path>, I: IntoIterator<Item = P>>(
         .map_err(|e| PathError::cannot_create_file(&interim_output_pot_path, e))?;
 
     let msgcat_command_name = "msgcat";
-    let msgcat = Exec::cmd(msgcat_command_name)
+    let msgcat = Exec::cmd(tools.program(msgcat_command_name))
         .args(msgcat_args.as_slice())
         .stdout(output_pot_file);
 
@@ -218,6 +350,41 @@ pub fn run_msgcat<P: AsRef<Path>, I: IntoIterator<Item = P>>(
     Ok(())
 }
 
+/// Update the header of the pot file at `pot_file_path` with the
+/// metadata specified in the `[gettext.pot_header]` section of the
+/// `gettext_config`, see [GettextPotHeader](i18n_config::GettextPotHeader).
+///
+/// The `POT-Creation-Date` is set using the `SOURCE_DATE_EPOCH`
+/// environment variable if it is present. Otherwise if the file only
+/// differs from the `previous_pot` file contents by its
+/// `POT-Creation-Date`, the `previous_pot` is restored so that
+/// repeated runs produce identical files.
+pub fn run_pot_header(
+    crt: &Crate,
+    gettext_config: &GettextConfig,
+    pot_file_path: &Path,
+    previous_pot: Option<&str>,
+) -> Result<()> {
+    info!(
+        "Updating the header of pot file \"{0}\"",
+        pot_file_path.to_string_lossy()
+    );
+    let pot = fs::read_to_string(pot_file_path).with_context(|| {
+        tr!(
+            "Unable to read the pot file \"{0}\".",
+            pot_file_path.to_string_lossy()
+        )
+    })?;
+
+    let updated_pot = pot_header::update_pot_header(crt, gettext_config, &pot, previous_pot);
+    if updated_pot != pot {
+        fs::write(pot_file_path, updated_pot)
+            .map_err(|e| PathError::cannot_create_file(pot_file_path, e))?;
+    }
+
+    Ok(())
+}
+
 /// Run the gettext `msginit` command to create a new `po` file.
 ///
 /// `pot_dir` is the directory where the input `pot` files are stored.
@@ -245,10 +412,11 @@ pub fn run_msginit(crt: &Crate, pot_dir: &Path, po_dir: &Path) -> Result<()> {
         let po_path = po_locale_dir.join(crt.module_name()).with_extension("po");
 
         if !po_path.exists() {
+            report_language(crt, Stage::Init, locale);
             create_dir_all(po_locale_dir.clone())
                 .map_err(|e| PathError::cannot_create_dir(po_locale_dir, e))?;
 
-            let mut msginit = Command::new(msginit_command_name);
+            let mut msginit = Command::new(gettext_config.tools.program(msginit_command_name));
             msginit.args(&[
                 format!(
                     "--input={}",
@@ -279,7 +447,10 @@ pub fn run_msginit(crt: &Crate, pot_dir: &Path, po_dir: &Path) -> Result<()> {
 }
 
 /// Run the gettext `msgmerge` command to update the `po` files with
-/// new/deleted messages from the source `pot` files.
+/// new/deleted messages from the source `pot` files. With the
+/// `builtin-gettext-tools` feature, the files are updated using
+/// [msgmerge::msgmerge()] instead, unless a `msgmerge` executable is
+/// configured (see [GettextTools::configured()]).
 ///
 /// `pot_dir` is the directory where the input `pot` files are stored.
 ///
@@ -305,8 +476,25 @@ pub fn run_msgmerge(crt: &Crate, pot_dir: &Path, po_dir: &Path) -> Result<()> {
             .with_extension("po");
 
         util::check_path_exists(&po_file_path)?;
+        report_language(crt, Stage::Merge, locale);
+
+        #[cfg(feature = "builtin-gettext-tools")]
+        if gettext_config
+            .tools
+            .configured(msgmerge_command_name)
+            .is_none()
+        {
+            ignored_args_warning(msgmerge_command_name, &gettext_config.msgmerge_args);
+            msgmerge::msgmerge(
+                &po_file_path,
+                &pot_file_path,
+                gettext_config.fuzzy_threshold(),
+            )?;
+            continue;
+        }
 
-        let mut msgmerge = Command::new(msgmerge_command_name);
+        let mut msgmerge = Command::new(gettext_config.tools.program(msgmerge_command_name));
+        msgmerge.args(&gettext_config.msgmerge_args);
         msgmerge.args([
             "--silent",
             "--backup=none",
@@ -325,8 +513,65 @@ pub fn run_msgmerge(crt: &Crate, pot_dir: &Path, po_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Apply the `obsolete_policy` in the crate's gettext config to the
+/// obsolete (`#~`) entries of its `po` files, which are left by
+/// `msgmerge` for the messages which were removed: they are either
+/// kept, removed, or moved to the attic file of the `po` file (see
+/// [util::attic_file_path()]).
+///
+/// `po_dir` is the directory where the `po` files are stored.
+pub fn run_obsolete_policy(crt: &Crate, po_dir: &Path) -> Result<()> {
+    let gettext_config = crt.gettext_config_or_err()?;
+    let obsolete_policy = gettext_config.obsolete_policy;
+    if obsolete_policy == ObsoletePolicy::Keep {
+        return Ok(());
+    }
+
+    for locale in &gettext_config.target_languages {
+        let po_file_path = po_dir
+            .join(locale)
+            .join(crt.module_name())
+            .with_extension("po");
+        let po = fs::read_to_string(&po_file_path).with_context(|| {
+            tr!(
+                "Unable to read the po file \"{0}\".",
+                po_file_path.to_string_lossy()
+            )
+        })?;
+        let (obsolete, current): (Vec<&str>, Vec<&str>) =
+            po::entries(&po).partition(|entry| po::PoEntry::parse(entry).is_obsolete());
+        if obsolete.is_empty() {
+            continue;
+        }
+        info!(
+            "Removing {0} obsolete entries from \"{1}\"",
+            obsolete.len(),
+            po_file_path.to_string_lossy()
+        );
+        let obsolete: Vec<&str> = obsolete
+            .iter()
+            .map(|entry| entry.trim_end_matches('\n'))
+            .collect();
+        if obsolete_policy == ObsoletePolicy::Attic {
+            util::append_to_attic(&po_file_path, &obsolete.join("\n\n"))?;
+        }
+        let mut po = current
+            .iter()
+            .map(|entry| entry.trim_end_matches('\n'))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        po.push('\n');
+        fs::write(&po_file_path, po)
+            .map_err(|e| PathError::cannot_create_file(&po_file_path, e))?;
+    }
+
+    Ok(())
+}
+
 /// Run the gettext `msgfmt` command to compile the `po` files into
-/// binary `mo` files.
+/// binary `mo` files. With the `builtin-gettext-tools` feature, the
+/// files are compiled using [po2mo] instead, unless a `msgfmt`
+/// executable is configured (see [GettextTools::configured()]).
 ///
 /// `po_dir` is the directory where the input `po` files are stored.
 ///
@@ -348,6 +593,7 @@ pub fn run_msgfmt(crt: &Crate, po_dir: &Path, mo_dir: &Path) -> Result<()> {
             .with_extension("po");
 
         util::check_path_exists(&po_file_path)?;
+        report_language(crt, Stage::Compile, locale);
 
         let mo_locale_dir = mo_dir.join(locale);
 
@@ -357,7 +603,19 @@ pub fn run_msgfmt(crt: &Crate, po_dir: &Path, mo_dir: &Path) -> Result<()> {
 
         let mo_file_path = mo_locale_dir.join(crt.module_name()).with_extension("mo");
 
-        let mut msgfmt = Command::new(msgfmt_command_name);
+        #[cfg(feature = "builtin-gettext-tools")]
+        if gettext_config
+            .tools
+            .configured(msgfmt_command_name)
+            .is_none()
+        {
+            ignored_args_warning(msgfmt_command_name, &gettext_config.msgfmt_args);
+            po2mo::po2mo(&po_file_path, &mo_file_path, gettext_config.use_fuzzy)?;
+            continue;
+        }
+
+        let mut msgfmt = Command::new(gettext_config.tools.program(msgfmt_command_name));
+        msgfmt.args(&gettext_config.msgfmt_args);
         let msgfmt_arg_output_file = format!(
             "--output-file={}",
             mo_file_path
@@ -381,117 +639,49 @@ pub fn run_msgfmt(crt: &Crate, po_dir: &Path, mo_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Warn that the additional `args` configured for the gettext tool
+/// with the `name` are not used by its built-in replacement.
+#[cfg(feature = "builtin-gettext-tools")]
+fn ignored_args_warning(name: &str, args: &[String]) {
+    if !args.is_empty() {
+        log::warn!(
+            "The `{0}_args` {1:?} are ignored, because the built-in replacement for `{0}` is used instead of a configured `{0}` executable.",
+            name, args
+        );
+    }
+}
+
+/// Report that the `stage` started for the `language` of the crate
+/// `crt`, see [progress](crate::progress).
+fn report_language(crt: &Crate, stage: Stage, language: &str) {
+    progress::report(|| Progress::Language {
+        crate_name: crt.name.clone(),
+        stage,
+        language: language.to_string(),
+    });
+}
+
 /// Run the gettext i18n build process for the provided crate. The
 /// crate must have an i18n config containing a gettext config.
 ///
 /// This function is recursively executed for each subcrate.
-pub fn run(crt: &Crate) -> Result<()> {
-    info!(
-        "Localizing crate \"{0}\" using the gettext system",
-        crt.path.to_string_lossy()
-    );
-    let (config_crate, _i18n_config) = crt.active_config()?.unwrap_or_else(|| {
-        panic!(
-            "expected that there would be an active config for the crate: \"{0}\" at \"{1}\"",
-            crt.name,
-            crt.path.to_string_lossy()
-        )
-    });
-
-    let gettext_config = config_crate
-        .gettext_config_or_err()
-        .expect("expected gettext config to be present");
-
-    let do_xtr = config_crate.gettext_config_or_err()?.xtr.unwrap_or(true);
-
-    // We don't use the i18n_config (which potentially comes from the
-    // parent crate )to get the subcrates, because this would result
-    // in an infinite loop.
-    let subcrates: Vec<Crate> = match &crt.i18n_config {
-        Some(config) => {
-            let subcrates: Result<Vec<Crate>, I18nConfigError> = config
-                .subcrates
-                .iter()
-                .map(|subcrate_path| {
-                    Crate::from(
-                        subcrate_path.clone(),
-                        Some(crt),
-                        crt.config_file_path.clone(),
-                    )
-                })
-                .collect();
-
-            subcrates.with_context(|| {
-                let subcrate_path_strings: Vec<String> = config
-                    .subcrates
-                    .iter()
-                    .map(|path| path.to_string_lossy().to_string())
-                    .collect();
-
-                tr!(
-                    "There was a problem parsing one of the subcrates: \"{0}\".",
-                    subcrate_path_strings.join(", ")
-                )
-            })?
-        }
-        None => vec![],
-    };
-
-    let src_dir = crt.path.join("src");
-    let pot_dir = config_crate.path.join(gettext_config.pot_dir());
-    let po_dir = config_crate.path.join(gettext_config.po_dir());
-    let mo_dir = config_crate.path.join(gettext_config.mo_dir());
-
-    // perform string extraction if required
-    if do_xtr {
-        let prepend_crate_path =
-            crt.path.canonicalize().unwrap() != config_crate.path.canonicalize().unwrap();
-        run_xtr(
-            crt,
-            gettext_config,
-            src_dir.as_path(),
-            pot_dir.as_path(),
-            prepend_crate_path,
-        )?;
-    }
-
-    // figure out where there are any subcrates which need their output
-    // pot files concatinated with this crate's pot file
-    let mut concatinate_crates = vec![];
-    for subcrate in &subcrates {
-        run(subcrate)?;
-        if subcrate.collated_subcrate() {
-            concatinate_crates.push(subcrate);
-        }
-    }
-
-    // Perform the concatination (if there are any required)
-    if !concatinate_crates.is_empty() {
-        assert!(crt.gettext_config_or_err()?.collate_extracted_subcrates);
-        concatinate_crates.insert(0, crt);
-
-        let concatinate_crate_paths: Vec<PathBuf> = concatinate_crates
-            .iter()
-            .map(|concat_crt: &&Crate| crate_module_pot_file_path(concat_crt, &pot_dir))
-            .collect();
-
-        let output_pot_path = crate_module_pot_file_path(crt, &pot_dir);
-        run_msgcat(concatinate_crate_paths, output_pot_path)?;
-
-        // remove this crate from the list because we don't want to delete it's pot file
-        concatinate_crates.remove(0);
-
-        for subcrate in concatinate_crates {
-            let subcrate_output_pot_path = crate_module_pot_file_path(subcrate, &pot_dir);
-            util::remove_file_or_error(subcrate_output_pot_path)?;
-        }
-    }
-
-    if !(crt.collated_subcrate()) {
-        run_msginit(crt, pot_dir.as_path(), po_dir.as_path())?;
-        run_msgmerge(crt, pot_dir.as_path(), po_dir.as_path())?;
-        run_msgfmt(crt, po_dir.as_path(), mo_dir.as_path())?;
-    }
+///
+/// `jobs` is the maximum number of external commands to run in
+/// parallel during string extraction.
+pub fn run(crt: &Crate, jobs: NonZeroUsize) -> Result<()> {
+    run_with_summary(crt, jobs, &mut RunSummary::default())
+}
 
-    Ok(())
+/// Run the gettext i18n build process for the provided crate, see
+/// [run()], adding what happened for the crate and each of its
+/// subcrates to the `summary`. The process can be customized using a
+/// [GettextPipeline].
+pub fn run_with_summary(crt: &Crate, jobs: NonZeroUsize, summary: &mut RunSummary) -> Result<()> {
+    GettextPipeline::for_crate(crt)
+        .jobs(jobs)
+        .extract()
+        .init_missing()
+        .merge()
+        .compile()
+        .run_with_summary(summary)
 }
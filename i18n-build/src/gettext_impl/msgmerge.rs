@@ -0,0 +1,342 @@
+//! A built-in replacement for the gettext `msgmerge` command, which
+//! updates `po` files with the messages of a `pot` file without
+//! requiring the gettext tools to be installed.
+//!
+//! ⚠️ *This module requires the following crate features to be activated: `builtin-gettext-tools`.*
+
+use super::po::{self, PoEntry};
+use crate::error::PathError;
+
+use anyhow::{Context, Result};
+use std::{collections::HashMap, fs, path::Path};
+use tr::tr;
+
+const POT_CREATION_DATE: &str = "\"POT-Creation-Date:";
+
+/// Update the `po` file at `po_path` with the messages of the `pot`
+/// file at `pot_path`, like `msgmerge --update po_path pot_path`, see
+/// [merge()].
+pub fn msgmerge(po_path: &Path, pot_path: &Path, fuzzy_threshold: f64) -> Result<()> {
+    let read = |path: &Path| {
+        fs::read_to_string(path).with_context(|| {
+            tr!(
+                // {0} is the path to the po or pot file.
+                "Unable to read \"{0}\".",
+                path.to_string_lossy()
+            )
+        })
+    };
+    let po = read(po_path)?;
+    let pot = read(pot_path)?;
+    let merged = merge(&po, &pot, fuzzy_threshold);
+    if merged != po {
+        fs::write(po_path, merged).map_err(|e| PathError::cannot_create_file(po_path, e))?;
+    }
+    Ok(())
+}
+
+/// Merge the messages of the contents of a `pot` file into the
+/// contents of a `po` file, returning the updated `po` file. Like
+/// `msgmerge`:
+///
+/// + The messages are those of the `pot` file, in the same order,
+///   with their translations (and translator comments) from the `po`
+///   file.
+/// + A new message without a translation is given the translation of
+///   the most similar message in the `po` file, marked as `fuzzy`, if
+///   the similarity of their `msgid` is at least the
+///   `fuzzy_threshold` (from `0.0` to `1.0`, where `1.0` disables
+///   fuzzy matching).
+/// + The translated messages which are no longer in the `pot` file
+///   are kept at the end as obsolete (`#~`) entries.
+/// + The header of the `po` file is kept, with the
+///   `POT-Creation-Date` of the `pot` file.
+///
+/// ```
+/// use i18n_build::gettext_impl::msgmerge::merge;
+///
+/// let po = "msgid \"Hello world\"\nmsgstr \"Bonjour le monde\"\n";
+/// let pot = "msgid \"Hello world!\"\nmsgstr \"\"\n";
+/// assert_eq!(
+///     "#, fuzzy\nmsgid \"Hello world!\"\nmsgstr \"Bonjour le monde\"\n",
+///     merge(po, pot, 0.6)
+/// );
+/// ```
+pub fn merge(po: &str, pot: &str, fuzzy_threshold: f64) -> String {
+    let po = po.replace("\r\n", "\n");
+    let pot = pot.replace("\r\n", "\n");
+    let po_entries: Vec<Definition> = entries(&po).map(Definition::parse).collect();
+    let pot_entries: Vec<&str> = entries(&pot).collect();
+
+    let mut used = vec![false; po_entries.len()];
+    let mut merged: Vec<String> = Vec::with_capacity(pot_entries.len());
+    let exact: HashMap<(Option<String>, String), usize> = po_entries
+        .iter()
+        .enumerate()
+        .filter(|(_, definition)| !definition.is_header())
+        .filter_map(|(i, definition)| Some((definition.key()?, i)))
+        .collect();
+
+    for pot_entry in &pot_entries {
+        let pot_message = PoEntry::parse(pot_entry);
+        if pot_message.is_header() {
+            merged.push(merge_header(&po_entries, pot_entry));
+            continue;
+        }
+        let msgid = match pot_message.msgid() {
+            Some(msgid) => msgid,
+            None => continue,
+        };
+
+        let key = (pot_message.msgctxt(), msgid.clone());
+        let (definition, fuzzy) = match exact.get(&key) {
+            Some(&i) => (Some(i), po_entries[i].is_fuzzy()),
+            None => (fuzzy_match(&po_entries, &msgid, fuzzy_threshold), true),
+        };
+        match definition {
+            Some(i) => {
+                used[i] = true;
+                merged.push(po_entries[i].translate(pot_entry, fuzzy));
+            }
+            None => merged.push(pot_entry.to_string()),
+        }
+    }
+    if !merged.iter().any(|entry| PoEntry::parse(entry).is_header()) {
+        if let Some(header) = po_entries.iter().find(|definition| definition.is_header()) {
+            merged.insert(0, header.entry.to_string());
+        }
+    }
+
+    // The translations of the messages which were removed become
+    // obsolete.
+    for (definition, used) in po_entries.iter().zip(used) {
+        if !used && !definition.is_header() {
+            if let Some(obsolete) = definition.obsolete() {
+                merged.push(obsolete);
+            }
+        }
+    }
+
+    let mut merged = merged.join("\n\n");
+    merged.push('\n');
+    merged
+}
+
+/// The non-empty entries of the contents of a `po` file, without
+/// their trailing newlines.
+fn entries(po: &str) -> impl Iterator<Item = &str> {
+    po::entries(po)
+        .map(|entry| entry.trim_end_matches('\n'))
+        .filter(|entry| !entry.trim().is_empty())
+}
+
+/// The header of the merged `po` file, which is the header of the
+/// `po` file (if it has one) with the `POT-Creation-Date` of the
+/// `pot_header`.
+fn merge_header(po_entries: &[Definition<'_>], pot_header: &str) -> String {
+    let po_header = match po_entries.iter().find(|definition| definition.is_header()) {
+        Some(definition) => definition.entry,
+        None => return pot_header.to_string(),
+    };
+    let creation_date = pot_header
+        .lines()
+        .find(|line| line.starts_with(POT_CREATION_DATE));
+    po_header
+        .lines()
+        .map(|line| match creation_date {
+            Some(creation_date) if line.starts_with(POT_CREATION_DATE) => creation_date,
+            _ => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The index of the translated entry in the `po_entries` whose
+/// `msgid` is the most similar to the `msgid`, if its similarity is
+/// at least the `fuzzy_threshold`.
+fn fuzzy_match(po_entries: &[Definition<'_>], msgid: &str, fuzzy_threshold: f64) -> Option<usize> {
+    if fuzzy_threshold >= 1.0 {
+        return None;
+    }
+    let msgid: Vec<char> = msgid.chars().collect();
+    po_entries
+        .iter()
+        .enumerate()
+        .filter(|(_, definition)| !definition.is_header() && definition.is_translated())
+        .filter_map(|(i, definition)| {
+            let candidate: Vec<char> = definition.message.msgid()?.chars().collect();
+            // The similarity can't be higher than this, so the
+            // comparison can be skipped for very different lengths.
+            let bound = 2.0 * msgid.len().min(candidate.len()) as f64
+                / (msgid.len() + candidate.len()).max(1) as f64;
+            if bound < fuzzy_threshold {
+                return None;
+            }
+            Some((i, similarity(&msgid, &candidate)))
+        })
+        .filter(|(_, similarity)| *similarity >= fuzzy_threshold)
+        .fold(
+            None,
+            |best: Option<(usize, f64)>, (i, similarity)| match best {
+                Some((_, best_similarity)) if best_similarity >= similarity => best,
+                _ => Some((i, similarity)),
+            },
+        )
+        .map(|(i, _)| i)
+}
+
+/// The similarity of the strings `a` and `b` from `0.0` to `1.0`:
+/// twice the length of their longest common subsequence divided by
+/// their total length.
+fn similarity(a: &[char], b: &[char]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let mut previous = vec![0usize; b.len() + 1];
+    let mut current = vec![0usize; b.len() + 1];
+    for a_char in a {
+        for (j, b_char) in b.iter().enumerate() {
+            current[j + 1] = if a_char == b_char {
+                previous[j] + 1
+            } else {
+                current[j].max(previous[j + 1])
+            };
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    2.0 * previous[b.len()] as f64 / (a.len() + b.len()) as f64
+}
+
+/// An entry of the `po` file, which may be obsolete.
+struct Definition<'a> {
+    /// The entry as it is in the `po` file.
+    entry: &'a str,
+    obsolete: bool,
+    /// The lines of the entry without the `#~` prefix of obsolete
+    /// entries.
+    message: PoEntry<'a>,
+}
+
+impl<'a> Definition<'a> {
+    fn parse(entry: &'a str) -> Self {
+        let message = PoEntry::parse(entry);
+        Self {
+            entry,
+            obsolete: message.is_obsolete(),
+            message,
+        }
+        .unprefixed()
+    }
+
+    /// Remove the `#~` prefix from the lines of obsolete entries, so
+    /// that their fields can be read.
+    fn unprefixed(self) -> Self {
+        if !self.obsolete {
+            return self;
+        }
+        let lines: Vec<&'a str> = self
+            .entry
+            .lines()
+            .map(|line| {
+                line.strip_prefix("#~ ")
+                    .or_else(|| line.strip_prefix("#~"))
+                    .unwrap_or(line)
+            })
+            .collect();
+        Self {
+            message: PoEntry::from_lines(lines),
+            ..self
+        }
+    }
+
+    fn key(&self) -> Option<(Option<String>, String)> {
+        Some((self.message.msgctxt(), self.message.msgid()?))
+    }
+
+    fn is_header(&self) -> bool {
+        !self.obsolete && self.message.is_header()
+    }
+
+    fn is_fuzzy(&self) -> bool {
+        self.message.is_fuzzy()
+    }
+
+    /// The translations of the message, which has plural forms if
+    /// there is more than one.
+    fn msgstr(&self) -> Vec<String> {
+        match self.message.msgstr() {
+            Some(msgstr) => vec![msgstr],
+            None => self.message.msgstr_plural(),
+        }
+    }
+
+    fn is_translated(&self) -> bool {
+        self.msgstr().iter().any(|msgstr| !msgstr.is_empty())
+    }
+
+    /// The translator comments (`#` followed by a space, or on its
+    /// own) of the entry.
+    fn translator_comments(&self) -> impl Iterator<Item = &'a str> {
+        self.entry
+            .lines()
+            .filter(|line| line.starts_with("# ") || *line == "#")
+    }
+
+    /// The `pot_entry` with the translation and translator comments
+    /// of this entry, marked as `fuzzy` if `fuzzy` is true.
+    fn translate(&self, pot_entry: &str, fuzzy: bool) -> String {
+        let lines: Vec<&str> = self
+            .translator_comments()
+            .chain(
+                pot_entry
+                    .lines()
+                    .filter(|line| !(line.starts_with("# ") || *line == "#")),
+            )
+            .collect();
+        let entry = lines.join("\n");
+        let message = PoEntry::parse(&entry);
+        let mut msgstr = self.msgstr();
+        let plural = message.msgid_plural().is_some();
+        let translated_plural = self.message.msgid_plural().is_some();
+        // Messages which gained or lost their plural forms need to be
+        // checked by a translator.
+        let fuzzy = fuzzy || plural != translated_plural;
+        if plural {
+            if !translated_plural {
+                let plural_forms = message.msgstr_plural().len();
+                msgstr.resize(plural_forms.max(1), String::new());
+            }
+            let entry = message.with_msgstr_plural(&msgstr);
+            PoEntry::parse(&entry).with_msgstr("", fuzzy)
+        } else {
+            message.with_msgstr(
+                msgstr.first().map(String::as_str).unwrap_or_default(),
+                fuzzy,
+            )
+        }
+    }
+
+    /// This entry as an obsolete entry, without its extracted comments
+    /// and references, or `None` if it is not translated.
+    fn obsolete(&self) -> Option<String> {
+        if self.obsolete {
+            return Some(self.entry.to_string());
+        }
+        if !self.is_translated() {
+            return None;
+        }
+        let lines: Vec<String> = self
+            .entry
+            .lines()
+            .filter(|line| !(line.starts_with("#.") || line.starts_with("#:")))
+            .map(|line| {
+                if line.starts_with('#') {
+                    line.to_string()
+                } else {
+                    format!("#~ {line}")
+                }
+            })
+            .collect();
+        Some(lines.join("\n"))
+    }
+}
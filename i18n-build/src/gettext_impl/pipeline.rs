@@ -0,0 +1,413 @@
+//! A builder for the steps of the gettext i18n build process, for
+//! build scripts which need to customize the process, see
+//! [GettextPipeline].
+
+use super::{
+    crate_module_pot_file_path, po, run_msgcat_sorted, run_msgfmt, run_msginit, run_msgmerge,
+    run_obsolete_policy, run_pot_header, run_xtr,
+};
+use crate::progress::{self, Progress};
+use crate::summary::{CrateSummary, LanguageChanges, RunSummary, Stage};
+use crate::util;
+
+use anyhow::{Context, Result};
+use i18n_config::{Crate, I18nConfigError};
+use log::info;
+use std::{
+    fs,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+};
+use tr::tr;
+
+/// A function which is run before or after a stage of a
+/// [GettextPipeline].
+type Hook<'a> = Box<dyn FnMut(&PipelineContext<'_>) -> Result<()> + 'a>;
+
+/// The crate and paths of a stage of a [GettextPipeline], which are
+/// passed to its hooks.
+#[derive(Debug, Clone, Copy)]
+pub struct PipelineContext<'c> {
+    /// The stage which is about to be run, or which was just run.
+    pub stage: Stage,
+    /// The crate (or subcrate) being localized.
+    pub crt: &'c Crate<'c>,
+    /// The directory containing the `pot` files.
+    pub pot_dir: &'c Path,
+    /// The directory containing the `po` files.
+    pub po_dir: &'c Path,
+    /// The directory containing the `mo` files.
+    pub mo_dir: &'c Path,
+    /// The path to the crate's `pot` file.
+    pub pot_file_path: &'c Path,
+}
+
+/// A builder for the gettext i18n build process of a crate (and its
+/// subcrates), which runs the selected steps in order, with the paths
+/// taken from the crate's i18n config. Hooks can be run before or
+/// after each [Stage] to customize the process, e.g. to post-process
+/// the `pot` file before the `po` files are updated.
+///
+/// [run()](super::run()) is equivalent to a pipeline with all of the
+/// steps:
+///
+/// ```no_run
+/// use i18n_build::gettext_impl::GettextPipeline;
+/// use i18n_build::summary::Stage;
+/// use i18n_config::Crate;
+/// use std::path::PathBuf;
+///
+/// let crt = Crate::from(PathBuf::from("my_crate"), None, "i18n.toml").unwrap();
+/// GettextPipeline::for_crate(&crt)
+///     .extract()
+///     .init_missing()
+///     .merge()
+///     .after(Stage::PotHeader, |context| {
+///         println!("updated {:?}", context.pot_file_path);
+///         Ok(())
+///     })
+///     .compile()
+///     .run()
+///     .unwrap();
+/// ```
+pub struct GettextPipeline<'a> {
+    crt: &'a Crate<'a>,
+    jobs: NonZeroUsize,
+    extract: bool,
+    init_missing: bool,
+    merge: bool,
+    compile: bool,
+    before: Vec<(Stage, Hook<'a>)>,
+    after: Vec<(Stage, Hook<'a>)>,
+}
+
+impl<'a> GettextPipeline<'a> {
+    /// A pipeline for the crate `crt`, which must have an i18n config
+    /// containing a gettext config, without any steps.
+    pub fn for_crate(crt: &'a Crate<'a>) -> Self {
+        Self {
+            crt,
+            jobs: util::default_jobs(),
+            extract: false,
+            init_missing: false,
+            merge: false,
+            compile: false,
+            before: Vec::new(),
+            after: Vec::new(),
+        }
+    }
+
+    /// The maximum number of external commands to run in parallel
+    /// during string extraction, by default [util::default_jobs()].
+    pub fn jobs(mut self, jobs: NonZeroUsize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    /// Extract the messages from the source code into the crate's
+    /// `pot` file ([Stage::Extract], unless `xtr` is disabled in the
+    /// config), concatenate the `pot` files of the collated subcrates
+    /// ([Stage::Collate]), and update the header of the `pot` file
+    /// ([Stage::PotHeader]).
+    pub fn extract(mut self) -> Self {
+        self.extract = true;
+        self
+    }
+
+    /// Create the `po` files of the target languages which don't have
+    /// one yet ([Stage::Init]).
+    pub fn init_missing(mut self) -> Self {
+        self.init_missing = true;
+        self
+    }
+
+    /// Update the `po` files with the messages of the `pot` file, and
+    /// apply the `obsolete_policy` of the config ([Stage::Merge]).
+    pub fn merge(mut self) -> Self {
+        self.merge = true;
+        self
+    }
+
+    /// Compile the `po` files into `mo` files ([Stage::Compile]).
+    pub fn compile(mut self) -> Self {
+        self.compile = true;
+        self
+    }
+
+    /// Run the `hook` before the `stage`, each time it is run for the
+    /// crate or one of its subcrates. An error returned by the hook
+    /// stops the pipeline.
+    pub fn before(
+        mut self,
+        stage: Stage,
+        hook: impl FnMut(&PipelineContext<'_>) -> Result<()> + 'a,
+    ) -> Self {
+        self.before.push((stage, Box::new(hook)));
+        self
+    }
+
+    /// Run the `hook` after the `stage` completed successfully, each
+    /// time it is run for the crate or one of its subcrates. An error
+    /// returned by the hook stops the pipeline.
+    pub fn after(
+        mut self,
+        stage: Stage,
+        hook: impl FnMut(&PipelineContext<'_>) -> Result<()> + 'a,
+    ) -> Self {
+        self.after.push((stage, Box::new(hook)));
+        self
+    }
+
+    /// Run the steps of the pipeline for the crate and each of its
+    /// subcrates.
+    pub fn run(self) -> Result<()> {
+        self.run_with_summary(&mut RunSummary::default())
+    }
+
+    /// Run the steps of the pipeline, see [GettextPipeline::run()],
+    /// adding what happened for the crate and each of its subcrates
+    /// to the `summary`.
+    pub fn run_with_summary(mut self, summary: &mut RunSummary) -> Result<()> {
+        let crt = self.crt;
+        self.run_crate(crt, summary)
+    }
+
+    fn run_crate(&mut self, crt: &Crate, summary: &mut RunSummary) -> Result<()> {
+        info!(
+            "Localizing crate \"{0}\" using the gettext system",
+            crt.path.to_string_lossy()
+        );
+        progress::report(|| Progress::Crate {
+            name: crt.name.clone(),
+            system: "gettext",
+        });
+        let (config_crate, _i18n_config) = crt.active_config()?.unwrap_or_else(|| {
+            panic!(
+                "expected that there would be an active config for the crate: \"{0}\" at \"{1}\"",
+                crt.name,
+                crt.path.to_string_lossy()
+            )
+        });
+
+        let gettext_config = config_crate
+            .gettext_config_or_err()
+            .expect("expected gettext config to be present");
+
+        let do_xtr = self.extract && config_crate.gettext_config_or_err()?.xtr.unwrap_or(true);
+
+        // We don't use the i18n_config (which potentially comes from the
+        // parent crate) to get the subcrates, because this would result
+        // in an infinite loop.
+        let subcrate_paths = crt.subcrate_paths();
+        let subcrates: Result<Vec<Crate>, I18nConfigError> = subcrate_paths
+            .iter()
+            .map(|subcrate_path| {
+                Crate::from(
+                    crt.path.join(subcrate_path),
+                    Some(crt),
+                    crt.config_file_path.clone(),
+                )
+            })
+            .collect();
+
+        let subcrates = subcrates.with_context(|| {
+            let subcrate_path_strings: Vec<String> = subcrate_paths
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect();
+
+            tr!(
+                "There was a problem parsing one of the subcrates: \"{0}\".",
+                subcrate_path_strings.join(", ")
+            )
+        })?;
+
+        let summary_index = summary.crates.len();
+        let mut crate_summary = CrateSummary {
+            name: crt.name.clone(),
+            path: crt.path.clone(),
+            ..CrateSummary::default()
+        };
+
+        let src_dirs = gettext_config.src_dirs(crt);
+        let pot_dir = config_crate.path.join(gettext_config.pot_dir());
+        let po_dir = config_crate.path.join(gettext_config.po_dir());
+        let mo_dir = config_crate.path.join(gettext_config.mo_dir());
+
+        // keep the previous pot file so that its creation date can be
+        // retained if nothing else has changed.
+        let pot_file_path = crate_module_pot_file_path(crt, &pot_dir);
+        let previous_pot = fs::read_to_string(&pot_file_path).ok();
+
+        let context = |stage: Stage| PipelineContext {
+            stage,
+            crt,
+            pot_dir: &pot_dir,
+            po_dir: &po_dir,
+            mo_dir: &mo_dir,
+            pot_file_path: &pot_file_path,
+        };
+
+        // perform string extraction if required
+        if do_xtr {
+            let prepend_crate_path =
+                crt.path.canonicalize().unwrap() != config_crate.path.canonicalize().unwrap();
+            let jobs = self.jobs;
+            self.stage(context(Stage::Extract), &mut crate_summary, || {
+                run_xtr(
+                    crt,
+                    gettext_config,
+                    &src_dirs,
+                    pot_dir.as_path(),
+                    prepend_crate_path,
+                    jobs,
+                )
+            })?;
+        }
+
+        // figure out where there are any subcrates which need their output
+        // pot files concatinated with this crate's pot file
+        let mut concatinate_crates = vec![];
+        for subcrate in &subcrates {
+            self.run_crate(subcrate, summary)?;
+            if subcrate.collated_subcrate() {
+                concatinate_crates.push(subcrate);
+            }
+        }
+
+        // Subcrates without any source code have no pot file.
+        concatinate_crates
+            .retain(|subcrate| crate_module_pot_file_path(subcrate, &pot_dir).exists());
+
+        // Perform the concatination (if there are any required)
+        if self.extract && !concatinate_crates.is_empty() {
+            let crate_gettext_config = crt.gettext_config_or_err()?;
+            assert!(crate_gettext_config.collate_extracted_subcrates);
+
+            // This crate may not have a pot file if it has no source
+            // code of its own.
+            let concatinate_crate_paths: Vec<PathBuf> =
+                pot_file_path
+                    .exists()
+                    .then(|| pot_file_path.clone())
+                    .into_iter()
+                    .chain(concatinate_crates.iter().map(|concat_crt: &&Crate| {
+                        crate_module_pot_file_path(concat_crt, &pot_dir)
+                    }))
+                    .collect();
+
+            self.stage(context(Stage::Collate), &mut crate_summary, || {
+                run_msgcat_sorted(
+                    &crate_gettext_config.tools,
+                    crate_gettext_config.sort_output,
+                    concatinate_crate_paths,
+                    pot_file_path.clone(),
+                )
+            })?;
+
+            for subcrate in concatinate_crates {
+                let subcrate_output_pot_path = crate_module_pot_file_path(subcrate, &pot_dir);
+                util::remove_file_or_error(subcrate_output_pot_path)?;
+            }
+        }
+
+        // Crates without any source code are skipped, unless the pot
+        // file is created some other way (when `xtr` is disabled).
+        let has_pot = !do_xtr || pot_file_path.exists();
+        if !has_pot {
+            info!(
+                "Skipping crate \"{0}\", there are no messages extracted from its source code",
+                crt.path.to_string_lossy()
+            );
+        }
+
+        if do_xtr && has_pot {
+            self.stage(context(Stage::PotHeader), &mut crate_summary, || {
+                run_pot_header(crt, gettext_config, &pot_file_path, previous_pot.as_deref())
+            })?;
+        }
+
+        if let Ok(pot) = fs::read_to_string(&pot_file_path) {
+            crate_summary.messages = Some(po::count_messages(&pot));
+        }
+
+        if !(crt.collated_subcrate()) && has_pot {
+            let po_file_path = |language: &String| {
+                po_dir
+                    .join(language)
+                    .join(crt.module_name())
+                    .with_extension("po")
+            };
+            let target_languages = &crt.gettext_config_or_err()?.target_languages;
+            let previous_pos: Vec<Option<String>> = target_languages
+                .iter()
+                .map(|language| fs::read_to_string(po_file_path(language)).ok())
+                .collect();
+
+            if self.init_missing {
+                self.stage(context(Stage::Init), &mut crate_summary, || {
+                    run_msginit(crt, pot_dir.as_path(), po_dir.as_path())
+                })?;
+            }
+            if self.merge {
+                self.stage(context(Stage::Merge), &mut crate_summary, || {
+                    run_msgmerge(crt, pot_dir.as_path(), po_dir.as_path())?;
+                    run_obsolete_policy(crt, po_dir.as_path())
+                })?;
+            }
+            if self.compile {
+                self.stage(context(Stage::Compile), &mut crate_summary, || {
+                    run_msgfmt(crt, po_dir.as_path(), mo_dir.as_path())
+                })?;
+            }
+
+            for (language, previous_po) in target_languages.iter().zip(previous_pos) {
+                if let Ok(po) = fs::read_to_string(po_file_path(language)) {
+                    let (new, changed, removed) = po::count_changes(previous_po.as_deref(), &po);
+                    let (translated, fuzzy, untranslated) = po::count_translations(&po);
+                    crate_summary.languages.push(LanguageChanges {
+                        language: language.clone(),
+                        new,
+                        changed,
+                        removed,
+                        translated,
+                        fuzzy,
+                        untranslated,
+                    });
+                }
+            }
+        }
+
+        summary.crates.insert(summary_index, crate_summary);
+
+        Ok(())
+    }
+
+    /// Run the stage `f` of the `context`, with its hooks, recording
+    /// how long it took in the `crate_summary`.
+    fn stage(
+        &mut self,
+        context: PipelineContext<'_>,
+        crate_summary: &mut CrateSummary,
+        f: impl FnOnce() -> Result<()>,
+    ) -> Result<()> {
+        progress::report(|| Progress::Stage {
+            crate_name: context.crt.name.clone(),
+            stage: context.stage,
+        });
+        run_hooks(&mut self.before, &context)?;
+        crate_summary.time(context.stage, f)?;
+        run_hooks(&mut self.after, &context)
+    }
+}
+
+/// Run the `hooks` for the stage of the `context`.
+fn run_hooks(hooks: &mut [(Stage, Hook<'_>)], context: &PipelineContext<'_>) -> Result<()> {
+    for (_, hook) in hooks
+        .iter_mut()
+        .filter(|(stage, _)| *stage == context.stage)
+    {
+        hook(context)?;
+    }
+    Ok(())
+}
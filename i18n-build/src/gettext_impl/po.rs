@@ -0,0 +1,300 @@
+//! Reading and editing the entries of `po` files.
+
+use std::{collections::HashMap, ops::Range};
+
+/// An entry of a `po` file: the comments, flags and fields of a
+/// single message, separated from the other entries by a blank line.
+pub(crate) struct PoEntry<'a> {
+    lines: Vec<&'a str>,
+    /// Whether the entry ended with a newline (only the last entry of
+    /// a file does).
+    trailing_newline: bool,
+}
+
+impl<'a> PoEntry<'a> {
+    pub(crate) fn parse(entry: &'a str) -> Self {
+        Self {
+            lines: entry.lines().collect(),
+            trailing_newline: entry.ends_with('\n'),
+        }
+    }
+
+    /// An entry consisting of the `lines`, without a trailing newline.
+    #[cfg(feature = "builtin-gettext-tools")]
+    pub(crate) fn from_lines(lines: Vec<&'a str>) -> Self {
+        Self {
+            lines,
+            trailing_newline: false,
+        }
+    }
+
+    /// Whether this is an obsolete entry (commented out with `#~`).
+    pub(crate) fn is_obsolete(&self) -> bool {
+        self.lines.iter().any(|line| line.starts_with("#~"))
+    }
+
+    /// Whether this entry has plural forms (`msgid_plural`).
+    pub(crate) fn is_plural(&self) -> bool {
+        self.lines
+            .iter()
+            .any(|line| line.starts_with("msgid_plural"))
+    }
+
+    /// Whether this entry is the header entry (with an empty
+    /// `msgid`).
+    pub(crate) fn is_header(&self) -> bool {
+        self.msgid().is_some_and(|msgid| msgid.is_empty()) && self.msgctxt().is_none()
+    }
+
+    /// Whether this entry has the `fuzzy` flag.
+    pub(crate) fn is_fuzzy(&self) -> bool {
+        self.lines
+            .iter()
+            .any(|line| line.starts_with("#,") && line.contains("fuzzy"))
+    }
+
+    /// Whether this entry has a translation: a non-empty `msgstr`, or
+    /// all of its plural forms are non-empty.
+    pub(crate) fn is_translated(&self) -> bool {
+        if self.is_plural() {
+            let msgstr_plural = self.msgstr_plural();
+            !msgstr_plural.is_empty() && msgstr_plural.iter().all(|msgstr| !msgstr.is_empty())
+        } else {
+            self.msgstr().is_some_and(|msgstr| !msgstr.is_empty())
+        }
+    }
+
+    pub(crate) fn msgctxt(&self) -> Option<String> {
+        self.field("msgctxt").map(|(_, value)| value)
+    }
+
+    pub(crate) fn msgid(&self) -> Option<String> {
+        self.field("msgid").map(|(_, value)| value)
+    }
+
+    pub(crate) fn msgstr(&self) -> Option<String> {
+        self.field("msgstr").map(|(_, value)| value)
+    }
+
+    pub(crate) fn msgid_plural(&self) -> Option<String> {
+        self.field("msgid_plural").map(|(_, value)| value)
+    }
+
+    /// The plural forms (`msgstr[n]`) of this entry, in order.
+    pub(crate) fn msgstr_plural(&self) -> Vec<String> {
+        (0..)
+            .map_while(|n| self.field(&format!("msgstr[{n}]")).map(|(_, value)| value))
+            .collect()
+    }
+
+    /// The extracted comments (`#.`) and translator comments (`# `)
+    /// of this entry.
+    pub(crate) fn comments(&self) -> Vec<String> {
+        self.lines
+            .iter()
+            .filter_map(|line| {
+                line.strip_prefix("#.")
+                    .or_else(|| line.strip_prefix("# "))
+                    .or_else(|| (*line == "#").then_some(""))
+            })
+            .map(|comment| comment.trim().to_string())
+            .collect()
+    }
+
+    /// Find the `field` (e.g. `msgid`) of this entry, returning the
+    /// range of lines it spans and its unquoted value.
+    fn field(&self, field: &str) -> Option<(Range<usize>, String)> {
+        let prefix = format!("{field} ");
+        let start = self
+            .lines
+            .iter()
+            .position(|line| line.starts_with(&prefix))?;
+        let mut value = unquote(self.lines[start][prefix.len()..].trim());
+        let mut end = start + 1;
+        while end < self.lines.len() && self.lines[end].starts_with('"') {
+            value.push_str(&unquote(self.lines[end].trim()));
+            end += 1;
+        }
+        Some((start..end, value))
+    }
+
+    /// This entry with the `msgstr` replaced, and the `fuzzy` flag set
+    /// or cleared. Clearing the flag also removes the previous
+    /// `msgid` (`#|`) comments added by `msgmerge` for fuzzy matches.
+    pub(crate) fn with_msgstr(&self, msgstr: &str, fuzzy: bool) -> String {
+        let msgstr_range = self.field("msgstr").map(|(range, _)| range);
+        let mut lines: Vec<String> = Vec::with_capacity(self.lines.len() + 1);
+        let mut flagged = false;
+        for (i, line) in self.lines.iter().enumerate() {
+            if let Some(range) = &msgstr_range {
+                if range.contains(&i) {
+                    if i == range.start {
+                        lines.push(format!("msgstr \"{0}\"", escape(msgstr)));
+                    }
+                    continue;
+                }
+            }
+
+            if let Some(flags) = line.strip_prefix("#,") {
+                flagged = true;
+                let mut flags: Vec<&str> = flags
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|flag| !flag.is_empty() && *flag != "fuzzy")
+                    .collect();
+                if fuzzy {
+                    flags.insert(0, "fuzzy");
+                }
+                if !flags.is_empty() {
+                    lines.push(format!("#, {0}", flags.join(", ")));
+                }
+            } else if !fuzzy && line.starts_with("#|") {
+                continue;
+            } else {
+                if fuzzy && !(flagged || line.starts_with('#')) {
+                    lines.push("#, fuzzy".to_string());
+                    flagged = true;
+                }
+                lines.push(line.to_string());
+            }
+        }
+
+        let mut entry = lines.join("\n");
+        if self.trailing_newline {
+            entry.push('\n');
+        }
+        entry
+    }
+
+    /// This entry with its plural forms (`msgstr[n]`) replaced by
+    /// `msgstr_plural`.
+    pub(crate) fn with_msgstr_plural(&self, msgstr_plural: &[String]) -> String {
+        let ranges: Vec<Range<usize>> = (0..)
+            .map_while(|n| self.field(&format!("msgstr[{n}]")).map(|(range, _)| range))
+            .collect();
+        let mut lines: Vec<String> = Vec::with_capacity(self.lines.len());
+        for (i, line) in self.lines.iter().enumerate() {
+            if ranges.iter().any(|range| range.contains(&i)) {
+                if ranges.first().is_some_and(|range| range.start == i) {
+                    lines.extend(
+                        msgstr_plural
+                            .iter()
+                            .enumerate()
+                            .map(|(n, msgstr)| format!("msgstr[{n}] \"{0}\"", escape(msgstr))),
+                    );
+                }
+                continue;
+            }
+            lines.push(line.to_string());
+        }
+
+        let mut entry = lines.join("\n");
+        if self.trailing_newline {
+            entry.push('\n');
+        }
+        entry
+    }
+}
+
+/// The messages of a `po` file (excluding the header and obsolete
+/// entries), keyed by their `msgctxt` and `msgid`, with their
+/// translations and whether they are fuzzy.
+fn messages(po: &str) -> HashMap<(Option<String>, String), (Vec<String>, bool)> {
+    entries(po)
+        .map(PoEntry::parse)
+        .filter(|entry| !(entry.is_obsolete() || entry.is_header()))
+        .filter_map(|entry| {
+            let msgstr = match entry.msgstr() {
+                Some(msgstr) => vec![msgstr],
+                None => entry.msgstr_plural(),
+            };
+            Some((
+                (entry.msgctxt(), entry.msgid()?),
+                (msgstr, entry.is_fuzzy()),
+            ))
+        })
+        .collect()
+}
+
+/// Count the messages which are new, changed (in their translation
+/// or `fuzzy` flag) and removed in the `after` contents of a `po`
+/// file compared to its `before` contents.
+pub(crate) fn count_changes(before: Option<&str>, after: &str) -> (usize, usize, usize) {
+    let before = before.map(messages).unwrap_or_default();
+    let after = messages(after);
+    let new = after
+        .keys()
+        .filter(|key| !before.contains_key(*key))
+        .count();
+    let removed = before
+        .keys()
+        .filter(|key| !after.contains_key(*key))
+        .count();
+    let changed = after
+        .iter()
+        .filter(|(key, value)| before.get(*key).is_some_and(|before| before != *value))
+        .count();
+    (new, changed, removed)
+}
+
+/// The number of messages in a `po` or `pot` file, excluding the
+/// header and obsolete entries.
+pub(crate) fn count_messages(po: &str) -> usize {
+    messages(po).len()
+}
+
+/// Count the messages of a `po` file which are translated, fuzzy and
+/// untranslated, excluding the header and obsolete entries, like
+/// `msgfmt --statistics` (fuzzy translations are not counted as
+/// translated).
+pub(crate) fn count_translations(po: &str) -> (usize, usize, usize) {
+    let (mut translated, mut fuzzy, mut untranslated) = (0, 0, 0);
+    for entry in entries(po).map(PoEntry::parse) {
+        if entry.is_obsolete() || entry.is_header() || entry.msgid().is_none() {
+            continue;
+        }
+        if !entry.is_translated() {
+            untranslated += 1;
+        } else if entry.is_fuzzy() {
+            fuzzy += 1;
+        } else {
+            translated += 1;
+        }
+    }
+    (translated, fuzzy, untranslated)
+}
+
+/// Split the contents of a `po` file into its entries, which can be
+/// joined again using `"\n\n"`.
+pub(crate) fn entries(po: &str) -> impl Iterator<Item = &str> {
+    po.split("\n\n")
+}
+
+/// Remove the quotes surrounding a `po` string, and unescape it.
+pub(crate) fn unquote(s: &str) -> String {
+    let s = s.strip_prefix('"').unwrap_or(s);
+    let s = s.strip_suffix('"').unwrap_or(s);
+    let mut unquoted = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => unquoted.push('\n'),
+                Some('t') => unquoted.push('\t'),
+                Some(other) => unquoted.push(other),
+                None => {}
+            }
+        } else {
+            unquoted.push(c);
+        }
+    }
+    unquoted
+}
+
+/// Escape a string to be quoted in a `po` file.
+pub(crate) fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t")
+}
@@ -0,0 +1,196 @@
+//! A built-in replacement for the gettext `msgfmt` command, which
+//! compiles `po` files into binary `mo` files without requiring the
+//! gettext tools to be installed.
+//!
+//! ⚠️ *This module requires the following crate features to be activated: `builtin-gettext-tools`.*
+
+use super::po::{self, PoEntry};
+use crate::error::PathError;
+use crate::util;
+
+use anyhow::{anyhow, Context, Result};
+use std::{collections::BTreeMap, convert::TryFrom, fs, path::Path};
+use tr::tr;
+
+/// The magic number at the start of a `mo` file.
+const MO_MAGIC: u32 = 0x9504_12de;
+
+/// The size of the header of a `mo` file, which is followed by the
+/// tables of the original and translated strings.
+const MO_HEADER_SIZE: usize = 28;
+
+/// Separates the `msgctxt` from the `msgid` in the keys of a `mo`
+/// file.
+const CONTEXT_SEPARATOR: char = '\u{4}';
+
+/// Compile the `po` file at `po_path` into the `mo` file at
+/// `mo_path`, like `msgfmt --output-file=mo_path po_path`, see
+/// [compile()].
+pub fn po2mo(po_path: &Path, mo_path: &Path, use_fuzzy: bool) -> Result<()> {
+    let po = fs::read_to_string(po_path).with_context(|| {
+        tr!(
+            // {0} is the path to the po file.
+            "Unable to read \"{0}\".",
+            po_path.to_string_lossy()
+        )
+    })?;
+    let mo = compile(&po, use_fuzzy).with_context(|| {
+        tr!(
+            // {0} is the path to the po file.
+            "Unable to compile \"{0}\".",
+            po_path.to_string_lossy()
+        )
+    })?;
+    if let Some(parent) = mo_path.parent() {
+        util::create_dir_all_if_not_exists(parent)?;
+    }
+    fs::write(mo_path, mo).map_err(|e| PathError::cannot_create_file(mo_path, e))?;
+    Ok(())
+}
+
+/// Compile the contents of a `po` file into the contents of a `mo`
+/// file. Like `msgfmt`, the untranslated and obsolete messages are
+/// skipped, and so are the messages marked as `fuzzy` unless
+/// `use_fuzzy` is enabled (like the `--use-fuzzy` option), although
+/// the header is always included (without its `POT-Creation-Date`). The messages with plural forms need
+/// to have the number of forms specified by `nplurals` in the
+/// `Plural-Forms` header (if it is present).
+///
+/// ```
+/// use i18n_build::gettext_impl::po2mo::compile;
+///
+/// let po = "msgid \"\"\nmsgstr \"Content-Type: text/plain; charset=UTF-8\\n\"\n\n\
+///           msgid \"Hello\"\nmsgstr \"Bonjour\"\n";
+/// let mo = compile(po, false).unwrap();
+/// assert_eq!(&[0xde, 0x12, 0x04, 0x95], &mo[..4]);
+/// ```
+pub fn compile(po: &str, use_fuzzy: bool) -> Result<Vec<u8>> {
+    let po = po.replace("\r\n", "\n");
+    let mut nplurals: Option<usize> = None;
+    let mut messages: BTreeMap<String, String> = BTreeMap::new();
+
+    for entry in po::entries(&po).map(PoEntry::parse) {
+        if entry.is_obsolete() {
+            continue;
+        }
+        let msgid = match entry.msgid() {
+            Some(msgid) => msgid,
+            None => continue,
+        };
+        let header = entry.is_header();
+        if header {
+            nplurals = entry.msgstr().as_deref().and_then(header_nplurals);
+        } else if entry.is_fuzzy() && !use_fuzzy {
+            continue;
+        }
+
+        let mut key = match entry.msgctxt() {
+            Some(msgctxt) => format!("{msgctxt}{CONTEXT_SEPARATOR}{msgid}"),
+            None => msgid.clone(),
+        };
+        let translation = match entry.msgid_plural() {
+            Some(msgid_plural) => {
+                let msgstr_plural = entry.msgstr_plural();
+                if msgstr_plural.first().is_none_or(String::is_empty) {
+                    continue;
+                }
+                if let Some(nplurals) = nplurals.filter(|n| *n != msgstr_plural.len()) {
+                    return Err(anyhow!(tr!(
+                        // {0} is the id of the message, {1} is the number of its plural forms, and {2} is the number of plural forms of the language.
+                        "The message \"{0}\" has {1} plural forms, but the \"Plural-Forms\" header specifies {2}.",
+                        msgid,
+                        msgstr_plural.len(),
+                        nplurals
+                    )));
+                }
+                key.push('\0');
+                key.push_str(&msgid_plural);
+                msgstr_plural.join("\0")
+            }
+            None => match entry.msgstr() {
+                // Like `msgfmt`, the creation date is removed from the
+                // header so that the compiled files are reproducible.
+                Some(msgstr) if header => msgstr
+                    .split_inclusive('\n')
+                    .filter(|line| !line.starts_with("POT-Creation-Date:"))
+                    .collect(),
+                Some(msgstr) if !msgstr.is_empty() => msgstr,
+                _ => continue,
+            },
+        };
+
+        if messages.insert(key, translation).is_some() {
+            return Err(anyhow!(tr!(
+                // {0} is the id of the message.
+                "The message \"{0}\" is defined more than once.",
+                msgid
+            )));
+        }
+    }
+
+    Ok(write_mo(&messages))
+}
+
+/// The `nplurals` of the `Plural-Forms` field of the `header` (the
+/// `msgstr` of the header entry), e.g. `2` for `Plural-Forms:
+/// nplurals=2; plural=(n != 1);`.
+fn header_nplurals(header: &str) -> Option<usize> {
+    let plural_forms = header.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("Plural-Forms")
+            .then_some(value)
+    })?;
+    plural_forms.split(';').find_map(|part| {
+        let (name, value) = part.split_once('=')?;
+        (name.trim() == "nplurals")
+            .then(|| value.trim().parse().ok())
+            .flatten()
+    })
+}
+
+/// Write the `messages` (keyed by their original strings, which are
+/// sorted as required by the format) in the little-endian `mo`
+/// format, without a hash table.
+fn write_mo(messages: &BTreeMap<String, String>) -> Vec<u8> {
+    let count = messages.len();
+    let originals_offset = MO_HEADER_SIZE;
+    let translations_offset = originals_offset + count * 8;
+    let strings_offset = translations_offset + count * 8;
+
+    let mut originals: Vec<(usize, usize)> = Vec::with_capacity(count);
+    let mut translations: Vec<(usize, usize)> = Vec::with_capacity(count);
+    let mut strings: Vec<u8> = Vec::new();
+    let mut push_string = |string: &str| {
+        let offset = strings_offset + strings.len();
+        strings.extend_from_slice(string.as_bytes());
+        strings.push(0);
+        (string.len(), offset)
+    };
+    for original in messages.keys() {
+        originals.push(push_string(original));
+    }
+    for translation in messages.values() {
+        translations.push(push_string(translation));
+    }
+
+    let mut mo: Vec<u8> = Vec::with_capacity(strings_offset + strings.len());
+    mo.extend_from_slice(&MO_MAGIC.to_le_bytes());
+    push_u32(&mut mo, 0); // revision
+    push_u32(&mut mo, count);
+    push_u32(&mut mo, originals_offset);
+    push_u32(&mut mo, translations_offset);
+    push_u32(&mut mo, 0); // hash table size
+    push_u32(&mut mo, strings_offset); // hash table offset
+    for (length, offset) in originals.into_iter().chain(translations) {
+        push_u32(&mut mo, length);
+        push_u32(&mut mo, offset);
+    }
+    mo.extend_from_slice(&strings);
+    mo
+}
+
+fn push_u32(mo: &mut Vec<u8>, value: usize) {
+    let value = u32::try_from(value).expect("expected the mo file to be smaller than 4GiB");
+    mo.extend_from_slice(&value.to_le_bytes());
+}
@@ -0,0 +1,167 @@
+//! Rewriting the header entry of generated `pot` files.
+
+use super::po::{escape, unquote};
+use i18n_config::{Crate, GettextConfig};
+
+const POT_CREATION_DATE: &str = "POT-Creation-Date";
+
+/// The header entry (`msgid ""`) of a `pot` file, split into the
+/// lines before it, its fields, and the lines after it.
+struct PotHeader<'a> {
+    before: Vec<&'a str>,
+    fields: Vec<(String, String)>,
+    after: Vec<&'a str>,
+}
+
+impl<'a> PotHeader<'a> {
+    fn parse(pot: &'a str) -> Option<Self> {
+        let lines: Vec<&str> = pot.lines().collect();
+        let start = lines
+            .windows(2)
+            .position(|window| window[0] == "msgid \"\"" && window[1].starts_with("msgstr "))?;
+
+        let mut msgstr = unquote(lines[start + 1].trim_start_matches("msgstr ").trim());
+        let mut end = start + 2;
+        while let Some(line) = lines.get(end).filter(|line| line.starts_with('"')) {
+            msgstr.push_str(&unquote(line.trim()));
+            end += 1;
+        }
+
+        let fields = msgstr
+            .split('\n')
+            .filter(|field| !field.is_empty())
+            .map(|field| match field.split_once(':') {
+                Some((key, value)) => (key.trim().to_string(), value.trim().to_string()),
+                None => (field.to_string(), String::new()),
+            })
+            .collect();
+
+        Some(Self {
+            before: lines[..start].to_vec(),
+            fields,
+            after: lines[end..].to_vec(),
+        })
+    }
+
+    fn set(&mut self, key: &str, value: &str) {
+        match self.fields.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value.to_string(),
+            None => self.fields.push((key.to_string(), value.to_string())),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn write(&self) -> String {
+        let mut lines: Vec<String> = self.before.iter().map(|line| line.to_string()).collect();
+        lines.push("msgid \"\"".to_string());
+        lines.push("msgstr \"\"".to_string());
+        lines.extend(
+            self.fields
+                .iter()
+                .map(|(key, value)| format!("\"{}\\n\"", escape(&format!("{key}: {value}")))),
+        );
+        lines.extend(self.after.iter().map(|line| line.to_string()));
+        let mut pot = lines.join("\n");
+        pot.push('\n');
+        pot
+    }
+}
+
+/// Read the `SOURCE_DATE_EPOCH` environment variable (see
+/// <https://reproducible-builds.org/specs/source-date-epoch/>) and
+/// format it as a gettext header date.
+fn source_date_epoch() -> Option<String> {
+    let epoch: u64 = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(format_date(epoch))
+}
+
+/// Format seconds since the unix epoch as a gettext header date in
+/// UTC, e.g. `2020-08-22 18:16+0000`.
+pub(crate) fn format_date(epoch: u64) -> String {
+    let days = (epoch / 86400) as i64;
+    let seconds_of_day = epoch % 86400;
+
+    // Convert days since the epoch to a civil date, see
+    // <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}+0000",
+        seconds_of_day / 3600,
+        (seconds_of_day % 3600) / 60
+    )
+}
+
+/// Apply the header metadata from the `gettext_config` to the pot
+/// file contents `pot`, and return the updated contents.
+///
+/// If the `SOURCE_DATE_EPOCH` environment variable is set, it is used
+/// for the `POT-Creation-Date`. Otherwise, if the `previous` contents
+/// of the pot file only differ by the `POT-Creation-Date`, the
+/// `previous` contents are returned unchanged, to avoid needless
+/// changes to the file.
+pub(crate) fn update_pot_header(
+    crt: &Crate,
+    gettext_config: &GettextConfig,
+    pot: &str,
+    previous: Option<&str>,
+) -> String {
+    let mut header = match PotHeader::parse(pot) {
+        Some(header) => header,
+        None => {
+            log::warn!("Unable to find the header entry in the generated pot file");
+            return pot.to_string();
+        }
+    };
+
+    let pot_header = &gettext_config.pot_header;
+    let project_id_version = pot_header
+        .project_id_version
+        .clone()
+        .unwrap_or_else(|| format!("{} {}", crt.name, crt.version));
+    header.set("Project-Id-Version", &project_id_version);
+    if let Some(language_team) = &pot_header.language_team {
+        header.set("Language-Team", language_team);
+    }
+    for (key, value) in &pot_header.headers {
+        header.set(key, value);
+    }
+
+    if let Some(date) = source_date_epoch() {
+        header.set(POT_CREATION_DATE, &date);
+        return header.write();
+    }
+
+    if let Some(previous) = previous {
+        let previous_date = PotHeader::parse(previous)
+            .and_then(|previous_header| previous_header.get(POT_CREATION_DATE).map(String::from));
+        let current_date = header.get(POT_CREATION_DATE).map(String::from);
+        if let (Some(previous_date), Some(current_date)) = (previous_date, current_date) {
+            header.set(POT_CREATION_DATE, &previous_date);
+            if header.write() == previous {
+                return previous.to_string();
+            }
+            header.set(POT_CREATION_DATE, &current_date);
+        }
+    }
+
+    header.write()
+}
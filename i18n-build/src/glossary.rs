@@ -0,0 +1,366 @@
+//! Terminology consistency checking, used by the `cargo i18n
+//! lint-glossary` command. A [Glossary] maps terms of the fallback
+//! language to their approved translations in each language, and to
+//! the variants which translators should not use. It is read from the
+//! `glossary` file in the crate's i18n config file, which is either a
+//! TOML file:
+//!
+//! ```toml
+//! [[term]]
+//! source = "file"
+//! translations = { fr = "fichier", de = "Datei" }
+//! disallowed = { fr = ["document", "dossier"] }
+//! ```
+//!
+//! or a CSV file (with a `.csv` extension) with a row for each term
+//! and language, and the disallowed variants separated by `|`:
+//!
+//! ```csv
+//! source,language,translation,disallowed
+//! file,fr,fichier,document|dossier
+//! file,de,Datei,
+//! ```
+//!
+//! ⚠️ *This module requires the following crate features to be activated: `glossary`.*
+
+use crate::fluent_impl::{parse_fluent_file, FluentLayout};
+use crate::gettext_impl::po::{self, PoEntry};
+use crate::lint::{entry_patterns, pattern_text};
+use crate::system::{Severity, ValidationIssue};
+
+use anyhow::{anyhow, Context, Result};
+use i18n_config::Crate;
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::read_to_string,
+    path::Path,
+};
+use tr::tr;
+use unic_langid::LanguageIdentifier;
+
+/// The approved and disallowed translations of terms, see the
+/// [module documentation](self).
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Glossary {
+    /// The terms of the glossary, the `[[term]]` tables of a TOML
+    /// glossary file.
+    #[serde(default, rename = "term")]
+    pub terms: Vec<GlossaryTerm>,
+}
+
+/// A term of a [Glossary].
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GlossaryTerm {
+    /// The term in the fallback language.
+    pub source: String,
+    /// The approved translation of the term in each language.
+    #[serde(default)]
+    pub translations: BTreeMap<LanguageIdentifier, String>,
+    /// The translations of the term which should not be used in each
+    /// language.
+    #[serde(default)]
+    pub disallowed: BTreeMap<LanguageIdentifier, Vec<String>>,
+}
+
+impl Glossary {
+    /// Read the glossary file at `path`, which is parsed as a CSV
+    /// file if it has the `csv` extension, or as a TOML file
+    /// otherwise.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = read_to_string(path).with_context(|| {
+            tr!(
+                // {0} is the path to the glossary file.
+                "Unable to read \"{0}\".",
+                path.to_string_lossy()
+            )
+        })?;
+        let glossary = if path.extension().is_some_and(|extension| extension == "csv") {
+            Self::from_csv(&contents)
+        } else {
+            Self::from_toml(&contents)
+        };
+        glossary.with_context(|| {
+            tr!(
+                // {0} is the path to the glossary file.
+                "Unable to parse the glossary \"{0}\".",
+                path.to_string_lossy()
+            )
+        })
+    }
+
+    /// Parse a glossary from the contents of a TOML glossary file.
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        Ok(basic_toml::from_str(toml)?)
+    }
+
+    /// Parse a glossary from the contents of a CSV glossary file,
+    /// which has a header row naming its `source`, `language`,
+    /// `translation` and `disallowed` columns. The `translation` and
+    /// `disallowed` columns are optional.
+    pub fn from_csv(csv: &str) -> Result<Self> {
+        let mut rows = csv.lines().filter(|line| !line.trim().is_empty());
+        let header = csv_fields(rows.next().unwrap_or_default());
+        let column = |name: &str| header.iter().position(|column| column.trim() == name);
+        let missing_column = |name: &str| {
+            anyhow!(tr!(
+                // {0} is the name of the column.
+                "The glossary has no \"{0}\" column.",
+                name
+            ))
+        };
+        let source_column = column("source").ok_or_else(|| missing_column("source"))?;
+        let language_column = column("language").ok_or_else(|| missing_column("language"))?;
+        let translation_column = column("translation");
+        let disallowed_column = column("disallowed");
+
+        let mut glossary = Glossary::default();
+        for row in rows {
+            let fields = csv_fields(row);
+            let field = |column: Option<usize>| {
+                column
+                    .and_then(|column| fields.get(column))
+                    .map(|field| field.trim())
+                    .unwrap_or_default()
+            };
+            let source = field(Some(source_column));
+            let language: LanguageIdentifier = field(Some(language_column)).parse()?;
+            let term = match glossary
+                .terms
+                .iter_mut()
+                .position(|term| term.source == source)
+            {
+                Some(index) => &mut glossary.terms[index],
+                None => {
+                    glossary.terms.push(GlossaryTerm {
+                        source: source.to_string(),
+                        translations: BTreeMap::new(),
+                        disallowed: BTreeMap::new(),
+                    });
+                    glossary.terms.last_mut().expect("expected a term")
+                }
+            };
+            let translation = field(translation_column);
+            if !translation.is_empty() {
+                term.translations
+                    .insert(language.clone(), translation.to_string());
+            }
+            let disallowed: Vec<String> = field(disallowed_column)
+                .split('|')
+                .map(str::trim)
+                .filter(|variant| !variant.is_empty())
+                .map(ToString::to_string)
+                .collect();
+            if !disallowed.is_empty() {
+                term.disallowed
+                    .entry(language)
+                    .or_default()
+                    .extend(disallowed);
+            }
+        }
+        Ok(glossary)
+    }
+
+    /// Check the `translation` into the `language` of the message
+    /// with the `id` and `source` text in the fallback language.
+    /// Disallowed variants of the terms in the `source` are errors,
+    /// and translations which don't use the approved translation of
+    /// a term are warnings.
+    fn check(
+        &self,
+        language: &LanguageIdentifier,
+        id: &str,
+        source: &str,
+        translation: &str,
+        path: &Path,
+        issues: &mut Vec<ValidationIssue>,
+    ) {
+        for term in self
+            .terms
+            .iter()
+            .filter(|term| contains_term(source, &term.source))
+        {
+            let approved = term.translations.get(language);
+            for variant in term
+                .disallowed
+                .get(language)
+                .into_iter()
+                .flatten()
+                .filter(|variant| contains_term(translation, variant))
+            {
+                issues.push(ValidationIssue {
+                    path: Some(path.to_path_buf()),
+                    message: match approved {
+                        Some(approved) => tr!(
+                            // {0} is the id of the message, {1} is the disallowed translation, {2} is the term, and {3} is its approved translation.
+                            "\"{0}\" uses the disallowed translation \"{1}\" of the term \"{2}\", instead of \"{3}\".",
+                            id,
+                            variant,
+                            term.source,
+                            approved
+                        ),
+                        None => tr!(
+                            // {0} is the id of the message, {1} is the disallowed translation, and {2} is the term.
+                            "\"{0}\" uses the disallowed translation \"{1}\" of the term \"{2}\".",
+                            id,
+                            variant,
+                            term.source
+                        ),
+                    },
+                    severity: Severity::Error,
+                });
+            }
+            if let Some(approved) = approved {
+                if !contains_term(translation, approved) {
+                    issues.push(ValidationIssue {
+                        path: Some(path.to_path_buf()),
+                        message: tr!(
+                            // {0} is the id of the message, {1} is the approved translation, and {2} is the term.
+                            "\"{0}\" doesn't use the approved translation \"{1}\" of the term \"{2}\".",
+                            id,
+                            approved,
+                            term.source
+                        ),
+                        severity: Severity::Warning,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Check the translations of the crate `crt` against the glossary in
+/// its i18n config file, returning the problems which were found: the
+/// translations which use a disallowed variant of a term (errors),
+/// and those which don't use the approved translation of a term
+/// (warnings). Both the fluent and gettext translations are checked.
+pub fn lint_glossary(crt: &Crate) -> Result<Vec<ValidationIssue>> {
+    let config = crt.config_or_err()?;
+    let glossary_path = match &config.glossary {
+        Some(glossary_path) => crt.path.join(glossary_path),
+        None => {
+            return Err(anyhow!(tr!(
+                // {0} is the name of the crate.
+                "There is no \"glossary\" in the i18n config file of the crate \"{0}\".",
+                crt.name
+            )));
+        }
+    };
+    let glossary = Glossary::from_file(&glossary_path)?;
+    let fallback_language = &config.fallback_language;
+
+    let mut issues = Vec::new();
+    if let Some(fluent_config) = &config.fluent {
+        let layout = FluentLayout::new(crt, fluent_config)?;
+        for language in layout
+            .languages()?
+            .into_iter()
+            .filter(|language| language != fallback_language)
+        {
+            for file_path in layout.file_paths(&language)? {
+                let path = layout.path(&language, &file_path);
+                let (resource, fallback_resource) = match (
+                    parse_fluent_file(&path)?,
+                    parse_fluent_file(&layout.path(fallback_language, &file_path))?,
+                ) {
+                    (Some(resource), Some(fallback_resource)) => (resource, fallback_resource),
+                    _ => continue,
+                };
+                let fallback_patterns: HashMap<_, _> = entry_patterns(&fallback_resource.body)
+                    .into_iter()
+                    .collect();
+                for (id, pattern) in entry_patterns(&resource.body) {
+                    if let Some(fallback_pattern) = fallback_patterns.get(&id) {
+                        glossary.check(
+                            &language,
+                            &id,
+                            &pattern_text(fallback_pattern),
+                            &pattern_text(pattern),
+                            &path,
+                            &mut issues,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(gettext_config) = &config.gettext {
+        let po_dir = crt.path.join(gettext_config.po_dir());
+        for target_language in &gettext_config.target_languages {
+            let language: LanguageIdentifier = target_language.parse()?;
+            let po_path = po_dir
+                .join(target_language)
+                .join(crt.module_name())
+                .with_extension("po");
+            if !po_path.exists() {
+                continue;
+            }
+            let po = read_to_string(&po_path).with_context(|| {
+                tr!(
+                    // {0} is the path to the po file.
+                    "Unable to read \"{0}\".",
+                    po_path.to_string_lossy()
+                )
+            })?;
+            for entry in po::entries(&po).map(PoEntry::parse) {
+                if entry.is_header() || entry.is_obsolete() {
+                    continue;
+                }
+                let msgid = entry.msgid().unwrap_or_default();
+                let source = std::iter::once(msgid.clone())
+                    .chain(entry.msgid_plural())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let translation = entry
+                    .msgstr()
+                    .into_iter()
+                    .chain(entry.msgstr_plural())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                if translation.trim().is_empty() {
+                    continue;
+                }
+                glossary.check(
+                    &language,
+                    &msgid,
+                    &source,
+                    &translation,
+                    &po_path,
+                    &mut issues,
+                );
+            }
+        }
+    }
+    Ok(issues)
+}
+
+/// Whether the `text` contains the `term` as a whole word (or
+/// words), ignoring case.
+fn contains_term(text: &str, term: &str) -> bool {
+    let text = text.to_lowercase();
+    let term = term.to_lowercase();
+    !term.is_empty()
+        && text.match_indices(&term).any(|(start, _)| {
+            !text[..start].ends_with(char::is_alphanumeric)
+                && !text[start + term.len()..].starts_with(char::is_alphanumeric)
+        })
+}
+
+/// Split a `row` of a CSV file into its fields, which may be quoted
+/// to contain commas, with `""` for a quote within a quoted field.
+fn csv_fields(row: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = row.chars().peekable();
+    while let Some(c) = chars.next() {
+        let field = fields.last_mut().expect("expected a field");
+        match c {
+            '"' if quoted && chars.next_if_eq(&'"').is_some() => field.push('"'),
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => field.push(c),
+        }
+    }
+    fields
+}
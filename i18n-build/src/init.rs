@@ -0,0 +1,315 @@
+//! Scaffolding for setting up the localization of a crate, used by
+//! the `cargo i18n init` command.
+
+use crate::error::PathError;
+use crate::util;
+
+use anyhow::{anyhow, Context, Result};
+use i18n_config::{Crate, I18nConfig};
+use std::{
+    fmt::Display,
+    fs::{read_to_string, write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tr::tr;
+
+/// The version requirements used for the dependencies added to
+/// `Cargo.toml` by [init()].
+const I18N_EMBED_VERSION: &str = "0.15";
+const I18N_EMBED_FL_VERSION: &str = "0.9";
+const RUST_EMBED_VERSION: &str = "8";
+const TR_VERSION: &str = "0.1";
+
+/// The localization system to set up using [init()].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalizationSystem {
+    /// The [fluent](https://projectfluent.org/) localization system.
+    Fluent,
+    /// The gettext localization system.
+    Gettext,
+}
+
+impl Display for LocalizationSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LocalizationSystem::Fluent => write!(f, "fluent"),
+            LocalizationSystem::Gettext => write!(f, "gettext"),
+        }
+    }
+}
+
+impl FromStr for LocalizationSystem {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "fluent" => Ok(LocalizationSystem::Fluent),
+            "gettext" => Ok(LocalizationSystem::Gettext),
+            _ => Err(anyhow!(tr!(
+                // {0} is the name of the localization system which was requested.
+                "Unknown localization system \"{0}\", expected \"fluent\" or \"gettext\".",
+                s
+            ))),
+        }
+    }
+}
+
+/// Options for [init()].
+#[derive(Debug, Clone)]
+pub struct InitOptions {
+    /// The localization system to use.
+    pub system: LocalizationSystem,
+    /// The language used in the source code, and which all messages
+    /// must be available in.
+    pub fallback_language: String,
+    /// The languages to translate into (only used by the gettext
+    /// system).
+    pub target_languages: Vec<String>,
+    /// Add the dependencies required to embed the localizations to
+    /// the crate's `Cargo.toml`.
+    pub patch_cargo_toml: bool,
+}
+
+impl Default for InitOptions {
+    fn default() -> Self {
+        Self {
+            system: LocalizationSystem::Fluent,
+            fallback_language: "en-US".to_string(),
+            target_languages: Vec::new(),
+            patch_cargo_toml: false,
+        }
+    }
+}
+
+/// Set up the localization of the crate at `crate_path`, creating the
+/// i18n config file at `config_file_path` (relative to the crate),
+/// the assets directory containing a sample message (for the fluent
+/// system) or the output directory (for the gettext system), and
+/// optionally adding the required dependencies to `Cargo.toml`.
+///
+/// Returns the paths of the files and directories which were created
+/// or modified. Fails without changing anything if the config file
+/// already exists.
+pub fn init<P1: AsRef<Path>, P2: AsRef<Path>>(
+    crate_path: P1,
+    config_file_path: P2,
+    options: &InitOptions,
+) -> Result<Vec<PathBuf>> {
+    let crate_path = crate_path.as_ref();
+    let crt = Crate::from(crate_path.to_path_buf(), None, config_file_path.as_ref())?;
+    let config_file_path = crate_path.join(config_file_path);
+    if config_file_path.exists() {
+        return Err(anyhow!(tr!(
+            // {0} is the path to the i18n config file.
+            "The i18n config file \"{0}\" already exists.",
+            config_file_path.to_string_lossy()
+        )));
+    }
+    if crt.i18n_config.is_some() {
+        return Err(anyhow!(tr!(
+            // {0} is the name of the crate.
+            "The crate \"{0}\" already has an i18n config in the \"[package.metadata.i18n]\" table of its Cargo.toml.",
+            crt.name
+        )));
+    }
+
+    // The default domain used by the fluent system is the crate name
+    // as it is used in source code.
+    let domain = crt.name.replace('-', "_");
+
+    let config = match options.system {
+        LocalizationSystem::Fluent => fluent_config(&options.fallback_language),
+        LocalizationSystem::Gettext => {
+            gettext_config(&options.fallback_language, &options.target_languages)
+        }
+    };
+
+    write(&config_file_path, config)
+        .map_err(|err| PathError::cannot_create_file(&config_file_path, err))?;
+    // Ensure the generated config is valid (e.g. the language
+    // identifiers), before creating anything else.
+    if let Err(err) = I18nConfig::from_file(&config_file_path) {
+        util::remove_file_if_exists(&config_file_path)?;
+        return Err(err).with_context(|| {
+            tr!("Unable to create a valid i18n config file using the specified options.")
+        });
+    }
+    let mut changed = vec![config_file_path];
+
+    match options.system {
+        LocalizationSystem::Fluent => {
+            let language_dir = crate_path.join("i18n").join(&options.fallback_language);
+            util::create_dir_all_if_not_exists(&language_dir)?;
+            let ftl_path = language_dir.join(format!("{domain}.ftl"));
+            if !ftl_path.exists() {
+                write(&ftl_path, "hello-world = Hello World!\n")
+                    .map_err(|err| PathError::cannot_create_file(&ftl_path, err))?;
+                changed.push(ftl_path);
+            }
+        }
+        LocalizationSystem::Gettext => {
+            let output_dir = crate_path.join("i18n");
+            util::create_dir_all_if_not_exists(&output_dir)?;
+            changed.push(output_dir);
+        }
+    }
+
+    if options.patch_cargo_toml {
+        let cargo_toml_path = crate_path.join("Cargo.toml");
+        if patch_cargo_toml(&cargo_toml_path, options.system)? {
+            changed.push(cargo_toml_path);
+        }
+    }
+
+    Ok(changed)
+}
+
+fn fluent_config(fallback_language: &str) -> String {
+    format!(
+        r#"# The language identifier of the language used in the source code,
+# and the primary fallback language (for which all messages must be
+# present).
+fallback_language = "{fallback_language}"
+
+# Use the fluent localization system.
+[fluent]
+# The path to the assets directory, relative to this file. The paths
+# inside the assets directory are structured like so:
+# `assets_dir/{{language}}/{{domain}}.ftl`
+assets_dir = "i18n"
+"#
+    )
+}
+
+fn gettext_config(fallback_language: &str, target_languages: &[String]) -> String {
+    let target_languages: Vec<String> = target_languages
+        .iter()
+        .map(|language| format!("{language:?}"))
+        .collect();
+    format!(
+        r#"# The language identifier of the language used in the source code.
+fallback_language = "{fallback_language}"
+
+# Use the gettext localization system.
+[gettext]
+# The languages that the software will be translated into.
+target_languages = [{target_languages}]
+
+# The path to the output directory, relative to this file.
+output_dir = "i18n"
+"#,
+        target_languages = target_languages.join(", ")
+    )
+}
+
+/// The dependencies required to embed localizations using the
+/// `system`, as lines in the `[dependencies]` table of a
+/// `Cargo.toml`.
+fn dependencies(system: LocalizationSystem) -> Vec<(&'static str, String)> {
+    match system {
+        LocalizationSystem::Fluent => vec![
+            (
+                "i18n-embed",
+                format!(
+                    r#"i18n-embed = {{ version = "{I18N_EMBED_VERSION}", features = ["fluent-system", "desktop-requester"] }}"#
+                ),
+            ),
+            (
+                "i18n-embed-fl",
+                format!(r#"i18n-embed-fl = "{I18N_EMBED_FL_VERSION}""#),
+            ),
+            (
+                "rust-embed",
+                format!(r#"rust-embed = "{RUST_EMBED_VERSION}""#),
+            ),
+        ],
+        LocalizationSystem::Gettext => vec![
+            (
+                "i18n-embed",
+                format!(
+                    r#"i18n-embed = {{ version = "{I18N_EMBED_VERSION}", features = ["gettext-system", "desktop-requester"] }}"#
+                ),
+            ),
+            (
+                "tr",
+                format!(
+                    r#"tr = {{ version = "{TR_VERSION}", default-features = false, features = ["gettext"] }}"#
+                ),
+            ),
+            (
+                "rust-embed",
+                format!(r#"rust-embed = "{RUST_EMBED_VERSION}""#),
+            ),
+        ],
+    }
+}
+
+/// Add the dependencies required by the `system` which are not
+/// already present to the `[dependencies]` table of the `Cargo.toml`
+/// at `cargo_toml_path`, preserving its formatting. Returns whether
+/// the file was modified.
+fn patch_cargo_toml(cargo_toml_path: &Path, system: LocalizationSystem) -> Result<bool> {
+    let cargo_toml = read_to_string(cargo_toml_path).with_context(|| {
+        tr!(
+            // {0} is the path to the Cargo.toml file.
+            "Unable to read \"{0}\".",
+            cargo_toml_path.to_string_lossy()
+        )
+    })?;
+    let mut lines: Vec<&str> = cargo_toml.lines().collect();
+
+    let dependencies_start = lines
+        .iter()
+        .position(|line| line.trim() == "[dependencies]");
+    let dependencies_end = dependencies_start.map(|start| {
+        lines[start + 1..]
+            .iter()
+            .position(|line| line.trim_start().starts_with('['))
+            .map_or(lines.len(), |offset| start + 1 + offset)
+    });
+    let existing: Vec<&str> = match (dependencies_start, dependencies_end) {
+        (Some(start), Some(end)) => lines[start + 1..end].to_vec(),
+        _ => Vec::new(),
+    };
+
+    let missing: Vec<String> = dependencies(system)
+        .into_iter()
+        .filter(|(name, _)| {
+            !existing.iter().any(|line| {
+                line.split('=')
+                    .next()
+                    .is_some_and(|key| key.trim() == *name)
+            })
+        })
+        .map(|(_, line)| line)
+        .collect();
+
+    if missing.is_empty() {
+        return Ok(false);
+    }
+
+    match dependencies_end {
+        Some(end) => {
+            // Insert after the last non-empty line in the table.
+            let mut insert_at = end;
+            while insert_at > 0 && lines[insert_at - 1].trim().is_empty() {
+                insert_at -= 1;
+            }
+            for (i, line) in missing.iter().enumerate() {
+                lines.insert(insert_at + i, line);
+            }
+        }
+        None => {
+            lines.push("");
+            lines.push("[dependencies]");
+            lines.extend(missing.iter().map(String::as_str));
+        }
+    }
+
+    let mut patched = lines.join("\n");
+    patched.push('\n');
+    write(cargo_toml_path, patched)
+        .map_err(|err| PathError::cannot_create_file(cargo_toml_path, err))?;
+    Ok(true)
+}
@@ -14,19 +14,81 @@
 //! + `localize`
 //!   + Enables the runtime localization of this library using
 //!     [localize()](#localize()) function via the
-//!     [i18n-embed](https://crates.io/crates/i18n-embed) crate
-
+//!     [i18n-embed](https://crates.io/crates/i18n-embed) crate, and
+//!     [system::LocalizationSystem::language_loader()]
+//! + `translate`
+//!   + Enables the [translate::HttpTranslator] machine translation
+//!     backend, which uses the HTTP API of a
+//!     [LibreTranslate](https://libretranslate.com/) compatible service
+//! + `builtin-gettext-tools`
+//!   + Updates the `po` files and compiles them into `mo` files using
+//!     the built-in [gettext_impl::msgmerge] and
+//!     [gettext_impl::po2mo] modules instead of the `msgmerge` and
+//!     `msgfmt` tools, unless their executables are explicitly
+//!     configured
+//! + `compress-assets`
+//!   + Enables the [compress] module, which compresses the
+//!     localization assets within a build script so that they can
+//!     be embedded with `i18n-embed`'s `CompressedRustEmbedAssets`
+//! + `glossary`
+//!   + Enables the [glossary] module, which checks the translations
+//!     of a crate against a glossary of terms
+
+pub mod add_language;
+pub mod codegen;
+#[cfg(feature = "compress-assets")]
+pub mod compress;
+pub mod doctor;
 pub mod error;
+pub(crate) mod fluent_impl;
 pub mod gettext_impl;
+#[cfg(feature = "glossary")]
+pub mod glossary;
+pub mod init;
+pub mod lint;
+pub mod migrate;
+pub mod progress;
+pub mod pseudo;
+pub mod report;
+pub mod summary;
+pub mod system;
+pub mod translate;
+pub mod usage;
 pub mod util;
 pub mod watch;
+pub mod xliff;
+
+use anyhow::{Context, Result};
+use i18n_config::{Crate, Workspace};
+use std::{num::NonZeroUsize, path::Path, time::Instant};
+use summary::RunSummary;
+use tr::tr;
 
-use anyhow::Result;
-use i18n_config::Crate;
+pub use watch::emit_cargo_rerun_directives;
 
 /// Run the i18n build process for the provided crate, which must
 /// contain an i18n config.
+///
+/// External commands are run in parallel using the default number of
+/// jobs, see [util::default_jobs()] and [run_with_jobs()].
 pub fn run(crt: Crate) -> Result<()> {
+    run_with_jobs(crt, util::default_jobs())
+}
+
+/// Run the i18n build process for the provided crate, which must
+/// contain an i18n config, running at most `jobs` external commands
+/// (such as `xtr`) in parallel.
+pub fn run_with_jobs(crt: Crate, jobs: NonZeroUsize) -> Result<()> {
+    run_with_summary(crt, jobs).map(|_| ())
+}
+
+/// Run the i18n build process for the provided crate, see
+/// [run_with_jobs()], returning a summary of what happened (such as
+/// the changes to the messages of each language) to present to the
+/// user.
+pub fn run_with_summary(crt: Crate, jobs: NonZeroUsize) -> Result<RunSummary> {
+    let start = Instant::now();
+    let mut summary = RunSummary::default();
     let mut crates: Vec<Crate> = Vec::new();
 
     let mut parent = crt.find_parent();
@@ -53,12 +115,58 @@ pub fn run(crt: Crate) -> Result<()> {
 
     let last_child_crt = parent;
 
-    let i18n_config = last_child_crt.config_or_err()?;
-    if i18n_config.gettext.is_some() {
-        gettext_impl::run(last_child_crt)?;
+    for system in system::crate_systems(last_child_crt)? {
+        system.extract(last_child_crt, jobs, &mut summary)?;
+    }
+
+    summary.duration = start.elapsed();
+    Ok(summary)
+}
+
+/// Run the i18n build process for each member of the `workspace`
+/// which is to be localized, see [Workspace::member_crates()]. The
+/// `config_file_path` is the path to each member's i18n config file
+/// relative to its root directory.
+pub fn run_workspace<P: AsRef<Path>>(
+    workspace: &Workspace,
+    config_file_path: P,
+    jobs: NonZeroUsize,
+) -> Result<()> {
+    run_workspace_with_summary(workspace, config_file_path, jobs).map(|_| ())
+}
+
+/// Run the i18n build process for each member of the `workspace`, see
+/// [run_workspace()], returning a summary of what happened.
+pub fn run_workspace_with_summary<P: AsRef<Path>>(
+    workspace: &Workspace,
+    config_file_path: P,
+    jobs: NonZeroUsize,
+) -> Result<RunSummary> {
+    let start = Instant::now();
+    let mut summary = RunSummary::default();
+    let crates = workspace.member_crates(config_file_path)?;
+
+    if crates.is_empty() {
+        log::warn!(
+            "None of the members of the workspace at \"{0}\" have an i18n config.",
+            workspace.root.to_string_lossy()
+        );
+    }
+
+    for crt in crates {
+        let crt_string = crt.to_string();
+        let crate_summary = run_with_summary(crt, jobs).with_context(|| {
+            tr!(
+                // {0} is the crate name and path.
+                "There was a problem localizing the workspace member {0}.",
+                crt_string
+            )
+        })?;
+        summary.crates.extend(crate_summary.crates);
     }
 
-    Ok(())
+    summary.duration = start.elapsed();
+    Ok(summary)
 }
 
 #[cfg(feature = "localize")]
@@ -87,7 +195,7 @@ mod localize_feature {
     ///
     /// ⚠️ *This API requires the following crate features to be activated: `localize`.*
     pub fn localizer() -> DefaultLocalizer<'static> {
-        DefaultLocalizer::new(&*language_loader(), &TRANSLATIONS)
+        DefaultLocalizer::new(language_loader(), &TRANSLATIONS)
     }
 }
 
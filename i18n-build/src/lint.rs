@@ -0,0 +1,379 @@
+//! Linting of the fluent files of a crate, used by the `cargo i18n
+//! lint` command, to find common mistakes in translations which are
+//! not syntax errors, such as variables which don't exist in the
+//! message of the fallback language. The severity of each lint is
+//! configured in the [FluentLints] of the crate's i18n config file.
+
+use crate::fluent_impl::{parse_fluent_file, FluentLayout};
+use crate::system::{Severity, ValidationIssue};
+
+use anyhow::{Context, Result};
+use fluent_syntax::{
+    ast::{Entry, Expression, InlineExpression, Pattern, PatternElement},
+    parser,
+};
+use i18n_config::{Crate, FluentLints, LintLevel};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs::read_to_string,
+    path::Path,
+};
+use tr::tr;
+
+/// Lint the fluent files of each language of the crate `crt`,
+/// returning the problems which were found, with the severity
+/// configured for their lint in the [FluentLints]:
+///
+/// + `unknown-variables`: variables in a translation which are not
+///   in the message of the fallback language.
+/// + `protected-terms`: terms from the `protected_terms` in the
+///   fluent config which are in the message of the fallback
+///   language, but missing from its translation.
+/// + `double-spaces`: two or more consecutive spaces in the text of
+///   a message.
+/// + `trailing-whitespace`: whitespace at the end of a line.
+/// + `markup`: HTML tags or printf-style format specifiers (e.g.
+///   `%s`) in a translation which differ from those in the message
+///   of the fallback language.
+///
+/// Crates without a fluent config have no problems.
+pub fn lint(crt: &Crate) -> Result<Vec<ValidationIssue>> {
+    let config = crt.config_or_err()?;
+    let fluent_config = match &config.fluent {
+        Some(fluent_config) => fluent_config,
+        None => return Ok(Vec::new()),
+    };
+    let layout = FluentLayout::new(crt, fluent_config)?;
+    let lints = &fluent_config.lints;
+    let fallback_language = &config.fallback_language;
+
+    let mut issues = Vec::new();
+    for language in layout.languages()? {
+        for file_path in layout.file_paths(&language)? {
+            let path = layout.path(&language, &file_path);
+            if !path.exists() {
+                continue;
+            }
+            let source = read_to_string(&path).with_context(|| {
+                tr!(
+                    // {0} is the path to the fluent file.
+                    "Unable to read \"{0}\".",
+                    path.to_string_lossy()
+                )
+            })?;
+            let mut linter = Linter {
+                lints,
+                protected_terms: &fluent_config.protected_terms,
+                path: &path,
+                issues: &mut issues,
+            };
+            linter.lint_trailing_whitespace(&source);
+
+            // Syntax errors are reported by `cargo i18n validate`.
+            let resource = parser::parse(source).unwrap_or_else(|(resource, _)| resource);
+            let fallback_resource = if language == *fallback_language {
+                None
+            } else {
+                parse_fluent_file(&layout.path(fallback_language, &file_path))?
+            };
+            let fallback_patterns: HashMap<String, &Pattern<String>> = fallback_resource
+                .as_ref()
+                .map(|resource| entry_patterns(&resource.body).into_iter().collect())
+                .unwrap_or_default();
+            for (id, pattern) in entry_patterns(&resource.body) {
+                linter.lint_double_spaces(&id, pattern);
+                if let Some(fallback_pattern) = fallback_patterns.get(&id) {
+                    linter.lint_translation(&id, pattern, fallback_pattern);
+                }
+            }
+        }
+    }
+    Ok(issues)
+}
+
+/// The [Severity] of problems found by a lint with the `level`, or
+/// `None` if the lint is not checked.
+fn severity(level: LintLevel) -> Option<Severity> {
+    match level {
+        LintLevel::Allow => None,
+        LintLevel::Warning => Some(Severity::Warning),
+        LintLevel::Error => Some(Severity::Error),
+    }
+}
+
+/// Records the problems found in a fluent file.
+struct Linter<'a> {
+    lints: &'a FluentLints,
+    protected_terms: &'a [String],
+    path: &'a Path,
+    issues: &'a mut Vec<ValidationIssue>,
+}
+
+impl Linter<'_> {
+    fn report(&mut self, level: LintLevel, lint: &str, message: String) {
+        if let Some(severity) = severity(level) {
+            self.issues.push(ValidationIssue {
+                path: Some(self.path.to_path_buf()),
+                message: format!("{lint}: {message}"),
+                severity,
+            });
+        }
+    }
+
+    fn lint_trailing_whitespace(&mut self, source: &str) {
+        for (index, line) in source.lines().enumerate() {
+            if line.ends_with([' ', '\t']) {
+                self.report(
+                    self.lints.trailing_whitespace,
+                    "trailing-whitespace",
+                    tr!(
+                        // {0} is the line number in the fluent file.
+                        "Line {0} ends with whitespace.",
+                        index + 1
+                    ),
+                );
+            }
+        }
+    }
+
+    fn lint_double_spaces(&mut self, id: &str, pattern: &Pattern<String>) {
+        if pattern_text(pattern).contains("  ") {
+            self.report(
+                self.lints.double_spaces,
+                "double-spaces",
+                tr!(
+                    // {0} is the id of the fluent message.
+                    "The text of \"{0}\" contains consecutive spaces.",
+                    id
+                ),
+            );
+        }
+    }
+
+    /// Check the `pattern` of a translated message against the
+    /// `fallback_pattern` of the fallback language.
+    fn lint_translation(
+        &mut self,
+        id: &str,
+        pattern: &Pattern<String>,
+        fallback_pattern: &Pattern<String>,
+    ) {
+        let fallback_variables = pattern_variables(fallback_pattern);
+        let unknown_variables: Vec<String> = pattern_variables(pattern)
+            .difference(&fallback_variables)
+            .map(|variable| format!("${variable}"))
+            .collect();
+        if !unknown_variables.is_empty() {
+            self.report(
+                self.lints.unknown_variables,
+                "unknown-variables",
+                tr!(
+                    // {0} is the id of the fluent message, and {1} is the list of variables.
+                    "\"{0}\" uses variables which are not in the fallback language: {1}",
+                    id,
+                    unknown_variables.join(", ")
+                ),
+            );
+        }
+
+        let text = pattern_text(pattern);
+        let fallback_text = pattern_text(fallback_pattern);
+        let missing_terms: Vec<&str> = self
+            .protected_terms
+            .iter()
+            .filter(|term| fallback_text.contains(term.as_str()) && !text.contains(term.as_str()))
+            .map(String::as_str)
+            .collect();
+        if !missing_terms.is_empty() {
+            self.report(
+                self.lints.protected_terms,
+                "protected-terms",
+                tr!(
+                    // {0} is the id of the fluent message, and {1} is the list of terms.
+                    "\"{0}\" is missing protected terms which need to be left untranslated: {1}",
+                    id,
+                    missing_terms.join(", ")
+                ),
+            );
+        }
+
+        let tags = markup(&text);
+        let fallback_tags = markup(&fallback_text);
+        if tags != fallback_tags {
+            self.report(
+                self.lints.markup,
+                "markup",
+                tr!(
+                    // {0} is the id of the fluent message, {1} and {2} are lists of HTML tags and format specifiers.
+                    "The HTML tags and format specifiers of \"{0}\" ({1}) differ from the fallback language ({2}).",
+                    id,
+                    tags.into_iter().collect::<Vec<_>>().join(" "),
+                    fallback_tags.into_iter().collect::<Vec<_>>().join(" ")
+                ),
+            );
+        }
+    }
+}
+
+/// The patterns of the messages and terms in the `entries` with their
+/// id, including the patterns of their attributes with the id
+/// `{id}.{attribute}`.
+pub(crate) fn entry_patterns(entries: &[Entry<String>]) -> Vec<(String, &Pattern<String>)> {
+    let mut patterns = Vec::new();
+    for entry in entries {
+        let (id, value, attributes) = match entry {
+            Entry::Message(message) => (
+                message.id.name.clone(),
+                message.value.as_ref(),
+                &message.attributes,
+            ),
+            Entry::Term(term) => (
+                format!("-{}", term.id.name),
+                Some(&term.value),
+                &term.attributes,
+            ),
+            _ => continue,
+        };
+        if let Some(value) = value {
+            patterns.push((id.clone(), value));
+        }
+        for attribute in attributes {
+            patterns.push((format!("{id}.{}", attribute.id.name), &attribute.value));
+        }
+    }
+    patterns
+}
+
+/// The text of the `pattern`, including the text of each variant of
+/// its select expressions.
+pub(crate) fn pattern_text(pattern: &Pattern<String>) -> String {
+    let mut text = String::new();
+    for element in &pattern.elements {
+        match element {
+            PatternElement::TextElement { value } => text.push_str(value),
+            PatternElement::Placeable { expression } => {
+                if let Expression::Select { variants, .. } = expression {
+                    for variant in variants {
+                        text.push_str(&pattern_text(&variant.value));
+                        text.push('\n');
+                    }
+                }
+            }
+        }
+    }
+    text
+}
+
+/// The names of the variables referenced in the `pattern`.
+pub(crate) fn pattern_variables(pattern: &Pattern<String>) -> BTreeSet<String> {
+    let mut variables = BTreeSet::new();
+    visit_inline_expressions(pattern, &mut |expression| {
+        if let InlineExpression::VariableReference { id } = expression {
+            variables.insert(id.name.clone());
+        }
+    });
+    variables
+}
+
+/// The ids of the terms referenced in the `pattern`, with their
+/// leading `-`.
+pub(crate) fn pattern_terms(pattern: &Pattern<String>) -> BTreeSet<String> {
+    let mut terms = BTreeSet::new();
+    visit_inline_expressions(pattern, &mut |expression| {
+        if let InlineExpression::TermReference { id, .. } = expression {
+            terms.insert(format!("-{}", id.name));
+        }
+    });
+    terms
+}
+
+/// Call `visit` for each of the inline expressions in the `pattern`,
+/// including those nested in select expressions and in the arguments
+/// of function and term references.
+fn visit_inline_expressions(
+    pattern: &Pattern<String>,
+    visit: &mut dyn FnMut(&InlineExpression<String>),
+) {
+    for element in &pattern.elements {
+        if let PatternElement::Placeable { expression } = element {
+            visit_expression(expression, visit);
+        }
+    }
+}
+
+fn visit_expression(
+    expression: &Expression<String>,
+    visit: &mut dyn FnMut(&InlineExpression<String>),
+) {
+    match expression {
+        Expression::Select { selector, variants } => {
+            visit_inline_expression(selector, visit);
+            for variant in variants {
+                visit_inline_expressions(&variant.value, visit);
+            }
+        }
+        Expression::Inline(expression) => visit_inline_expression(expression, visit),
+    }
+}
+
+fn visit_inline_expression(
+    expression: &InlineExpression<String>,
+    visit: &mut dyn FnMut(&InlineExpression<String>),
+) {
+    visit(expression);
+    match expression {
+        InlineExpression::FunctionReference { arguments, .. }
+        | InlineExpression::TermReference {
+            arguments: Some(arguments),
+            ..
+        } => {
+            for argument in &arguments.positional {
+                visit_inline_expression(argument, visit);
+            }
+            for argument in &arguments.named {
+                visit_inline_expression(&argument.value, visit);
+            }
+        }
+        InlineExpression::Placeable { expression } => visit_expression(expression, visit),
+        _ => {}
+    }
+}
+
+/// The HTML tags (without their attributes, which may be translated)
+/// and printf-style format specifiers in the `text`.
+fn markup(text: &str) -> BTreeSet<String> {
+    let mut markup = BTreeSet::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '<' => {
+                let rest = &text[start + 1..];
+                let name_start = usize::from(rest.starts_with('/'));
+                let name_len = rest[name_start..]
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+                    .unwrap_or(rest.len() - name_start);
+                let name = &rest[name_start..name_start + name_len];
+                if name.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && rest[name_start + name_len..].contains('>')
+                {
+                    markup.insert(format!("<{}{}>", &rest[..name_start], name));
+                }
+            }
+            '%' => {
+                if chars.next_if(|(_, c)| *c == '%').is_some() {
+                    continue;
+                }
+                let rest = &text[start + 1..];
+                let len = rest
+                    .find(|c: char| !(c.is_ascii_digit() || c == '$' || c == '.'))
+                    .unwrap_or(rest.len());
+                if rest[len..].starts_with(['s', 'd', 'i', 'u', 'f', 'x', 'X', 'c', 'p', 'e', '@'])
+                {
+                    markup.insert(format!("%{}", &rest[..=len]));
+                }
+            }
+            _ => {}
+        }
+    }
+    markup
+}
@@ -0,0 +1,943 @@
+//! Migrating a crate from the gettext localization system to fluent,
+//! used by the `cargo i18n migrate` command.
+//!
+//! [migrate_to_fluent()] converts the crate's `po` files into `ftl`
+//! files, keeping the existing translations, and
+//! [rewrite_tr_calls()] creates a patch which replaces the `tr!()`
+//! calls in the crate's source code with the equivalent `fl!()` calls.
+
+use crate::error::PathError;
+use crate::fluent_impl::FluentLayout;
+use crate::gettext_impl::po::{self, PoEntry};
+use crate::util;
+
+use anyhow::{anyhow, Context, Result};
+use fluent_syntax::{
+    ast::{
+        Comment, Entry, Expression, Identifier, InlineExpression, Message, Pattern, PatternElement,
+        Resource, Variant, VariantKey,
+    },
+    serializer,
+};
+use i18n_config::{Crate, FluentConfig, GettextConfig};
+use log::warn;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write as _,
+    fs::{read_to_string, write, OpenOptions},
+    io::Write as _,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+use tr::tr;
+use unic_langid::LanguageIdentifier;
+
+/// The maximum number of words of a `msgid` used for the id of the
+/// fluent message generated from it.
+const MESSAGE_ID_WORDS: usize = 5;
+
+/// The number of unchanged lines included around each change in the
+/// patch created by [rewrite_tr_calls()].
+const PATCH_CONTEXT_LINES: usize = 3;
+
+/// Options for [migrate_to_fluent()].
+#[derive(Debug, Clone)]
+pub struct MigrateOptions {
+    /// The directory to create the fluent files in (relative to the
+    /// crate), if the crate doesn't already have a `[fluent]` config.
+    pub assets_dir: PathBuf,
+}
+
+impl Default for MigrateOptions {
+    fn default() -> Self {
+        Self {
+            assets_dir: PathBuf::from("i18n"),
+        }
+    }
+}
+
+/// A message which was migrated from gettext, identified by its
+/// `msgctxt` and `msgid`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MessageKey {
+    /// The `msgctxt` of the message.
+    pub context: Option<String>,
+    /// The `msgid` of the message.
+    pub msgid: String,
+}
+
+/// The outcome of [migrate_to_fluent()].
+#[derive(Debug, Clone, Default)]
+pub struct Migration {
+    /// The id of the fluent message generated for each gettext
+    /// message.
+    pub message_ids: HashMap<MessageKey, String>,
+    /// The paths of the files which were created or modified.
+    pub changed: Vec<PathBuf>,
+}
+
+/// A message in the gettext source language.
+struct SourceMessage {
+    key: MessageKey,
+    msgid_plural: Option<String>,
+    comments: Vec<String>,
+}
+
+/// Convert the gettext `po` files of the crate `crt` into fluent
+/// `ftl` files, one for the fallback language (containing the
+/// `msgid`s) and one for each of the gettext `target_languages`
+/// (containing their translations).
+///
+/// The id of each fluent message is generated from the first words
+/// of its `msgid`, prefixed by its `msgctxt` if it has one. The
+/// `tr!()` placeholders are converted into fluent variables: `{name}`
+/// becomes `{ $name }`, positional placeholders such as `{0}` become
+/// `{ $arg0 }`, and the count of plural messages becomes `{ $n }`.
+/// Plural messages are converted into a select expression on `$n`.
+///
+/// If the crate doesn't have a `[fluent]` config yet, one is
+/// appended to its i18n config file (using
+/// [MigrateOptions::assets_dir]). The `[gettext]` config is left in
+/// place, to be removed once the migration is complete.
+pub fn migrate_to_fluent(crt: &Crate, options: &MigrateOptions) -> Result<Migration> {
+    let config = crt.config_or_err()?;
+    let gettext_config = crt.gettext_config_or_err()?;
+
+    let layout = match &config.fluent {
+        Some(fluent_config) => FluentLayout::new(crt, fluent_config)?,
+        None => FluentLayout::new(
+            crt,
+            &FluentConfig {
+                assets_dir: options.assets_dir.clone(),
+                domain: None,
+                path_template: None,
+                required_languages: Vec::new(),
+                protected_terms: Vec::new(),
+                lints: Default::default(),
+                allow_missing_at_compile_time: false,
+                obsolete_policy: Default::default(),
+                use_isolating: true,
+                source_comments: None,
+                ignore: Vec::new(),
+            },
+        )?,
+    };
+
+    let target_languages = gettext_config
+        .target_languages
+        .iter()
+        .map(|language| language.parse::<LanguageIdentifier>())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut translations = Vec::new();
+    for language in &target_languages {
+        let po_path = po_path(crt, gettext_config, language);
+        if !po_path.exists() {
+            warn!(
+                "Skipping \"{0}\", its po file \"{1}\" does not exist.",
+                language,
+                po_path.to_string_lossy()
+            );
+            continue;
+        }
+        translations.push((language, read_po(&po_path)?));
+    }
+
+    let source_messages = source_messages(crt, gettext_config, &translations)?;
+    if source_messages.is_empty() {
+        return Err(anyhow!(tr!(
+            "Unable to find any gettext messages to migrate, run `cargo i18n` to create the pot file first."
+        )));
+    }
+
+    let mut migration = Migration::default();
+    let mut used_ids = HashSet::new();
+    for message in &source_messages {
+        let id = message_id(&message.key, &mut used_ids);
+        migration.message_ids.insert(message.key.clone(), id);
+    }
+
+    let mut resources = vec![(
+        &config.fallback_language,
+        fallback_resource(&source_messages, &migration.message_ids),
+    )];
+    for (language, po) in &translations {
+        resources.push((
+            language,
+            translated_resource(
+                &source_messages,
+                &migration.message_ids,
+                po,
+                gettext_config.use_fuzzy,
+            ),
+        ));
+    }
+
+    let domain_file_name = format!("{0}.ftl", layout.domain);
+    let language_path =
+        |language: &LanguageIdentifier| layout.path(language, Path::new(&domain_file_name));
+    for (language, _) in &resources {
+        let path = language_path(language);
+        if path.exists() {
+            return Err(anyhow!(tr!(
+                // {0} is the path to the fluent file for the language.
+                "The language file \"{0}\" already exists.",
+                path.to_string_lossy()
+            )));
+        }
+    }
+    for (language, resource) in &resources {
+        let path = language_path(language);
+        if let Some(parent) = path.parent() {
+            util::create_dir_all_if_not_exists(parent)?;
+        }
+        write(&path, serializer::serialize(resource))
+            .map_err(|err| PathError::cannot_create_file(&path, err))?;
+        migration.changed.push(path);
+    }
+
+    if config.fluent.is_none() {
+        let config_file_path = crt.path.join(&crt.config_file_path);
+        let assets_dir = options
+            .assets_dir
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        OpenOptions::new()
+            .append(true)
+            .open(&config_file_path)
+            .and_then(|mut file| write!(file, "\n[fluent]\nassets_dir = \"{assets_dir}\"\n"))
+            .map_err(|err| PathError::cannot_create_file(&config_file_path, err))?;
+        migration.changed.push(config_file_path);
+    }
+
+    Ok(migration)
+}
+
+fn po_path(crt: &Crate, gettext_config: &GettextConfig, language: &LanguageIdentifier) -> PathBuf {
+    crt.path
+        .join(gettext_config.po_dir())
+        .join(language.to_string())
+        .join(crt.module_name())
+        .with_extension("po")
+}
+
+fn read_po(path: &Path) -> Result<String> {
+    read_to_string(path).with_context(|| {
+        tr!(
+            // {0} is the path to the po or pot file.
+            "Unable to read \"{0}\".",
+            path.to_string_lossy()
+        )
+    })
+}
+
+/// The messages in the crate's `pot` file, or (if it hasn't been
+/// created) in its `po` files.
+fn source_messages(
+    crt: &Crate,
+    gettext_config: &GettextConfig,
+    translations: &[(&LanguageIdentifier, String)],
+) -> Result<Vec<SourceMessage>> {
+    let pot_path = crt
+        .path
+        .join(gettext_config.pot_dir())
+        .join(crt.module_name())
+        .with_extension("pot");
+    let sources = if pot_path.exists() {
+        vec![read_po(&pot_path)?]
+    } else {
+        translations.iter().map(|(_, po)| po.clone()).collect()
+    };
+
+    let mut keys = HashSet::new();
+    let mut messages = Vec::new();
+    for source in &sources {
+        for entry in po::entries(source).map(PoEntry::parse) {
+            if entry.is_obsolete() || entry.is_header() {
+                continue;
+            }
+            let msgid = match entry.msgid() {
+                Some(msgid) => msgid,
+                None => continue,
+            };
+            let key = MessageKey {
+                context: entry.msgctxt(),
+                msgid,
+            };
+            if keys.insert(key.clone()) {
+                messages.push(SourceMessage {
+                    key,
+                    msgid_plural: entry.msgid_plural(),
+                    comments: entry
+                        .comments()
+                        .into_iter()
+                        .filter(|comment| !comment.is_empty())
+                        .collect(),
+                });
+            }
+        }
+    }
+    Ok(messages)
+}
+
+/// Generate a unique fluent message id for the message with the
+/// `key`.
+fn message_id(key: &MessageKey, used_ids: &mut HashSet<String>) -> String {
+    let mut words: Vec<String> = Vec::new();
+    if let Some(context) = &key.context {
+        words.extend(id_words(context));
+    }
+    words.extend(id_words(&key.msgid).into_iter().take(MESSAGE_ID_WORDS));
+
+    let mut base = words.join("-");
+    if !base.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        base = if base.is_empty() {
+            "message".to_string()
+        } else {
+            format!("message-{base}")
+        };
+    }
+
+    let mut id = base.clone();
+    let mut n = 2;
+    while !used_ids.insert(id.clone()) {
+        id = format!("{base}-{n}");
+        n += 1;
+    }
+    id
+}
+
+/// The lowercase ASCII words in `s` which can be used in a fluent
+/// identifier, excluding any `{placeholders}` and apostrophes.
+fn id_words(s: &str) -> Vec<String> {
+    let mut text = String::with_capacity(s.len());
+    let mut in_placeholder = false;
+    for c in s.chars() {
+        match c {
+            '{' => in_placeholder = true,
+            '}' => {
+                in_placeholder = false;
+                text.push(' ');
+            }
+            '\'' | '\u{2019}' => {}
+            _ if !in_placeholder => text.push(c),
+            _ => {}
+        }
+    }
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect()
+}
+
+fn fallback_resource(
+    messages: &[SourceMessage],
+    message_ids: &HashMap<MessageKey, String>,
+) -> Resource<String> {
+    let body = messages
+        .iter()
+        .map(|message| {
+            let value = match &message.msgid_plural {
+                Some(msgid_plural) => {
+                    plural_pattern(&[message.key.msgid.as_str(), msgid_plural.as_str()])
+                }
+                None => convert_format(&message.key.msgid, false),
+            };
+            Entry::Message(Message {
+                id: Identifier {
+                    name: message_ids[&message.key].clone(),
+                },
+                value: Some(value),
+                attributes: Vec::new(),
+                comment: (!message.comments.is_empty()).then(|| Comment {
+                    content: message.comments.clone(),
+                }),
+            })
+        })
+        .collect();
+    Resource { body }
+}
+
+fn translated_resource(
+    messages: &[SourceMessage],
+    message_ids: &HashMap<MessageKey, String>,
+    po: &str,
+    use_fuzzy: bool,
+) -> Resource<String> {
+    let entries: HashMap<MessageKey, PoEntry> = po::entries(po)
+        .map(PoEntry::parse)
+        .filter(|entry| !entry.is_obsolete() && (use_fuzzy || !entry.is_fuzzy()))
+        .filter_map(|entry| {
+            let key = MessageKey {
+                context: entry.msgctxt(),
+                msgid: entry.msgid()?,
+            };
+            Some((key, entry))
+        })
+        .collect();
+
+    let mut body = Vec::new();
+    for message in messages {
+        let entry = match entries.get(&message.key) {
+            Some(entry) => entry,
+            None => continue,
+        };
+        let id = &message_ids[&message.key];
+        let value = if message.msgid_plural.is_some() {
+            let forms = entry.msgstr_plural();
+            if forms.iter().any(String::is_empty) {
+                continue;
+            }
+            match forms.len() {
+                0 => continue,
+                1 => convert_format(&forms[0], true),
+                2 => plural_pattern(&[forms[0].as_str(), forms[1].as_str()]),
+                _ => {
+                    warn!(
+                        "Skipping the translation of \"{0}\", plural messages with more than two forms need to be migrated manually.",
+                        id
+                    );
+                    continue;
+                }
+            }
+        } else {
+            match entry.msgstr() {
+                Some(msgstr) if !msgstr.is_empty() => convert_format(&msgstr, false),
+                _ => continue,
+            }
+        };
+        body.push(Entry::Message(Message {
+            id: Identifier { name: id.clone() },
+            value: Some(value),
+            attributes: Vec::new(),
+            comment: None,
+        }));
+    }
+    Resource { body }
+}
+
+/// A select expression on `$n`, choosing between the `one` and
+/// `other` plural `forms`.
+fn plural_pattern(forms: &[&str; 2]) -> Pattern<String> {
+    let variant = |name: &str, form: &str, default: bool| Variant {
+        key: VariantKey::Identifier {
+            name: name.to_string(),
+        },
+        value: convert_format(form, true),
+        default,
+    };
+    Pattern {
+        elements: vec![PatternElement::Placeable {
+            expression: Expression::Select {
+                selector: variable("n"),
+                variants: vec![
+                    variant("one", forms[0], false),
+                    variant("other", forms[1], true),
+                ],
+            },
+        }],
+    }
+}
+
+fn variable(name: &str) -> InlineExpression<String> {
+    InlineExpression::VariableReference {
+        id: Identifier {
+            name: name.to_string(),
+        },
+    }
+}
+
+/// The name of the fluent variable for the positional `tr!()`
+/// argument at `index`. In plural messages the first positional
+/// argument is the count.
+fn positional_variable(index: usize, plural: bool) -> String {
+    if plural && index == 0 {
+        "n".to_string()
+    } else {
+        format!("arg{index}")
+    }
+}
+
+/// Convert a `tr!()` format string into a fluent pattern.
+fn convert_format(format: &str, plural: bool) -> Pattern<String> {
+    let mut elements = Vec::new();
+    let mut text = String::new();
+    let literal = |text: &str| PatternElement::Placeable {
+        expression: Expression::Inline(InlineExpression::StringLiteral {
+            value: text.to_string(),
+        }),
+    };
+    let flush = |text: &mut String, elements: &mut Vec<PatternElement<String>>| {
+        if !text.is_empty() {
+            elements.push(PatternElement::TextElement {
+                value: std::mem::take(text),
+            });
+        }
+    };
+
+    let mut next_index = 0;
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                flush(&mut text, &mut elements);
+                elements.push(literal("{"));
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                flush(&mut text, &mut elements);
+                elements.push(literal("}"));
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        break;
+                    }
+                    placeholder.push(c);
+                }
+                // Formatting options such as `{0:>5}` have no equivalent.
+                let name = placeholder.split(':').next().unwrap_or_default().trim();
+                let name = if name.is_empty() {
+                    next_index += 1;
+                    positional_variable(next_index - 1, plural)
+                } else if let Ok(index) = name.parse::<usize>() {
+                    positional_variable(index, plural)
+                } else {
+                    name.to_string()
+                };
+                flush(&mut text, &mut elements);
+                elements.push(PatternElement::Placeable {
+                    expression: Expression::Inline(variable(&name)),
+                });
+            }
+            '}' => {
+                flush(&mut text, &mut elements);
+                elements.push(literal("}"));
+            }
+            _ => text.push(c),
+        }
+    }
+    flush(&mut text, &mut elements);
+    Pattern { elements }
+}
+
+/// A `tr!()` call in the source code.
+struct TrCall {
+    /// The byte range of the call, from `tr` to the closing bracket.
+    range: Range<usize>,
+    key: MessageKey,
+    /// The expression for the count of a plural message.
+    count: Option<String>,
+    /// The arguments, with their names if they are named.
+    args: Vec<(Option<String>, String)>,
+}
+
+/// Create a patch (in the unified diff format, with paths relative to
+/// the crate's directory) which replaces the `tr!()` calls in the
+/// source files of the crate `crt` with `fl!()` calls using the
+/// message ids of the `migration` and the language loader named
+/// `loader`. The source files are not modified.
+///
+/// Calls for messages which were not migrated, or which can't be
+/// converted automatically (e.g. a plural message with positional
+/// arguments), are left unchanged and logged as warnings.
+pub fn rewrite_tr_calls(crt: &Crate, migration: &Migration, loader: &str) -> Result<String> {
+    let gettext_config = crt.gettext_config_or_err()?;
+    let mut paths = Vec::new();
+    for src_dir in gettext_config.src_dirs(crt) {
+        paths.extend(util::rust_source_files(
+            crt,
+            &src_dir,
+            &gettext_config.ignore,
+        )?);
+    }
+    let mut patch = String::new();
+    for path in &paths {
+        let path = path.as_path();
+        let source = read_to_string(path).with_context(|| {
+            tr!(
+                // {0} is the path to a source file of the crate.
+                "Unable to read \"{0}\".",
+                path.to_string_lossy()
+            )
+        })?;
+
+        let mut replacements = Vec::new();
+        for call in find_tr_calls(&source) {
+            let id = match migration.message_ids.get(&call.key) {
+                Some(id) => id,
+                None => {
+                    warn!(
+                        "Skipping the `tr!()` call for \"{0}\" in \"{1}\", the message was not migrated.",
+                        call.key.msgid,
+                        path.to_string_lossy()
+                    );
+                    continue;
+                }
+            };
+            if call.count.is_some() && call.args.iter().any(|(name, _)| name.is_none()) {
+                warn!(
+                    "Skipping the `tr!()` call for \"{0}\" in \"{1}\", plural messages with positional arguments need to be migrated manually.",
+                    call.key.msgid,
+                    path.to_string_lossy()
+                );
+                continue;
+            }
+
+            let mut fl_call = format!("fl!({loader}, \"{id}\"");
+            if let Some(count) = &call.count {
+                let _ = write!(fl_call, ", n = {count}");
+            }
+            for (index, (name, value)) in call.args.iter().enumerate() {
+                let name = name
+                    .clone()
+                    .unwrap_or_else(|| positional_variable(index, false));
+                let _ = write!(fl_call, ", {name} = {value}");
+            }
+            fl_call.push(')');
+            replacements.push((call.range, fl_call));
+        }
+
+        if !replacements.is_empty() {
+            let relative_path = path.strip_prefix(&crt.path).unwrap_or(path);
+            let relative_path = relative_path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            patch.push_str(&unified_diff(&relative_path, &source, &replacements));
+        }
+    }
+    Ok(patch)
+}
+
+/// Find the `tr!()` calls in the Rust `source`.
+fn find_tr_calls(source: &str) -> Vec<TrCall> {
+    let bytes = source.as_bytes();
+    let mut calls = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if let Some(end) = skip_literal(source, i) {
+            i = end;
+            continue;
+        }
+        let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+        if source[i..].starts_with("tr") && (i == 0 || !is_ident(bytes[i - 1])) {
+            let after = source[i + 2..].trim_start();
+            if let Some(after) = after.strip_prefix('!') {
+                let open = source.len() - after.trim_start().len();
+                if matches!(bytes.get(open), Some(b'(' | b'[' | b'{')) {
+                    if let Some(call) = parse_tr_call(source, i, open) {
+                        i = call.range.end;
+                        calls.push(call);
+                        continue;
+                    }
+                }
+            }
+        }
+        i += source[i..].chars().next().map_or(1, char::len_utf8);
+    }
+    calls
+}
+
+/// If there is a comment, string literal or character literal at
+/// byte `i` of the `source`, the byte index after its end.
+pub(crate) fn skip_literal(source: &str, i: usize) -> Option<usize> {
+    let rest = &source[i..];
+    let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let preceded_by_ident = i > 0 && is_ident(source.as_bytes()[i - 1]);
+
+    if rest.starts_with("//") {
+        return Some(rest.find('\n').map_or(source.len(), |end| i + end));
+    }
+    if let Some(comment) = rest.strip_prefix("/*") {
+        return Some(comment.find("*/").map_or(source.len(), |end| i + end + 4));
+    }
+    if rest.starts_with('"') {
+        return Some(i + string_literal(rest).map_or(rest.len(), |(_, len)| len));
+    }
+    if !preceded_by_ident && (rest.starts_with("r\"") || rest.starts_with("r#")) {
+        return raw_string_literal(rest).map(|(_, len)| i + len);
+    }
+    if !preceded_by_ident && rest.starts_with("b\"") {
+        return Some(i + 1 + string_literal(&rest[1..]).map_or(rest.len() - 1, |(_, len)| len));
+    }
+    if let Some(literal) = rest.strip_prefix('\'') {
+        return match literal.chars().next() {
+            Some('\\') => literal[1..].find('\'').map(|end| i + end + 3),
+            Some(c) if literal[c.len_utf8()..].starts_with('\'') => Some(i + c.len_utf8() + 2),
+            // A lifetime.
+            _ => Some(i + 1),
+        };
+    }
+    None
+}
+
+/// Parse the string literal at the start of `s`, returning its value
+/// and its length in bytes.
+pub(crate) fn string_literal(s: &str) -> Option<(String, usize)> {
+    if s.starts_with('r') {
+        return raw_string_literal(s);
+    }
+    let mut value = String::new();
+    let mut chars = s.char_indices();
+    if chars.next()?.1 != '"' {
+        return None;
+    }
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, i + 1)),
+            '\\' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                'r' => value.push('\r'),
+                '0' => value.push('\0'),
+                'u' => {
+                    let mut hex = String::new();
+                    for (_, c) in chars.by_ref() {
+                        match c {
+                            '{' => {}
+                            '}' => break,
+                            _ => hex.push(c),
+                        }
+                    }
+                    value.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                }
+                'x' => {
+                    let hex: String = [chars.next()?.1, chars.next()?.1].iter().collect();
+                    value.push(char::from(u8::from_str_radix(&hex, 16).ok()?));
+                }
+                '\n' => {
+                    // A line continuation skips the following whitespace.
+                    let rest = &s[i + 2..];
+                    let skip = rest.len() - rest.trim_start().len();
+                    for _ in rest[..skip].chars() {
+                        chars.next();
+                    }
+                }
+                other => value.push(other),
+            },
+            _ => value.push(c),
+        }
+    }
+    None
+}
+
+/// Parse the raw string literal (e.g. `r#"..."#`) at the start of `s`,
+/// returning its value and its length in bytes.
+fn raw_string_literal(s: &str) -> Option<(String, usize)> {
+    let rest = s.strip_prefix('r')?;
+    let hashes = rest.len() - rest.trim_start_matches('#').len();
+    let rest = rest[hashes..].strip_prefix('"')?;
+    let terminator = format!("\"{0}", "#".repeat(hashes));
+    let end = rest.find(&terminator)?;
+    Some((
+        rest[..end].to_string(),
+        1 + hashes + 1 + end + terminator.len(),
+    ))
+}
+
+/// Parse the `tr!()` call starting at byte `start` of the `source`,
+/// whose arguments are opened by the bracket at byte `open`.
+fn parse_tr_call(source: &str, start: usize, open: usize) -> Option<TrCall> {
+    let (segments, close) = split_arguments(source, open)?;
+    let mut segments = segments.into_iter().map(|range| source[range].trim());
+
+    let spec = segments.next()?;
+    let (first, len) = string_literal(spec)?;
+    let mut rest = spec[len..].trim_start();
+    let (context, msgid) = match rest.strip_prefix("=>") {
+        Some(after) => {
+            let after = after.trim_start();
+            let (msgid, len) = string_literal(after)?;
+            rest = after[len..].trim_start();
+            (Some(first), msgid)
+        }
+        None => (None, first),
+    };
+    let count = match rest.strip_prefix('|') {
+        Some(after) => {
+            let after = after.trim_start();
+            let (_, len) = string_literal(after)?;
+            let count = after[len..].trim_start().strip_prefix('%')?.trim();
+            rest = "";
+            Some(count.to_string())
+        }
+        None => None,
+    };
+    if !rest.is_empty() {
+        return None;
+    }
+
+    let args = segments
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| match named_argument(segment) {
+            Some((name, value)) => (Some(name.to_string()), value.to_string()),
+            None => (None, segment.to_string()),
+        })
+        .collect();
+
+    Some(TrCall {
+        range: start..close + 1,
+        key: MessageKey { context, msgid },
+        count,
+        args,
+    })
+}
+
+/// Split the macro arguments opened by the bracket at byte `open` of
+/// the `source` at the top level commas, returning the byte ranges of
+/// the arguments and the byte index of the closing bracket.
+pub(crate) fn split_arguments(source: &str, open: usize) -> Option<(Vec<Range<usize>>, usize)> {
+    let bytes = source.as_bytes();
+    let mut depth = 0;
+    let mut segments = Vec::new();
+    let mut segment_start = open + 1;
+    let mut i = open + 1;
+    while i < bytes.len() {
+        if let Some(end) = skip_literal(source, i) {
+            i = end;
+            continue;
+        }
+        match bytes[i] {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' if depth == 0 => {
+                segments.push(segment_start..i);
+                return Some((segments, i));
+            }
+            b')' | b']' | b'}' => depth -= 1,
+            b',' if depth == 0 => {
+                segments.push(segment_start..i);
+                segment_start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Split a named macro argument (e.g. `name = value`) into its name
+/// and value.
+fn named_argument(argument: &str) -> Option<(&str, &str)> {
+    let (name, value) = argument.split_once('=')?;
+    let name = name.trim();
+    let is_ident = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if !is_ident || value.starts_with(['=', '>']) {
+        return None;
+    }
+    Some((name, value.trim()))
+}
+
+/// The replacement text for a byte range of a source file.
+type Replacement = (Range<usize>, String);
+
+/// Create a unified diff for the file at `path` with the `source`,
+/// applying the `replacements` (which must be sorted and not
+/// overlap).
+fn unified_diff(path: &str, source: &str, replacements: &[Replacement]) -> String {
+    let lines: Vec<&str> = source.split_inclusive('\n').collect();
+    let mut line_starts = Vec::with_capacity(lines.len());
+    let mut offset = 0;
+    for line in &lines {
+        line_starts.push(offset);
+        offset += line.len();
+    }
+    let line_of = |byte: usize| line_starts.partition_point(|start| *start <= byte) - 1;
+
+    // Group the replacements which change the same lines.
+    let mut changes: Vec<(Range<usize>, Vec<&Replacement>)> = Vec::new();
+    for replacement in replacements {
+        let first = line_of(replacement.0.start);
+        let last = line_of(replacement.0.end.max(replacement.0.start + 1) - 1);
+        match changes.last_mut() {
+            Some((lines, group)) if first < lines.end => {
+                lines.end = last + 1;
+                group.push(replacement);
+            }
+            _ => changes.push((first..last + 1, vec![replacement])),
+        }
+    }
+
+    let diff_line = |diff: &mut String, prefix: char, line: &str| {
+        diff.push(prefix);
+        diff.push_str(line);
+        if !line.ends_with('\n') {
+            diff.push_str("\n\\ No newline at end of file\n");
+        }
+    };
+
+    let mut diff = format!("--- a/{path}\n+++ b/{path}\n");
+    let mut delta: isize = 0;
+    let mut i = 0;
+    while i < changes.len() {
+        // Include the following changes whose context overlaps.
+        let mut j = i + 1;
+        while j < changes.len()
+            && changes[j].0.start <= changes[j - 1].0.end + 2 * PATCH_CONTEXT_LINES
+        {
+            j += 1;
+        }
+
+        let hunk_start = changes[i].0.start.saturating_sub(PATCH_CONTEXT_LINES);
+        let hunk_end = (changes[j - 1].0.end + PATCH_CONTEXT_LINES).min(lines.len());
+        let mut body = String::new();
+        let mut old_count = 0;
+        let mut new_count = 0;
+        let mut line = hunk_start;
+        for (changed_lines, group) in &changes[i..j] {
+            for context in &lines[line..changed_lines.start] {
+                diff_line(&mut body, ' ', context);
+            }
+            let old_start = line_starts[changed_lines.start];
+            let old_end = line_starts
+                .get(changed_lines.end)
+                .copied()
+                .unwrap_or(source.len());
+            let mut new = String::new();
+            let mut position = old_start;
+            for (range, replacement) in group {
+                new.push_str(&source[position..range.start]);
+                new.push_str(replacement);
+                position = range.end;
+            }
+            new.push_str(&source[position..old_end]);
+
+            for old_line in &lines[changed_lines.clone()] {
+                diff_line(&mut body, '-', old_line);
+            }
+            for new_line in new.split_inclusive('\n') {
+                diff_line(&mut body, '+', new_line);
+                new_count += 1;
+            }
+            old_count += changed_lines.len();
+            new_count += changed_lines.start - line;
+            old_count += changed_lines.start - line;
+            line = changed_lines.end;
+        }
+        for context in &lines[line..hunk_end] {
+            diff_line(&mut body, ' ', context);
+        }
+        old_count += hunk_end - line;
+        new_count += hunk_end - line;
+
+        let _ = writeln!(
+            diff,
+            "@@ -{0},{1} +{2},{3} @@",
+            hunk_start + 1,
+            old_count,
+            (hunk_start as isize + 1 + delta),
+            new_count
+        );
+        diff.push_str(&body);
+        delta += new_count as isize - old_count as isize;
+        i = j;
+    }
+    diff
+}
@@ -0,0 +1,78 @@
+//! Reporting the progress of the i18n build process while it runs,
+//! used by `cargo i18n` to show what it is doing on large workspaces.
+//! A handler which receives each [Progress] update can be set using
+//! [set_progress_handler()].
+
+use crate::summary::Stage;
+
+use std::{fmt::Display, sync::RwLock};
+
+/// A function which receives the [Progress] updates.
+type ProgressHandler = Box<dyn Fn(&Progress) + Send + Sync>;
+
+static PROGRESS_HANDLER: RwLock<Option<ProgressHandler>> = RwLock::new(None);
+
+/// An update on the progress of the i18n build process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Progress {
+    /// Started localizing a crate (or subcrate) using a localization
+    /// system.
+    Crate {
+        /// The name of the crate.
+        name: String,
+        /// The name of the localization system, see
+        /// [LocalizationSystem::name()](crate::system::LocalizationSystem::name()).
+        system: &'static str,
+    },
+    /// Started a stage of the gettext system for a crate.
+    Stage {
+        /// The name of the crate.
+        crate_name: String,
+        stage: Stage,
+    },
+    /// Started a stage of the gettext system for one of the target
+    /// languages of a crate.
+    Language {
+        /// The name of the crate.
+        crate_name: String,
+        stage: Stage,
+        /// The language identifier, as specified in the gettext
+        /// `target_languages`.
+        language: String,
+    },
+}
+
+impl Display for Progress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Progress::Crate { name, system } => write!(f, "{name} ({system})"),
+            Progress::Stage { crate_name, stage } => write!(f, "{crate_name}: {stage}"),
+            Progress::Language {
+                crate_name,
+                stage,
+                language,
+            } => write!(f, "{crate_name}: {stage} [{language}]"),
+        }
+    }
+}
+
+/// Set the `handler` which receives the [Progress] updates of the
+/// i18n build process (from any thread), replacing the previous
+/// handler. `None` stops reporting the progress, which is the
+/// default.
+pub fn set_progress_handler(handler: Option<ProgressHandler>) {
+    *PROGRESS_HANDLER
+        .write()
+        .unwrap_or_else(|err| err.into_inner()) = handler;
+}
+
+/// Report the `progress` to the handler, if there is one. The update
+/// is only created when it is needed.
+pub(crate) fn report(progress: impl FnOnce() -> Progress) {
+    let handler = PROGRESS_HANDLER
+        .read()
+        .unwrap_or_else(|err| err.into_inner());
+    if let Some(handler) = handler.as_ref() {
+        handler(&progress());
+    }
+}
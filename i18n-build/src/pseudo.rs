@@ -0,0 +1,300 @@
+//! Generation of pseudolocalized resources from the fallback
+//! language of a crate, used by the `cargo i18n pseudo` command. The
+//! messages are transformed using the
+//! [Pseudolocalization](i18n_embed::Pseudolocalization) options, so
+//! that developers can spot text which hasn't been localized, and
+//! layouts which can't accommodate longer translations.
+
+use crate::error::PathError;
+use crate::fluent_impl::{parse_fluent_file, FluentLayout};
+use crate::gettext_impl::po::{self, PoEntry};
+use crate::util;
+
+use anyhow::{Context, Result};
+use fluent_syntax::{
+    ast::{Comment, Entry, Expression, InlineExpression, Pattern, PatternElement, Resource},
+    serializer,
+};
+use i18n_config::Crate;
+use i18n_embed::{Pseudolocalization, PSEUDOLOCALIZATION_LANGUAGE};
+use std::{
+    fs::{read_to_string, write},
+    path::PathBuf,
+};
+use tr::tr;
+use unic_langid::LanguageIdentifier;
+
+/// The comment at the start of each file created by
+/// [pseudolocalize()].
+pub const GENERATED_COMMENT: &str =
+    "Generated by `cargo i18n pseudo` from the fallback language, do not edit.";
+
+/// Generate the resources of the [PSEUDOLOCALIZATION_LANGUAGE] for
+/// the crate `crt` by transforming the messages of its fallback
+/// language using the `pseudolocalization` options:
+///
+/// + For the fluent system, each of the fallback language's ftl
+///   files (including its shared terms file) is written to the
+///   `qps-Ploc` directory of the assets.
+///   Only the text of the messages is accented, their placeables
+///   are left intact.
+/// + For the gettext system, the `pot` file of the crate is
+///   converted into the `qps-Ploc` language's `po` file, leaving
+///   placeholders such as `{0}` intact. It is only compiled by
+///   `cargo i18n` if `qps-Ploc` is one of the `target_languages` in
+///   the i18n config file.
+///
+/// Existing files are replaced. Returns the paths of the files which
+/// were created or modified.
+pub fn pseudolocalize(
+    crt: &Crate,
+    pseudolocalization: &Pseudolocalization,
+) -> Result<Vec<PathBuf>> {
+    let config = crt.config_or_err()?;
+    let mut changed = Vec::new();
+
+    if let Some(fluent_config) = &config.fluent {
+        let layout = FluentLayout::new(crt, fluent_config)?;
+        let pseudo_language: LanguageIdentifier = PSEUDOLOCALIZATION_LANGUAGE
+            .parse()
+            .expect("expected the pseudolocalization language to be valid");
+        let mut paths: Vec<(PathBuf, PathBuf)> = layout
+            .file_paths(&config.fallback_language)?
+            .into_iter()
+            .map(|file_path| {
+                (
+                    layout.path(&config.fallback_language, &file_path),
+                    layout.path(&pseudo_language, &file_path),
+                )
+            })
+            .collect();
+        paths.extend(
+            layout
+                .terms_path(&config.fallback_language)
+                .zip(layout.terms_path(&pseudo_language)),
+        );
+        for (fallback_path, path) in paths {
+            let resource = match parse_fluent_file(&fallback_path)? {
+                Some(resource) => resource,
+                None => continue,
+            };
+            if let Some(parent) = path.parent() {
+                util::create_dir_all_if_not_exists(parent)?;
+            }
+            let contents =
+                serializer::serialize(&pseudolocalize_resource(resource, pseudolocalization));
+            write(&path, contents).map_err(|err| PathError::cannot_create_file(&path, err))?;
+            changed.push(path);
+        }
+    }
+
+    if let Some(gettext_config) = &config.gettext {
+        let pot_path = crt
+            .path
+            .join(gettext_config.pot_dir())
+            .join(crt.module_name())
+            .with_extension("pot");
+        let pot = read_to_string(&pot_path).with_context(|| {
+            tr!(
+                // {0} is the path to the pot file.
+                "Unable to read \"{0}\", run \"cargo i18n\" to create it.",
+                pot_path.to_string_lossy()
+            )
+        })?;
+        let po_path = crt
+            .path
+            .join(gettext_config.po_dir())
+            .join(PSEUDOLOCALIZATION_LANGUAGE)
+            .join(crt.module_name())
+            .with_extension("po");
+        if let Some(parent) = po_path.parent() {
+            util::create_dir_all_if_not_exists(parent)?;
+        }
+        write(&po_path, pseudolocalize_pot(&pot, pseudolocalization))
+            .map_err(|err| PathError::cannot_create_file(&po_path, err))?;
+        changed.push(po_path);
+    }
+
+    Ok(changed)
+}
+
+/// Pseudolocalize the messages and terms of a fluent `resource`.
+/// Junk entries and comments are removed.
+fn pseudolocalize_resource(
+    resource: Resource<String>,
+    pseudolocalization: &Pseudolocalization,
+) -> Resource<String> {
+    let mut body = vec![Entry::ResourceComment(Comment {
+        content: vec![GENERATED_COMMENT.to_string()],
+    })];
+    for entry in resource.body {
+        match entry {
+            Entry::Message(mut message) => {
+                message.comment = None;
+                if let Some(value) = &mut message.value {
+                    pseudolocalize_pattern(value, pseudolocalization);
+                }
+                for attribute in &mut message.attributes {
+                    pseudolocalize_pattern(&mut attribute.value, pseudolocalization);
+                }
+                body.push(Entry::Message(message));
+            }
+            // Terms are only accented, because they are referenced
+            // within messages which are already wrapped.
+            Entry::Term(mut term) => {
+                term.comment = None;
+                accent_pattern(&mut term.value, pseudolocalization);
+                for attribute in &mut term.attributes {
+                    accent_pattern(&mut attribute.value, pseudolocalization);
+                }
+                body.push(Entry::Term(term));
+            }
+            _ => {}
+        }
+    }
+    Resource { body }
+}
+
+/// Accent the text of the `pattern`, and add the expansion padding
+/// and brackets around it.
+fn pseudolocalize_pattern(pattern: &mut Pattern<String>, pseudolocalization: &Pseudolocalization) {
+    accent_pattern(pattern, pseudolocalization);
+
+    let length: usize = pattern
+        .elements
+        .iter()
+        .map(|element| match element {
+            PatternElement::TextElement { value } => value.chars().count(),
+            PatternElement::Placeable { .. } => 0,
+        })
+        .sum();
+    if pseudolocalization.brackets {
+        // A string literal, because `[` at the start of a line in a
+        // multiline pattern would be parsed as a variant key.
+        pattern.elements.insert(
+            0,
+            PatternElement::Placeable {
+                expression: Expression::Inline(InlineExpression::StringLiteral {
+                    value: "[".to_string(),
+                }),
+            },
+        );
+    }
+    let mut end = pseudolocalization.padding(length);
+    if pseudolocalization.brackets {
+        end.push(']');
+    }
+    if !end.is_empty() {
+        pattern
+            .elements
+            .push(PatternElement::TextElement { value: end });
+    }
+}
+
+/// Accent the text of the `pattern`, including the variants of its
+/// select expressions.
+fn accent_pattern(pattern: &mut Pattern<String>, pseudolocalization: &Pseudolocalization) {
+    for element in &mut pattern.elements {
+        match element {
+            PatternElement::TextElement { value } => {
+                *value = pseudolocalization.accent(value).into_owned();
+            }
+            PatternElement::Placeable { expression } => {
+                accent_expression(expression, pseudolocalization)
+            }
+        }
+    }
+}
+
+fn accent_expression(expression: &mut Expression<String>, pseudolocalization: &Pseudolocalization) {
+    match expression {
+        Expression::Select { variants, .. } => {
+            for variant in variants {
+                accent_pattern(&mut variant.value, pseudolocalization);
+            }
+        }
+        Expression::Inline(InlineExpression::Placeable { expression }) => {
+            accent_expression(expression, pseudolocalization)
+        }
+        Expression::Inline(_) => {}
+    }
+}
+
+/// Convert the contents of a `pot` file into a `po` file with the
+/// pseudolocalized messages.
+fn pseudolocalize_pot(pot: &str, pseudolocalization: &Pseudolocalization) -> String {
+    po::entries(pot)
+        .map(|entry| {
+            let entry = PoEntry::parse(entry);
+            if entry.is_header() {
+                entry.with_msgstr(&pseudo_header(&entry.msgstr().unwrap_or_default()), false)
+            } else if entry.is_plural() {
+                let msgid = entry.msgid().unwrap_or_default();
+                let msgid_plural = entry.msgid_plural().unwrap_or_default();
+                entry.with_msgstr_plural(&[
+                    pseudolocalize_format_string(&msgid, pseudolocalization),
+                    pseudolocalize_format_string(&msgid_plural, pseudolocalization),
+                ])
+            } else {
+                match entry.msgid() {
+                    Some(msgid) => entry.with_msgstr(
+                        &pseudolocalize_format_string(&msgid, pseudolocalization),
+                        false,
+                    ),
+                    None => entry.with_msgstr("", false),
+                }
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// The `header` of the `pot` file, with the fields which are
+/// required to compile it as the pseudolocalization language's `po`
+/// file filled in.
+fn pseudo_header(header: &str) -> String {
+    const LANGUAGE: &str = "Language:";
+    const PLURAL_FORMS: &str = "Plural-Forms:";
+
+    let mut fields: Vec<String> = header
+        .lines()
+        .filter(|line| !(line.starts_with(LANGUAGE) || line.starts_with(PLURAL_FORMS)))
+        .map(|line| line.replace("charset=CHARSET", "charset=UTF-8"))
+        .collect();
+    fields.push(format!("{LANGUAGE} {PSEUDOLOCALIZATION_LANGUAGE}"));
+    fields.push(format!("{PLURAL_FORMS} nplurals=2; plural=(n != 1);"));
+    fields.into_iter().map(|line| format!("{line}\n")).collect()
+}
+
+/// Pseudolocalize a `tr!()` format string, leaving its placeholders
+/// (such as `{0}` or `{name}`) intact.
+fn pseudolocalize_format_string(text: &str, pseudolocalization: &Pseudolocalization) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+    let mut accented = String::with_capacity(text.len());
+    let mut length = 0;
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..]
+            .find('}')
+            .map_or(rest.len(), |end| start + end + 1);
+        length += rest[..start].chars().count();
+        accented.push_str(&pseudolocalization.accent(&rest[..start]));
+        accented.push_str(&rest[start..end]);
+        rest = &rest[end..];
+    }
+    length += rest.chars().count();
+    accented.push_str(&pseudolocalization.accent(rest));
+
+    let mut pseudo = String::with_capacity(accented.len() + 2);
+    if pseudolocalization.brackets {
+        pseudo.push('[');
+    }
+    pseudo.push_str(&accented);
+    pseudo.push_str(&pseudolocalization.padding(length));
+    if pseudolocalization.brackets {
+        pseudo.push(']');
+    }
+    pseudo
+}
@@ -0,0 +1,442 @@
+//! Reporting the translation status of the languages of a crate, used
+//! by `cargo i18n report`. The [CrateReport] can be rendered as a
+//! Markdown or HTML document, suitable for committing to the
+//! repository or publishing as an artifact in continuous integration,
+//! and its coverage as an SVG [badge()] for the crate's README.
+
+use crate::fluent_impl::{message_ids, missing_messages, parse_fluent_file, FluentLayout};
+use crate::gettext_impl::{po, pot_header::format_date};
+use crate::translate::FLUENT_DRAFT_COMMENT;
+use crate::xliff::{escape_xml, export_languages};
+
+use anyhow::{anyhow, Context, Result};
+use fluent_syntax::ast::Entry;
+use i18n_config::{Crate, GettextConfig};
+use std::{
+    fmt::{Display, Write},
+    fs::read_to_string,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tr::tr;
+use unic_langid::LanguageIdentifier;
+
+/// The format of a rendered [CrateReport].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// A HTML document.
+    Html,
+    /// A Markdown document.
+    Markdown,
+}
+
+impl Display for ReportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportFormat::Html => write!(f, "html"),
+            ReportFormat::Markdown => write!(f, "markdown"),
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "html" => Ok(ReportFormat::Html),
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            _ => Err(anyhow!(tr!(
+                // {0} is the report format which was requested.
+                "Unsupported report format \"{0}\", expected \"html\" or \"markdown\".",
+                s
+            ))),
+        }
+    }
+}
+
+/// The translation status of one of the languages of a crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageStatus {
+    /// The language.
+    pub language: LanguageIdentifier,
+    /// The number of messages in the fallback language (or the `pot`
+    /// file for the gettext system).
+    pub messages: usize,
+    /// The number of those messages which are translated into the
+    /// language, including those which need review.
+    pub translated: usize,
+    /// The number of translated messages which need review: the
+    /// `fuzzy` entries of the gettext system, and the machine
+    /// translated drafts of the fluent system.
+    pub needs_review: usize,
+    /// The ids (or `msgid` for the gettext system) of the messages
+    /// which are not translated.
+    pub missing: Vec<String>,
+    /// When the language's files were last modified, if they exist.
+    pub last_modified: Option<SystemTime>,
+}
+
+impl LanguageStatus {
+    /// The percentage of the messages which are translated.
+    pub fn coverage(&self) -> f64 {
+        coverage(self.translated, self.messages)
+    }
+}
+
+/// The translation status of each of the languages of a crate,
+/// created using [report()].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrateReport {
+    /// The name of the crate.
+    pub name: String,
+    /// The fallback language of the crate, whose messages are
+    /// translated.
+    pub fallback_language: LanguageIdentifier,
+    /// The status of each of the languages other than the fallback
+    /// language.
+    pub languages: Vec<LanguageStatus>,
+}
+
+impl CrateReport {
+    /// The percentage of the messages of all of the languages which
+    /// are translated.
+    pub fn coverage(&self) -> f64 {
+        coverage(
+            self.languages.iter().map(|status| status.translated).sum(),
+            self.languages.iter().map(|status| status.messages).sum(),
+        )
+    }
+
+    /// Render the report in the `format`.
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Html => self.to_html(),
+            ReportFormat::Markdown => self.to_markdown(),
+        }
+    }
+
+    /// Render the report as a Markdown document.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        let _ = writeln!(markdown, "# {}\n", self.title());
+        let _ = writeln!(markdown, "{}\n", self.description());
+        let _ = writeln!(markdown, "| {} |", table_headings().join(" | "));
+        let _ = writeln!(markdown, "|---|---:|---:|---:|---:|---|");
+        for status in &self.languages {
+            let _ = writeln!(markdown, "| {} |", table_row(status).join(" | "));
+        }
+
+        let incomplete: Vec<&LanguageStatus> = self
+            .languages
+            .iter()
+            .filter(|status| !status.missing.is_empty())
+            .collect();
+        if !incomplete.is_empty() {
+            let _ = writeln!(markdown, "\n## {}", missing_messages_heading());
+            for status in incomplete {
+                let _ = writeln!(markdown, "\n### {}\n", status.language);
+                for id in &status.missing {
+                    let _ = writeln!(markdown, "- `{}`", id.replace('`', "\\`"));
+                }
+            }
+        }
+        markdown
+    }
+
+    /// Render the report as a HTML document.
+    pub fn to_html(&self) -> String {
+        let title = escape_xml(&self.title());
+        let mut html = String::new();
+        let _ = writeln!(html, "<!DOCTYPE html>");
+        let _ = writeln!(html, "<html>");
+        let _ = writeln!(html, "<head>");
+        let _ = writeln!(html, "<meta charset=\"utf-8\">");
+        let _ = writeln!(html, "<title>{title}</title>");
+        let _ = writeln!(html, "</head>");
+        let _ = writeln!(html, "<body>");
+        let _ = writeln!(html, "<h1>{title}</h1>");
+        let _ = writeln!(html, "<p>{}</p>", escape_xml(&self.description()));
+        let _ = writeln!(html, "<table>");
+        let headings: Vec<String> = table_headings()
+            .iter()
+            .map(|heading| format!("<th>{}</th>", escape_xml(heading)))
+            .collect();
+        let _ = writeln!(html, "<tr>{}</tr>", headings.concat());
+        for status in &self.languages {
+            let cells: Vec<String> = table_row(status)
+                .iter()
+                .map(|cell| format!("<td>{}</td>", escape_xml(cell)))
+                .collect();
+            let _ = writeln!(html, "<tr>{}</tr>", cells.concat());
+        }
+        let _ = writeln!(html, "</table>");
+
+        let incomplete: Vec<&LanguageStatus> = self
+            .languages
+            .iter()
+            .filter(|status| !status.missing.is_empty())
+            .collect();
+        if !incomplete.is_empty() {
+            let _ = writeln!(html, "<h2>{}</h2>", escape_xml(&missing_messages_heading()));
+            for status in incomplete {
+                let _ = writeln!(html, "<h3>{}</h3>", status.language);
+                let _ = writeln!(html, "<ul>");
+                for id in &status.missing {
+                    let _ = writeln!(html, "<li><code>{}</code></li>", escape_xml(id));
+                }
+                let _ = writeln!(html, "</ul>");
+            }
+        }
+        let _ = writeln!(html, "</body>");
+        let _ = writeln!(html, "</html>");
+        html
+    }
+
+    fn title(&self) -> String {
+        tr!(
+            // The title of the report created by `cargo i18n report`, {0} is the name of the crate.
+            "Translation status of {0}",
+            self.name
+        )
+    }
+
+    fn description(&self) -> String {
+        tr!(
+            // {0} is the fallback language, {1} is the percentage of the messages which are translated, e.g. "85%".
+            "The messages are translated from \"{0}\", {1} of them are translated overall.",
+            self.fallback_language,
+            format_coverage(self.coverage())
+        )
+    }
+}
+
+fn table_headings() -> [String; 6] {
+    [
+        // The headings of the table in the report created by `cargo i18n report`.
+        tr!("Language"),
+        tr!("Translated"),
+        tr!("Needs review"),
+        tr!("Missing"),
+        tr!("Coverage"),
+        tr!("Last modified"),
+    ]
+}
+
+fn table_row(status: &LanguageStatus) -> [String; 6] {
+    [
+        status.language.to_string(),
+        format!("{0}/{1}", status.translated, status.messages),
+        status.needs_review.to_string(),
+        status.missing.len().to_string(),
+        format_coverage(status.coverage()),
+        status
+            .last_modified
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|modified| format_date(modified.as_secs()))
+            .unwrap_or_default(),
+    ]
+}
+
+fn missing_messages_heading() -> String {
+    tr!(
+        // A heading in the report created by `cargo i18n report`.
+        "Missing messages"
+    )
+}
+
+fn coverage(translated: usize, messages: usize) -> f64 {
+    if messages == 0 {
+        100.0
+    } else {
+        translated as f64 * 100.0 / messages as f64
+    }
+}
+
+/// Format the `coverage` percentage, rounded down so that a language
+/// is only shown as complete when all its messages are translated.
+fn format_coverage(coverage: f64) -> String {
+    format!("{}%", coverage.floor())
+}
+
+/// Report the translation status of each of the languages of the
+/// crate `crt` (see [export_languages()]), for the fluent and gettext
+/// localization systems it is configured to use.
+pub fn report(crt: &Crate) -> Result<CrateReport> {
+    let config = crt.config_or_err()?;
+    let mut languages = Vec::new();
+    for language in export_languages(crt)? {
+        let mut status = LanguageStatus {
+            language,
+            messages: 0,
+            translated: 0,
+            needs_review: 0,
+            missing: Vec::new(),
+            last_modified: None,
+        };
+        if let Some(fluent_config) = &config.fluent {
+            let layout = FluentLayout::new(crt, fluent_config)?;
+            fluent_status(&layout, &config.fallback_language, &mut status)?;
+        }
+        if let Some(gettext_config) = &config.gettext {
+            gettext_status(crt, gettext_config, &mut status)?;
+        }
+        languages.push(status);
+    }
+    Ok(CrateReport {
+        name: crt.name.clone(),
+        fallback_language: config.fallback_language.clone(),
+        languages,
+    })
+}
+
+/// Add the status of the fluent files of the `status`'s language.
+fn fluent_status(
+    layout: &FluentLayout,
+    fallback_language: &LanguageIdentifier,
+    status: &mut LanguageStatus,
+) -> Result<()> {
+    let mut messages = 0;
+    for file_path in layout.file_paths(fallback_language)? {
+        if let Some(resource) = parse_fluent_file(&layout.path(fallback_language, &file_path))? {
+            messages += message_ids(&resource).len();
+        }
+    }
+    let missing: Vec<String> = missing_messages(layout, fallback_language, &status.language)?
+        .into_iter()
+        .flat_map(|(_, ids)| ids)
+        .collect();
+    status.messages += messages;
+    status.translated += messages.saturating_sub(missing.len());
+    status.missing.extend(missing);
+
+    let language = status.language.clone();
+    for file_path in layout.file_paths(&language)? {
+        let path = layout.path(&language, &file_path);
+        let resource = match parse_fluent_file(&path)? {
+            Some(resource) => resource,
+            None => continue,
+        };
+        update_last_modified(status, &path);
+        status.needs_review += resource
+            .body
+            .iter()
+            .filter(|entry| match entry {
+                Entry::Message(message) => message.comment.as_ref().is_some_and(|comment| {
+                    comment
+                        .content
+                        .iter()
+                        .any(|line| line == FLUENT_DRAFT_COMMENT)
+                }),
+                _ => false,
+            })
+            .count();
+    }
+    Ok(())
+}
+
+/// Add the status of the `po` file of the `status`'s language. When
+/// the language has no `po` file yet, all the messages in the `pot`
+/// file are missing.
+fn gettext_status(
+    crt: &Crate,
+    gettext_config: &GettextConfig,
+    status: &mut LanguageStatus,
+) -> Result<()> {
+    let po_path = crt
+        .path
+        .join(gettext_config.po_dir())
+        .join(status.language.to_string())
+        .join(crt.module_name())
+        .with_extension("po");
+    let pot_path = crt
+        .path
+        .join(gettext_config.pot_dir())
+        .join(crt.module_name())
+        .with_extension("pot");
+    let path: PathBuf = if po_path.exists() {
+        update_last_modified(status, &po_path);
+        po_path
+    } else if pot_path.exists() {
+        pot_path
+    } else {
+        return Ok(());
+    };
+    let contents = read_to_string(&path).with_context(|| {
+        tr!(
+            // {0} is the path to the po file.
+            "Unable to read \"{0}\".",
+            path.to_string_lossy()
+        )
+    })?;
+
+    for entry in po::entries(&contents).map(po::PoEntry::parse) {
+        if entry.is_obsolete() || entry.is_header() {
+            continue;
+        }
+        let msgid = match entry.msgid() {
+            Some(msgid) => msgid,
+            None => continue,
+        };
+        status.messages += 1;
+        if entry.is_translated() {
+            status.translated += 1;
+            if entry.is_fuzzy() {
+                status.needs_review += 1;
+            }
+        } else {
+            status.missing.push(msgid);
+        }
+    }
+    Ok(())
+}
+
+fn update_last_modified(status: &mut LanguageStatus, path: &Path) {
+    if let Ok(modified) = path.metadata().and_then(|metadata| metadata.modified()) {
+        if status.last_modified.is_none_or(|last| last < modified) {
+            status.last_modified = Some(modified);
+        }
+    }
+}
+
+/// Create an SVG badge, like those of <https://shields.io>, showing
+/// the `coverage` percentage (see [CrateReport::coverage()]) with the
+/// `label`, for the crate's README. Its color ranges from red to
+/// green as the coverage increases.
+///
+/// ```
+/// let svg = i18n_build::report::badge("translated", 85.5);
+/// assert!(svg.starts_with("<svg"));
+/// assert!(svg.contains("85%"));
+/// ```
+pub fn badge(label: &str, coverage: f64) -> String {
+    let value = format_coverage(coverage);
+    let color = match coverage {
+        c if c >= 100.0 => "#4c1",
+        c if c >= 90.0 => "#97ca00",
+        c if c >= 75.0 => "#a4a61d",
+        c if c >= 50.0 => "#dfb317",
+        c if c >= 25.0 => "#fe7d37",
+        _ => "#e05d44",
+    };
+    // An approximation of the width of the text in the 11px font.
+    let text_width = |text: &str| text.chars().count() * 7 + 10;
+    let label_width = text_width(label);
+    let value_width = text_width(&value);
+    let width = label_width + value_width;
+    let label = escape_xml(label);
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"20\" role=\"img\" aria-label=\"{label}: {value}\">\n\
+         <title>{label}: {value}</title>\n\
+         <linearGradient id=\"s\" x2=\"0\" y2=\"100%\"><stop offset=\"0\" stop-color=\"#bbb\" stop-opacity=\".1\"/><stop offset=\"1\" stop-opacity=\".1\"/></linearGradient>\n\
+         <clipPath id=\"r\"><rect width=\"{width}\" height=\"20\" rx=\"3\" fill=\"#fff\"/></clipPath>\n\
+         <g clip-path=\"url(#r)\"><rect width=\"{label_width}\" height=\"20\" fill=\"#555\"/><rect x=\"{label_width}\" width=\"{value_width}\" height=\"20\" fill=\"{color}\"/><rect width=\"{width}\" height=\"20\" fill=\"url(#s)\"/></g>\n\
+         <g fill=\"#fff\" text-anchor=\"middle\" font-family=\"Verdana,Geneva,DejaVu Sans,sans-serif\" font-size=\"11\">\
+         <text x=\"{label_x}\" y=\"14\">{label}</text><text x=\"{value_x}\" y=\"14\">{value}</text></g>\n\
+         </svg>\n",
+        label_x = label_width / 2,
+        value_x = label_width + value_width / 2,
+    )
+}
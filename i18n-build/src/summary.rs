@@ -0,0 +1,226 @@
+//! A summary of the effects of a run of the i18n build process, which
+//! is printed by `cargo i18n` when it completes. Nothing in the
+//! summary leaves the machine it was created on.
+
+use serde::{Serialize, Serializer};
+use std::{
+    fmt::Display,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use tr::tr;
+
+/// A stage of the gettext build process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    /// Extracting the messages from the source code using `xtr`.
+    Extract,
+    /// Concatenating the `pot` files of the collated subcrates using
+    /// `msgcat`.
+    Collate,
+    /// Updating the header of the `pot` file.
+    PotHeader,
+    /// Creating the `po` files for new languages using `msginit`.
+    Init,
+    /// Merging the changes in the `pot` file into the `po` files
+    /// using `msgmerge`.
+    Merge,
+    /// Compiling the `po` files into `mo` files using `msgfmt`.
+    Compile,
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Stage::Extract => "xtr",
+            Stage::Collate => "msgcat",
+            Stage::PotHeader => "pot header",
+            Stage::Init => "msginit",
+            Stage::Merge => "msgmerge",
+            Stage::Compile => "msgfmt",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The changes to the messages of a language's `po` file, and how many
+/// of its messages are translated after the run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LanguageChanges {
+    /// The language identifier, as specified in the gettext
+    /// `target_languages`.
+    pub language: String,
+    /// The number of messages which were added.
+    pub new: usize,
+    /// The number of messages whose translation or `fuzzy` flag was
+    /// changed.
+    pub changed: usize,
+    /// The number of messages which were removed (or marked as
+    /// obsolete).
+    pub removed: usize,
+    /// The number of messages which are translated, excluding the
+    /// fuzzy translations.
+    pub translated: usize,
+    /// The number of messages with a fuzzy translation, which needs
+    /// to be reviewed.
+    pub fuzzy: usize,
+    /// The number of messages which are not translated.
+    pub untranslated: usize,
+}
+
+/// What happened while localizing a single crate.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CrateSummary {
+    /// The name of the crate.
+    pub name: String,
+    /// The path to the crate.
+    pub path: PathBuf,
+    /// The number of messages in the crate's `pot` file, if it has
+    /// one.
+    pub messages: Option<usize>,
+    /// The changes to the `po` file of each language.
+    pub languages: Vec<LanguageChanges>,
+    /// How long each stage took, in the order they were run.
+    #[serde(serialize_with = "serialize_stages")]
+    pub stages: Vec<(Stage, Duration)>,
+}
+
+impl CrateSummary {
+    /// Run the stage `f`, recording how long it took.
+    pub(crate) fn time<T>(&mut self, stage: Stage, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.stages.push((stage, start.elapsed()));
+        result
+    }
+}
+
+/// A summary of a run of the i18n build process, created using
+/// [run_with_summary()](crate::run_with_summary()) or
+/// [run_workspace_with_summary()](crate::run_workspace_with_summary()).
+/// Its [Display] implementation formats it for the user, and it can
+/// be serialized (e.g. as JSON) with the durations in seconds.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RunSummary {
+    /// The crates which were localized, including subcrates.
+    pub crates: Vec<CrateSummary>,
+    /// How long the whole run took.
+    #[serde(serialize_with = "serialize_seconds")]
+    pub duration: Duration,
+}
+
+/// Serialize the `duration` as a number of seconds.
+fn serialize_seconds<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64())
+}
+
+/// Serialize the `stages` as a sequence of `{ "stage": "merge",
+/// "seconds": 0.1 }` objects.
+fn serialize_stages<S: Serializer>(
+    stages: &[(Stage, Duration)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct StageDuration {
+        stage: Stage,
+        seconds: f64,
+    }
+    serializer.collect_seq(stages.iter().map(|(stage, duration)| StageDuration {
+        stage: *stage,
+        seconds: duration.as_secs_f64(),
+    }))
+}
+
+/// Write the changes and translation statistics of the `languages`
+/// as a table, with a column for each of the counts.
+fn write_languages_table(
+    f: &mut std::fmt::Formatter<'_>,
+    languages: &[LanguageChanges],
+) -> std::fmt::Result {
+    let headings = [
+        // The headings of the table of languages in the summary printed by `cargo i18n`.
+        tr!("Language"),
+        tr!("New"),
+        tr!("Changed"),
+        tr!("Removed"),
+        tr!("Translated"),
+        tr!("Fuzzy"),
+        tr!("Untranslated"),
+    ];
+    let rows: Vec<[String; 7]> = languages
+        .iter()
+        .map(|language| {
+            [
+                language.language.clone(),
+                language.new.to_string(),
+                language.changed.to_string(),
+                language.removed.to_string(),
+                language.translated.to_string(),
+                language.fuzzy.to_string(),
+                language.untranslated.to_string(),
+            ]
+        })
+        .collect();
+    let widths: Vec<usize> = (0..headings.len())
+        .map(|column| {
+            std::iter::once(&headings)
+                .chain(&rows)
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    for row in std::iter::once(&headings).chain(&rows) {
+        // The language is aligned to the left, and the counts to the
+        // right.
+        let mut line = format!("  {0:<1$}", row[0], widths[0]);
+        for (cell, width) in row.iter().zip(&widths).skip(1) {
+            line.push_str(&format!("  {cell:>width$}"));
+        }
+        writeln!(f, "{0}", line.trim_end())?;
+    }
+    Ok(())
+}
+
+impl Display for RunSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for crt in &self.crates {
+            match crt.messages {
+                Some(messages) => writeln!(
+                    f,
+                    "{}",
+                    tr!(
+                        // {0} is the name of the crate, {1} is the number of messages.
+                        "{0}: {1} messages",
+                        crt.name,
+                        messages
+                    )
+                )?,
+                None => writeln!(f, "{0}:", crt.name)?,
+            }
+            if !crt.languages.is_empty() {
+                write_languages_table(f, &crt.languages)?;
+            }
+            if !crt.stages.is_empty() {
+                let stages: Vec<String> = crt
+                    .stages
+                    .iter()
+                    .map(|(stage, duration)| format!("{stage} {duration:.2?}"))
+                    .collect();
+                writeln!(f, "  {0}", stages.join(", "))?;
+            }
+        }
+        write!(
+            f,
+            "{}",
+            tr!(
+                // {0} is the number of crates, {1} is the duration of the run, e.g. "1.23s".
+                "Localized {0} crates in {1}",
+                self.crates.len(),
+                format!("{:.2?}", self.duration)
+            )
+        )
+    }
+}
@@ -0,0 +1,597 @@
+//! The localization systems which can be used to localize a crate,
+//! and the [LocalizationSystem] trait for adding support for other
+//! localization systems (e.g. from a third-party crate) to
+//! [run()](crate::run()) and `cargo i18n`.
+
+use std::{
+    fmt::Display,
+    fs::read_to_string,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock, RwLock},
+};
+
+use anyhow::{Context, Result};
+use fluent_syntax::parser;
+use i18n_config::Crate;
+use serde::{Deserialize, Serialize};
+use tr::tr;
+
+use crate::{
+    fluent_impl::{
+        fluent_languages, missing_messages, remove_obsolete_messages, undefined_terms,
+        update_source_comments, FluentLayout,
+    },
+    gettext_impl,
+    progress::{self, Progress},
+    summary::RunSummary,
+    usage::message_comments,
+};
+
+/// A problem found in the localization resources of a crate by
+/// [LocalizationSystem::validate()].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    /// The file with the problem, if there is one.
+    pub path: Option<PathBuf>,
+    /// A description of the problem.
+    pub message: String,
+    /// Whether the problem fails the validation.
+    pub severity: Severity,
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.severity == Severity::Warning {
+            f.write_str(&tr!(
+                // The prefix of the problems found by `cargo i18n validate` which are only warnings.
+                "warning: "
+            ))?;
+        }
+        match &self.path {
+            Some(path) => write!(f, "{0}: {1}", path.to_string_lossy(), self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+/// The severity of a [ValidationIssue].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// A problem which fails the validation.
+    Error,
+    /// A problem which is reported, but doesn't fail the validation,
+    /// such as missing messages in a language which is not one of
+    /// the `required_languages` (see
+    /// [FluentConfig::required_languages](i18n_config::FluentConfig::required_languages)).
+    Warning,
+}
+
+/// A localization system which can be used to localize a crate.
+/// Implementations are registered using [register_system()], after
+/// which they are used by [run()](crate::run()) for each crate with a
+/// table for the system in its i18n config file, see
+/// [I18nConfig::systems](i18n_config::I18nConfig::systems).
+pub trait LocalizationSystem: Send + Sync {
+    /// The name of this system, which is also the name of its table
+    /// in the i18n config file (e.g. `qt` for `[qt]`).
+    fn name(&self) -> &str;
+
+    /// The extensions of the resource files used by this system,
+    /// without the leading `.` (e.g. `["ts", "qm"]`).
+    fn resource_extensions(&self) -> &[&str];
+
+    /// Whether the crate is localized using this system. By default
+    /// this is whether the crate's i18n config file has a table
+    /// for the system.
+    fn is_used_by(&self, crt: &Crate) -> Result<bool> {
+        Ok(crt.config_or_err()?.systems.contains_key(self.name()))
+    }
+
+    /// Extract the messages from the source code of the crate, and
+    /// update its localization resources, running at most `jobs`
+    /// external commands in parallel. The effects are recorded in
+    /// the `summary`.
+    fn extract(&self, crt: &Crate, jobs: NonZeroUsize, summary: &mut RunSummary) -> Result<()>;
+
+    /// Check the localization resources of the crate, returning the
+    /// problems which were found. By default no problems are found.
+    fn validate(&self, #[allow(unused_variables)] crt: &Crate) -> Result<Vec<ValidationIssue>> {
+        Ok(Vec::new())
+    }
+
+    /// Create a [LanguageLoader](i18n_embed::LanguageLoader) for the
+    /// crate's localization resources, if this system has one. By
+    /// default there is none.
+    ///
+    /// ⚠️ *This API requires the following crate features to be activated: `localize`.*
+    #[cfg(feature = "localize")]
+    fn language_loader(
+        &self,
+        #[allow(unused_variables)] crt: &Crate,
+    ) -> Result<Option<Box<dyn i18n_embed::LanguageLoader + Send + Sync>>> {
+        Ok(None)
+    }
+}
+
+/// The gettext localization system, used by crates with a
+/// `[gettext]` table in their i18n config file.
+#[derive(Debug, Default)]
+pub struct GettextSystem;
+
+impl LocalizationSystem for GettextSystem {
+    fn name(&self) -> &str {
+        "gettext"
+    }
+
+    fn resource_extensions(&self) -> &[&str] {
+        &["pot", "po", "mo"]
+    }
+
+    fn is_used_by(&self, crt: &Crate) -> Result<bool> {
+        Ok(crt.config_or_err()?.gettext.is_some())
+    }
+
+    fn extract(&self, crt: &Crate, jobs: NonZeroUsize, summary: &mut RunSummary) -> Result<()> {
+        gettext_impl::run_with_summary(crt, jobs, summary)
+    }
+
+    #[cfg(feature = "localize")]
+    fn language_loader(
+        &self,
+        crt: &Crate,
+    ) -> Result<Option<Box<dyn i18n_embed::LanguageLoader + Send + Sync>>> {
+        let config = crt.config_or_err()?;
+        // The loader requires a static module name, this is only
+        // called a few times in tools such as `cargo i18n`.
+        let module: &'static str = Box::leak(crt.module_name().into_boxed_str());
+        Ok(Some(Box::new(
+            i18n_embed::gettext::GettextLanguageLoader::new(
+                module,
+                config.fallback_language.clone(),
+            ),
+        )))
+    }
+}
+
+/// The fluent localization system, used by crates with a `[fluent]`
+/// table in their i18n config file.
+#[derive(Debug, Default)]
+pub struct FluentSystem;
+
+impl LocalizationSystem for FluentSystem {
+    fn name(&self) -> &str {
+        "fluent"
+    }
+
+    fn resource_extensions(&self) -> &[&str] {
+        &["ftl"]
+    }
+
+    fn is_used_by(&self, crt: &Crate) -> Result<bool> {
+        Ok(crt.config_or_err()?.fluent.is_some())
+    }
+
+    /// Fluent messages are written by hand in the resources of the
+    /// fallback language, so there is nothing to extract. If the
+    /// `source_comments` tag is configured in the
+    /// [FluentConfig](i18n_config::FluentConfig), the comments for
+    /// translators preceding the `fl!()` calls are copied to the
+    /// messages of the fallback language. The messages of the other
+    /// languages which are no longer in the fallback language are
+    /// removed according to the `obsolete_policy`.
+    fn extract(&self, crt: &Crate, _jobs: NonZeroUsize, _summary: &mut RunSummary) -> Result<()> {
+        let config = crt.config_or_err()?;
+        let fluent_config = match &config.fluent {
+            Some(fluent_config) => fluent_config,
+            None => return Ok(()),
+        };
+        progress::report(|| Progress::Crate {
+            name: crt.name.clone(),
+            system: "fluent",
+        });
+        let layout = FluentLayout::new(crt, fluent_config)?;
+        if let Some(tag) = &fluent_config.source_comments {
+            update_source_comments(
+                &layout,
+                &config.fallback_language,
+                &message_comments(crt, tag)?,
+            )?;
+        }
+        remove_obsolete_messages(
+            &layout,
+            &config.fallback_language,
+            fluent_config.obsolete_policy,
+        )?;
+        Ok(())
+    }
+
+    /// Check that the fluent files of each language can be parsed,
+    /// that the terms referenced by their messages are defined (in
+    /// the same file or in the shared terms file of the language),
+    /// and that they contain all of the messages of the fallback
+    /// language. Missing messages are an error for the
+    /// `required_languages` in the [FluentConfig](i18n_config::FluentConfig),
+    /// and a warning for the other languages.
+    fn validate(&self, crt: &Crate) -> Result<Vec<ValidationIssue>> {
+        let config = crt.config_or_err()?;
+        let fluent_config = match &config.fluent {
+            Some(fluent_config) => fluent_config,
+            None => return Ok(Vec::new()),
+        };
+        let layout = FluentLayout::new(crt, fluent_config)?;
+
+        let mut issues = Vec::new();
+        for language in layout.languages()? {
+            let paths = layout
+                .file_paths(&language)?
+                .into_iter()
+                .map(|file_path| layout.path(&language, &file_path))
+                .chain(layout.terms_path(&language));
+            for path in paths {
+                if !path.exists() {
+                    continue;
+                }
+                let source = read_to_string(&path).with_context(|| {
+                    tr!(
+                        // {0} is the path to the fluent file.
+                        "Unable to read \"{0}\".",
+                        path.to_string_lossy()
+                    )
+                })?;
+                if let Err((_, errors)) = parser::parse(source.as_str()) {
+                    issues.extend(errors.into_iter().map(|error| ValidationIssue {
+                        path: Some(path.clone()),
+                        message: tr!(
+                            // {0} is the description of a fluent syntax error.
+                            "Syntax error: {0}",
+                            error
+                        ),
+                        severity: Severity::Error,
+                    }));
+                }
+            }
+            for (path, term_ids) in undefined_terms(&layout, &language)? {
+                issues.push(ValidationIssue {
+                    path: Some(path),
+                    message: tr!(
+                        // {0} is the number of terms, and {1} is the list of term ids.
+                        "Referencing {0} undefined terms: {1}",
+                        term_ids.len(),
+                        term_ids.join(", ")
+                    ),
+                    severity: Severity::Error,
+                });
+            }
+        }
+
+        let fallback_language = &config.fallback_language;
+        let languages = layout.languages()?;
+        for language in &fluent_config.required_languages {
+            if !languages.contains(language) {
+                issues.push(ValidationIssue {
+                    path: None,
+                    message: tr!(
+                        // {0} is the identifier of the language.
+                        "The required language \"{0}\" has no fluent files.",
+                        language
+                    ),
+                    severity: Severity::Error,
+                });
+            }
+        }
+        for language in languages
+            .iter()
+            .filter(|language| *language != fallback_language)
+        {
+            let severity = if fluent_config.required_languages.contains(language) {
+                Severity::Error
+            } else {
+                Severity::Warning
+            };
+            for (path, message_ids) in missing_messages(&layout, fallback_language, language)? {
+                issues.push(ValidationIssue {
+                    path: Some(path),
+                    message: tr!(
+                        // {0} is the number of messages, {1} is the identifier of the language, and {2} is the list of message ids.
+                        "Missing {0} messages of the fallback language in \"{1}\": {2}",
+                        message_ids.len(),
+                        language,
+                        message_ids.join(", ")
+                    ),
+                    severity,
+                });
+            }
+        }
+        Ok(issues)
+    }
+
+    #[cfg(feature = "localize")]
+    fn language_loader(
+        &self,
+        crt: &Crate,
+    ) -> Result<Option<Box<dyn i18n_embed::LanguageLoader + Send + Sync>>> {
+        let config = crt.config_or_err()?;
+        let domain = config
+            .fluent
+            .as_ref()
+            .and_then(|fluent_config| fluent_config.domain.clone())
+            .unwrap_or_else(|| crt.module_name());
+        let loader =
+            i18n_embed::fluent::FluentLanguageLoader::new(domain, config.fallback_language.clone());
+        if let Some(path_template) = config
+            .fluent
+            .as_ref()
+            .and_then(|fluent_config| fluent_config.path_template.clone())
+        {
+            loader.set_path_template(Some(i18n_embed::PathTemplate::new(path_template)?));
+        }
+        loader.set_language_aliases(config.language_aliases.clone().into_iter().collect());
+        Ok(Some(Box::new(loader)))
+    }
+}
+
+/// The table in the i18n config file for the localization systems
+/// with resource files at `{assets_dir}/{language}/{domain}.{ext}`,
+/// such as `[qt]` and `[properties]`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ResourcesConfig {
+    /// (Required) The path to the assets directory, relative to the
+    /// crate.
+    pub assets_dir: PathBuf,
+    /// (Optional) Domain name to override default value (i.e. the
+    /// module name of the crate).
+    pub domain: Option<String>,
+}
+
+impl ResourcesConfig {
+    /// Read the table for the system with the `name` from the
+    /// crate's i18n config file, if it has one.
+    fn read(crt: &Crate, name: &str) -> Result<Option<Self>> {
+        Ok(crt.config_or_err()?.system_config(name)?)
+    }
+
+    fn domain(&self, crt: &Crate) -> String {
+        self.domain.clone().unwrap_or_else(|| crt.module_name())
+    }
+
+    /// The paths of the resource files with the `extension` which
+    /// exist for each language in the assets directory.
+    fn resource_files(&self, crt: &Crate, extension: &str) -> Result<Vec<PathBuf>> {
+        let assets_dir = crt.path.join(&self.assets_dir);
+        let file_name = format!("{0}.{1}", self.domain(crt), extension);
+        Ok(fluent_languages(&assets_dir)?
+            .into_iter()
+            .map(|language| assets_dir.join(language.to_string()).join(&file_name))
+            .filter(|path| path.exists())
+            .collect())
+    }
+}
+
+/// The Qt Linguist localization system, used by crates with a `[qt]`
+/// table in their i18n config file (see [ResourcesConfig]), which
+/// share `.ts` files with Qt applications.
+#[derive(Debug, Default)]
+pub struct QtSystem;
+
+impl LocalizationSystem for QtSystem {
+    fn name(&self) -> &str {
+        "qt"
+    }
+
+    fn resource_extensions(&self) -> &[&str] {
+        &["ts"]
+    }
+
+    /// The messages are extracted from the sources of the Qt
+    /// application by `lupdate`, so there is nothing to extract.
+    fn extract(&self, _crt: &Crate, _jobs: NonZeroUsize, _summary: &mut RunSummary) -> Result<()> {
+        Ok(())
+    }
+
+    /// Check that the `.ts` files of each language can be parsed.
+    fn validate(&self, crt: &Crate) -> Result<Vec<ValidationIssue>> {
+        let config = match ResourcesConfig::read(crt, self.name())? {
+            Some(config) => config,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut issues = Vec::new();
+        for path in config.resource_files(crt, "ts")? {
+            let source = match read_utf8_resource_file(&path)? {
+                Ok(source) => source,
+                Err(issue) => {
+                    issues.push(issue);
+                    continue;
+                }
+            };
+            let options = roxmltree::ParsingOptions {
+                allow_dtd: true,
+                ..roxmltree::ParsingOptions::default()
+            };
+            match roxmltree::Document::parse_with_options(&source, options) {
+                Ok(document) if !document.root_element().has_tag_name("TS") => {
+                    issues.push(ValidationIssue {
+                        path: Some(path),
+                        message: tr!("The root element is not <TS>."),
+                        severity: Severity::Error,
+                    })
+                }
+                Ok(_) => {}
+                Err(error) => issues.push(ValidationIssue {
+                    path: Some(path),
+                    message: tr!(
+                        // {0} is the description of an XML syntax error.
+                        "Syntax error: {0}",
+                        error
+                    ),
+                    severity: Severity::Error,
+                }),
+            }
+        }
+        Ok(issues)
+    }
+
+    #[cfg(feature = "localize")]
+    fn language_loader(
+        &self,
+        crt: &Crate,
+    ) -> Result<Option<Box<dyn i18n_embed::LanguageLoader + Send + Sync>>> {
+        let fallback_language = crt.config_or_err()?.fallback_language.clone();
+        Ok(ResourcesConfig::read(crt, self.name())?.map(|config| {
+            Box::new(i18n_embed::qt::QtLanguageLoader::new(
+                config.domain(crt),
+                fallback_language,
+            )) as Box<dyn i18n_embed::LanguageLoader + Send + Sync>
+        }))
+    }
+}
+
+/// The Java `.properties` localization system, used by crates with a
+/// `[properties]` table in their i18n config file (see
+/// [ResourcesConfig]), which share resource bundles with Java
+/// applications.
+#[derive(Debug, Default)]
+pub struct PropertiesSystem;
+
+impl LocalizationSystem for PropertiesSystem {
+    fn name(&self) -> &str {
+        "properties"
+    }
+
+    fn resource_extensions(&self) -> &[&str] {
+        &["properties"]
+    }
+
+    /// The messages of the base bundle are written by hand in the
+    /// file for the fallback language, so there is nothing to
+    /// extract.
+    fn extract(&self, _crt: &Crate, _jobs: NonZeroUsize, _summary: &mut RunSummary) -> Result<()> {
+        Ok(())
+    }
+
+    /// Check that there is a file for the fallback language, which
+    /// contains the base bundle, and that the files of each language
+    /// are encoded using UTF-8.
+    fn validate(&self, crt: &Crate) -> Result<Vec<ValidationIssue>> {
+        let config = match ResourcesConfig::read(crt, self.name())? {
+            Some(config) => config,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut issues = Vec::new();
+        let fallback_path = crt
+            .path
+            .join(&config.assets_dir)
+            .join(crt.config_or_err()?.fallback_language.to_string())
+            .join(format!("{0}.properties", config.domain(crt)));
+        if !fallback_path.exists() {
+            issues.push(ValidationIssue {
+                path: Some(fallback_path),
+                message: tr!("The file for the fallback language does not exist."),
+                severity: Severity::Error,
+            });
+        }
+
+        for path in config.resource_files(crt, "properties")? {
+            if let Err(issue) = read_utf8_resource_file(&path)? {
+                issues.push(issue);
+            }
+        }
+        Ok(issues)
+    }
+
+    #[cfg(feature = "localize")]
+    fn language_loader(
+        &self,
+        crt: &Crate,
+    ) -> Result<Option<Box<dyn i18n_embed::LanguageLoader + Send + Sync>>> {
+        let fallback_language = crt.config_or_err()?.fallback_language.clone();
+        Ok(ResourcesConfig::read(crt, self.name())?.map(|config| {
+            Box::new(i18n_embed::properties::PropertiesLanguageLoader::new(
+                config.domain(crt),
+                fallback_language,
+            )) as Box<dyn i18n_embed::LanguageLoader + Send + Sync>
+        }))
+    }
+}
+
+/// Read the resource file at `path`, or return an issue if it is not
+/// encoded using UTF-8.
+fn read_utf8_resource_file(path: &Path) -> Result<Result<String, ValidationIssue>> {
+    let bytes = std::fs::read(path).with_context(|| {
+        tr!(
+            // {0} is the path to the resource file.
+            "Unable to read \"{0}\".",
+            path.to_string_lossy()
+        )
+    })?;
+    Ok(String::from_utf8(bytes).map_err(|_| ValidationIssue {
+        path: Some(path.to_path_buf()),
+        message: tr!("The file is not encoded using UTF-8."),
+        severity: Severity::Error,
+    }))
+}
+
+type Systems = RwLock<Vec<Arc<dyn LocalizationSystem>>>;
+
+fn registered_systems() -> &'static Systems {
+    static SYSTEMS: OnceLock<Systems> = OnceLock::new();
+    SYSTEMS.get_or_init(|| {
+        RwLock::new(vec![
+            Arc::new(GettextSystem),
+            Arc::new(FluentSystem),
+            Arc::new(QtSystem),
+            Arc::new(PropertiesSystem),
+        ])
+    })
+}
+
+/// Register the `system`, replacing any system previously registered
+/// with the same [LocalizationSystem::name()]. The
+/// [GettextSystem] and [FluentSystem] are registered by default.
+pub fn register_system(system: impl LocalizationSystem + 'static) {
+    let mut systems = registered_systems().write().expect("systems lock poisoned");
+    systems.retain(|registered| registered.name() != system.name());
+    systems.push(Arc::new(system));
+}
+
+/// The localization systems which are currently registered, in the
+/// order they were registered.
+pub fn systems() -> Vec<Arc<dyn LocalizationSystem>> {
+    registered_systems()
+        .read()
+        .expect("systems lock poisoned")
+        .clone()
+}
+
+/// The registered localization system which uses resource files with
+/// the `extension` (e.g. `ftl`), if there is one.
+pub fn system_for_extension(extension: &str) -> Option<Arc<dyn LocalizationSystem>> {
+    systems()
+        .into_iter()
+        .find(|system| system.resource_extensions().contains(&extension))
+}
+
+/// The registered localization systems which are used by the crate.
+pub fn crate_systems(crt: &Crate) -> Result<Vec<Arc<dyn LocalizationSystem>>> {
+    let mut used = Vec::new();
+    for system in systems() {
+        if system.is_used_by(crt)? {
+            used.push(system);
+        }
+    }
+    Ok(used)
+}
+
+/// Check the localization resources of the crate using each of the
+/// registered systems which it uses, see
+/// [LocalizationSystem::validate()].
+pub fn validate(crt: &Crate) -> Result<Vec<ValidationIssue>> {
+    let mut issues = Vec::new();
+    for system in crate_systems(crt)? {
+        issues.extend(system.validate(crt)?);
+    }
+    Ok(issues)
+}
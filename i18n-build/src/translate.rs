@@ -0,0 +1,460 @@
+//! Machine translation of the messages which are missing from the
+//! localizations of a crate, used by the `cargo i18n translate`
+//! command. The translations are added as drafts which need to be
+//! reviewed: fluent messages are preceded by a comment, and gettext
+//! entries are marked as `fuzzy`.
+
+use crate::error::PathError;
+use crate::fluent_impl::{message_ids, parse_fluent_file, FluentLayout};
+use crate::gettext_impl::po::{self, PoEntry};
+use crate::util;
+
+use anyhow::{Context, Result};
+use fluent_syntax::{
+    ast::{Comment, Entry, Expression, Message, Pattern, PatternElement, Resource},
+    serializer,
+};
+use i18n_config::Crate;
+use log::{info, warn};
+use std::{
+    collections::HashSet,
+    fmt::Write,
+    fs::{read_to_string, write},
+    path::{Path, PathBuf},
+};
+use tr::tr;
+use unic_langid::LanguageIdentifier;
+
+/// The comment preceding each fluent message created by [translate()].
+pub const FLUENT_DRAFT_COMMENT: &str = "Machine translated draft, needs review.";
+
+/// A backend used by [translate()] to translate text, such as a
+/// machine translation service or a translation memory.
+pub trait MachineTranslator {
+    /// Translate the `text` from the `source` language into the
+    /// `target` language.
+    ///
+    /// Placeholders in the `text` such as `{0}` need to be preserved
+    /// in the translation, otherwise the translation is discarded.
+    fn translate(
+        &self,
+        text: &str,
+        source: &LanguageIdentifier,
+        target: &LanguageIdentifier,
+    ) -> Result<String>;
+}
+
+/// A [MachineTranslator] which uses the HTTP API of a
+/// [LibreTranslate](https://libretranslate.com/) compatible service.
+///
+/// ⚠️ *This API requires the following crate features to be activated: `translate`.*
+#[cfg(feature = "translate")]
+#[derive(Debug)]
+pub struct HttpTranslator {
+    endpoint: String,
+    api_key: Option<String>,
+    agent: ureq::Agent,
+}
+
+#[cfg(feature = "translate")]
+impl HttpTranslator {
+    /// Create a new translator which sends requests to the
+    /// translation `endpoint` (e.g.
+    /// `https://libretranslate.com/translate`), optionally
+    /// authenticated using the `api_key`.
+    pub fn new<S: Into<String>>(endpoint: S, api_key: Option<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            api_key,
+            agent: ureq::Agent::new(),
+        }
+    }
+
+    /// Create a new translator using the `[translate]` section of a
+    /// crate's i18n config, reading the API key from the environment
+    /// variable named by its `api_key_env`.
+    pub fn from_config(config: &i18n_config::TranslateConfig) -> Result<Self> {
+        let api_key = match &config.api_key_env {
+            Some(api_key_env) => Some(std::env::var(api_key_env).with_context(|| {
+                tr!(
+                    // {0} is the name of the environment variable.
+                    "Unable to read the machine translation API key from the environment variable \"{0}\".",
+                    api_key_env
+                )
+            })?),
+            None => None,
+        };
+        Ok(Self::new(config.endpoint.clone(), api_key))
+    }
+}
+
+#[cfg(feature = "translate")]
+impl MachineTranslator for HttpTranslator {
+    fn translate(
+        &self,
+        text: &str,
+        source: &LanguageIdentifier,
+        target: &LanguageIdentifier,
+    ) -> Result<String> {
+        let mut request = serde_json::json!({
+            "q": text,
+            "source": source.language.as_str(),
+            "target": target.language.as_str(),
+            "format": "text",
+        });
+        if let Some(api_key) = &self.api_key {
+            request["api_key"] = api_key.clone().into();
+        }
+
+        let response: serde_json::Value = self
+            .agent
+            .post(&self.endpoint)
+            .send_json(request)
+            .with_context(|| {
+                tr!(
+                    // {0} is the URL of the machine translation service.
+                    "The machine translation request to \"{0}\" failed.",
+                    self.endpoint
+                )
+            })?
+            .into_json()?;
+
+        response
+            .get("translatedText")
+            .and_then(serde_json::Value::as_str)
+            .map(ToString::to_string)
+            .ok_or_else(|| {
+                anyhow::anyhow!(tr!(
+                    // {0} is the URL of the machine translation service.
+                    "The response from \"{0}\" did not contain a translation.",
+                    self.endpoint
+                ))
+            })
+    }
+}
+
+/// Use the `translator` to add draft translations for the messages
+/// which are missing from the localizations of the crate `crt`:
+///
+/// + For the fluent system, the messages of the `fallback_language`
+///   which are missing from each language are appended to its files,
+///   preceded by a comment ([FLUENT_DRAFT_COMMENT]). Messages
+///   containing select expressions are skipped.
+/// + For the gettext system, the untranslated entries of each
+///   language's `po` file are filled in and marked as `fuzzy`, so
+///   they are not used until they have been reviewed (unless
+///   `use_fuzzy` is enabled). Plural entries are skipped.
+///
+/// Only the specified `languages` are translated, or if it is empty,
+/// every language of the crate. Returns the paths of the files which
+/// were created or modified.
+pub fn translate(
+    crt: &Crate,
+    translator: &dyn MachineTranslator,
+    languages: &[LanguageIdentifier],
+) -> Result<Vec<PathBuf>> {
+    let config = crt.config_or_err()?;
+    let mut changed = Vec::new();
+
+    if let Some(fluent_config) = &config.fluent {
+        let layout = FluentLayout::new(crt, fluent_config)?;
+        let target_languages = if languages.is_empty() {
+            layout.languages()?
+        } else {
+            languages.to_vec()
+        };
+        for language in target_languages
+            .iter()
+            .filter(|language| **language != config.fallback_language)
+        {
+            changed.extend(translate_fluent_language(
+                translator,
+                &layout,
+                &config.fallback_language,
+                language,
+            )?);
+        }
+    }
+
+    if let Some(gettext_config) = &config.gettext {
+        let po_dir = crt.path.join(gettext_config.po_dir());
+        for target_language in &gettext_config.target_languages {
+            let language: LanguageIdentifier = target_language.parse()?;
+            if !(languages.is_empty() || languages.contains(&language)) {
+                continue;
+            }
+            let po_path = po_dir
+                .join(target_language)
+                .join(crt.module_name())
+                .with_extension("po");
+            if !po_path.exists() {
+                info!(
+                    "Skipping \"{0}\" which does not exist yet, it will be created the next time `cargo i18n` is run.",
+                    po_path.to_string_lossy()
+                );
+                continue;
+            }
+            if translate_po_file(translator, &po_path, &config.fallback_language, &language)? {
+                changed.push(po_path);
+            }
+        }
+    }
+
+    Ok(changed)
+}
+
+/// Append draft translations of the fallback language's messages
+/// which are missing from the fluent files of the `language`.
+fn translate_fluent_language(
+    translator: &dyn MachineTranslator,
+    layout: &FluentLayout,
+    fallback_language: &LanguageIdentifier,
+    language: &LanguageIdentifier,
+) -> Result<Vec<PathBuf>> {
+    let mut changed = Vec::new();
+    for file_path in layout.file_paths(fallback_language)? {
+        let fallback_path = layout.path(fallback_language, &file_path);
+        let fallback_resource = parse_fluent_file(&fallback_path)?.unwrap_or_else(|| {
+            warn!(
+                "The fallback language file \"{0}\" does not exist.",
+                fallback_path.to_string_lossy()
+            );
+            Resource { body: Vec::new() }
+        });
+
+        let path = layout.path(language, &file_path);
+        let existing_ids: HashSet<String> = parse_fluent_file(&path)?
+            .map(|resource| message_ids(&resource))
+            .unwrap_or_default();
+
+        let mut drafts = Vec::new();
+        for entry in fallback_resource.body {
+            let message = match entry {
+                Entry::Message(message) if !existing_ids.contains(&message.id.name) => message,
+                _ => continue,
+            };
+            match translate_message(translator, &message, fallback_language, language)? {
+                Some(draft) => drafts.push(Entry::Message(draft)),
+                None => warn!(
+                    "Unable to machine translate the message \"{0}\" into \"{1}\", it needs to be translated manually.",
+                    message.id.name,
+                    language
+                ),
+            }
+        }
+
+        if drafts.is_empty() {
+            continue;
+        }
+
+        let mut contents = if path.exists() {
+            read_to_string(&path).with_context(|| {
+                tr!(
+                    // {0} is the path to the fluent file.
+                    "Unable to read \"{0}\".",
+                    path.to_string_lossy()
+                )
+            })?
+        } else {
+            String::new()
+        };
+        if !contents.is_empty() {
+            if !contents.ends_with('\n') {
+                contents.push('\n');
+            }
+            contents.push('\n');
+        }
+        contents.push_str(&serializer::serialize(&Resource { body: drafts }));
+
+        if let Some(parent) = path.parent() {
+            util::create_dir_all_if_not_exists(parent)?;
+        }
+        write(&path, contents).map_err(|err| PathError::cannot_create_file(&path, err))?;
+        changed.push(path);
+    }
+    Ok(changed)
+}
+
+/// Translate the value and the attributes of the `message`, or return
+/// `None` if any of them can't be translated.
+fn translate_message(
+    translator: &dyn MachineTranslator,
+    message: &Message<String>,
+    source: &LanguageIdentifier,
+    target: &LanguageIdentifier,
+) -> Result<Option<Message<String>>> {
+    let value = match &message.value {
+        Some(value) => match translate_pattern(translator, value, source, target)? {
+            Some(value) => Some(value),
+            None => return Ok(None),
+        },
+        None => None,
+    };
+    let mut attributes = Vec::with_capacity(message.attributes.len());
+    for attribute in &message.attributes {
+        match translate_pattern(translator, &attribute.value, source, target)? {
+            Some(value) => attributes.push(fluent_syntax::ast::Attribute {
+                id: attribute.id.clone(),
+                value,
+            }),
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(Message {
+        id: message.id.clone(),
+        value,
+        attributes,
+        comment: Some(Comment {
+            content: vec![FLUENT_DRAFT_COMMENT.to_string()],
+        }),
+    }))
+}
+
+/// Translate the text of the `pattern`, replacing its placeables with
+/// numbered placeholders (e.g. `{0}`) which are restored after the
+/// translation. Returns `None` if the pattern contains a select
+/// expression, or the translation didn't preserve the placeholders.
+fn translate_pattern(
+    translator: &dyn MachineTranslator,
+    pattern: &Pattern<String>,
+    source: &LanguageIdentifier,
+    target: &LanguageIdentifier,
+) -> Result<Option<Pattern<String>>> {
+    let mut text = String::new();
+    let mut placeables: Vec<&Expression<String>> = Vec::new();
+    for element in &pattern.elements {
+        match element {
+            PatternElement::TextElement { value } => text.push_str(value),
+            PatternElement::Placeable { expression } => {
+                if let Expression::Select { .. } = expression {
+                    return Ok(None);
+                }
+                write!(text, "{{{0}}}", placeables.len())?;
+                placeables.push(expression);
+            }
+        }
+    }
+
+    let translated = translator.translate(&text, source, target)?;
+
+    let mut elements = Vec::new();
+    let mut used = vec![false; placeables.len()];
+    let mut rest = translated.as_str();
+    while let Some(start) = rest.find('{') {
+        push_text_elements(&mut elements, &rest[..start]);
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => return Ok(None),
+        };
+        let index = match rest[start + 1..end].trim().parse::<usize>() {
+            Ok(index) if index < placeables.len() && !used[index] => index,
+            _ => return Ok(None),
+        };
+        used[index] = true;
+        elements.push(PatternElement::Placeable {
+            expression: placeables[index].clone(),
+        });
+        rest = &rest[end + 1..];
+    }
+    if rest.contains('}') || !used.iter().all(|used| *used) {
+        return Ok(None);
+    }
+    push_text_elements(&mut elements, rest);
+
+    if elements.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(Pattern { elements }))
+}
+
+/// Push the `text` as text elements, one for each line, which is how
+/// the serializer expects multiline text.
+fn push_text_elements(elements: &mut Vec<PatternElement<String>>, text: &str) {
+    elements.extend(
+        text.split_inclusive('\n')
+            .map(|line| PatternElement::TextElement {
+                value: line.to_string(),
+            }),
+    );
+}
+
+/// Fill in the untranslated entries of the `po` file at `po_path`
+/// with fuzzy translations. Returns whether the file was modified.
+fn translate_po_file(
+    translator: &dyn MachineTranslator,
+    po_path: &Path,
+    source: &LanguageIdentifier,
+    target: &LanguageIdentifier,
+) -> Result<bool> {
+    let po = read_to_string(po_path).with_context(|| {
+        tr!(
+            // {0} is the path to the po file.
+            "Unable to read \"{0}\".",
+            po_path.to_string_lossy()
+        )
+    })?;
+
+    let mut modified = false;
+    let mut entries = Vec::new();
+    for entry in po::entries(&po) {
+        match translate_po_entry(translator, entry, source, target)? {
+            Some(translated) => {
+                modified = true;
+                entries.push(translated);
+            }
+            None => entries.push(entry.to_string()),
+        }
+    }
+
+    if modified {
+        write(po_path, entries.join("\n\n"))
+            .map_err(|err| PathError::cannot_create_file(po_path, err))?;
+    }
+    Ok(modified)
+}
+
+/// Translate the po `entry` if it is untranslated, returning the
+/// entry with the translation and the `fuzzy` flag.
+fn translate_po_entry(
+    translator: &dyn MachineTranslator,
+    entry: &str,
+    source: &LanguageIdentifier,
+    target: &LanguageIdentifier,
+) -> Result<Option<String>> {
+    let entry = PoEntry::parse(entry);
+    if entry.is_obsolete() || entry.is_plural() || entry.is_header() {
+        return Ok(None);
+    }
+    let msgid = match (entry.msgid(), entry.msgstr()) {
+        (Some(msgid), Some(msgstr)) if msgstr.is_empty() => msgid,
+        _ => return Ok(None),
+    };
+
+    let translation = translator.translate(&msgid, source, target)?;
+    if translation.trim().is_empty() || placeholders(&translation) != placeholders(&msgid) {
+        warn!(
+            "Unable to machine translate \"{0}\" into \"{1}\", it needs to be translated manually.",
+            msgid, target
+        );
+        return Ok(None);
+    }
+
+    Ok(Some(entry.with_msgstr(&translation, true)))
+}
+
+/// The placeholders (e.g. `{0}` or `{name}`) in the `text`, sorted.
+fn placeholders(text: &str) -> Vec<&str> {
+    let mut placeholders = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        match rest[start..].find('}') {
+            Some(end) => {
+                placeholders.push(&rest[start..=start + end]);
+                rest = &rest[start + end + 1..];
+            }
+            None => break,
+        }
+    }
+    placeholders.sort_unstable();
+    placeholders
+}
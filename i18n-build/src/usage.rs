@@ -0,0 +1,172 @@
+//! Finding where the fluent messages of a crate are used in its
+//! source code, by scanning the source files for `fl!()` calls. This
+//! is used by `cargo i18n where`, to add the locations of the
+//! messages to the fluent files created by `cargo i18n add-language`,
+//! and to copy the comments for translators preceding the calls to
+//! the fluent files, to give translators the context of each message.
+
+use crate::migrate::{skip_literal, split_arguments, string_literal};
+use crate::util;
+
+use anyhow::{Context, Result};
+use i18n_config::Crate;
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+use tr::tr;
+
+/// A location in the source code of a crate.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SourceLocation {
+    /// The path of the source file, relative to the crate.
+    pub path: PathBuf,
+    /// The line number, starting from 1.
+    pub line: usize,
+}
+
+impl Display for SourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = self
+            .path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        write!(f, "{0}:{1}", path, self.line)
+    }
+}
+
+/// The locations of the `fl!()` calls in the source files (in the
+/// [source_dirs](Crate::source_dirs)) of the crate `crt`, for each of the message ids
+/// they reference, in the order of the files and lines. Calls whose
+/// message id is not a string literal are ignored.
+pub fn message_usages(crt: &Crate) -> Result<BTreeMap<String, Vec<SourceLocation>>> {
+    let mut usages: BTreeMap<String, Vec<SourceLocation>> = BTreeMap::new();
+    for_each_fl_call(crt, |path, source, id, start| {
+        usages.entry(id).or_default().push(SourceLocation {
+            path: path.to_path_buf(),
+            line: source[..start].matches('\n').count() + 1,
+        });
+    })?;
+    Ok(usages)
+}
+
+/// The comments for translators preceding the `fl!()` calls in the
+/// source files of the crate `crt`, for each of the message ids they
+/// reference. A comment is the part of the block of line comments
+/// (`//` or `///`) directly above the line of the call which starts
+/// with the `tag` (e.g. `// i18n: Shown on the login screen.`), with
+/// the tag removed. Identical comments for the same message are only
+/// included once.
+pub fn message_comments(crt: &Crate, tag: &str) -> Result<BTreeMap<String, Vec<String>>> {
+    let mut comments: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for_each_fl_call(crt, |_, source, id, start| {
+        if let Some(comment) = preceding_comment(source, start, tag) {
+            let message_comments = comments.entry(id).or_default();
+            if !message_comments.contains(&comment) {
+                message_comments.push(comment);
+            }
+        }
+    })?;
+    Ok(comments)
+}
+
+/// Call `f` with the path (relative to the crate) and contents of
+/// each of the source files of the crate `crt`, the message id of each
+/// of the `fl!()` calls in the file, and the byte index where the
+/// call starts.
+fn for_each_fl_call(crt: &Crate, mut f: impl FnMut(&Path, &str, String, usize)) -> Result<()> {
+    let ignore = crt
+        .i18n_config
+        .as_ref()
+        .and_then(|config| config.fluent.as_ref())
+        .map(|fluent_config| fluent_config.ignore.as_slice())
+        .unwrap_or_default();
+    let mut paths = Vec::new();
+    for source_dir in &crt.source_dirs {
+        paths.extend(util::rust_source_files(
+            crt,
+            &crt.path.join(source_dir),
+            ignore,
+        )?);
+    }
+    for path in &paths {
+        let path = path.as_path();
+        let source = read_to_string(path).with_context(|| {
+            tr!(
+                // {0} is the path to a source file of the crate.
+                "Unable to read \"{0}\".",
+                path.to_string_lossy()
+            )
+        })?;
+        let relative_path = path.strip_prefix(&crt.path).unwrap_or(path);
+        for (id, start) in find_fl_calls(&source) {
+            f(relative_path, &source, id, start);
+        }
+    }
+    Ok(())
+}
+
+/// The comment starting with the `tag` in the block of line comments
+/// directly above the line containing byte `start` of the `source`,
+/// without the tag, joining its lines with newlines.
+fn preceding_comment(source: &str, start: usize, tag: &str) -> Option<String> {
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let mut block: Vec<&str> = source[..line_start]
+        .lines()
+        .rev()
+        .map_while(|line| {
+            let comment = line.trim_start().strip_prefix("//")?;
+            Some(comment.strip_prefix('/').unwrap_or(comment).trim())
+        })
+        .collect();
+    block.reverse();
+
+    let tagged = block.iter().position(|line| line.starts_with(tag))?;
+    let mut lines = block[tagged..].to_vec();
+    lines[0] = lines[0][tag.len()..].trim_start();
+    let comment = lines.join("\n").trim().to_string();
+    (!comment.is_empty()).then_some(comment)
+}
+
+/// Find the `fl!()` calls in the Rust `source`, returning the message
+/// id of each call and the byte index where it starts.
+fn find_fl_calls(source: &str) -> Vec<(String, usize)> {
+    let bytes = source.as_bytes();
+    let mut calls = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if let Some(end) = skip_literal(source, i) {
+            i = end;
+            continue;
+        }
+        let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+        if source[i..].starts_with("fl") && (i == 0 || !is_ident(bytes[i - 1])) {
+            let after = source[i + 2..].trim_start();
+            if let Some(after) = after.strip_prefix('!') {
+                let open = source.len() - after.trim_start().len();
+                if matches!(bytes.get(open), Some(b'(' | b'[' | b'{')) {
+                    if let Some((arguments, _)) = split_arguments(source, open) {
+                        // The first argument is the loader, the second
+                        // is the message id.
+                        let id = arguments
+                            .get(1)
+                            .and_then(|range| string_literal(source[range.clone()].trim()));
+                        if let Some((id, _)) = id {
+                            calls.push((id, i));
+                        }
+                        // Continue inside the arguments, which may
+                        // contain more calls.
+                        i = open + 1;
+                        continue;
+                    }
+                }
+            }
+        }
+        i += source[i..].chars().next().map_or(1, char::len_utf8);
+    }
+    calls
+}
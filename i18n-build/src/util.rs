@@ -1,18 +1,29 @@
 //! Utility functions for use with the `i18n_build` library.
 
 use log::debug;
-use std::fs::{create_dir_all, remove_file, rename};
-use std::path::Path;
-use std::process::Command;
+use std::fs::{create_dir_all, read_to_string, remove_file, rename, write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 
-use crate::error::PathError;
-use anyhow::{ensure, Context, Result};
+use crate::error::{CommandError, PathError, ToolDiagnostic};
+use anyhow::{Context, Result};
+use i18n_config::Crate;
+use ignore::{overrides::OverrideBuilder, WalkBuilder};
 use tr::tr;
 
-/// Run the specified command, check that it's output was reported as successful.
+/// Run the specified command, check that it's output was reported as
+/// successful. The output of the command is captured, and passed
+/// through if it was successful. Otherwise a [CommandError] is
+/// returned with the output, and the problems with specific files
+/// which were reported in it (see [ToolDiagnostic]).
 pub fn run_command_and_check_success(command_name: &str, mut command: Command) -> Result<()> {
     debug!("Running command: {0:?}", &command);
     let output = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
         .spawn()
         .with_context(|| tr!("The \"{0}\" command was unable to start.", command_name))?
         .wait_with_output()
@@ -23,13 +34,23 @@ pub fn run_command_and_check_success(command_name: &str, mut command: Command) -
             )
         })?;
 
-    ensure!(
-        output.status.success(),
-        tr!(
-            "The \"{0}\" command reported that it was unsuccessful.",
-            command_name
-        )
-    );
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    if !output.status.success() {
+        let mut diagnostics = ToolDiagnostic::parse(command_name, &stderr);
+        diagnostics.extend(ToolDiagnostic::parse(command_name, &stdout));
+        return Err(CommandError {
+            command_name: command_name.to_string(),
+            status: output.status,
+            stdout,
+            stderr,
+            diagnostics,
+        }
+        .into());
+    }
+
+    print!("{stdout}");
+    eprint!("{stderr}");
     Ok(())
 }
 
@@ -76,3 +97,149 @@ pub fn rename_file<P1: AsRef<Path>, P2: AsRef<Path>>(from: P1, to: P2) -> Result
     rename(from_ref, to_ref)
         .map_err(|e| PathError::cannot_rename_file(from_ref.to_path_buf(), to_ref.to_path_buf(), e))
 }
+
+/// The path of the attic file for the file at `path`, which is next
+/// to it with `.attic` appended to its name, see
+/// [ObsoletePolicy::Attic](i18n_config::ObsoletePolicy::Attic).
+///
+/// ```
+/// use i18n_build::util::attic_file_path;
+/// use std::path::{Path, PathBuf};
+///
+/// assert_eq!(
+///     PathBuf::from("i18n/po/fr/my_crate.po.attic"),
+///     attic_file_path(Path::new("i18n/po/fr/my_crate.po"))
+/// );
+/// ```
+pub fn attic_file_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".attic");
+    path.with_file_name(file_name)
+}
+
+/// Append the `entries` (separated by blank lines) to the attic file
+/// of the file at `path` (see [attic_file_path()]), creating it if it
+/// doesn't exist.
+pub(crate) fn append_to_attic(path: &Path, entries: &str) -> Result<()> {
+    let attic_path = attic_file_path(path);
+    let mut contents = if attic_path.exists() {
+        read_to_string(&attic_path).with_context(|| {
+            tr!(
+                // {0} is the path to the attic file.
+                "Unable to read \"{0}\".",
+                attic_path.to_string_lossy()
+            )
+        })?
+    } else {
+        String::new()
+    };
+    if !contents.is_empty() {
+        if !contents.ends_with('\n') {
+            contents.push('\n');
+        }
+        contents.push('\n');
+    }
+    contents.push_str(entries);
+    if !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    write(&attic_path, contents).map_err(|e| PathError::cannot_create_file(&attic_path, e))?;
+    Ok(())
+}
+
+/// The paths of the rust source files within the `src_dir` of the
+/// crate `crt`, sorted by their path. The files ignored by
+/// `.gitignore` files (even outside of a git repository) and hidden
+/// files are skipped, as are the files matching one of the `ignore`
+/// glob patterns, which are relative to the crate (e.g.
+/// `"**/generated/**"`).
+pub fn rust_source_files(crt: &Crate, src_dir: &Path, ignore: &[String]) -> Result<Vec<PathBuf>> {
+    let mut overrides = OverrideBuilder::new(&crt.path);
+    for pattern in ignore {
+        // Override globs are included unless they are negated.
+        overrides.add(&format!("!{pattern}")).with_context(|| {
+            tr!(
+                // {0} is a glob pattern from the `ignore` list in the i18n config.
+                "Invalid ignore pattern \"{0}\".",
+                pattern
+            )
+        })?;
+    }
+
+    let mut files = Vec::new();
+    let walker = WalkBuilder::new(src_dir)
+        .overrides(overrides.build()?)
+        .require_git(false)
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+    for result in walker {
+        let entry = result.with_context(|| {
+            tr!(
+                // {0} is the path to the source code directory of the crate.
+                "Unable to read the source directory \"{0}\".",
+                src_dir.to_string_lossy()
+            )
+        })?;
+        let is_file = entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file());
+        if is_file
+            && entry
+                .path()
+                .extension()
+                .is_some_and(|extension| extension == "rs")
+        {
+            files.push(entry.into_path());
+        }
+    }
+    Ok(files)
+}
+
+/// The default number of jobs used to run commands in parallel,
+/// which is the available parallelism of the system.
+pub fn default_jobs() -> NonZeroUsize {
+    std::thread::available_parallelism().unwrap_or(NonZeroUsize::MIN)
+}
+
+/// Call `f` for each of the `items` using up to `jobs` threads,
+/// returning the results in the same order as the `items`. If any of
+/// the calls fail, the error for the first failing item is returned.
+pub fn run_parallel<T, R, F>(jobs: NonZeroUsize, items: &[T], f: F) -> Result<Vec<R>>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Result<R> + Sync,
+{
+    if jobs.get() == 1 || items.len() <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next_index = AtomicUsize::new(0);
+    let results: Vec<Mutex<Option<Result<R>>>> = items.iter().map(|_| Mutex::new(None)).collect();
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.get().min(items.len()) {
+            scope.spawn(|| loop {
+                let index = next_index.fetch_add(1, Ordering::Relaxed);
+                let item = match items.get(index) {
+                    Some(item) => item,
+                    None => break,
+                };
+                let result = f(item);
+                *results[index]
+                    .lock()
+                    .expect("expected result lock not to be poisoned") = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|result| {
+            result
+                .into_inner()
+                .expect("expected result lock not to be poisoned")
+                .expect("expected every item to have been processed")
+        })
+        .collect()
+}
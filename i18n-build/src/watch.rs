@@ -2,6 +2,7 @@
 //! this library.
 
 use crate::error::{PathError, PathType};
+use i18n_config::{Crate, I18nConfigError};
 use std::path::Path;
 
 use anyhow::{anyhow, Result};
@@ -39,3 +40,54 @@ pub fn cargo_rerun_if_dir_changed(path: &Path) -> Result<()> {
 
     Ok(())
 }
+
+/// Tell `Cargo` to rerun the build script that calls this function
+/// (upon rebuild) if any of the inputs of the i18n build process (see
+/// [run()](crate::run())) for the specified crate change: its i18n
+/// config file, the source code of the crate and its subcrates when
+/// using the `gettext` system, the `po` files of the `gettext`
+/// system, and the assets of the `fluent` system.
+///
+/// Directories which don't exist are skipped, because `Cargo` would
+/// otherwise rerun the build script on every build.
+///
+/// ```no_run
+/// use i18n_config::Crate;
+///
+/// let crt = Crate::from(".", None, "i18n.toml").unwrap();
+/// i18n_build::emit_cargo_rerun_directives(&crt).unwrap();
+/// i18n_build::run(crt).unwrap();
+/// ```
+pub fn emit_cargo_rerun_directives(crt: &Crate) -> Result<()> {
+    let (config_crate, config) = crt
+        .active_config()?
+        .ok_or_else(|| I18nConfigError::NoI18nConfig(crt.to_string()))?;
+
+    cargo_rerun_if_changed(&config_crate.path.join(&config_crate.config_file_path))?;
+
+    let mut dirs = Vec::new();
+    if let Some(gettext_config) = &config.gettext {
+        dirs.push(crt.path.join("src"));
+        dirs.push(config_crate.path.join(gettext_config.po_dir()));
+    }
+    if let Some(fluent_config) = &config.fluent {
+        dirs.push(config_crate.path.join(&fluent_config.assets_dir));
+    }
+    for dir in dirs {
+        // Cargo checks the contents of directories recursively.
+        if dir.exists() {
+            cargo_rerun_if_changed(&dir)?;
+        }
+    }
+
+    for subcrate_path in crt.subcrate_paths() {
+        let subcrate = Crate::from(
+            crt.path.join(subcrate_path),
+            Some(crt),
+            crt.config_file_path.clone(),
+        )?;
+        emit_cargo_rerun_directives(&subcrate)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,819 @@
+//! Exchanging the localizations of a crate with translators using
+//! [XLIFF](https://en.wikipedia.org/wiki/XLIFF) files, used by the
+//! `cargo i18n export` and `cargo i18n import` commands.
+//!
+//! For the fluent system, each message value and attribute (with the
+//! id `message-id.attribute-id`) is a translation unit, whose source
+//! and target are written using fluent syntax, e.g. `Hello { $name }!`.
+//! For the gettext system, each entry of the language's `po` file is a
+//! translation unit, with its `msgctxt` as a note in the `context`
+//! category. Plural entries are not exported.
+
+use crate::error::PathError;
+use crate::fluent_impl::{parse_fluent_file, FluentLayout};
+use crate::gettext_impl::po::{self, PoEntry};
+use crate::translate::FLUENT_DRAFT_COMMENT;
+use crate::util;
+
+use anyhow::{anyhow, Context, Result};
+use fluent_syntax::{
+    ast::{Attribute, Comment, Entry, Identifier, Message, Pattern, Resource},
+    parser, serializer,
+};
+use i18n_config::Crate;
+use log::warn;
+use std::{
+    collections::HashMap,
+    fmt::{Display, Write},
+    fs::{read_to_string, write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tr::tr;
+use unic_langid::LanguageIdentifier;
+
+/// The category of the note containing the `msgctxt` of a gettext
+/// translation unit.
+const CONTEXT_NOTE_CATEGORY: &str = "context";
+
+/// The version of the XLIFF standard used by an [XliffDocument].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XliffVersion {
+    /// XLIFF 1.2
+    V1_2,
+    /// XLIFF 2.0
+    V2_0,
+}
+
+impl Display for XliffVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            XliffVersion::V1_2 => write!(f, "1.2"),
+            XliffVersion::V2_0 => write!(f, "2.0"),
+        }
+    }
+}
+
+impl FromStr for XliffVersion {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "1.2" => Ok(XliffVersion::V1_2),
+            "2.0" => Ok(XliffVersion::V2_0),
+            _ => Err(anyhow!(tr!(
+                // {0} is the XLIFF version which was requested.
+                "Unsupported XLIFF version \"{0}\", expected \"1.2\" or \"2.0\".",
+                s
+            ))),
+        }
+    }
+}
+
+/// A message to be translated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranslationUnit {
+    /// The id of the unit, unique within its [XliffFile].
+    pub id: String,
+    /// The gettext `msgctxt` of the message.
+    pub context: Option<String>,
+    /// The message in the source language.
+    pub source: String,
+    /// The translation of the message, if it has been translated.
+    pub target: Option<String>,
+    /// Whether the translation needs to be reviewed (a fuzzy gettext
+    /// entry, or a machine translated fluent message).
+    pub needs_review: bool,
+    /// Comments describing the message for translators.
+    pub notes: Vec<String>,
+}
+
+/// The translation units of a single localization resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XliffFile {
+    /// The path of the resource, relative to the directory of the
+    /// target language.
+    pub original: String,
+    /// The messages in the resource.
+    pub units: Vec<TranslationUnit>,
+}
+
+/// The contents of an XLIFF file, containing the messages of a crate
+/// to be translated into a single language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XliffDocument {
+    /// The version of the XLIFF standard used when writing this
+    /// document.
+    pub version: XliffVersion,
+    /// The language of the messages' source text.
+    pub source_language: LanguageIdentifier,
+    /// The language of the messages' translations.
+    pub target_language: LanguageIdentifier,
+    /// The localization resources.
+    pub files: Vec<XliffFile>,
+}
+
+impl XliffDocument {
+    /// Write this document as XLIFF.
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        match self.version {
+            XliffVersion::V1_2 => self.write_v1_2(&mut xml),
+            XliffVersion::V2_0 => self.write_v2_0(&mut xml),
+        }
+        .expect("writing to a String should not fail");
+        xml
+    }
+
+    fn write_v1_2(&self, xml: &mut String) -> std::fmt::Result {
+        writeln!(
+            xml,
+            "<xliff version=\"1.2\" xmlns=\"urn:oasis:names:tc:xliff:document:1.2\">"
+        )?;
+        for file in &self.files {
+            writeln!(
+                xml,
+                "  <file original=\"{0}\" source-language=\"{1}\" target-language=\"{2}\" datatype=\"plaintext\">",
+                escape_xml(&file.original),
+                self.source_language,
+                self.target_language
+            )?;
+            writeln!(xml, "    <body>")?;
+            for unit in &file.units {
+                writeln!(
+                    xml,
+                    "      <trans-unit id=\"{0}\" xml:space=\"preserve\">",
+                    escape_xml(&unit.id)
+                )?;
+                writeln!(
+                    xml,
+                    "        <source>{0}</source>",
+                    escape_xml(&unit.source)
+                )?;
+                if let Some(target) = &unit.target {
+                    writeln!(
+                        xml,
+                        "        <target state=\"{0}\">{1}</target>",
+                        if unit.needs_review {
+                            "needs-review-translation"
+                        } else {
+                            "translated"
+                        },
+                        escape_xml(target)
+                    )?;
+                }
+                if let Some(context) = &unit.context {
+                    writeln!(
+                        xml,
+                        "        <note from=\"{CONTEXT_NOTE_CATEGORY}\">{0}</note>",
+                        escape_xml(context)
+                    )?;
+                }
+                for note in &unit.notes {
+                    writeln!(xml, "        <note>{0}</note>", escape_xml(note))?;
+                }
+                writeln!(xml, "      </trans-unit>")?;
+            }
+            writeln!(xml, "    </body>")?;
+            writeln!(xml, "  </file>")?;
+        }
+        writeln!(xml, "</xliff>")
+    }
+
+    fn write_v2_0(&self, xml: &mut String) -> std::fmt::Result {
+        writeln!(
+            xml,
+            "<xliff version=\"2.0\" xmlns=\"urn:oasis:names:tc:xliff:document:2.0\" srcLang=\"{0}\" trgLang=\"{1}\">",
+            self.source_language, self.target_language
+        )?;
+        for (i, file) in self.files.iter().enumerate() {
+            writeln!(
+                xml,
+                "  <file id=\"f{0}\" original=\"{1}\">",
+                i + 1,
+                escape_xml(&file.original)
+            )?;
+            for unit in &file.units {
+                writeln!(xml, "    <unit id=\"{0}\">", escape_xml(&unit.id))?;
+                if unit.context.is_some() || !unit.notes.is_empty() {
+                    writeln!(xml, "      <notes>")?;
+                    if let Some(context) = &unit.context {
+                        writeln!(
+                            xml,
+                            "        <note category=\"{CONTEXT_NOTE_CATEGORY}\">{0}</note>",
+                            escape_xml(context)
+                        )?;
+                    }
+                    for note in &unit.notes {
+                        writeln!(xml, "        <note>{0}</note>", escape_xml(note))?;
+                    }
+                    writeln!(xml, "      </notes>")?;
+                }
+                let state = match (&unit.target, unit.needs_review) {
+                    (None, _) | (Some(_), true) => "initial",
+                    (Some(_), false) => "translated",
+                };
+                writeln!(xml, "      <segment state=\"{state}\">")?;
+                writeln!(
+                    xml,
+                    "        <source xml:space=\"preserve\">{0}</source>",
+                    escape_xml(&unit.source)
+                )?;
+                if let Some(target) = &unit.target {
+                    writeln!(
+                        xml,
+                        "        <target xml:space=\"preserve\">{0}</target>",
+                        escape_xml(target)
+                    )?;
+                }
+                writeln!(xml, "      </segment>")?;
+                writeln!(xml, "    </unit>")?;
+            }
+            writeln!(xml, "  </file>")?;
+        }
+        writeln!(xml, "</xliff>")
+    }
+
+    /// Read a document from XLIFF 1.2 or 2.0.
+    pub fn from_xml(xml: &str) -> Result<Self> {
+        let document = roxmltree::Document::parse(xml)
+            .with_context(|| tr!("Unable to parse the XLIFF document."))?;
+        let root = document.root_element();
+        if root.tag_name().name() != "xliff" {
+            return Err(anyhow!(tr!(
+                "The document is not an XLIFF document, expected an <xliff> root element."
+            )));
+        }
+        let version: XliffVersion = root.attribute("version").unwrap_or_default().parse()?;
+
+        let (source_language, target_language) = match version {
+            XliffVersion::V1_2 => {
+                let file = child_elements(root, "file").next();
+                (
+                    file.and_then(|file| file.attribute("source-language")),
+                    file.and_then(|file| file.attribute("target-language")),
+                )
+            }
+            XliffVersion::V2_0 => (root.attribute("srcLang"), root.attribute("trgLang")),
+        };
+        let parse_language = |language: Option<&str>| -> Result<LanguageIdentifier> {
+            let language = language.ok_or_else(|| {
+                anyhow!(tr!(
+                    "The XLIFF document does not specify its source and target languages."
+                ))
+            })?;
+            Ok(language.parse()?)
+        };
+
+        let files = child_elements(root, "file")
+            .map(|file| XliffFile {
+                original: file.attribute("original").unwrap_or_default().to_string(),
+                units: match version {
+                    XliffVersion::V1_2 => file
+                        .descendants()
+                        .filter(|node| node.has_tag_name("trans-unit"))
+                        .map(read_unit_v1_2)
+                        .collect(),
+                    XliffVersion::V2_0 => file
+                        .descendants()
+                        .filter(|node| node.has_tag_name("unit"))
+                        .map(read_unit_v2_0)
+                        .collect(),
+                },
+            })
+            .collect();
+
+        Ok(Self {
+            version,
+            source_language: parse_language(source_language)?,
+            target_language: parse_language(target_language)?,
+            files,
+        })
+    }
+}
+
+fn read_unit_v1_2(unit: roxmltree::Node<'_, '_>) -> TranslationUnit {
+    let target = child_elements(unit, "target").next();
+    let (context, notes) = read_notes(child_elements(unit, "note"), "from");
+    TranslationUnit {
+        id: unit.attribute("id").unwrap_or_default().to_string(),
+        context,
+        source: child_elements(unit, "source")
+            .next()
+            .map(element_text)
+            .unwrap_or_default(),
+        target: target.map(element_text),
+        needs_review: target
+            .and_then(|target| target.attribute("state"))
+            .is_some_and(|state| state.starts_with("needs-")),
+        notes,
+    }
+}
+
+fn read_unit_v2_0(unit: roxmltree::Node<'_, '_>) -> TranslationUnit {
+    let (context, notes) = read_notes(
+        child_elements(unit, "notes").flat_map(|notes| child_elements(notes, "note")),
+        "category",
+    );
+    let segments: Vec<_> = child_elements(unit, "segment").collect();
+    let text = |name: &str| -> Option<String> {
+        let texts: Vec<String> = segments
+            .iter()
+            .filter_map(|segment| child_elements(*segment, name).next())
+            .map(element_text)
+            .collect();
+        (!texts.is_empty()).then(|| texts.concat())
+    };
+    TranslationUnit {
+        id: unit.attribute("id").unwrap_or_default().to_string(),
+        context,
+        source: text("source").unwrap_or_default(),
+        target: text("target"),
+        needs_review: segments
+            .iter()
+            .any(|segment| segment.attribute("state") == Some("initial")),
+        notes,
+    }
+}
+
+/// Read the `notes`, separating the note containing the gettext
+/// context (identified by the `category_attribute`) from the others.
+fn read_notes<'a, 'input: 'a>(
+    notes: impl Iterator<Item = roxmltree::Node<'a, 'input>>,
+    category_attribute: &str,
+) -> (Option<String>, Vec<String>) {
+    let mut context = None;
+    let mut other = Vec::new();
+    for note in notes {
+        if note.attribute(category_attribute) == Some(CONTEXT_NOTE_CATEGORY) {
+            context = Some(element_text(note));
+        } else {
+            other.push(element_text(note));
+        }
+    }
+    (context, other)
+}
+
+fn child_elements<'a, 'input: 'a>(
+    node: roxmltree::Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'input>> + 'a {
+    node.children()
+        .filter(move |child| child.is_element() && child.tag_name().name() == name)
+}
+
+/// The text content of the `element`, including the text of any
+/// inline elements.
+fn element_text(element: roxmltree::Node<'_, '_>) -> String {
+    element
+        .descendants()
+        .filter(|node| node.is_text())
+        .filter_map(|node| node.text())
+        .collect()
+}
+
+pub(crate) fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The languages of the crate `crt` which can be exported using
+/// [export()]: the languages in the fluent assets directory, or the
+/// gettext `target_languages` (excluding the fallback language).
+pub fn export_languages(crt: &Crate) -> Result<Vec<LanguageIdentifier>> {
+    let config = crt.config_or_err()?;
+    let mut languages = Vec::new();
+    if let Some(fluent_config) = &config.fluent {
+        languages.extend(FluentLayout::new(crt, fluent_config)?.languages()?);
+    }
+    if let Some(gettext_config) = &config.gettext {
+        for language in &gettext_config.target_languages {
+            let language: LanguageIdentifier = language.parse()?;
+            if !languages.contains(&language) {
+                languages.push(language);
+            }
+        }
+    }
+    languages.retain(|language| *language != config.fallback_language);
+    Ok(languages)
+}
+
+/// Export the messages of the crate `crt` and their translations into
+/// the `language` as an XLIFF document, which can be written using
+/// [XliffDocument::to_xml()].
+pub fn export(
+    crt: &Crate,
+    language: &LanguageIdentifier,
+    version: XliffVersion,
+) -> Result<XliffDocument> {
+    let config = crt.config_or_err()?;
+    let mut files = Vec::new();
+
+    if let Some(fluent_config) = &config.fluent {
+        let layout = FluentLayout::new(crt, fluent_config)?;
+        for file_path in layout.file_paths(&config.fallback_language)? {
+            let source =
+                match parse_fluent_file(&layout.path(&config.fallback_language, &file_path))? {
+                    Some(source) => source,
+                    None => continue,
+                };
+            let target = parse_fluent_file(&layout.path(language, &file_path))?;
+            files.push(XliffFile {
+                original: original_path(&file_path),
+                units: fluent_units(&source, target.as_ref()),
+            });
+        }
+    }
+
+    if let Some(gettext_config) = &config.gettext {
+        let po_path = crt
+            .path
+            .join(gettext_config.po_dir())
+            .join(language.to_string())
+            .join(crt.module_name())
+            .with_extension("po");
+        util::check_path_exists(&po_path)?;
+        let po = read_to_string(&po_path).with_context(|| {
+            tr!(
+                // {0} is the path to the po file.
+                "Unable to read \"{0}\".",
+                po_path.to_string_lossy()
+            )
+        })?;
+        files.push(XliffFile {
+            original: format!("{0}.po", crt.module_name()),
+            units: po_units(&po),
+        });
+    }
+
+    Ok(XliffDocument {
+        version,
+        source_language: config.fallback_language.clone(),
+        target_language: language.clone(),
+        files,
+    })
+}
+
+fn original_path(file_path: &Path) -> String {
+    file_path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn fluent_units(
+    source: &Resource<String>,
+    target: Option<&Resource<String>>,
+) -> Vec<TranslationUnit> {
+    let target_messages: HashMap<&str, &Message<String>> = target
+        .map(|target| {
+            target
+                .body
+                .iter()
+                .filter_map(|entry| match entry {
+                    Entry::Message(message) => Some((message.id.name.as_str(), message)),
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut units = Vec::new();
+    for entry in &source.body {
+        let message = match entry {
+            Entry::Message(message) => message,
+            _ => continue,
+        };
+        let target_message = target_messages.get(message.id.name.as_str());
+        let needs_review = target_message
+            .and_then(|message| message.comment.as_ref())
+            .is_some_and(|comment| {
+                comment
+                    .content
+                    .iter()
+                    .any(|line| line == FLUENT_DRAFT_COMMENT)
+            });
+        let notes: Vec<String> = message
+            .comment
+            .iter()
+            .map(|comment| comment.content.join("\n"))
+            .collect();
+
+        if let Some(value) = &message.value {
+            units.push(TranslationUnit {
+                id: message.id.name.clone(),
+                context: None,
+                source: pattern_text(value),
+                target: target_message
+                    .and_then(|message| message.value.as_ref())
+                    .map(pattern_text),
+                needs_review,
+                notes: notes.clone(),
+            });
+        }
+        for attribute in &message.attributes {
+            units.push(TranslationUnit {
+                id: format!("{0}.{1}", message.id.name, attribute.id.name),
+                context: None,
+                source: pattern_text(&attribute.value),
+                target: target_message
+                    .and_then(|message| {
+                        message
+                            .attributes
+                            .iter()
+                            .find(|target| target.id.name == attribute.id.name)
+                    })
+                    .map(|attribute| pattern_text(&attribute.value)),
+                needs_review,
+                notes: notes.clone(),
+            });
+        }
+    }
+    units
+}
+
+/// Write the `pattern` using fluent syntax, without the indentation
+/// of its continuation lines.
+fn pattern_text(pattern: &Pattern<String>) -> String {
+    let resource = Resource {
+        body: vec![Entry::Message(Message {
+            id: Identifier {
+                name: "x".to_string(),
+            },
+            value: Some(pattern.clone()),
+            attributes: Vec::new(),
+            comment: None,
+        })],
+    };
+    let serialized = serializer::serialize(&resource);
+    let value = serialized
+        .strip_prefix("x =")
+        .expect("expected the serialized message to start with its id");
+    match value.strip_prefix('\n') {
+        Some(multiline) => multiline
+            .lines()
+            .map(|line| line.strip_prefix("    ").unwrap_or(line))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        None => value
+            .trim_start_matches(' ')
+            .trim_end_matches('\n')
+            .to_string(),
+    }
+}
+
+/// Parse the `text` of a pattern written using fluent syntax.
+fn parse_pattern(text: &str) -> Option<Pattern<String>> {
+    let source = format!("x =\n    {0}\n", text.replace('\n', "\n    "));
+    let resource = parser::parse(source).ok()?;
+    match resource.body.into_iter().next()? {
+        Entry::Message(Message {
+            value: Some(value), ..
+        }) => Some(value),
+        _ => None,
+    }
+}
+
+fn po_units(po: &str) -> Vec<TranslationUnit> {
+    let mut units = Vec::new();
+    for entry in po::entries(po).map(PoEntry::parse) {
+        if entry.is_obsolete() || entry.is_header() {
+            continue;
+        }
+        let msgid = match entry.msgid() {
+            Some(msgid) => msgid,
+            None => continue,
+        };
+        if entry.is_plural() {
+            warn!(
+                "Skipping the plural message \"{0}\", which can't be exported to XLIFF.",
+                msgid
+            );
+            continue;
+        }
+        units.push(TranslationUnit {
+            id: (units.len() + 1).to_string(),
+            context: entry.msgctxt(),
+            source: msgid,
+            target: entry.msgstr().filter(|msgstr| !msgstr.is_empty()),
+            needs_review: entry.is_fuzzy(),
+            notes: entry.comments(),
+        });
+    }
+    units
+}
+
+/// Import the translations in the XLIFF `document` into the
+/// localizations of the crate `crt`, for the document's target
+/// language. For the fluent system, the messages are added to (or
+/// replaced in) the language's files. For the gettext system, the
+/// entries of the language's `po` file are matched using their
+/// `msgctxt` and `msgid`. Units without a target are ignored.
+///
+/// Returns the paths of the files which were created or modified.
+pub fn import(crt: &Crate, document: &XliffDocument) -> Result<Vec<PathBuf>> {
+    let config = crt.config_or_err()?;
+    let language = &document.target_language;
+    if *language == config.fallback_language {
+        return Err(anyhow!(tr!(
+            // {0} is the language identifier of the XLIFF document's target language.
+            "Unable to import translations into the fallback language \"{0}\".",
+            language
+        )));
+    }
+
+    let mut changed = Vec::new();
+    for file in &document.files {
+        if file.original.ends_with(".ftl") {
+            let fluent_config = crt.config_or_err()?.fluent.as_ref().ok_or_else(|| {
+                anyhow!(tr!(
+                    // {0} is the path of the resource in the XLIFF document.
+                    "Unable to import \"{0}\", the crate does not use the fluent localization system.",
+                    file.original
+                ))
+            })?;
+            let path =
+                FluentLayout::new(crt, fluent_config)?.path(language, Path::new(&file.original));
+            if import_fluent_file(&path, &file.units)? {
+                changed.push(path);
+            }
+        } else if file.original.ends_with(".po") {
+            let gettext_config = crt.gettext_config_or_err()?;
+            let path = crt
+                .path
+                .join(gettext_config.po_dir())
+                .join(language.to_string())
+                .join(&file.original);
+            if import_po_file(&path, &file.units)? {
+                changed.push(path);
+            }
+        } else {
+            warn!(
+                "Skipping \"{0}\" in the XLIFF document, which is not a fluent or po file.",
+                file.original
+            );
+        }
+    }
+    Ok(changed)
+}
+
+/// The translations of a fluent message imported from XLIFF.
+#[derive(Default)]
+struct ImportedMessage {
+    value: Option<Pattern<String>>,
+    attributes: Vec<Attribute<String>>,
+    needs_review: bool,
+}
+
+fn import_fluent_file(path: &Path, units: &[TranslationUnit]) -> Result<bool> {
+    let mut ids: Vec<String> = Vec::new();
+    let mut imported: HashMap<String, ImportedMessage> = HashMap::new();
+    for unit in units {
+        let target = match &unit.target {
+            Some(target) if !target.trim().is_empty() => target,
+            _ => continue,
+        };
+        let pattern = match parse_pattern(target) {
+            Some(pattern) => pattern,
+            None => {
+                warn!(
+                    "Skipping the translation of \"{0}\", which is not valid fluent syntax.",
+                    unit.id
+                );
+                continue;
+            }
+        };
+        let (message_id, attribute_id) = match unit.id.split_once('.') {
+            Some((message_id, attribute_id)) => (message_id, Some(attribute_id)),
+            None => (unit.id.as_str(), None),
+        };
+        let message = imported.entry(message_id.to_string()).or_insert_with(|| {
+            ids.push(message_id.to_string());
+            ImportedMessage::default()
+        });
+        message.needs_review |= unit.needs_review;
+        match attribute_id {
+            Some(attribute_id) => message.attributes.push(Attribute {
+                id: Identifier {
+                    name: attribute_id.to_string(),
+                },
+                value: pattern,
+            }),
+            None => message.value = Some(pattern),
+        }
+    }
+
+    if imported.is_empty() {
+        return Ok(false);
+    }
+
+    let mut resource = parse_fluent_file(path)?.unwrap_or(Resource { body: Vec::new() });
+    for entry in &mut resource.body {
+        if let Entry::Message(message) = entry {
+            if let Some(translation) = imported.remove(&message.id.name) {
+                merge_message(message, translation);
+            }
+        }
+    }
+    for id in ids {
+        if let Some(translation) = imported.remove(&id) {
+            let mut message = Message {
+                id: Identifier { name: id },
+                value: None,
+                attributes: Vec::new(),
+                comment: None,
+            };
+            merge_message(&mut message, translation);
+            resource.body.push(Entry::Message(message));
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        util::create_dir_all_if_not_exists(parent)?;
+    }
+    write(path, serializer::serialize(&resource))
+        .map_err(|err| PathError::cannot_create_file(path, err))?;
+    Ok(true)
+}
+
+fn merge_message(message: &mut Message<String>, translation: ImportedMessage) {
+    if translation.value.is_some() {
+        message.value = translation.value;
+    }
+    for attribute in translation.attributes {
+        match message
+            .attributes
+            .iter_mut()
+            .find(|existing| existing.id.name == attribute.id.name)
+        {
+            Some(existing) => existing.value = attribute.value,
+            None => message.attributes.push(attribute),
+        }
+    }
+
+    let mut comment = message
+        .comment
+        .take()
+        .map(|comment| comment.content)
+        .unwrap_or_default();
+    comment.retain(|line| line != FLUENT_DRAFT_COMMENT);
+    if translation.needs_review {
+        comment.push(FLUENT_DRAFT_COMMENT.to_string());
+    }
+    message.comment = (!comment.is_empty()).then_some(Comment { content: comment });
+}
+
+fn import_po_file(path: &Path, units: &[TranslationUnit]) -> Result<bool> {
+    util::check_path_exists(path)?;
+    let po = read_to_string(path).with_context(|| {
+        tr!(
+            // {0} is the path to the po file.
+            "Unable to read \"{0}\".",
+            path.to_string_lossy()
+        )
+    })?;
+
+    let translations: HashMap<(Option<&str>, &str), (&str, bool)> = units
+        .iter()
+        .filter_map(|unit| {
+            let target = unit.target.as_deref().filter(|target| !target.is_empty())?;
+            Some((
+                (unit.context.as_deref(), unit.source.as_str()),
+                (target, unit.needs_review),
+            ))
+        })
+        .collect();
+
+    let mut modified = false;
+    let mut entries = Vec::new();
+    for entry in po::entries(&po) {
+        let parsed = PoEntry::parse(entry);
+        let translation = match (parsed.msgid(), parsed.msgstr()) {
+            (Some(msgid), Some(msgstr)) if !(parsed.is_obsolete() || parsed.is_plural()) => {
+                translations
+                    .get(&(parsed.msgctxt().as_deref(), msgid.as_str()))
+                    .filter(|(target, needs_review)| {
+                        *target != msgstr || *needs_review != parsed.is_fuzzy()
+                    })
+                    .copied()
+            }
+            _ => None,
+        };
+        match translation {
+            Some((target, needs_review)) => {
+                modified = true;
+                entries.push(parsed.with_msgstr(target, needs_review));
+            }
+            None => entries.push(entry.to_string()),
+        }
+    }
+
+    if modified {
+        write(path, entries.join("\n\n"))
+            .map_err(|err| PathError::cannot_create_file(path, err))?;
+    }
+    Ok(modified)
+}
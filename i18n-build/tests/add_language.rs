@@ -0,0 +1,51 @@
+mod common;
+
+use i18n_config::Crate;
+
+/// `cargo i18n add-language` creates the commented out fluent
+/// messages for the new language, and appends the new language to the
+/// gettext `target_languages`.
+#[test]
+fn add_language() {
+    use i18n_build::{
+        add_language::add_language,
+        init::{init, InitOptions, LocalizationSystem},
+    };
+
+    for system in [LocalizationSystem::Fluent, LocalizationSystem::Gettext] {
+        let tmp = common::temp_crate("add-language", "");
+        let crate_path = tmp.path().to_path_buf();
+        std::fs::create_dir_all(crate_path.join("src")).unwrap();
+        let options = InitOptions {
+            system,
+            target_languages: vec!["fr".to_string()],
+            ..InitOptions::default()
+        };
+        init(&crate_path, "i18n.toml", &options).unwrap();
+        std::fs::write(
+            crate_path.join("src/main.rs"),
+            "fn main() {\n    println!(\"{}\", fl!(LOADER, \"hello-world\"));\n}\n",
+        )
+        .unwrap();
+
+        let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+        add_language(&crt, "de").unwrap();
+
+        let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+        match system {
+            LocalizationSystem::Fluent => {
+                let ftl =
+                    std::fs::read_to_string(crate_path.join("i18n/de/add_language.ftl")).unwrap();
+                assert!(ftl.ends_with("\n# Used in src/main.rs:2\n# hello-world = Hello World!\n"));
+            }
+            LocalizationSystem::Gettext => {
+                assert_eq!(
+                    vec!["fr".to_string(), "de".to_string()],
+                    crt.gettext_config_or_err().unwrap().target_languages
+                );
+            }
+        }
+        assert!(add_language(&crt, "de").is_err());
+        assert!(add_language(&crt, "en-US").is_err());
+    }
+}
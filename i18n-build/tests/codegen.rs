@@ -0,0 +1,75 @@
+mod common;
+
+use i18n_config::Crate;
+use tempfile::TempDir;
+
+#[path = "codegen/messages.rs"]
+mod messages;
+
+/// Create a crate using the fluent system for the codegen test.
+fn codegen_crate() -> TempDir {
+    let tmp = common::temp_crate("codegen", "");
+    let crate_path = tmp.path().to_path_buf();
+    std::fs::create_dir_all(crate_path.join("i18n/en/codegen")).unwrap();
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        "fallback_language = \"en\"\n\n[fluent]\nassets_dir = \"i18n\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("i18n/en/codegen.ftl"),
+        "hello-world = Hello World!\n\
+        greeting = Hello { $name }, you have { $unread-count ->\n    \
+            [one] one message\n   \
+           *[other] { $unread-count } messages\n\
+        }.\n\
+        login-button = Log in\n    \
+            .tooltip = Log in to { $site }\n\
+        type = Type\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("i18n/en/codegen/settings.ftl"),
+        "title = Settings\n",
+    )
+    .unwrap();
+    tmp
+}
+
+/// The module generated by `cargo i18n codegen` is the same as the
+/// `codegen/messages.rs` module, whose functions obtain the messages.
+#[test]
+fn codegen() {
+    use i18n_embed::{fluent::FluentLanguageLoader, FileSystemAssets, LanguageLoader};
+
+    let tmp = codegen_crate();
+    let crate_path = tmp.path().to_path_buf();
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let source = i18n_build::codegen::generate(&crt).unwrap();
+    assert_eq!(include_str!("codegen/messages.rs"), source);
+
+    let output = crate_path.join(i18n_build::codegen::DEFAULT_OUTPUT);
+    assert!(i18n_build::codegen::write(&crt, &output).unwrap());
+    assert!(!i18n_build::codegen::write(&crt, &output).unwrap());
+
+    let loader = FluentLanguageLoader::new(messages::DOMAIN, "en".parse().unwrap());
+    let assets = FileSystemAssets::try_new(crate_path.join("i18n")).unwrap();
+    loader.load_fallback_language(&assets).unwrap();
+    loader.set_use_isolating(false);
+    i18n_embed::fluent::set_global_loader(Box::leak(Box::new(loader)));
+
+    assert_eq!("Hello World!", messages::hello_world());
+    assert_eq!(
+        "Hello Alice, you have 2 messages.",
+        messages::greeting("Alice", 2)
+    );
+    assert_eq!("Log in", messages::login_button());
+    assert_eq!(
+        "Log in to example.com",
+        messages::login_button_tooltip("example.com")
+    );
+    assert_eq!("Type", messages::r#type());
+    assert_eq!("Settings", messages::settings_title());
+    assert_eq!("settings.title", messages::ids::SETTINGS_TITLE);
+    assert_eq!("tooltip", messages::attrs::login_button::TOOLTIP);
+}
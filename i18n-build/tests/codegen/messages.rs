@@ -0,0 +1,92 @@
+// This file is generated by `cargo i18n codegen` from the fluent files
+// of the "en" language, do not edit it.
+
+#![allow(dead_code)]
+#![cfg_attr(rustfmt, rustfmt::skip)]
+
+use i18n_embed::fluent::{global_loader, FluentValue};
+
+/// The domain of the messages.
+pub const DOMAIN: &str = "codegen";
+
+/// The ids of the messages.
+pub mod ids {
+    /// `hello-world`
+    pub const HELLO_WORLD: &str = "hello-world";
+    /// `greeting`
+    pub const GREETING: &str = "greeting";
+    /// `login-button`
+    pub const LOGIN_BUTTON: &str = "login-button";
+    /// `type`
+    pub const TYPE: &str = "type";
+    /// `settings.title`
+    pub const SETTINGS_TITLE: &str = "settings.title";
+}
+
+/// The ids of the attributes of each message.
+pub mod attrs {
+    /// The attributes of `login-button`.
+    pub mod login_button {
+        /// `login-button.tooltip`
+        pub const TOOLTIP: &str = "tooltip";
+    }
+}
+
+/// `hello-world`
+///
+/// ```text
+/// Hello World!
+/// ```
+pub fn hello_world() -> String {
+    global_loader(DOMAIN).get("hello-world")
+}
+
+/// `greeting`
+///
+/// ```text
+/// Hello { $name }, you have { $unread-count -> … }.
+/// ```
+pub fn greeting<'a>(name: impl Into<FluentValue<'a>>, unread_count: impl Into<FluentValue<'a>>) -> String {
+    let mut args = std::collections::HashMap::new();
+    args.insert("name", name.into());
+    args.insert("unread-count", unread_count.into());
+    global_loader(DOMAIN).get_args_concrete("greeting", args)
+}
+
+/// `login-button`
+///
+/// ```text
+/// Log in
+/// ```
+pub fn login_button() -> String {
+    global_loader(DOMAIN).get("login-button")
+}
+
+/// `login-button.tooltip`
+///
+/// ```text
+/// Log in to { $site }
+/// ```
+pub fn login_button_tooltip<'a>(site: impl Into<FluentValue<'a>>) -> String {
+    let mut args = std::collections::HashMap::new();
+    args.insert("site", site.into());
+    global_loader(DOMAIN).get_attr_args_concrete("login-button", "tooltip", args)
+}
+
+/// `type`
+///
+/// ```text
+/// Type
+/// ```
+pub fn r#type() -> String {
+    global_loader(DOMAIN).get("type")
+}
+
+/// `settings.title`
+///
+/// ```text
+/// Settings
+/// ```
+pub fn settings_title() -> String {
+    global_loader(DOMAIN).get("settings.title")
+}
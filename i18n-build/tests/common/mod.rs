@@ -0,0 +1,17 @@
+use tempfile::TempDir;
+
+/// Create a crate named `name` in a temporary directory, which is
+/// removed when the returned [`TempDir`] is dropped. The `manifest` is
+/// appended to the `[package]` table of its `Cargo.toml`.
+pub fn temp_crate(name: &str, manifest: &str) -> TempDir {
+    let dir = tempfile::Builder::new()
+        .prefix("i18n-build-")
+        .tempdir()
+        .unwrap();
+    std::fs::write(
+        dir.path().join("Cargo.toml"),
+        format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n{manifest}"),
+    )
+    .unwrap();
+    dir
+}
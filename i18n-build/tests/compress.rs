@@ -0,0 +1,44 @@
+/// The localization assets are compressed with gzip into the output
+/// directory, and the unchanged files are not written again.
+#[test]
+fn compress_assets() {
+    use i18n_build::compress::compress_assets;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let src_dir = tmp.path().join("i18n");
+    let out_dir = tmp.path().join("compressed");
+    for language in ["en", "fr"] {
+        std::fs::create_dir_all(src_dir.join(language)).unwrap();
+        std::fs::write(
+            src_dir.join(language).join("compress.ftl"),
+            "hello-world = Hello World!\n",
+        )
+        .unwrap();
+    }
+
+    let compressed = compress_assets(&src_dir, &out_dir).unwrap();
+    assert_eq!(
+        vec![
+            out_dir.join("en/compress.ftl.gz"),
+            out_dir.join("fr/compress.ftl.gz"),
+        ],
+        compressed
+    );
+    let contents = std::fs::read(&compressed[0]).unwrap();
+    assert_eq!(&[0x1f, 0x8b], &contents[..2]);
+    let modified = std::fs::metadata(&compressed[0])
+        .unwrap()
+        .modified()
+        .unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    assert_eq!(compressed, compress_assets(&src_dir, &out_dir).unwrap());
+    assert_eq!(contents, std::fs::read(&compressed[0]).unwrap());
+    assert_eq!(
+        modified,
+        std::fs::metadata(&compressed[0])
+            .unwrap()
+            .modified()
+            .unwrap()
+    );
+}
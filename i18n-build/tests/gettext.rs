@@ -0,0 +1,441 @@
+mod common;
+
+use i18n_config::Crate;
+use std::path::PathBuf;
+
+#[test]
+fn gettext_tools() {
+    use i18n_build::doctor::check_tools;
+    use i18n_config::GettextTools;
+
+    let tmp = common::temp_crate("gettext-tools", "");
+    let crate_path = tmp.path().to_path_buf();
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        "fallback_language = \"en\"\n\n[gettext]\ntarget_languages = [\"fr\"]\n\
+        output_dir = \"i18n\"\nxtr = false\n\n\
+        [gettext.tools]\nmsgfmt = \"/nonexistent/bin/msgfmt\"\n",
+    )
+    .unwrap();
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let tools = &crt.gettext_config_or_err().unwrap().tools;
+    assert_eq!(Some(PathBuf::from("/nonexistent/bin/msgfmt")), tools.msgfmt);
+
+    let statuses = check_tools(Some(&crt));
+    assert_eq!(GettextTools::NAMES.len(), statuses.len());
+    let msgfmt = statuses.iter().find(|s| s.name == "msgfmt").unwrap();
+    assert_eq!(PathBuf::from("/nonexistent/bin/msgfmt"), msgfmt.program);
+    assert!(msgfmt.required);
+    assert!(msgfmt.is_missing());
+    // String extraction is disabled, so `xtr` is not required.
+    let xtr = statuses.iter().find(|s| s.name == "xtr").unwrap();
+    assert_eq!(PathBuf::from("xtr"), xtr.program);
+    assert!(!xtr.required);
+    assert!(!xtr.is_missing());
+
+    // Without a gettext config none of the tools are required.
+    assert!(check_tools(None).iter().all(|status| !status.required));
+}
+
+#[test]
+fn builtin_po2mo() {
+    use i18n_build::gettext_impl::po2mo::compile;
+
+    let po = r#"# French translations.
+msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+"Plural-Forms: nplurals=2; plural=(n > 1);\n"
+
+msgid "Hello"
+msgstr "Bonjour"
+
+msgctxt "menu"
+msgid "File"
+msgstr "Fichier"
+
+#, fuzzy
+msgid "Goodbye"
+msgstr "Au revoir"
+
+msgid "Untranslated"
+msgstr ""
+
+msgid "{0} file"
+msgid_plural "{0} files"
+msgstr[0] "{0} fichier"
+msgstr[1] "{0} fichiers"
+
+#~ msgid "Obsolete"
+#~ msgstr "Obsolète"
+"#;
+
+    let catalog = gettext::Catalog::parse(&compile(po, false).unwrap()[..]).unwrap();
+    assert_eq!("Bonjour", catalog.gettext("Hello"));
+    assert_eq!("Fichier", catalog.pgettext("menu", "File"));
+    assert_eq!("File", catalog.gettext("File"));
+    assert_eq!("Goodbye", catalog.gettext("Goodbye"));
+    assert_eq!("Untranslated", catalog.gettext("Untranslated"));
+    assert_eq!("Obsolete", catalog.gettext("Obsolete"));
+    assert_eq!("{0} fichier", catalog.ngettext("{0} file", "{0} files", 1));
+    assert_eq!("{0} fichiers", catalog.ngettext("{0} file", "{0} files", 2));
+
+    let catalog = gettext::Catalog::parse(&compile(po, true).unwrap()[..]).unwrap();
+    assert_eq!("Au revoir", catalog.gettext("Goodbye"));
+
+    // The number of plural forms needs to match the header.
+    let po = po.replace("nplurals=2", "nplurals=3");
+    assert!(compile(&po, false).is_err());
+}
+
+#[test]
+fn builtin_msgmerge() {
+    use i18n_build::gettext_impl::msgmerge::merge;
+
+    let po = r#"msgid ""
+msgstr ""
+"Project-Id-Version: app 1.0\n"
+"POT-Creation-Date: 2024-01-01 00:00+0000\n"
+"Language: fr\n"
+"Content-Type: text/plain; charset=UTF-8\n"
+"Plural-Forms: nplurals=2; plural=(n > 1);\n"
+
+# Keep it short.
+#: src/main.rs:1
+msgid "Open the file"
+msgstr "Ouvrir le fichier"
+
+#: src/main.rs:2
+msgid "Save the document to disk"
+msgstr "Enregistrer le document sur le disque"
+
+#: src/main.rs:3
+msgid "Removed message"
+msgstr "Message supprimé"
+
+#: src/main.rs:4
+msgid "Untranslated removed"
+msgstr ""
+
+#: src/main.rs:5
+msgid "{0} file"
+msgid_plural "{0} files"
+msgstr[0] "{0} fichier"
+msgstr[1] "{0} fichiers"
+
+#~ msgid "Old obsolete"
+#~ msgstr "Ancien"
+"#;
+    let pot = r#"msgid ""
+msgstr ""
+"Project-Id-Version: app 1.0\n"
+"POT-Creation-Date: 2024-02-02 00:00+0000\n"
+"Language: \n"
+"Content-Type: text/plain; charset=UTF-8\n"
+
+#: src/main.rs:10
+msgid "Open the file"
+msgstr ""
+
+#: src/main.rs:11
+msgid "Save the documents to disk"
+msgstr ""
+
+#: src/main.rs:12
+msgid "Something completely new"
+msgstr ""
+
+#: src/main.rs:13
+msgid "{0} file"
+msgid_plural "{0} files"
+msgstr[0] ""
+msgstr[1] ""
+
+#: src/main.rs:14
+msgid "Old obsolete"
+msgstr ""
+"#;
+
+    // The same as the output of `msgmerge`.
+    let expected = r#"msgid ""
+msgstr ""
+"Project-Id-Version: app 1.0\n"
+"POT-Creation-Date: 2024-02-02 00:00+0000\n"
+"Language: fr\n"
+"Content-Type: text/plain; charset=UTF-8\n"
+"Plural-Forms: nplurals=2; plural=(n > 1);\n"
+
+# Keep it short.
+#: src/main.rs:10
+msgid "Open the file"
+msgstr "Ouvrir le fichier"
+
+#: src/main.rs:11
+#, fuzzy
+msgid "Save the documents to disk"
+msgstr "Enregistrer le document sur le disque"
+
+#: src/main.rs:12
+msgid "Something completely new"
+msgstr ""
+
+#: src/main.rs:13
+msgid "{0} file"
+msgid_plural "{0} files"
+msgstr[0] "{0} fichier"
+msgstr[1] "{0} fichiers"
+
+#: src/main.rs:14
+msgid "Old obsolete"
+msgstr "Ancien"
+
+#~ msgid "Removed message"
+#~ msgstr "Message supprimé"
+"#;
+    assert_eq!(expected, merge(po, pot, 0.6));
+
+    // Without fuzzy matching, the changed message is untranslated,
+    // and its previous translation is obsolete.
+    let merged = merge(po, pot, 1.0);
+    assert!(merged.contains("msgid \"Save the documents to disk\"\nmsgstr \"\"\n"));
+    assert!(merged.contains("#~ msgid \"Save the document to disk\"\n"));
+    assert!(!merged.contains("#, fuzzy"));
+}
+
+#[test]
+fn obsolete_policy() {
+    use i18n_build::{gettext_impl::run_obsolete_policy, util::attic_file_path};
+
+    let tmp = common::temp_crate("obsolete-policy", "");
+    let crate_path = tmp.path().to_path_buf();
+    for language in ["en", "fr"] {
+        std::fs::create_dir_all(crate_path.join("i18n").join(language)).unwrap();
+    }
+    std::fs::create_dir_all(crate_path.join("i18n/po/fr")).unwrap();
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        "fallback_language = \"en\"\n\n\
+        [gettext]\ntarget_languages = [\"fr\"]\noutput_dir = \"i18n\"\n\
+        obsolete_policy = \"attic\"\n\n\
+        [fluent]\nassets_dir = \"i18n\"\nobsolete_policy = \"prune\"\n",
+    )
+    .unwrap();
+
+    // The gettext obsolete entries are moved to the attic.
+    let po_path = crate_path.join("i18n/po/fr/obsolete_policy.po");
+    std::fs::write(
+        &po_path,
+        "msgid \"\"\nmsgstr \"Language: fr\\n\"\n\n\
+        msgid \"Hello\"\nmsgstr \"Bonjour\"\n\n\
+        #~ msgid \"Goodbye\"\n#~ msgstr \"Au revoir\"\n",
+    )
+    .unwrap();
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    run_obsolete_policy(&crt, &crate_path.join("i18n/po")).unwrap();
+    assert_eq!(
+        "msgid \"\"\nmsgstr \"Language: fr\\n\"\n\nmsgid \"Hello\"\nmsgstr \"Bonjour\"\n",
+        std::fs::read_to_string(&po_path).unwrap()
+    );
+    assert_eq!(
+        "#~ msgid \"Goodbye\"\n#~ msgstr \"Au revoir\"\n",
+        std::fs::read_to_string(attic_file_path(&po_path)).unwrap()
+    );
+
+    // The fluent messages which are no longer in the fallback
+    // language are removed.
+    std::fs::write(
+        crate_path.join("i18n/en/obsolete_policy.ftl"),
+        "hello = Hello\n",
+    )
+    .unwrap();
+    let ftl_path = crate_path.join("i18n/fr/obsolete_policy.ftl");
+    std::fs::write(&ftl_path, "hello = Bonjour\ngoodbye = Au revoir\n").unwrap();
+    let system = i18n_build::system::FluentSystem;
+    i18n_build::system::LocalizationSystem::extract(
+        &system,
+        &crt,
+        i18n_build::util::default_jobs(),
+        &mut Default::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        "hello = Bonjour\n",
+        std::fs::read_to_string(&ftl_path).unwrap()
+    );
+    assert!(!attic_file_path(&ftl_path).exists());
+}
+
+#[test]
+fn msgcat_sort_output() {
+    use i18n_build::gettext_impl::run_msgcat_sorted;
+    use i18n_config::{GettextSortOutput, GettextTools};
+
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    let header = "msgid \"\"\nmsgstr \"Content-Type: text/plain; charset=UTF-8\\n\"\n\n";
+    let b_pot = dir.join("b.pot");
+    std::fs::write(
+        &b_pot,
+        format!("{header}#: src/b.rs:1\nmsgid \"Date\"\nmsgstr \"\"\n"),
+    )
+    .unwrap();
+    let a_pot = dir.join("a.pot");
+    std::fs::write(
+        &a_pot,
+        format!(
+            "{header}#: src/a.rs:2\nmsgid \"Banana\"\nmsgstr \"\"\n\n\
+            #: src/a.rs:1\nmsgid \"Cherry\"\nmsgstr \"\"\n"
+        ),
+    )
+    .unwrap();
+
+    let msgids = |sort_output: GettextSortOutput| {
+        let output = dir.join("output.pot");
+        run_msgcat_sorted(
+            &GettextTools::default(),
+            sort_output,
+            [b_pot.as_path(), a_pot.as_path()],
+            output.as_path(),
+        )
+        .unwrap();
+        std::fs::read_to_string(&output)
+            .unwrap()
+            .lines()
+            .filter_map(|line| line.strip_prefix("msgid \""))
+            .map(|msgid| msgid.trim_end_matches('"').to_string())
+            .filter(|msgid| !msgid.is_empty())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        vec!["Date", "Banana", "Cherry"],
+        msgids(GettextSortOutput::None)
+    );
+    assert_eq!(
+        vec!["Cherry", "Banana", "Date"],
+        msgids(GettextSortOutput::File)
+    );
+    assert_eq!(
+        vec!["Banana", "Cherry", "Date"],
+        msgids(GettextSortOutput::Msgid)
+    );
+}
+
+#[test]
+fn gettext_pipeline() {
+    use i18n_build::{gettext_impl::GettextPipeline, summary::Stage};
+    use std::cell::RefCell;
+
+    let tmp = common::temp_crate("gettext-pipeline", "");
+    let crate_path = tmp.path().to_path_buf();
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        "fallback_language = \"en\"\n\n[gettext]\ntarget_languages = [\"fr\"]\n\
+        output_dir = \"i18n\"\nxtr = false\n",
+    )
+    .unwrap();
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let stages = RefCell::new(Vec::new());
+    GettextPipeline::for_crate(&crt)
+        .init_missing()
+        .merge()
+        .before(Stage::Init, |context| {
+            // The pot file would usually be extracted from the source.
+            std::fs::create_dir_all(context.pot_dir)?;
+            std::fs::write(
+                context.pot_file_path,
+                "msgid \"\"\nmsgstr \"Content-Type: text/plain; charset=UTF-8\\n\"\n\n\
+                msgid \"Hello\"\nmsgstr \"\"\n",
+            )?;
+            Ok(())
+        })
+        .after(Stage::Init, |context| {
+            stages.borrow_mut().push(context.stage);
+            Ok(())
+        })
+        .after(Stage::Merge, |context| {
+            stages.borrow_mut().push(context.stage);
+            assert!(context.po_dir.join("fr/gettext_pipeline.po").exists());
+            Ok(())
+        })
+        .run()
+        .unwrap();
+
+    // The steps which were not selected are not run.
+    assert_eq!(vec![Stage::Init, Stage::Merge], stages.into_inner());
+    assert!(!crate_path.join("i18n/mo").exists());
+}
+
+/// The `msgmerge_args` and `msgfmt_args` in the `[gettext]` section
+/// are passed to the gettext tools.
+#[test]
+fn gettext_tool_args() {
+    use i18n_build::gettext_impl::{run_msgfmt, run_msgmerge};
+
+    let tmp = common::temp_crate("tool-args", "");
+    let crate_path = tmp.path().to_path_buf();
+    std::fs::create_dir_all(crate_path.join("i18n/pot")).unwrap();
+    std::fs::create_dir_all(crate_path.join("i18n/po/de")).unwrap();
+    let write_config = |args: &str| {
+        std::fs::write(
+            crate_path.join("i18n.toml"),
+            format!(
+                "fallback_language = \"en\"\n\n\
+                 [gettext]\ntarget_languages = [\"de\"]\noutput_dir = \"i18n\"\n{0}\n\n\
+                 [gettext.tools]\nmsgmerge = \"msgmerge\"\nmsgfmt = \"msgfmt\"\n",
+                args
+            ),
+        )
+        .unwrap();
+    };
+    std::fs::write(
+        crate_path.join("i18n/pot/tool_args.pot"),
+        "msgid \"\"\n\
+         msgstr \"\"\n\
+         \"Content-Type: text/plain; charset=UTF-8\\n\"\n\
+         \n\
+         #, c-format\n\
+         msgid \"%d files\"\n\
+         msgstr \"\"\n\
+         \n\
+         msgid \"Hello Worlds!\"\n\
+         msgstr \"\"\n",
+    )
+    .unwrap();
+    let po_path = crate_path.join("i18n/po/de/tool_args.po");
+    std::fs::write(
+        &po_path,
+        "msgid \"\"\n\
+         msgstr \"\"\n\
+         \"Language: de\\n\"\n\
+         \"Content-Type: text/plain; charset=UTF-8\\n\"\n\
+         \n\
+         #, c-format\n\
+         msgid \"%d files\"\n\
+         msgstr \"%s Dateien\"\n\
+         \n\
+         msgid \"Hello World!\"\n\
+         msgstr \"Hallo Welt!\"\n",
+    )
+    .unwrap();
+    let pot_dir = crate_path.join("i18n/pot");
+    let po_dir = crate_path.join("i18n/po");
+    let mo_dir = crate_path.join("i18n/mo");
+
+    write_config("msgmerge_args = [\"--no-fuzzy-matching\"]\nmsgfmt_args = [\"--check\"]");
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let gettext_config = crt.gettext_config_or_err().unwrap();
+    assert_eq!(vec!["--no-fuzzy-matching"], gettext_config.msgmerge_args);
+    assert_eq!(vec!["--check"], gettext_config.msgfmt_args);
+    run_msgmerge(&crt, &pot_dir, &po_dir).unwrap();
+    let po = std::fs::read_to_string(&po_path).unwrap();
+    assert!(!po.contains("#, fuzzy"));
+    // The format string of the translation doesn't match.
+    assert!(run_msgfmt(&crt, &po_dir, &mo_dir).is_err());
+
+    write_config("");
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    run_msgfmt(&crt, &po_dir, &mo_dir).unwrap();
+    assert!(mo_dir.join("de/tool_args.mo").exists());
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use i18n_config::Crate;
+
+#[test]
+fn glossary() {
+    use i18n_build::{
+        glossary::{lint_glossary, Glossary},
+        system::Severity,
+    };
+
+    let toml = Glossary::from_toml(
+        "[[term]]\nsource = \"file\"\n\
+        translations = { fr = \"fichier\" }\n\
+        disallowed = { fr = [\"document\", \"dossier\"] }\n",
+    )
+    .unwrap();
+    let csv = Glossary::from_csv(
+        "source,language,translation,disallowed\n\
+        file,fr,fichier,\"document|dossier\"\n",
+    )
+    .unwrap();
+    assert_eq!(toml, csv);
+
+    let tmp = common::temp_crate("glossary", "");
+    let crate_path = tmp.path().to_path_buf();
+    for dir in ["i18n/ftl/en", "i18n/ftl/fr", "i18n/po/fr"] {
+        std::fs::create_dir_all(crate_path.join(dir)).unwrap();
+    }
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        "fallback_language = \"en\"\nglossary = \"glossary.csv\"\n\n\
+        [fluent]\nassets_dir = \"i18n/ftl\"\n\n\
+        [gettext]\ntarget_languages = [\"fr\"]\noutput_dir = \"i18n\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("glossary.csv"),
+        "source,language,translation,disallowed\nfile,fr,fichier,document|dossier\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("i18n/ftl/en/glossary.ftl"),
+        "open = Open a file\nsave = Save the file\nprofile = Edit the profile\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("i18n/ftl/fr/glossary.ftl"),
+        "open = Ouvrir un fichier\nsave = Enregistrer le document\nprofile = Modifier le profil\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("i18n/po/fr/glossary.po"),
+        "msgid \"\"\nmsgstr \"\"\n\n\
+        msgid \"Delete the file\"\nmsgstr \"Supprimer l'élément\"\n",
+    )
+    .unwrap();
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let issues = lint_glossary(&crt).unwrap();
+    assert_eq!(3, issues.len());
+    assert_eq!(
+        Some(crate_path.join("i18n/ftl/fr/glossary.ftl")),
+        issues[0].path
+    );
+    assert_eq!(Severity::Error, issues[0].severity);
+    assert!(issues[0].message.contains("\"document\""));
+    assert_eq!(Severity::Warning, issues[1].severity);
+    assert!(issues[1].message.starts_with("\"save\""));
+    assert_eq!(
+        Some(crate_path.join("i18n/po/fr/glossary.po")),
+        issues[2].path
+    );
+    assert_eq!(Severity::Warning, issues[2].severity);
+}
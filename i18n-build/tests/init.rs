@@ -0,0 +1,44 @@
+mod common;
+
+use i18n_config::Crate;
+use std::path::PathBuf;
+
+/// `cargo i18n init` creates a config file, and a sample message for
+/// the fluent system, which can then be loaded.
+#[test]
+fn init_fluent() {
+    use i18n_build::init::{init, InitOptions, LocalizationSystem};
+
+    let tmp = common::temp_crate("init-fluent", "\n[dependencies]\nlog = \"0.4\"\n");
+    let crate_path = tmp.path().to_path_buf();
+    std::fs::create_dir_all(crate_path.join("src")).unwrap();
+
+    let options = InitOptions {
+        system: LocalizationSystem::Fluent,
+        patch_cargo_toml: true,
+        ..InitOptions::default()
+    };
+    let changed = init(&crate_path, "i18n.toml", &options).unwrap();
+    assert_eq!(
+        vec![
+            crate_path.join("i18n.toml"),
+            crate_path.join("i18n/en-US/init_fluent.ftl"),
+            crate_path.join("Cargo.toml"),
+        ],
+        changed
+    );
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let config = crt.config_or_err().unwrap();
+    assert_eq!(
+        PathBuf::from("i18n"),
+        config.fluent.as_ref().unwrap().assets_dir
+    );
+
+    let cargo_toml = std::fs::read_to_string(crate_path.join("Cargo.toml")).unwrap();
+    assert!(cargo_toml.contains("log = \"0.4\"\ni18n-embed = "));
+    assert!(cargo_toml.contains("\ni18n-embed-fl = "));
+
+    // The crate can't be initialized twice.
+    assert!(init(&crate_path, "i18n.toml", &options).is_err());
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use i18n_config::Crate;
+
+#[test]
+fn fluent_lint() {
+    use i18n_build::{lint::lint, system::Severity};
+
+    let tmp = common::temp_crate("fluent-lint", "");
+    let crate_path = tmp.path().to_path_buf();
+    for language in ["en", "fr"] {
+        std::fs::create_dir_all(crate_path.join("i18n").join(language)).unwrap();
+    }
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        "fallback_language = \"en\"\n\n\
+        [fluent]\nassets_dir = \"i18n\"\nprotected_terms = [\"Acme\"]\n\n\
+        [fluent.lints]\nmarkup = \"error\"\ndouble-spaces = \"allow\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("i18n/en/fluent_lint.ftl"),
+        "welcome = Welcome to Acme, { $name }!\n\
+        link = Click <a href=\"x\">here</a> for %s\n\
+        spaced = Two  spaces\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("i18n/fr/fluent_lint.ftl"),
+        "welcome = Bienvenue chez Acmé, { $nom }! \n\
+        link = Cliquez <b>ici</b> pour %s\n\
+        spaced = Deux  espaces\n",
+    )
+    .unwrap();
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let issues = lint(&crt).unwrap();
+    let fr_path = crate_path.join("i18n/fr/fluent_lint.ftl");
+    assert!(issues
+        .iter()
+        .all(|issue| issue.path.as_ref() == Some(&fr_path)));
+    let lints: Vec<(&str, Severity)> = issues
+        .iter()
+        .map(|issue| (issue.message.split(':').next().unwrap(), issue.severity))
+        .collect();
+    assert_eq!(
+        vec![
+            ("trailing-whitespace", Severity::Warning),
+            ("unknown-variables", Severity::Error),
+            ("protected-terms", Severity::Error),
+            ("markup", Severity::Error),
+        ],
+        lints
+    );
+    assert!(issues[1].message.ends_with(": $nom"));
+    assert!(issues[2].message.ends_with(": Acme"));
+    assert!(issues[3].message.contains("(%s </b> <b>)"));
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use i18n_config::Crate;
+
+#[test]
+fn migrate_to_fluent() {
+    use i18n_build::{
+        init::{init, InitOptions, LocalizationSystem},
+        migrate::{migrate_to_fluent, rewrite_tr_calls, MigrateOptions},
+    };
+
+    let tmp = common::temp_crate("migrate", "");
+    let crate_path = tmp.path().to_path_buf();
+    std::fs::create_dir_all(crate_path.join("src")).unwrap();
+    let options = InitOptions {
+        system: LocalizationSystem::Gettext,
+        target_languages: vec!["de".to_string()],
+        ..InitOptions::default()
+    };
+    init(&crate_path, "i18n.toml", &options).unwrap();
+    std::fs::write(
+        crate_path.join("src/main.rs"),
+        "fn main() {\n\
+         \x20   // tr!(\"Not a call\")\n\
+         \x20   println!(\"{}\", tr!(\"Hello World!\"));\n\
+         \x20   println!(\"{}\", tr!(\"Hello {0}, I'm {me}!\", name, me = \"Bob\"));\n\
+         \x20   println!(\"{}\", tr!(\"menu\" => \"Open\"));\n\
+         \x20   println!(\"{}\", tr!(\"{} file\" | \"{} files\" % files.len()));\n\
+         }\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(crate_path.join("i18n/pot")).unwrap();
+    std::fs::write(
+        crate_path.join("i18n/pot/migrate.pot"),
+        "msgid \"\"\n\
+         msgstr \"\"\n\
+         \n\
+         #: src/main.rs:3\n\
+         msgid \"Hello World!\"\n\
+         msgstr \"\"\n\
+         \n\
+         #. A greeting from {me}.\n\
+         #: src/main.rs:4\n\
+         msgid \"Hello {0}, I'm {me}!\"\n\
+         msgstr \"\"\n\
+         \n\
+         #: src/main.rs:5\n\
+         msgctxt \"menu\"\n\
+         msgid \"Open\"\n\
+         msgstr \"\"\n\
+         \n\
+         #: src/main.rs:6\n\
+         msgid \"{} file\"\n\
+         msgid_plural \"{} files\"\n\
+         msgstr[0] \"\"\n\
+         msgstr[1] \"\"\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(crate_path.join("i18n/po/de")).unwrap();
+    std::fs::write(
+        crate_path.join("i18n/po/de/migrate.po"),
+        "msgid \"\"\n\
+         msgstr \"\"\n\
+         \"Language: de\\n\"\n\
+         \n\
+         #: src/main.rs:3\n\
+         msgid \"Hello World!\"\n\
+         msgstr \"Hallo Welt!\"\n\
+         \n\
+         #: src/main.rs:4\n\
+         #, fuzzy\n\
+         msgid \"Hello {0}, I'm {me}!\"\n\
+         msgstr \"Hallo {0}!\"\n\
+         \n\
+         #: src/main.rs:5\n\
+         msgctxt \"menu\"\n\
+         msgid \"Open\"\n\
+         msgstr \"Öffnen\"\n\
+         \n\
+         #: src/main.rs:6\n\
+         msgid \"{} file\"\n\
+         msgid_plural \"{} files\"\n\
+         msgstr[0] \"{} Datei\"\n\
+         msgstr[1] \"{} Dateien\"\n",
+    )
+    .unwrap();
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let migration = migrate_to_fluent(&crt, &MigrateOptions::default()).unwrap();
+    assert_eq!(3, migration.changed.len());
+
+    let en = std::fs::read_to_string(crate_path.join("i18n/en-US/migrate.ftl")).unwrap();
+    assert_eq!(
+        "hello-world = Hello World!\n\
+         # A greeting from {me}.\n\
+         hello-im = Hello { $arg0 }, I'm { $me }!\n\
+         menu-open = Open\n\
+         file =\n    { $n ->\n        [one] { $n } file\n       *[other] { $n } files\n    }\n",
+        en
+    );
+    // The fuzzy translation is not migrated.
+    let de = std::fs::read_to_string(crate_path.join("i18n/de/migrate.ftl")).unwrap();
+    assert_eq!(
+        "hello-world = Hallo Welt!\n\
+         menu-open = Öffnen\n\
+         file =\n    { $n ->\n        [one] { $n } Datei\n       *[other] { $n } Dateien\n    }\n",
+        de
+    );
+    let config = std::fs::read_to_string(crate_path.join("i18n.toml")).unwrap();
+    assert!(config.ends_with("\n[fluent]\nassets_dir = \"i18n\"\n"));
+
+    let patch = rewrite_tr_calls(&crt, &migration, "LANGUAGE_LOADER").unwrap();
+    assert_eq!(
+        "--- a/src/main.rs\n\
+         +++ b/src/main.rs\n\
+         @@ -1,7 +1,7 @@\n \
+         fn main() {\n \
+         \x20   // tr!(\"Not a call\")\n\
+         -    println!(\"{}\", tr!(\"Hello World!\"));\n\
+         +    println!(\"{}\", fl!(LANGUAGE_LOADER, \"hello-world\"));\n\
+         -    println!(\"{}\", tr!(\"Hello {0}, I'm {me}!\", name, me = \"Bob\"));\n\
+         +    println!(\"{}\", fl!(LANGUAGE_LOADER, \"hello-im\", arg0 = name, me = \"Bob\"));\n\
+         -    println!(\"{}\", tr!(\"menu\" => \"Open\"));\n\
+         +    println!(\"{}\", fl!(LANGUAGE_LOADER, \"menu-open\"));\n\
+         -    println!(\"{}\", tr!(\"{} file\" | \"{} files\" % files.len()));\n\
+         +    println!(\"{}\", fl!(LANGUAGE_LOADER, \"file\", n = files.len()));\n \
+         }\n",
+        patch
+    );
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use i18n_config::Crate;
+
+#[test]
+fn progress() {
+    use i18n_build::{
+        gettext_impl::GettextPipeline,
+        progress::{set_progress_handler, Progress},
+        summary::Stage,
+    };
+    use std::sync::{Arc, Mutex};
+
+    let tmp = common::temp_crate("progress", "");
+    let crate_path = tmp.path().to_path_buf();
+    std::fs::create_dir_all(crate_path.join("i18n/pot")).unwrap();
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        "fallback_language = \"en\"\n\n[gettext]\ntarget_languages = [\"fr\"]\n\
+        output_dir = \"i18n\"\nxtr = false\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("i18n/pot/progress.pot"),
+        "msgid \"\"\nmsgstr \"Content-Type: text/plain; charset=UTF-8\\n\"\n\n\
+        msgid \"Hello\"\nmsgstr \"\"\n",
+    )
+    .unwrap();
+
+    let updates = Arc::new(Mutex::new(Vec::new()));
+    let handler_updates = updates.clone();
+    set_progress_handler(Some(Box::new(move |progress: &Progress| {
+        handler_updates.lock().unwrap().push(progress.clone());
+    })));
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let result = GettextPipeline::for_crate(&crt)
+        .init_missing()
+        .merge()
+        .run();
+    set_progress_handler(None);
+    result.unwrap();
+
+    // Other tests may be running at the same time.
+    let updates: Vec<String> = updates
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|progress| match progress {
+            Progress::Crate { name, .. } => name == "progress",
+            Progress::Stage { crate_name, .. } | Progress::Language { crate_name, .. } => {
+                crate_name == "progress"
+            }
+        })
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        vec![
+            "progress (gettext)".to_string(),
+            format!("progress: {}", Stage::Init),
+            format!("progress: {} [fr]", Stage::Init),
+            format!("progress: {}", Stage::Merge),
+            format!("progress: {} [fr]", Stage::Merge),
+        ],
+        updates
+    );
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use i18n_config::Crate;
+
+#[test]
+fn report() {
+    use i18n_build::{
+        init::{init, InitOptions, LocalizationSystem},
+        report::{badge, report, ReportFormat},
+    };
+
+    for system in [LocalizationSystem::Fluent, LocalizationSystem::Gettext] {
+        let tmp = common::temp_crate("report", "");
+        let crate_path = tmp.path().to_path_buf();
+        std::fs::create_dir_all(crate_path.join("src")).unwrap();
+        let options = InitOptions {
+            system,
+            target_languages: vec!["de".to_string()],
+            ..InitOptions::default()
+        };
+        init(&crate_path, "i18n.toml", &options).unwrap();
+
+        match system {
+            LocalizationSystem::Fluent => {
+                std::fs::write(
+                    crate_path.join("i18n/en-US/report.ftl"),
+                    "hello-world = Hello World!\n\
+                     greeting = Hello { $name }!\n\
+                     bye = Bye <b>now</b>!\n",
+                )
+                .unwrap();
+                std::fs::create_dir_all(crate_path.join("i18n/de")).unwrap();
+                std::fs::write(
+                    crate_path.join("i18n/de/report.ftl"),
+                    "hello-world = Hallo Welt!\n\
+                     # Machine translated draft, needs review.\n\
+                     greeting = Hallo { $name }!\n",
+                )
+                .unwrap();
+            }
+            LocalizationSystem::Gettext => {
+                std::fs::create_dir_all(crate_path.join("i18n/po/de")).unwrap();
+                std::fs::write(
+                    crate_path.join("i18n/po/de/report.po"),
+                    "msgid \"\"\n\
+                     msgstr \"\"\n\
+                     \"Language: de\\n\"\n\
+                     \n\
+                     msgid \"Hello World!\"\n\
+                     msgstr \"Hallo Welt!\"\n\
+                     \n\
+                     #, fuzzy\n\
+                     msgid \"Hello {0}!\"\n\
+                     msgstr \"Hallo {0}!\"\n\
+                     \n\
+                     msgid \"Bye <b>now</b>!\"\n\
+                     msgstr \"\"\n\
+                     \n\
+                     #~ msgid \"Obsolete\"\n\
+                     #~ msgstr \"Veraltet\"\n",
+                )
+                .unwrap();
+            }
+        }
+
+        let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+        let crate_report = report(&crt).unwrap();
+        assert_eq!("report", crate_report.name);
+        assert_eq!(1, crate_report.languages.len());
+        let status = &crate_report.languages[0];
+        assert_eq!("de", status.language.to_string());
+        assert_eq!(3, status.messages);
+        assert_eq!(2, status.translated);
+        assert_eq!(1, status.needs_review);
+        let missing = match system {
+            LocalizationSystem::Fluent => "bye",
+            LocalizationSystem::Gettext => "Bye <b>now</b>!",
+        };
+        assert_eq!(vec![missing.to_string()], status.missing);
+        assert!(status.last_modified.is_some());
+        assert_eq!(66, crate_report.coverage().floor() as u32);
+
+        let markdown = crate_report.render(ReportFormat::Markdown);
+        assert!(markdown.starts_with("# Translation status of report\n"));
+        assert!(markdown.contains("| de | 2/3 | 1 | 1 | 66% | "));
+        assert!(markdown.contains(&format!("### de\n\n- `{}`\n", missing)));
+
+        let html = crate_report.render(ReportFormat::Html);
+        assert!(html.contains("<td>de</td><td>2/3</td><td>1</td><td>1</td><td>66%</td>"));
+        if system == LocalizationSystem::Gettext {
+            assert!(html.contains("<li><code>Bye &lt;b&gt;now&lt;/b&gt;!</code></li>"));
+        }
+    }
+
+    let svg = badge("translated", 66.7);
+    assert!(svg.contains("translated: 66%"));
+    assert!(svg.contains("fill=\"#dfb317\""));
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use i18n_config::Crate;
+
+#[test]
+fn run_summary() {
+    use i18n_build::summary::{LanguageChanges, Stage};
+
+    let tmp = common::temp_crate("summary", "");
+    let crate_path = tmp.path().to_path_buf();
+    std::fs::create_dir_all(crate_path.join("src")).unwrap();
+    std::fs::write(crate_path.join("src/lib.rs"), "").unwrap();
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        "fallback_language = \"en\"\n\n\
+         [gettext]\n\
+         target_languages = [\"de\", \"fr\"]\n\
+         output_dir = \"i18n\"\n\
+         xtr = false\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(crate_path.join("i18n/pot")).unwrap();
+    std::fs::write(
+        crate_path.join("i18n/pot/summary.pot"),
+        "msgid \"\"\n\
+         msgstr \"\"\n\
+         \"Content-Type: text/plain; charset=UTF-8\\n\"\n\
+         \n\
+         msgid \"Hello World!\"\n\
+         msgstr \"\"\n\
+         \n\
+         msgid \"Goodbye World!\"\n\
+         msgstr \"\"\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(crate_path.join("i18n/po/de")).unwrap();
+    std::fs::write(
+        crate_path.join("i18n/po/de/summary.po"),
+        "msgid \"\"\n\
+         msgstr \"\"\n\
+         \"Language: de\\n\"\n\
+         \"Content-Type: text/plain; charset=UTF-8\\n\"\n\
+         \n\
+         msgid \"Hello World!\"\n\
+         msgstr \"Hallo Welt!\"\n\
+         \n\
+         msgid \"Removed\"\n\
+         msgstr \"Entfernt\"\n",
+    )
+    .unwrap();
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let summary = i18n_build::run_with_summary(crt, std::num::NonZeroUsize::MIN).unwrap();
+    assert_eq!(1, summary.crates.len());
+    let crate_summary = &summary.crates[0];
+    assert_eq!("summary", crate_summary.name);
+    assert_eq!(Some(2), crate_summary.messages);
+    assert_eq!(
+        vec![
+            LanguageChanges {
+                language: "de".to_string(),
+                new: 1,
+                changed: 0,
+                removed: 1,
+                translated: 1,
+                fuzzy: 1,
+                untranslated: 0,
+            },
+            LanguageChanges {
+                language: "fr".to_string(),
+                new: 2,
+                changed: 0,
+                removed: 0,
+                translated: 0,
+                fuzzy: 0,
+                untranslated: 2,
+            },
+        ],
+        crate_summary.languages
+    );
+    let stages: Vec<Stage> = crate_summary
+        .stages
+        .iter()
+        .map(|(stage, _)| *stage)
+        .collect();
+    assert_eq!(vec![Stage::Init, Stage::Merge, Stage::Compile], stages);
+    assert!(summary.to_string().contains(
+        "summary: 2 messages\n\
+         \x20 Language  New  Changed  Removed  Translated  Fuzzy  Untranslated\n\
+         \x20 de          1        0        1           1      1             0\n\
+         \x20 fr          2        0        0           0      0             2\n"
+    ));
+    let json = serde_json::to_value(&summary).unwrap();
+    let crate_json = &json["crates"][0];
+    assert_eq!("de", crate_json["languages"][0]["language"]);
+    assert_eq!(1, crate_json["languages"][0]["fuzzy"]);
+    assert_eq!("init", crate_json["stages"][0]["stage"]);
+    assert!(crate_json["stages"][0]["seconds"].is_f64());
+    assert!(json["duration"].is_f64());
+}
@@ -0,0 +1,278 @@
+mod common;
+
+use i18n_config::Crate;
+
+/// Localization systems registered using `register_system()` are
+/// run for the crates with a table for them in their i18n config, in
+/// addition to the built-in systems.
+#[test]
+fn localization_system() {
+    use anyhow::Result;
+    use i18n_build::{
+        init::{init, InitOptions},
+        summary::{CrateSummary, RunSummary},
+        system::{
+            crate_systems, register_system, system_for_extension, validate, LocalizationSystem,
+            Severity, ValidationIssue,
+        },
+    };
+    use std::num::NonZeroUsize;
+
+    struct GreetingSystem;
+
+    impl LocalizationSystem for GreetingSystem {
+        fn name(&self) -> &str {
+            "greeting"
+        }
+
+        fn resource_extensions(&self) -> &[&str] {
+            &["greeting"]
+        }
+
+        fn extract(
+            &self,
+            crt: &Crate,
+            _jobs: NonZeroUsize,
+            summary: &mut RunSummary,
+        ) -> Result<()> {
+            let config: serde_json::Value = crt
+                .config_or_err()?
+                .system_config("greeting")?
+                .expect("expected the greeting config");
+            summary.crates.push(CrateSummary {
+                name: config["greeting"].as_str().unwrap().to_string(),
+                path: crt.path.clone(),
+                ..CrateSummary::default()
+            });
+            Ok(())
+        }
+
+        fn validate(&self, _crt: &Crate) -> Result<Vec<ValidationIssue>> {
+            Ok(vec![ValidationIssue {
+                path: None,
+                message: "Greetings are never valid".to_string(),
+                severity: Severity::Error,
+            }])
+        }
+    }
+
+    register_system(GreetingSystem);
+    assert_eq!("fluent", system_for_extension("ftl").unwrap().name());
+    assert_eq!("greeting", system_for_extension("greeting").unwrap().name());
+    assert!(system_for_extension("txt").is_none());
+
+    let tmp = common::temp_crate("localization-system", "");
+    let crate_path = tmp.path().to_path_buf();
+    std::fs::create_dir_all(crate_path.join("src")).unwrap();
+    init(&crate_path, "i18n.toml", &InitOptions::default()).unwrap();
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let names: Vec<String> = crate_systems(&crt)
+        .unwrap()
+        .iter()
+        .map(|system| system.name().to_string())
+        .collect();
+    assert_eq!(vec!["fluent"], names);
+    assert_eq!(Vec::<ValidationIssue>::new(), validate(&crt).unwrap());
+
+    let mut i18n_toml = std::fs::read_to_string(crate_path.join("i18n.toml")).unwrap();
+    i18n_toml.push_str("\n[greeting]\ngreeting = \"Hello\"\n");
+    std::fs::write(crate_path.join("i18n.toml"), i18n_toml).unwrap();
+    let ftl_path = crate_path.join("i18n/en-US/localization_system.ftl");
+    let mut ftl = std::fs::read_to_string(&ftl_path).unwrap();
+    ftl.push_str("broken = { $\n");
+    std::fs::write(&ftl_path, ftl).unwrap();
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let names: Vec<String> = crate_systems(&crt)
+        .unwrap()
+        .iter()
+        .map(|system| system.name().to_string())
+        .collect();
+    assert_eq!(vec!["fluent", "greeting"], names);
+
+    let summary = i18n_build::run_with_summary(crt, NonZeroUsize::MIN).unwrap();
+    assert_eq!(1, summary.crates.len());
+    assert_eq!("Hello", summary.crates[0].name);
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let issues = validate(&crt).unwrap();
+    assert_eq!(2, issues.len());
+    assert_eq!(Some(ftl_path), issues[0].path);
+    assert!(issues[0].message.starts_with("Syntax error"));
+    assert_eq!("Greetings are never valid", issues[1].to_string());
+}
+
+/// Missing fluent messages are errors for the `required_languages`,
+/// and warnings for the other languages.
+#[test]
+fn fluent_required_languages() {
+    use i18n_build::system::{validate, Severity};
+
+    let tmp = common::temp_crate("required-languages", "");
+    let crate_path = tmp.path().to_path_buf();
+    for language in ["en", "de", "fr"] {
+        std::fs::create_dir_all(crate_path.join("i18n").join(language)).unwrap();
+    }
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        "fallback_language = \"en\"\n\n\
+        [fluent]\nassets_dir = \"i18n\"\nrequired_languages = [\"de\", \"it\"]\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("i18n/en/required_languages.ftl"),
+        "hello = Hello\ngoodbye = Goodbye\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("i18n/de/required_languages.ftl"),
+        "hello = Hallo\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("i18n/fr/required_languages.ftl"),
+        "hello = Bonjour\n",
+    )
+    .unwrap();
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let issues = validate(&crt).unwrap();
+    assert_eq!(3, issues.len());
+    assert_eq!(None, issues[0].path);
+    assert!(issues[0].message.contains("\"it\""));
+    assert_eq!(Severity::Error, issues[0].severity);
+    assert_eq!(
+        Some(crate_path.join("i18n/de/required_languages.ftl")),
+        issues[1].path
+    );
+    assert!(issues[1].message.ends_with(": goodbye"));
+    assert_eq!(Severity::Error, issues[1].severity);
+    assert_eq!(
+        Some(crate_path.join("i18n/fr/required_languages.ftl")),
+        issues[2].path
+    );
+    assert_eq!(Severity::Warning, issues[2].severity);
+    assert!(issues[2].to_string().starts_with("warning: "));
+}
+
+/// Terms referenced by fluent messages must be defined in the same
+/// file, or in the shared `_terms.ftl` file of the language.
+#[test]
+fn fluent_shared_terms() {
+    use i18n_build::{pseudo::pseudolocalize, system::validate};
+    use i18n_embed::Pseudolocalization;
+
+    let tmp = common::temp_crate("shared-terms", "");
+    let crate_path = tmp.path().to_path_buf();
+    for language in ["en", "fr"] {
+        std::fs::create_dir_all(crate_path.join("i18n").join(language)).unwrap();
+    }
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        "fallback_language = \"en\"\n\n[fluent]\nassets_dir = \"i18n\"\n",
+    )
+    .unwrap();
+    std::fs::write(crate_path.join("i18n/en/_terms.ftl"), "-brand = Acme\n").unwrap();
+    std::fs::write(
+        crate_path.join("i18n/en/shared_terms.ftl"),
+        "welcome = Welcome to { -brand }!\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("i18n/fr/_terms.ftl"),
+        "-brand = Acme\n-slogan = { -brand } { -rocket }\n",
+    )
+    .unwrap();
+    let fr_path = crate_path.join("i18n/fr/shared_terms.ftl");
+    std::fs::write(
+        &fr_path,
+        "welcome = Bienvenue chez { -marque } { -brand } !\n",
+    )
+    .unwrap();
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let issues = validate(&crt).unwrap();
+    assert_eq!(2, issues.len(), "{:?}", issues);
+    assert_eq!(Some(fr_path.clone()), issues[0].path);
+    assert!(issues[0].message.ends_with(": -marque"));
+    assert_eq!(Some(crate_path.join("i18n/fr/_terms.ftl")), issues[1].path);
+    assert!(issues[1].message.ends_with(": -rocket"));
+
+    std::fs::write(
+        crate_path.join("i18n/fr/_terms.ftl"),
+        "-brand = Acme\n-rocket = Fusée\n",
+    )
+    .unwrap();
+    std::fs::write(
+        &fr_path,
+        "-marque = Acme\nwelcome = Bienvenue chez { -marque } { -brand } !\n",
+    )
+    .unwrap();
+    assert!(validate(&crt).unwrap().is_empty());
+
+    // The terms are pseudolocalized along with the messages.
+    pseudolocalize(&crt, &Pseudolocalization::default()).unwrap();
+    assert!(crate_path.join("i18n/qps-Ploc/_terms.ftl").exists());
+    assert!(validate(&crt).unwrap().is_empty());
+}
+
+#[test]
+fn qt_and_properties_systems() {
+    use i18n_build::system::{crate_systems, system_for_extension, validate};
+
+    assert_eq!("qt", system_for_extension("ts").unwrap().name());
+    assert_eq!(
+        "properties",
+        system_for_extension("properties").unwrap().name()
+    );
+
+    let tmp = common::temp_crate("qt-and-properties", "");
+    let crate_path = tmp.path().to_path_buf();
+    std::fs::create_dir_all(crate_path.join("i18n/ts/de")).unwrap();
+    std::fs::create_dir_all(crate_path.join("i18n/properties/de")).unwrap();
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        "fallback_language = \"en\"\n\n\
+        [qt]\nassets_dir = \"i18n/ts\"\n\n\
+        [properties]\nassets_dir = \"i18n/properties\"\ndomain = \"messages\"\n",
+    )
+    .unwrap();
+    let ts_path = crate_path.join("i18n/ts/de/qt_and_properties.ts");
+    std::fs::write(&ts_path, "<TS><context></TS>").unwrap();
+    std::fs::write(
+        crate_path.join("i18n/properties/de/messages.properties"),
+        "greeting = Hallo\n",
+    )
+    .unwrap();
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let names: Vec<String> = crate_systems(&crt)
+        .unwrap()
+        .iter()
+        .map(|system| system.name().to_string())
+        .collect();
+    assert_eq!(vec!["qt", "properties"], names);
+
+    let issues = validate(&crt).unwrap();
+    assert_eq!(2, issues.len());
+    assert_eq!(Some(ts_path), issues[0].path);
+    assert!(issues[0].message.starts_with("Syntax error"));
+    assert_eq!(
+        Some(crate_path.join("i18n/properties/en/messages.properties")),
+        issues[1].path
+    );
+
+    std::fs::write(
+        crate_path.join("i18n/ts/de/qt_and_properties.ts"),
+        "<!DOCTYPE TS><TS><context><name>Main</name></context></TS>",
+    )
+    .unwrap();
+    std::fs::create_dir_all(crate_path.join("i18n/properties/en")).unwrap();
+    std::fs::write(
+        crate_path.join("i18n/properties/en/messages.properties"),
+        "greeting = Hello\n",
+    )
+    .unwrap();
+    assert!(validate(&crt).unwrap().is_empty());
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use i18n_config::Crate;
+
+#[test]
+fn translate() {
+    use i18n_build::{
+        init::{init, InitOptions, LocalizationSystem},
+        translate::{translate, MachineTranslator},
+    };
+    use unic_langid::LanguageIdentifier;
+
+    /// Translates text by converting it to uppercase.
+    struct UppercaseTranslator;
+
+    impl MachineTranslator for UppercaseTranslator {
+        fn translate(
+            &self,
+            text: &str,
+            _source: &LanguageIdentifier,
+            _target: &LanguageIdentifier,
+        ) -> anyhow::Result<String> {
+            Ok(text.to_uppercase())
+        }
+    }
+
+    let de: LanguageIdentifier = "de".parse().unwrap();
+    for system in [LocalizationSystem::Fluent, LocalizationSystem::Gettext] {
+        let tmp = common::temp_crate("translate", "");
+        let crate_path = tmp.path().to_path_buf();
+        std::fs::create_dir_all(crate_path.join("src")).unwrap();
+        let options = InitOptions {
+            system,
+            target_languages: vec!["de".to_string()],
+            ..InitOptions::default()
+        };
+        init(&crate_path, "i18n.toml", &options).unwrap();
+
+        match system {
+            LocalizationSystem::Fluent => {
+                std::fs::write(
+                    crate_path.join("i18n/en-US/translate.ftl"),
+                    "hello-world = Hello World!\n\
+                     greeting = Hello { $name }!\n\
+                     emails = { $count ->\n    [one] One email\n   *[other] { $count } emails\n}\n",
+                )
+                .unwrap();
+                std::fs::create_dir_all(crate_path.join("i18n/de")).unwrap();
+                std::fs::write(
+                    crate_path.join("i18n/de/translate.ftl"),
+                    "hello-world = Hallo Welt!\n",
+                )
+                .unwrap();
+            }
+            LocalizationSystem::Gettext => {
+                std::fs::create_dir_all(crate_path.join("i18n/po/de")).unwrap();
+                std::fs::write(
+                    crate_path.join("i18n/po/de/translate.po"),
+                    "msgid \"\"\n\
+                     msgstr \"\"\n\
+                     \"Language: de\\n\"\n\
+                     \n\
+                     #: src/main.rs:1\n\
+                     msgid \"Hello World!\"\n\
+                     msgstr \"Hallo Welt!\"\n\
+                     \n\
+                     #: src/main.rs:2\n\
+                     msgid \"Hello {0}!\"\n\
+                     msgstr \"\"\n\
+                     \n\
+                     #: src/main.rs:3\n\
+                     msgid \"Hello {name}!\"\n\
+                     msgstr \"\"\n",
+                )
+                .unwrap();
+            }
+        }
+
+        let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+        let changed = translate(&crt, &UppercaseTranslator, std::slice::from_ref(&de)).unwrap();
+        assert_eq!(1, changed.len());
+
+        match system {
+            LocalizationSystem::Fluent => {
+                let ftl =
+                    std::fs::read_to_string(crate_path.join("i18n/de/translate.ftl")).unwrap();
+                assert_eq!(
+                    "hello-world = Hallo Welt!\n\n\
+                     # Machine translated draft, needs review.\n\
+                     greeting = HELLO { $name }!\n",
+                    ftl
+                );
+            }
+            LocalizationSystem::Gettext => {
+                let po =
+                    std::fs::read_to_string(crate_path.join("i18n/po/de/translate.po")).unwrap();
+                assert!(po.contains(
+                    "#: src/main.rs:2\n#, fuzzy\nmsgid \"Hello {0}!\"\nmsgstr \"HELLO {0}!\"\n"
+                ));
+                // The translation didn't preserve the placeholder.
+                assert!(po.ends_with("msgid \"Hello {name}!\"\nmsgstr \"\"\n"));
+            }
+        }
+
+        // Translating again doesn't change anything.
+        assert!(translate(&crt, &UppercaseTranslator, &[])
+            .unwrap()
+            .is_empty());
+    }
+}
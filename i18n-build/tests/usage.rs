@@ -0,0 +1,271 @@
+mod common;
+
+use i18n_config::Crate;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn message_usages() {
+    use i18n_build::usage::message_usages;
+
+    let tmp = common::temp_crate("message-usages", "");
+    let crate_path = tmp.path().to_path_buf();
+    std::fs::create_dir_all(crate_path.join("src/ui")).unwrap();
+    std::fs::write(
+        crate_path.join("src/main.rs"),
+        "mod ui;\n\
+         \n\
+         fn main() {\n\
+         \x20   // fl!(LOADER, \"commented-out\")\n\
+         \x20   let s = \"fl!(LOADER, \\\"in-a-string\\\")\";\n\
+         \x20   println!(\"{}\", fl!(LOADER, \"hello-world\"));\n\
+         \x20   i18n_embed_fl::fl!(\n\
+         \x20       LOADER,\n\
+         \x20       \"greeting\",\n\
+         \x20       name = fl!(LOADER, \"hello-world\")\n\
+         \x20   );\n\
+         \x20   fl!(LOADER, id);\n\
+         }\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("src/ui/mod.rs"),
+        "pub fn title() -> String {\n    fl!(LOADER, \"hello-world\", \"title\")\n}\n",
+    )
+    .unwrap();
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let usages: Vec<(String, Vec<String>)> = message_usages(&crt)
+        .unwrap()
+        .into_iter()
+        .map(|(id, locations)| {
+            (
+                id,
+                locations
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            ("greeting".to_string(), vec!["src/main.rs:7".to_string()]),
+            (
+                "hello-world".to_string(),
+                vec![
+                    "src/main.rs:6".to_string(),
+                    "src/main.rs:10".to_string(),
+                    "src/ui/mod.rs:2".to_string(),
+                ]
+            ),
+        ],
+        usages
+    );
+}
+
+/// The source directories of a crate are found using the target
+/// paths of its `Cargo.toml`, and crates without any source code are
+/// skipped.
+#[test]
+fn source_dirs() {
+    use i18n_build::usage::message_usages;
+
+    let tmp = common::temp_crate(
+        "source-dirs",
+        "\n[lib]\nproc-macro = true\npath = \"lib/lib.rs\"\n\n\
+        [[bin]]\nname = \"tool\"\npath = \"src/bin/tool.rs\"\n",
+    );
+    let crate_path = tmp.path().to_path_buf();
+    std::fs::create_dir_all(crate_path.join("lib")).unwrap();
+    std::fs::create_dir_all(crate_path.join("src/bin")).unwrap();
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        "fallback_language = \"en\"\n\n\
+        [gettext]\ntarget_languages = [\"fr\"]\noutput_dir = \"i18n\"\n\n\
+        [fluent]\nassets_dir = \"i18n\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("lib/lib.rs"),
+        "fn f() { fl!(LOADER, \"from-lib\"); }\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("src/bin/tool.rs"),
+        "fn main() { fl!(LOADER, \"from-bin\"); }\n",
+    )
+    .unwrap();
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    assert_eq!(
+        vec![PathBuf::from("lib"), PathBuf::from("src")],
+        crt.source_dirs
+    );
+    assert_eq!(
+        vec![crate_path.join("lib"), crate_path.join("src")],
+        crt.gettext_config_or_err().unwrap().src_dirs(&crt)
+    );
+    let ids: Vec<String> = message_usages(&crt).unwrap().into_keys().collect();
+    assert_eq!(vec!["from-bin".to_string(), "from-lib".to_string()], ids);
+
+    // The crate no longer has any source code.
+    std::fs::remove_dir_all(crate_path.join("lib")).unwrap();
+    std::fs::remove_dir_all(crate_path.join("src")).unwrap();
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    assert!(crt.source_dirs.is_empty());
+    assert!(message_usages(&crt).unwrap().is_empty());
+    let summary = i18n_build::run_with_summary(crt, std::num::NonZeroUsize::MIN).unwrap();
+    assert_eq!(None, summary.crates[0].messages);
+    assert!(!crate_path.join("i18n/pot").join("source_dirs.pot").exists());
+    assert!(!crate_path.join("i18n/po").exists());
+
+    // The `src_dir` is used instead, even if it doesn't exist.
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        "fallback_language = \"en\"\n\n\
+        [gettext]\ntarget_languages = [\"fr\"]\noutput_dir = \"i18n\"\nsrc_dir = \"code\"\n",
+    )
+    .unwrap();
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    assert_eq!(
+        vec![crate_path.join("code")],
+        crt.gettext_config_or_err().unwrap().src_dirs(&crt)
+    );
+    assert!(i18n_build::run(crt).is_err());
+}
+
+/// The source files ignored by `.gitignore` files, or by the `ignore`
+/// patterns of the config, are not extracted or searched for `fl!()`
+/// calls.
+#[test]
+fn source_ignore() {
+    use i18n_build::{usage::message_usages, util::rust_source_files};
+
+    let tmp = common::temp_crate("source-ignore", "");
+    let crate_path = tmp.path().to_path_buf();
+    std::fs::create_dir_all(crate_path.join("src/generated")).unwrap();
+    std::fs::create_dir_all(crate_path.join("src/proto")).unwrap();
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        "fallback_language = \"en\"\n\n\
+        [gettext]\ntarget_languages = []\noutput_dir = \"i18n\"\nignore = [\"**/generated/**\"]\n\n\
+        [fluent]\nassets_dir = \"i18n\"\nignore = [\"**/generated/**\"]\n",
+    )
+    .unwrap();
+    std::fs::write(crate_path.join(".gitignore"), "/src/proto\n").unwrap();
+    for path in [
+        "src/main.rs",
+        "src/generated/messages.rs",
+        "src/proto/api.rs",
+    ] {
+        let id = Path::new(path).file_stem().unwrap().to_string_lossy();
+        std::fs::write(
+            crate_path.join(path),
+            format!("fn f() {{ fl!(LOADER, \"{0}\"); }}\n", id),
+        )
+        .unwrap();
+    }
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let ignore = &crt.gettext_config_or_err().unwrap().ignore;
+    assert_eq!(vec!["**/generated/**".to_string()], *ignore);
+    assert_eq!(
+        vec![crate_path.join("src/main.rs")],
+        rust_source_files(&crt, &crate_path.join("src"), ignore).unwrap()
+    );
+    assert_eq!(
+        vec![
+            crate_path.join("src/generated/messages.rs"),
+            crate_path.join("src/main.rs")
+        ],
+        rust_source_files(&crt, &crate_path.join("src"), &[]).unwrap()
+    );
+    let ids: Vec<String> = message_usages(&crt).unwrap().into_keys().collect();
+    assert_eq!(vec!["main".to_string()], ids);
+}
+
+#[test]
+fn fluent_source_comments() {
+    use i18n_build::{
+        add_language::add_language,
+        system::{FluentSystem, LocalizationSystem},
+        usage::message_comments,
+    };
+
+    let tmp = common::temp_crate("source-comments", "");
+    let crate_path = tmp.path().to_path_buf();
+    std::fs::create_dir_all(crate_path.join("src")).unwrap();
+    std::fs::create_dir_all(crate_path.join("i18n/en")).unwrap();
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        "fallback_language = \"en\"\n\n\
+        [gettext]\ntarget_languages = []\noutput_dir = \"i18n\"\nadd_comments = \"i18n:\"\n\n\
+        [fluent]\nassets_dir = \"i18n\"\nsource_comments = \"i18n:\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("src/main.rs"),
+        "fn main() {\n\
+         \x20   // A comment which isn't for translators.\n\
+         \x20   // i18n: Shown when the application starts,\n\
+         \x20   // before the user has logged in.\n\
+         \x20   println!(\"{}\", fl!(LOADER, \"hello-world\"));\n\
+         \x20   // i18n: Not directly above the call.\n\
+         \n\
+         \x20   println!(\"{}\", fl!(LOADER, \"bye\"));\n\
+         }\n\
+         \n\
+         /// i18n: The title of the window.\n\
+         fn title() -> String { fl!(LOADER, \"title\") }\n",
+    )
+    .unwrap();
+    let ftl_path = crate_path.join("i18n/en/source_comments.ftl");
+    std::fs::write(
+        &ftl_path,
+        "# An outdated comment.\nhello-world = Hello World!\nbye = Bye!\ntitle = Title\n",
+    )
+    .unwrap();
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    assert_eq!(
+        Some("i18n:"),
+        crt.gettext_config_or_err().unwrap().add_comments.as_deref()
+    );
+    let comments = message_comments(&crt, "i18n:").unwrap();
+    assert_eq!(
+        Some(&vec![
+            "Shown when the application starts,\nbefore the user has logged in.".to_string()
+        ]),
+        comments.get("hello-world")
+    );
+    assert_eq!(None, comments.get("bye"));
+
+    for _ in 0..2 {
+        FluentSystem
+            .extract(
+                &crt,
+                i18n_build::util::default_jobs(),
+                &mut Default::default(),
+            )
+            .unwrap();
+        assert_eq!(
+            "# Shown when the application starts,\n\
+             # before the user has logged in.\n\
+             hello-world = Hello World!\n\
+             bye = Bye!\n\
+             # The title of the window.\n\
+             title = Title\n",
+            std::fs::read_to_string(&ftl_path).unwrap()
+        );
+    }
+
+    // The comments are copied to the files of new languages.
+    add_language(&crt, "de").unwrap();
+    let ftl = std::fs::read_to_string(crate_path.join("i18n/de/source_comments.ftl")).unwrap();
+    assert!(ftl.contains(
+        "# Shown when the application starts,\n\
+         # before the user has logged in.\n\
+         # Used in src/main.rs:5\n\
+         # hello-world = Hello World!\n"
+    ));
+}
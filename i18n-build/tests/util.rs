@@ -0,0 +1,37 @@
+use std::path::PathBuf;
+
+/// When a command fails, its output is captured, and the problems
+/// with specific files reported in it are parsed.
+#[test]
+fn command_error_diagnostics() {
+    use i18n_build::{error::CommandError, system::Severity, util::run_command_and_check_success};
+
+    let mut command = std::process::Command::new("sh");
+    command.args([
+        "-c",
+        "echo 'msgfmt: po/fr/app.po:12: missing msgstr' >&2; \
+        echo 'po/fr/app.po:20:3: warning: unknown keyword' >&2; \
+        echo 'msgfmt: found 1 fatal error' >&2; \
+        exit 1",
+    ]);
+    let err = run_command_and_check_success("msgfmt", command).unwrap_err();
+    let command_error = err.downcast_ref::<CommandError>().unwrap();
+    assert_eq!(Some(1), command_error.status.code());
+    assert!(command_error.stderr.contains("found 1 fatal error"));
+    assert_eq!(2, command_error.diagnostics.len());
+    assert_eq!(
+        PathBuf::from("po/fr/app.po"),
+        command_error.diagnostics[0].path
+    );
+    assert_eq!(12, command_error.diagnostics[0].line);
+    assert_eq!(Severity::Error, command_error.diagnostics[0].severity);
+    assert_eq!(Some(3), command_error.diagnostics[1].column);
+    assert_eq!(Severity::Warning, command_error.diagnostics[1].severity);
+    assert!(err.to_string().ends_with(
+        "\npo/fr/app.po:12: missing msgstr\npo/fr/app.po:20:3: warning: unknown keyword"
+    ));
+
+    let mut command = std::process::Command::new("sh");
+    command.args(["-c", "exit 0"]);
+    run_command_and_check_success("sh", command).unwrap();
+}
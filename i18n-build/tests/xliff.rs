@@ -0,0 +1,127 @@
+mod common;
+
+use i18n_config::Crate;
+
+#[test]
+fn xliff() {
+    use i18n_build::{
+        init::{init, InitOptions, LocalizationSystem},
+        xliff::{export, import, XliffDocument, XliffVersion},
+    };
+    use unic_langid::LanguageIdentifier;
+
+    let de: LanguageIdentifier = "de".parse().unwrap();
+    for system in [LocalizationSystem::Fluent, LocalizationSystem::Gettext] {
+        for version in [XliffVersion::V1_2, XliffVersion::V2_0] {
+            let tmp = common::temp_crate("xliff", "");
+            let crate_path = tmp.path().to_path_buf();
+            std::fs::create_dir_all(crate_path.join("src")).unwrap();
+            let options = InitOptions {
+                system,
+                target_languages: vec!["de".to_string()],
+                ..InitOptions::default()
+            };
+            init(&crate_path, "i18n.toml", &options).unwrap();
+
+            match system {
+                LocalizationSystem::Fluent => {
+                    std::fs::write(
+                        crate_path.join("i18n/en-US/xliff.ftl"),
+                        "hello-world = Hello World!\n\
+                         # The greeting for a <person>.\n\
+                         greeting = Hello { $name }!\n    .title = Greeting\n",
+                    )
+                    .unwrap();
+                    std::fs::create_dir_all(crate_path.join("i18n/de")).unwrap();
+                    std::fs::write(
+                        crate_path.join("i18n/de/xliff.ftl"),
+                        "hello-world = Hallo Welt!\n",
+                    )
+                    .unwrap();
+                }
+                LocalizationSystem::Gettext => {
+                    std::fs::create_dir_all(crate_path.join("i18n/po/de")).unwrap();
+                    std::fs::write(
+                        crate_path.join("i18n/po/de/xliff.po"),
+                        "msgid \"\"\n\
+                         msgstr \"\"\n\
+                         \"Language: de\\n\"\n\
+                         \n\
+                         #: src/main.rs:1\n\
+                         msgid \"Hello World!\"\n\
+                         msgstr \"Hallo Welt!\"\n\
+                         \n\
+                         #. The greeting for a <person>.\n\
+                         #: src/main.rs:2\n\
+                         msgctxt \"greeting\"\n\
+                         msgid \"Hello {0}!\"\n\
+                         msgstr \"\"\n",
+                    )
+                    .unwrap();
+                }
+            }
+
+            let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+            let document = export(&crt, &de, version).unwrap();
+            let xml = document.to_xml();
+            assert!(xml.contains("The greeting for a &lt;person&gt;."));
+            let mut document = XliffDocument::from_xml(&xml).unwrap();
+            assert_eq!(version, document.version);
+            assert_eq!(de, document.target_language);
+            assert_eq!(1, document.files.len());
+
+            let file = &mut document.files[0];
+            let units = &mut file.units;
+            match system {
+                LocalizationSystem::Fluent => {
+                    assert_eq!("xliff.ftl", file.original);
+                    assert_eq!(3, units.len());
+                    assert_eq!("hello-world", units[0].id);
+                    assert_eq!(Some("Hallo Welt!"), units[0].target.as_deref());
+                    assert_eq!("Hello { $name }!", units[1].source);
+                    assert_eq!(vec!["The greeting for a <person>."], units[1].notes);
+                    assert_eq!("greeting.title", units[2].id);
+                    assert_eq!(None, units[2].target);
+                    units[1].target = Some("Hallo { $name }!".to_string());
+                    units[2].target = Some("Begrüßung".to_string());
+                    units[2].needs_review = true;
+                }
+                LocalizationSystem::Gettext => {
+                    assert_eq!("xliff.po", file.original);
+                    assert_eq!(2, units.len());
+                    assert_eq!("Hello World!", units[0].source);
+                    assert_eq!(Some("Hallo Welt!"), units[0].target.as_deref());
+                    assert_eq!(Some("greeting"), units[1].context.as_deref());
+                    assert_eq!(None, units[1].target);
+                    units[1].target = Some("Hallo {0}!".to_string());
+                    units[1].needs_review = true;
+                }
+            }
+
+            let document = XliffDocument::from_xml(&document.to_xml()).unwrap();
+            let changed = import(&crt, &document).unwrap();
+            assert_eq!(1, changed.len());
+
+            match system {
+                LocalizationSystem::Fluent => {
+                    let ftl =
+                        std::fs::read_to_string(crate_path.join("i18n/de/xliff.ftl")).unwrap();
+                    assert_eq!(
+                        "hello-world = Hallo Welt!\n\
+                         # Machine translated draft, needs review.\n\
+                         greeting = Hallo { $name }!\n    .title = Begrüßung\n",
+                        ftl
+                    );
+                }
+                LocalizationSystem::Gettext => {
+                    let po =
+                        std::fs::read_to_string(crate_path.join("i18n/po/de/xliff.po")).unwrap();
+                    assert!(po.ends_with(
+                        "#: src/main.rs:2\n#, fuzzy\nmsgctxt \"greeting\"\n\
+                         msgid \"Hello {0}!\"\nmsgstr \"Hallo {0}!\"\n"
+                    ));
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
+use crate::ObsoletePolicy;
 use serde::Deserialize;
 use std::path::PathBuf;
+use unic_langid::LanguageIdentifier;
 
 /// The data structure representing what is stored (and possible to
 /// store) within the `fluent` subsection of a `i18n.toml` file.
@@ -14,5 +16,149 @@ pub struct FluentConfig {
     /// (Optional) Domain name to override default value (i.e. package name)
     /// The paths inside the assets directory should be  structured
     /// like so: `assets_dir/{language}/{domain}.ftl`
+    ///
+    /// Several crates can share a single set of localization files
+    /// by specifying the same `domain` and `assets_dir`.
     pub domain: Option<String>,
+
+    /// (Optional) The template for the paths of the fluent files
+    /// within the assets directory, for projects using another
+    /// layout, e.g. `{domain}/{language}.ftl` or
+    /// `locales/{language}.ftl`. It needs to contain the
+    /// `{language}` placeholder, and may contain the `{domain}`
+    /// placeholder. Namespace files are located in the directory
+    /// with the same path as the language file without its
+    /// extension.
+    ///
+    /// Default: `{language}/{domain}.ftl`.
+    pub path_template: Option<String>,
+
+    /// (Optional) The languages which need to contain all of the
+    /// messages of the fallback language. `cargo i18n validate` fails
+    /// when they are missing messages, and only warns about missing
+    /// messages in the other (best-effort) languages.
+    #[serde(default)]
+    pub required_languages: Vec<LanguageIdentifier>,
+
+    /// (Optional) Terms such as brand or product names which need to
+    /// be left untranslated. `cargo i18n lint` reports translations
+    /// which don't contain the protected terms used in the message of
+    /// the fallback language.
+    #[serde(default)]
+    pub protected_terms: Vec<String>,
+
+    /// (Optional) The severity of each of the lints checked by
+    /// `cargo i18n lint`.
+    #[serde(default)]
+    pub lints: FluentLints,
+
+    /// (Optional) Whether the `fl!()` macro generates unchecked code
+    /// with a warning, instead of failing to compile, when the
+    /// localization files of the fallback language cannot be found.
+    /// This is useful when the crate is compiled without them, such
+    /// as in `trybuild` tests or vendored builds. See also
+    /// [skip_compile_time_checks()](crate::skip_compile_time_checks()).
+    ///
+    /// Default: `false`.
+    #[serde(default)]
+    pub allow_missing_at_compile_time: bool,
+    /// How the messages of the other languages which are no longer
+    /// in the fallback language are treated when `cargo i18n` is run.
+    /// By default they are kept, otherwise they are removed from the
+    /// fluent files (and moved to their attic files, see
+    /// [ObsoletePolicy::Attic]).
+    ///
+    /// Default: `keep`.
+    #[serde(default)]
+    pub obsolete_policy: ObsoletePolicy,
+    /// (Optional) Whether the loaders created by the
+    /// `fluent_language_loader!()` and `static_loader!()` macros
+    /// insert Unicode Directionality Isolation Marks around
+    /// placeables. The setting is kept by the loader, and applied
+    /// to the languages each time they are loaded.
+    ///
+    /// Default: `true`.
+    #[serde(default = "default_use_isolating")]
+    pub use_isolating: bool,
+    /// (Optional) The tag (e.g. `"i18n:"`) of the comments preceding
+    /// the `fl!()` calls in the source code (such as `// i18n: Shown
+    /// on the login screen.`) which are copied to the messages in the
+    /// fluent files of the fallback language as translator comments
+    /// when `cargo i18n` is run, replacing their existing comments.
+    ///
+    /// By default the comments are not copied.
+    pub source_comments: Option<String>,
+    /// (Optional) Glob patterns for the paths (relative to the crate)
+    /// of the rust source files which are not searched for `fl!()`
+    /// calls, such as generated code, e.g. `"**/generated/**"`. The
+    /// files ignored by `.gitignore` files are also not searched.
+    #[serde(default)]
+    pub ignore: Vec<String>,
+}
+
+fn default_use_isolating() -> bool {
+    true
+}
+
+/// The severity of a lint checked by `cargo i18n lint`.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// The lint is not checked.
+    Allow,
+    /// Problems are reported, but don't fail the lint.
+    Warning,
+    /// Problems are reported, and fail the lint.
+    Error,
+}
+
+/// The `lints` subsection of the `fluent` subsection of a
+/// `i18n.toml` file, with the [LintLevel] of each of the lints
+/// checked by `cargo i18n lint`.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct FluentLints {
+    /// Variables in a translation which are not in the message of
+    /// the fallback language. Default: `error`.
+    #[serde(default = "LintLevel::error")]
+    pub unknown_variables: LintLevel,
+    /// Protected terms (see [FluentConfig::protected_terms]) in the
+    /// message of the fallback language which are missing from a
+    /// translation. Default: `error`.
+    #[serde(default = "LintLevel::error")]
+    pub protected_terms: LintLevel,
+    /// Two or more consecutive spaces in the text of a message.
+    /// Default: `warning`.
+    #[serde(default = "LintLevel::warning")]
+    pub double_spaces: LintLevel,
+    /// Whitespace at the end of a line. Default: `warning`.
+    #[serde(default = "LintLevel::warning")]
+    pub trailing_whitespace: LintLevel,
+    /// HTML tags or format specifiers (e.g. `%s`) in a translation
+    /// which differ from the message of the fallback language.
+    /// Default: `warning`.
+    #[serde(default = "LintLevel::warning")]
+    pub markup: LintLevel,
+}
+
+impl LintLevel {
+    fn error() -> Self {
+        LintLevel::Error
+    }
+
+    fn warning() -> Self {
+        LintLevel::Warning
+    }
+}
+
+impl Default for FluentLints {
+    fn default() -> Self {
+        Self {
+            unknown_variables: LintLevel::Error,
+            protected_terms: LintLevel::Error,
+            double_spaces: LintLevel::Warning,
+            trailing_whitespace: LintLevel::Warning,
+            markup: LintLevel::Warning,
+        }
+    }
 }
@@ -1,5 +1,8 @@
 use serde::Deserialize;
-use std::path::PathBuf;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
 /// The data structure representing what is stored (and possible to
 /// store) within the `gettext` subsection of a `i18n.toml` file.
@@ -40,6 +43,56 @@ pub struct GettextConfig {
     ///  nothing is generated. [possible values: full, file, never].
     #[serde(default)]
     pub add_location: GettextAddLocation,
+    /// Place the comment blocks starting with this tag (e.g.
+    /// `"i18n:"`) which precede the messages in the source code in
+    /// the pot files as translator comments, using the
+    /// `--add-comments` option of the `xtr` command.
+    ///
+    /// By default no comments are placed in the pot files.
+    pub add_comments: Option<String>,
+    /// How the messages of the generated pot files are sorted when
+    /// the pot files of the source files are concatinated with the
+    /// `msgcat` command. [possible values: none, file, msgid].
+    ///
+    /// By default this is **none**, the messages are in the order of
+    /// the source files (sorted by their path) and their lines.
+    #[serde(default)]
+    pub sort_output: GettextSortOutput,
+    /// Additional keywords for the `xtr` command to extract messages
+    /// from, using the same syntax as `xgettext`. For example
+    /// `"ctr:1c,2"` extracts messages from `ctr!("context", "message")`
+    /// macro calls, using the first argument as the message context
+    /// (`msgctxt`). Messages with a context in the `tr!("context" =>
+    /// "message")` form are always extracted.
+    #[serde(default)]
+    pub xtr_keywords: Vec<String>,
+    /// Directories (relative to the crate) containing rust source
+    /// files which are not extracted by the `xtr` command, such as
+    /// test helpers or examples within `src`.
+    #[serde(default)]
+    pub exclude_dirs: Vec<PathBuf>,
+    /// Glob patterns for the paths (relative to the crate) of the
+    /// rust source files in `src` which are extracted by the `xtr`
+    /// command, e.g. `"src/ui/**/*.rs"`. `*` matches any characters
+    /// within a path component, and `**` matches any number of path
+    /// components. By default all of the rust source files in `src`
+    /// are extracted.
+    #[serde(default)]
+    pub include_globs: Vec<String>,
+    /// Glob patterns for the paths (relative to the crate) of the
+    /// rust source files which are not extracted by the `xtr`
+    /// command, such as generated code, e.g. `"**/generated/**"`.
+    /// The files ignored by `.gitignore` files are also not
+    /// extracted.
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    /// The directory (relative to the crate) containing the rust
+    /// source files which are extracted by the `xtr` command.
+    ///
+    /// By default this is each of the
+    /// [source_dirs](crate::Crate::source_dirs) of the crate, and
+    /// string extraction is skipped for crates without any.
+    pub src_dir: Option<PathBuf>,
     /// Path to where the pot files will be written to by the `xtr`
     /// command, and were they will be read from by `msginit` and
     /// `msgmerge`.
@@ -56,6 +109,33 @@ pub struct GettextConfig {
     /// By default this is **false**.
     #[serde(default)]
     pub use_fuzzy: bool,
+    /// Additional arguments for the `msgmerge` command, such as
+    /// `["--no-fuzzy-matching"]`. They are not used by the built-in
+    /// replacement for `msgmerge`.
+    #[serde(default)]
+    pub msgmerge_args: Vec<String>,
+    /// Additional arguments for the `msgfmt` command, such as
+    /// `["--check", "--statistics"]` or `["--endianness=big"]`. They
+    /// are not used by the built-in replacement for `msgfmt`.
+    #[serde(default)]
+    pub msgfmt_args: Vec<String>,
+    /// The minimum similarity (from `0.0` to `1.0`) between the
+    /// `msgid` of a new message and an existing translation for the
+    /// translation to be used as a fuzzy match, when the `po` files
+    /// are updated using the built-in replacement for `msgmerge`.
+    fuzzy_threshold: Option<f64>,
+    /// How the obsolete entries of the `po` files are treated after
+    /// they have been updated.
+    ///
+    /// By default they are **kept**.
+    #[serde(default)]
+    pub obsolete_policy: ObsoletePolicy,
+    /// Metadata to set in the header of the generated pot files.
+    #[serde(default)]
+    pub pot_header: GettextPotHeader,
+    /// The executables to run for the gettext tools.
+    #[serde(default)]
+    pub tools: GettextTools,
 }
 
 impl GettextConfig {
@@ -97,6 +177,232 @@ impl GettextConfig {
             .clone()
             .unwrap_or_else(|| self.output_dir.join("mo"))
     }
+
+    /// The minimum similarity between the `msgid` of a new message
+    /// and an existing translation for the translation to be used as a
+    /// fuzzy match, when the `po` files are updated using the built-in
+    /// replacement for `msgmerge`. `1.0` disables fuzzy matching.
+    ///
+    /// By default this is **0.6**, like `msgmerge`.
+    pub fn fuzzy_threshold(&self) -> f64 {
+        self.fuzzy_threshold.unwrap_or(0.6)
+    }
+
+    /// The directories containing the rust source files of the crate
+    /// `crt` which are extracted by the `xtr` command: the
+    /// [src_dir](GettextConfig::src_dir) if there is one, otherwise
+    /// each of the [source_dirs](crate::Crate::source_dirs) of the
+    /// crate.
+    pub fn src_dirs(&self, crt: &crate::Crate) -> Vec<PathBuf> {
+        match &self.src_dir {
+            Some(src_dir) => vec![crt.path.join(src_dir)],
+            None => crt
+                .source_dirs
+                .iter()
+                .map(|source_dir| crt.path.join(source_dir))
+                .collect(),
+        }
+    }
+
+    /// Whether the rust source file at `path` (relative to the crate)
+    /// is extracted by the `xtr` command: it is not within one of the
+    /// [exclude_dirs](GettextConfig::exclude_dirs), and matches one
+    /// of the [include_globs](GettextConfig::include_globs) if there
+    /// are any.
+    ///
+    /// ```
+    /// use i18n_config::GettextConfig;
+    /// use std::path::Path;
+    ///
+    /// let config: GettextConfig = basic_toml::from_str(
+    ///     r#"
+    ///     target_languages = ["fr"]
+    ///     output_dir = "i18n"
+    ///     exclude_dirs = ["src/ui/test_helpers"]
+    ///     include_globs = ["src/main.rs", "src/ui/**/*.rs"]
+    ///     "#,
+    /// )
+    /// .unwrap();
+    /// assert!(config.is_extracted(Path::new("src/main.rs")));
+    /// assert!(config.is_extracted(Path::new("src/ui/window.rs")));
+    /// assert!(config.is_extracted(Path::new("src/ui/dialogs/about.rs")));
+    /// assert!(!config.is_extracted(Path::new("src/ui/test_helpers/mock.rs")));
+    /// assert!(!config.is_extracted(Path::new("src/lib.rs")));
+    /// ```
+    pub fn is_extracted(&self, path: &Path) -> bool {
+        if self
+            .exclude_dirs
+            .iter()
+            .any(|exclude_dir| path.starts_with(exclude_dir))
+        {
+            return false;
+        }
+        let components: Vec<String> = path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().to_string())
+            .collect();
+        self.include_globs.is_empty()
+            || self.include_globs.iter().any(|glob| {
+                let glob_components: Vec<&str> = glob
+                    .split('/')
+                    .filter(|component| !component.is_empty() && *component != ".")
+                    .collect();
+                glob_matches(&glob_components, &components)
+            })
+    }
+}
+
+/// Whether the path `components` match the `glob` components, where
+/// `**` matches any number of components.
+fn glob_matches(glob: &[&str], components: &[String]) -> bool {
+    match glob.split_first() {
+        None => components.is_empty(),
+        Some((&"**", rest)) => {
+            (0..=components.len()).any(|skip| glob_matches(rest, &components[skip..]))
+        }
+        Some((pattern, rest)) => match components.split_first() {
+            Some((component, components)) => {
+                crate::wildcard_matches(pattern, component) && glob_matches(rest, components)
+            }
+            None => false,
+        },
+    }
+}
+
+/// The data structure representing what is stored (and possible to
+/// store) within the `gettext.pot_header` subsection of a `i18n.toml`
+/// file. These values are written to the header of the pot file
+/// after the strings have been extracted.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct GettextPotHeader {
+    /// The value of the `Project-Id-Version` header. By default this
+    /// is the crate name and version.
+    pub project_id_version: Option<String>,
+    /// The value of the `Language-Team` header.
+    pub language_team: Option<String>,
+    /// Additional headers to set, for example `X-Generator`. Existing
+    /// headers with the same name are replaced.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+/// The data structure representing what is stored (and possible to
+/// store) within the `gettext.tools` subsection of a `i18n.toml`
+/// file. Each value is either the path to the executable of a tool,
+/// or the name of an executable on the `PATH`, for systems where the
+/// tools are installed in a non-standard location (e.g. on Windows
+/// or Nix). By default the tools are run using their name.
+///
+/// The executables can also be overridden using the
+/// `CARGO_I18N_XTR`, `CARGO_I18N_MSGCAT`, `CARGO_I18N_MSGINIT`,
+/// `CARGO_I18N_MSGMERGE` and `CARGO_I18N_MSGFMT` environment
+/// variables, which take precedence over this config.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct GettextTools {
+    /// The `xtr` executable, used to extract the strings from the
+    /// rust source files.
+    pub xtr: Option<PathBuf>,
+    /// The `msgcat` executable, used to concatinate pot files.
+    pub msgcat: Option<PathBuf>,
+    /// The `msginit` executable, used to create po files.
+    pub msginit: Option<PathBuf>,
+    /// The `msgmerge` executable, used to update po files.
+    pub msgmerge: Option<PathBuf>,
+    /// The `msgfmt` executable, used to compile mo files.
+    pub msgfmt: Option<PathBuf>,
+}
+
+impl GettextTools {
+    /// The names of the tools which can be configured.
+    pub const NAMES: [&'static str; 5] = ["xtr", "msgcat", "msginit", "msgmerge", "msgfmt"];
+
+    /// The name of the environment variable which overrides the
+    /// executable of the tool with the `name`.
+    ///
+    /// ```
+    /// use i18n_config::GettextTools;
+    ///
+    /// assert_eq!("CARGO_I18N_MSGFMT", GettextTools::env_var("msgfmt"));
+    /// ```
+    pub fn env_var(name: &str) -> String {
+        format!("CARGO_I18N_{}", name.to_uppercase())
+    }
+
+    /// The executable to run for the tool with the `name` (one of
+    /// [GettextTools::NAMES]): the value of its environment variable
+    /// (see [GettextTools::env_var()]) if it is set, otherwise the
+    /// configured executable, otherwise the `name` itself.
+    pub fn program(&self, name: &str) -> PathBuf {
+        self.configured(name).unwrap_or_else(|| PathBuf::from(name))
+    }
+
+    /// The executable explicitly specified for the tool with the
+    /// `name` using its environment variable or this config, see
+    /// [GettextTools::program()].
+    pub fn configured(&self, name: &str) -> Option<PathBuf> {
+        if let Some(program) = std::env::var_os(Self::env_var(name)).filter(|p| !p.is_empty()) {
+            return Some(PathBuf::from(program));
+        }
+        match name {
+            "xtr" => self.xtr.clone(),
+            "msgcat" => self.msgcat.clone(),
+            "msginit" => self.msginit.clone(),
+            "msgmerge" => self.msgmerge.clone(),
+            "msgfmt" => self.msgfmt.clone(),
+            _ => None,
+        }
+    }
+}
+
+/// How the obsolete entries of the translations (the translations of
+/// messages which were removed from the source) are treated when the
+/// translations are updated by `cargo i18n`.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ObsoletePolicy {
+    /// Keep them in the file, as `#~` comments in `po` files.
+    #[default]
+    Keep,
+    /// Remove them from the file.
+    Prune,
+    /// Move them from the file to the end of its attic file, which is
+    /// next to it with `.attic` appended to its name (e.g.
+    /// `my_crate.po.attic`), so that they can still be referred to.
+    Attic,
+}
+
+/// How the messages of the generated pot files are sorted, see
+/// [GettextConfig::sort_output].
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GettextSortOutput {
+    /// Keep the messages in the order in which they were extracted.
+    #[default]
+    None,
+    /// Sort the messages by the path of the file they were extracted
+    /// from, and then by their line.
+    File,
+    /// Sort the messages by their `msgid`.
+    Msgid,
+}
+
+impl GettextSortOutput {
+    /// The option of the `msgcat` command which sorts its output in
+    /// this way, if any.
+    ///
+    /// ```
+    /// use i18n_config::GettextSortOutput;
+    ///
+    /// assert_eq!(Some("--sort-by-file"), GettextSortOutput::File.msgcat_option());
+    /// assert_eq!(None, GettextSortOutput::None.msgcat_option());
+    /// ```
+    pub fn msgcat_option(&self) -> Option<&'static str> {
+        match self {
+            GettextSortOutput::None => None,
+            GettextSortOutput::File => Some("--sort-by-file"),
+            GettextSortOutput::Msgid => Some("--sort-output"),
+        }
+    }
 }
 
 #[derive(Deserialize, Debug, Clone, Default)]
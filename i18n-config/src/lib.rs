@@ -4,13 +4,20 @@
 
 mod fluent;
 mod gettext;
+mod translate;
+mod workspace;
 
-pub use fluent::FluentConfig;
-pub use gettext::GettextConfig;
+pub use fluent::{FluentConfig, FluentLints, LintLevel};
+pub use gettext::{
+    GettextConfig, GettextPotHeader, GettextSortOutput, GettextTools, ObsoletePolicy,
+};
+pub use translate::TranslateConfig;
+pub use workspace::{Workspace, WorkspaceMember};
 
 use std::fs::read_to_string;
 use std::io;
 use std::{
+    collections::BTreeMap,
     fmt::Display,
     path::{Path, PathBuf},
 };
@@ -19,6 +26,7 @@ use log::{debug, error};
 use serde_derive::Deserialize;
 use thiserror::Error;
 use unic_langid::LanguageIdentifier;
+use walkdir::WalkDir;
 
 /// An error type explaining why a crate failed to verify.
 #[derive(Debug, Error)]
@@ -54,18 +62,102 @@ pub enum I18nConfigError {
     NoParentI18nConfig(String, String),
     #[error("Cannot read `CARGO_MANIFEST_DIR` environment variable.")]
     CannotReadCargoManifestDir,
+    #[error("Cannot run `cargo metadata` for {0:?} because {1}.")]
+    CannotRunCargoMetadata(PathBuf, #[source] io::Error),
+    #[error("`cargo metadata` for {0:?} was unsuccessful: {1}")]
+    CargoMetadataFailed(PathBuf, String),
+    #[error("Cannot parse the output of `cargo metadata` for {0:?} because {1}.")]
+    CannotParseCargoMetadata(PathBuf, #[source] serde_json::Error),
+    #[error("{0} inherits the workspace i18n config, but there is no `[workspace.metadata.i18n]` in the workspace at {1:?}.")]
+    NoWorkspaceI18nConfig(String, PathBuf),
+    #[error("Cannot deserialize the config for the localization system \"{0}\" because {1}.")]
+    CannotDeserializeSystemConfig(String, #[source] serde_json::Error),
+    #[error("Cannot deserialize the `[package.metadata.i18n]` config in {0:?} because {1}.")]
+    CannotDeserializeCargoTomlConfig(PathBuf, #[source] serde_json::Error),
+    #[error("There is no `[package.metadata.i18n]` config in {0:?}.")]
+    NoCargoTomlConfig(PathBuf),
 }
 
 #[derive(Deserialize)]
 struct RawCrate {
-    #[serde(alias = "workspace")]
-    package: RawPackage,
+    package: Option<RawPackage>,
+    workspace: Option<RawWorkspace>,
+    /// The `[lib]` target.
+    lib: Option<RawTarget>,
+    /// The `[[bin]]` targets.
+    #[serde(default)]
+    bin: Vec<RawTarget>,
+}
+
+#[derive(Deserialize)]
+struct RawTarget {
+    /// The path to the root source file of the target.
+    path: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct RawWorkspace {
+    #[serde(default)]
+    members: Vec<String>,
 }
 
 #[derive(Deserialize)]
 struct RawPackage {
     name: String,
     version: String,
+    metadata: Option<RawPackageMetadata>,
+}
+
+#[derive(Deserialize)]
+struct RawPackageMetadata {
+    /// The `[package.metadata.i18n]` table, which is either the i18n
+    /// config of the crate, or `{ workspace = true }` if it inherits
+    /// the workspace's i18n config.
+    i18n: Option<serde_json::Value>,
+}
+
+impl RawPackage {
+    /// The i18n config in the `[package.metadata.i18n]` table of the
+    /// `Cargo.toml` at `cargo_path`, if there is one.
+    fn i18n_config(self, cargo_path: &Path) -> Result<Option<I18nConfig>, I18nConfigError> {
+        let table = match self.metadata.and_then(|metadata| metadata.i18n) {
+            Some(table) => table,
+            None => return Ok(None),
+        };
+        if table.get("workspace").and_then(serde_json::Value::as_bool) == Some(true) {
+            return Ok(None);
+        }
+        serde_json::from_value(table).map(Some).map_err(|err| {
+            I18nConfigError::CannotDeserializeCargoTomlConfig(cargo_path.into(), err)
+        })
+    }
+}
+
+/// The directories containing the source code of the crate at
+/// `crate_path` with the `target_paths` (relative to the crate), see
+/// [Crate::source_dirs]. Directories within another source directory
+/// are not included.
+fn source_dirs<'a>(
+    crate_path: &Path,
+    target_paths: impl Iterator<Item = &'a Path>,
+) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::iter::once(PathBuf::from("src"))
+        .chain(target_paths.map(|path| path.parent().unwrap_or(path).to_path_buf()))
+        .filter(|dir| crate_path.join(dir).is_dir())
+        .collect();
+    dirs.sort();
+    dirs.dedup();
+    // Sorted directories come after the directories containing them.
+    let mut source_dirs: Vec<PathBuf> = Vec::new();
+    for dir in dirs {
+        if !source_dirs
+            .iter()
+            .any(|source_dir| dir.starts_with(source_dir))
+        {
+            source_dirs.push(dir);
+        }
+    }
+    source_dirs
 }
 
 /// Represents a rust crate.
@@ -82,8 +174,18 @@ pub struct Crate<'a> {
     pub parent: Option<&'a Crate<'a>>,
     /// The file path expected to be used for `i18n_config` relative to this crate's root.
     pub config_file_path: PathBuf,
-    /// The localization config for this crate (if it exists).
+    /// The localization config for this crate (if it exists), read
+    /// from the `config_file_path`, or otherwise from the
+    /// `[package.metadata.i18n]` table of its `Cargo.toml`.
     pub i18n_config: Option<I18nConfig>,
+    /// The directories (relative to the crate) containing the source
+    /// code of the crate's library and binary targets: the
+    /// directories of the target paths specified in its `Cargo.toml`
+    /// (e.g. `lib` for `[lib] path = "lib/lib.rs"`), and `src` where
+    /// the targets are found by default. Only the directories which
+    /// exist are included, so this is empty for crates without any
+    /// source code.
+    pub source_dirs: Vec<PathBuf>,
 }
 
 impl<'a> Crate<'a> {
@@ -114,20 +216,50 @@ impl<'a> Crate<'a> {
         let cargo_toml: RawCrate = basic_toml::from_str(&toml_str)
             .map_err(|err| I18nConfigError::CannotDeserializeToml(cargo_path.clone(), err))?;
 
+        let package = match (cargo_toml.package, cargo_toml.workspace) {
+            (Some(package), _) => package,
+            (None, Some(_)) => {
+                return Err(I18nConfigError::NotACrate(
+                    path_into,
+                    WhyNotCrate::Workspace,
+                ))
+            }
+            (None, None) => {
+                return Err(I18nConfigError::CannotParseCargoToml(
+                    cargo_path,
+                    "there is no [package] section".to_string(),
+                ))
+            }
+        };
+
+        let name = package.name.clone();
+        let version = package.version.clone();
+        let source_dirs = source_dirs(
+            &path_into,
+            cargo_toml
+                .lib
+                .iter()
+                .chain(&cargo_toml.bin)
+                .filter_map(|target| target.path.as_deref()),
+        );
+
+        // The i18n config file takes precedence over the config in the
+        // `[package.metadata.i18n]` table of the `Cargo.toml`.
         let full_config_file_path = path_into.join(&config_file_path_into);
         let i18n_config = if full_config_file_path.exists() {
             Some(I18nConfig::from_file(&full_config_file_path)?)
         } else {
-            None
+            package.i18n_config(&cargo_path)?
         };
 
         Ok(Crate {
-            name: cargo_toml.package.name,
-            version: cargo_toml.package.version,
+            name,
+            version,
             path: path_into,
             parent,
             config_file_path: config_file_path_into,
             i18n_config,
+            source_dirs,
         })
     }
 
@@ -142,7 +274,7 @@ impl<'a> Crate<'a> {
     /// otherwise return None.
     pub fn parent_active_config(
         &'a self,
-    ) -> Result<Option<(&'a Crate, &'a I18nConfig)>, I18nConfigError> {
+    ) -> Result<Option<(&'a Crate<'a>, &'a I18nConfig)>, I18nConfigError> {
         match self.parent {
             Some(parent) => parent.active_config(),
             None => Ok(None),
@@ -152,7 +284,9 @@ impl<'a> Crate<'a> {
     /// Identify the config which should be used for this crate, and
     /// the crate (either this crate or one of it's parents)
     /// associated with that config.
-    pub fn active_config(&'a self) -> Result<Option<(&'a Crate, &'a I18nConfig)>, I18nConfigError> {
+    pub fn active_config(
+        &'a self,
+    ) -> Result<Option<(&'a Crate<'a>, &'a I18nConfig)>, I18nConfigError> {
         debug!("Resolving active config for {0}", self);
         match &self.i18n_config {
             Some(config) => {
@@ -209,6 +343,18 @@ impl<'a> Crate<'a> {
         }
     }
 
+    /// Get the [TranslateConfig](TranslateConfig) in this crate, or
+    /// return an error if there is none present.
+    pub fn translate_config_or_err(&self) -> Result<&TranslateConfig, I18nConfigError> {
+        match &self.config_or_err()?.translate {
+            Some(translate_config) => Ok(translate_config),
+            None => Err(I18nConfigError::OptionMissingInI18nConfig(
+                "translate section".to_string(),
+                self.config_file_path.clone(),
+            )),
+        }
+    }
+
     /// If this crate has a parent, check whether the parent wants to
     /// collate subcrates string extraction, as per the parent's
     /// [GettextConfig#collate_extracted_subcrates](GettextConfig#collate_extracted_subcrates).
@@ -235,16 +381,107 @@ impl<'a> Crate<'a> {
         parent_extract_to_subcrate && extract_to_parent
     }
 
+    /// The paths (relative to this crate) of the subcrates to perform
+    /// localization within. These are the `subcrates` listed in this
+    /// crate's i18n config, followed by the discovered subcrates if
+    /// [I18nConfig::auto_discover_subcrates] is enabled.
+    ///
+    /// Subcrates are discovered by searching this crate's directory
+    /// for nested crates which have an i18n config file, skipping
+    /// hidden directories, `target` directories and the contents of
+    /// the nested crates themselves.
+    pub fn subcrate_paths(&self) -> Vec<PathBuf> {
+        let config = match &self.i18n_config {
+            Some(config) => config,
+            None => return Vec::new(),
+        };
+
+        let mut subcrate_paths = config.subcrates.clone();
+
+        if config.auto_discover_subcrates {
+            let listed_paths: Vec<PathBuf> = subcrate_paths
+                .iter()
+                .filter_map(|path| self.path.join(path).canonicalize().ok())
+                .collect();
+
+            for discovered_path in self.discover_subcrates() {
+                let is_listed = self
+                    .path
+                    .join(&discovered_path)
+                    .canonicalize()
+                    .map(|path| listed_paths.contains(&path))
+                    .unwrap_or(false);
+
+                if !is_listed {
+                    debug!("Discovered subcrate {0:?} of {1}.", discovered_path, self);
+                    subcrate_paths.push(discovered_path);
+                }
+            }
+        }
+
+        subcrate_paths
+    }
+
+    /// Search this crate's directory for nested crates which have an
+    /// i18n config file, returning their paths relative to this crate.
+    fn discover_subcrates(&self) -> Vec<PathBuf> {
+        let mut subcrate_paths = Vec::new();
+        let mut walker = WalkDir::new(&self.path)
+            .min_depth(1)
+            .sort_by_file_name()
+            .into_iter();
+
+        while let Some(result) = walker.next() {
+            let entry = match result {
+                Ok(entry) => entry,
+                Err(err) => {
+                    error!(
+                        "Error while searching for the subcrates of {0}: {1}",
+                        self, err
+                    );
+                    continue;
+                }
+            };
+
+            if !entry.file_type().is_dir() {
+                continue;
+            }
+
+            let file_name = entry.file_name().to_string_lossy();
+            if file_name.starts_with('.') || file_name == "target" {
+                walker.skip_current_dir();
+                continue;
+            }
+
+            let path = entry.path();
+            if path.join("Cargo.toml").exists() {
+                if path.join(&self.config_file_path).exists() || has_cargo_toml_config(path) {
+                    if let Ok(relative_path) = path.strip_prefix(&self.path) {
+                        subcrate_paths.push(relative_path.to_path_buf());
+                    }
+                }
+                // Nested crates are responsible for their own subcrates.
+                walker.skip_current_dir();
+            }
+        }
+
+        subcrate_paths
+    }
+
     /// Attempt to resolve the parents of this crate which have this
     /// crate listed as a subcrate in their i18n config.
     pub fn find_parent(&self) -> Option<Crate<'a>> {
-        let parent_crt = match self
-            .path
-            .canonicalize()
-            .map(|op| op.parent().map(|p| p.to_path_buf()))
-            .ok()
-            .unwrap_or(None)
-        {
+        // The nearest ancestor directory containing a crate, because
+        // subcrates may be nested within other directories of their
+        // parent crate.
+        let parent_path = self.path.canonicalize().ok().and_then(|path| {
+            path.ancestors()
+                .skip(1)
+                .find(|ancestor| ancestor.join("Cargo.toml").exists())
+                .map(Path::to_path_buf)
+        });
+
+        let parent_crt = match parent_path {
             Some(parent_path) => match Crate::from(parent_path, None, "i18n.toml") {
                 Ok(parent_crate) => {
                     debug!("Found parent ({0}) of {1}.", parent_crate, self);
@@ -274,9 +511,9 @@ impl<'a> Crate<'a> {
 
         match parent_crt {
             Some(crt) => match &crt.i18n_config {
-                Some(config) => {
-                    let this_is_subcrate = config
-                        .subcrates
+                Some(_) => {
+                    let subcrate_paths = crt.subcrate_paths();
+                    let this_is_subcrate = subcrate_paths
                         .iter()
                         .any(|subcrate_path| {
                             let subcrate_path_canon = match crt.path.join(subcrate_path).canonicalize() {
@@ -301,7 +538,7 @@ impl<'a> Crate<'a> {
                     if this_is_subcrate {
                         Some(crt)
                     } else {
-                        debug!("Parent {0} does not have {1} correctly listed as one of its subcrates (curently: {2:?}) in its i18n config.", crt, self, config.subcrates);
+                        debug!("Parent {0} does not have {1} correctly listed as one of its subcrates (curently: {2:?}) in its i18n config.", crt, self, subcrate_paths);
                         None
                     }
                 }
@@ -342,18 +579,71 @@ pub struct I18nConfig {
     /// subcrate needs to have its own `i18n.toml`.
     #[serde(default)]
     pub subcrates: Vec<PathBuf>,
+    /// Automatically discover subcrates to perform localization
+    /// within, in addition to the `subcrates`. These are the crates
+    /// nested within this crate's directory which have their own
+    /// `i18n.toml`, see [Crate::subcrate_paths()]. By default this is
+    /// false.
+    #[serde(default)]
+    pub auto_discover_subcrates: bool,
     /// The subcomponent of this config relating to gettext, only
     /// present if the gettext localization system will be used.
     pub gettext: Option<GettextConfig>,
     /// The subcomponent of this config relating to gettext, only
     /// present if the fluent localization system will be used.
     pub fluent: Option<FluentConfig>,
+    /// The subcomponent of this config relating to machine
+    /// translation, only present if the `cargo i18n translate`
+    /// command will be used.
+    pub translate: Option<TranslateConfig>,
+    /// Aliases for languages, keyed by the alias, used by the
+    /// language loaders to serve requests for the alias using the
+    /// files of the language it refers to (e.g. `no = "nb"`), for
+    /// assets which use different language identifiers. By default
+    /// there are none.
+    #[serde(default)]
+    pub language_aliases: BTreeMap<LanguageIdentifier, LanguageIdentifier>,
+    /// The path to the glossary file (relative to the crate), with
+    /// the approved and disallowed translations of terms which are
+    /// checked by `cargo i18n lint-glossary`. By default there is
+    /// none.
+    pub glossary: Option<PathBuf>,
+    /// The subcomponents of this config relating to other
+    /// localization systems (such as those provided by third-party
+    /// crates), keyed by the name of their table (e.g. `[qt]`).
+    #[serde(flatten)]
+    pub systems: BTreeMap<String, serde_json::Value>,
 }
 
 impl I18nConfig {
-    /// Load the config from the specified toml file path.
+    /// Deserialize the subcomponent of this config relating to the
+    /// localization system with the `name`, see
+    /// [I18nConfig::systems]. Returns `None` if there is no table
+    /// for the system.
+    pub fn system_config<T: serde::de::DeserializeOwned>(
+        &self,
+        name: &str,
+    ) -> Result<Option<T>, I18nConfigError> {
+        self.systems
+            .get(name)
+            .map(|value| {
+                serde_json::from_value(value.clone()).map_err(|err| {
+                    I18nConfigError::CannotDeserializeSystemConfig(name.to_string(), err)
+                })
+            })
+            .transpose()
+    }
+
+    /// Load the config from the specified toml file path. If the file
+    /// is a `Cargo.toml`, the config is read from its
+    /// `[package.metadata.i18n]` table (see
+    /// [I18nConfig::from_cargo_toml()]).
     pub fn from_file<P: AsRef<Path>>(toml_path: P) -> Result<I18nConfig, I18nConfigError> {
         let toml_path_final: &Path = toml_path.as_ref();
+        if toml_path_final.file_name() == Some("Cargo.toml".as_ref()) {
+            return Self::from_cargo_toml(toml_path_final)?
+                .ok_or_else(|| I18nConfigError::NoCargoTomlConfig(toml_path_final.to_path_buf()));
+        }
         let toml_str = read_to_string(toml_path_final).map_err(|err| {
             I18nConfigError::CannotReadFile(
                 toml_path_final.to_path_buf(),
@@ -367,6 +657,59 @@ impl I18nConfig {
 
         Ok(config)
     }
+
+    /// Load the config from the `[package.metadata.i18n]` table of the
+    /// `Cargo.toml` at `cargo_path`, which takes the same form as an
+    /// `i18n.toml` file, so that crates don't need a separate config
+    /// file. Returns `None` if there is no such table, or if it is
+    /// `{ workspace = true }` (inheriting the workspace's config, see
+    /// [Workspace]).
+    ///
+    /// ```toml
+    /// [package.metadata.i18n]
+    /// fallback_language = "en"
+    ///
+    /// [package.metadata.i18n.fluent]
+    /// assets_dir = "i18n"
+    /// ```
+    pub fn from_cargo_toml<P: AsRef<Path>>(
+        cargo_path: P,
+    ) -> Result<Option<I18nConfig>, I18nConfigError> {
+        let cargo_path = cargo_path.as_ref();
+        let toml_str = read_to_string(cargo_path).map_err(|err| {
+            I18nConfigError::CannotReadFile(cargo_path.to_path_buf(), std::env::current_dir(), err)
+        })?;
+        let cargo_toml: RawCrate = basic_toml::from_str(&toml_str)
+            .map_err(|err| I18nConfigError::CannotDeserializeToml(cargo_path.to_path_buf(), err))?;
+        match cargo_toml.package {
+            Some(package) => package.i18n_config(cargo_path),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Whether the crate in the directory `crate_dir` has an i18n config
+/// in the `[package.metadata.i18n]` table of its `Cargo.toml`.
+fn has_cargo_toml_config(crate_dir: &Path) -> bool {
+    matches!(
+        I18nConfig::from_cargo_toml(crate_dir.join("Cargo.toml")),
+        Ok(Some(_))
+    )
+}
+
+/// The path to the file containing the i18n config of the crate in
+/// the directory `crate_dir`: its `i18n.toml`, otherwise its
+/// `Cargo.toml` if the config is in its `[package.metadata.i18n]`
+/// table, or `None` if it has neither.
+fn i18n_config_file(crate_dir: &Path) -> Option<PathBuf> {
+    let i18n_config_file = crate_dir.join("i18n.toml");
+    if i18n_config_file.exists() {
+        Some(i18n_config_file)
+    } else if has_cargo_toml_config(crate_dir) {
+        Some(crate_dir.join("Cargo.toml"))
+    } else {
+        None
+    }
 }
 
 /// Important i18n-config paths related to the current crate.
@@ -374,23 +717,210 @@ pub struct CratePaths {
     /// The current crate directory path (where the `Cargo.toml` is
     /// located).
     pub crate_dir: PathBuf,
-    /// The current i18n config file path
+    /// The current i18n config file path, which is a `Cargo.toml` if
+    /// the config is in its `[package.metadata.i18n]` table (see
+    /// [I18nConfig::from_file()]).
     pub i18n_config_file: PathBuf,
+    /// The directory of the crate which the `i18n_config_file`
+    /// belongs to, which paths in the config are relative to. This is
+    /// the `crate_dir` unless the config is inherited from a parent
+    /// crate.
+    pub i18n_config_crate_dir: PathBuf,
+    /// The package name of the parent crate which the i18n config is
+    /// inherited from, used as the default domain instead of the
+    /// current crate's name. `None` if the config is not inherited,
+    /// or if it is inherited from a virtual workspace.
+    pub i18n_config_crate_name: Option<String>,
+}
+
+/// The environment variable which makes the procedural macros skip
+/// their compile time checks, see [skip_compile_time_checks()].
+pub const SKIP_CHECKS_ENV_VAR: &str = "I18N_EMBED_SKIP_CHECKS";
+
+/// Whether the procedural macros (such as `fl!()`) should skip their
+/// compile time checks, and generate unchecked code with a warning
+/// instead of reading the `i18n.toml` config file and the
+/// localization files. This is the case when the
+/// [SKIP_CHECKS_ENV_VAR] environment variable is set to anything
+/// other than `0` or `false`, e.g. `I18N_EMBED_SKIP_CHECKS=1`, for
+/// builds where the files are not available, such as `trybuild`
+/// tests, in which `CARGO_MANIFEST_DIR` is a generated crate.
+///
+/// This is intended to be called by a procedural macro during crate
+/// compilation.
+///
+/// ```
+/// use i18n_config::{skip_compile_time_checks, SKIP_CHECKS_ENV_VAR};
+///
+/// std::env::set_var(SKIP_CHECKS_ENV_VAR, "1");
+/// assert!(skip_compile_time_checks());
+/// std::env::set_var(SKIP_CHECKS_ENV_VAR, "0");
+/// assert!(!skip_compile_time_checks());
+/// std::env::remove_var(SKIP_CHECKS_ENV_VAR);
+/// assert!(!skip_compile_time_checks());
+/// ```
+pub fn skip_compile_time_checks() -> bool {
+    std::env::var(SKIP_CHECKS_ENV_VAR)
+        .map(|value| !matches!(value.trim(), "" | "0" | "false"))
+        .unwrap_or(false)
+}
+
+/// Whether the crate is being documented on
+/// [docs.rs](https://docs.rs), where the procedural macros generate
+/// unchecked code with a warning instead of failing to compile when
+/// the `i18n.toml` config file or the localization files are missing
+/// (e.g. because they are excluded from the published package).
+///
+/// This is intended to be called by a procedural macro during crate
+/// compilation.
+pub fn is_docs_rs() -> bool {
+    std::env::var_os("DOCS_RS").is_some()
 }
 
 /// Locate the current crate's directory and `i18n.toml` config file.
 /// This is intended to be called by a procedural macro during crate
 /// compilation.
+///
+/// If the current crate has no `i18n.toml`, but has its config in the
+/// `[package.metadata.i18n]` table of its `Cargo.toml` (see
+/// [I18nConfig::from_cargo_toml()]), the config file is its
+/// `Cargo.toml`. Otherwise the config is inherited from the nearest
+/// parent crate (or workspace) with an i18n config which covers the
+/// current crate, either by listing it in its `subcrates`, or by
+/// including it in its `[workspace] members`.
 pub fn locate_crate_paths() -> Result<CratePaths, I18nConfigError> {
     let crate_dir = Path::new(
         &std::env::var_os("CARGO_MANIFEST_DIR")
             .ok_or(I18nConfigError::CannotReadCargoManifestDir)?,
     )
     .to_path_buf();
-    let i18n_config_file = crate_dir.join("i18n.toml");
+
+    let i18n_config_file = match i18n_config_file(&crate_dir) {
+        Some(i18n_config_file) => i18n_config_file,
+        None => {
+            if let Some(crate_paths) = locate_inherited_crate_paths(&crate_dir) {
+                return Ok(crate_paths);
+            }
+            crate_dir.join("i18n.toml")
+        }
+    };
 
     Ok(CratePaths {
+        i18n_config_crate_dir: crate_dir.clone(),
         crate_dir,
         i18n_config_file,
+        i18n_config_crate_name: None,
     })
 }
+
+/// Search the ancestors of the crate at `crate_dir` for the nearest
+/// crate with an `i18n.toml` which covers this crate.
+fn locate_inherited_crate_paths(crate_dir: &Path) -> Option<CratePaths> {
+    let crate_dir_canon = crate_dir.canonicalize().ok()?;
+
+    for ancestor in crate_dir_canon.ancestors().skip(1) {
+        let cargo_path = ancestor.join("Cargo.toml");
+        if !cargo_path.exists() {
+            continue;
+        }
+        let i18n_config_file = match i18n_config_file(ancestor) {
+            Some(i18n_config_file) => i18n_config_file,
+            None => continue,
+        };
+
+        let cargo_toml: RawCrate = match read_to_string(&cargo_path)
+            .ok()
+            .and_then(|toml_str| basic_toml::from_str(&toml_str).ok())
+        {
+            Some(cargo_toml) => cargo_toml,
+            None => {
+                debug!(
+                    "Unable to read {0:?} while searching for a parent i18n config.",
+                    cargo_path
+                );
+                continue;
+            }
+        };
+
+        let config = match I18nConfig::from_file(&i18n_config_file) {
+            Ok(config) => config,
+            Err(err) => {
+                error!("Error while searching for a parent i18n config: {0}", err);
+                continue;
+            }
+        };
+
+        let relative_path = match crate_dir_canon.strip_prefix(ancestor) {
+            Ok(relative_path) => relative_path,
+            Err(_) => continue,
+        };
+
+        let is_subcrate = config.subcrates.iter().any(|subcrate_path| {
+            ancestor
+                .join(subcrate_path)
+                .canonicalize()
+                .map(|subcrate_path| subcrate_path == crate_dir_canon)
+                .unwrap_or(false)
+        });
+
+        let is_workspace_member = cargo_toml
+            .workspace
+            .map(|workspace| {
+                workspace
+                    .members
+                    .iter()
+                    .any(|pattern| member_pattern_matches(pattern, relative_path))
+            })
+            .unwrap_or(false);
+
+        if is_subcrate || is_workspace_member {
+            debug!(
+                "Crate at {0:?} inherits the i18n config {1:?}.",
+                crate_dir, i18n_config_file
+            );
+            return Some(CratePaths {
+                crate_dir: crate_dir.to_path_buf(),
+                i18n_config_file,
+                i18n_config_crate_dir: ancestor.to_path_buf(),
+                i18n_config_crate_name: cargo_toml.package.map(|package| package.name),
+            });
+        }
+    }
+
+    None
+}
+
+/// Whether the `path` (relative to the workspace root) matches the
+/// workspace `members` entry `pattern`, which may contain `*`
+/// wildcards within its path components (e.g. `crates/*`).
+fn member_pattern_matches(pattern: &str, path: &Path) -> bool {
+    let pattern_components: Vec<&str> = pattern
+        .split('/')
+        .filter(|component| !component.is_empty() && *component != ".")
+        .collect();
+    let path_components: Vec<String> = path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+        .collect();
+
+    pattern_components.len() == path_components.len()
+        && pattern_components
+            .iter()
+            .zip(&path_components)
+            .all(|(pattern, component)| wildcard_matches(pattern, component))
+}
+
+/// Whether `text` matches `pattern`, where `*` in the `pattern`
+/// matches any sequence of characters.
+pub(crate) fn wildcard_matches(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => {
+            text.starts_with(prefix)
+                && (0..=text.len() - prefix.len()).any(|i| {
+                    let remaining = &text[prefix.len() + i..];
+                    text.is_char_boundary(prefix.len() + i) && wildcard_matches(rest, remaining)
+                })
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use serde::Deserialize;
+
+/// The data structure representing what is stored (and possible to
+/// store) within the `translate` subsection of a `i18n.toml` file,
+/// which configures the machine translation service used by the
+/// `cargo i18n translate` command.
+#[derive(Deserialize, Debug, Clone)]
+pub struct TranslateConfig {
+    /// (Required) The URL of the translation endpoint of a
+    /// [LibreTranslate](https://libretranslate.com/) compatible
+    /// service, e.g. `https://libretranslate.com/translate`.
+    pub endpoint: String,
+    /// (Optional) The name of the environment variable containing the
+    /// API key for the service. The key itself should not be stored in
+    /// the config file.
+    pub api_key_env: Option<String>,
+}
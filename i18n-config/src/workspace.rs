@@ -0,0 +1,201 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use log::debug;
+use serde::Deserialize;
+
+use crate::{Crate, I18nConfig, I18nConfigError};
+
+/// The subset of the output of `cargo metadata --format-version 1`
+/// required to discover the members of a workspace.
+#[derive(Deserialize)]
+struct RawMetadata {
+    packages: Vec<RawMetadataPackage>,
+    workspace_members: Vec<String>,
+    workspace_root: PathBuf,
+    /// The `[workspace.metadata]` table.
+    #[serde(default)]
+    metadata: Option<RawWorkspaceMetadata>,
+}
+
+#[derive(Deserialize)]
+struct RawMetadataPackage {
+    id: String,
+    name: String,
+    manifest_path: PathBuf,
+    /// The `[package.metadata]` table.
+    #[serde(default)]
+    metadata: Option<RawPackageMetadata>,
+}
+
+#[derive(Deserialize)]
+struct RawWorkspaceMetadata {
+    i18n: Option<I18nConfig>,
+}
+
+#[derive(Deserialize)]
+struct RawPackageMetadata {
+    i18n: Option<RawPackageI18nMetadata>,
+}
+
+#[derive(Deserialize)]
+struct RawPackageI18nMetadata {
+    #[serde(default)]
+    workspace: bool,
+}
+
+/// A member of a [Workspace].
+#[derive(Debug, Clone)]
+pub struct WorkspaceMember {
+    /// The name of the member crate.
+    pub name: String,
+    /// The path to the member crate's directory (containing its
+    /// `Cargo.toml`).
+    pub path: PathBuf,
+    /// Whether the member inherits the workspace's i18n config,
+    /// specified using `i18n = { workspace = true }` in its
+    /// `[package.metadata]` table.
+    pub inherits_workspace_config: bool,
+}
+
+/// A cargo workspace, discovered using `cargo metadata`.
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    /// The path to the root directory of the workspace.
+    pub root: PathBuf,
+    /// The members of the workspace.
+    pub members: Vec<WorkspaceMember>,
+    /// The shared i18n config specified in the
+    /// `[workspace.metadata.i18n]` table of the workspace's
+    /// `Cargo.toml` (if there is one), which takes the same form as an
+    /// `i18n.toml` file.
+    pub i18n_config: Option<I18nConfig>,
+}
+
+impl Workspace {
+    /// Discover the workspace containing the crate or workspace root
+    /// at `path` by running `cargo metadata`.
+    pub fn from_cargo_metadata<P: AsRef<Path>>(path: P) -> Result<Workspace, I18nConfigError> {
+        let manifest_path = path.as_ref().join("Cargo.toml");
+        let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+
+        let mut command = Command::new(cargo);
+        command.args([
+            "metadata",
+            "--no-deps",
+            "--format-version",
+            "1",
+            "--manifest-path",
+        ]);
+        command.arg(&manifest_path);
+        debug!("Running command: {0:?}", command);
+
+        let output = command
+            .output()
+            .map_err(|err| I18nConfigError::CannotRunCargoMetadata(manifest_path.clone(), err))?;
+
+        if !output.status.success() {
+            return Err(I18nConfigError::CargoMetadataFailed(
+                manifest_path,
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        Self::from_metadata_json(&manifest_path, &output.stdout)
+    }
+
+    /// Parse the workspace from the json output of `cargo metadata
+    /// --format-version 1` for the manifest at `manifest_path`.
+    pub fn from_metadata_json<P: AsRef<Path>>(
+        manifest_path: P,
+        json: &[u8],
+    ) -> Result<Workspace, I18nConfigError> {
+        let metadata: RawMetadata = serde_json::from_slice(json).map_err(|err| {
+            I18nConfigError::CannotParseCargoMetadata(manifest_path.as_ref().to_path_buf(), err)
+        })?;
+
+        let members = metadata
+            .workspace_members
+            .iter()
+            .filter_map(|member_id| {
+                metadata
+                    .packages
+                    .iter()
+                    .find(|package| &package.id == member_id)
+            })
+            .map(|package| WorkspaceMember {
+                name: package.name.clone(),
+                path: package
+                    .manifest_path
+                    .parent()
+                    .map(Path::to_path_buf)
+                    .unwrap_or_default(),
+                inherits_workspace_config: package
+                    .metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.i18n.as_ref())
+                    .map(|i18n| i18n.workspace)
+                    .unwrap_or(false),
+            })
+            .collect();
+
+        Ok(Workspace {
+            root: metadata.workspace_root,
+            members,
+            i18n_config: metadata.metadata.and_then(|metadata| metadata.i18n),
+        })
+    }
+
+    /// The crates for the members of this workspace which are to be
+    /// localized. These are the members which have their own i18n
+    /// config at `config_file_path` (relative to the member's
+    /// directory), and the members which inherit the workspace's i18n
+    /// config. Members listed as a subcrate of another crate are
+    /// excluded, because they are localized along with their parent.
+    ///
+    /// For members inheriting the workspace's i18n config, the paths
+    /// in the config are relative to the member's directory, as if
+    /// the config had been copied into the member's `i18n.toml`.
+    pub fn member_crates<P: AsRef<Path>>(
+        &self,
+        config_file_path: P,
+    ) -> Result<Vec<Crate<'static>>, I18nConfigError> {
+        let config_file_path = config_file_path.as_ref();
+        let mut crates = Vec::new();
+
+        for member in &self.members {
+            let mut crt = Crate::from(member.path.clone(), None, config_file_path)?;
+
+            if crt.i18n_config.is_none() {
+                match (&self.i18n_config, member.inherits_workspace_config) {
+                    (Some(workspace_config), true) => {
+                        debug!("{0} inherits the workspace i18n config.", crt);
+                        crt.i18n_config = Some(workspace_config.clone());
+                    }
+                    (None, true) => {
+                        return Err(I18nConfigError::NoWorkspaceI18nConfig(
+                            crt.to_string(),
+                            self.root.clone(),
+                        ));
+                    }
+                    (_, false) => {
+                        debug!("Skipping {0} because it has no i18n config.", crt);
+                        continue;
+                    }
+                }
+            }
+
+            if crt.find_parent().is_some() {
+                debug!(
+                    "Skipping {0} because it is localized as a subcrate of its parent.",
+                    crt
+                );
+                continue;
+            }
+
+            crates.push(crt);
+        }
+
+        Ok(crates)
+    }
+}
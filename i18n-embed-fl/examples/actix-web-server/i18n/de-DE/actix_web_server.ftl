@@ -0,0 +1,6 @@
+app-title = Mehrsprachige API
+hello-name = Hallo { $name }!
+unread-messages = { $count ->
+    [one] Sie haben eine ungelesene Nachricht.
+   *[other] Sie haben { $count } ungelesene Nachrichten.
+}
@@ -0,0 +1,131 @@
+//! A multilingual REST API using `actix-web`, which localizes each
+//! response for the languages requested in its `Accept-Language`
+//! header, or its `lang` query parameter or cookie, using the
+//! `i18n_embed::actix_web::RequestLoader` extractor.
+//!
+//! + `GET /api/hello?name=Ferris`: a greeting.
+//! + `GET /api/unread?count=3`: the number of unread messages.
+//! + `GET /api/translations`: all of the messages, for a JavaScript
+//!   frontend to display.
+//! + `GET /api/languages`: the available languages.
+
+use std::collections::BTreeMap;
+
+use actix_web::{get, web, HttpResponse, Responder};
+use i18n_embed::{
+    actix_web::{LocalizationConfig, RequestLoader},
+    fluent::{fluent_language_loader, FluentLanguageLoader},
+    LanguageLoader,
+};
+use i18n_embed_fl::fl;
+use rust_embed::RustEmbed;
+use serde::{Deserialize, Serialize};
+
+#[derive(RustEmbed)]
+#[folder = "i18n/"]
+struct Localizations;
+
+/// Create the loader shared by all of the requests, with all of the
+/// available languages loaded.
+pub fn language_loader() -> FluentLanguageLoader {
+    let loader: FluentLanguageLoader = fluent_language_loader!();
+    loader.load_available_languages(&Localizations).unwrap();
+    // The messages are served as JSON rather than displayed directly,
+    // so the isolation marks around placeables are not needed.
+    loader.set_use_isolating(false);
+    loader
+}
+
+/// Create the configuration of the `RequestLoader` extractor for the
+/// shared `loader`. The `Accept-Language` header can be overridden
+/// using the `lang` query parameter or cookie. The loader falls back
+/// to its fallback language if none of the requested languages are
+/// available.
+pub fn localization_config(loader: FluentLanguageLoader) -> LocalizationConfig {
+    LocalizationConfig::new(loader)
+        .with_query_parameter("lang")
+        .with_cookie("lang")
+}
+
+/// Register the API's routes, using the shared `localization_config`.
+pub fn configure(
+    localization_config: web::Data<LocalizationConfig>,
+) -> impl FnOnce(&mut web::ServiceConfig) {
+    move |config| {
+        config
+            .app_data(localization_config)
+            .service(hello)
+            .service(unread)
+            .service(translations)
+            .service(languages);
+    }
+}
+
+/// A localized message, along with the language it was localized for.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Message {
+    pub language: String,
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+struct HelloQuery {
+    name: String,
+}
+
+#[get("/api/hello")]
+async fn hello(loader: RequestLoader, query: web::Query<HelloQuery>) -> impl Responder {
+    web::Json(Message {
+        language: loader.current_language().to_string(),
+        message: fl!(loader, "hello-name", name = query.name.as_str()),
+    })
+}
+
+#[derive(Deserialize)]
+struct UnreadQuery {
+    count: u32,
+}
+
+#[get("/api/unread")]
+async fn unread(loader: RequestLoader, query: web::Query<UnreadQuery>) -> impl Responder {
+    web::Json(Message {
+        language: loader.current_language().to_string(),
+        message: fl!(loader, "unread-messages", count = query.count),
+    })
+}
+
+/// All of the messages, localized for the requested languages.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Translations {
+    pub language: String,
+    pub messages: BTreeMap<String, String>,
+}
+
+/// Export all of the messages for the requested languages, so that
+/// a JavaScript frontend can display them without a Fluent runtime.
+/// Each message is taken from the first of the negotiated languages
+/// which has it. Messages with arguments keep their placeables (e.g.
+/// `Hello {$name}!`), which the frontend substitutes itself.
+#[get("/api/translations")]
+async fn translations(loader: RequestLoader) -> impl Responder {
+    let mut fallback_chain = loader.current_languages();
+    fallback_chain.push(loader.fallback_language().clone());
+
+    let mut messages = BTreeMap::new();
+    for language in fallback_chain.iter().rev() {
+        messages.extend(loader.format_all(language, |_| None));
+    }
+    web::Json(Translations {
+        language: loader.current_language().to_string(),
+        messages,
+    })
+}
+
+#[get("/api/languages")]
+async fn languages(loader: RequestLoader) -> impl Responder {
+    let languages: Vec<String> = loader
+        .available_languages(&Localizations)
+        .map(|languages| languages.iter().map(ToString::to_string).collect())
+        .unwrap_or_default();
+    HttpResponse::Ok().json(languages)
+}
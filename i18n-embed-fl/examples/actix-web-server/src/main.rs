@@ -0,0 +1,14 @@
+use actix_web::{web, App, HttpServer};
+use actix_web_server::{configure, language_loader, localization_config};
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    let config = web::Data::new(localization_config(language_loader()));
+
+    println!("Listening on http://127.0.0.1:8080, try:");
+    println!("curl -H 'Accept-Language: de-DE' 'http://127.0.0.1:8080/api/hello?name=Ferris'");
+    HttpServer::new(move || App::new().configure(configure(config.clone())))
+        .bind(("127.0.0.1", 8080))?
+        .run()
+        .await
+}
@@ -0,0 +1,82 @@
+use actix_web::cookie::Cookie;
+use actix_web::{test, web, App};
+use actix_web_server::{configure, language_loader, localization_config, Message, Translations};
+
+async fn call<T: serde::de::DeserializeOwned>(request: test::TestRequest) -> T {
+    let app = test::init_service(App::new().configure(configure(web::Data::new(
+        localization_config(language_loader()),
+    ))))
+    .await;
+    test::call_and_read_body_json(&app, request.to_request()).await
+}
+
+async fn get<T: serde::de::DeserializeOwned>(uri: &str, accept_language: Option<&str>) -> T {
+    let mut request = test::TestRequest::get().uri(uri);
+    if let Some(accept_language) = accept_language {
+        request = request.insert_header(("Accept-Language", accept_language));
+    }
+    call(request).await
+}
+
+#[actix_web::test]
+async fn hello_negotiates_language() {
+    let response: Message = get("/api/hello?name=Ferris", Some("de-DE,de;q=0.9,en;q=0.8")).await;
+    assert_eq!("de-DE", response.language);
+    assert_eq!("Hallo Ferris!", response.message);
+
+    let response: Message = get("/api/hello?name=Ferris", Some("fr-CH, fr;q=0.9")).await;
+    assert_eq!("fr", response.language);
+    assert_eq!("Bonjour Ferris !", response.message);
+}
+
+#[actix_web::test]
+async fn hello_falls_back() {
+    let response: Message = get("/api/hello?name=Ferris", Some("ja-JP")).await;
+    assert_eq!("en-US", response.language);
+    assert_eq!("Hello Ferris!", response.message);
+
+    let response: Message = get("/api/hello?name=Ferris", None).await;
+    assert_eq!("Hello Ferris!", response.message);
+}
+
+#[actix_web::test]
+async fn hello_overrides() {
+    let response: Message = get("/api/hello?name=Ferris&lang=fr", Some("de-DE")).await;
+    assert_eq!("fr", response.language);
+
+    let request = test::TestRequest::get()
+        .uri("/api/hello?name=Ferris")
+        .insert_header(("Accept-Language", "fr"))
+        .cookie(Cookie::new("lang", "de-DE"));
+    let response: Message = call(request).await;
+    assert_eq!("de-DE", response.language);
+    assert_eq!("Hallo Ferris!", response.message);
+}
+
+#[actix_web::test]
+async fn unread_plurals() {
+    let response: Message = get("/api/unread?count=1", Some("de")).await;
+    assert_eq!("Sie haben eine ungelesene Nachricht.", response.message);
+    let response: Message = get("/api/unread?count=3", Some("de")).await;
+    assert_eq!("Sie haben 3 ungelesene Nachrichten.", response.message);
+
+    // Missing in `fr`, so it falls back to `en-US`.
+    let response: Message = get("/api/unread?count=3", Some("fr")).await;
+    assert_eq!("You have 3 unread messages.", response.message);
+}
+
+#[actix_web::test]
+async fn translations_export() {
+    let response: Translations = get("/api/translations", Some("fr")).await;
+    assert_eq!("fr", response.language);
+    assert_eq!("API multilingue", response.messages["app-title"]);
+    assert_eq!("Bonjour {$name} !", response.messages["hello-name"]);
+    assert!(response.messages["unread-messages"].contains("unread messages"));
+}
+
+#[actix_web::test]
+async fn languages() {
+    let mut languages: Vec<String> = get("/api/languages", None).await;
+    languages.sort();
+    assert_eq!(vec!["de-DE", "en-US", "fr"], languages);
+}
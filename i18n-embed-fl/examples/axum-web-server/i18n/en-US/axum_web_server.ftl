@@ -0,0 +1,6 @@
+app-title = Multilingual API
+hello-name = Hello { $name }!
+unread-messages = { $count ->
+    [one] You have one unread message.
+   *[other] You have { $count } unread messages.
+}
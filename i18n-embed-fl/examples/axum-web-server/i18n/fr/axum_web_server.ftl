@@ -0,0 +1,2 @@
+app-title = API multilingue
+hello-name = Bonjour { $name } !
@@ -0,0 +1,63 @@
+//! A multilingual web server using `axum`, which localizes each
+//! response for the languages requested in its `Accept-Language`
+//! header using the `i18n_embed::axum::LocalizationLayer`.
+//!
+//! + `GET /api/hello?name=Ferris`: a greeting.
+//! + `GET /api/unread?count=3`: the number of unread messages.
+
+use axum::{extract::Query, routing::get, Extension, Router};
+use i18n_embed::{
+    axum::{LocalizationLayer, RequestLoader},
+    fluent::{fluent_language_loader, FluentLanguageLoader},
+    LanguageLoader,
+};
+use i18n_embed_fl::fl;
+use rust_embed::RustEmbed;
+use serde::Deserialize;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/"]
+struct Localizations;
+
+/// Create the loader shared by all of the requests, with all of the
+/// available languages loaded.
+pub fn language_loader() -> FluentLanguageLoader {
+    let loader: FluentLanguageLoader = fluent_language_loader!();
+    loader.load_available_languages(&Localizations).unwrap();
+    // The responses are plain text, so the isolation marks around
+    // placeables are not needed.
+    loader.set_use_isolating(false);
+    loader
+}
+
+/// Create the server's routes, localized using the shared `loader`.
+pub fn router(loader: FluentLanguageLoader) -> Router {
+    Router::new()
+        .route("/api/hello", get(hello))
+        .route("/api/unread", get(unread))
+        .layer(LocalizationLayer::new(loader))
+}
+
+#[derive(Deserialize)]
+struct HelloQuery {
+    name: String,
+}
+
+async fn hello(
+    Extension(loader): Extension<RequestLoader>,
+    Query(query): Query<HelloQuery>,
+) -> String {
+    fl!(loader, "hello-name", name = query.name.as_str())
+}
+
+#[derive(Deserialize)]
+struct UnreadQuery {
+    count: u32,
+}
+
+async fn unread(
+    Extension(loader): Extension<RequestLoader>,
+    Query(query): Query<UnreadQuery>,
+) -> String {
+    fl!(loader, "unread-messages", count = query.count)
+}
@@ -0,0 +1,10 @@
+use axum_web_server::{language_loader, router};
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", 8080)).await?;
+
+    println!("Listening on http://127.0.0.1:8080, try:");
+    println!("curl -H 'Accept-Language: de-DE' 'http://127.0.0.1:8080/api/hello?name=Ferris'");
+    axum::serve(listener, router(language_loader())).await
+}
@@ -0,0 +1,51 @@
+use axum::{body::Body, http::Request};
+use axum_web_server::{language_loader, router};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+async fn get(uri: &str, accept_language: Option<&str>) -> String {
+    let mut request = Request::get(uri);
+    if let Some(accept_language) = accept_language {
+        request = request.header("Accept-Language", accept_language);
+    }
+    let response = router(language_loader())
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn hello_negotiates_language() {
+    assert_eq!(
+        "Hallo Ferris!",
+        get("/api/hello?name=Ferris", Some("de-DE,de;q=0.9,en;q=0.8")).await
+    );
+    assert_eq!(
+        "Bonjour Ferris !",
+        get("/api/hello?name=Ferris", Some("fr-CH, fr;q=0.9")).await
+    );
+}
+
+#[tokio::test]
+async fn hello_falls_back() {
+    assert_eq!(
+        "Hello Ferris!",
+        get("/api/hello?name=Ferris", Some("ja-JP")).await
+    );
+    assert_eq!("Hello Ferris!", get("/api/hello?name=Ferris", None).await);
+}
+
+#[tokio::test]
+async fn unread_plurals() {
+    assert_eq!(
+        "Sie haben 3 ungelesene Nachrichten.",
+        get("/api/unread?count=3", Some("de")).await
+    );
+    // Missing in `fr`, so it falls back to `en-US`.
+    assert_eq!(
+        "You have 3 unread messages.",
+        get("/api/unread?count=3", Some("fr")).await
+    );
+}
@@ -0,0 +1,8 @@
+app-title = Mehrsprachiges egui
+language = Sprache
+hello-name = Hallo { $name }!
+clicked = { $count ->
+    [one] Sie haben einmal auf die Schaltfläche geklickt.
+   *[other] Sie haben { $count } Mal auf die Schaltfläche geklickt.
+}
+click-me = Klick mich
@@ -0,0 +1,8 @@
+app-title = Multilingual egui
+language = Language
+hello-name = Hello { $name }!
+clicked = { $count ->
+    [one] You clicked the button once.
+   *[other] You clicked the button { $count } times.
+}
+click-me = Click me
@@ -0,0 +1,8 @@
+app-title = egui multilingue
+language = Langue
+hello-name = Bonjour { $name } !
+clicked = { $count ->
+    [one] Vous avez cliqué une fois sur le bouton.
+   *[other] Vous avez cliqué { $count } fois sur le bouton.
+}
+click-me = Cliquez ici
@@ -0,0 +1,72 @@
+//! A multilingual desktop application using `egui`, which initially
+//! selects the languages requested by the operating system, and
+//! allows the user to select another language using the
+//! `i18n_embed::egui::LanguageSelector` widget.
+
+use std::sync::LazyLock;
+
+use eframe::egui;
+use i18n_embed::{
+    egui::LanguageSelector,
+    fluent::{fluent_language_loader, FluentLanguageLoader},
+    DefaultLocalizer, DesktopLanguageRequester, Localizer,
+};
+use i18n_embed_fl::fl;
+use rust_embed::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/"]
+struct Localizations;
+
+static LANGUAGE_LOADER: LazyLock<FluentLanguageLoader> = LazyLock::new(|| {
+    let loader: FluentLanguageLoader = fluent_language_loader!();
+    // egui doesn't support the isolation marks around placeables.
+    loader.set_use_isolating(false);
+    loader
+});
+
+struct App {
+    localizer: DefaultLocalizer<'static>,
+    name: String,
+    count: u32,
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let loader = &*LANGUAGE_LOADER;
+        egui::TopBottomPanel::top("toolbar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading(fl!(loader, "app-title"));
+                ui.add(LanguageSelector::new(&self.localizer).label(fl!(loader, "language")));
+            });
+        });
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.text_edit_singleline(&mut self.name);
+            ui.label(fl!(loader, "hello-name", name = self.name.as_str()));
+            if ui.button(fl!(loader, "click-me")).clicked() {
+                self.count += 1;
+            }
+            ui.label(fl!(loader, "clicked", count = self.count));
+        });
+    }
+}
+
+fn main() -> eframe::Result {
+    let localizer = DefaultLocalizer::new(&*LANGUAGE_LOADER, &Localizations);
+    let requested_languages = DesktopLanguageRequester::requested_languages();
+    if let Err(error) = localizer.select(&requested_languages) {
+        eprintln!("Error while loading the languages: {error}");
+    }
+
+    eframe::run_native(
+        "egui-desktop",
+        eframe::NativeOptions::default(),
+        Box::new(|_creation_context| {
+            Ok(Box::new(App {
+                localizer,
+                name: String::from("Ferris"),
+                count: 0,
+            }))
+        }),
+    )
+}
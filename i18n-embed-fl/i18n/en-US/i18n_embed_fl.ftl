@@ -3,4 +3,8 @@ hello-arg = Hello {$name}!
     .attr = Hello {$name}'s attribute!
 hello-arg-2 = Hello {$name1} and {$name2}!
 hello-attr = Uninspiring.
-    .text = Hello, attribute!
\ No newline at end of file
+    .text = Hello, attribute!
+apples = { $count ->
+    [one] One apple
+   *[other] { $count } apples
+}
@@ -0,0 +1,3 @@
+hello-world = Bonjour le monde!
+hello-arg = Bonjour {$name}!
+    .attr = L'attribut de {$name}!
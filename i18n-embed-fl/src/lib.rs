@@ -1,13 +1,20 @@
 use fluent::{FluentAttribute, FluentMessage};
-use fluent_syntax::ast::{CallArguments, Expression, InlineExpression, Pattern, PatternElement};
-use i18n_embed::{fluent::FluentLanguageLoader, FileSystemAssets, LanguageLoader};
+use fluent_syntax::{
+    ast::{
+        Attribute, CallArguments, Entry, Expression, Identifier, InlineExpression, Message,
+        Pattern, PatternElement, Resource, VariantKey,
+    },
+    serializer,
+};
+use i18n_embed::{fluent::FluentLanguageLoader, FileSystemAssets, LanguageLoader, PathTemplate};
 use proc_macro::TokenStream;
 use proc_macro_error2::{abort, emit_error, proc_macro_error};
 use quote::quote;
 use std::{
     collections::{HashMap, HashSet},
-    path::Path,
+    path::{Path, PathBuf},
     sync::OnceLock,
+    time::SystemTime,
 };
 use syn::{parse::Parse, parse_macro_input, spanned::Spanned};
 use unic_langid::LanguageIdentifier;
@@ -135,9 +142,42 @@ impl Parse for FlArgs {
     }
 }
 
+/// An optional `locale = expr` argument following the loader, used to
+/// select the language for this lookup explicitly.
+#[derive(Debug)]
+struct FlLocale(Option<syn::Expr>);
+
+impl FlLocale {
+    /// Whether the `input` starts with a `locale = expr` argument.
+    fn peek(input: syn::parse::ParseStream) -> bool {
+        input.peek(syn::Ident)
+            && input.peek2(syn::Token![=])
+            && input
+                .fork()
+                .parse::<syn::Ident>()
+                .is_ok_and(|ident| ident == "locale")
+    }
+}
+
+impl Parse for FlLocale {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if Self::peek(input) {
+            input.parse::<syn::Ident>()?;
+            input.parse::<syn::Token![=]>()?;
+            let locale = input.parse::<syn::Expr>()?;
+            input.parse::<syn::Token![,]>()?;
+            return Ok(Self(Some(locale)));
+        }
+        Ok(Self(None))
+    }
+}
+
 /// Input for the [fl()] macro.
 struct FlMacroInput {
-    fluent_loader: syn::Expr,
+    /// `None` if the loader was omitted, in which case the global
+    /// loader for the crate's domain is used.
+    fluent_loader: Option<syn::Expr>,
+    locale: FlLocale,
     message_id: syn::Lit,
     attr: FlAttr,
     args: FlArgs,
@@ -145,14 +185,21 @@ struct FlMacroInput {
 
 impl Parse for FlMacroInput {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let fluent_loader = input.parse()?;
-        input.parse::<syn::Token![,]>()?;
+        let fluent_loader = if input.peek(syn::LitStr) || FlLocale::peek(input) {
+            None
+        } else {
+            let fluent_loader = input.parse()?;
+            input.parse::<syn::Token![,]>()?;
+            Some(fluent_loader)
+        };
+        let locale = input.parse()?;
         let message_id = input.parse()?;
         let attr = input.parse()?;
         let args = input.parse()?;
 
         Ok(Self {
             fluent_loader,
+            locale,
             message_id,
             attr,
             args,
@@ -163,12 +210,71 @@ impl Parse for FlMacroInput {
 struct DomainSpecificData {
     loader: FluentLanguageLoader,
     _assets: FileSystemAssets,
+    /// The files which were read to load the domain: the i18n config
+    /// file and the fluent file of the `fallback_language`, with
+    /// their modification times when they were read.
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+    /// The crates (by `CARGO_CRATE_NAME`) for which the compiler has
+    /// been told to track the `files`, see
+    /// [DomainSpecificData::track_files()].
+    tracked_crates: dashmap::DashSet<String>,
 }
 
-fn domains() -> &'static dashmap::DashMap<String, DomainSpecificData> {
-    static DOMAINS: OnceLock<dashmap::DashMap<String, DomainSpecificData>> = OnceLock::new();
+impl DomainSpecificData {
+    /// Whether any of the `files` have been modified since the domain
+    /// was loaded, in which case it needs to be loaded again. This
+    /// happens when the files are edited while the same process keeps
+    /// expanding the macros, such as rust-analyzer's proc-macro
+    /// server.
+    fn is_stale(&self) -> bool {
+        self.files
+            .iter()
+            .any(|(file, modified)| &modified_time(file) != modified)
+    }
+
+    /// Items which make the compiler track the files of the domain,
+    /// so that the crate being compiled is recompiled when they
+    /// change. This uses `include_bytes!()` because
+    /// `proc_macro::tracked_path` is not yet stable. The items are
+    /// only generated by the first macro invocation in each crate.
+    fn track_files(&self) -> Option<proc_macro2::TokenStream> {
+        let crate_name = std::env::var("CARGO_CRATE_NAME").unwrap_or_default();
+        if !self.tracked_crates.insert(crate_name) {
+            return None;
+        }
 
-    DOMAINS.get_or_init(|| dashmap::DashMap::new())
+        let files = self
+            .files
+            .iter()
+            .filter_map(|(file, _)| file.canonicalize().ok()?.to_str().map(ToString::to_string));
+        Some(quote! {
+            #(const _: &[u8] = include_bytes!(#files);)*
+        })
+    }
+}
+
+/// Identifies the fluent resources loaded for a domain. Several
+/// crates which specify the same `assets_dir` and `domain` in their
+/// configuration share the same [DomainSpecificData].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DomainKey {
+    /// The canonical path to the assets directory.
+    assets_dir: PathBuf,
+    domain: String,
+}
+
+fn domains() -> &'static dashmap::DashMap<DomainKey, DomainSpecificData> {
+    static DOMAINS: OnceLock<dashmap::DashMap<DomainKey, DomainSpecificData>> = OnceLock::new();
+
+    DOMAINS.get_or_init(dashmap::DashMap::new)
+}
+
+/// The [DomainKey] for each crate (by package name) which has
+/// already been resolved by a previous macro invocation.
+fn crate_domains() -> &'static dashmap::DashMap<String, DomainKey> {
+    static CRATE_DOMAINS: OnceLock<dashmap::DashMap<String, DomainKey>> = OnceLock::new();
+
+    CRATE_DOMAINS.get_or_init(dashmap::DashMap::new)
 }
 
 /// A macro to obtain localized messages and optionally their attributes, and check the `message_id`, `attribute_id`
@@ -177,6 +283,11 @@ fn domains() -> &'static dashmap::DashMap<String, DomainSpecificData> {
 /// Compile time checks are performed using the `fallback_language`
 /// specified in the current crate's `i18n.toml` confiration file.
 ///
+/// The `message_id` is documented with the source of the message
+/// in the `fallback_language`, so hovering over it in an IDE (such as
+/// one using rust-analyzer) displays the message, including its
+/// attributes.
+///
 /// This macro supports three different calling syntaxes which are
 /// explained in the following sections.
 ///
@@ -190,6 +301,12 @@ fn domains() -> &'static dashmap::DashMap<String, DomainSpecificData> {
 /// message with no arguments. The `message_id` should be specified as
 /// a literal string, and is checked at compile time.
 ///
+/// This form returns a
+/// [LocalizedStr](i18n_embed::fluent::LocalizedStr) obtained using
+/// [FluentLanguageLoader::get_cow()](i18n_embed::fluent::FluentLanguageLoader::get_cow()),
+/// which avoids allocating a new `String` for messages without
+/// placeables. The forms with arguments return a `String`.
+///
 /// ### Example
 ///
 /// ```
@@ -260,6 +377,28 @@ fn domains() -> &'static dashmap::DashMap<String, DomainSpecificData> {
 /// );
 /// ```
 ///
+/// ### Numeric Arguments
+///
+/// Arguments which the fluent message passes to the `NUMBER()` or
+/// `DATETIME()` functions, or uses as the selector of plural (or
+/// numeric) variants, need to be numbers so that they are formatted
+/// and select the correct variant at runtime. Passing a string
+/// literal for one of these arguments is a compile error:
+///
+/// ```compile_fail
+/// # use i18n_embed::{
+/// #     fluent::{fluent_language_loader, FluentLanguageLoader},
+/// #     LanguageLoader,
+/// # };
+/// # use i18n_embed_fl::fl;
+/// # let loader: FluentLanguageLoader = fluent_language_loader!();
+/// // apples = { $count ->
+/// //     [one] One apple
+/// //    *[other] { $count } apples
+/// // }
+/// fl!(loader, "apples", count = "1");
+/// ```
+///
 /// ## Arguments Hashmap
 ///
 /// ```ignore
@@ -332,96 +471,124 @@ fn domains() -> &'static dashmap::DashMap<String, DomainSpecificData> {
 ///
 /// assert_eq!("Hello \u{2068}Bob\u{2069}'s attribute!", fl!(loader, "hello-arg", "attr", args));
 /// ```
+///
+/// ## Explicit Locale
+///
+/// ```ignore
+/// fl!(loader: FluentLanguageLoader, locale = language, "message_id", ...)
+/// ```
+///
+/// In all of the above patterns you can optionally specify `locale =
+/// language` directly after the loader, where `language` is an
+/// expression that resolves to a type implementing
+/// `AsRef<LanguageIdentifier>`. The message will be obtained in that
+/// language (falling back to the loader's `fallback_language`) using
+/// [FluentLanguageLoader::get_or_fallback()](i18n_embed::fluent::FluentLanguageLoader::get_or_fallback())
+/// and its variants, so the language needs to have already been
+/// loaded. This is useful
+/// for server applications which load all available languages once
+/// and select one per request. The `message_id`, `attribute_id` and
+/// arguments are still checked at compile time against the
+/// `fallback_language`.
+///
+/// ### Example
+///
+/// ```
+/// # use i18n_embed::{
+/// #     fluent::{fluent_language_loader, FluentLanguageLoader},
+/// #     LanguageLoader,
+/// # };
+/// # use i18n_embed_fl::fl;
+/// # use rust_embed::RustEmbed;
+/// # #[derive(RustEmbed)]
+/// # #[folder = "i18n/"]
+/// # struct Localizations;
+/// # let loader: FluentLanguageLoader = fluent_language_loader!();
+/// loader.load_available_languages(&Localizations).unwrap();
+///
+/// let user_language: unic_langid::LanguageIdentifier = "fr".parse().unwrap();
+/// assert_eq!(
+///     "Bonjour \u{2068}Bob\u{2069}!",
+///     fl!(loader, locale = &user_language, "hello-arg", name = "Bob")
+/// );
+/// ```
+///
+/// ## Global Loader
+///
+/// ```ignore
+/// fl!("message_id", ...)
+/// ```
+///
+/// In all of the above patterns the loader can be omitted, in which
+/// case the global loader registered for the current crate's domain
+/// is used (see
+/// [init_global_loader!()](i18n_embed::init_global_loader!()) and
+/// [set_global_loader()](i18n_embed::fluent::set_global_loader())).
+/// This is convenient for application crates which only have a single
+/// loader. The same compile time checks are performed, but the code
+/// will panic at runtime if no global loader has been registered.
+///
+/// ### Example
+///
+/// ```
+/// # use i18n_embed::{fluent::FluentLanguageLoader, LanguageLoader};
+/// # use i18n_embed_fl::fl;
+/// # use rust_embed::RustEmbed;
+/// # #[derive(RustEmbed)]
+/// # #[folder = "i18n/"]
+/// # struct Localizations;
+/// let loader: &'static FluentLanguageLoader = i18n_embed::init_global_loader!();
+/// loader.load_fallback_language(&Localizations).unwrap();
+///
+/// assert_eq!("Hello \u{2068}Bob\u{2069}!", fl!("hello-arg", name = "Bob"));
+/// ```
+///
+/// ## Skipping the Checks
+///
+/// The checks need the `i18n.toml` configuration file and the fluent
+/// file of the `fallback_language`, which are not available in some
+/// builds, such as `trybuild` tests (where `CARGO_MANIFEST_DIR` is a
+/// generated crate), or vendored builds which exclude them. Setting
+/// the `I18N_EMBED_SKIP_CHECKS=1` environment variable makes this
+/// macro generate unchecked code with a warning instead of reading
+/// them. Alternatively, `allow_missing_at_compile_time = true` in the
+/// `[fluent]` subsection of `i18n.toml` only skips the checks when
+/// the fluent files are missing, which is also the case when the
+/// crate is documented on docs.rs.
 #[proc_macro]
 #[proc_macro_error]
 pub fn fl(input: TokenStream) -> TokenStream {
     let input: FlMacroInput = parse_macro_input!(input as FlMacroInput);
+    fl_impl(input).into()
+}
 
-    let fluent_loader = input.fluent_loader;
+/// Check the `input` of the [fl()] macro (or the [fl_scope()] macro
+/// with a message id), and generate the code to obtain the message.
+fn fl_impl(input: FlMacroInput) -> proc_macro2::TokenStream {
     let message_id = input.message_id;
 
-    let domain = {
-        let manifest = find_crate::Manifest::new().expect("Error reading Cargo.toml");
-        manifest.crate_package().map(|pkg| pkg.name).unwrap_or(
-            std::env::var("CARGO_PKG_NAME").expect("Error fetching `CARGO_PKG_NAME` env"),
-        )
-    };
+    let data = domain_data();
+    // `None` if the checks are skipped.
+    let domain_data = data.checked();
 
-    let domain_data = if let Some(domain_data) = domains().get(&domain) {
-        domain_data
-    } else {
-        let crate_paths = i18n_config::locate_crate_paths()
-            .unwrap_or_else(|error| panic!("fl!() is unable to locate crate paths: {}", error));
-
-        let config_file_path = &crate_paths.i18n_config_file;
-
-        let config = i18n_config::I18nConfig::from_file(config_file_path).unwrap_or_else(|err| {
-            abort! {
-                proc_macro2::Span::call_site(),
-                format!(
-                    "fl!() had a problem reading i18n config file {config_file_path:?}: {err}"
-                );
-                help = "Try creating the `i18n.toml` configuration file.";
-            }
-        });
-
-        let fluent_config = config.fluent.unwrap_or_else(|| {
-            abort! {
-                proc_macro2::Span::call_site(),
-                format!(
-                    "fl!() had a problem parsing i18n config file {config_file_path:?}: \
-                    there is no `[fluent]` subsection."
-                );
-                help = "Add the `[fluent]` subsection to `i18n.toml`, \
-                        along with its required `assets_dir`.";
-            }
-        });
-
-        // Use the domain override in the configuration.
-        let domain = fluent_config.domain.unwrap_or(domain);
-
-        let assets_dir = Path::new(&crate_paths.crate_dir).join(fluent_config.assets_dir);
-        let assets = FileSystemAssets::try_new(assets_dir).unwrap();
-
-        let fallback_language: LanguageIdentifier = config.fallback_language;
-
-        let loader = FluentLanguageLoader::new(&domain, fallback_language.clone());
-
-        loader
-            .load_languages(&assets, &[fallback_language.clone()])
-            .unwrap_or_else(|err| match err {
-                i18n_embed::I18nEmbedError::LanguageNotAvailable(file, language_id) => {
-                    if fallback_language != language_id {
-                        panic!(
-                            "fl!() encountered an unexpected problem, \
-                            the language being loaded (\"{0}\") is not the \
-                            `fallback_language` (\"{1}\")",
-                            language_id, fallback_language
-                        )
-                    }
-                    abort! {
-                        proc_macro2::Span::call_site(),
-                        format!(
-                            "fl!() was unable to load the localization \
-                            file for the `fallback_language` \
-                            (\"{fallback_language}\"): {file}"
-                        );
-                        help = "Try creating the required fluent localization file.";
-                    }
-                }
-                _ => panic!(
-                    "fl!() had an unexpected problem while \
-                        loading language \"{0}\": {1}",
-                    fallback_language, err
-                ),
-            });
-
-        let data = DomainSpecificData {
-            loader,
-            _assets: assets,
-        };
-
-        domains().entry(domain.clone()).or_insert(data).downgrade()
+    let fluent_loader = match input.fluent_loader {
+        Some(fluent_loader) => quote! { #fluent_loader },
+        None => {
+            let i18n_embed_crate_ident = i18n_embed_crate_ident();
+            let domain = data.domain();
+            quote! { #i18n_embed_crate_ident::fluent::global_loader(#domain) }
+        }
+    };
+    // With an explicit locale, the message is looked up in the bundles
+    // of that language (and the fallback language) using the
+    // `get_or_fallback` methods, which take the language as their
+    // first argument.
+    let locale = input.locale.0.map(|locale| {
+        quote! { ::std::convert::AsRef::as_ref(&#locale), }
+    });
+    let method = |name: &str, locale_name: &str| {
+        let name = if locale.is_some() { locale_name } else { name };
+        syn::Ident::new(name, proc_macro2::Span::call_site())
     };
 
     let message_id_string = match &message_id {
@@ -461,6 +628,38 @@ pub fn fl(input: TokenStream) -> TokenStream {
         }
     };
 
+    // Document the message id with the source of the message in the
+    // `fallback_language`, so that it is displayed when hovering over
+    // the `message_id` in an IDE.
+    // The files of the domain are tracked alongside, so that the crate
+    // is recompiled when they change.
+    let message_id_expr = match (&message_id_string, domain_data) {
+        (Some(message_id_str), Some(domain_data)) => domain_data
+            .loader
+            .with_fluent_message(message_id_str, |message| {
+                message_source(message_id_str, &message)
+            })
+            .map(|source| {
+                let doc = format!(
+                    "`{message_id_str}` in the `fallback_language` (\"{0}\"):\n\n\
+                    ```fluent\n{source}```",
+                    domain_data.loader.fallback_language(),
+                );
+                let ident = syn::Ident::new("MESSAGE", message_id.span());
+                let track_files = domain_data.track_files();
+                quote! {
+                    {
+                        #track_files
+                        #[doc = #doc]
+                        const #ident: &str = #message_id;
+                        #ident
+                    }
+                }
+            }),
+        _ => None,
+    }
+    .unwrap_or_else(|| quote! { #message_id });
+
     // If we have already confirmed that the loader has the message.
     // `false` if we haven't checked, or we have checked but no
     // message was found.
@@ -471,23 +670,27 @@ pub fn fl(input: TokenStream) -> TokenStream {
     let gen = match input.args {
         FlArgs::HashMap(args_hash_map) => {
             if attr_lit.is_none() {
+                let method = method("get_args", "get_or_fallback_args");
                 quote! {
-                    (#fluent_loader).get_args(#message_id, #args_hash_map)
+                    (#fluent_loader).#method(#locale #message_id_expr, #args_hash_map)
                 }
             } else {
+                let method = method("get_attr_args", "get_or_fallback_attr_args");
                 quote! {
-                    (#fluent_loader).get_attr_args(#message_id, #attr_lit, #args_hash_map)
+                    (#fluent_loader).#method(#locale #message_id_expr, #attr_lit, #args_hash_map)
                 }
             }
         }
         FlArgs::None => {
             if attr_lit.is_none() {
+                let method = method("get_cow", "get_or_fallback");
                 quote! {
-                    (#fluent_loader).get(#message_id)
+                    (#fluent_loader).#method(#locale #message_id_expr)
                 }
             } else {
+                let method = method("get_attr_cow", "get_or_fallback_attr");
                 quote! {
-                    (#fluent_loader).get_attr(#message_id, #attr_lit)
+                    (#fluent_loader).#method(#locale #message_id_expr, #attr_lit)
                 }
             }
         }
@@ -501,7 +704,8 @@ pub fn fl(input: TokenStream) -> TokenStream {
             }
 
             if attr_lit.is_none() {
-                if let Some(message_id_str) = &message_id_string {
+                if let (Some(message_id_str), Some(domain_data)) = (&message_id_string, domain_data)
+                {
                     checked_loader_has_message = domain_data
                         .loader
                         .with_fluent_message(message_id_str, |message: FluentMessage<'_>| {
@@ -510,9 +714,11 @@ pub fn fl(input: TokenStream) -> TokenStream {
                         .is_some();
                 }
 
+                let method = method("get_args_concrete", "get_or_fallback_args_concrete");
                 let gen = quote! {
-                    (#fluent_loader).get_args_concrete(
-                        #message_id,
+                    (#fluent_loader).#method(
+                        #locale
+                        #message_id_expr,
                         {
                             let mut args = std::collections::HashMap::new();
                             #arg_assignments
@@ -522,7 +728,8 @@ pub fn fl(input: TokenStream) -> TokenStream {
 
                 gen
             } else {
-                if let Some(message_id_str) = &message_id_string {
+                if let (Some(message_id_str), Some(domain_data)) = (&message_id_string, domain_data)
+                {
                     if let Some(attr_id_str) = &attr_str {
                         let attr_res = domain_data.loader.with_fluent_message(
                             message_id_str,
@@ -539,9 +746,14 @@ pub fn fl(input: TokenStream) -> TokenStream {
                     }
                 }
 
+                let method = method(
+                    "get_attr_args_concrete",
+                    "get_or_fallback_attr_args_concrete",
+                );
                 let gen = quote! {
-                    (#fluent_loader).get_attr_args_concrete(
-                        #message_id,
+                    (#fluent_loader).#method(
+                        #locale
+                        #message_id_expr,
                         #attr_lit,
                         {
                             let mut args = std::collections::HashMap::new();
@@ -555,7 +767,7 @@ pub fn fl(input: TokenStream) -> TokenStream {
         }
     };
 
-    if let Some(message_id_str) = &message_id_string {
+    if let (Some(message_id_str), Some(domain_data)) = (&message_id_string, domain_data) {
         if !checked_loader_has_message && !domain_data.loader.has(message_id_str) {
             let suggestions =
                 fuzzy_message_suggestions(&domain_data.loader, message_id_str, 5).join("\n");
@@ -613,7 +825,503 @@ pub fn fl(input: TokenStream) -> TokenStream {
         }
     }
 
-    gen.into()
+    data.with_skipped_checks_warning(gen)
+}
+
+/// Input for the [fl_scope()] macro.
+struct FlScopeMacroInput {
+    /// `None` if the loader was omitted, in which case the global
+    /// loader for the crate's domain is used.
+    fluent_loader: Option<syn::Expr>,
+    prefix: syn::LitStr,
+    /// The remainder of the id of the message to obtain, with its
+    /// optional attribute and arguments, or `None` to obtain the
+    /// scope.
+    message: Option<(syn::LitStr, FlAttr, FlArgs)>,
+}
+
+impl Parse for FlScopeMacroInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let fluent_loader = if input.peek(syn::LitStr) {
+            None
+        } else {
+            let fluent_loader = input.parse()?;
+            input.parse::<syn::Token![,]>()?;
+            Some(fluent_loader)
+        };
+        let prefix = input.parse()?;
+        let message = if input.is_empty() {
+            None
+        } else {
+            input.parse::<syn::Token![,]>()?;
+            let message_id = input.parse()?;
+            let attr = input.parse()?;
+            let args = input.parse()?;
+            Some((message_id, attr, args))
+        };
+
+        Ok(Self {
+            fluent_loader,
+            prefix,
+            message,
+        })
+    }
+}
+
+/// A macro to obtain a
+/// [MessageScope](i18n_embed::fluent::MessageScope) for messages with
+/// ids sharing a common prefix, checking at compile time that the
+/// `fallback_language` contains messages with that prefix.
+///
+/// ```ignore
+/// fl_scope!(loader: FluentLanguageLoader, "prefix")
+/// ```
+///
+/// Messages are obtained from the scope using the remainder of their
+/// id, so `scope.get("volume")` on a scope with the prefix
+/// `"settings-audio"` obtains the message `settings-audio-volume`.
+/// Only the prefix is checked at compile time, the ids passed to the
+/// scope's methods are checked when the message is requested at
+/// runtime. As with [fl!()](fl!()), the loader can be omitted to use
+/// the global loader for the current crate's domain.
+///
+/// ```ignore
+/// fl_scope!(loader: FluentLanguageLoader, "prefix", "message_id", ...)
+/// ```
+///
+/// When the remainder of a message id follows the prefix, the message
+/// is obtained directly instead of the scope, like
+/// `fl!(loader, "prefix-message_id", ...)` with the same optional
+/// attribute id and arguments. The whole message id (and the
+/// arguments) are checked at compile time, so this form is
+/// preferable to the scope's methods when the message id is known.
+///
+/// ### Example
+///
+/// ```
+/// # use i18n_embed::{
+/// #     fluent::{fluent_language_loader, FluentLanguageLoader},
+/// #     LanguageLoader,
+/// # };
+/// # use i18n_embed_fl::fl_scope;
+/// # use rust_embed::RustEmbed;
+/// # #[derive(RustEmbed)]
+/// # #[folder = "i18n/"]
+/// # struct Localizations;
+/// # let loader: FluentLanguageLoader = fluent_language_loader!();
+/// # loader
+/// #     .load_languages(&Localizations, &[loader.fallback_language().clone()])
+/// #     .unwrap();
+/// let hello = fl_scope!(loader, "hello");
+/// assert_eq!("Hello World!", hello.get("world"));
+/// assert_eq!("Hello World!", fl_scope!(loader, "hello", "world"));
+/// ```
+#[proc_macro]
+#[proc_macro_error]
+pub fn fl_scope(input: TokenStream) -> TokenStream {
+    let input: FlScopeMacroInput = parse_macro_input!(input as FlScopeMacroInput);
+    let prefix = input.prefix;
+    let prefix_str = prefix.value();
+
+    // The whole message id is checked by `fl!()`, which also reports
+    // an unknown prefix.
+    if let Some((message_id, attr, args)) = input.message {
+        let message_id = syn::LitStr::new(
+            &format!("{prefix_str}-{0}", message_id.value()),
+            message_id.span(),
+        );
+        return fl_impl(FlMacroInput {
+            fluent_loader: input.fluent_loader,
+            locale: FlLocale(None),
+            message_id: syn::Lit::Str(message_id),
+            attr,
+            args,
+        })
+        .into();
+    }
+
+    let data = domain_data();
+    let message_id_prefix = format!("{prefix_str}-");
+
+    // The prefix is not checked if the checks are skipped.
+    let unknown_prefix = data.checked().filter(|domain_data| {
+        !domain_data
+            .loader
+            .message_ids(domain_data.loader.fallback_language())
+            .iter()
+            .any(|message_id| message_id.starts_with(message_id_prefix.as_str()))
+    });
+
+    if let Some(domain_data) = unknown_prefix {
+        let suggestions = fuzzy_message_suggestions(&domain_data.loader, &prefix_str, 5).join("\n");
+
+        let hint = format!(
+            "Perhaps you are looking for the prefix of one of the following messages?\n\n\
+            {suggestions}"
+        );
+
+        emit_error! {
+            prefix,
+            format!(
+                "fl_scope!() `prefix` validation failed. There are no messages \
+                with an id starting with \"{0}\" in the `fallback_language` (\"{1}\")",
+                message_id_prefix,
+                domain_data.loader.current_language(),
+            );
+            help = "Enter the correct `prefix`, without the trailing `-`.";
+
+            hint = hint;
+        };
+    }
+
+    let fluent_loader = match input.fluent_loader {
+        Some(fluent_loader) => quote! { #fluent_loader },
+        None => {
+            let i18n_embed_crate_ident = i18n_embed_crate_ident();
+            let domain = data.domain();
+            quote! { #i18n_embed_crate_ident::fluent::global_loader(#domain) }
+        }
+    };
+
+    let gen = quote! {
+        (#fluent_loader).message_scope(#prefix)
+    };
+
+    data.with_skipped_checks_warning(gen).into()
+}
+
+/// The data used to check the invocations of the macros in the
+/// current crate, see [domain_data()].
+enum DomainData {
+    /// The fluent resources of the domain were loaded, and the macro
+    /// invocations are checked against them.
+    Checked(dashmap::mapref::one::Ref<'static, DomainKey, DomainSpecificData>),
+    /// The checks are skipped, and unchecked code is generated with a
+    /// warning, because the `I18N_EMBED_SKIP_CHECKS` environment
+    /// variable is set, or the files which are needed for the checks
+    /// are missing and this is allowed (see
+    /// [domain_data()]).
+    Unchecked {
+        domain: String,
+        /// Why the checks are skipped.
+        reason: String,
+    },
+}
+
+impl DomainData {
+    /// The data to check the macro invocations with, or `None` if the
+    /// checks are skipped.
+    fn checked(&self) -> Option<&DomainSpecificData> {
+        match self {
+            DomainData::Checked(domain_data) => Some(domain_data),
+            DomainData::Unchecked { .. } => None,
+        }
+    }
+
+    /// The domain of the current crate.
+    fn domain(&self) -> &str {
+        match self {
+            DomainData::Checked(domain_data) => domain_data.loader.domain(),
+            DomainData::Unchecked { domain, .. } => domain,
+        }
+    }
+
+    /// Add a warning to the code generated by a macro (`gen`) if the
+    /// checks are skipped. The warning is only generated by the first
+    /// macro invocation in each crate. Warnings emitted by procedural
+    /// macros are ignored on stable Rust, so this uses the
+    /// deprecation of an item generated alongside the code instead.
+    fn with_skipped_checks_warning(
+        &self,
+        gen: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        static WARNED_CRATES: OnceLock<dashmap::DashSet<String>> = OnceLock::new();
+
+        let reason = match self {
+            DomainData::Checked(_) => return gen,
+            DomainData::Unchecked { reason, .. } => reason,
+        };
+        let crate_name = std::env::var("CARGO_CRATE_NAME").unwrap_or_default();
+        if !WARNED_CRATES
+            .get_or_init(dashmap::DashSet::new)
+            .insert(crate_name)
+        {
+            return gen;
+        }
+
+        let note = format!(
+            "the messages of the fl!() macros in this crate are not checked at compile time: {reason}"
+        );
+        quote! {
+            {
+                const _: () = {
+                    #[deprecated(note = #note)]
+                    struct FlChecksSkipped;
+                    let _ = FlChecksSkipped;
+                };
+                #gen
+            }
+        }
+    }
+}
+
+/// Load the [DomainData] for the current crate, using the cached data
+/// if it has already been loaded by a previous macro invocation,
+/// either for this crate or for another crate sharing the same
+/// `assets_dir` and `domain`.
+///
+/// The checks are skipped if the `I18N_EMBED_SKIP_CHECKS` environment
+/// variable is set (see [i18n_config::skip_compile_time_checks()]).
+/// Missing files (the `i18n.toml` config file, or the fluent file of
+/// the `fallback_language`) fail the compilation, unless the crate is
+/// being documented on docs.rs, or the `[fluent]` subsection of the
+/// config has `allow_missing_at_compile_time = true`, in which case
+/// the checks are also skipped.
+fn domain_data() -> DomainData {
+    let crate_name = {
+        let manifest = find_crate::Manifest::new().expect("Error reading Cargo.toml");
+        manifest.crate_package().map(|pkg| pkg.name).unwrap_or(
+            std::env::var("CARGO_PKG_NAME").expect("Error fetching `CARGO_PKG_NAME` env"),
+        )
+    };
+
+    if i18n_config::skip_compile_time_checks() {
+        return DomainData::Unchecked {
+            domain: unchecked_domain(&crate_name),
+            reason: format!(
+                "the `{0}` environment variable is set",
+                i18n_config::SKIP_CHECKS_ENV_VAR
+            ),
+        };
+    }
+
+    let cached_domain_data = crate_domains()
+        .get(&crate_name)
+        .and_then(|domain_key| domains().get(&*domain_key))
+        .filter(|domain_data| !domain_data.is_stale());
+
+    if let Some(domain_data) = cached_domain_data {
+        return DomainData::Checked(domain_data);
+    }
+
+    // Skip the checks instead of failing to compile because of a
+    // missing file, if this is allowed.
+    let missing = |allow_missing: bool, message: String, help: &str| -> DomainData {
+        if !allow_missing {
+            abort! {
+                proc_macro2::Span::call_site(),
+                message;
+                help = help;
+            }
+        }
+        DomainData::Unchecked {
+            domain: unchecked_domain(&crate_name),
+            reason: message,
+        }
+    };
+
+    let crate_paths = match i18n_config::locate_crate_paths() {
+        Ok(crate_paths) => crate_paths,
+        Err(error) if i18n_config::is_docs_rs() => {
+            return missing(
+                true,
+                format!("fl!() is unable to locate crate paths: {error}"),
+                "",
+            )
+        }
+        Err(error) => panic!("fl!() is unable to locate crate paths: {}", error),
+    };
+
+    let config_file_path = &crate_paths.i18n_config_file;
+
+    let config = match i18n_config::I18nConfig::from_file(config_file_path) {
+        Ok(config) => config,
+        Err(err) => {
+            return missing(
+                i18n_config::is_docs_rs() && !config_file_path.exists(),
+                format!("fl!() had a problem reading i18n config file {config_file_path:?}: {err}"),
+                "Try creating the `i18n.toml` configuration file.",
+            )
+        }
+    };
+
+    let fluent_config = config.fluent.unwrap_or_else(|| {
+        abort! {
+            proc_macro2::Span::call_site(),
+            format!(
+                "fl!() had a problem parsing i18n config file {config_file_path:?}: \
+                there is no `[fluent]` subsection."
+            );
+            help = "Add the `[fluent]` subsection to `i18n.toml`, \
+                    along with its required `assets_dir`.";
+        }
+    });
+    let allow_missing = fluent_config.allow_missing_at_compile_time || i18n_config::is_docs_rs();
+
+    // Use the domain override in the configuration, otherwise the
+    // name of the parent crate if the configuration is inherited.
+    let domain = fluent_config
+        .domain
+        .or(crate_paths.i18n_config_crate_name)
+        .unwrap_or(crate_name.clone());
+
+    let assets_dir = Path::new(&crate_paths.i18n_config_crate_dir).join(fluent_config.assets_dir);
+
+    let domain_key = DomainKey {
+        assets_dir: assets_dir.canonicalize().unwrap_or(assets_dir.clone()),
+        domain: domain.clone(),
+    };
+    crate_domains().insert(crate_name.clone(), domain_key.clone());
+
+    // Another crate sharing the same domain may have already
+    // loaded it.
+    if let Some(domain_data) = domains()
+        .get(&domain_key)
+        .filter(|domain_data| !domain_data.is_stale())
+    {
+        return DomainData::Checked(domain_data);
+    }
+
+    let assets = match FileSystemAssets::try_new(&assets_dir) {
+        Ok(assets) => assets,
+        Err(err) => {
+            crate_domains().remove(&crate_name);
+            return missing(
+                allow_missing,
+                format!("fl!() was unable to read the `assets_dir` {assets_dir:?}: {err}"),
+                "Try creating the `assets_dir` specified in the `[fluent]` subsection of `i18n.toml`.",
+            );
+        }
+    };
+
+    let fallback_language: LanguageIdentifier = config.fallback_language;
+
+    let loader = FluentLanguageLoader::new(&domain, fallback_language.clone());
+    if let Some(path_template) = fluent_config.path_template {
+        let path_template = PathTemplate::new(path_template).unwrap_or_else(|err| {
+            abort! {
+                proc_macro2::Span::call_site(),
+                format!(
+                    "fl!() had a problem parsing i18n config file {config_file_path:?}: {err}"
+                );
+                help = "Correct the `path_template` in the `[fluent]` subsection of `i18n.toml`.";
+            }
+        });
+        loader.set_path_template(Some(path_template));
+    }
+    // The fallback language may be served by the files of another
+    // language.
+    loader.set_language_aliases(config.language_aliases.into_iter().collect());
+
+    if let Err(err) = loader.load_languages(&assets, std::slice::from_ref(&fallback_language)) {
+        match err {
+            i18n_embed::I18nEmbedError::LanguageNotAvailable(file, language_id) => {
+                if fallback_language != language_id {
+                    panic!(
+                        "fl!() encountered an unexpected problem, \
+                        the language being loaded (\"{0}\") is not the \
+                        `fallback_language` (\"{1}\")",
+                        language_id, fallback_language
+                    )
+                }
+                crate_domains().remove(&crate_name);
+                return missing(
+                    allow_missing,
+                    format!(
+                        "fl!() was unable to load the localization \
+                        file for the `fallback_language` \
+                        (\"{fallback_language}\"): {file}"
+                    ),
+                    "Try creating the required fluent localization file.",
+                );
+            }
+            _ => panic!(
+                "fl!() had an unexpected problem while \
+                    loading language \"{0}\": {1}",
+                fallback_language, err
+            ),
+        }
+    }
+
+    let (fallback_file, _) = loader.language_files(&fallback_language, &assets);
+    let files = vec![config_file_path.clone(), assets_dir.join(fallback_file)]
+        .into_iter()
+        .map(|file| {
+            let modified = modified_time(&file);
+            (file, modified)
+        })
+        .collect();
+
+    let data = DomainSpecificData {
+        loader,
+        _assets: assets,
+        files,
+        tracked_crates: dashmap::DashSet::new(),
+    };
+
+    // Replaces the stale data, if the files have been modified.
+    DomainData::Checked(domains().entry(domain_key).insert(data).downgrade())
+}
+
+/// The domain of the current crate (named `crate_name`) when the
+/// checks are skipped: the domain in its `i18n.toml` config file if
+/// it can be read, otherwise the name of the crate (or of the parent
+/// crate which the config is inherited from).
+fn unchecked_domain(crate_name: &str) -> String {
+    let crate_paths = match i18n_config::locate_crate_paths() {
+        Ok(crate_paths) => crate_paths,
+        Err(_) => return crate_name.to_string(),
+    };
+    i18n_config::I18nConfig::from_file(&crate_paths.i18n_config_file)
+        .ok()
+        .and_then(|config| config.fluent?.domain)
+        .or(crate_paths.i18n_config_crate_name)
+        .unwrap_or_else(|| crate_name.to_string())
+}
+
+/// The modification time of the `file`, if it is available.
+fn modified_time(file: &Path) -> Option<SystemTime> {
+    std::fs::metadata(file)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// The identifier for the `i18n-embed` crate, as it is named in the
+/// current crate's dependencies.
+fn i18n_embed_crate_ident() -> syn::Ident {
+    let manifest = find_crate::Manifest::new().expect("Error reading Cargo.toml");
+    let name = match manifest.find(|s| s == "i18n-embed") {
+        Some(package) => package.name,
+        None => abort! {
+            proc_macro2::Span::call_site(),
+            "fl!() without a loader requires `i18n-embed` to be a dependency of this crate";
+            help = "Add `i18n-embed` to the dependencies in `Cargo.toml`, \
+                    or specify the loader: `fl!(loader, \"message-id\")`.";
+        },
+    };
+    syn::Ident::new(&name, proc_macro2::Span::call_site())
+}
+
+/// The source of the `message` with the `message_id`, including its
+/// attributes.
+fn message_source(message_id: &str, message: &FluentMessage<'_>) -> String {
+    let message = Message {
+        id: Identifier { name: message_id },
+        value: message.value().cloned(),
+        attributes: message
+            .attributes()
+            .map(|attribute| Attribute {
+                id: Identifier {
+                    name: attribute.id(),
+                },
+                value: attribute.value().clone(),
+            })
+            .collect(),
+        comment: None,
+    };
+    serializer::serialize(&Resource {
+        body: vec![Entry::Message(message)],
+    })
 }
 
 fn fuzzy_message_suggestions(
@@ -621,17 +1329,14 @@ fn fuzzy_message_suggestions(
     message_id_str: &str,
     n_suggestions: usize,
 ) -> Vec<String> {
-    let mut scored_messages: Vec<(String, usize)> =
-        loader.with_message_iter(loader.fallback_language(), |message_iter| {
-            message_iter
-                .map(|message| {
-                    (
-                        message.id.name.to_string(),
-                        strsim::levenshtein(message_id_str, message.id.name),
-                    )
-                })
-                .collect()
-        });
+    let mut scored_messages: Vec<(String, usize)> = loader
+        .message_ids(loader.fallback_language())
+        .into_iter()
+        .map(|message_id| {
+            let score = strsim::levenshtein(message_id_str, &message_id);
+            (message_id, score)
+        })
+        .collect();
 
     scored_messages.sort_by_key(|(_message, score)| *score);
 
@@ -673,6 +1378,8 @@ fn check_message_args(
     specified_args: &HashMap<syn::LitStr, Box<syn::Expr>>,
 ) {
     if let Some(pattern) = message.value() {
+        check_numeric_args(pattern, specified_args);
+
         let mut args = Vec::new();
         args_from_pattern(pattern, &mut args);
 
@@ -740,6 +1447,8 @@ fn check_attribute_args(
     specified_args: &HashMap<syn::LitStr, Box<syn::Expr>>,
 ) {
     let pattern = attr.value();
+    check_numeric_args(pattern, specified_args);
+
     let mut args = Vec::new();
     args_from_pattern(pattern, &mut args);
 
@@ -801,6 +1510,145 @@ fn check_attribute_args(
     }
 }
 
+/// How a fluent pattern uses an argument which needs to be a number
+/// (or a date).
+enum NumericUsage<S> {
+    /// The argument is passed to a function such as `NUMBER()`.
+    Function(S),
+    /// The argument is the selector of plural or numeric variants.
+    Selector,
+}
+
+/// The functions whose first argument needs to be a number (or a
+/// date).
+const NUMERIC_FUNCTIONS: &[&str] = &["NUMBER", "DATETIME"];
+
+/// The plural categories used as variant keys, other than `other`
+/// which is also the default variant of other selectors.
+const PLURAL_CATEGORIES: &[&str] = &["zero", "one", "two", "few", "many"];
+
+/// Emit an error for each of the `specified_args` which is a string
+/// literal, but is used as a number by the `pattern`.
+fn check_numeric_args(
+    pattern: &Pattern<&str>,
+    specified_args: &HashMap<syn::LitStr, Box<syn::Expr>>,
+) {
+    let mut numeric_args = Vec::new();
+    numeric_args_from_pattern(pattern, &mut numeric_args);
+
+    for (key, value) in specified_args {
+        let is_string_literal = matches!(
+            &**value,
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(_),
+                ..
+            })
+        );
+        if !is_string_literal {
+            continue;
+        }
+        let arg = key.value();
+        if let Some((_, usage)) = numeric_args.iter().find(|(name, _)| *name == arg) {
+            let usage = match usage {
+                NumericUsage::Function(function) => format!("passed to `{function}()`"),
+                NumericUsage::Selector => "the selector of plural variants".to_string(),
+            };
+            emit_error! {
+                value,
+                format!(
+                    "fl!() argument `{0}` is a string literal, but it is \
+                    {1} in the fluent message.",
+                    arg, usage
+                );
+                help = "Specify a number for this argument, otherwise it is not \
+                        formatted as a number, and the plural variant is not \
+                        selected correctly at runtime.";
+            };
+        }
+    }
+}
+
+fn numeric_args_from_pattern<S: Copy + AsRef<str>>(
+    pattern: &Pattern<S>,
+    numeric_args: &mut Vec<(S, NumericUsage<S>)>,
+) {
+    pattern.elements.iter().for_each(|element| {
+        if let PatternElement::Placeable { expression } = element {
+            numeric_args_from_expression(expression, numeric_args)
+        }
+    });
+}
+
+fn numeric_args_from_expression<S: Copy + AsRef<str>>(
+    expr: &Expression<S>,
+    numeric_args: &mut Vec<(S, NumericUsage<S>)>,
+) {
+    match expr {
+        Expression::Inline(inline_expr) => {
+            numeric_args_from_inline_expression(inline_expr, numeric_args);
+        }
+        Expression::Select { selector, variants } => {
+            let numeric_variants = variants.iter().any(|variant| match &variant.key {
+                VariantKey::NumberLiteral { .. } => true,
+                VariantKey::Identifier { name } => PLURAL_CATEGORIES.contains(&name.as_ref()),
+            });
+            if let (InlineExpression::VariableReference { id }, true) = (selector, numeric_variants)
+            {
+                numeric_args.push((id.name, NumericUsage::Selector));
+            }
+            numeric_args_from_inline_expression(selector, numeric_args);
+
+            variants.iter().for_each(|variant| {
+                numeric_args_from_pattern(&variant.value, numeric_args);
+            })
+        }
+    }
+}
+
+fn numeric_args_from_inline_expression<S: Copy + AsRef<str>>(
+    inline_expr: &InlineExpression<S>,
+    numeric_args: &mut Vec<(S, NumericUsage<S>)>,
+) {
+    match inline_expr {
+        InlineExpression::FunctionReference {
+            id,
+            arguments: call_args,
+        } => {
+            if let Some(InlineExpression::VariableReference { id: arg_id }) =
+                call_args.positional.first()
+            {
+                if NUMERIC_FUNCTIONS.contains(&id.name.as_ref()) {
+                    numeric_args.push((arg_id.name, NumericUsage::Function(id.name)));
+                }
+            }
+            numeric_args_from_call_arguments(call_args, numeric_args);
+        }
+        InlineExpression::TermReference {
+            arguments: Some(call_args),
+            ..
+        } => {
+            numeric_args_from_call_arguments(call_args, numeric_args);
+        }
+        InlineExpression::Placeable { expression } => {
+            numeric_args_from_expression(expression, numeric_args)
+        }
+        _ => {}
+    }
+}
+
+fn numeric_args_from_call_arguments<S: Copy + AsRef<str>>(
+    call_args: &CallArguments<S>,
+    numeric_args: &mut Vec<(S, NumericUsage<S>)>,
+) {
+    call_args.positional.iter().for_each(|expr| {
+        numeric_args_from_inline_expression(expr, numeric_args);
+    });
+
+    call_args.named.iter().for_each(|named_arg| {
+        numeric_args_from_inline_expression(&named_arg.value, numeric_args);
+    })
+}
+
 fn args_from_pattern<S: Copy>(pattern: &Pattern<S>, args: &mut Vec<S>) {
     pattern.elements.iter().for_each(|element| {
         if let PatternElement::Placeable { expression } = element {
@@ -2,7 +2,7 @@ use i18n_embed::{
     fluent::{fluent_language_loader, FluentLanguageLoader},
     LanguageLoader,
 };
-use i18n_embed_fl::fl;
+use i18n_embed_fl::{fl, fl_scope};
 use rust_embed::RustEmbed;
 use std::collections::HashMap;
 
@@ -68,6 +68,18 @@ fn with_one_arg_lit() {
     );
 }
 
+#[test]
+fn with_numeric_args() {
+    let loader: FluentLanguageLoader = fluent_language_loader!();
+    loader
+        .load_languages(&Localizations, &[loader.fallback_language().clone()])
+        .unwrap();
+    loader.set_use_isolating(false);
+
+    pretty_assertions::assert_eq!("One apple", fl!(loader, "apples", count = 1));
+    pretty_assertions::assert_eq!("3 apples", fl!(loader, "apples", count = 3));
+}
+
 #[test]
 fn with_attr() {
     let loader: FluentLanguageLoader = fluent_language_loader!();
@@ -90,3 +102,94 @@ fn with_attr_and_args() {
         fl!(loader, "hello-arg", "attr", name = "Bob")
     );
 }
+
+#[test]
+fn with_locale() {
+    let loader: FluentLanguageLoader = fluent_language_loader!();
+    loader.load_available_languages(&Localizations).unwrap();
+
+    let fr: unic_langid::LanguageIdentifier = "fr".parse().unwrap();
+
+    pretty_assertions::assert_eq!(
+        "Bonjour le monde!",
+        fl!(loader, locale = &fr, "hello-world")
+    );
+    pretty_assertions::assert_eq!(
+        "Bonjour \u{2068}Bob\u{2069}!",
+        fl!(loader, locale = fr.clone(), "hello-arg", name = "Bob")
+    );
+    pretty_assertions::assert_eq!(
+        "Hello, attribute!",
+        fl!(loader, locale = &fr, "hello-attr", "text")
+    );
+    let mut args: HashMap<&str, &str> = HashMap::new();
+    args.insert("name", "Bob");
+    pretty_assertions::assert_eq!(
+        "Bonjour \u{2068}Bob\u{2069}!",
+        fl!(loader, locale = &fr, "hello-arg", args)
+    );
+    pretty_assertions::assert_eq!("Hello World!", fl!(loader, "hello-world"));
+}
+
+#[test]
+fn global_loader() {
+    let loaders: Vec<&'static FluentLanguageLoader> =
+        (0..2).map(|_| i18n_embed::init_global_loader!()).collect();
+    // The loader is only created once by each invocation of the macro.
+    assert!(std::ptr::eq(loaders[0], loaders[1]));
+    let loader = loaders[0];
+    loader.load_available_languages(&Localizations).unwrap();
+    assert!(std::ptr::eq(
+        loader,
+        i18n_embed::fluent::global_loader(loader.domain())
+    ));
+
+    let fr: unic_langid::LanguageIdentifier = "fr".parse().unwrap();
+
+    pretty_assertions::assert_eq!("Hello World!", fl!("hello-world"));
+    pretty_assertions::assert_eq!("Hello \u{2068}Bob\u{2069}!", fl!("hello-arg", name = "Bob"));
+    pretty_assertions::assert_eq!("Hello, attribute!", fl!(locale = &fr, "hello-attr", "text"));
+    pretty_assertions::assert_eq!(
+        "Bonjour \u{2068}Bob\u{2069}!",
+        fl!(locale = fr, "hello-arg", name = "Bob")
+    );
+}
+
+#[test]
+fn scope() {
+    let loader: FluentLanguageLoader = fluent_language_loader!();
+    loader
+        .load_languages(&Localizations, &[loader.fallback_language().clone()])
+        .unwrap();
+
+    let hello = fl_scope!(loader, "hello");
+    pretty_assertions::assert_eq!("hello", hello.prefix());
+    pretty_assertions::assert_eq!("Hello World!", hello.get("world"));
+    pretty_assertions::assert_eq!("Hello, attribute!", hello.get_attr("attr", "text"));
+
+    let mut args: HashMap<&str, &str> = HashMap::new();
+    args.insert("name", "Bob");
+    pretty_assertions::assert_eq!("Hello \u{2068}Bob\u{2069}!", hello.get_args("arg", args));
+    assert!(!hello.has("missing"));
+
+    // The whole message id is checked at compile time.
+    pretty_assertions::assert_eq!("Hello World!", fl_scope!(loader, "hello", "world"));
+    pretty_assertions::assert_eq!(
+        "Hello, attribute!",
+        fl_scope!(loader, "hello", "attr", "text")
+    );
+    pretty_assertions::assert_eq!(
+        "Hello \u{2068}Bob\u{2069}!",
+        fl_scope!(loader, "hello", "arg", name = "Bob")
+    );
+}
+
+#[test]
+fn namespace() {
+    let loader: FluentLanguageLoader = fluent_language_loader!();
+    loader
+        .load_languages(&Localizations, &[loader.fallback_language().clone()])
+        .unwrap();
+
+    pretty_assertions::assert_eq!("Settings", fl!(loader, "settings.title"));
+}
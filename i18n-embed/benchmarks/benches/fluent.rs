@@ -0,0 +1,121 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use fluent::FluentArgs;
+use i18n_embed::{fluent::FluentLanguageLoader, LanguageLoader};
+use i18n_embed_benchmarks::{de, en, CorpusAssets, CorpusSize, DOMAIN};
+
+/// A loader with the [de()] language loaded, falling back to [en()].
+fn loader(assets: &CorpusAssets) -> FluentLanguageLoader {
+    let loader = FluentLanguageLoader::new(DOMAIN, en());
+    loader.load_languages(assets, &[de()]).unwrap();
+    loader
+}
+
+fn get(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fluent/get");
+    for size in CorpusSize::ALL {
+        let loader = loader(&CorpusAssets::new(size));
+        // `label-0` is translated, `label-1` falls back to `en`.
+        for id in ["label-0", "label-1"] {
+            group.bench_with_input(BenchmarkId::new(id, size.name()), &id, |b, id| {
+                b.iter(|| loader.get(black_box(id)))
+            });
+        }
+        group.bench_with_input(
+            BenchmarkId::new("get_cow", size.name()),
+            &"label-0",
+            |b, id| b.iter(|| loader.get_cow(black_box(id))),
+        );
+    }
+    group.finish();
+}
+
+fn get_args(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fluent/get_args");
+    for size in CorpusSize::ALL {
+        let loader = loader(&CorpusAssets::new(size));
+        let mut name = FluentArgs::new();
+        name.set("name", "Ferris");
+        group.bench_with_input(
+            BenchmarkId::new("greeting-0", size.name()),
+            &name,
+            |b, args| b.iter(|| loader.get_args_fluent(black_box("greeting-0"), Some(args))),
+        );
+        let mut count = FluentArgs::new();
+        count.set("count", 5);
+        group.bench_with_input(
+            BenchmarkId::new("files-0", size.name()),
+            &count,
+            |b, args| b.iter(|| loader.get_args_fluent(black_box("files-0"), Some(args))),
+        );
+    }
+    group.finish();
+}
+
+fn select_languages(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fluent/select_languages");
+    for size in CorpusSize::ALL {
+        let loader = loader(&CorpusAssets::new(size));
+        let languages = [de()];
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size.name()),
+            &languages,
+            |b, languages| b.iter(|| loader.select_languages(black_box(languages))),
+        );
+    }
+    group.finish();
+}
+
+/// Compares localizing a message with a [LanguageScope] against a
+/// loader created using `select_languages()`, as in a server which
+/// localizes each request.
+///
+/// [LanguageScope]: i18n_embed::fluent::LanguageScope
+fn scope(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fluent/scope");
+    for size in CorpusSize::ALL {
+        let loader = loader(&CorpusAssets::new(size));
+        let languages = [de()];
+        group.bench_with_input(
+            BenchmarkId::new("select_languages", size.name()),
+            &languages,
+            |b, languages| {
+                b.iter(|| {
+                    loader
+                        .select_languages(black_box(languages))
+                        .get(black_box("label-0"))
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("scope", size.name()),
+            &languages,
+            |b, languages| b.iter(|| loader.scope(black_box(languages)).get(black_box("label-0"))),
+        );
+    }
+    group.finish();
+}
+
+fn load_languages(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fluent/load_languages");
+    for size in CorpusSize::ALL {
+        let assets = CorpusAssets::new(size);
+        let loader = FluentLanguageLoader::new(DOMAIN, en());
+        let languages = [de()];
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size.name()),
+            &languages,
+            |b, languages| b.iter(|| loader.load_languages(&assets, black_box(languages))),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    get,
+    get_args,
+    select_languages,
+    scope,
+    load_languages
+);
+criterion_main!(benches);
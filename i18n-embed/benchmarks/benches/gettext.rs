@@ -0,0 +1,31 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use i18n_embed::{gettext::GettextLanguageLoader, LanguageLoader};
+use i18n_embed_benchmarks::GettextAssets;
+use unic_langid::LanguageIdentifier;
+
+/// The domain of the gettext resources of the `i18n-embed` tests.
+const DOMAIN: &str = "i18n_embed";
+
+fn gettext(c: &mut Criterion) {
+    let ru: LanguageIdentifier = "ru".parse().unwrap();
+    let mut group = c.benchmark_group("gettext");
+
+    let loader = GettextLanguageLoader::new(DOMAIN, "en".parse().unwrap());
+    group.bench_function("load_languages", |b| {
+        b.iter(|| loader.load_languages(&GettextAssets, black_box(std::slice::from_ref(&ru))))
+    });
+
+    loader
+        .load_languages(&GettextAssets, std::slice::from_ref(&ru))
+        .unwrap();
+    group.bench_function("gettext", |b| {
+        b.iter(|| loader.gettext(black_box("only ru")))
+    });
+    group.bench_function("ngettext", |b| {
+        b.iter(|| loader.ngettext(black_box("{n} file"), "{n} files", black_box(3)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, gettext);
+criterion_main!(benches);
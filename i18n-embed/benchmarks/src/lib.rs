@@ -0,0 +1,122 @@
+//! Generated localization resources for the benchmarks of the
+//! `i18n-embed` language loaders, see the `benches` directory.
+//!
+//! Run the benchmarks with `cargo bench -p i18n-embed-benchmarks`,
+//! optionally with `--features cache` to compare the loaders with
+//! the message formatting cache enabled.
+//!
+//! To evaluate the performance of a change, save a baseline before
+//! making it with `cargo bench -p i18n-embed-benchmarks --
+//! --save-baseline before`, and compare against it afterwards with
+//! `cargo bench -p i18n-embed-benchmarks -- --baseline before`.
+
+use std::{borrow::Cow, collections::HashMap};
+
+use i18n_embed::I18nAssets;
+use unic_langid::LanguageIdentifier;
+
+/// The domain of the generated fluent resources.
+pub const DOMAIN: &str = "bench";
+
+/// The sizes of the generated fluent resources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorpusSize {
+    /// 10 messages of each kind.
+    Small,
+    /// 100 messages of each kind.
+    Medium,
+    /// 1000 messages of each kind.
+    Large,
+}
+
+impl CorpusSize {
+    /// All of the sizes, from smallest to largest.
+    pub const ALL: [CorpusSize; 3] = [CorpusSize::Small, CorpusSize::Medium, CorpusSize::Large];
+
+    /// The number of messages of each kind in the corpus.
+    pub fn messages(self) -> usize {
+        match self {
+            CorpusSize::Small => 10,
+            CorpusSize::Medium => 100,
+            CorpusSize::Large => 1000,
+        }
+    }
+
+    /// The name of the size, used as the parameter of the benchmarks.
+    pub fn name(self) -> &'static str {
+        match self {
+            CorpusSize::Small => "small",
+            CorpusSize::Medium => "medium",
+            CorpusSize::Large => "large",
+        }
+    }
+}
+
+/// The language of the fallback resources.
+pub fn en() -> LanguageIdentifier {
+    "en".parse().unwrap()
+}
+
+/// The language of the translated resources, which only contain
+/// every other message, so that half of the lookups fall back to
+/// [en()].
+pub fn de() -> LanguageIdentifier {
+    "de".parse().unwrap()
+}
+
+/// Generate a fluent resource with `n` messages of each kind: plain
+/// text (`label-{i}`), with an argument (`greeting-{i}`), and with a
+/// selector (`files-{i}`).
+fn ftl(language: &str, n: usize, step: usize) -> String {
+    let mut ftl = String::new();
+    for i in (0..n).step_by(step) {
+        ftl.push_str(&format!("label-{i} = Label {i} ({language})\n"));
+        ftl.push_str(&format!("greeting-{i} = Hello {{ $name }}! ({language})\n"));
+        ftl.push_str(&format!(
+            "files-{i} = {{ $count ->\n    [one] One file ({language})\n   *[other] {{ $count }} files ({language})\n}}\n"
+        ));
+    }
+    ftl
+}
+
+/// [I18nAssets] containing generated fluent resources for [en()] and
+/// [de()] in the [DOMAIN].
+#[derive(Debug)]
+pub struct CorpusAssets {
+    files: HashMap<String, Vec<u8>>,
+}
+
+impl CorpusAssets {
+    /// Generate the resources of the specified `size`.
+    pub fn new(size: CorpusSize) -> Self {
+        let n = size.messages();
+        let files = vec![
+            (format!("en/{DOMAIN}.ftl"), ftl("en", n, 1)),
+            (format!("de/{DOMAIN}.ftl"), ftl("de", n, 2)),
+        ]
+        .into_iter()
+        .map(|(path, ftl)| (path, ftl.into_bytes()))
+        .collect();
+        Self { files }
+    }
+}
+
+impl I18nAssets for CorpusAssets {
+    fn get_files(&self, file_path: &str) -> Vec<Cow<'_, [u8]>> {
+        self.files
+            .get(file_path)
+            .map(|file| Cow::Borrowed(file.as_slice()))
+            .into_iter()
+            .collect()
+    }
+
+    fn filenames_iter(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new(self.files.keys().cloned())
+    }
+}
+
+/// The gettext resources of the `i18n-embed` tests, compiled from
+/// `i18n-embed/i18n/po`.
+#[derive(rust_embed::RustEmbed)]
+#[folder = "../i18n/mo"]
+pub struct GettextAssets;
@@ -0,0 +1,5 @@
+greeting = Hello { $name }!
+installed-packs = { $count ->
+    [one] One language pack is installed.
+   *[other] { $count } language packs are installed.
+    }
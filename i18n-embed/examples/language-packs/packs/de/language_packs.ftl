@@ -0,0 +1,5 @@
+greeting = Hallo { $name }!
+installed-packs = { $count ->
+    [one] Ein Sprachpaket ist installiert.
+   *[other] { $count } Sprachpakete sind installiert.
+    }
@@ -0,0 +1,5 @@
+greeting = Bonjour { $name } !
+installed-packs = { $count ->
+    [one] Un pack de langue est installé.
+   *[other] { $count } packs de langue sont installés.
+    }
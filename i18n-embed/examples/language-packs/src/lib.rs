@@ -0,0 +1,155 @@
+//! An application which ships with English embedded, and downloads
+//! language packs for the other languages from its server at
+//! runtime. The packs are verified against the digests in the
+//! server's manifest (which is signed using the server's private key)
+//! before they are installed, and the loader is
+//! reloaded whenever a pack is installed, so an updated pack takes
+//! effect immediately.
+
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    net::{SocketAddr, TcpStream},
+    sync::Arc,
+};
+
+use i18n_embed::{
+    fluent::{fluent_language_loader, FluentLanguageLoader},
+    unic_langid::LanguageIdentifier,
+    AssetsMultiplexor, I18nAssets, I18nEmbedError, LanguageLoader, LanguagePackManager,
+    LanguagePackSource, Watcher,
+};
+use rust_embed::RustEmbed;
+
+pub mod server;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/"]
+struct Localizations;
+
+/// A [LanguagePackSource] which downloads the language packs from
+/// `http://{addr}/`.
+///
+/// This uses a minimal HTTP/1.0 client for the purposes of the
+/// example, a real application would use a HTTP client crate (e.g.
+/// `ureq` or `reqwest`) with TLS.
+#[derive(Debug)]
+pub struct HttpSource {
+    addr: SocketAddr,
+}
+
+impl HttpSource {
+    pub fn new(addr: SocketAddr) -> Self {
+        Self { addr }
+    }
+
+    fn get(&self, path: &str) -> std::io::Result<Vec<u8>> {
+        let mut stream = TcpStream::connect(self.addr)?;
+        write!(
+            stream,
+            "GET /{path} HTTP/1.0\r\nHost: {}\r\n\r\n",
+            self.addr
+        )?;
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response)?;
+
+        let header_end = response
+            .windows(4)
+            .position(|window| window == b"\r\n\r\n")
+            .ok_or_else(|| std::io::Error::other("Invalid HTTP response"))?;
+        let headers = String::from_utf8_lossy(&response[..header_end]);
+        let status_line = headers.lines().next().unwrap_or_default();
+        if status_line.split(' ').nth(1) != Some("200") {
+            return Err(std::io::Error::other(format!(
+                "Unexpected HTTP response: {status_line}"
+            )));
+        }
+        Ok(response[header_end + 4..].to_vec())
+    }
+}
+
+impl LanguagePackSource for HttpSource {
+    fn fetch(&self, path: &str) -> Result<Vec<u8>, I18nEmbedError> {
+        self.get(path)
+            .map_err(|error| I18nEmbedError::LanguagePackFetch(path.to_string(), Box::new(error)))
+    }
+}
+
+/// The localization of the application.
+pub struct App {
+    // Dropped first, to unsubscribe the reloading of the loader.
+    _watcher: Box<dyn Watcher + Send + Sync + 'static>,
+    loader: Arc<FluentLanguageLoader>,
+    assets: Arc<AssetsMultiplexor>,
+    manager: LanguagePackManager<HttpSource>,
+}
+
+impl App {
+    /// Load the embedded English, and prepare to install language
+    /// packs from the `source`, whose manifest is signed with the
+    /// private key of the `public_key`.
+    pub fn new(source: HttpSource, public_key: Vec<u8>) -> Result<Self, I18nEmbedError> {
+        let manager = LanguagePackManager::new(source).with_public_key(public_key);
+        // The installed packs take priority over the embedded
+        // languages, so that a pack can also update them.
+        let assets = Arc::new(AssetsMultiplexor::new([
+            Box::new(manager.assets()) as Box<dyn I18nAssets + Send + Sync>,
+            Box::new(Localizations),
+        ]));
+
+        let loader: Arc<FluentLanguageLoader> = Arc::new(fluent_language_loader!());
+        loader.load_fallback_language(&*assets)?;
+
+        // Reload the selected languages whenever a pack is installed.
+        let watcher = {
+            let loader = loader.clone();
+            let reload_assets = assets.clone();
+            assets.subscribe_changed(Arc::new(move || {
+                if let Err(error) = loader.reload(&*reload_assets) {
+                    eprintln!("Error reloading the languages: {error}");
+                }
+            }))?
+        };
+
+        Ok(Self {
+            _watcher: watcher,
+            loader,
+            assets,
+            manager,
+        })
+    }
+
+    pub fn loader(&self) -> &FluentLanguageLoader {
+        &self.loader
+    }
+
+    pub fn manager(&self) -> &LanguagePackManager<HttpSource> {
+        &self.manager
+    }
+
+    /// Download, verify and install the pack for the `language`.
+    pub fn install(&self, language: &LanguageIdentifier) -> Result<(), I18nEmbedError> {
+        self.manager.install(language)
+    }
+
+    /// Switch to the most suitable of the `requested_languages` which
+    /// are embedded or installed.
+    pub fn select(
+        &self,
+        requested_languages: &[LanguageIdentifier],
+    ) -> Result<Vec<LanguageIdentifier>, I18nEmbedError> {
+        i18n_embed::select(&*self.loader, &*self.assets, requested_languages)
+    }
+
+    pub fn greeting(&self, name: &str) -> String {
+        let mut args = HashMap::new();
+        args.insert("name", name);
+        self.loader.get_args("greeting", args)
+    }
+
+    pub fn installed_packs(&self) -> String {
+        let mut args = HashMap::new();
+        args.insert("count", self.manager.installed_languages().len());
+        self.loader.get_args("installed-packs", args)
+    }
+}
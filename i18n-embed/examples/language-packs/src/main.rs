@@ -0,0 +1,38 @@
+use std::path::Path;
+
+use language_packs::{server::PackServer, App, HttpSource};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let server = PackServer::start()?;
+    server.publish_dir(&Path::new(env!("CARGO_MANIFEST_DIR")).join("packs"))?;
+    println!("Serving language packs on http://{}", server.addr());
+
+    let app = App::new(HttpSource::new(server.addr()), server.public_key())?;
+    println!("{}", app.greeting("Ferris"));
+
+    let available = app.manager().available_languages()?;
+    for language in &available {
+        app.install(language)?;
+        println!("Installed the language pack for {language}.");
+    }
+    println!("{}", app.installed_packs());
+
+    for language in &available {
+        app.select(std::slice::from_ref(language))?;
+        println!("[{language}] {}", app.greeting("Ferris"));
+    }
+
+    // An updated pack takes effect as soon as it is installed.
+    let de = "de".parse()?;
+    app.select(std::slice::from_ref(&de))?;
+    server.publish("de/language_packs.ftl", "greeting = Servus { $name }!\n");
+    app.install(&de)?;
+    println!("[{de}] (updated) {}", app.greeting("Ferris"));
+
+    // A pack which was tampered with is rejected.
+    server.tamper("fr/language_packs.ftl", "greeting = Pwned { $name }!\n");
+    if let Err(error) = app.install(&"fr".parse()?) {
+        println!("{error}");
+    }
+    Ok(())
+}
@@ -0,0 +1,163 @@
+//! A minimal HTTP server which publishes language packs, standing in
+//! for the server of the application in this example. The manifest is
+//! signed whenever it changes, a real application would sign it once
+//! when the packs are released, keeping the private key offline.
+
+use std::{
+    collections::BTreeMap,
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use i18n_embed::{LANGUAGE_PACK_MANIFEST, LANGUAGE_PACK_SIGNATURE};
+use ring::{
+    rand::SystemRandom,
+    signature::{Ed25519KeyPair, KeyPair},
+};
+use sha2::{Digest, Sha256};
+
+#[derive(Default)]
+struct Published {
+    files: BTreeMap<String, Vec<u8>>,
+    manifest: String,
+    signature: Vec<u8>,
+}
+
+/// Serves the published language packs, along with their manifest.
+#[derive(Clone)]
+pub struct PackServer {
+    addr: SocketAddr,
+    published: Arc<Mutex<Published>>,
+    key_pair: Arc<Ed25519KeyPair>,
+}
+
+impl PackServer {
+    /// Start serving on a free local port, in a background thread.
+    pub fn start() -> std::io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0))?;
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+            .map_err(|_| std::io::Error::other("Unable to generate the signing key"))?;
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref())
+            .map_err(|_| std::io::Error::other("Unable to read the signing key"))?;
+        let server = Self {
+            addr: listener.local_addr()?,
+            published: Arc::default(),
+            key_pair: Arc::new(key_pair),
+        };
+        let published = server.published.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if let Err(error) = respond(stream, &published) {
+                    eprintln!("Error responding to a request: {error}");
+                }
+            }
+        });
+        Ok(server)
+    }
+
+    /// Publish all of the language packs in the `packs_dir`, which
+    /// contains a directory for each language.
+    pub fn publish_dir(&self, packs_dir: &Path) -> std::io::Result<()> {
+        for language_dir in std::fs::read_dir(packs_dir)? {
+            let language_dir = language_dir?;
+            for file in std::fs::read_dir(language_dir.path())? {
+                let file = file?;
+                let path = format!(
+                    "{}/{}",
+                    language_dir.file_name().to_string_lossy(),
+                    file.file_name().to_string_lossy()
+                );
+                self.publish(&path, std::fs::read(file.path())?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Publish the `contents` of the file at `path`, updating the
+    /// manifest with its digest and signing it.
+    pub fn publish(&self, path: &str, contents: impl Into<Vec<u8>>) {
+        let mut published = self.published.lock().unwrap();
+        published.files.insert(path.to_string(), contents.into());
+        published.manifest = published
+            .files
+            .iter()
+            .map(|(path, contents)| {
+                let digest: String = Sha256::digest(contents)
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect();
+                format!("{digest}  {path}\n")
+            })
+            .collect();
+        published.signature = self
+            .key_pair
+            .sign(published.manifest.as_bytes())
+            .as_ref()
+            .to_vec();
+    }
+
+    /// Replace the `contents` of the file at `path` without updating
+    /// the manifest, as if they were tampered with in transit.
+    pub fn tamper(&self, path: &str, contents: impl Into<Vec<u8>>) {
+        let mut published = self.published.lock().unwrap();
+        published.files.insert(path.to_string(), contents.into());
+    }
+
+    /// Replace the `manifest` without signing it, as if it was
+    /// tampered with in transit.
+    pub fn tamper_manifest(&self, manifest: &str) {
+        self.published.lock().unwrap().manifest = manifest.to_string();
+    }
+
+    /// The public key of the key which the manifest is signed with,
+    /// which is embedded in the application.
+    pub fn public_key(&self) -> Vec<u8> {
+        self.key_pair.public_key().as_ref().to_vec()
+    }
+
+    /// The address which the server is listening on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+fn respond(stream: TcpStream, published: &Mutex<Published>) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Skip the headers.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let path = request_line
+        .strip_prefix("GET /")
+        .and_then(|rest| rest.split(' ').next())
+        .unwrap_or_default();
+    let body = {
+        let published = published.lock().unwrap();
+        if path == LANGUAGE_PACK_MANIFEST {
+            Some(published.manifest.clone().into_bytes())
+        } else if path == LANGUAGE_PACK_SIGNATURE {
+            Some(published.signature.clone())
+        } else {
+            published.files.get(path).cloned()
+        }
+    };
+
+    let mut stream = reader.into_inner();
+    match body {
+        Some(body) => {
+            write!(
+                stream,
+                "HTTP/1.0 200 OK\r\nContent-Length: {}\r\n\r\n",
+                body.len()
+            )?;
+            stream.write_all(&body)
+        }
+        None => stream.write_all(b"HTTP/1.0 404 Not Found\r\nContent-Length: 0\r\n\r\n"),
+    }
+}
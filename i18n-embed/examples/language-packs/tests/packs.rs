@@ -0,0 +1,108 @@
+use std::path::Path;
+
+use i18n_embed::{unic_langid::LanguageIdentifier, I18nEmbedError, LanguageLoader};
+use language_packs::{server::PackServer, App, HttpSource};
+
+fn start() -> (PackServer, App) {
+    let server = PackServer::start().unwrap();
+    server
+        .publish_dir(&Path::new(env!("CARGO_MANIFEST_DIR")).join("packs"))
+        .unwrap();
+    let app = App::new(HttpSource::new(server.addr()), server.public_key()).unwrap();
+    (server, app)
+}
+
+/// Remove the unicode isolation marks around the placeables.
+fn strip_isolation(message: String) -> String {
+    message.replace(['\u{2068}', '\u{2069}'], "")
+}
+
+fn language(language: &str) -> LanguageIdentifier {
+    language.parse().unwrap()
+}
+
+#[test]
+fn install_and_switch_language() {
+    let (_server, app) = start();
+    assert_eq!("Hello Ferris!", strip_isolation(app.greeting("Ferris")));
+
+    let mut available = app.manager().available_languages().unwrap();
+    available.sort_by_key(ToString::to_string);
+    assert_eq!(vec![language("de"), language("fr")], available);
+
+    // Not installed yet, so it falls back to the embedded English.
+    assert_eq!(vec![language("en")], app.select(&[language("de")]).unwrap());
+
+    app.install(&language("de")).unwrap();
+    app.install(&language("fr")).unwrap();
+    assert_eq!(
+        vec![language("de"), language("fr")],
+        app.manager().installed_languages()
+    );
+
+    app.select(&[language("de")]).unwrap();
+    assert_eq!(language("de"), app.loader().current_language());
+    assert_eq!("Hallo Ferris!", strip_isolation(app.greeting("Ferris")));
+    assert_eq!(
+        "2 Sprachpakete sind installiert.",
+        strip_isolation(app.installed_packs())
+    );
+
+    app.select(&[language("fr")]).unwrap();
+    assert_eq!("Bonjour Ferris !", strip_isolation(app.greeting("Ferris")));
+}
+
+#[test]
+fn updated_pack_is_reloaded() {
+    let (server, app) = start();
+    app.install(&language("de")).unwrap();
+    app.select(&[language("de")]).unwrap();
+
+    server.publish("de/language_packs.ftl", "greeting = Servus { $name }!\n");
+    app.install(&language("de")).unwrap();
+    assert_eq!("Servus Ferris!", strip_isolation(app.greeting("Ferris")));
+}
+
+#[test]
+fn tampered_pack_is_rejected() {
+    let (server, app) = start();
+    server.tamper("de/language_packs.ftl", "greeting = Pwned { $name }!\n");
+
+    let error = app.install(&language("de")).unwrap_err();
+    assert!(
+        matches!(error, I18nEmbedError::LanguagePackChecksumMismatch(ref path, _, _) if path == "de/language_packs.ftl"),
+        "{}",
+        error
+    );
+    assert!(app.manager().installed_languages().is_empty());
+}
+
+#[test]
+fn tampered_manifest_is_rejected() {
+    let (server, app) = start();
+    // The digest of the tampered pack, which would otherwise be
+    // accepted.
+    server.tamper("de/language_packs.ftl", "greeting = Pwned!\n");
+    server.tamper_manifest(
+        "b44167ba0c12451f89559b45810fd4f6ca1a48d0eac64b1c4df4b84eb0905347  de/language_packs.ftl\n",
+    );
+
+    let error = app.install(&language("de")).unwrap_err();
+    assert!(
+        matches!(error, I18nEmbedError::LanguagePackSignatureInvalid),
+        "{}",
+        error
+    );
+    assert!(app.manager().installed_languages().is_empty());
+}
+
+#[test]
+fn unavailable_pack() {
+    let (_server, app) = start();
+    let error = app.install(&language("ja")).unwrap_err();
+    assert!(
+        matches!(error, I18nEmbedError::LanguagePackNotAvailable(ref ja) if *ja == language("ja")),
+        "{}",
+        error
+    );
+}
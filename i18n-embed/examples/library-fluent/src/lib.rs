@@ -2,7 +2,6 @@ use i18n_embed::{
     fluent::{fluent_language_loader, FluentLanguageLoader},
     DefaultLocalizer, LanguageLoader, RustEmbedNotifyAssets,
 };
-use i18n_embed_fl::fl;
 use once_cell::sync::Lazy;
 use rust_embed::RustEmbed;
 
@@ -39,7 +38,7 @@ macro_rules! fl {
 /// Get the hello world statement in whatever the currently selected
 /// localization is.
 pub fn hello_world() -> String {
-    fl!("hello-world")
+    fl!("hello-world").into()
 }
 
 // Get the `Localizer` to be used for localizing this library.
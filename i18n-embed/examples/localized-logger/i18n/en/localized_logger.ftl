@@ -0,0 +1,6 @@
+app-started = Application started
+files-found = Found { $count ->
+    [one] one file
+   *[other] { $count } files
+    }
+disk-almost-full = The disk is { $percent }% full
@@ -0,0 +1,6 @@
+app-started = Application démarrée
+files-found = { $count ->
+    [one] Un fichier trouvé
+   *[other] { $count } fichiers trouvés
+    }
+disk-almost-full = Le disque est plein à { $percent } %
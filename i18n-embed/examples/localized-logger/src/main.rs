@@ -0,0 +1,45 @@
+use std::fs::File;
+
+use i18n_embed::{
+    fluent::{fluent_language_loader, FluentLanguageLoader},
+    DesktopLanguageRequester, LocalizedLogger,
+};
+use once_cell::sync::Lazy;
+use rust_embed::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/"]
+struct Localizations;
+
+static LANGUAGE_LOADER: Lazy<FluentLanguageLoader> = Lazy::new(|| fluent_language_loader!());
+
+fn main() {
+    let requested_languages = DesktopLanguageRequester::requested_languages();
+    if let Err(error) = i18n_embed::select(&*LANGUAGE_LOADER, &Localizations, &requested_languages)
+    {
+        eprintln!("Error while loading languages for localized_logger {error}");
+    }
+
+    // The log file keeps the original English messages, including
+    // the debug messages which are not shown to the user.
+    let log_file = File::create("localized-logger.log").expect("Unable to create the log file");
+    let file_logger = env_logger::Builder::new()
+        .filter_level(log::LevelFilter::Debug)
+        .target(env_logger::Target::Pipe(Box::new(log_file)))
+        .build();
+
+    // The log pane shows the messages localized into the selected
+    // language.
+    LocalizedLogger::new(&LANGUAGE_LOADER, |record| {
+        println!("[{0}] {1}", record.level, record.message)
+    })
+    .with_logger(file_logger)
+    .init()
+    .expect("Unable to set the logger");
+
+    log::info!(event = "app-started"; "Application started");
+    log::debug!("Scanning the current directory");
+    let count = std::fs::read_dir(".").map_or(0, |entries| entries.count());
+    log::info!(event = "files-found", count = count; "Found {count} files");
+    log::warn!(event = "disk-almost-full", percent = 92; "The disk is 92% full");
+}
@@ -42,6 +42,16 @@ pub fn gettext_language_loader(_: proc_macro::TokenStream) -> proc_macro::TokenS
         })
         .i18n_config_file;
 
+    if let Some(warning) = missing_config_warning("gettext_language_loader", &config_file_path) {
+        let gen = quote::quote! {
+            #i18n_embed_crate_ident::gettext::GettextLanguageLoader::new(
+                module_path!(),
+                #MISSING_CONFIG_FALLBACK_LANGUAGE.parse().unwrap(),
+            )
+        };
+        return with_warning(&warning, gen).into();
+    }
+
     let config = i18n_config::I18nConfig::from_file(&config_file_path).unwrap_or_else(|err| {
         panic!(
             "gettext_language_loader!() had a problem reading i18n config file {0:?}: {1}",
@@ -62,14 +72,26 @@ pub fn gettext_language_loader(_: proc_macro::TokenStream) -> proc_macro::TokenS
         proc_macro2::Span::call_site(),
     );
 
-    let gen = quote::quote! {
-        #i18n_embed_crate_ident::gettext::GettextLanguageLoader::new(
-            module_path!(),
-            #fallback_language.parse().unwrap(),
-        )
+    let gen = match language_aliases(&i18n_embed_crate_ident, &config.language_aliases) {
+        Some(language_aliases) => quote::quote! {
+            {
+                let loader = #i18n_embed_crate_ident::gettext::GettextLanguageLoader::new(
+                    module_path!(),
+                    #fallback_language.parse().unwrap(),
+                );
+                loader.set_language_aliases(#language_aliases);
+                loader
+            }
+        },
+        None => quote::quote! {
+            #i18n_embed_crate_ident::gettext::GettextLanguageLoader::new(
+                module_path!(),
+                #fallback_language.parse().unwrap(),
+            )
+        },
     };
 
-    gen.into()
+    track_file(&config_file_path, gen).into()
 }
 
 /// A procedural macro to create a new `FluentLanguageLoader` using
@@ -84,9 +106,286 @@ pub fn gettext_language_loader(_: proc_macro::TokenStream) -> proc_macro::TokenS
 /// use i18n_embed::fluent::{fluent_language_loader, FluentLanguageLoader};
 /// let my_language_loader: FluentLanguageLoader = fluent_language_loader!();
 /// ```
+///
+/// ## Parameters
+///
+/// The configuration can be overridden, or supplied for crates
+/// without an `i18n.toml` (e.g. quick prototypes), using the
+/// following optional parameters:
+///
+/// + `domain`: the domain of the loader, instead of the domain in the
+///   configuration (or the name of the crate).
+/// + `fallback_language`: the fallback language of the loader. When
+///   this is specified, the `i18n.toml` configuration is not read.
+/// + `default_fallback`: the fallback language of the loader if the
+///   crate has no `i18n.toml` configuration, instead of failing to
+///   compile.
+///
+/// ```ignore
+/// let my_language_loader: FluentLanguageLoader =
+///     fluent_language_loader!(domain = "my_app", default_fallback = "en");
+/// ```
+///
+/// The `i18n.toml` configuration remains the recommended way to
+/// configure the loader, because it is shared with the `fl!()` macro
+/// and the `cargo i18n` tool.
+///
+/// When the `I18N_EMBED_SKIP_CHECKS` environment variable is set (e.g.
+/// for `trybuild` tests), or when the crate is documented on docs.rs,
+/// a missing `i18n.toml` configuration doesn't fail the compilation:
+/// the loader uses the `default_fallback` or `en-US` fallback
+/// language instead, with a warning.
 #[proc_macro]
 #[cfg(feature = "fluent-system")]
-pub fn fluent_language_loader(_: proc_macro::TokenStream) -> proc_macro::TokenStream {
+pub fn fluent_language_loader(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let params = syn::parse_macro_input!(input as LoaderParams);
+    let FluentLoaderConfig {
+        i18n_embed_crate_ident,
+        domain,
+        fallback_language,
+        path_template,
+        language_aliases,
+        use_isolating,
+        config_file,
+        warning,
+    } = fluent_loader_config("fluent_language_loader", params);
+
+    let fallback_language = syn::LitStr::new(
+        &fallback_language.to_string(),
+        proc_macro2::Span::call_site(),
+    );
+
+    let set_path_template = path_template.map(|path_template| {
+        quote::quote! {
+            loader.set_path_template(Some(
+                #i18n_embed_crate_ident::PathTemplate::new(#path_template).unwrap(),
+            ));
+        }
+    });
+    let set_language_aliases = language_aliases.map(|language_aliases| {
+        quote::quote! {
+            loader.set_language_aliases(#language_aliases);
+        }
+    });
+
+    let set_use_isolating = (!use_isolating).then(|| {
+        quote::quote! {
+            loader.set_use_isolating(false);
+        }
+    });
+
+    let gen = if set_path_template.is_some()
+        || set_language_aliases.is_some()
+        || set_use_isolating.is_some()
+    {
+        quote::quote! {
+            {
+                let loader = #i18n_embed_crate_ident::fluent::FluentLanguageLoader::new(
+                    #domain,
+                    #fallback_language.parse().unwrap(),
+                );
+                #set_path_template
+                #set_language_aliases
+                #set_use_isolating
+                loader
+            }
+        }
+    } else {
+        quote::quote! {
+            #i18n_embed_crate_ident::fluent::FluentLanguageLoader::new(
+                #domain,
+                #fallback_language.parse().unwrap(),
+            )
+        }
+    };
+
+    match (config_file, warning) {
+        (Some(config_file), _) => track_file(&config_file, gen).into(),
+        (None, Some(warning)) => with_warning(&warning, gen).into(),
+        (None, None) => gen.into(),
+    }
+}
+
+/// A procedural macro to create a new `FluentLanguageLoader` using
+/// the current crate's `i18n.toml` configuration, and domain, in a
+/// `const` context (using `FluentLanguageLoader::new_const()`). This
+/// allows the loader to be stored in a `static` without a wrapper
+/// such as `OnceLock`. The `fallback_language` cannot have any
+/// variants. It accepts the same parameters as
+/// `fluent_language_loader!()`.
+///
+/// ⚠️ *This API requires the following crate features to be
+/// activated: `fluent-system`.*
+///
+/// ## Example
+///
+/// ```ignore
+/// use i18n_embed::fluent::{static_loader, FluentLanguageLoader};
+/// static LANGUAGE_LOADER: FluentLanguageLoader = static_loader!();
+/// ```
+#[proc_macro]
+#[cfg(feature = "fluent-system")]
+pub fn static_loader(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let params = syn::parse_macro_input!(input as LoaderParams);
+    let FluentLoaderConfig {
+        i18n_embed_crate_ident,
+        domain,
+        fallback_language,
+        path_template,
+        language_aliases,
+        use_isolating,
+        config_file,
+        warning,
+    } = fluent_loader_config("static_loader", params);
+
+    let (language, script, region, variants) = fallback_language.clone().into_parts();
+    if !variants.is_empty() {
+        panic!(
+            "static_loader!() does not support the fallback language \"{0}\" because it has variants",
+            fallback_language
+        )
+    }
+    let language: u64 = Option::<u64>::from(language).unwrap_or_else(|| {
+        panic!(
+            "static_loader!() does not support the undetermined fallback language \"{0}\"",
+            fallback_language
+        )
+    });
+    let script = match script.map(u32::from) {
+        Some(script) => quote::quote! {
+            Some(#i18n_embed_crate_ident::unic_langid::subtags::Script::from_raw_unchecked(#script))
+        },
+        None => quote::quote! { None },
+    };
+    let region = match region.map(u32::from) {
+        Some(region) => quote::quote! {
+            Some(#i18n_embed_crate_ident::unic_langid::subtags::Region::from_raw_unchecked(#region))
+        },
+        None => quote::quote! { None },
+    };
+
+    let layout = if path_template.is_some() || language_aliases.is_some() {
+        let path_template = match path_template {
+            Some(path_template) => quote::quote! {
+                Some(#i18n_embed_crate_ident::PathTemplate::new_const(#path_template))
+            },
+            None => quote::quote! { None },
+        };
+        let language_aliases = language_aliases
+            .unwrap_or_else(|| quote::quote! { #i18n_embed_crate_ident::LanguageAliases::new() });
+        Some(quote::quote! { , #path_template, #language_aliases })
+    } else {
+        None
+    };
+    let constructor = match layout {
+        Some(_) => quote::quote!(new_const_with_layout),
+        None => quote::quote!(new_const),
+    };
+    let with_use_isolating = (!use_isolating).then(|| quote::quote! { .with_use_isolating(false) });
+
+    // The subtags were validated when parsing the language, so they
+    // can be used unchecked (this is how `unic_langid::langid!()`
+    // creates a `LanguageIdentifier` in a `const` context).
+    let gen = quote::quote! {
+        #i18n_embed_crate_ident::fluent::FluentLanguageLoader::#constructor(#domain, {
+            #[allow(unsafe_code)]
+            let fallback_language = unsafe {
+                #i18n_embed_crate_ident::unic_langid::LanguageIdentifier::from_raw_parts_unchecked(
+                    #i18n_embed_crate_ident::unic_langid::subtags::Language::from_raw_unchecked(#language),
+                    #script,
+                    #region,
+                    None,
+                )
+            };
+            fallback_language
+        } #layout)#with_use_isolating
+    };
+
+    match (config_file, warning) {
+        (Some(config_file), _) => track_file(&config_file, gen).into(),
+        (None, Some(warning)) => with_warning(&warning, gen).into(),
+        (None, None) => gen.into(),
+    }
+}
+
+/// The optional parameters of the fluent loader macros, see
+/// `fluent_language_loader!()`.
+#[cfg(feature = "fluent-system")]
+#[derive(Default)]
+struct LoaderParams {
+    domain: Option<String>,
+    fallback_language: Option<unic_langid::LanguageIdentifier>,
+    default_fallback: Option<unic_langid::LanguageIdentifier>,
+}
+
+#[cfg(feature = "fluent-system")]
+impl syn::parse::Parse for LoaderParams {
+    fn parse(input: syn::parse::ParseStream<'_>) -> syn::Result<Self> {
+        let mut params = LoaderParams::default();
+        let name_values =
+            syn::punctuated::Punctuated::<syn::MetaNameValue, syn::Token![,]>::parse_terminated(
+                input,
+            )?;
+        for name_value in name_values {
+            let value = match &name_value.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(value),
+                    ..
+                }) => value,
+                value => return Err(syn::Error::new_spanned(value, "expected a string literal")),
+            };
+            let language = || {
+                value.value().parse().map_err(|error| {
+                    syn::Error::new_spanned(value, format!("invalid language identifier: {error}"))
+                })
+            };
+            let name = name_value.path.get_ident().map(ToString::to_string);
+            match name.as_deref() {
+                Some("domain") => params.domain = Some(value.value()),
+                Some("fallback_language") => params.fallback_language = Some(language()?),
+                Some("default_fallback") => params.default_fallback = Some(language()?),
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        &name_value.path,
+                        "unknown parameter, expected one of: \
+                        `domain`, `fallback_language`, `default_fallback`",
+                    ))
+                }
+            }
+        }
+        Ok(params)
+    }
+}
+
+/// The configuration of a fluent loader created by one of the fluent
+/// loader macros, see [fluent_loader_config()].
+#[cfg(feature = "fluent-system")]
+struct FluentLoaderConfig {
+    /// The identifier of the `i18n_embed` crate.
+    i18n_embed_crate_ident: syn::Ident,
+    domain: syn::LitStr,
+    fallback_language: unic_langid::LanguageIdentifier,
+    /// The path template, if configured.
+    path_template: Option<syn::LitStr>,
+    /// An expression creating the `LanguageAliases`, if any are
+    /// configured.
+    language_aliases: Option<proc_macro2::TokenStream>,
+    /// Whether to insert Unicode Directionality Isolation Marks
+    /// around placeables.
+    use_isolating: bool,
+    /// The path to the `i18n.toml` configuration, if it was read.
+    config_file: Option<std::path::PathBuf>,
+    /// The warning to generate alongside the loader, if it was created
+    /// without its missing configuration, see
+    /// [missing_config_warning()].
+    warning: Option<String>,
+}
+
+/// Read the current crate's `i18n.toml` configuration for one of the
+/// fluent loader macros named `macro_name`, taking the `params` into
+/// account.
+#[cfg(feature = "fluent-system")]
+fn fluent_loader_config(macro_name: &str, params: LoaderParams) -> FluentLoaderConfig {
     let manifest = find_crate::Manifest::new().expect("Error reading Cargo.toml");
     let current_crate_package_name = manifest
         .crate_package()
@@ -106,18 +405,58 @@ pub fn fluent_language_loader(_: proc_macro::TokenStream) -> proc_macro::TokenSt
     let i18n_embed_crate_ident =
         syn::Ident::new(&i18n_embed_crate_name, proc_macro2::Span::call_site());
 
-    let config_file_path = i18n_config::locate_crate_paths()
-        .unwrap_or_else(|error| {
-            panic!(
-                "fluent_language_loader!() is unable to locate i18n config file: {}",
-                error
-            )
-        })
-        .i18n_config_file;
+    let crate_paths = i18n_config::locate_crate_paths().unwrap_or_else(|error| {
+        panic!(
+            "{0}!() is unable to locate i18n config file: {1}",
+            macro_name, error
+        )
+    });
+    let config_file_path = crate_paths.i18n_config_file;
+
+    // The configuration is not required when the fallback language is
+    // supplied as a parameter.
+    let LoaderParams {
+        domain,
+        fallback_language,
+        default_fallback,
+    } = params;
+    let fallback_language =
+        fallback_language.or_else(|| default_fallback.filter(|_| !config_file_path.exists()));
+    let (fallback_language, warning) = match fallback_language {
+        Some(fallback_language) => (Some(fallback_language), None),
+        None => match missing_config_warning(macro_name, &config_file_path) {
+            Some(warning) => (
+                Some(
+                    MISSING_CONFIG_FALLBACK_LANGUAGE
+                        .parse()
+                        .expect("expected a valid language identifier"),
+                ),
+                Some(warning),
+            ),
+            None => (None, None),
+        },
+    };
+    if let Some(fallback_language) = fallback_language {
+        let domain_str = domain
+            .or(crate_paths.i18n_config_crate_name)
+            .unwrap_or(current_crate_package_name);
+        let domain = syn::LitStr::new(&domain_str, proc_macro2::Span::call_site());
+        return FluentLoaderConfig {
+            i18n_embed_crate_ident,
+            domain,
+            fallback_language,
+            path_template: None,
+            language_aliases: None,
+            use_isolating: true,
+            config_file: None,
+            warning,
+        };
+    }
 
     let config = i18n_config::I18nConfig::from_file(&config_file_path).unwrap_or_else(|err| {
         panic!(
-            "fluent_language_loader!() had a problem reading i18n config file {0:?}: {1}",
+            "{0}!() had a problem reading i18n config file {1:?}: {2}",
+            macro_name,
             std::fs::canonicalize(&config_file_path).unwrap_or_else(|_| config_file_path.clone()),
             err
         )
@@ -125,28 +464,137 @@ pub fn fluent_language_loader(_: proc_macro::TokenStream) -> proc_macro::TokenSt
 
     if config.fluent.is_none() {
         panic!(
-            "fluent_language_loader!() had a problem parsing i18n config file {0:?}: there is no `[fluent]` section",
+            "{0}!() had a problem parsing i18n config file {1:?}: there is no `[fluent]` section",
+            macro_name,
             std::fs::canonicalize(&config_file_path).unwrap_or(config_file_path)
         )
     }
 
-    let fallback_language = syn::LitStr::new(
-        &config.fallback_language.to_string(),
-        proc_macro2::Span::call_site(),
-    );
-
-    let domain_str = config
-        .fluent
-        .and_then(|f| f.domain)
+    let domain_str = domain
+        .or_else(|| config.fluent.as_ref().and_then(|f| f.domain.clone()))
+        .or(crate_paths.i18n_config_crate_name)
         .unwrap_or(current_crate_package_name);
     let domain = syn::LitStr::new(&domain_str, proc_macro2::Span::call_site());
 
-    let gen = quote::quote! {
-        #i18n_embed_crate_ident::fluent::FluentLanguageLoader::new(
-            #domain,
-            #fallback_language.parse().unwrap(),
-        )
-    };
+    let use_isolating = config
+        .fluent
+        .as_ref()
+        .is_none_or(|fluent_config| fluent_config.use_isolating);
+    let path_template = config
+        .fluent
+        .and_then(|fluent_config| fluent_config.path_template)
+        .map(|path_template| {
+            if path_template.matches("{language}").count() != 1 {
+                panic!(
+                    "{0}!() had a problem parsing i18n config file {1:?}: the `path_template` \"{2}\" needs to contain the \"{{language}}\" placeholder exactly once",
+                    macro_name,
+                    std::fs::canonicalize(&config_file_path)
+                        .unwrap_or_else(|_| config_file_path.clone()),
+                    path_template
+                )
+            }
+            syn::LitStr::new(&path_template, proc_macro2::Span::call_site())
+        });
+
+    let language_aliases = language_aliases(&i18n_embed_crate_ident, &config.language_aliases);
+
+    FluentLoaderConfig {
+        i18n_embed_crate_ident,
+        domain,
+        fallback_language: config.fallback_language,
+        path_template,
+        language_aliases,
+        use_isolating,
+        config_file: Some(config_file_path),
+        warning: None,
+    }
+}
+
+/// The fallback language of the loaders created by the loader macros
+/// without their missing `i18n.toml` configuration, see
+/// [missing_config_warning()].
+#[cfg(any(feature = "fluent-system", feature = "gettext-system"))]
+const MISSING_CONFIG_FALLBACK_LANGUAGE: &str = "en-US";
+
+/// The loader macros (named `macro_name`) fail to compile when the
+/// `i18n.toml` configuration at `config_file_path` is missing, unless
+/// the compile time checks are skipped (see
+/// `i18n_config::skip_compile_time_checks()`) or the crate is being
+/// documented on docs.rs. In that case the loader is created with the
+/// [MISSING_CONFIG_FALLBACK_LANGUAGE] instead, and this returns the
+/// warning to generate alongside it.
+#[cfg(any(feature = "fluent-system", feature = "gettext-system"))]
+fn missing_config_warning(macro_name: &str, config_file_path: &std::path::Path) -> Option<String> {
+    if config_file_path.exists()
+        || !(i18n_config::skip_compile_time_checks() || i18n_config::is_docs_rs())
+    {
+        return None;
+    }
+    Some(format!(
+        "{macro_name}!() was unable to read the i18n config file {config_file_path:?}, \
+        and uses the fallback language \"{MISSING_CONFIG_FALLBACK_LANGUAGE}\" instead"
+    ))
+}
+
+/// Generate a `warning` alongside the expression generated by a
+/// macro (`gen`). Warnings emitted by procedural macros are ignored
+/// on stable Rust, so this uses the deprecation of an item generated
+/// alongside the expression instead.
+#[cfg(any(feature = "fluent-system", feature = "gettext-system"))]
+fn with_warning(warning: &str, gen: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    quote::quote! {
+        {
+            const _: () = {
+                #[deprecated(note = #warning)]
+                struct MissingI18nConfig;
+                let _ = MissingI18nConfig;
+            };
+            #gen
+        }
+    }
+}
 
-    gen.into()
+/// An expression creating the `LanguageAliases` for the
+/// `language_aliases` in the i18n config, or `None` if there are
+/// none. It can be used in a `const` context.
+#[cfg(any(feature = "fluent-system", feature = "gettext-system"))]
+fn language_aliases(
+    i18n_embed_crate_ident: &syn::Ident,
+    language_aliases: &std::collections::BTreeMap<
+        unic_langid::LanguageIdentifier,
+        unic_langid::LanguageIdentifier,
+    >,
+) -> Option<proc_macro2::TokenStream> {
+    if language_aliases.is_empty() {
+        return None;
+    }
+    let aliases = language_aliases.iter().map(|(alias, language)| {
+        let alias = alias.to_string();
+        let language = language.to_string();
+        quote::quote! { (#alias, #language) }
+    });
+    Some(quote::quote! {
+        #i18n_embed_crate_ident::LanguageAliases::new_const(&[#(#aliases),*])
+    })
+}
+
+/// Make the compiler track the file at `path` in the expression
+/// generated by a macro (`gen`), so that the crate invoking the macro
+/// is recompiled when the file changes. This uses `include_bytes!()`
+/// because `proc_macro::tracked_path` is not yet stable.
+#[cfg(any(feature = "fluent-system", feature = "gettext-system"))]
+fn track_file(path: &std::path::Path, gen: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let path = match std::fs::canonicalize(path) {
+        Ok(path) => path,
+        Err(_) => return gen,
+    };
+    match path.to_str() {
+        Some(path) => quote::quote! {
+            {
+                const _: &[u8] = include_bytes!(#path);
+                #gen
+            }
+        },
+        None => gen,
+    }
 }
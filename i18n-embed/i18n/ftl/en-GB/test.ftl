@@ -7,3 +7,4 @@ with-attr = Hello
     .attr = World!
 with-attr-and-args = Hello
     .who = {$name}!
+relative-time-now = just now
@@ -0,0 +1,7 @@
+# Messages using ICU MessageFormat style placeholders.
+greeting = Hello {name}!
+app-name = Example
+about = About {app-name}
+total = Total: {amount, number}
+fluent = Hello { $name }!
+items = {count, plural, one {# item} other {# items}}
@@ -26,3 +26,11 @@ with-attr = Hello
     .attr = World (US version)!
 with-attr-and-args = Hello
     .who = {$name}!
+welcome-email =
+    .subject = Welcome, { $name }!
+    .body = Hi { $name }, you have { $count ->
+        [one] one new message
+       *[other] { $count } new messages
+    }.
+total = Total: { CURRENCY($amount, $currency) }
+region-info = { $region } { $timezone }
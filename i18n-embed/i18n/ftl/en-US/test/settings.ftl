@@ -0,0 +1,2 @@
+title = Settings
+greeting = Hello from { title }!
@@ -0,0 +1,2 @@
+volume = Volume
+    .tooltip = Adjust the volume
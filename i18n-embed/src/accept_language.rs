@@ -0,0 +1,65 @@
+use unic_langid::LanguageIdentifier;
+
+/// Parse the value of an HTTP `Accept-Language` header (e.g.
+/// `fr-CH, fr;q=0.9, en;q=0.8, *;q=0.5`) into the requested
+/// languages, in order of preference, for use with
+/// [select()](crate::select()) or
+/// `FluentLanguageLoader::select_languages_negotiate()`.
+///
+/// The languages are ordered by their quality values (`q`), keeping
+/// the order of the header for languages with the same quality.
+/// Languages with a quality of `0` (which are not acceptable), the
+/// `*` wildcard, duplicates, and entries with an invalid language tag
+/// or quality value are skipped.
+///
+/// ```
+/// use i18n_embed::parse_accept_language;
+///
+/// let languages = parse_accept_language("en;q=0.8, fr-CH, de;q=0, fr;q=0.9, *;q=0.5");
+/// assert_eq!(
+///     vec!["fr-CH", "fr", "en"],
+///     languages.iter().map(ToString::to_string).collect::<Vec<_>>()
+/// );
+/// ```
+pub fn parse_accept_language(header: &str) -> Vec<LanguageIdentifier> {
+    let mut languages: Vec<(LanguageIdentifier, f32)> = Vec::new();
+    for entry in header.split(',') {
+        let mut parts = entry.split(';');
+        let tag = parts.next().unwrap_or_default().trim();
+        if tag.is_empty() || tag == "*" {
+            continue;
+        }
+        let quality = match parse_quality(parts) {
+            Some(quality) if quality > 0.0 => quality,
+            _ => continue,
+        };
+        let language: LanguageIdentifier = match tag.parse() {
+            Ok(language) => language,
+            Err(_) => continue,
+        };
+        if languages.iter().all(|(existing, _)| existing != &language) {
+            languages.push((language, quality));
+        }
+    }
+
+    // A stable sort, so that languages with the same quality keep the
+    // order of the header.
+    languages.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    languages
+        .into_iter()
+        .map(|(language, _)| language)
+        .collect()
+}
+
+/// Parse the quality value from the `parameters` of an entry, which
+/// is `1` if there is no `q` parameter, or `None` if it is invalid.
+fn parse_quality(parameters: std::str::Split<'_, char>) -> Option<f32> {
+    let mut quality = 1.0;
+    for parameter in parameters {
+        let (name, value) = parameter.split_once('=')?;
+        if name.trim().eq_ignore_ascii_case("q") {
+            quality = value.trim().parse().ok()?;
+        }
+    }
+    (0.0..=1.0).contains(&quality).then_some(quality)
+}
@@ -0,0 +1,181 @@
+//! This module contains an [actix-web](https://docs.rs/actix-web)
+//! extractor which localizes each request for the languages it
+//! requests.
+//!
+//! The [RequestLoader] extractor negotiates the languages requested
+//! by the request against the languages loaded by the shared
+//! [FluentLanguageLoader] of a [LocalizationConfig], which is
+//! registered as application data. The languages are requested by
+//! the `Accept-Language` header, which can be overridden using a
+//! query parameter or a cookie (e.g. set by a language switcher):
+//!
+//! ```ignore
+//! use actix_web::{get, App};
+//! use i18n_embed::actix_web::{LocalizationConfig, RequestLoader};
+//! use i18n_embed_fl::fl;
+//!
+//! #[get("/")]
+//! async fn hello(loader: RequestLoader) -> String {
+//!     fl!(loader, "hello-world")
+//! }
+//!
+//! let loader: FluentLanguageLoader = fluent_language_loader!();
+//! loader.load_available_languages(&Localizations)?;
+//! let config = LocalizationConfig::new(loader)
+//!     .with_query_parameter("lang")
+//!     .with_cookie("lang");
+//! let app = App::new().app_data(config).service(hello);
+//! ```
+//!
+//! ⚠️ *This module requires the following crate features to be activated: `actix-web`.*
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    future::{ready, Ready},
+    ops::Deref,
+    sync::Arc,
+};
+
+use ::actix_web::{
+    dev::Payload, error::ErrorInternalServerError, http::header::ACCEPT_LANGUAGE, web, FromRequest,
+    HttpRequest,
+};
+use unic_langid::LanguageIdentifier;
+
+use crate::{
+    fluent::{FluentLanguageLoader, NegotiationStrategy},
+    parse_accept_language,
+};
+
+/// The configuration of the [RequestLoader] extractor, which needs to
+/// be registered as application data (using `App::app_data()`),
+/// either directly or wrapped in `web::Data`.
+#[derive(Debug, Clone)]
+pub struct LocalizationConfig {
+    loader: Arc<FluentLanguageLoader>,
+    strategy: NegotiationStrategy,
+    query_parameter: Option<Cow<'static, str>>,
+    cookie: Option<Cow<'static, str>>,
+}
+
+impl LocalizationConfig {
+    /// Create a new `LocalizationConfig` for the `loader` shared by
+    /// all of the requests, which should have all of the available
+    /// languages loaded (e.g. using
+    /// [LanguageLoader::load_available_languages()](crate::LanguageLoader::load_available_languages())).
+    pub fn new(loader: impl Into<Arc<FluentLanguageLoader>>) -> Self {
+        Self {
+            loader: loader.into(),
+            strategy: NegotiationStrategy::Filtering,
+            query_parameter: None,
+            cookie: None,
+        }
+    }
+
+    /// Set the strategy used to negotiate the requested languages
+    /// against the loaded languages, by default
+    /// [NegotiationStrategy::Filtering].
+    pub fn with_strategy(mut self, strategy: NegotiationStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Prefer the language in the query parameter with the `name`
+    /// (e.g. `?lang=fr`) over the other requested languages.
+    pub fn with_query_parameter(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.query_parameter = Some(name.into());
+        self
+    }
+
+    /// Prefer the language in the cookie with the `name` over the
+    /// languages in the `Accept-Language` header. The query parameter
+    /// takes precedence over the cookie.
+    pub fn with_cookie(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.cookie = Some(name.into());
+        self
+    }
+
+    /// The languages requested by the `request`, in order of
+    /// preference.
+    fn requested_languages(&self, request: &HttpRequest) -> Vec<LanguageIdentifier> {
+        let query_language = self.query_parameter.as_ref().and_then(|name| {
+            web::Query::<HashMap<String, String>>::from_query(request.query_string())
+                .ok()?
+                .get(name.as_ref())?
+                .parse()
+                .ok()
+        });
+        let cookie_language = self
+            .cookie
+            .as_ref()
+            .and_then(|name| request.cookie(name)?.value().parse().ok());
+        let header_languages = request
+            .headers()
+            .get_all(ACCEPT_LANGUAGE)
+            .filter_map(|header| header.to_str().ok())
+            .flat_map(parse_accept_language);
+
+        query_language
+            .into_iter()
+            .chain(cookie_language)
+            .chain(header_languages)
+            .collect()
+    }
+}
+
+/// An extractor for the [FluentLanguageLoader] for the languages
+/// negotiated for a request, see the [module documentation](self).
+/// It dereferences to the loader, so it can be used with the `fl!()`
+/// macro.
+#[derive(Debug, Clone)]
+pub struct RequestLoader {
+    loader: Arc<FluentLanguageLoader>,
+    requested_languages: Arc<[LanguageIdentifier]>,
+}
+
+impl RequestLoader {
+    /// The languages requested by the request, in order of
+    /// preference.
+    pub fn requested_languages(&self) -> &[LanguageIdentifier] {
+        &self.requested_languages
+    }
+}
+
+impl Deref for RequestLoader {
+    type Target = FluentLanguageLoader;
+
+    fn deref(&self) -> &Self::Target {
+        &self.loader
+    }
+}
+
+impl FromRequest for RequestLoader {
+    type Error = ::actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(request: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let config = request.app_data::<LocalizationConfig>().or_else(|| {
+            request
+                .app_data::<web::Data<LocalizationConfig>>()
+                .map(|config| config.get_ref())
+        });
+        let config = match config {
+            Some(config) => config,
+            None => {
+                return ready(Err(ErrorInternalServerError(
+                    "RequestLoader requires a LocalizationConfig to be registered as app data",
+                )))
+            }
+        };
+
+        let requested_languages = config.requested_languages(request);
+        let loader = config
+            .loader
+            .select_languages_negotiate(&requested_languages, config.strategy);
+        ready(Ok(Self {
+            loader: Arc::new(loader),
+            requested_languages: requested_languages.into(),
+        }))
+    }
+}
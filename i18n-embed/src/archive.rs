@@ -0,0 +1,469 @@
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    convert::TryFrom,
+    fs::File,
+    io::{self, BufReader, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use flate2::read::{DeflateDecoder, MultiGzDecoder};
+
+use crate::{I18nAssets, I18nEmbedError};
+
+/// The signature of the end of central directory record of a zip file.
+const ZIP_END_SIGNATURE: u32 = 0x0605_4b50;
+/// The signature of a central directory file header of a zip file.
+const ZIP_CENTRAL_SIGNATURE: u32 = 0x0201_4b50;
+/// The signature of a local file header of a zip file.
+const ZIP_LOCAL_SIGNATURE: u32 = 0x0403_4b50;
+/// The size of the end of central directory record, without its
+/// trailing comment (which can be up to 65535 bytes long).
+const ZIP_END_SIZE: u64 = 22;
+/// The size of a local file header, without the file name and extra
+/// field.
+const ZIP_LOCAL_SIZE: usize = 30;
+/// The magic number at the start of a gzip file.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+/// The size of the blocks of a tar file.
+const TAR_BLOCK_SIZE: u64 = 512;
+
+/// The format of the archive read by [ArchiveAssets].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArchiveFormat {
+    /// A zip file, with stored or deflated entries.
+    Zip,
+    /// A gzip compressed tar file (`.tar.gz` or `.tgz`).
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// Detect the format of the archive from its first bytes.
+    fn detect(magic: &[u8]) -> Option<Self> {
+        if magic.starts_with(&ZIP_LOCAL_SIGNATURE.to_le_bytes())
+            || magic.starts_with(&ZIP_END_SIGNATURE.to_le_bytes())
+        {
+            Some(Self::Zip)
+        } else if magic.starts_with(&GZIP_MAGIC) {
+            Some(Self::TarGz)
+        } else {
+            None
+        }
+    }
+}
+
+/// Where the contents of a file are stored in the archive.
+#[derive(Debug, Clone, Copy)]
+struct ArchiveEntry {
+    /// For zip files, the offset of the local file header. For tar
+    /// files, the offset of the contents in the decompressed tar.
+    offset: u64,
+    /// The size of the contents in the archive.
+    compressed_size: u64,
+    /// The size of the contents once they are decompressed.
+    size: u64,
+    /// Whether the contents are deflated (zip files only).
+    deflated: bool,
+    /// The expected CRC-32 of the contents (zip files only).
+    crc32: u32,
+}
+
+/// An [I18nAssets] implementation which reads the localization files
+/// from a zip or a gzip compressed tar (`.tar.gz`) archive on disk,
+/// such as an optional language pack which was downloaded as a single
+/// file. The archive uses the same layout as the other assets (e.g.
+/// `de-DE/my_crate.ftl`).
+///
+/// The archive is indexed when it is opened, and the contents of a
+/// file are only read (and decompressed) when it is requested. Zip
+/// files are read directly from the offset of the file, while tar
+/// files need to be decompressed up to the file, because gzip streams
+/// can't be seeked. Encrypted zip entries and zip64 archives are not
+/// supported.
+///
+/// ⚠️ *This API requires the following crate features to be activated: `archive-assets`.*
+#[derive(Debug)]
+pub struct ArchiveAssets {
+    path: PathBuf,
+    format: ArchiveFormat,
+    index: BTreeMap<String, ArchiveEntry>,
+}
+
+impl ArchiveAssets {
+    /// Open the archive at `path`, detecting its format from its
+    /// contents, and index the files which it contains.
+    pub fn open<P: Into<PathBuf>>(path: P) -> Result<Self, I18nEmbedError> {
+        let path = path.into();
+        let mut magic = Vec::with_capacity(4);
+        File::open(&path)
+            .and_then(|file| file.take(4).read_to_end(&mut magic))
+            .map_err(|error| I18nEmbedError::ArchiveIo(path.clone(), error))?;
+        let format = ArchiveFormat::detect(&magic).ok_or_else(|| {
+            I18nEmbedError::InvalidArchive(path.clone(), "unknown archive format".to_string())
+        })?;
+        Self::open_with_format(path, format)
+    }
+
+    /// Open the archive at `path` in the specified `format`, and index
+    /// the files which it contains.
+    pub fn open_with_format<P: Into<PathBuf>>(
+        path: P,
+        format: ArchiveFormat,
+    ) -> Result<Self, I18nEmbedError> {
+        let path = path.into();
+        let index = match format {
+            ArchiveFormat::Zip => index_zip(&path),
+            ArchiveFormat::TarGz => index_tar_gz(&path),
+        }?;
+        Ok(Self {
+            path,
+            format,
+            index,
+        })
+    }
+
+    /// The path of the archive.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The format of the archive.
+    pub fn format(&self) -> ArchiveFormat {
+        self.format
+    }
+
+    /// Read the contents of the file in the archive described by the
+    /// `entry`.
+    fn read(&self, entry: &ArchiveEntry) -> Result<Vec<u8>, I18nEmbedError> {
+        match self.format {
+            ArchiveFormat::Zip => read_zip_entry(&self.path, entry),
+            ArchiveFormat::TarGz => read_tar_gz_entry(&self.path, entry),
+        }
+    }
+}
+
+impl I18nAssets for ArchiveAssets {
+    fn get_files(&self, file_path: &str) -> Vec<Cow<'_, [u8]>> {
+        let entry = match self.index.get(file_path) {
+            Some(entry) => entry,
+            None => return Vec::new(),
+        };
+
+        match self.read(entry) {
+            Ok(contents) => vec![Cow::from(contents)],
+            Err(e) => {
+                diagnostic!(
+                    error,
+                    target: "i18n_embed::assets",
+                    { path = file_path },
+                    "Unexpected error while reading localization asset file: {}",
+                    e
+                );
+                Vec::new()
+            }
+        }
+    }
+
+    fn filenames_iter(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new(self.index.keys().cloned())
+    }
+}
+
+/// Normalize the path of a file in an archive, so that it matches
+/// the file paths of the other assets.
+fn normalize_path(path: &str) -> String {
+    path.trim_start_matches("./")
+        .trim_start_matches('/')
+        .to_string()
+}
+
+fn invalid(path: &Path, message: &str) -> I18nEmbedError {
+    I18nEmbedError::InvalidArchive(path.to_path_buf(), message.to_string())
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+/// Index the files of the zip archive at `path` using its central
+/// directory.
+fn index_zip(path: &Path) -> Result<BTreeMap<String, ArchiveEntry>, I18nEmbedError> {
+    let io_error = |error| I18nEmbedError::ArchiveIo(path.to_path_buf(), error);
+    let mut file = File::open(path).map_err(io_error)?;
+    let length = file.seek(SeekFrom::End(0)).map_err(io_error)?;
+
+    // The end of central directory record is at the end of the file,
+    // followed by a comment of unknown length.
+    let tail_length = length.min(ZIP_END_SIZE + u64::from(u16::MAX));
+    let mut tail = vec![0; tail_length as usize];
+    file.seek(SeekFrom::Start(length - tail_length))
+        .and_then(|_| file.read_exact(&mut tail))
+        .map_err(io_error)?;
+    let end = (0..=tail.len().saturating_sub(ZIP_END_SIZE as usize))
+        .rev()
+        .find(|&i| u32_at(&tail, i) == ZIP_END_SIGNATURE)
+        .ok_or_else(|| invalid(path, "the end of central directory record is missing"))?;
+    let entries = u16_at(&tail, end + 10);
+    let directory_size = u32_at(&tail, end + 12);
+    let directory_offset = u32_at(&tail, end + 16);
+    if entries == u16::MAX || directory_offset == u32::MAX {
+        return Err(invalid(path, "zip64 archives are not supported"));
+    }
+
+    let mut directory = vec![0; directory_size as usize];
+    file.seek(SeekFrom::Start(u64::from(directory_offset)))
+        .and_then(|_| file.read_exact(&mut directory))
+        .map_err(io_error)?;
+
+    let mut index = BTreeMap::new();
+    let mut position = 0;
+    for _ in 0..entries {
+        if position + 46 > directory.len() || u32_at(&directory, position) != ZIP_CENTRAL_SIGNATURE
+        {
+            return Err(invalid(path, "the central directory is truncated"));
+        }
+        let header = &directory[position..];
+        let flags = u16_at(header, 8);
+        let method = u16_at(header, 10);
+        let crc32 = u32_at(header, 16);
+        let compressed_size = u32_at(header, 20);
+        let size = u32_at(header, 24);
+        let name_length = usize::from(u16_at(header, 28));
+        let extra_length = usize::from(u16_at(header, 30));
+        let comment_length = usize::from(u16_at(header, 32));
+        let offset = u32_at(header, 42);
+        let name = header
+            .get(46..46 + name_length)
+            .ok_or_else(|| invalid(path, "the central directory is truncated"))?;
+        position += 46 + name_length + extra_length + comment_length;
+
+        let name = match std::str::from_utf8(name) {
+            Ok(name) => normalize_path(name),
+            Err(_) => {
+                diagnostic!(
+                    error,
+                    target: "i18n_embed::assets",
+                    { path = path.display() },
+                    "Filename {:?} in the archive is not valid UTF-8.",
+                    String::from_utf8_lossy(name)
+                );
+                continue;
+            }
+        };
+        if name.is_empty() || name.ends_with('/') {
+            continue;
+        }
+        if compressed_size == u32::MAX || size == u32::MAX || offset == u32::MAX {
+            return Err(invalid(path, "zip64 archives are not supported"));
+        }
+        if flags & 1 != 0 {
+            return Err(invalid(path, "encrypted entries are not supported"));
+        }
+        let deflated = match method {
+            0 => false,
+            8 => true,
+            _ => {
+                return Err(invalid(
+                    path,
+                    &format!("the compression method {method} of \"{name}\" is not supported"),
+                ))
+            }
+        };
+        index.insert(
+            name,
+            ArchiveEntry {
+                offset: u64::from(offset),
+                compressed_size: u64::from(compressed_size),
+                size: u64::from(size),
+                deflated,
+                crc32,
+            },
+        );
+    }
+    Ok(index)
+}
+
+/// Read the contents of the file described by the `entry` from the
+/// zip archive at `path`.
+fn read_zip_entry(path: &Path, entry: &ArchiveEntry) -> Result<Vec<u8>, I18nEmbedError> {
+    let io_error = |error| I18nEmbedError::ArchiveIo(path.to_path_buf(), error);
+    let mut file = File::open(path).map_err(io_error)?;
+    let mut header = [0; ZIP_LOCAL_SIZE];
+    file.seek(SeekFrom::Start(entry.offset))
+        .and_then(|_| file.read_exact(&mut header))
+        .map_err(io_error)?;
+    if u32_at(&header, 0) != ZIP_LOCAL_SIGNATURE {
+        return Err(invalid(path, "a local file header is missing"));
+    }
+    // The lengths of the name and extra field of the local header may
+    // differ from those in the central directory.
+    let skip = i64::from(u16_at(&header, 26)) + i64::from(u16_at(&header, 28));
+    file.seek(SeekFrom::Current(skip)).map_err(io_error)?;
+
+    let compressed = file.take(entry.compressed_size);
+    let mut contents = Vec::with_capacity(usize::try_from(entry.size).unwrap_or_default());
+    if entry.deflated {
+        DeflateDecoder::new(compressed).read_to_end(&mut contents)
+    } else {
+        BufReader::new(compressed).read_to_end(&mut contents)
+    }
+    .map_err(io_error)?;
+
+    if contents.len() as u64 != entry.size || crc32fast::hash(&contents) != entry.crc32 {
+        return Err(invalid(path, "the contents of an entry are corrupted"));
+    }
+    Ok(contents)
+}
+
+/// Parse an octal number field of a tar header.
+fn parse_octal(field: &[u8]) -> Option<u64> {
+    let field = std::str::from_utf8(field).ok()?;
+    let field = field.trim_matches(|c: char| c == '\0' || c == ' ');
+    if field.is_empty() {
+        return Some(0);
+    }
+    u64::from_str_radix(field, 8).ok()
+}
+
+/// Parse a nul terminated string field of a tar header.
+fn parse_string(field: &[u8]) -> &[u8] {
+    let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    &field[..end]
+}
+
+/// The `path` record of the contents of a pax extended header.
+fn pax_path(records: &[u8]) -> Option<Vec<u8>> {
+    let mut records = records;
+    while !records.is_empty() {
+        let space = records.iter().position(|&b| b == b' ')?;
+        let length: usize = std::str::from_utf8(&records[..space]).ok()?.parse().ok()?;
+        let record = records.get(space + 1..length)?;
+        let record = record.strip_suffix(b"\n").unwrap_or(record);
+        if let Some(value) = record.strip_prefix(b"path=") {
+            return Some(value.to_vec());
+        }
+        records = &records[length..];
+    }
+    None
+}
+
+/// Index the files of the gzip compressed tar archive at `path`,
+/// decompressing it once while skipping over the contents of the
+/// files.
+fn index_tar_gz(path: &Path) -> Result<BTreeMap<String, ArchiveEntry>, I18nEmbedError> {
+    let io_error = |error| I18nEmbedError::ArchiveIo(path.to_path_buf(), error);
+    let file = File::open(path).map_err(io_error)?;
+    let mut tar = MultiGzDecoder::new(BufReader::new(file));
+
+    let mut index = BTreeMap::new();
+    let mut offset = 0;
+    // The name of the next file, from a GNU long name or pax header.
+    let mut long_name: Option<Vec<u8>> = None;
+    let mut header = [0; TAR_BLOCK_SIZE as usize];
+    loop {
+        match tar.read_exact(&mut header) {
+            Ok(()) => {}
+            // Some archives omit the end of archive blocks.
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(error) => return Err(io_error(error)),
+        }
+        offset += TAR_BLOCK_SIZE;
+        if header.iter().all(|&b| b == 0) {
+            break;
+        }
+
+        let size = parse_octal(&header[124..136])
+            .ok_or_else(|| invalid(path, "the size of an entry is invalid"))?;
+        let padded_size = size.div_ceil(TAR_BLOCK_SIZE) * TAR_BLOCK_SIZE;
+        let type_flag = header[156];
+        match type_flag {
+            b'L' | b'x' => {
+                let mut data = Vec::new();
+                (&mut tar)
+                    .take(padded_size)
+                    .read_to_end(&mut data)
+                    .map_err(io_error)?;
+                data.truncate(size as usize);
+                offset += padded_size;
+                let name = if type_flag == b'L' {
+                    Some(parse_string(&data).to_vec())
+                } else {
+                    pax_path(&data)
+                };
+                if name.is_some() {
+                    long_name = name;
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        let name = long_name.take().unwrap_or_else(|| {
+            let name = parse_string(&header[0..100]);
+            let prefix = parse_string(&header[345..500]);
+            if &header[257..262] == b"ustar" && !prefix.is_empty() {
+                [prefix, b"/", name].concat()
+            } else {
+                name.to_vec()
+            }
+        });
+        if matches!(type_flag, b'0' | b'\0') {
+            match String::from_utf8(name) {
+                Ok(name) => {
+                    let name = normalize_path(&name);
+                    if !name.is_empty() {
+                        index.insert(
+                            name,
+                            ArchiveEntry {
+                                offset,
+                                compressed_size: size,
+                                size,
+                                deflated: false,
+                                crc32: 0,
+                            },
+                        );
+                    }
+                }
+                Err(error) => {
+                    diagnostic!(
+                        error,
+                        target: "i18n_embed::assets",
+                        { path = path.display() },
+                        "Filename {:?} in the archive is not valid UTF-8.",
+                        String::from_utf8_lossy(error.as_bytes())
+                    );
+                }
+            }
+        }
+
+        io::copy(&mut (&mut tar).take(padded_size), &mut io::sink()).map_err(io_error)?;
+        offset += padded_size;
+    }
+    Ok(index)
+}
+
+/// Read the contents of the file described by the `entry` from the
+/// gzip compressed tar archive at `path`.
+fn read_tar_gz_entry(path: &Path, entry: &ArchiveEntry) -> Result<Vec<u8>, I18nEmbedError> {
+    let io_error = |error| I18nEmbedError::ArchiveIo(path.to_path_buf(), error);
+    let file = File::open(path).map_err(io_error)?;
+    let mut tar = MultiGzDecoder::new(BufReader::new(file));
+    io::copy(&mut (&mut tar).take(entry.offset), &mut io::sink()).map_err(io_error)?;
+    let mut contents = Vec::with_capacity(usize::try_from(entry.size).unwrap_or_default());
+    tar.take(entry.size)
+        .read_to_end(&mut contents)
+        .map_err(io_error)?;
+    if contents.len() as u64 != entry.size {
+        return Err(invalid(path, "the archive is truncated"));
+    }
+    Ok(contents)
+}
@@ -1,4 +1,8 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex, RwLock, Weak},
+};
 
 use rust_embed::RustEmbed;
 
@@ -98,10 +102,21 @@ where
     ) -> Result<Box<dyn Watcher + Send + Sync + 'static>, I18nEmbedError> {
         let base_dir = &self.base_dir;
         if base_dir.is_dir() {
-            log::debug!("Watching for changed files in {:?}", self.base_dir);
+            diagnostic!(
+                debug,
+                target: "i18n_embed::assets",
+                { base_dir = base_dir.display() },
+                "Watching for changed files in {:?}",
+                self.base_dir
+            );
             notify_watcher(base_dir, changed).map_err(Into::into)
         } else {
-            log::debug!("base_dir {base_dir:?} does not yet exist, unable to watch for changes");
+            diagnostic!(
+                debug,
+                target: "i18n_embed::assets",
+                { base_dir = base_dir.display() },
+                "base_dir {base_dir:?} does not yet exist, unable to watch for changes"
+            );
             Ok(Box::new(()))
         }
     }
@@ -187,7 +202,7 @@ fn notify_watcher(
         let event: notify::Event = match event_result {
             Ok(event) => event,
             Err(error) => {
-                log::error!("{error}");
+                diagnostic!(error, target: "i18n_embed::assets", {}, "{error}");
                 return;
             }
         };
@@ -212,6 +227,60 @@ fn notify_watcher(
 /// in the future ther may be new methods added to this trait.
 pub trait Watcher {}
 
+/// The callbacks subscribed to the changes of [I18nAssets] which
+/// notify them, see [I18nAssets::subscribe_changed()].
+#[derive(Default)]
+pub(crate) struct Subscribers {
+    callbacks: Mutex<HashMap<u64, Arc<dyn Fn() + Send + Sync + 'static>>>,
+    next_id: Mutex<u64>,
+}
+
+impl Subscribers {
+    /// Subscribe the `changed` callback, which is unsubscribed when
+    /// the returned [Watcher] is dropped.
+    pub(crate) fn subscribe(
+        self: &Arc<Self>,
+        changed: Arc<dyn Fn() + Send + Sync + 'static>,
+    ) -> Box<dyn Watcher + Send + Sync + 'static> {
+        let id = {
+            let mut next_id = self.next_id.lock().unwrap();
+            *next_id += 1;
+            *next_id
+        };
+        self.callbacks.lock().unwrap().insert(id, changed);
+        Box::new(SubscriberWatcher {
+            id,
+            subscribers: Arc::downgrade(self),
+        })
+    }
+
+    /// Call each of the subscribed callbacks.
+    pub(crate) fn notify(&self) {
+        // The callbacks are cloned so that they can subscribe or
+        // unsubscribe while they are being notified.
+        let callbacks: Vec<_> = self.callbacks.lock().unwrap().values().cloned().collect();
+        for changed in callbacks {
+            changed();
+        }
+    }
+}
+
+/// Unsubscribes from the [Subscribers] when dropped.
+struct SubscriberWatcher {
+    id: u64,
+    subscribers: Weak<Subscribers>,
+}
+
+impl Watcher for SubscriberWatcher {}
+
+impl Drop for SubscriberWatcher {
+    fn drop(&mut self) {
+        if let Some(subscribers) = self.subscribers.upgrade() {
+            subscribers.callbacks.lock().unwrap().remove(&self.id);
+        }
+    }
+}
+
 #[cfg(feature = "autoreload")]
 impl Watcher for notify::RecommendedWatcher {}
 
@@ -227,29 +296,45 @@ impl I18nAssets for FileSystemAssets {
         match std::fs::read(full_path) {
             Ok(contents) => vec![Cow::from(contents)],
             Err(e) => {
-                log::error!(
-                    target: "i18n_embed::assets", 
-                    "Unexpected error while reading localization asset file: {}", 
-                    e);
+                diagnostic!(
+                    error,
+                    target: "i18n_embed::assets",
+                    { path = file_path },
+                    "Unexpected error while reading localization asset file: {}",
+                    e
+                );
                 Vec::new()
             }
         }
     }
 
     fn filenames_iter(&self) -> Box<dyn Iterator<Item = String>> {
+        let base_dir = self.base_dir.clone();
         Box::new(
             walkdir::WalkDir::new(&self.base_dir)
                 .into_iter()
-                .filter_map(|f| match f {
+                .filter_map(move |f| match f {
                     Ok(f) => {
                         if f.file_type().is_file() {
-                            match f.file_name().to_str() {
-                                Some(filename) => Some(filename.to_string()),
+                            // Filenames are relative to the base
+                            // directory, using `/` as the separator
+                            // (consistent with `RustEmbed`).
+                            let relative_path =
+                                f.path().strip_prefix(&base_dir).unwrap_or(f.path());
+                            let components: Option<Vec<&str>> = relative_path
+                                .components()
+                                .map(|component| component.as_os_str().to_str())
+                                .collect();
+                            match components {
+                                Some(components) => Some(components.join("/")),
                                 None => {
-                                    log::error!(
-                                target: "i18n_embed::assets", 
-                                "Filename {:?} is not valid UTF-8.", 
-                                f.file_name());
+                                    diagnostic!(
+                                        error,
+                                        target: "i18n_embed::assets",
+                                        { path = f.path().display() },
+                                        "Filename {:?} is not valid UTF-8.",
+                                        f.path()
+                                    );
                                     None
                                 }
                             }
@@ -258,10 +343,13 @@ impl I18nAssets for FileSystemAssets {
                         }
                     }
                     Err(err) => {
-                        log::error!(
-                    target: "i18n_embed::assets", 
-                    "Unexpected error while gathering localization asset filenames: {}", 
-                    err);
+                        diagnostic!(
+                            error,
+                            target: "i18n_embed::assets",
+                            {},
+                            "Unexpected error while gathering localization asset filenames: {}",
+                            err
+                        );
                         None
                     }
                 }),
@@ -283,10 +371,30 @@ impl I18nAssets for FileSystemAssets {
     }
 }
 
+/// How an [`AssetsMultiplexor`] combines the files at the same path
+/// provided by several of its assets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum MergePolicy {
+    /// Only the file of the asset with the highest priority is used.
+    FirstWins,
+    /// Only the file of the asset with the lowest priority is used.
+    LastWins,
+    /// The files of all of the assets are used, in order of priority
+    /// from high to low. The fluent loader merges their messages,
+    /// using each message from the file with the highest priority
+    /// which contains it. Loaders which don't support merging files
+    /// (such as the gettext loader) use the first file.
+    #[default]
+    MergeMessages,
+}
+
 /// A way to multiplex implmentations of [`I18nAssets`].
 pub struct AssetsMultiplexor {
     /// Assets that are multiplexed, ordered from most to least priority.
     assets: Vec<Box<dyn I18nAssets + Send + Sync + 'static>>,
+    /// How the files at the same path in several `assets` are
+    /// combined.
+    policy: MergePolicy,
 }
 
 impl std::fmt::Debug for AssetsMultiplexor {
@@ -296,20 +404,74 @@ impl std::fmt::Debug for AssetsMultiplexor {
                 "assets",
                 &self.assets.iter().map(|_| "<ASSET>").collect::<Vec<_>>(),
             )
+            .field("policy", &self.policy)
             .finish()
     }
 }
 
 impl AssetsMultiplexor {
     /// Construct a new [`AssetsMultiplexor`]. `assets` are specified in order of priority of
-    /// processing for the [`crate::LanguageLoader`].
+    /// processing for the [`crate::LanguageLoader`], and their files
+    /// are combined using [`MergePolicy::MergeMessages`].
     pub fn new(
         assets: impl IntoIterator<Item = Box<dyn I18nAssets + Send + Sync + 'static>>,
     ) -> Self {
         Self {
             assets: assets.into_iter().collect(),
+            policy: MergePolicy::default(),
         }
     }
+
+    /// Construct a new [`AssetsMultiplexor`] from `assets` with
+    /// explicit priority weights, where assets with a higher weight
+    /// take priority over those with a lower weight, and assets with
+    /// the same weight are in the order they are specified. The files
+    /// at the same path in several assets are combined according to
+    /// the `policy`.
+    ///
+    /// ```
+    /// use i18n_embed::{AssetsMultiplexor, I18nAssets, MergePolicy};
+    /// # use std::borrow::Cow;
+    /// # struct Single(&'static str);
+    /// # impl I18nAssets for Single {
+    /// #     fn get_files(&self, _: &str) -> Vec<Cow<'_, [u8]>> {
+    /// #         vec![Cow::Borrowed(self.0.as_bytes())]
+    /// #     }
+    /// #     fn filenames_iter(&self) -> Box<dyn Iterator<Item = String> + '_> {
+    /// #         Box::new(std::iter::once("en/app.ftl".to_string()))
+    /// #     }
+    /// # }
+    ///
+    /// let assets = AssetsMultiplexor::new_with_policy(
+    ///     vec![
+    ///         (Box::new(Single("built-in")) as Box<dyn I18nAssets + Send + Sync>, 0),
+    ///         (Box::new(Single("override")), 10),
+    ///     ],
+    ///     MergePolicy::FirstWins,
+    /// );
+    /// let files = assets.get_files("en/app.ftl");
+    /// assert_eq!(1, files.len());
+    /// assert_eq!(b"override", &*files[0]);
+    /// ```
+    pub fn new_with_policy(
+        assets: impl IntoIterator<Item = (Box<dyn I18nAssets + Send + Sync + 'static>, i32)>,
+        policy: MergePolicy,
+    ) -> Self {
+        let mut assets: Vec<_> = assets.into_iter().collect();
+        // A stable sort, so that assets with the same weight keep
+        // their order.
+        assets.sort_by_key(|(_, weight)| std::cmp::Reverse(*weight));
+        Self {
+            assets: assets.into_iter().map(|(assets, _)| assets).collect(),
+            policy,
+        }
+    }
+
+    /// How the files at the same path in several assets are
+    /// combined.
+    pub fn policy(&self) -> MergePolicy {
+        self.policy
+    }
 }
 
 #[allow(dead_code)] // We rely on the Drop implementation of the Watcher to remove the file system watch.
@@ -319,10 +481,15 @@ impl Watcher for Watchers {}
 
 impl I18nAssets for AssetsMultiplexor {
     fn get_files(&self, file_path: &str) -> Vec<Cow<'_, [u8]>> {
-        self.assets
+        let mut files = self
+            .assets
             .iter()
-            .flat_map(|assets| assets.get_files(file_path))
-            .collect()
+            .flat_map(|assets| assets.get_files(file_path));
+        match self.policy {
+            MergePolicy::FirstWins => files.next().into_iter().collect(),
+            MergePolicy::LastWins => files.last().into_iter().collect(),
+            MergePolicy::MergeMessages => files.collect(),
+        }
     }
 
     fn filenames_iter(&self) -> Box<dyn Iterator<Item = String> + '_> {
@@ -345,3 +512,115 @@ impl I18nAssets for AssetsMultiplexor {
         Ok(Box::new(Watchers(watchers)))
     }
 }
+
+/// [I18nAssets] stored in memory, whose files can be inserted and
+/// removed at runtime, such as translations which have been
+/// downloaded or stored in a database. Subscribers (see
+/// [I18nAssets::subscribe_changed()]) are notified of each change,
+/// so that the loaders using these assets can reload them (e.g. using
+/// [crate::DefaultLocalizer::with_autoreload()]).
+///
+/// Clones share the same files.
+///
+/// ```
+/// use i18n_embed::{I18nAssets, InMemoryAssets};
+///
+/// let assets = InMemoryAssets::new();
+/// assets.insert("en-US/my_crate.ftl", "hello = Hello!");
+///
+/// assert_eq!(vec!["en-US/my_crate.ftl"], assets.filenames_iter().collect::<Vec<_>>());
+/// assert_eq!(b"hello = Hello!", &*assets.get_files("en-US/my_crate.ftl")[0]);
+///
+/// assert!(assets.remove("en-US/my_crate.ftl"));
+/// assert!(assets.get_files("en-US/my_crate.ftl").is_empty());
+/// ```
+#[derive(Clone, Default)]
+pub struct InMemoryAssets {
+    inner: Arc<InMemoryAssetsInner>,
+}
+
+#[derive(Default)]
+struct InMemoryAssetsInner {
+    files: RwLock<BTreeMap<String, Vec<u8>>>,
+    subscribers: Arc<Subscribers>,
+}
+
+impl std::fmt::Debug for InMemoryAssets {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InMemoryAssets")
+            .field("files", &self.filenames_iter().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl InMemoryAssets {
+    /// Construct new [InMemoryAssets] without any files.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert the file at the `path` (e.g. `en-US/my_crate.ftl`) with
+    /// the `contents`, replacing any existing file at that path, and
+    /// notify the subscribers.
+    pub fn insert(&self, path: impl Into<String>, contents: impl Into<Vec<u8>>) {
+        self.update(|files| {
+            files.insert(path.into(), contents.into());
+        });
+    }
+
+    /// Remove the file at the `path`, and notify the subscribers.
+    /// Returns `false` (without notifying the subscribers) if there
+    /// was no such file.
+    pub fn remove(&self, path: &str) -> bool {
+        let removed = self.inner.files.write().unwrap().remove(path).is_some();
+        if removed {
+            self.inner.subscribers.notify();
+        }
+        removed
+    }
+
+    /// Make several changes to the files, a map of their paths to
+    /// their contents, and then notify the subscribers once, so that
+    /// the loaders don't reload the files in an intermediate state.
+    pub fn update<R>(&self, f: impl FnOnce(&mut BTreeMap<String, Vec<u8>>) -> R) -> R {
+        let result = f(&mut self.inner.files.write().unwrap());
+        self.inner.subscribers.notify();
+        result
+    }
+}
+
+impl<C: Into<Vec<u8>>> From<HashMap<String, C>> for InMemoryAssets {
+    fn from(files: HashMap<String, C>) -> Self {
+        let assets = Self::new();
+        *assets.inner.files.write().unwrap() = files
+            .into_iter()
+            .map(|(path, contents)| (path, contents.into()))
+            .collect();
+        assets
+    }
+}
+
+impl I18nAssets for InMemoryAssets {
+    fn get_files(&self, file_path: &str) -> Vec<Cow<'_, [u8]>> {
+        self.inner
+            .files
+            .read()
+            .unwrap()
+            .get(file_path)
+            .map(|contents| Cow::Owned(contents.clone()))
+            .into_iter()
+            .collect()
+    }
+
+    fn filenames_iter(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        let filenames: Vec<String> = self.inner.files.read().unwrap().keys().cloned().collect();
+        Box::new(filenames.into_iter())
+    }
+
+    fn subscribe_changed(
+        &self,
+        changed: Arc<dyn Fn() + Send + Sync + 'static>,
+    ) -> Result<Box<dyn Watcher + Send + Sync + 'static>, I18nEmbedError> {
+        Ok(self.inner.subscribers.subscribe(changed))
+    }
+}
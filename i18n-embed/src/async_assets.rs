@@ -0,0 +1,215 @@
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+};
+
+use async_trait::async_trait;
+use fluent_langneg::{negotiate_languages, NegotiationStrategy};
+use unic_langid::LanguageIdentifier;
+
+use crate::{I18nAssets, I18nEmbedError, LanguageLoader};
+
+/// An asynchronous source of localization assets, such as a HTTP
+/// server or a slow disk, which can be loaded without blocking the
+/// worker threads of an async runtime using
+/// [load_languages_async()] or [select_async()].
+///
+/// Synchronous [I18nAssets] can be used wherever `AsyncI18nAssets`
+/// are expected by wrapping them in [SyncAssets].
+///
+/// ⚠️ *This API requires the following crate features to be activated: `async`.*
+#[async_trait]
+pub trait AsyncI18nAssets: Send + Sync {
+    /// Get the files at the `file_path`, in order of priority, see
+    /// [I18nAssets::get_files()].
+    async fn get_files(&self, file_path: &str) -> Vec<Cow<'_, [u8]>>;
+
+    /// The paths of all of the files in the assets, see
+    /// [I18nAssets::filenames_iter()].
+    async fn filenames(&self) -> Vec<String>;
+}
+
+/// An adapter which implements [AsyncI18nAssets] for synchronous
+/// [I18nAssets], such as assets embedded using `RustEmbed`. The files
+/// are read on the task which awaits them, so this is only suitable
+/// for assets which don't block, such as those embedded in the
+/// binary.
+///
+/// ⚠️ *This API requires the following crate features to be activated: `async`.*
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SyncAssets<A>(pub A);
+
+#[async_trait]
+impl<A> AsyncI18nAssets for SyncAssets<A>
+where
+    A: I18nAssets + Send + Sync,
+{
+    async fn get_files(&self, file_path: &str) -> Vec<Cow<'_, [u8]>> {
+        self.0.get_files(file_path)
+    }
+
+    async fn filenames(&self) -> Vec<String> {
+        self.0.filenames_iter().collect()
+    }
+}
+
+/// Load the languages `language_ids` from the `i18n_assets` like
+/// [LanguageLoader::load_languages()], fetching the files which are
+/// needed for them (and those which don't belong to a language, such
+/// as CSV tables) asynchronously before loading them. The files of
+/// the other languages are not fetched.
+///
+/// ⚠️ *This API requires the following crate features to be activated: `async`.*
+pub async fn load_languages_async(
+    language_loader: &(dyn LanguageLoader + Send + Sync),
+    i18n_assets: &dyn AsyncI18nAssets,
+    language_ids: &[LanguageIdentifier],
+) -> Result<(), I18nEmbedError> {
+    let mut snapshot = AssetsSnapshot::new(i18n_assets).await;
+    snapshot
+        .fetch(language_loader, i18n_assets, language_ids)
+        .await;
+    language_loader.load_languages(&snapshot, language_ids)
+}
+
+/// Select the most suitable of the languages available in the
+/// `i18n_assets` for the `requested_languages`, and load them, like
+/// [select()](crate::select()), fetching the files asynchronously
+/// (see [load_languages_async()]). Returns the languages which were
+/// loaded.
+///
+/// ⚠️ *This API requires the following crate features to be activated: `async`.*
+pub async fn select_async(
+    language_loader: &(dyn LanguageLoader + Send + Sync),
+    i18n_assets: &dyn AsyncI18nAssets,
+    requested_languages: &[LanguageIdentifier],
+) -> Result<Vec<LanguageIdentifier>, I18nEmbedError> {
+    let mut snapshot = AssetsSnapshot::new(i18n_assets).await;
+    snapshot.fetch(language_loader, i18n_assets, &[]).await;
+
+    let available_languages = language_loader.available_languages(&snapshot)?;
+    let supported_languages: Vec<LanguageIdentifier> = negotiate_languages(
+        requested_languages,
+        &available_languages,
+        Some(language_loader.fallback_language()),
+        NegotiationStrategy::Filtering,
+    )
+    .into_iter()
+    .cloned()
+    .collect();
+
+    if !supported_languages.is_empty() {
+        snapshot
+            .fetch(language_loader, i18n_assets, &supported_languages)
+            .await;
+        language_loader.load_languages(&snapshot, &supported_languages)?;
+    }
+    Ok(supported_languages)
+}
+
+/// The files fetched from [AsyncI18nAssets], which are loaded
+/// synchronously by the [LanguageLoader].
+struct AssetsSnapshot {
+    filenames: Vec<String>,
+    files: HashMap<String, Vec<Vec<u8>>>,
+}
+
+impl AssetsSnapshot {
+    async fn new(i18n_assets: &dyn AsyncI18nAssets) -> Self {
+        Self {
+            filenames: i18n_assets.filenames().await,
+            files: HashMap::new(),
+        }
+    }
+
+    /// Fetch the files which are needed to load the `languages` (and
+    /// the fallback language) using the `language_loader`, which
+    /// haven't been fetched yet. These are the files of the
+    /// languages (including the namespace files in their
+    /// directories), and the files which don't belong to any
+    /// language.
+    async fn fetch(
+        &mut self,
+        language_loader: &(dyn LanguageLoader + Send + Sync),
+        i18n_assets: &dyn AsyncI18nAssets,
+        languages: &[LanguageIdentifier],
+    ) {
+        let domain = language_loader.domain();
+        let path_template = language_loader.path_template();
+        let language_aliases = language_loader.language_aliases();
+        let resolve = |language: &LanguageIdentifier| {
+            language_aliases
+                .resolve(language)
+                .unwrap_or_else(|| language.clone())
+        };
+        let needed: HashSet<LanguageIdentifier> = languages
+            .iter()
+            .chain(std::iter::once(language_loader.fallback_language()))
+            .map(resolve)
+            .collect();
+
+        // The directories of the languages which are not needed, which
+        // may contain namespace files.
+        let excluded_directories: Vec<String> = self
+            .filenames
+            .iter()
+            .filter_map(|filename| {
+                let language = path_template.language(filename, domain)?;
+                let parsed: LanguageIdentifier = language.parse().ok()?;
+                if needed.contains(&resolve(&parsed)) {
+                    return None;
+                }
+                path_template
+                    .directory(language, domain)
+                    .map(|directory| format!("{directory}/"))
+            })
+            .collect();
+
+        let mut paths: Vec<String> = self
+            .filenames
+            .iter()
+            .filter(|filename| match path_template.language(filename, domain) {
+                Some(language) => language
+                    .parse()
+                    .is_ok_and(|language: LanguageIdentifier| needed.contains(&resolve(&language))),
+                None => !excluded_directories
+                    .iter()
+                    .any(|directory| filename.starts_with(directory.as_str())),
+            })
+            .cloned()
+            .collect();
+        // The files which may exist without being listed in the
+        // filenames of the assets.
+        paths.extend(
+            needed
+                .iter()
+                .map(|language| path_template.path(language, domain)),
+        );
+
+        for path in paths {
+            if self.files.contains_key(&path) {
+                continue;
+            }
+            let files = i18n_assets
+                .get_files(&path)
+                .await
+                .into_iter()
+                .map(Cow::into_owned)
+                .collect();
+            self.files.insert(path, files);
+        }
+    }
+}
+
+impl I18nAssets for AssetsSnapshot {
+    fn get_files(&self, file_path: &str) -> Vec<Cow<'_, [u8]>> {
+        self.files
+            .get(file_path)
+            .map(|files| files.iter().map(|file| Cow::Borrowed(&file[..])).collect())
+            .unwrap_or_default()
+    }
+
+    fn filenames_iter(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new(self.filenames.iter().cloned())
+    }
+}
@@ -0,0 +1,155 @@
+//! This module contains a [tower](https://docs.rs/tower) middleware
+//! which localizes each request of a web server built with
+//! [axum](https://docs.rs/axum) (or another framework using `tower`)
+//! for the languages requested in its `Accept-Language` header.
+//!
+//! The [LocalizationLayer] negotiates the requested languages against
+//! the languages loaded by a shared [FluentLanguageLoader], and
+//! inserts a [RequestLoader] for them into the extensions of the
+//! request, which handlers obtain using axum's `Extension` extractor:
+//!
+//! ```ignore
+//! use axum::{routing::get, Extension, Router};
+//! use i18n_embed::axum::{LocalizationLayer, RequestLoader};
+//! use i18n_embed_fl::fl;
+//!
+//! async fn hello(Extension(loader): Extension<RequestLoader>) -> String {
+//!     fl!(loader, "hello-world")
+//! }
+//!
+//! let loader: FluentLanguageLoader = fluent_language_loader!();
+//! loader.load_available_languages(&Localizations)?;
+//! let app = Router::new()
+//!     .route("/", get(hello))
+//!     .layer(LocalizationLayer::new(loader));
+//! ```
+//!
+//! ⚠️ *This module requires the following crate features to be activated: `axum`.*
+
+use std::{
+    ops::Deref,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use http::{header::ACCEPT_LANGUAGE, HeaderMap, Request};
+use tower_layer::Layer;
+use tower_service::Service;
+use unic_langid::LanguageIdentifier;
+
+use crate::{
+    fluent::{FluentLanguageLoader, NegotiationStrategy},
+    parse_accept_language,
+};
+
+/// A [Layer] which localizes each request for the languages in its
+/// `Accept-Language` header, see the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct LocalizationLayer {
+    loader: Arc<FluentLanguageLoader>,
+    strategy: NegotiationStrategy,
+}
+
+impl LocalizationLayer {
+    /// Create a new `LocalizationLayer` for the `loader` shared by all
+    /// of the requests, which should have all of the available
+    /// languages loaded (e.g. using
+    /// [LanguageLoader::load_available_languages()](crate::LanguageLoader::load_available_languages())).
+    pub fn new(loader: impl Into<Arc<FluentLanguageLoader>>) -> Self {
+        Self {
+            loader: loader.into(),
+            strategy: NegotiationStrategy::Filtering,
+        }
+    }
+
+    /// Set the strategy used to negotiate the requested languages
+    /// against the loaded languages, by default
+    /// [NegotiationStrategy::Filtering].
+    pub fn with_strategy(mut self, strategy: NegotiationStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+}
+
+impl<S> Layer<S> for LocalizationLayer {
+    type Service = LocalizationService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        LocalizationService {
+            inner,
+            loader: self.loader.clone(),
+            strategy: self.strategy,
+        }
+    }
+}
+
+/// The [Service] created by the [LocalizationLayer], which inserts a
+/// [RequestLoader] into the extensions of each request before
+/// passing it to the `inner` service.
+#[derive(Debug, Clone)]
+pub struct LocalizationService<S> {
+    inner: S,
+    loader: Arc<FluentLanguageLoader>,
+    strategy: NegotiationStrategy,
+}
+
+impl<S, B> Service<Request<B>> for LocalizationService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        let requested_languages = requested_languages(request.headers());
+        let loader = self
+            .loader
+            .select_languages_negotiate(&requested_languages, self.strategy);
+        request.extensions_mut().insert(RequestLoader {
+            loader: Arc::new(loader),
+            requested_languages: requested_languages.into(),
+        });
+        self.inner.call(request)
+    }
+}
+
+/// The [FluentLanguageLoader] for the languages negotiated for a
+/// request by the [LocalizationLayer], which is inserted into the
+/// extensions of the request. It dereferences to the loader, so it
+/// can be used with the `fl!()` macro.
+#[derive(Debug, Clone)]
+pub struct RequestLoader {
+    loader: Arc<FluentLanguageLoader>,
+    requested_languages: Arc<[LanguageIdentifier]>,
+}
+
+impl RequestLoader {
+    /// The languages requested in the `Accept-Language` header of the
+    /// request, in order of preference.
+    pub fn requested_languages(&self) -> &[LanguageIdentifier] {
+        &self.requested_languages
+    }
+}
+
+impl Deref for RequestLoader {
+    type Target = FluentLanguageLoader;
+
+    fn deref(&self) -> &Self::Target {
+        &self.loader
+    }
+}
+
+/// The languages requested in the `Accept-Language` `headers`.
+fn requested_languages(headers: &HeaderMap) -> Vec<LanguageIdentifier> {
+    headers
+        .get_all(ACCEPT_LANGUAGE)
+        .iter()
+        .filter_map(|header| header.to_str().ok())
+        .flat_map(parse_accept_language)
+        .collect()
+}
@@ -0,0 +1,27 @@
+use crate::I18nEmbedError;
+use icu_collator::{Collator, CollatorOptions};
+use unic_langid::LanguageIdentifier;
+
+/// Create a [Collator] for comparing strings according to the sort
+/// order of the specified `language`, e.g. to sort a list of
+/// localized labels. If the `language` is not supported by the
+/// collation data, the root collation order is used.
+///
+/// ⚠️ *This API requires the following crate features to be activated: `icu-collator`.*
+pub fn collator(
+    language: &LanguageIdentifier,
+    options: CollatorOptions,
+) -> Result<Collator, I18nEmbedError> {
+    let locale = icu_locid::Locale::try_from_bytes(language.to_string().as_bytes())
+        .unwrap_or_else(|error| {
+            log::warn!(
+                target: "i18n_embed::collator",
+                "Unable to convert language \"{}\" to an ICU locale, using the root collation order: {}",
+                language,
+                error
+            );
+            icu_locid::Locale::UND
+        });
+    Collator::try_new(&(&locale).into(), options)
+        .map_err(|error| I18nEmbedError::Collator(language.clone(), error))
+}
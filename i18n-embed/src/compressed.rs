@@ -0,0 +1,99 @@
+use std::{borrow::Cow, collections::BTreeSet, io::Read};
+
+use flate2::read::GzDecoder;
+use rust_embed::RustEmbed;
+
+use crate::I18nAssets;
+
+/// The extension which is appended to the path of the gzip compressed
+/// files in a [CompressedRustEmbedAssets].
+pub const COMPRESSED_ASSET_EXTENSION: &str = ".gz";
+
+/// A wrapper for [`rust_embed::RustEmbed`] which embeds gzip
+/// compressed localization files, to reduce the size of binaries with
+/// many languages. The compressed files have the
+/// [COMPRESSED_ASSET_EXTENSION] appended to their path (e.g.
+/// `de-DE/my_crate.ftl.gz`), and are decompressed each time they are
+/// loaded, which makes them available as the uncompressed file (e.g.
+/// `de-DE/my_crate.ftl`). Files without the extension are provided
+/// as they are.
+///
+/// The compressed files can be created at build time with the
+/// `compress_assets()` function of the `i18n-build` crate.
+///
+/// ```ignore
+/// use i18n_embed::CompressedRustEmbedAssets;
+/// use rust_embed::RustEmbed;
+///
+/// #[derive(RustEmbed)]
+/// #[folder = "i18n-compressed"]
+/// struct Localizations;
+///
+/// let assets = CompressedRustEmbedAssets::<Localizations>::new();
+/// ```
+///
+/// ⚠️ *This type requires the following crate features to be activated: `compressed-assets`.*
+#[derive(Debug)]
+pub struct CompressedRustEmbedAssets<T: RustEmbed> {
+    embed: core::marker::PhantomData<T>,
+}
+
+impl<T: RustEmbed> CompressedRustEmbedAssets<T> {
+    /// Construct a new [`CompressedRustEmbedAssets`].
+    pub fn new() -> Self {
+        Self {
+            embed: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: RustEmbed> Default for CompressedRustEmbedAssets<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> I18nAssets for CompressedRustEmbedAssets<T>
+where
+    T: RustEmbed,
+{
+    fn get_files(&self, file_path: &str) -> Vec<Cow<'_, [u8]>> {
+        let compressed = match T::get(&format!("{file_path}{COMPRESSED_ASSET_EXTENSION}")) {
+            Some(file) => file.data,
+            None => {
+                return T::get(file_path)
+                    .map(|file| file.data)
+                    .into_iter()
+                    .collect()
+            }
+        };
+
+        let mut contents = Vec::new();
+        match GzDecoder::new(&*compressed).read_to_end(&mut contents) {
+            Ok(_) => vec![Cow::from(contents)],
+            Err(e) => {
+                diagnostic!(
+                    error,
+                    target: "i18n_embed::assets",
+                    { path = file_path },
+                    "Unexpected error while decompressing localization asset file: {}",
+                    e
+                );
+                Vec::new()
+            }
+        }
+    }
+
+    fn filenames_iter(&self) -> Box<dyn Iterator<Item = String>> {
+        // A file may be embedded both compressed and uncompressed.
+        let filenames: BTreeSet<String> = T::iter()
+            .map(|filename| {
+                filename
+                    .strip_suffix(COMPRESSED_ASSET_EXTENSION)
+                    .unwrap_or(&filename)
+                    .to_string()
+            })
+            .collect();
+        Box::new(filenames.into_iter())
+    }
+}
@@ -0,0 +1,310 @@
+//! This module contains the types and functions to load translations
+//! from a single CSV file containing a table with a column for each
+//! language, for small teams which manage their translations in a
+//! spreadsheet.
+//!
+//! Most important is the [CsvLanguageLoader].
+//!
+//! ⚠️ *This module requires the following crate features to be activated: `csv-system`.*
+
+use std::{borrow::Cow, collections::HashMap, fmt::Display};
+
+use unic_langid::LanguageIdentifier;
+
+use crate::{table::LanguageTables, I18nAssets, I18nEmbedError, LanguageLoader};
+
+/// The translations in a CSV file.
+struct CsvTable {
+    /// The languages of the translation columns.
+    languages: Vec<LanguageIdentifier>,
+    /// The key of each row, and its translation for each of the
+    /// `languages` (if not empty).
+    rows: Vec<(String, Vec<Option<String>>)>,
+}
+
+impl CsvTable {
+    fn parse(path: &str, file: &[u8]) -> Result<Self, I18nEmbedError> {
+        let csv_error = |error| I18nEmbedError::ErrorParsingCsv(path.to_string(), error);
+        let mut reader = ::csv::ReaderBuilder::new().flexible(true).from_reader(file);
+
+        // The index of each translation column, and its language.
+        let mut columns: Vec<(usize, LanguageIdentifier)> = Vec::new();
+        for (index, header) in reader.headers().map_err(csv_error)?.iter().enumerate() {
+            let header = header.trim();
+            if index == 0 || header.is_empty() || header.starts_with('#') {
+                continue;
+            }
+            let language = header
+                .parse()
+                .map_err(|error| I18nEmbedError::ErrorParsingLocale(header.to_string(), error))?;
+            columns.push((index, language));
+        }
+
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            let record = record.map_err(csv_error)?;
+            let key = match record.get(0).map(str::trim) {
+                Some(key) if !key.is_empty() => key.to_string(),
+                _ => continue,
+            };
+            let translations = columns
+                .iter()
+                .map(|(index, _)| {
+                    record
+                        .get(*index)
+                        .filter(|translation| !translation.is_empty())
+                        .map(ToString::to_string)
+                })
+                .collect();
+            rows.push((key, translations));
+        }
+
+        Ok(Self {
+            languages: columns.into_iter().map(|(_, language)| language).collect(),
+            rows,
+        })
+    }
+
+    /// The translations for the `language`, or `None` if the table
+    /// has no column for it.
+    fn translations(&self, language: &LanguageIdentifier) -> Option<HashMap<String, String>> {
+        let column = self.languages.iter().position(|l| l == language)?;
+        Some(
+            self.rows
+                .iter()
+                .filter_map(|(key, translations)| {
+                    translations[column]
+                        .clone()
+                        .map(|translation| (key.clone(), translation))
+                })
+                .collect(),
+        )
+    }
+}
+
+/// [LanguageLoader] implementation for translations in a single CSV
+/// file at `{domain}.csv` in the assets. The first row of the file
+/// contains the headers: the first column contains the keys of the
+/// messages, and the header of each of the other columns is the
+/// language of the translations in the column (e.g. `de-AT`).
+/// Columns with an empty header, or one starting with `#`, are
+/// ignored (e.g. `# notes for translators`).
+///
+/// ```csv
+/// key,en,de,# notes
+/// greeting,Hello {name}!,Hallo {name}!,Shown on the start page
+/// goodbye,Goodbye,,
+/// ```
+///
+/// Empty cells are missing translations, which fall back to the next
+/// of the loaded languages. The column for the fallback language is
+/// required.
+///
+/// The file can be embedded in the application (e.g. using
+/// `RustEmbed`), or read from the disk using `FileSystemAssets`, in
+/// which case the translations can be reloaded when the file changes
+/// using `DefaultLocalizer::with_autoreload()` (with the `autoreload`
+/// feature).
+///
+/// ⚠️ *This API requires the following crate features to be activated: `csv-system`.*
+#[derive(Debug)]
+pub struct CsvLanguageLoader {
+    tables: LanguageTables<String, String>,
+    domain: String,
+    fallback_language: LanguageIdentifier,
+}
+
+impl CsvLanguageLoader {
+    /// Create a new `CsvLanguageLoader` for the `domain`.
+    pub fn new(domain: impl Into<String>, fallback_language: LanguageIdentifier) -> Self {
+        Self {
+            tables: LanguageTables::new(&fallback_language),
+            domain: domain.into(),
+            fallback_language,
+        }
+    }
+
+    /// Get the message with the `key` from the first of the loaded
+    /// languages which has it. If the message is not available in
+    /// any of them, an error is logged and the `key` is returned.
+    pub fn get(&self, key: &str) -> String {
+        self.tables
+            .lookup(key, |_, value| value.clone())
+            .unwrap_or_else(|| {
+                log::error!(
+                    target: "i18n_embed::csv",
+                    "Unable to find the message \"{0}\" in the \"{1}\" domain",
+                    key,
+                    self.domain
+                );
+                key.to_string()
+            })
+    }
+
+    /// Get the message with the `key`, see [CsvLanguageLoader::get()],
+    /// replacing each `{name}` placeholder with the argument of the
+    /// same name in `args`. Placeholders without an argument are left
+    /// unchanged.
+    pub fn get_args<K, V>(&self, key: &str, args: &HashMap<K, V>) -> String
+    where
+        K: AsRef<str>,
+        V: Display,
+    {
+        let message = self.get(key);
+        let args: HashMap<&str, &V> = args.iter().map(|(k, v)| (k.as_ref(), v)).collect();
+
+        let mut formatted = String::with_capacity(message.len());
+        let mut rest = message.as_str();
+        while let Some(start) = rest.find('{') {
+            formatted.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let end = match rest.find('}') {
+                Some(end) => end,
+                None => break,
+            };
+            match args.get(rest[1..end].trim()) {
+                Some(arg) => formatted.push_str(&arg.to_string()),
+                None => formatted.push_str(&rest[..=end]),
+            }
+            rest = &rest[end + 1..];
+        }
+        formatted.push_str(rest);
+        formatted
+    }
+
+    /// Whether the message with the `key` is available in any of the
+    /// loaded languages.
+    pub fn has(&self, key: &str) -> bool {
+        self.tables.contains(key)
+    }
+
+    /// Parse the CSV files for this loader's domain in the
+    /// `i18n_assets`, in order of preference.
+    fn csv_tables(&self, i18n_assets: &dyn I18nAssets) -> Result<Vec<CsvTable>, I18nEmbedError> {
+        let path = self.language_file_name();
+        i18n_assets
+            .get_files(&path)
+            .iter()
+            .map(|file| CsvTable::parse(&path, file))
+            .collect()
+    }
+}
+
+impl LanguageLoader for CsvLanguageLoader {
+    /// The fallback language, which is required to have a column in
+    /// the CSV file.
+    fn fallback_language(&self) -> &LanguageIdentifier {
+        &self.fallback_language
+    }
+
+    /// The domain for the translation that this loader is associated with.
+    fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// The language file name to use for this loader's domain.
+    fn language_file_name(&self) -> String {
+        format!("{}.csv", self.domain)
+    }
+
+    /// The CSV file contains the translations for all the languages,
+    /// so this is the CSV file if it has a column for the
+    /// `language_id`.
+    fn language_files<'a>(
+        &self,
+        language_id: &LanguageIdentifier,
+        i18n_assets: &'a dyn I18nAssets,
+    ) -> (String, Vec<Cow<'a, [u8]>>) {
+        let path = self.language_file_name();
+        let files = i18n_assets
+            .get_files(&path)
+            .into_iter()
+            .filter(|file| {
+                CsvTable::parse(&path, file)
+                    .is_ok_and(|table| table.languages.contains(language_id))
+            })
+            .collect();
+        (path, files)
+    }
+
+    /// The languages with a column in the CSV file.
+    fn available_languages(
+        &self,
+        i18n_assets: &dyn I18nAssets,
+    ) -> Result<Vec<LanguageIdentifier>, I18nEmbedError> {
+        let mut languages = vec![self.fallback_language.clone()];
+        for table in self.csv_tables(i18n_assets)? {
+            for language in table.languages {
+                if !languages.contains(&language) {
+                    languages.push(language);
+                }
+            }
+        }
+        Ok(languages)
+    }
+
+    /// Get the language which is currently loaded for this loader.
+    fn current_language(&self) -> LanguageIdentifier {
+        self.tables.current_language()
+    }
+
+    /// Load the languages `language_ids` using the CSV file in the
+    /// `i18n_assets` in order of fallback preference. This also sets
+    /// the [LanguageLoader::current_language()] to the first in the
+    /// `language_ids` slice. You can use [select()](super::select())
+    /// to determine which fallbacks are actually available for an
+    /// arbitrary slice of preferences.
+    fn load_languages(
+        &self,
+        i18n_assets: &dyn I18nAssets,
+        language_ids: &[LanguageIdentifier],
+    ) -> Result<(), I18nEmbedError> {
+        if language_ids.is_empty() {
+            return Err(I18nEmbedError::RequestedLanguagesEmpty);
+        }
+
+        let mut load_language_ids = language_ids.to_vec();
+        if !load_language_ids.contains(&self.fallback_language) {
+            load_language_ids.push(self.fallback_language.clone());
+        }
+
+        let csv_tables = self.csv_tables(i18n_assets)?;
+        let mut tables = Vec::new();
+        for language in &load_language_ids {
+            let mut entries: Option<HashMap<String, String>> = None;
+            for translations in csv_tables
+                .iter()
+                .filter_map(|table| table.translations(language))
+            {
+                let entries = entries.get_or_insert_with(HashMap::new);
+                for (key, translation) in translations {
+                    entries.entry(key).or_insert(translation);
+                }
+            }
+
+            match entries {
+                Some(entries) => tables.push((language.clone(), entries)),
+                None if language == &self.fallback_language => {
+                    return Err(I18nEmbedError::LanguageNotAvailable(
+                        self.language_file_name(),
+                        language.clone(),
+                    ));
+                }
+                None => log::debug!(
+                    target: "i18n_embed::csv",
+                    "There is no column for language \"{0}\" in \"{1}\"",
+                    language,
+                    self.language_file_name()
+                ),
+            }
+        }
+
+        self.tables.set(load_language_ids, tables);
+        Ok(())
+    }
+
+    fn reload(&self, i18n_assets: &dyn I18nAssets) -> Result<(), I18nEmbedError> {
+        let language_ids = self.tables.languages();
+        self.load_languages(i18n_assets, &language_ids)
+    }
+}
@@ -0,0 +1,54 @@
+//! Macros for the diagnostics emitted by the loaders, which use the
+//! [tracing](https://crates.io/crates/tracing) crate when the
+//! `tracing` feature is enabled, otherwise the
+//! [log](https://crates.io/crates/log) crate.
+
+/// Emit a diagnostic event at the `level` (`error`, `warn`, `info`,
+/// `debug` or `trace`) for the `target`, with the message formatted
+/// from the remaining arguments. When the `tracing` feature is
+/// enabled, the values of the `fields` are recorded on the event
+/// (formatted using [Display](std::fmt::Display)), so that they can
+/// be correlated with the span of the request which caused it.
+/// Otherwise the event is logged, and the `fields` are only in the
+/// message.
+///
+/// ```ignore
+/// diagnostic!(error, target: "i18n_embed::fluent", { message_id = id },
+///     "Unable to find localization for id \"{}\".", id);
+/// ```
+#[cfg(feature = "tracing")]
+macro_rules! diagnostic {
+    ($level:ident, target: $target:expr, { $($field:ident = $value:expr),* $(,)? }, $($arg:tt)+) => {
+        ::tracing::$level!(target: $target, $($field = %$value,)* $($arg)+)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! diagnostic {
+    ($level:ident, target: $target:expr, { $($field:ident = $value:expr),* $(,)? }, $($arg:tt)+) => {
+        ::log::$level!(target: $target, $($arg)+)
+    };
+}
+
+/// Enter a debug level span with the `name` and fields (using the
+/// syntax of [tracing::span!()](https://docs.rs/tracing/latest/tracing/macro.span.html))
+/// when the `tracing` feature is enabled. The span is exited when the
+/// returned guard is dropped.
+#[cfg(feature = "tracing")]
+macro_rules! diagnostic_span {
+    ($name:literal $(, $($fields:tt)*)?) => {
+        ::tracing::debug_span!($name $(, $($fields)*)?).entered()
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! diagnostic_span {
+    ($name:literal $(, $($fields:tt)*)?) => {
+        $crate::diagnostics::NoSpan
+    };
+}
+
+/// The guard returned by `diagnostic_span!()` when the `tracing`
+/// feature is disabled.
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct NoSpan;
@@ -0,0 +1,56 @@
+use unic_langid::{CharacterDirection, LanguageIdentifier};
+
+/// The direction in which the text of a language is written, used by
+/// user interfaces to mirror their layout for right-to-left languages
+/// such as Arabic or Hebrew, see
+/// [LanguageLoader::text_direction()](crate::LanguageLoader::text_direction()).
+///
+/// ```
+/// use i18n_embed::TextDirection;
+///
+/// assert_eq!(TextDirection::RightToLeft, TextDirection::of(&"ar".parse().unwrap()));
+/// assert_eq!(TextDirection::RightToLeft, TextDirection::of(&"az-Arab".parse().unwrap()));
+/// assert_eq!(TextDirection::LeftToRight, TextDirection::of(&"en-US".parse().unwrap()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextDirection {
+    /// Left-to-right, e.g. English.
+    LeftToRight,
+    /// Right-to-left, e.g. Arabic or Hebrew.
+    RightToLeft,
+    /// Top-to-bottom, e.g. Mongolian in its traditional script.
+    TopToBottom,
+}
+
+impl TextDirection {
+    /// The direction of the `language`, determined by its script, or
+    /// by its language subtag if it has no script.
+    pub fn of(language: &LanguageIdentifier) -> Self {
+        language.character_direction().into()
+    }
+
+    /// Whether the text is written right-to-left.
+    pub fn is_right_to_left(self) -> bool {
+        self == Self::RightToLeft
+    }
+
+    /// The value of the HTML `dir` attribute for this direction,
+    /// either `ltr` or `rtl`. Top-to-bottom text has no `dir`, so it
+    /// is `ltr`, which is the direction of its lines.
+    pub fn html_dir(self) -> &'static str {
+        match self {
+            Self::RightToLeft => "rtl",
+            Self::LeftToRight | Self::TopToBottom => "ltr",
+        }
+    }
+}
+
+impl From<CharacterDirection> for TextDirection {
+    fn from(direction: CharacterDirection) -> Self {
+        match direction {
+            CharacterDirection::LTR => Self::LeftToRight,
+            CharacterDirection::RTL => Self::RightToLeft,
+            CharacterDirection::TTB => Self::TopToBottom,
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! This module contains a language selector widget for desktop
+//! applications built with [egui](https://docs.rs/egui).
+//!
+//! The [LanguageSelector] is a drop-down menu of the
+//! [available languages](Localizer::available_languages()) of a
+//! [Localizer], displayed using their native names (see
+//! [locale_presentation()]), which selects the chosen language when it
+//! is clicked:
+//!
+//! ```ignore
+//! use i18n_embed::egui::LanguageSelector;
+//!
+//! let localizer = DefaultLocalizer::new(&*LANGUAGE_LOADER, &Localizations);
+//!
+//! egui::CentralPanel::default().show(ctx, |ui| {
+//!     if ui.add(LanguageSelector::new(&localizer)).changed() {
+//!         // Another language was selected.
+//!     }
+//! });
+//! ```
+//!
+//! ⚠️ *This module requires the following crate features to be activated: `egui`.*
+
+#[cfg(feature = "fluent-system")]
+use ::egui::RichText;
+use ::egui::{ComboBox, Id, Response, Ui, Widget, WidgetText};
+use unic_langid::LanguageIdentifier;
+
+use crate::{locale_presentation, Localizer};
+
+/// A drop-down menu for selecting one of the available languages of
+/// a [Localizer], see the [module documentation](self).
+///
+/// The [Response] of the widget is marked as changed when another
+/// language has been selected.
+pub struct LanguageSelector<'a> {
+    localizer: &'a dyn Localizer,
+    id_salt: Id,
+    label: Option<WidgetText>,
+}
+
+impl std::fmt::Debug for LanguageSelector<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LanguageSelector")
+            .field("id_salt", &self.id_salt)
+            .field("label", &self.label.as_ref().map(WidgetText::text))
+            .finish()
+    }
+}
+
+impl<'a> LanguageSelector<'a> {
+    /// Create a new `LanguageSelector` for the languages of the
+    /// `localizer`.
+    pub fn new(localizer: &'a dyn Localizer) -> Self {
+        Self {
+            localizer,
+            id_salt: Id::new("i18n_embed_language_selector"),
+            label: None,
+        }
+    }
+
+    /// Set the source of the id of the widget, which needs to be
+    /// unique when there is more than one `LanguageSelector` in the
+    /// same [Ui].
+    pub fn id_salt(mut self, id_salt: impl std::hash::Hash) -> Self {
+        self.id_salt = Id::new(id_salt);
+        self
+    }
+
+    /// Display a `label` next to the drop-down menu.
+    pub fn label(mut self, label: impl Into<WidgetText>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+
+impl Widget for LanguageSelector<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let current_language = self.localizer.language_loader().current_language();
+        let languages = self
+            .localizer
+            .available_languages()
+            .unwrap_or_else(|error| {
+                log::error!("Unable to obtain the available languages: {}", error);
+                vec![current_language.clone()]
+            });
+
+        let combo_box = match self.label {
+            Some(label) => ComboBox::new(self.id_salt, label),
+            None => ComboBox::from_id_salt(self.id_salt),
+        };
+        let mut selected_language = None;
+        let mut response = combo_box
+            .selected_text(display_name(&current_language))
+            .show_ui(ui, |ui| {
+                for language in languages {
+                    let selected = language == current_language;
+                    if ui
+                        .selectable_label(selected, display_name(&language))
+                        .clicked()
+                        && !selected
+                    {
+                        selected_language = Some(language);
+                    }
+                }
+            })
+            .response;
+
+        if let Some(language) = selected_language {
+            match self.localizer.select(&[language]) {
+                Ok(_) => response.mark_changed(),
+                Err(error) => log::error!("Unable to select the language: {}", error),
+            }
+        }
+
+        response
+    }
+}
+
+/// Display a message obtained using `fl!()` without arguments (see
+/// [LocalizedStr](crate::fluent::LocalizedStr)) in a widget.
+#[cfg(feature = "fluent-system")]
+impl From<crate::fluent::LocalizedStr> for RichText {
+    fn from(text: crate::fluent::LocalizedStr) -> Self {
+        RichText::new(text.into_owned())
+    }
+}
+
+#[cfg(feature = "fluent-system")]
+impl From<crate::fluent::LocalizedStr> for WidgetText {
+    fn from(text: crate::fluent::LocalizedStr) -> Self {
+        RichText::from(text).into()
+    }
+}
+
+/// The name of the `language` displayed in the selector: its native
+/// name, qualified by the language identifier when it has more than a
+/// language subtag (e.g. "English (en-GB)"), or just the identifier
+/// when the native name is unknown.
+fn display_name(language: &LanguageIdentifier) -> String {
+    let qualified = language.region.is_some()
+        || language.script.is_some()
+        || language.variants().next().is_some();
+    match locale_presentation(language).native_name {
+        Some(native_name) if qualified => format!("{} ({})", native_name, language),
+        Some(native_name) => native_name,
+        None => language.to_string(),
+    }
+}
@@ -5,44 +5,217 @@
 //!
 //! ⚠️ *This module requires the following crate features to be activated: `fluent-system`.*
 
-use crate::{I18nAssets, I18nEmbedError, LanguageLoader};
+#[cfg(feature = "cache")]
+use crate::message_cache::MessageCache;
+use crate::{
+    I18nAssets, I18nEmbedError, LanguageAliases, LanguageLoader, ListStyle, LocalizedTemplate,
+    PathTemplate, Pseudolocalization, RegionContext, RelativeTime,
+};
 
 use arc_swap::ArcSwap;
+/// The value of a message argument, re-exported from the
+/// [fluent](https://crates.io/crates/fluent) crate.
+pub use fluent::FluentValue;
 pub use fluent_langneg::NegotiationStrategy;
-pub use i18n_embed_impl::fluent_language_loader;
+pub use i18n_embed_impl::{fluent_language_loader, static_loader};
 
-use fluent::{
-    bundle::FluentBundle, FluentArgs, FluentAttribute, FluentMessage, FluentResource, FluentValue,
-};
-use fluent_syntax::ast::{self, Pattern};
+use fluent::{bundle::FluentBundle, FluentArgs, FluentError, FluentMessage, FluentResource};
+use fluent_syntax::ast;
 use intl_memoizer::concurrent::IntlLangMemoizer;
-use parking_lot::RwLock;
-use std::{borrow::Cow, collections::HashMap, fmt::Debug, iter::FromIterator, sync::Arc};
+use parking_lot::{Mutex, RwLock};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    iter::FromIterator,
+    num::{NonZeroU32, NonZeroUsize},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock,
+    },
+    time::{Duration, Instant},
+};
 use unic_langid::LanguageIdentifier;
+use yoke::Yoke;
+
+/// Configuration applied to the bundles of a loader using
+/// [FluentLanguageLoader::with_bundles_mut()].
+type BundleConfiguration =
+    Arc<dyn Fn(&mut FluentBundle<Arc<FluentResource>, IntlLangMemoizer>) + Send + Sync>;
 
 struct LanguageBundle {
     language: LanguageIdentifier,
+    /// The namespace of the messages in this bundle, `None` for the
+    /// messages in the `{domain}.ftl` file, or `Some("feature")` for
+    /// the messages in a `{domain}/feature.ftl` file.
+    namespace: Option<String>,
     bundle: FluentBundle<Arc<FluentResource>, IntlLangMemoizer>,
     resource: Arc<FluentResource>,
+    /// The shared terms of the language from its [TERMS_FILE], which
+    /// are added to the bundle after the `resource`.
+    terms: Option<Arc<FluentResource>>,
+    pseudolocalization: Option<Pseudolocalization>,
+    /// The configuration which has been applied to the bundle, in
+    /// order, so that it can be applied again when the bundle is
+    /// re-created from its resource.
+    configuration: Vec<BundleConfiguration>,
 }
 
 impl LanguageBundle {
-    fn new(language: LanguageIdentifier, resource: FluentResource) -> Self {
+    /// Create a bundle for the `resource` and the shared `terms` of
+    /// the language, appending the errors which occur while adding
+    /// them to the bundle (such as messages which are defined more
+    /// than once) to `errors`.
+    fn new(
+        language: LanguageIdentifier,
+        namespace: Option<String>,
+        resource: FluentResource,
+        terms: Option<Arc<FluentResource>>,
+        pseudolocalization: Option<Pseudolocalization>,
+        errors: &mut Vec<FluentError>,
+    ) -> Self {
+        let mut bundle =
+            Self::from_shared_resource(language, namespace, Arc::new(resource), terms, errors);
+        bundle.set_pseudolocalization(pseudolocalization);
+        bundle
+    }
+
+    fn from_shared_resource(
+        language: LanguageIdentifier,
+        namespace: Option<String>,
+        resource: Arc<FluentResource>,
+        terms: Option<Arc<FluentResource>>,
+        errors: &mut Vec<FluentError>,
+    ) -> Self {
         let mut bundle = FluentBundle::new_concurrent(vec![language.clone()]);
-        let resource = Arc::new(resource);
-        if let Err(errors) = bundle.add_resource(resource.clone()) {
-            errors.iter().for_each(|error | {
-                log::error!(target: "i18n_embed::fluent", "Error while adding resource to bundle: {0:?}.", error);
-            })
+        if let Err(add_errors) = bundle.add_resource(resource.clone()) {
+            add_errors.iter().for_each(|error | {
+                diagnostic!(error, target: "i18n_embed::fluent", { language = language }, "Error while adding resource to bundle: {0:?}.", error);
+            });
+            errors.extend(add_errors);
+        }
+        if let Some(terms) = &terms {
+            // The entries defined in the resource take precedence over
+            // the shared terms, so they are not overridden.
+            let _ = bundle.add_resource(terms.clone());
         }
         Self {
             language,
+            namespace,
             bundle,
             resource,
+            terms,
+            pseudolocalization: None,
+            configuration: Vec::new(),
+        }
+    }
+
+    /// Pseudolocalize the messages formatted using this bundle. The
+    /// text of the messages is accented using the transform of the
+    /// fluent bundle, so that their placeables are left intact.
+    fn set_pseudolocalization(&mut self, pseudolocalization: Option<Pseudolocalization>) {
+        self.pseudolocalization = pseudolocalization;
+        let accents =
+            pseudolocalization.is_some_and(|pseudolocalization| pseudolocalization.accents);
+        self.bundle
+            .set_transform(accents.then_some(crate::pseudo::accent as fn(&str) -> Cow<'_, str>));
+    }
+
+    /// Get the message referenced by the `message_id`, which may be
+    /// prefixed with its namespace (e.g. `feature.message-id`), if
+    /// it is present in this bundle.
+    fn get_message(&self, message_id: &str) -> Option<FluentMessage<'_>> {
+        let (namespace, message_id) = split_message_id(message_id);
+        if self.namespace.as_deref() == namespace {
+            self.bundle.get_message(message_id)
+        } else {
+            None
+        }
+    }
+
+    /// Format the message referenced by the `message_id` (or its
+    /// attribute referenced by `attribute_id`) with the specified
+    /// `args`, if it is present in this bundle. Formatting errors are
+    /// logged as occurring for the `current_language`.
+    fn format<'args>(
+        &self,
+        message_id: &str,
+        attribute_id: Option<&str>,
+        args: Option<&'args FluentArgs<'args>>,
+        current_language: &LanguageIdentifier,
+    ) -> Option<String> {
+        let pattern = self.pattern(message_id, attribute_id)?;
+        let mut errors = Vec::new();
+        let value = self.bundle.format_pattern(pattern, args, &mut errors);
+        if !errors.is_empty() {
+            diagnostic!(
+                error,
+                target: "i18n_embed::fluent",
+                { language = current_language, message_id = message_id },
+                "Failed to format a message for language \"{}\" and id \"{}\".\nErrors\n{:?}.",
+                current_language, message_id, errors
+            )
+        }
+        Some(match &self.pseudolocalization {
+            Some(pseudolocalization) => pseudolocalization.wrap(&value),
+            None => value.into(),
+        })
+    }
+
+    /// The text of the message referenced by the `message_id` (or
+    /// its attribute referenced by `attribute_id`), borrowed from the
+    /// parsed resource, if it is present in this bundle and consists
+    /// of a single text element which is left unchanged by the
+    /// transform of the bundle. Other messages need to be formatted.
+    fn text(&self, message_id: &str, attribute_id: Option<&str>) -> Option<&str> {
+        if self.pseudolocalization.is_some() {
+            return None;
+        }
+        let pattern = self.pattern(message_id, attribute_id)?;
+        if !matches!(
+            pattern.elements.as_slice(),
+            [ast::PatternElement::TextElement { .. }]
+        ) {
+            return None;
+        }
+        match self.bundle.format_pattern(pattern, None, &mut Vec::new()) {
+            Cow::Borrowed(text) => Some(text),
+            Cow::Owned(_) => None,
+        }
+    }
+
+    /// The pattern of the message referenced by the `message_id` (or
+    /// its attribute referenced by `attribute_id`), if it is present
+    /// in this bundle.
+    fn pattern(&self, message_id: &str, attribute_id: Option<&str>) -> Option<&ast::Pattern<&str>> {
+        let message = self.get_message(message_id)?;
+        match attribute_id {
+            Some(attribute_id) => Some(message.get_attribute(attribute_id)?.value()),
+            None => message.value(),
+        }
+    }
+
+    /// The id of the `message` including the namespace of this
+    /// bundle, as it is referenced when using the loader.
+    fn message_id(&self, message: &ast::Message<&str>) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("{}.{}", namespace, message.id.name),
+            None => message.id.name.to_string(),
         }
     }
 }
 
+/// Split a `message_id` referencing a message in a namespace, such as
+/// `feature.message-id` into its namespace and the id of the message
+/// within that namespace. Fluent message ids cannot contain a `.`, so
+/// the namespace is everything before the last `.`.
+fn split_message_id(message_id: &str) -> (Option<&str>, &str) {
+    match message_id.rsplit_once('.') {
+        Some((namespace, message_id)) => (Some(namespace), message_id),
+        None => (None, message_id),
+    }
+}
+
 impl Debug for LanguageBundle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "LanguageBundle(language: {})", self.language)
@@ -54,7 +227,7 @@ struct LanguageConfig {
     /// Storage for language localization resources. Outer `Vec` is per language (as specified in
     /// [`LanguageConfig::language_map`]), inner Vec is for storage of multiple bundles per
     /// language, in order of priority (highest to lowest).
-    language_bundles: Vec<Vec<LanguageBundle>>,
+    language_bundles: Vec<Vec<Arc<LanguageBundle>>>,
     /// This maps a `LanguageIdentifier` to the index inside the
     /// `language_bundles` vector.
     language_map: HashMap<LanguageIdentifier, usize>,
@@ -73,29 +246,543 @@ struct CurrentLanguages {
 struct FluentLanguageLoaderInner {
     language_config: Arc<RwLock<LanguageConfig>>,
     current_languages: CurrentLanguages,
+    /// Messages formatted using the `current_languages`, which is
+    /// replaced along with them.
+    #[cfg(feature = "cache")]
+    message_cache: MessageCache,
+}
+
+/// A problem found in a fluent file while loading it, see
+/// [FluentLanguageLoader::load_languages_with_diagnostics()].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceDiagnostic {
+    /// The language of the file.
+    pub language: LanguageIdentifier,
+    /// The path of the file within the assets.
+    pub path: String,
+    /// The problem: a [FluentError::ParserError] for a syntax error,
+    /// or a [FluentError::Overriding] for a message or term which is
+    /// defined more than once.
+    pub error: FluentError,
+}
+
+impl std::fmt::Display for ResourceDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\"{}\": {}", self.path, self.error)
+    }
+}
+
+/// A localized message obtained using
+/// [FluentLanguageLoader::get_cow()] or
+/// [FluentLanguageLoader::get_attr_cow()]. The text of messages
+/// without placeables is borrowed from the loaded fluent resource,
+/// which is kept alive (even if the languages are reloaded) until
+/// this is dropped, so obtaining it does not allocate. Other
+/// messages are formatted into an owned [String].
+///
+/// This dereferences to a [str], and converts into a [String] using
+/// [LocalizedStr::into_owned()] or [Into].
+#[derive(Clone)]
+pub struct LocalizedStr(Yoke<Cow<'static, str>, Option<Arc<LanguageBundle>>>);
+
+impl LocalizedStr {
+    /// Borrow the text of the message referenced by the `message_id`
+    /// (or its attribute referenced by `attribute_id`) from the
+    /// `language_bundle`, if it is a single text element.
+    fn borrowed(
+        language_bundle: &Arc<LanguageBundle>,
+        message_id: &str,
+        attribute_id: Option<&str>,
+    ) -> Option<Self> {
+        Yoke::try_attach_to_cart(language_bundle.clone(), |language_bundle| {
+            language_bundle
+                .text(message_id, attribute_id)
+                .map(Cow::Borrowed)
+                .ok_or(())
+        })
+        .ok()
+        .map(|text| Self(text.wrap_cart_in_option()))
+    }
+
+    /// The text, either borrowed from the fluent resource, or owned.
+    pub fn as_cow(&self) -> &Cow<'_, str> {
+        self.0.get()
+    }
+
+    /// Convert this into an owned [String], which only allocates if
+    /// the text is borrowed.
+    pub fn into_owned(self) -> String {
+        self.as_cow().clone().into_owned()
+    }
+}
+
+impl From<String> for LocalizedStr {
+    fn from(text: String) -> Self {
+        Self(Yoke::new_owned(Cow::Owned(text)))
+    }
+}
+
+impl From<LocalizedStr> for String {
+    fn from(text: LocalizedStr) -> Self {
+        text.into_owned()
+    }
+}
+
+impl std::ops::Deref for LocalizedStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_cow()
+    }
+}
+
+impl AsRef<str> for LocalizedStr {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl Debug for LocalizedStr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl std::fmt::Display for LocalizedStr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl PartialEq for LocalizedStr {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for LocalizedStr {}
+
+impl PartialEq<str> for LocalizedStr {
+    fn eq(&self, other: &str) -> bool {
+        &**self == other
+    }
+}
+
+impl PartialEq<&str> for LocalizedStr {
+    fn eq(&self, other: &&str) -> bool {
+        &**self == *other
+    }
+}
+
+impl PartialEq<String> for LocalizedStr {
+    fn eq(&self, other: &String) -> bool {
+        &**self == other
+    }
+}
+
+impl PartialEq<LocalizedStr> for str {
+    fn eq(&self, other: &LocalizedStr) -> bool {
+        self == &**other
+    }
+}
+
+impl PartialEq<LocalizedStr> for &str {
+    fn eq(&self, other: &LocalizedStr) -> bool {
+        *self == &**other
+    }
+}
+
+impl PartialEq<LocalizedStr> for String {
+    fn eq(&self, other: &LocalizedStr) -> bool {
+        self == &**other
+    }
+}
+
+/// Counters recorded by a [FluentLanguageLoader] while lookup
+/// tracing is enabled, see
+/// [FluentLanguageLoader::enable_lookup_tracing()].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LookupStats {
+    /// For each message id, the number of lookups which were served
+    /// by each language in the fallback chain. Attribute lookups are
+    /// recorded using the id `message-id.attribute-id`.
+    pub served: HashMap<String, HashMap<LanguageIdentifier, usize>>,
+    /// For each message id, the number of lookups which could not be
+    /// served by any of the loaded languages.
+    pub missing: HashMap<String, usize>,
+}
+
+impl LookupStats {
+    /// The ids of messages which were served at least once by a
+    /// language other than the specified `language` (usually the
+    /// [LanguageLoader::current_language()]), along with the language
+    /// which served them and the number of times it did so.
+    pub fn fallbacks<'a>(
+        &'a self,
+        language: &'a LanguageIdentifier,
+    ) -> impl Iterator<Item = (&'a str, &'a LanguageIdentifier, usize)> + 'a {
+        self.served.iter().flat_map(move |(message_id, languages)| {
+            languages
+                .iter()
+                .filter(move |(served_by, _)| *served_by != language)
+                .map(move |(served_by, count)| (message_id.as_str(), served_by, *count))
+        })
+    }
+}
+
+/// Lookup tracing state, shared between a loader and the loaders
+/// created from it using [FluentLanguageLoader::select_languages()].
+#[derive(Debug, Default)]
+struct LookupTracing {
+    enabled: AtomicBool,
+    stats: Mutex<LookupStats>,
+}
+
+impl LookupTracing {
+    fn record(&self, id: &dyn Fn() -> String, language: Option<&LanguageIdentifier>) {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut stats = self.stats.lock();
+        match language {
+            Some(language) => {
+                *stats
+                    .served
+                    .entry(id())
+                    .or_default()
+                    .entry(language.clone())
+                    .or_default() += 1;
+            }
+            None => *stats.missing.entry(id()).or_default() += 1,
+        }
+    }
+}
+
+/// How a [FluentLanguageLoader] logs the messages which couldn't be
+/// found in any of the loaded languages, set using
+/// [FluentLanguageLoader::set_log_policy()].
+///
+/// ⚠️ *This API requires the following crate features to be activated: `fluent-system`.*
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogPolicy {
+    /// Log an error for every missing message.
+    #[default]
+    All,
+    /// Don't log missing messages.
+    Silent,
+    /// Log an error for one in every `n` missing messages, starting
+    /// with the first one.
+    Sample(NonZeroU32),
+    /// Log an error for at most `max` missing messages in each period
+    /// of the `interval`.
+    ///
+    /// **Note:** This uses [std::time::Instant], which is not
+    /// available on the `wasm32-unknown-unknown` target.
+    RateLimit {
+        /// The maximum number of errors logged in each `interval`.
+        max: u32,
+        /// The length of each period.
+        interval: Duration,
+    },
+    /// Log an error only the first time each message id is missing
+    /// for each language, or (if the `interval` is specified) at most
+    /// once per `interval` for each message id and language. The
+    /// number of times each message was missing is available using
+    /// [FluentLanguageLoader::missing_message_counts()].
+    ///
+    /// **Note:** Specifying an `interval` uses [std::time::Instant],
+    /// which is not available on the `wasm32-unknown-unknown` target.
+    Deduplicate {
+        /// The minimum time between errors logged for the same
+        /// message id and language, or `None` to only log the first
+        /// one.
+        interval: Option<Duration>,
+    },
+}
+
+/// A message (or attribute) which couldn't be found in any of the
+/// languages selected by a [FluentLanguageLoader] or
+/// [LoaderSnapshot], passed to its [MissingMessageHandler].
+///
+/// ⚠️ *This API requires the following crate features to be activated: `fluent-system`.*
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingMessage<'a> {
+    /// The domain of the loader.
+    pub domain: &'a str,
+    /// The language which was selected when the message was
+    /// requested.
+    pub language: &'a LanguageIdentifier,
+    /// The id of the missing message.
+    pub message_id: &'a str,
+    /// The id of the missing attribute, if an attribute of the
+    /// message was requested.
+    pub attribute_id: Option<&'a str>,
+}
+
+impl MissingMessage<'_> {
+    /// The text which is returned in place of the missing message
+    /// when no [MissingMessageHandler] has been set, e.g. `No
+    /// localization for id: "hello-world"`.
+    pub fn placeholder(&self) -> String {
+        match self.attribute_id {
+            Some(attribute_id) => format!(
+                "No localization for message id: \"{}\" and attribute id: \"{attribute_id}\"",
+                self.message_id
+            ),
+            None => format!("No localization for id: \"{}\"", self.message_id),
+        }
+    }
+}
+
+/// Chooses the text which a [FluentLanguageLoader] returns in place
+/// of a message which couldn't be found in any of the selected
+/// languages, set using
+/// [FluentLanguageLoader::set_missing_message_handler()]. This is
+/// implemented for closures, for example:
+///
+/// ```ignore
+/// // Show the message id rather than the default placeholder.
+/// loader.set_missing_message_handler(|missing: &MissingMessage<'_>| {
+///     missing.message_id.to_string()
+/// });
+///
+/// // Catch missing messages during development.
+/// #[cfg(debug_assertions)]
+/// loader.set_missing_message_handler(|missing: &MissingMessage<'_>| -> String {
+///     panic!("{}", missing.placeholder())
+/// });
+/// ```
+///
+/// The handler is called after the missing message has been counted
+/// and logged according to the [LogPolicy].
+///
+/// ⚠️ *This API requires the following crate features to be activated: `fluent-system`.*
+pub trait MissingMessageHandler: Send + Sync {
+    /// The text to return in place of the `missing` message.
+    fn handle(&self, missing: &MissingMessage<'_>) -> String;
+}
+
+impl<F> MissingMessageHandler for F
+where
+    F: Fn(&MissingMessage<'_>) -> String + Send + Sync,
+{
+    fn handle(&self, missing: &MissingMessage<'_>) -> String {
+        self(missing)
+    }
+}
+
+/// The [LogPolicy] for missing messages and its state, along with the
+/// [MissingMessageHandler], shared between a loader, the loaders
+/// created from it using [FluentLanguageLoader::select_languages()],
+/// and its snapshots.
+#[derive(Default)]
+struct MissingLog {
+    state: Mutex<MissingLogState>,
+    handler: RwLock<Option<Arc<dyn MissingMessageHandler>>>,
+}
+
+impl Debug for MissingLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MissingLog")
+            .field("state", &self.state)
+            .field(
+                "handler",
+                &self.handler.read().as_ref().map(|_| "<HANDLER>"),
+            )
+            .finish()
+    }
+}
+
+#[derive(Debug, Default)]
+struct MissingLogState {
+    policy: LogPolicy,
+    /// The number of missing messages since the policy was set, or
+    /// since the start of the current rate limiting period.
+    count: u64,
+    /// The start of the current rate limiting period.
+    period_start: Option<Instant>,
+    /// When an error was last logged for each message id and
+    /// language, for [LogPolicy::Deduplicate] with an interval.
+    logged: HashMap<(String, LanguageIdentifier), Instant>,
+    /// The number of times each message id was missing, for each
+    /// language.
+    missing: HashMap<String, HashMap<LanguageIdentifier, usize>>,
+}
+
+impl MissingLog {
+    /// Record that the message `id` is missing for the `language`,
+    /// returning whether it should be logged according to the policy.
+    fn should_log(&self, id: &str, language: &LanguageIdentifier) -> bool {
+        let mut state = self.state.lock();
+        let missing_count = {
+            let count = state
+                .missing
+                .entry(id.to_string())
+                .or_default()
+                .entry(language.clone())
+                .or_default();
+            *count += 1;
+            *count
+        };
+        match state.policy {
+            LogPolicy::All => true,
+            LogPolicy::Silent => false,
+            LogPolicy::Sample(n) => {
+                let log = state.count.is_multiple_of(u64::from(n.get()));
+                state.count += 1;
+                log
+            }
+            LogPolicy::RateLimit { max, interval } => {
+                let now = Instant::now();
+                if state
+                    .period_start
+                    .is_none_or(|start| now.duration_since(start) >= interval)
+                {
+                    state.period_start = Some(now);
+                    state.count = 0;
+                }
+                state.count += 1;
+                state.count <= u64::from(max)
+            }
+            LogPolicy::Deduplicate { interval: None } => missing_count == 1,
+            LogPolicy::Deduplicate {
+                interval: Some(interval),
+            } => {
+                let now = Instant::now();
+                let key = (id.to_string(), language.clone());
+                let log = state
+                    .logged
+                    .get(&key)
+                    .is_none_or(|logged| now.duration_since(*logged) >= interval);
+                if log {
+                    state.logged.insert(key, now);
+                }
+                log
+            }
+        }
+    }
+
+    /// Record and log the `missing` message, returning the text to
+    /// return in its place.
+    fn missing(&self, missing: &MissingMessage<'_>) -> String {
+        match missing.attribute_id {
+            None => {
+                if self.should_log(missing.message_id, missing.language) {
+                    diagnostic!(
+                        error,
+                        target: "i18n_embed::fluent",
+                        {
+                            domain = missing.domain,
+                            language = missing.language,
+                            message_id = missing.message_id,
+                        },
+                        "Unable to find localization for language \"{}\" and id \"{}\".",
+                        missing.language,
+                        missing.message_id
+                    );
+                }
+            }
+            Some(attribute_id) => {
+                if self.should_log(
+                    &format!("{}.{attribute_id}", missing.message_id),
+                    missing.language,
+                ) {
+                    diagnostic!(
+                        error,
+                        target: "i18n_embed::fluent",
+                        {
+                            domain = missing.domain,
+                            language = missing.language,
+                            message_id = missing.message_id,
+                            attribute_id = attribute_id,
+                        },
+                        "Unable to find localization for language \"{}\", message id \"{}\" and attribute id \"{}\".",
+                        missing.language,
+                        missing.message_id,
+                        attribute_id
+                    );
+                }
+            }
+        }
+
+        // The handler is cloned so that the lock isn't held while it
+        // is called, in case it sets another handler.
+        let handler = self.handler.read().clone();
+        match handler {
+            Some(handler) => handler.handle(missing),
+            None => missing.placeholder(),
+        }
+    }
+
+    fn set_policy(&self, policy: LogPolicy) {
+        let mut state = self.state.lock();
+        *state = MissingLogState {
+            policy,
+            missing: std::mem::take(&mut state.missing),
+            ..MissingLogState::default()
+        };
+    }
 }
 
+/// The name of the file containing the terms (e.g. `-brand-name`)
+/// which are shared by all the fluent files of a language, located
+/// in the same directory as the language file (e.g.
+/// `en-US/_terms.ftl` for `en-US/my_crate.ftl`). See
+/// [PathTemplate::sibling_path()].
+///
+/// ⚠️ *This API requires the following crate features to be activated: `fluent-system`.*
+pub const TERMS_FILE: &str = "_terms.ftl";
+
 /// [LanguageLoader] implemenation for the `fluent` localization
 /// system. Also provides methods to access localizations which have
 /// been loaded.
 ///
+/// In addition to the `{language}/{domain}.ftl` file, messages for a
+/// language can be organised into namespaces using files located at
+/// `{language}/{domain}/{namespace}.ftl`. These messages are
+/// referenced with their id prefixed by the namespace, for example
+/// the message `volume` in `en-US/my_crate/settings/audio.ftl` is
+/// obtained with `loader.get("settings.audio.volume")`. Messages can
+/// only reference other messages within the same file.
+///
+/// Another layout for the files can be used with
+/// [FluentLanguageLoader::set_path_template()], the namespace files
+/// are then located in the directory with the same path as the
+/// language file without its `.ftl` extension.
+///
+/// The terms in the [TERMS_FILE] of a language (e.g.
+/// `en-US/_terms.ftl`) can be referenced by the messages in all of
+/// its files, and of all the domains with files in the same
+/// directory, so that terms such as the brand name don't need to be
+/// duplicated. Terms defined in a file take precedence over the
+/// shared terms.
+///
 /// ⚠️ *This API requires the following crate features to be activated: `fluent-system`.*
 #[derive(Debug)]
 pub struct FluentLanguageLoader {
-    inner: ArcSwap<FluentLanguageLoaderInner>,
-    domain: String,
+    /// Created when the loader is first used, so that the loader can
+    /// be created in a `const` context, see
+    /// [FluentLanguageLoader::new_const()].
+    state: OnceLock<LoaderState>,
+    domain: Cow<'static, str>,
     fallback_language: unic_langid::LanguageIdentifier,
+    message_format_placeholders: AtomicBool,
+    use_isolating: AtomicBool,
+    pseudolocalization: Mutex<Option<Pseudolocalization>>,
+    path_template: Mutex<Option<PathTemplate>>,
+    language_aliases: Mutex<LanguageAliases>,
+    region_context: Option<RegionContext>,
 }
 
-impl FluentLanguageLoader {
-    /// Create a new `FluentLanguageLoader`, which loads messages for
-    /// the specified `domain`, and relies on the specified
-    /// `fallback_language` for any messages that do not exist for the
-    /// current language.
-    pub fn new<S: Into<String>>(
-        domain: S,
-        fallback_language: unic_langid::LanguageIdentifier,
-    ) -> Self {
+#[derive(Debug)]
+struct LoaderState {
+    inner: ArcSwap<FluentLanguageLoaderInner>,
+    lookup_tracing: Arc<LookupTracing>,
+    missing_log: Arc<MissingLog>,
+}
+
+impl LoaderState {
+    fn new(fallback_language: &LanguageIdentifier) -> Self {
         let config = LanguageConfig {
             language_bundles: Vec::new(),
             language_map: HashMap::new(),
@@ -108,18 +795,214 @@ impl FluentLanguageLoader {
                     languages: vec![fallback_language.clone()],
                     indices: vec![],
                 },
+                #[cfg(feature = "cache")]
+                message_cache: MessageCache::default(),
             })),
-            domain: domain.into(),
+            lookup_tracing: Arc::default(),
+            missing_log: Arc::default(),
+        }
+    }
+}
+
+impl FluentLanguageLoader {
+    /// Create a new `FluentLanguageLoader`, which loads messages for
+    /// the specified `domain`, and relies on the specified
+    /// `fallback_language` for any messages that do not exist for the
+    /// current language.
+    pub fn new<S: Into<String>>(
+        domain: S,
+        fallback_language: unic_langid::LanguageIdentifier,
+    ) -> Self {
+        Self {
+            state: OnceLock::new(),
+            domain: Cow::Owned(domain.into()),
+            fallback_language,
+            message_format_placeholders: AtomicBool::new(false),
+            use_isolating: AtomicBool::new(true),
+            pseudolocalization: Mutex::new(None),
+            path_template: Mutex::new(None),
+            language_aliases: Mutex::new(LanguageAliases::new()),
+            region_context: None,
+        }
+    }
+
+    /// Create a new `FluentLanguageLoader` in a `const` context, see
+    /// [FluentLanguageLoader::new()]. This allows the loader to be
+    /// stored in a `static` without a wrapper such as
+    /// [OnceLock]:
+    ///
+    /// ```ignore
+    /// static LOADER: FluentLanguageLoader =
+    ///     FluentLanguageLoader::new_const("my_crate", langid!("en-US"));
+    /// ```
+    ///
+    /// See [static_loader!()] to create it using the crate's i18n
+    /// config, like [fluent_language_loader!()].
+    pub const fn new_const(
+        domain: &'static str,
+        fallback_language: unic_langid::LanguageIdentifier,
+    ) -> Self {
+        Self {
+            state: OnceLock::new(),
+            domain: Cow::Borrowed(domain),
+            fallback_language,
+            message_format_placeholders: AtomicBool::new(false),
+            use_isolating: AtomicBool::new(true),
+            pseudolocalization: Mutex::new(None),
+            path_template: Mutex::new(None),
+            language_aliases: Mutex::new(LanguageAliases::new()),
+            region_context: None,
+        }
+    }
+
+    /// Create a new `FluentLanguageLoader` in a `const` context which
+    /// locates its files using the `path_template` and
+    /// `language_aliases`, see [FluentLanguageLoader::new_const()],
+    /// [FluentLanguageLoader::set_path_template()] and
+    /// [FluentLanguageLoader::set_language_aliases()].
+    pub const fn new_const_with_layout(
+        domain: &'static str,
+        fallback_language: unic_langid::LanguageIdentifier,
+        path_template: Option<PathTemplate>,
+        language_aliases: LanguageAliases,
+    ) -> Self {
+        Self {
+            state: OnceLock::new(),
+            domain: Cow::Borrowed(domain),
+            fallback_language,
+            message_format_placeholders: AtomicBool::new(false),
+            use_isolating: AtomicBool::new(true),
+            pseudolocalization: Mutex::new(None),
+            path_template: Mutex::new(path_template),
+            language_aliases: Mutex::new(language_aliases),
+            region_context: None,
+        }
+    }
+
+    /// Set whether to insert Unicode Directionality Isolation Marks
+    /// around placeables in a `const` context, see
+    /// [FluentLanguageLoader::set_use_isolating()]. This is used by
+    /// [static_loader!()] for the `use_isolating` in the `[fluent]`
+    /// section of the `i18n.toml` configuration file.
+    pub const fn with_use_isolating(mut self, value: bool) -> Self {
+        self.use_isolating = AtomicBool::new(value);
+        self
+    }
+
+    /// Create a new loader with the same domain, fallback language
+    /// and configuration as this one (such as the
+    /// [FluentLanguageLoader::set_path_template()],
+    /// [FluentLanguageLoader::set_pseudolocalization()] and
+    /// [FluentLanguageLoader::set_use_isolating()] settings), but
+    /// without any of its state: no languages are loaded, and the
+    /// lookup statistics and missing message log (including its
+    /// [LogPolicy] and handler) start afresh. This is useful in tests
+    /// which need loaders configured like the application's, but
+    /// isolated from each other.
+    ///
+    /// ```
+    /// use i18n_embed::{fluent::FluentLanguageLoader, LanguageLoader};
+    ///
+    /// let loader = FluentLanguageLoader::new("my_crate", "en-US".parse().unwrap());
+    /// loader.set_use_isolating(false);
+    ///
+    /// let empty = loader.clone_empty();
+    /// assert_eq!("my_crate", empty.domain());
+    /// assert!(!empty.use_isolating());
+    /// ```
+    pub fn clone_empty(&self) -> FluentLanguageLoader {
+        self.with_overridden_fallback(self.fallback_language.clone())
+    }
+
+    /// Create a new loader like [FluentLanguageLoader::clone_empty()],
+    /// but with another `fallback_language`.
+    pub fn with_overridden_fallback(
+        &self,
+        fallback_language: LanguageIdentifier,
+    ) -> FluentLanguageLoader {
+        FluentLanguageLoader {
+            state: OnceLock::new(),
+            domain: self.domain.clone(),
             fallback_language,
+            message_format_placeholders: AtomicBool::new(
+                self.message_format_placeholders.load(Ordering::Relaxed),
+            ),
+            use_isolating: AtomicBool::new(self.use_isolating()),
+            pseudolocalization: Mutex::new(self.pseudolocalization()),
+            path_template: Mutex::new(self.path_template.lock().clone()),
+            language_aliases: Mutex::new(self.language_aliases()),
+            region_context: self.region_context.clone(),
         }
     }
 
+    fn state(&self) -> &LoaderState {
+        self.state
+            .get_or_init(|| LoaderState::new(&self.fallback_language))
+    }
+
+    fn inner(&self) -> &ArcSwap<FluentLanguageLoaderInner> {
+        &self.state().inner
+    }
+
+    /// Obtain the files for the namespaces of the specified
+    /// `language`, located at `{language}/{domain}/{namespace}.ftl`
+    /// within the `i18n_assets` (or the directory of the language
+    /// file for another [PathTemplate]), along with their paths and
+    /// namespaces. Files in nested directories use a namespace joined
+    /// with a `.`, e.g. `{domain}/settings/audio.ftl` has the
+    /// namespace `settings.audio`.
+    fn namespace_files<'a>(
+        &self,
+        language: &LanguageIdentifier,
+        i18n_assets: &'a dyn I18nAssets,
+    ) -> Vec<(String, Option<String>, Cow<'a, [u8]>)> {
+        let asset_language = self.asset_language(language, i18n_assets);
+        let namespace_dir = match self
+            .path_template()
+            .directory(asset_language, self.domain())
+        {
+            Some(directory) => format!("{directory}/"),
+            None => return Vec::new(),
+        };
+        let mut paths: Vec<(String, String)> = i18n_assets
+            .filenames_iter()
+            .filter_map(|path| {
+                let namespace = path
+                    .strip_prefix(&namespace_dir)?
+                    .strip_suffix(".ftl")?
+                    .replace('/', ".");
+                Some((path, namespace))
+            })
+            .collect();
+        paths.sort();
+        paths.dedup();
+
+        paths
+            .into_iter()
+            .flat_map(|(path, namespace)| {
+                diagnostic!(debug, target: "i18n_embed::fluent", { path = path }, "Attempting to load namespace file: \"{0}\"", path);
+                i18n_assets
+                    .get_files(&path)
+                    .into_iter()
+                    .map(move |file| (path.clone(), Some(namespace.clone()), file))
+            })
+            .collect()
+    }
+
     fn current_language_impl(
         &self,
         inner: &FluentLanguageLoaderInner,
     ) -> unic_langid::LanguageIdentifier {
-        inner
-            .current_languages
+        self.current_language_impl_for(&inner.current_languages)
+    }
+
+    /// The most preferred of the `current_languages`, or the fallback
+    /// language if there are none.
+    fn current_language_impl_for(
+        &self,
+        current_languages: &CurrentLanguages,
+    ) -> unic_langid::LanguageIdentifier {
+        current_languages
             .languages
             .first()
             .map_or_else(|| self.fallback_language.clone(), Clone::clone)
@@ -127,7 +1010,59 @@ impl FluentLanguageLoader {
 
     /// The languages associated with each actual currently loaded language bundle.
     pub fn current_languages(&self) -> Vec<unic_langid::LanguageIdentifier> {
-        self.inner.load().current_languages.languages.clone()
+        self.inner().load().current_languages.languages.clone()
+    }
+
+    /// Load the languages `language_ids` like
+    /// [LanguageLoader::load_languages()], returning the problems
+    /// found in their fluent files: syntax errors, and messages or
+    /// terms which are defined more than once in the same file. These
+    /// are logged, and the valid messages are still loaded.
+    pub fn load_languages_with_diagnostics(
+        &self,
+        i18n_assets: &dyn I18nAssets,
+        language_ids: &[unic_langid::LanguageIdentifier],
+    ) -> Result<Vec<ResourceDiagnostic>, I18nEmbedError> {
+        self.load_languages_impl(i18n_assets, language_ids, false)
+    }
+
+    /// Load the languages `language_ids` like
+    /// [LanguageLoader::load_languages()], but fail with
+    /// [I18nEmbedError::InvalidResources] if there are any problems
+    /// in their fluent files (see
+    /// [FluentLanguageLoader::load_languages_with_diagnostics()]), in
+    /// which case the languages which were previously loaded are
+    /// kept. This can be used by applications or tests which should
+    /// not be shipped with broken translations.
+    pub fn load_languages_strict(
+        &self,
+        i18n_assets: &dyn I18nAssets,
+        language_ids: &[unic_langid::LanguageIdentifier],
+    ) -> Result<(), I18nEmbedError> {
+        self.load_languages_impl(i18n_assets, language_ids, true)
+            .map(|_| ())
+    }
+
+    /// Load the languages available in the `i18n_assets` like
+    /// [LanguageLoader::load_available_languages()], but only those
+    /// for which the `filter` returns `true`, e.g. to restrict them to
+    /// the languages supported by a product, or to exclude the
+    /// pseudolocalized resources of the
+    /// [PSEUDOLOCALIZATION_LANGUAGE](crate::PSEUDOLOCALIZATION_LANGUAGE).
+    /// The fallback language is always loaded. Returns the languages
+    /// which were loaded.
+    pub fn load_available_languages_filtered(
+        &self,
+        i18n_assets: &dyn I18nAssets,
+        filter: impl Fn(&LanguageIdentifier) -> bool,
+    ) -> Result<Vec<LanguageIdentifier>, I18nEmbedError> {
+        let languages: Vec<LanguageIdentifier> = self
+            .available_languages(i18n_assets)?
+            .into_iter()
+            .filter(|language| language == &self.fallback_language || filter(language))
+            .collect();
+        self.load_languages(i18n_assets, &languages)?;
+        Ok(languages)
     }
 
     /// Get a localized message referenced by the `message_id`.
@@ -135,6 +1070,53 @@ impl FluentLanguageLoader {
         self.get_args_fluent(message_id, None)
     }
 
+    /// Get a localized message referenced by the `message_id`, like
+    /// [FluentLanguageLoader::get()], but without allocating a new
+    /// [String] each time for messages without placeables (e.g.
+    /// `hello = Hello World!`), whose text is borrowed from the
+    /// loaded fluent resource, see [LocalizedStr]. This is intended
+    /// for hot paths such as render loops, and is used by the `fl!()`
+    /// macro when no arguments are supplied. Other messages are
+    /// formatted as usual.
+    pub fn get_cow(&self, message_id: &str) -> LocalizedStr {
+        self.borrowed(message_id, None, || message_id.to_string())
+            .unwrap_or_else(|| self.get_args_fluent(message_id, None).into())
+    }
+
+    /// Borrow the text of the message referenced by the `message_id`
+    /// (or its attribute referenced by `attribute_id`) from the first
+    /// bundle which contains it, if it has no placeables. The lookup
+    /// is recorded using the `lookup_id`.
+    fn borrowed(
+        &self,
+        message_id: &str,
+        attribute_id: Option<&str>,
+        lookup_id: impl Fn() -> String,
+    ) -> Option<LocalizedStr> {
+        let inner = self.inner().load();
+        let language_config = inner.language_config.read();
+        let mut language_bundles: Box<dyn Iterator<Item = &Arc<LanguageBundle>>> =
+            match attribute_id {
+                // Attributes are looked up in all the loaded languages,
+                // see `get_attr_args_fluent()`.
+                Some(_) => Box::new(language_config.language_bundles.iter().flatten()),
+                None => Box::new(
+                    inner
+                        .current_languages
+                        .indices
+                        .iter()
+                        .flat_map(|&idx| language_config.language_bundles[idx].iter()),
+                ),
+            };
+        let language_bundle = language_bundles
+            .find(|language_bundle| language_bundle.pattern(message_id, attribute_id).is_some())?;
+        let text = LocalizedStr::borrowed(language_bundle, message_id, attribute_id)?;
+        self.state()
+            .lookup_tracing
+            .record(&lookup_id, Some(&language_bundle.language));
+        Some(text)
+    }
+
     /// A non-generic version of [FluentLanguageLoader::get_args()].
     pub fn get_args_concrete<'args>(
         &self,
@@ -151,40 +1133,1901 @@ impl FluentLanguageLoader {
         message_id: &str,
         args: Option<&'args FluentArgs<'args>>,
     ) -> String {
-        let inner = self.inner.load();
+        self.try_get_args_fluent(message_id, args)
+            .unwrap_or_else(|| self.missing_message(message_id))
+    }
+
+    /// Record and log that the message referenced by the `message_id`
+    /// is missing, returning the text to display in its place.
+    fn missing_message(&self, message_id: &str) -> String {
+        self.state()
+            .lookup_tracing
+            .record(&|| message_id.to_string(), None);
+        self.state().missing_log.missing(&MissingMessage {
+            domain: &self.domain,
+            language: &self.current_language(),
+            message_id,
+            attribute_id: None,
+        })
+    }
+
+    /// Get a localized message referenced by the `message_id` from
+    /// the currently selected languages, or `None` if it is not
+    /// available in any of them.
+    fn try_get_args_fluent<'args>(
+        &self,
+        message_id: &str,
+        args: Option<&'args FluentArgs<'args>>,
+    ) -> Option<String> {
+        let inner = self.inner().load();
         let language_config = inner.language_config.read();
-        inner
+        let current_language = inner
             .current_languages
-            .indices
-            .iter()
-            .map(|&idx| &language_config.language_bundles[idx])
-            .flat_map(|language_bundles| language_bundles.iter())
-            .find_map(|language_bundle| language_bundle
-                .bundle
-                .get_message(message_id)
-                .and_then(|m: FluentMessage<'_>| m.value())
-                .map(|pattern: &Pattern<&str>| {
-                    let mut errors = Vec::new();
-                    let value = language_bundle.bundle.format_pattern(pattern, args, &mut errors);
-                    if !errors.is_empty() {
-                        log::error!(
-                            target:"i18n_embed::fluent",
-                            "Failed to format a message for language \"{}\" and id \"{}\".\nErrors\n{:?}.",
-                            inner.current_languages.languages.first().unwrap_or(&self.fallback_language), message_id, errors
-                        )
-                    }
-                    value.into()
+            .languages
+            .first()
+            .unwrap_or(&self.fallback_language);
+        let region_args = self.region_args(args, current_language);
+        let args = region_args.as_ref().or(args);
+        self.cached(&inner, message_id, None, args, || {
+            inner
+                .current_languages
+                .indices
+                .iter()
+                .map(|&idx| &language_config.language_bundles[idx])
+                .flat_map(|language_bundles| language_bundles.iter())
+                .find_map(|language_bundle| {
+                    let value = language_bundle.format(message_id, None, args, current_language)?;
+                    Some((value, language_bundle.language.clone()))
                 })
-            )
+        })
+        .map(|(value, language)| {
+            self.state()
+                .lookup_tracing
+                .record(&|| message_id.to_string(), Some(&language));
+            value
+        })
+    }
+
+    /// Obtain the message formatted by `format` from the message
+    /// cache of the `inner` state when the `cache` feature is
+    /// enabled, see [FluentLanguageLoader::clear_message_cache()].
+    /// `format` returns the message and the language of the bundle
+    /// which served it.
+    #[cfg_attr(not(feature = "cache"), allow(unused_variables))]
+    fn cached(
+        &self,
+        inner: &FluentLanguageLoaderInner,
+        message_id: &str,
+        attribute_id: Option<&str>,
+        args: Option<&FluentArgs<'_>>,
+        format: impl FnOnce() -> Option<(String, LanguageIdentifier)>,
+    ) -> Option<(String, LanguageIdentifier)> {
+        #[cfg(feature = "cache")]
+        return inner
+            .message_cache
+            .get_or_format(message_id, attribute_id, args, format);
+        #[cfg(not(feature = "cache"))]
+        format()
+    }
+
+    /// The `args` along with the `$region`, `$currency` and
+    /// `$timezone` variables of this loader's [RegionContext], or
+    /// `None` if it doesn't have one. The `args` take precedence.
+    fn region_args<'args>(
+        &self,
+        args: Option<&'args FluentArgs<'args>>,
+        current_language: &LanguageIdentifier,
+    ) -> Option<FluentArgs<'args>> {
+        region_args(self.region_context.as_ref(), args, current_language)
+    }
+
+    /// Set the [RegionContext] of this loader, usually one created
+    /// for a request using [FluentLanguageLoader::select_languages()]
+    /// or [FluentLanguageLoader::scope()]. Its region (or otherwise
+    /// the region of the [LanguageLoader::current_language()]),
+    /// currency and time zone are available to every message
+    /// obtained from this loader as the `$region`, `$currency` and
+    /// `$timezone` variables, unless an argument with the same name is
+    /// supplied. This allows custom functions added to the bundles
+    /// using [FluentLanguageLoader::with_bundles_mut()] to format
+    /// amounts and dates for the request:
+    ///
+    /// ```ftl
+    /// total = Total: { CURRENCY($amount, $currency) }
+    /// ```
+    ///
+    /// Loaders created from this one inherit its context.
+    pub fn with_region_context(mut self, context: RegionContext) -> Self {
+        self.region_context = Some(context);
+        self
+    }
+
+    /// The [RegionContext] of this loader, if it has one, see
+    /// [FluentLanguageLoader::with_region_context()].
+    pub fn region_context(&self) -> Option<&RegionContext> {
+        self.region_context.as_ref()
+    }
+
+    /// Get a localized message referenced by the `message_id`, and
+    /// formatted with the specified `args`.
+    pub fn get_args<'a, S, V>(&self, id: &str, args: HashMap<S, V>) -> String
+    where
+        S: Into<Cow<'a, str>> + Clone,
+        V: Into<FluentValue<'a>> + Clone,
+    {
+        self.get_args_fluent(id, hash_map_to_fluent_args(args).as_ref())
+    }
+
+    /// Get a localized attribute referenced by the `message_id` and `attribute_id`.
+    pub fn get_attr(&self, message_id: &str, attribute_id: &str) -> String {
+        self.get_attr_args_fluent(message_id, attribute_id, None)
+    }
+
+    /// A non-generic version of [FluentLanguageLoader::get_attr_args()].
+    pub fn get_attr_args_concrete<'args>(
+        &self,
+        message_id: &str,
+        attribute_id: &str,
+        args: HashMap<&'args str, FluentValue<'args>>,
+    ) -> String {
+        self.get_attr_args_fluent(
+            message_id,
+            attribute_id,
+            hash_map_to_fluent_args(args).as_ref(),
+        )
+    }
+
+    /// A non-generic version of [FluentLanguageLoader::get_attr_args()]
+    /// accepting [FluentArgs] instead of a [HashMap].
+    pub fn get_attr_args_fluent<'args>(
+        &self,
+        message_id: &str,
+        attribute_id: &str,
+        args: Option<&'args FluentArgs<'args>>,
+    ) -> String {
+        let inner = self.inner().load();
+        let language_config = inner.language_config.read();
+        let current_language = self.current_language_impl(&inner);
+        let region_args = self.region_args(args, &current_language);
+        let args = region_args.as_ref().or(args);
+
+        self.cached(&inner, message_id, Some(attribute_id), args, || {
+            language_config
+                .language_bundles
+                .iter()
+                .flat_map(|language_bundles| language_bundles.iter())
+                .find_map(|language_bundle| {
+                    let value = language_bundle.format(
+                        message_id,
+                        Some(attribute_id),
+                        args,
+                        &current_language,
+                    )?;
+                    Some((value, language_bundle.language.clone()))
+                })
+        })
+        .map(|(value, language)| {
+            self.state()
+                .lookup_tracing
+                .record(&|| format!("{message_id}.{attribute_id}"), Some(&language));
+            value
+        })
+        .unwrap_or_else(|| self.missing_attribute(message_id, attribute_id, &current_language))
+    }
+
+    /// Get a localized attribute referenced by the `message_id` and
+    /// `attribute_id`, without allocating a new [String] each time
+    /// for attributes without placeables, see
+    /// [FluentLanguageLoader::get_cow()].
+    pub fn get_attr_cow(&self, message_id: &str, attribute_id: &str) -> LocalizedStr {
+        self.borrowed(message_id, Some(attribute_id), || {
+            format!("{message_id}.{attribute_id}")
+        })
+        .unwrap_or_else(|| {
+            self.get_attr_args_fluent(message_id, attribute_id, None)
+                .into()
+        })
+    }
+
+    /// Record and log that the attribute referenced by the
+    /// `message_id` and `attribute_id` is missing, returning the text
+    /// to display in its place.
+    fn missing_attribute(
+        &self,
+        message_id: &str,
+        attribute_id: &str,
+        current_language: &LanguageIdentifier,
+    ) -> String {
+        self.state()
+            .lookup_tracing
+            .record(&|| format!("{message_id}.{attribute_id}"), None);
+        self.state().missing_log.missing(&MissingMessage {
+            domain: &self.domain,
+            language: current_language,
+            message_id,
+            attribute_id: Some(attribute_id),
+        })
+    }
+
+    /// Get a localized attribute referenced by the `message_id` and `attribute_id`, and
+    /// formatted with the specified `args`.
+    pub fn get_attr_args<'a, S, V>(
+        &self,
+        message_id: &str,
+        attribute_id: &str,
+        args: HashMap<S, V>,
+    ) -> String
+    where
+        S: Into<Cow<'a, str>> + Clone,
+        V: Into<FluentValue<'a>> + Clone,
+    {
+        self.get_attr_args_fluent(
+            message_id,
+            attribute_id,
+            hash_map_to_fluent_args(args).as_ref(),
+        )
+    }
+
+    /// Get a localized message referenced by the `message_id` in the
+    /// `language`, or otherwise in the fallback language, skipping
+    /// any languages in between. For example with the fallback
+    /// language `en-US`, a message missing from `en-GB` is obtained
+    /// from `en-US` even when `en` is also loaded. This suits content
+    /// such as email templates, where a partial regional fallback is
+    /// undesirable.
+    ///
+    /// Unlike [FluentLanguageLoader::get()], this doesn't depend on
+    /// the currently selected languages, but the `language` needs to
+    /// have been loaded, e.g. using
+    /// [LanguageLoader::load_available_languages()], otherwise the
+    /// message is obtained from the fallback language.
+    pub fn get_or_fallback(&self, language: &LanguageIdentifier, message_id: &str) -> String {
+        self.get_or_fallback_impl(language, message_id, None, None)
+    }
+
+    /// Get a localized message referenced by the `message_id` in the
+    /// `language` (or otherwise the fallback language), and formatted
+    /// with the specified `args`, see
+    /// [FluentLanguageLoader::get_or_fallback()].
+    pub fn get_or_fallback_args<'a, S, V>(
+        &self,
+        language: &LanguageIdentifier,
+        message_id: &str,
+        args: HashMap<S, V>,
+    ) -> String
+    where
+        S: Into<Cow<'a, str>> + Clone,
+        V: Into<FluentValue<'a>> + Clone,
+    {
+        self.get_or_fallback_args_fluent(
+            language,
+            message_id,
+            hash_map_to_fluent_args(args).as_ref(),
+        )
+    }
+
+    /// A non-generic version of
+    /// [FluentLanguageLoader::get_or_fallback_args()].
+    pub fn get_or_fallback_args_concrete<'args>(
+        &self,
+        language: &LanguageIdentifier,
+        message_id: &str,
+        args: HashMap<&'args str, FluentValue<'args>>,
+    ) -> String {
+        self.get_or_fallback_args_fluent(
+            language,
+            message_id,
+            hash_map_to_fluent_args(args).as_ref(),
+        )
+    }
+
+    /// A non-generic version of
+    /// [FluentLanguageLoader::get_or_fallback_args()] accepting
+    /// [FluentArgs] instead of a [HashMap].
+    pub fn get_or_fallback_args_fluent<'args>(
+        &self,
+        language: &LanguageIdentifier,
+        message_id: &str,
+        args: Option<&'args FluentArgs<'args>>,
+    ) -> String {
+        self.get_or_fallback_impl(language, message_id, None, args)
+    }
+
+    /// Get a localized attribute referenced by the `message_id` and
+    /// `attribute_id` in the `language` (or otherwise the fallback
+    /// language), see [FluentLanguageLoader::get_or_fallback()].
+    pub fn get_or_fallback_attr(
+        &self,
+        language: &LanguageIdentifier,
+        message_id: &str,
+        attribute_id: &str,
+    ) -> String {
+        self.get_or_fallback_impl(language, message_id, Some(attribute_id), None)
+    }
+
+    /// Get a localized attribute referenced by the `message_id` and
+    /// `attribute_id` in the `language` (or otherwise the fallback
+    /// language), and formatted with the specified `args`, see
+    /// [FluentLanguageLoader::get_or_fallback()].
+    pub fn get_or_fallback_attr_args<'a, S, V>(
+        &self,
+        language: &LanguageIdentifier,
+        message_id: &str,
+        attribute_id: &str,
+        args: HashMap<S, V>,
+    ) -> String
+    where
+        S: Into<Cow<'a, str>> + Clone,
+        V: Into<FluentValue<'a>> + Clone,
+    {
+        self.get_or_fallback_impl(
+            language,
+            message_id,
+            Some(attribute_id),
+            hash_map_to_fluent_args(args).as_ref(),
+        )
+    }
+
+    /// A non-generic version of
+    /// [FluentLanguageLoader::get_or_fallback_attr_args()].
+    pub fn get_or_fallback_attr_args_concrete<'args>(
+        &self,
+        language: &LanguageIdentifier,
+        message_id: &str,
+        attribute_id: &str,
+        args: HashMap<&'args str, FluentValue<'args>>,
+    ) -> String {
+        self.get_or_fallback_impl(
+            language,
+            message_id,
+            Some(attribute_id),
+            hash_map_to_fluent_args(args).as_ref(),
+        )
+    }
+
+    /// The implementation of [FluentLanguageLoader::get_or_fallback()]
+    /// and its variants. The message cache isn't used, because it
+    /// only applies to the currently selected languages.
+    fn get_or_fallback_impl<'args>(
+        &self,
+        language: &LanguageIdentifier,
+        message_id: &str,
+        attribute_id: Option<&str>,
+        args: Option<&'args FluentArgs<'args>>,
+    ) -> String {
+        let inner = self.inner().load();
+        let language_config = inner.language_config.read();
+        let region_args = self.region_args(args, language);
+        let args = region_args.as_ref().or(args);
+
+        let mut languages = vec![language];
+        if language != &self.fallback_language {
+            languages.push(&self.fallback_language);
+        }
+        let lookup_id = || match attribute_id {
+            Some(attribute_id) => format!("{message_id}.{attribute_id}"),
+            None => message_id.to_string(),
+        };
+
+        languages
+            .into_iter()
+            .filter_map(|language| language_config.language_map.get(language))
+            .flat_map(|&idx| language_config.language_bundles[idx].iter())
+            .find_map(|language_bundle| {
+                let value = language_bundle.format(message_id, attribute_id, args, language)?;
+                Some((value, &language_bundle.language))
+            })
+            .map(|(value, bundle_language)| {
+                self.state()
+                    .lookup_tracing
+                    .record(&lookup_id, Some(bundle_language));
+                value
+            })
             .unwrap_or_else(|| {
-                log::error!(
-                    target:"i18n_embed::fluent",
-                    "Unable to find localization for language \"{}\" and id \"{}\".",
-                    inner.current_languages.languages.first().unwrap_or(&self.fallback_language),
-                    message_id
-                );
-                format!("No localization for id: \"{}\"", message_id)
+                self.state().lookup_tracing.record(&lookup_id, None);
+                self.state().missing_log.missing(&MissingMessage {
+                    domain: &self.domain,
+                    language,
+                    message_id,
+                    attribute_id,
+                })
+            })
+    }
+
+    /// available in any of the languages currently loaded (including
+    /// the fallback language).
+    pub fn has(&self, message_id: &str) -> bool {
+        self.inner()
+            .load()
+            .language_config
+            .read()
+            .language_bundles
+            .iter()
+            .flat_map(|language_bundles| language_bundles.iter())
+            .any(|language_bundle| language_bundle.get_message(message_id).is_some())
+    }
+
+    /// Determines if an attribute associated with the specified `message_id`
+    /// is available in any of the currently loaded languages, including the fallback language.
+    ///
+    /// Returns true if at least one available instance was found,
+    /// false otherwise.
+    ///
+    /// Note that this also returns false if the `message_id` could not be found;
+    /// use [FluentLanguageLoader::has()] to determine if the `message_id` is available.
+    pub fn has_attr(&self, message_id: &str, attribute_id: &str) -> bool {
+        self.inner()
+            .load()
+            .language_config
+            .read()
+            .language_bundles
+            .iter()
+            .flat_map(|bundles| bundles.iter())
+            .find_map(|bundle| {
+                bundle
+                    .get_message(message_id)
+                    .map(|message| message.get_attribute(attribute_id).is_some())
+            })
+            .unwrap_or(false)
+    }
+
+    /// Run the `closure` with the message that matches the specified
+    /// `message_id` (if it is available in any of the languages
+    /// currently loaded, including the fallback language). Returns
+    /// `Some` of whatever whatever the closure returns, or `None` if
+    /// no messages were found matching the `message_id`.
+    pub fn with_fluent_message<OUT, C>(&self, message_id: &str, closure: C) -> Option<OUT>
+    where
+        C: Fn(fluent::FluentMessage<'_>) -> OUT,
+    {
+        self.inner()
+            .load()
+            .language_config
+            .read()
+            .language_bundles
+            .iter()
+            .flat_map(|language_bundles| language_bundles.iter())
+            .find_map(|language_bundle| language_bundle.get_message(message_id))
+            .map(closure)
+    }
+
+    /// Runs the provided `closure` with an iterator over the messages
+    /// available for the specified `language`. There may be duplicate
+    /// messages when they are duplicated in resources applicable to
+    /// the language. Returns the result of the closure.
+    pub fn with_message_iter<OUT, C>(&self, language: &LanguageIdentifier, closure: C) -> OUT
+    where
+        C: Fn(&mut dyn Iterator<Item = &ast::Message<&str>>) -> OUT,
+    {
+        let inner = self.inner().load();
+        let config_lock = inner.language_config.read();
+
+        let mut iter = config_lock
+            .language_bundles
+            .iter()
+            .flat_map(|language_bundles| language_bundles.iter())
+            .filter(|language_bundle| &language_bundle.language == language)
+            .flat_map(|language_bundle| {
+                language_bundle
+                    .resource
+                    .entries()
+                    .filter_map(|entry| match entry {
+                        ast::Entry::Message(message) => Some(message),
+                        _ => None,
+                    })
+            });
+
+        (closure)(&mut iter)
+    }
+
+    /// The ids of the messages available for the specified
+    /// `language`, including the namespace for messages loaded from
+    /// `{domain}/{namespace}.ftl` files (e.g. `feature.message-id`).
+    /// There may be duplicate ids when messages are duplicated in
+    /// resources applicable to the language.
+    pub fn message_ids(&self, language: &LanguageIdentifier) -> Vec<String> {
+        let inner = self.inner().load();
+        let config_lock = inner.language_config.read();
+
+        config_lock
+            .language_bundles
+            .iter()
+            .flat_map(|language_bundles| language_bundles.iter())
+            .filter(|language_bundle| &language_bundle.language == language)
+            .flat_map(|language_bundle| {
+                language_bundle
+                    .resource
+                    .entries()
+                    .filter_map(move |entry| match entry {
+                        ast::Entry::Message(message) => Some(language_bundle.message_id(message)),
+                        _ => None,
+                    })
+            })
+            .collect()
+    }
+
+    /// The proportion (from `0.0` to `1.0`) of the messages of the
+    /// fallback language which are also available in each of the
+    /// loaded languages, e.g. `0.82` if 82% of them are translated,
+    /// so that applications can show how complete each language is
+    /// in their language picker. The messages are compared using
+    /// their ids (as returned by
+    /// [FluentLanguageLoader::message_ids()]), and the messages which
+    /// are not in the fallback language are ignored. A language is
+    /// complete (`1.0`) if the fallback language has no messages.
+    pub fn completeness(&self) -> HashMap<LanguageIdentifier, f32> {
+        let inner = self.inner().load();
+        let config_lock = inner.language_config.read();
+
+        let message_ids = |language: &LanguageIdentifier| -> HashSet<String> {
+            config_lock
+                .language_bundles
+                .iter()
+                .flat_map(|language_bundles| language_bundles.iter())
+                .filter(|language_bundle| &language_bundle.language == language)
+                .flat_map(|language_bundle| {
+                    language_bundle
+                        .resource
+                        .entries()
+                        .filter_map(move |entry| match entry {
+                            ast::Entry::Message(message) => {
+                                Some(language_bundle.message_id(message))
+                            }
+                            _ => None,
+                        })
+                })
+                .collect()
+        };
+
+        let fallback_message_ids = message_ids(self.fallback_language());
+        config_lock
+            .language_map
+            .keys()
+            .map(|language| {
+                let completeness = if fallback_message_ids.is_empty() {
+                    1.0
+                } else {
+                    let translated = message_ids(language)
+                        .intersection(&fallback_message_ids)
+                        .count();
+                    translated as f32 / fallback_message_ids.len() as f32
+                };
+                (language.clone(), completeness)
+            })
+            .collect()
+    }
+
+    /// Format every message available for the specified `language`
+    /// in a single pass, returning the id of each message (as
+    /// returned by [FluentLanguageLoader::message_ids()]) along with
+    /// its formatted value. The `args_provider` is called with the id
+    /// of each message to obtain the arguments to format it with.
+    /// Messages without a value (only attributes) are skipped, and
+    /// duplicated messages are only formatted once, using the one
+    /// with the highest priority.
+    ///
+    /// This is much cheaper than calling
+    /// [FluentLanguageLoader::get_args_fluent()] for each message
+    /// when exporting all of the messages of a language, because the
+    /// loaded bundles are only locked once. Messages are not looked
+    /// up in the fallback chain, and the lookups are not recorded by
+    /// [FluentLanguageLoader::enable_lookup_tracing()]. Returns an
+    /// empty `Vec` if the `language` is not loaded.
+    pub fn format_all<'args, F>(
+        &self,
+        language: &LanguageIdentifier,
+        args_provider: F,
+    ) -> Vec<(String, String)>
+    where
+        F: Fn(&str) -> Option<FluentArgs<'args>>,
+    {
+        let inner = self.inner().load();
+        let config_lock = inner.language_config.read();
+        let language_bundles = match config_lock.language_map.get(language) {
+            Some(&idx) => &config_lock.language_bundles[idx],
+            None => return Vec::new(),
+        };
+
+        let mut seen = HashSet::new();
+        let mut formatted = Vec::new();
+        for language_bundle in language_bundles {
+            for entry in language_bundle.resource.entries() {
+                let message = match entry {
+                    ast::Entry::Message(message) if message.value.is_some() => message,
+                    _ => continue,
+                };
+                let message_id = language_bundle.message_id(message);
+                if !seen.insert(message_id.clone()) {
+                    continue;
+                }
+                let args = args_provider(&message_id);
+                if let Some(value) =
+                    language_bundle.format(&message_id, None, args.as_ref(), language)
+                {
+                    formatted.push((message_id, value));
+                }
+            }
+        }
+        formatted
+    }
+
+    /// Join `items` into a single list localized for the
+    /// [LanguageLoader::current_language()], see
+    /// [format_list()](crate::format_list()). The result can be passed
+    /// as an argument to a message.
+    pub fn format_list<S: AsRef<str>>(&self, items: &[S], style: ListStyle) -> String {
+        crate::format_list(&self.current_language(), items, style)
+    }
+
+    /// Format a human readable relative time such as "3 minutes ago"
+    /// or "in 2 days" for the current language. A [Duration] is
+    /// treated as being in the past, use [RelativeTime::Future] for a
+    /// time in the future. The duration is truncated to the largest
+    /// whole unit (seconds, minutes, hours, days, months or years).
+    ///
+    /// The messages are provided by `i18n-embed` for a number of
+    /// languages (falling back to English), and can be overridden by
+    /// the application by defining messages with the same ids in its
+    /// own resources: `relative-time-now`, and
+    /// `relative-time-{unit}-ago` and `relative-time-in-{unit}` with
+    /// a `$count` argument, where `{unit}` is one of `seconds`,
+    /// `minutes`, `hours`, `days`, `months` or `years`.
+    ///
+    /// [Duration]: std::time::Duration
+    pub fn format_relative_time<T: Into<RelativeTime>>(&self, time: T) -> String {
+        let (message_id, count) = time.into().message();
+        let mut args = FluentArgs::new();
+        args.set("count", count);
+        self.try_get_args_fluent(&message_id, Some(&args))
+            .unwrap_or_else(|| {
+                crate::relative_time::format_builtin(&self.current_languages(), &message_id, &args)
+            })
+    }
+
+    /// Enable or disable recording which language in the fallback
+    /// chain served each message lookup, retrieved using
+    /// [FluentLanguageLoader::lookup_stats()]. This is useful for
+    /// finding messages which are silently falling back to another
+    /// language. The recorded statistics are shared with loaders
+    /// created from this one using
+    /// [FluentLanguageLoader::select_languages()].
+    ///
+    /// Default: `false`.
+    pub fn enable_lookup_tracing(&self, enabled: bool) {
+        self.state()
+            .lookup_tracing
+            .enabled
+            .store(enabled, Ordering::Relaxed);
+    }
+
+    /// The statistics recorded so far while lookup tracing was
+    /// enabled, see [FluentLanguageLoader::enable_lookup_tracing()].
+    pub fn lookup_stats(&self) -> LookupStats {
+        self.state().lookup_tracing.stats.lock().clone()
+    }
+
+    /// Clear the statistics recorded while lookup tracing was
+    /// enabled.
+    pub fn reset_lookup_stats(&self) {
+        *self.state().lookup_tracing.stats.lock() = LookupStats::default();
+    }
+
+    /// Set how messages which couldn't be found in any of the loaded
+    /// languages are logged, to avoid flooding the logs of a busy
+    /// service with the same errors. The policy is shared with the
+    /// loaders created from this one using
+    /// [FluentLanguageLoader::select_languages()], and with its
+    /// snapshots. Setting the policy resets its sampling, rate
+    /// limiting and de-duplication state, but not the
+    /// [FluentLanguageLoader::missing_message_counts()].
+    ///
+    /// Default: [LogPolicy::All].
+    pub fn set_log_policy(&self, policy: LogPolicy) {
+        self.state().missing_log.set_policy(policy);
+    }
+
+    /// The policy for logging missing messages, see
+    /// [FluentLanguageLoader::set_log_policy()].
+    pub fn log_policy(&self) -> LogPolicy {
+        self.state().missing_log.state.lock().policy
+    }
+
+    /// Set the `handler` which chooses the text returned in place of
+    /// messages (and attributes) which couldn't be found in any of the
+    /// loaded languages, instead of the default placeholder (see
+    /// [MissingMessage::placeholder()]). Like the [LogPolicy], the
+    /// handler is shared with the loaders created from this one using
+    /// [FluentLanguageLoader::select_languages()], and with its
+    /// snapshots.
+    pub fn set_missing_message_handler(&self, handler: impl MissingMessageHandler + 'static) {
+        *self.state().missing_log.handler.write() = Some(Arc::new(handler));
+    }
+
+    /// Remove the handler set using
+    /// [FluentLanguageLoader::set_missing_message_handler()], so that
+    /// the default placeholder is returned for missing messages.
+    pub fn reset_missing_message_handler(&self) {
+        *self.state().missing_log.handler.write() = None;
+    }
+
+    /// For each message id, the number of times it couldn't be found
+    /// in any of the loaded languages, for each of the languages
+    /// which were selected when it was requested. Attributes are
+    /// counted using the id `message-id.attribute-id`. Misses are
+    /// counted regardless of the [LogPolicy], and are shared in the
+    /// same way.
+    pub fn missing_message_counts(&self) -> HashMap<String, HashMap<LanguageIdentifier, usize>> {
+        self.state().missing_log.state.lock().missing.clone()
+    }
+
+    /// Clear the counts returned by
+    /// [FluentLanguageLoader::missing_message_counts()]. Messages
+    /// which were already logged with [LogPolicy::Deduplicate] will be
+    /// logged again the next time they are missing.
+    pub fn reset_missing_message_counts(&self) {
+        let mut state = self.state().missing_log.state.lock();
+        state.missing.clear();
+        state.logged.clear();
+    }
+
+    /// Enable or disable converting ICU MessageFormat style
+    /// placeholders such as `{name}` into fluent variable references
+    /// such as `{ $name }` when resources are loaded, to ease the
+    /// incremental migration of legacy resources. `{name, number}`
+    /// is also converted to `{ $name }`, and placeholders with the
+    /// id of a message defined in the same file are left as message
+    /// references. MessageFormat `plural` and `select` placeholders
+    /// can't be converted, and are reported with a warning. Each
+    /// conversion is logged at the debug level.
+    ///
+    /// **Note:** This only affects languages loaded after it is
+    /// called, using [`LanguageLoader::load_languages`] or
+    /// [`LanguageLoader::reload`].
+    ///
+    /// Default: `false`.
+    pub fn enable_message_format_placeholders(&self, enabled: bool) {
+        self.message_format_placeholders
+            .store(enabled, Ordering::Relaxed);
+    }
+
+    /// Pseudolocalize every message formatted by this loader using
+    /// the `pseudolocalization` options, or stop pseudolocalizing
+    /// them if it is `None`, so that developers can spot text which
+    /// hasn't been localized, and layouts which can't accommodate
+    /// longer translations. Only the text of the messages is
+    /// accented, the values of their placeables are not.
+    ///
+    /// This applies to the languages which are currently loaded
+    /// (including for the loaders created from this one using
+    /// [FluentLanguageLoader::select_languages()] which share them),
+    /// and the languages loaded after it is called.
+    ///
+    /// ```ignore
+    /// if std::env::var_os("MY_APP_PSEUDO").is_some() {
+    ///     loader.set_pseudolocalization(Some(Pseudolocalization::default()));
+    /// }
+    /// ```
+    ///
+    /// Default: `None`.
+    pub fn set_pseudolocalization(&self, pseudolocalization: Option<Pseudolocalization>) {
+        *self.pseudolocalization.lock() = pseudolocalization;
+        self.with_language_bundles_mut(|bundle| bundle.set_pseudolocalization(pseudolocalization));
+    }
+
+    /// The options for pseudolocalizing messages, see
+    /// [FluentLanguageLoader::set_pseudolocalization()].
+    pub fn pseudolocalization(&self) -> Option<Pseudolocalization> {
+        *self.pseudolocalization.lock()
+    }
+
+    /// Locate the language files within the assets using the
+    /// `path_template` (e.g. `locales/{language}.ftl`), instead of
+    /// the default `{language}/{domain}.ftl` layout if it is `None`.
+    /// This takes effect the next time languages are loaded.
+    ///
+    /// ```
+    /// use i18n_embed::{fluent::FluentLanguageLoader, PathTemplate};
+    ///
+    /// let loader = FluentLanguageLoader::new("my_crate", "en-US".parse().unwrap());
+    /// loader.set_path_template(Some(PathTemplate::new("{domain}/{language}.ftl").unwrap()));
+    /// ```
+    ///
+    /// The `path_template` in the `[fluent]` section of the `i18n.toml`
+    /// configuration file is used by [fluent_language_loader!()] and
+    /// [static_loader!()].
+    pub fn set_path_template(&self, path_template: Option<PathTemplate>) {
+        *self.path_template.lock() = path_template;
+    }
+
+    /// Serve requests for a language using the files of another
+    /// language, see [LanguageAliases]. This takes effect the next
+    /// time languages are loaded.
+    ///
+    /// ```
+    /// use i18n_embed::{fluent::FluentLanguageLoader, LanguageAliases};
+    ///
+    /// let loader = FluentLanguageLoader::new("my_crate", "en-US".parse().unwrap());
+    /// let mut aliases = LanguageAliases::new();
+    /// aliases.insert("no".parse().unwrap(), "nb".parse().unwrap());
+    /// loader.set_language_aliases(aliases);
+    /// ```
+    ///
+    /// The `[language_aliases]` table of the `i18n.toml`
+    /// configuration file is used by [fluent_language_loader!()] and
+    /// [static_loader!()].
+    pub fn set_language_aliases(&self, language_aliases: LanguageAliases) {
+        *self.language_aliases.lock() = language_aliases;
+    }
+
+    /// Set whether the underlying Fluent logic should insert Unicode
+    /// Directionality Isolation Marks around placeables.
+    ///
+    /// See [`fluent::bundle::FluentBundleBase::set_use_isolating`] for more
+    /// information.
+    ///
+    /// This applies to the languages which are currently loaded, and
+    /// the languages loaded (or reloaded) after it is called. The
+    /// `use_isolating` in the `[fluent]` section of the `i18n.toml`
+    /// configuration file is used by [fluent_language_loader!()] and
+    /// [static_loader!()].
+    ///
+    /// Default: `true`.
+    pub fn set_use_isolating(&self, value: bool) {
+        self.use_isolating.store(value, Ordering::Relaxed);
+        self.with_bundles_mut(move |bundle| bundle.set_use_isolating(value));
+    }
+
+    /// Whether Unicode Directionality Isolation Marks are inserted
+    /// around placeables, see
+    /// [FluentLanguageLoader::set_use_isolating()].
+    pub fn use_isolating(&self) -> bool {
+        self.use_isolating.load(Ordering::Relaxed)
+    }
+
+    /// Apply some configuration to each budle in this loader.
+    ///
+    /// **Note:** This function will have no effect if
+    /// [`LanguageLoader::load_languages`] has not been called first.
+    ///
+    /// **Note:** Bundles shared with a [LoaderSnapshot] are not
+    /// modified, instead they are re-created from their resources in
+    /// this loader before `f` is applied. The configuration applied by
+    /// previous calls is kept, and applied again to the re-created
+    /// bundles, which is why `f` needs to be `'static`.
+    pub fn with_bundles_mut<F>(&self, f: F)
+    where
+        F: Fn(&mut FluentBundle<Arc<FluentResource>, IntlLangMemoizer>) + Send + Sync + 'static,
+    {
+        let f: BundleConfiguration = Arc::new(f);
+        self.with_language_bundles_mut(|bundle| {
+            f(&mut bundle.bundle);
+            bundle.configuration.push(f.clone());
+        });
+    }
+
+    /// Apply `f` to each [LanguageBundle] in this loader, see
+    /// [FluentLanguageLoader::with_bundles_mut()].
+    fn with_language_bundles_mut(&self, f: impl Fn(&mut LanguageBundle)) {
+        for bundle in self
+            .inner()
+            .load()
+            .language_config
+            .write()
+            .language_bundles
+            .iter_mut()
+            .flat_map(|bundles| bundles.iter_mut())
+        {
+            if Arc::get_mut(bundle).is_none() {
+                let mut unshared = LanguageBundle::from_shared_resource(
+                    bundle.language.clone(),
+                    bundle.namespace.clone(),
+                    bundle.resource.clone(),
+                    bundle.terms.clone(),
+                    &mut Vec::new(),
+                );
+                unshared.set_pseudolocalization(bundle.pseudolocalization);
+                unshared.bundle.set_use_isolating(self.use_isolating());
+                for configuration in &bundle.configuration {
+                    configuration(&mut unshared.bundle);
+                }
+                unshared.configuration = bundle.configuration.clone();
+                *bundle = Arc::new(unshared);
+            }
+            let bundle = Arc::get_mut(bundle).expect("bundle should not be shared");
+            f(bundle);
+        }
+        self.clear_message_cache();
+    }
+
+    /// Clear the cache of formatted messages. Messages (and their
+    /// attributes) obtained without arguments, or with up to four
+    /// string or number arguments, are cached when the `cache`
+    /// feature is enabled, so that formatting the same static labels
+    /// repeatedly (e.g. while redrawing a user interface) is cheap.
+    ///
+    /// The cache is replaced when languages are loaded or reloaded,
+    /// and cleared by [FluentLanguageLoader::with_bundles_mut()], so
+    /// this only needs to be called if the bundles are configured
+    /// with custom functions whose output changes over time. Loaders
+    /// created using [FluentLanguageLoader::select_languages()] have
+    /// their own cache.
+    ///
+    /// ⚠️ *The cache requires the following crate features to be activated: `cache`.*
+    pub fn clear_message_cache(&self) {
+        #[cfg(feature = "cache")]
+        self.inner().load().message_cache.clear();
+    }
+
+    /// Take an immutable [LoaderSnapshot] of the currently loaded
+    /// bundles and selected languages. The snapshot shares the
+    /// bundles with this loader, so taking it does not copy any
+    /// loaded resources, and obtaining messages from it requires no
+    /// locking. It is unaffected by languages subsequently being
+    /// loaded or selected by this loader.
+    pub fn snapshot(&self) -> LoaderSnapshot {
+        let inner = self.inner().load();
+        let language_config = inner.language_config.read();
+        let bundles = inner
+            .current_languages
+            .indices
+            .iter()
+            .flat_map(|&idx| language_config.language_bundles[idx].iter().cloned())
+            .collect();
+
+        LoaderSnapshot {
+            inner: Arc::new(LoaderSnapshotInner {
+                bundles,
+                languages: inner.current_languages.languages.clone(),
+                domain: self.domain.to_string(),
+                fallback_language: self.fallback_language.clone(),
+                missing_log: self.state().missing_log.clone(),
+            }),
+        }
+    }
+
+    /// Create a new loader with a subset of currently loaded languages.
+    /// This is a rather cheap operation and does not require any
+    /// extensive copy operations. Cheap does not mean free so you
+    /// should not call this message repeatedly in order to translate
+    /// multiple strings for the same language.
+    pub fn select_languages<LI: AsRef<LanguageIdentifier>>(
+        &self,
+        languages: &[LI],
+    ) -> FluentLanguageLoader {
+        let inner = self.inner().load();
+        self.derived(
+            self.selected_languages(&inner, languages),
+            inner.language_config.clone(),
+        )
+    }
+
+    /// The `languages` selected from the languages in the bundle
+    /// storage of the `inner` state, along with the fallback language.
+    fn selected_languages<LI: AsRef<LanguageIdentifier>>(
+        &self,
+        inner: &FluentLanguageLoaderInner,
+        languages: &[LI],
+    ) -> CurrentLanguages {
+        let config_lock = inner.language_config.read();
+        let fallback_language: Option<&unic_langid::LanguageIdentifier> = if languages
+            .iter()
+            .any(|language| language.as_ref() == &self.fallback_language)
+        {
+            None
+        } else {
+            Some(&self.fallback_language)
+        };
+
+        let indices = languages
+            .iter()
+            .map(|lang| lang.as_ref())
+            .chain(fallback_language)
+            .filter_map(|lang| config_lock.language_map.get(lang.as_ref()))
+            .cloned()
+            .collect();
+        CurrentLanguages {
+            languages: languages.iter().map(|lang| lang.as_ref().clone()).collect(),
+            indices,
+        }
+    }
+
+    /// Create a new loader with the same configuration as this one,
+    /// which uses the `current_languages` of the `language_config`.
+    fn derived(
+        &self,
+        current_languages: CurrentLanguages,
+        language_config: Arc<RwLock<LanguageConfig>>,
+    ) -> FluentLanguageLoader {
+        FluentLanguageLoader {
+            state: OnceLock::from(LoaderState {
+                inner: ArcSwap::new(Arc::new(FluentLanguageLoaderInner {
+                    current_languages,
+                    language_config,
+                    #[cfg(feature = "cache")]
+                    message_cache: MessageCache::default(),
+                })),
+                lookup_tracing: self.state().lookup_tracing.clone(),
+                missing_log: self.state().missing_log.clone(),
+            }),
+            domain: self.domain.clone(),
+            fallback_language: self.fallback_language.clone(),
+            message_format_placeholders: AtomicBool::new(
+                self.message_format_placeholders.load(Ordering::Relaxed),
+            ),
+            use_isolating: AtomicBool::new(self.use_isolating()),
+            pseudolocalization: Mutex::new(self.pseudolocalization()),
+            path_template: Mutex::new(self.path_template.lock().clone()),
+            language_aliases: Mutex::new(self.language_aliases()),
+            region_context: self.region_context.clone(),
+        }
+    }
+
+    /// Create a [LanguageScope] for the specified `languages`, which
+    /// provides the same message lookup API as this loader, while
+    /// sharing its loaded bundles.
+    /// The scope falls back to the [LanguageLoader::fallback_language()]
+    /// for messages that are not available in any of the `languages`.
+    ///
+    /// This is the recommended way to localize with a language
+    /// specific to a single request in a server, where changing the
+    /// languages of a shared loader is not an option:
+    ///
+    /// ```ignore
+    /// let scope = loader.scope(&request_languages);
+    /// let message = scope.get("hello-world");
+    /// ```
+    pub fn scope<LI: AsRef<LanguageIdentifier>>(&self, languages: &[LI]) -> LanguageScope<'_> {
+        let inner = self.inner().load();
+        let current_languages = self.selected_languages(&inner, languages);
+        LanguageScope {
+            loader: self,
+            language_config: inner.language_config.clone(),
+            current_languages,
+            region_context: self.region_context.clone(),
+        }
+    }
+
+    /// Create a [MessageScope] for messages with ids sharing the
+    /// specified `prefix`. Messages are obtained from the scope using
+    /// the remainder of their id, joined to the `prefix` with a `-`:
+    ///
+    /// ```ignore
+    /// let audio = loader.message_scope("settings-audio");
+    /// // Obtains the message with the id "settings-audio-volume".
+    /// let volume = audio.get("volume");
+    /// ```
+    ///
+    /// See also the `fl_scope!()` macro in the `i18n-embed-fl` crate,
+    /// which checks the `prefix` at compile time.
+    pub fn message_scope<'a, P: Into<Cow<'a, str>>>(&'a self, prefix: P) -> MessageScope<'a> {
+        MessageScope {
+            loader: self,
+            prefix: prefix.into(),
+        }
+    }
+
+    /// Create a [LocalizedTemplate] for the email or notification
+    /// template in the message referenced by `message_id`, which has
+    /// `.subject` and `.body` attributes:
+    ///
+    /// ```ignore
+    /// let email = loader.template("welcome-email").render(Some(&args))?;
+    /// send_email(&email.subject, &email.body);
+    /// ```
+    pub fn template<'a, M: Into<Cow<'a, str>>>(&'a self, message_id: M) -> LocalizedTemplate<'a> {
+        LocalizedTemplate::new(self, message_id.into())
+    }
+
+    /// Select the requested `languages` from the currently loaded languages using the supplied
+    /// [`NegotiationStrategy`].
+    pub fn select_languages_negotiate<LI: AsRef<LanguageIdentifier>>(
+        &self,
+        languages: &[LI],
+        strategy: NegotiationStrategy,
+    ) -> FluentLanguageLoader {
+        let available_languages = &self.inner().load().current_languages.languages;
+        let negotiated_languages = fluent_langneg::negotiate_languages(
+            languages,
+            available_languages,
+            Some(self.fallback_language()),
+            strategy,
+        );
+
+        self.select_languages(&negotiated_languages)
+    }
+}
+
+/// A lightweight handle to a [FluentLanguageLoader] with a
+/// particular selection of languages, created using
+/// [FluentLanguageLoader::scope()]. It provides the same message
+/// lookup API as the loader, using the bundle storage of the loader
+/// it was created from along with the indices of the selected
+/// languages, so creating a scope does not copy any loaded resources
+/// or create another loader.
+///
+/// Lookups using a scope are recorded by
+/// [FluentLanguageLoader::enable_lookup_tracing()], and missing
+/// messages are logged according to the loader's
+/// [FluentLanguageLoader::set_log_policy()]. Formatted messages are
+/// not cached.
+///
+/// ⚠️ *This API requires the following crate features to be activated: `fluent-system`.*
+#[derive(Debug)]
+pub struct LanguageScope<'a> {
+    loader: &'a FluentLanguageLoader,
+    language_config: Arc<RwLock<LanguageConfig>>,
+    current_languages: CurrentLanguages,
+    region_context: Option<RegionContext>,
+}
+
+impl LanguageScope<'_> {
+    /// Set the [RegionContext] of this scope, see
+    /// [FluentLanguageLoader::with_region_context()].
+    pub fn with_region_context(mut self, context: RegionContext) -> Self {
+        self.region_context = Some(context);
+        self
+    }
+
+    /// The [RegionContext] of this scope, if it has one, which is
+    /// inherited from the loader it was created from unless it is set
+    /// using [LanguageScope::with_region_context()].
+    pub fn region_context(&self) -> Option<&RegionContext> {
+        self.region_context.as_ref()
+    }
+
+    /// The languages selected for this scope, in order of priority.
+    pub fn languages(&self) -> &[LanguageIdentifier] {
+        &self.current_languages.languages
+    }
+
+    /// The most preferred language selected for this scope, or the
+    /// fallback language if none were selected.
+    pub fn current_language(&self) -> LanguageIdentifier {
+        self.loader
+            .current_language_impl_for(&self.current_languages)
+    }
+
+    /// Get a localized message referenced by the `message_id`.
+    pub fn get(&self, message_id: &str) -> String {
+        self.get_args_fluent(message_id, None)
+    }
+
+    /// Get a localized message referenced by the `message_id`,
+    /// without allocating a new [String] each time for messages
+    /// without placeables, see [FluentLanguageLoader::get_cow()].
+    pub fn get_cow(&self, message_id: &str) -> LocalizedStr {
+        self.borrowed(message_id, None)
+            .unwrap_or_else(|| self.get_args_fluent(message_id, None).into())
+    }
+
+    /// A non-generic version of [LanguageScope::get_args()].
+    pub fn get_args_concrete<'args>(
+        &self,
+        message_id: &str,
+        args: HashMap<&'args str, FluentValue<'args>>,
+    ) -> String {
+        self.get_args_fluent(message_id, hash_map_to_fluent_args(args).as_ref())
+    }
+
+    /// A non-generic version of [LanguageScope::get_args()]
+    /// accepting [FluentArgs] instead of a [HashMap].
+    pub fn get_args_fluent<'args>(
+        &self,
+        message_id: &str,
+        args: Option<&'args FluentArgs<'args>>,
+    ) -> String {
+        self.format(message_id, None, args)
+    }
+
+    /// Get a localized message referenced by the `message_id`, and
+    /// formatted with the specified `args`.
+    pub fn get_args<'a, S, V>(&self, id: &str, args: HashMap<S, V>) -> String
+    where
+        S: Into<Cow<'a, str>> + Clone,
+        V: Into<FluentValue<'a>> + Clone,
+    {
+        self.get_args_fluent(id, hash_map_to_fluent_args(args).as_ref())
+    }
+
+    /// Get a localized attribute referenced by the `message_id` and `attribute_id`.
+    pub fn get_attr(&self, message_id: &str, attribute_id: &str) -> String {
+        self.get_attr_args_fluent(message_id, attribute_id, None)
+    }
+
+    /// Get a localized attribute referenced by the `message_id` and
+    /// `attribute_id`, without allocating a new [String] each time
+    /// for attributes without placeables, see
+    /// [FluentLanguageLoader::get_cow()].
+    pub fn get_attr_cow(&self, message_id: &str, attribute_id: &str) -> LocalizedStr {
+        self.borrowed(message_id, Some(attribute_id))
+            .unwrap_or_else(|| {
+                self.get_attr_args_fluent(message_id, attribute_id, None)
+                    .into()
+            })
+    }
+
+    /// A non-generic version of [LanguageScope::get_attr_args()].
+    pub fn get_attr_args_concrete<'args>(
+        &self,
+        message_id: &str,
+        attribute_id: &str,
+        args: HashMap<&'args str, FluentValue<'args>>,
+    ) -> String {
+        self.get_attr_args_fluent(
+            message_id,
+            attribute_id,
+            hash_map_to_fluent_args(args).as_ref(),
+        )
+    }
+
+    /// A non-generic version of [LanguageScope::get_attr_args()]
+    /// accepting [FluentArgs] instead of a [HashMap].
+    pub fn get_attr_args_fluent<'args>(
+        &self,
+        message_id: &str,
+        attribute_id: &str,
+        args: Option<&'args FluentArgs<'args>>,
+    ) -> String {
+        self.format(message_id, Some(attribute_id), args)
+    }
+
+    /// Get a localized attribute referenced by the `message_id` and `attribute_id`, and
+    /// formatted with the specified `args`.
+    pub fn get_attr_args<'a, S, V>(
+        &self,
+        message_id: &str,
+        attribute_id: &str,
+        args: HashMap<S, V>,
+    ) -> String
+    where
+        S: Into<Cow<'a, str>> + Clone,
+        V: Into<FluentValue<'a>> + Clone,
+    {
+        self.get_attr_args_fluent(
+            message_id,
+            attribute_id,
+            hash_map_to_fluent_args(args).as_ref(),
+        )
+    }
+
+    /// Determine if a message with the specified `message_id` is
+    /// available in any of the selected languages (including the
+    /// fallback language).
+    pub fn has(&self, message_id: &str) -> bool {
+        let language_config = self.language_config.read();
+        let has = self
+            .language_bundles(&language_config)
+            .any(|language_bundle| language_bundle.get_message(message_id).is_some());
+        has
+    }
+
+    /// Determine if an attribute associated with the specified
+    /// `message_id` is available in any of the selected languages
+    /// (including the fallback language).
+    pub fn has_attr(&self, message_id: &str, attribute_id: &str) -> bool {
+        let language_config = self.language_config.read();
+        let has_attr = self
+            .language_bundles(&language_config)
+            .find_map(|language_bundle| {
+                language_bundle
+                    .get_message(message_id)
+                    .map(|message| message.get_attribute(attribute_id).is_some())
+            })
+            .unwrap_or(false);
+        has_attr
+    }
+
+    /// The bundles of the selected languages in order of priority.
+    fn language_bundles<'c>(
+        &'c self,
+        language_config: &'c LanguageConfig,
+    ) -> impl Iterator<Item = &'c Arc<LanguageBundle>> + 'c {
+        self.current_languages
+            .indices
+            .iter()
+            .flat_map(move |&idx| language_config.language_bundles[idx].iter())
+    }
+
+    /// The lookup id recorded by the lookup tracing of the loader.
+    fn lookup_id(message_id: &str, attribute_id: Option<&str>) -> String {
+        match attribute_id {
+            Some(attribute_id) => format!("{message_id}.{attribute_id}"),
+            None => message_id.to_string(),
+        }
+    }
+
+    /// Format the message referenced by the `message_id` (or its
+    /// attribute referenced by `attribute_id`) using the first of the
+    /// selected languages which contains it.
+    fn format<'args>(
+        &self,
+        message_id: &str,
+        attribute_id: Option<&str>,
+        args: Option<&'args FluentArgs<'args>>,
+    ) -> String {
+        let current_language = self.current_language();
+        let region_args = region_args(self.region_context.as_ref(), args, &current_language);
+        let args = region_args.as_ref().or(args);
+        let language_config = self.language_config.read();
+        let state = self.loader.state();
+        let formatted = self
+            .language_bundles(&language_config)
+            .find_map(|language_bundle| {
+                let value =
+                    language_bundle.format(message_id, attribute_id, args, &current_language)?;
+                Some((value, &language_bundle.language))
+            });
+        let lookup_id = || Self::lookup_id(message_id, attribute_id);
+        match formatted {
+            Some((value, language)) => {
+                state.lookup_tracing.record(&lookup_id, Some(language));
+                value
+            }
+            None => {
+                state.lookup_tracing.record(&lookup_id, None);
+                state.missing_log.missing(&MissingMessage {
+                    domain: &self.loader.domain,
+                    language: &current_language,
+                    message_id,
+                    attribute_id,
+                })
+            }
+        }
+    }
+
+    /// Borrow the text of the message referenced by the `message_id`
+    /// (or its attribute referenced by `attribute_id`), see
+    /// [FluentLanguageLoader::get_cow()].
+    fn borrowed(&self, message_id: &str, attribute_id: Option<&str>) -> Option<LocalizedStr> {
+        let language_config = self.language_config.read();
+        let language_bundle = self
+            .language_bundles(&language_config)
+            .find(|language_bundle| language_bundle.pattern(message_id, attribute_id).is_some())?;
+        let text = LocalizedStr::borrowed(language_bundle, message_id, attribute_id)?;
+        self.loader.state().lookup_tracing.record(
+            &|| Self::lookup_id(message_id, attribute_id),
+            Some(&language_bundle.language),
+        );
+        Some(text)
+    }
+}
+
+/// A [FluentLanguageLoader] which loads the bundles of a language
+/// only when a message is first looked up in that language, and
+/// which can evict the least recently used languages when more than
+/// a maximum number of them are loaded (see
+/// [LazyFluentLanguageLoader::with_capacity()]). This is intended for
+/// servers which support many languages with limited memory, where
+/// most requests use only a few of them.
+///
+/// The [LanguageLoader::fallback_language()] is loaded when the lazy
+/// loader is created, and is never evicted. The bundles of the
+/// fallback language are shared by each of the other languages.
+///
+/// ```ignore
+/// let loader = LazyFluentLanguageLoader::new(fluent_language_loader!(), Localizations)?
+///     .with_capacity(NonZeroUsize::new(3).unwrap());
+/// let message = loader.get(&request_language, "hello-world");
+/// ```
+///
+/// ⚠️ *This API requires the following crate features to be activated: `fluent-system`.*
+pub struct LazyFluentLanguageLoader {
+    /// The loader which provides the configuration, with the fallback
+    /// language loaded.
+    loader: FluentLanguageLoader,
+    i18n_assets: Arc<dyn I18nAssets + Send + Sync>,
+    capacity: Option<NonZeroUsize>,
+    state: Mutex<LazyState>,
+}
+
+struct LazyState {
+    fallback_bundles: Vec<Arc<LanguageBundle>>,
+    /// A loader for only the fallback language.
+    fallback: Arc<FluentLanguageLoader>,
+    /// The loaded languages, from the least to the most recently
+    /// used.
+    languages: Vec<LazyLanguage>,
+}
+
+struct LazyLanguage {
+    language: LanguageIdentifier,
+    /// The bundles of the language, which is empty if the language is
+    /// not available.
+    bundles: Vec<Arc<LanguageBundle>>,
+    /// A loader for the language with the fallback language.
+    loader: Arc<FluentLanguageLoader>,
+}
+
+impl Debug for LazyFluentLanguageLoader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LazyFluentLanguageLoader")
+            .field("loader", &self.loader)
+            .field("capacity", &self.capacity)
+            .field("loaded_languages", &self.loaded_languages())
+            .finish_non_exhaustive()
+    }
+}
+
+impl LazyFluentLanguageLoader {
+    /// Create a new `LazyFluentLanguageLoader` which uses the
+    /// configuration of the `loader` (such as its domain and
+    /// [PathTemplate]) to load the languages from the `i18n_assets`.
+    /// The fallback language of the `loader` is loaded immediately,
+    /// and no languages are evicted until a capacity is set.
+    pub fn new<A>(loader: FluentLanguageLoader, i18n_assets: A) -> Result<Self, I18nEmbedError>
+    where
+        A: I18nAssets + Send + Sync + 'static,
+    {
+        let i18n_assets: Arc<dyn I18nAssets + Send + Sync> = Arc::new(i18n_assets);
+        let state = Self::load_fallback(&loader, &*i18n_assets)?;
+        Ok(Self {
+            loader,
+            i18n_assets,
+            capacity: None,
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Set the maximum number of languages (other than the fallback
+    /// language) which are kept loaded. When another language is
+    /// loaded, the least recently used language is evicted, and it is
+    /// loaded again from the assets if it is used later.
+    pub fn with_capacity(mut self, capacity: NonZeroUsize) -> Self {
+        self.capacity = Some(capacity);
+        self.state.get_mut().evict_above(capacity);
+        self
+    }
+
+    /// The loader which provides the configuration of this lazy
+    /// loader, with only the fallback language loaded.
+    pub fn loader(&self) -> &FluentLanguageLoader {
+        &self.loader
+    }
+
+    /// The languages other than the fallback language which are
+    /// currently loaded, from the least to the most recently used.
+    pub fn loaded_languages(&self) -> Vec<LanguageIdentifier> {
+        self.state
+            .lock()
+            .languages
+            .iter()
+            .map(|language| language.language.clone())
+            .collect()
+    }
+
+    /// Obtain a loader for the `language`, which falls back to the
+    /// fallback language, loading the `language` if it is not
+    /// already loaded. If the `language` is not available, the loader
+    /// only has the fallback language.
+    pub fn language(
+        &self,
+        language: &LanguageIdentifier,
+    ) -> Result<Arc<FluentLanguageLoader>, I18nEmbedError> {
+        Ok(self.language_entry(language)?.1)
+    }
+
+    /// Create a [LanguageScope] for the specified `languages` in order
+    /// of preference, like [FluentLanguageLoader::scope()], loading
+    /// any of the `languages` which are not already loaded.
+    pub fn scope<LI: AsRef<LanguageIdentifier>>(
+        &self,
+        languages: &[LI],
+    ) -> Result<LanguageScope<'_>, I18nEmbedError> {
+        // Only the languages which are available are selected, along
+        // with the fallback language, so that the languages of the
+        // scope match its bundles.
+        let mut selected_languages = Vec::with_capacity(languages.len() + 1);
+        let mut language_bundles = Vec::with_capacity(languages.len() + 1);
+        for language in languages {
+            let language = language.as_ref();
+            let (bundles, _) = self.language_entry(language)?;
+            if !bundles.is_empty() && !selected_languages.contains(language) {
+                selected_languages.push(language.clone());
+                language_bundles.push(bundles);
+            }
+        }
+        let fallback_language = self.loader.fallback_language();
+        if !selected_languages.contains(fallback_language) {
+            selected_languages.push(fallback_language.clone());
+            language_bundles.push(self.state.lock().fallback_bundles.clone());
+        }
+
+        Ok(LanguageScope {
+            loader: &self.loader,
+            current_languages: CurrentLanguages {
+                languages: selected_languages,
+                indices: (0..language_bundles.len()).collect(),
+            },
+            language_config: language_config(language_bundles),
+            region_context: self.loader.region_context.clone(),
+        })
+    }
+
+    /// Get a localized message referenced by the `message_id` in the
+    /// `language`, see [FluentLanguageLoader::get()]. If the language
+    /// can't be loaded, the message is obtained from the fallback
+    /// language.
+    pub fn get(&self, language: &LanguageIdentifier, message_id: &str) -> String {
+        self.language_or_fallback(language).get(message_id)
+    }
+
+    /// Get a localized message referenced by the `message_id` in the
+    /// `language` and formatted with the `args`, see
+    /// [FluentLanguageLoader::get_args()].
+    pub fn get_args<'a, S, V>(
+        &self,
+        language: &LanguageIdentifier,
+        message_id: &str,
+        args: HashMap<S, V>,
+    ) -> String
+    where
+        S: Into<Cow<'a, str>> + Clone,
+        V: Into<FluentValue<'a>> + Clone,
+    {
+        self.language_or_fallback(language)
+            .get_args(message_id, args)
+    }
+
+    /// Get the attribute `attribute_id` of the localized message
+    /// referenced by the `message_id` in the `language`, see
+    /// [FluentLanguageLoader::get_attr()].
+    pub fn get_attr(
+        &self,
+        language: &LanguageIdentifier,
+        message_id: &str,
+        attribute_id: &str,
+    ) -> String {
+        self.language_or_fallback(language)
+            .get_attr(message_id, attribute_id)
+    }
+
+    /// Whether the message referenced by the `message_id` is available
+    /// in the `language` or the fallback language, see
+    /// [FluentLanguageLoader::has()].
+    pub fn has(&self, language: &LanguageIdentifier, message_id: &str) -> bool {
+        self.language_or_fallback(language).has(message_id)
+    }
+
+    /// Evict the `language`, returning whether it was loaded. It is
+    /// loaded again if it is used later.
+    pub fn evict(&self, language: &LanguageIdentifier) -> bool {
+        let mut state = self.state.lock();
+        let len = state.languages.len();
+        state
+            .languages
+            .retain(|loaded| &loaded.language != language);
+        state.languages.len() != len
+    }
+
+    /// Reload the fallback language from the assets, and evict the
+    /// other languages so that they are reloaded when they are used.
+    pub fn reload(&self) -> Result<(), I18nEmbedError> {
+        let state = Self::load_fallback(&self.loader, &*self.i18n_assets)?;
+        *self.state.lock() = state;
+        Ok(())
+    }
+
+    fn load_fallback(
+        loader: &FluentLanguageLoader,
+        i18n_assets: &dyn I18nAssets,
+    ) -> Result<LazyState, I18nEmbedError> {
+        let fallback_language = loader.fallback_language().clone();
+        loader.load_languages(i18n_assets, std::slice::from_ref(&fallback_language))?;
+        let fallback_bundles = loader
+            .inner()
+            .load()
+            .language_config
+            .read()
+            .language_bundles
+            .first()
+            .cloned()
+            .unwrap_or_default();
+        let fallback = Arc::new(loader.derived(
+            CurrentLanguages {
+                languages: vec![fallback_language],
+                indices: vec![0],
+            },
+            language_config(vec![fallback_bundles.clone()]),
+        ));
+        Ok(LazyState {
+            fallback_bundles,
+            fallback,
+            languages: Vec::new(),
+        })
+    }
+
+    /// The loader for the `language`, or the loader for the fallback
+    /// language if the `language` can't be loaded.
+    fn language_or_fallback(&self, language: &LanguageIdentifier) -> Arc<FluentLanguageLoader> {
+        self.language(language).unwrap_or_else(|error| {
+            diagnostic!(
+                error,
+                target: "i18n_embed::fluent",
+                { language = language },
+                "Unable to load the language \"{0}\": {1}",
+                language,
+                error
+            );
+            self.state.lock().fallback.clone()
+        })
+    }
+
+    /// The bundles of the `language` and a loader for it, loading it
+    /// if it is not already loaded.
+    fn language_entry(
+        &self,
+        language: &LanguageIdentifier,
+    ) -> Result<(Vec<Arc<LanguageBundle>>, Arc<FluentLanguageLoader>), I18nEmbedError> {
+        if language == self.loader.fallback_language() {
+            let state = self.state.lock();
+            return Ok((state.fallback_bundles.clone(), state.fallback.clone()));
+        }
+        if let Some(loaded) = self.state.lock().touch(language) {
+            return Ok(loaded);
+        }
+
+        // The language is loaded without holding the lock, so that
+        // the lookups in other languages are not blocked.
+        diagnostic!(debug, target: "i18n_embed::fluent", { language = language }, "Lazily loading the language: \"{0}\"", language);
+        let bundles = self.loader.load_language_bundles(
+            language,
+            &*self.i18n_assets,
+            self.loader.pseudolocalization(),
+            &mut Vec::new(),
+        )?;
+
+        let mut state = self.state.lock();
+        if let Some(loaded) = state.touch(language) {
+            return Ok(loaded);
+        }
+        let mut languages = vec![self.loader.fallback_language().clone()];
+        let mut language_bundles = vec![state.fallback_bundles.clone()];
+        if !bundles.is_empty() {
+            languages.insert(0, language.clone());
+            language_bundles.insert(0, bundles.clone());
+        }
+        let loader = Arc::new(self.loader.derived(
+            CurrentLanguages {
+                languages,
+                indices: (0..language_bundles.len()).collect(),
+            },
+            language_config(language_bundles),
+        ));
+        state.languages.push(LazyLanguage {
+            language: language.clone(),
+            bundles: bundles.clone(),
+            loader: loader.clone(),
+        });
+        if let Some(capacity) = self.capacity {
+            state.evict_above(capacity);
+        }
+        Ok((bundles, loader))
+    }
+}
+
+impl LazyState {
+    /// Mark the `language` as the most recently used, returning its
+    /// bundles and loader if it is loaded.
+    fn touch(
+        &mut self,
+        language: &LanguageIdentifier,
+    ) -> Option<(Vec<Arc<LanguageBundle>>, Arc<FluentLanguageLoader>)> {
+        let index = self
+            .languages
+            .iter()
+            .position(|loaded| &loaded.language == language)?;
+        let loaded = self.languages.remove(index);
+        let entry = (loaded.bundles.clone(), loaded.loader.clone());
+        self.languages.push(loaded);
+        Some(entry)
+    }
+
+    /// Evict the least recently used languages until there are no
+    /// more than `capacity` of them.
+    fn evict_above(&mut self, capacity: NonZeroUsize) {
+        let excess = self.languages.len().saturating_sub(capacity.get());
+        for evicted in self.languages.drain(..excess) {
+            diagnostic!(debug, target: "i18n_embed::fluent", { language = evicted.language }, "Evicting the language: \"{0}\"", evicted.language);
+        }
+    }
+}
+
+/// A [LanguageConfig] with the `language_bundles` of each language,
+/// in order of priority.
+fn language_config(language_bundles: Vec<Vec<Arc<LanguageBundle>>>) -> Arc<RwLock<LanguageConfig>> {
+    Arc::new(RwLock::new(LanguageConfig {
+        language_map: language_bundles
+            .iter()
+            .enumerate()
+            .filter_map(|(i, bundles)| Some((bundles.first()?.language.clone(), i)))
+            .collect(),
+        language_bundles,
+    }))
+}
+
+/// A handle to a [FluentLanguageLoader] for obtaining messages with
+/// ids that share a common prefix, created using
+/// [FluentLanguageLoader::message_scope()]. This keeps call sites
+/// concise in modules which use many messages with ids like
+/// `settings-audio-volume` and `settings-audio-balance`.
+///
+/// ⚠️ *This API requires the following crate features to be activated: `fluent-system`.*
+#[derive(Debug, Clone)]
+pub struct MessageScope<'a> {
+    loader: &'a FluentLanguageLoader,
+    prefix: Cow<'a, str>,
+}
+
+impl<'a> MessageScope<'a> {
+    /// The prefix shared by the ids of the messages in this scope.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// The loader which this scope obtains messages from.
+    pub fn loader(&self) -> &'a FluentLanguageLoader {
+        self.loader
+    }
+
+    /// The full id of the message referenced by `message_id` within
+    /// this scope.
+    pub fn id(&self, message_id: &str) -> String {
+        format!("{}-{}", self.prefix, message_id)
+    }
+
+    /// Get a localized message referenced by the `message_id` within
+    /// this scope, see [FluentLanguageLoader::get()].
+    pub fn get(&self, message_id: &str) -> String {
+        self.loader.get(&self.id(message_id))
+    }
+
+    /// A non-generic version of [MessageScope::get_args()].
+    pub fn get_args_concrete<'args>(
+        &self,
+        message_id: &str,
+        args: HashMap<&'args str, FluentValue<'args>>,
+    ) -> String {
+        self.loader.get_args_concrete(&self.id(message_id), args)
+    }
+
+    /// Get a localized message referenced by the `message_id` within
+    /// this scope, and formatted with the specified `args`, see
+    /// [FluentLanguageLoader::get_args()].
+    pub fn get_args<'args, S, V>(&self, message_id: &str, args: HashMap<S, V>) -> String
+    where
+        S: Into<Cow<'args, str>> + Clone,
+        V: Into<FluentValue<'args>> + Clone,
+    {
+        self.loader.get_args(&self.id(message_id), args)
+    }
+
+    /// Get a localized attribute referenced by the `message_id`
+    /// within this scope and `attribute_id`, see
+    /// [FluentLanguageLoader::get_attr()].
+    pub fn get_attr(&self, message_id: &str, attribute_id: &str) -> String {
+        self.loader.get_attr(&self.id(message_id), attribute_id)
+    }
+
+    /// A non-generic version of [MessageScope::get_attr_args()].
+    pub fn get_attr_args_concrete<'args>(
+        &self,
+        message_id: &str,
+        attribute_id: &str,
+        args: HashMap<&'args str, FluentValue<'args>>,
+    ) -> String {
+        self.loader
+            .get_attr_args_concrete(&self.id(message_id), attribute_id, args)
+    }
+
+    /// Get a localized attribute referenced by the `message_id`
+    /// within this scope and `attribute_id`, and formatted with the
+    /// specified `args`, see [FluentLanguageLoader::get_attr_args()].
+    pub fn get_attr_args<'args, S, V>(
+        &self,
+        message_id: &str,
+        attribute_id: &str,
+        args: HashMap<S, V>,
+    ) -> String
+    where
+        S: Into<Cow<'args, str>> + Clone,
+        V: Into<FluentValue<'args>> + Clone,
+    {
+        self.loader
+            .get_attr_args(&self.id(message_id), attribute_id, args)
+    }
+
+    /// Determine if the message referenced by the `message_id` within
+    /// this scope is available, see [FluentLanguageLoader::has()].
+    pub fn has(&self, message_id: &str) -> bool {
+        self.loader.has(&self.id(message_id))
+    }
+}
+
+/// An immutable snapshot of the bundles and selected languages of a
+/// [FluentLanguageLoader], created using
+/// [FluentLanguageLoader::snapshot()]. It provides the same message
+/// lookup API as the loader, but without any locking, so it is ideal
+/// for handing to worker threads, FFI callbacks or parallel iterators
+/// which format large numbers of messages. Cloning a snapshot is
+/// cheap, as it only increments a reference count.
+///
+/// Lookups using a snapshot are not recorded by
+/// [FluentLanguageLoader::enable_lookup_tracing()], but missing
+/// messages are logged according to the loader's
+/// [FluentLanguageLoader::set_log_policy()].
+///
+/// ⚠️ *This API requires the following crate features to be activated: `fluent-system`.*
+#[derive(Debug, Clone)]
+pub struct LoaderSnapshot {
+    inner: Arc<LoaderSnapshotInner>,
+}
+
+#[derive(Debug)]
+struct LoaderSnapshotInner {
+    /// The bundles of the selected languages, in order of priority
+    /// (including the fallback language).
+    bundles: Vec<Arc<LanguageBundle>>,
+    languages: Vec<LanguageIdentifier>,
+    domain: String,
+    fallback_language: LanguageIdentifier,
+    missing_log: Arc<MissingLog>,
+}
+
+impl LoaderSnapshot {
+    /// The domain of the loader this snapshot was taken from.
+    pub fn domain(&self) -> &str {
+        &self.inner.domain
+    }
+
+    /// The fallback language of the loader this snapshot was taken
+    /// from.
+    pub fn fallback_language(&self) -> &LanguageIdentifier {
+        &self.inner.fallback_language
+    }
+
+    /// The languages which were selected when this snapshot was
+    /// taken, in order of priority.
+    pub fn current_languages(&self) -> &[LanguageIdentifier] {
+        &self.inner.languages
+    }
+
+    /// The language which was selected when this snapshot was taken.
+    pub fn current_language(&self) -> &LanguageIdentifier {
+        self.inner
+            .languages
+            .first()
+            .unwrap_or(&self.inner.fallback_language)
+    }
+
+    /// Get a localized message referenced by the `message_id`.
+    pub fn get(&self, message_id: &str) -> String {
+        self.get_args_fluent(message_id, None)
+    }
+
+    /// A non-generic version of [LoaderSnapshot::get_args()].
+    pub fn get_args_concrete<'args>(
+        &self,
+        message_id: &str,
+        args: HashMap<&'args str, FluentValue<'args>>,
+    ) -> String {
+        self.get_args_fluent(message_id, hash_map_to_fluent_args(args).as_ref())
+    }
+
+    /// A non-generic version of [LoaderSnapshot::get_args()]
+    /// accepting [FluentArgs] instead of a [HashMap].
+    pub fn get_args_fluent<'args>(
+        &self,
+        message_id: &str,
+        args: Option<&'args FluentArgs<'args>>,
+    ) -> String {
+        self.format(message_id, None, args).unwrap_or_else(|| {
+            self.inner.missing_log.missing(&MissingMessage {
+                domain: &self.inner.domain,
+                language: self.current_language(),
+                message_id,
+                attribute_id: None,
             })
+        })
     }
 
     /// Get a localized message referenced by the `message_id`, and
@@ -202,7 +3045,7 @@ impl FluentLanguageLoader {
         self.get_attr_args_fluent(message_id, attribute_id, None)
     }
 
-    /// A non-generic version of [FluentLanguageLoader::get_attr_args()].
+    /// A non-generic version of [LoaderSnapshot::get_attr_args()].
     pub fn get_attr_args_concrete<'args>(
         &self,
         message_id: &str,
@@ -216,7 +3059,7 @@ impl FluentLanguageLoader {
         )
     }
 
-    /// A non-generic version of [FluentLanguageLoader::get_attr_args()]
+    /// A non-generic version of [LoaderSnapshot::get_attr_args()]
     /// accepting [FluentArgs] instead of a [HashMap].
     pub fn get_attr_args_fluent<'args>(
         &self,
@@ -224,45 +3067,15 @@ impl FluentLanguageLoader {
         attribute_id: &str,
         args: Option<&'args FluentArgs<'args>>,
     ) -> String {
-        let inner = self.inner.load();
-        let language_config = inner.language_config.read();
-        let current_language = self.current_language_impl(&inner);
-
-        language_config.language_bundles.iter()
-            .flat_map(|language_bundles| language_bundles.iter())
-            .find_map(|language_bundle| {
-            language_bundle
-                .bundle
-                .get_message(message_id)
-                .and_then(|m: FluentMessage<'_>| {
-                    m.get_attribute(attribute_id)
-                    .map(|a: FluentAttribute<'_>| {
-                        a.value()
-                    })
-                })
-                .map(|pattern: &Pattern<&str>| {
-                    let mut errors = Vec::new();
-                    let value = language_bundle.bundle.format_pattern(pattern, args, &mut errors);
-                    if !errors.is_empty() {
-                        log::error!(
-                            target:"i18n_embed::fluent",
-                            "Failed to format a message for language \"{}\" and id \"{}\".\nErrors\n{:?}.",
-                            current_language, message_id, errors
-                        )
-                    }
-                    value.into()
+        self.format(message_id, Some(attribute_id), args)
+            .unwrap_or_else(|| {
+                self.inner.missing_log.missing(&MissingMessage {
+                    domain: &self.inner.domain,
+                    language: self.current_language(),
+                    message_id,
+                    attribute_id: Some(attribute_id),
                 })
-        })
-        .unwrap_or_else(|| {
-            log::error!(
-                target:"i18n_embed::fluent",
-                "Unable to find localization for language \"{}\", message id \"{}\" and attribute id \"{}\".",
-                current_language,
-                message_id,
-                attribute_id
-            );
-            format!("No localization for message id: \"{message_id}\" and attribute id: \"{attribute_id}\"")
-        })
+            })
     }
 
     /// Get a localized attribute referenced by the `message_id` and `attribute_id`, and
@@ -284,184 +3097,41 @@ impl FluentLanguageLoader {
         )
     }
 
-    /// available in any of the languages currently loaded (including
-    /// the fallback language).
+    /// Determine if a message with the specified `message_id` is
+    /// available in any of the selected languages (including the
+    /// fallback language).
     pub fn has(&self, message_id: &str) -> bool {
         self.inner
-            .load()
-            .language_config
-            .read()
-            .language_bundles
+            .bundles
             .iter()
-            .flat_map(|language_bundles| language_bundles.iter())
-            .any(|language_bundle| language_bundle.bundle.has_message(message_id))
+            .any(|language_bundle| language_bundle.get_message(message_id).is_some())
     }
 
-    /// Determines if an attribute associated with the specified `message_id`
-    /// is available in any of the currently loaded languages, including the fallback language.
-    ///
-    /// Returns true if at least one available instance was found,
-    /// false otherwise.
-    ///
-    /// Note that this also returns false if the `message_id` could not be found;
-    /// use [FluentLanguageLoader::has()] to determine if the `message_id` is available.
+    /// Determine if an attribute associated with the specified
+    /// `message_id` is available in any of the selected languages
+    /// (including the fallback language).
     pub fn has_attr(&self, message_id: &str, attribute_id: &str) -> bool {
         self.inner
-            .load()
-            .language_config
-            .read()
-            .language_bundles
+            .bundles
             .iter()
-            .flat_map(|bundles| bundles.iter())
-            .find_map(|bundle| {
-                bundle
-                    .bundle
+            .find_map(|language_bundle| {
+                language_bundle
                     .get_message(message_id)
                     .map(|message| message.get_attribute(attribute_id).is_some())
             })
             .unwrap_or(false)
     }
 
-    /// Run the `closure` with the message that matches the specified
-    /// `message_id` (if it is available in any of the languages
-    /// currently loaded, including the fallback language). Returns
-    /// `Some` of whatever whatever the closure returns, or `None` if
-    /// no messages were found matching the `message_id`.
-    pub fn with_fluent_message<OUT, C>(&self, message_id: &str, closure: C) -> Option<OUT>
-    where
-        C: Fn(fluent::FluentMessage<'_>) -> OUT,
-    {
-        self.inner
-            .load()
-            .language_config
-            .read()
-            .language_bundles
-            .iter()
-            .flat_map(|language_bundles| language_bundles.iter())
-            .find_map(|language_bundle| language_bundle.bundle.get_message(message_id))
-            .map(closure)
-    }
-
-    /// Runs the provided `closure` with an iterator over the messages
-    /// available for the specified `language`. There may be duplicate
-    /// messages when they are duplicated in resources applicable to
-    /// the language. Returns the result of the closure.
-    pub fn with_message_iter<OUT, C>(&self, language: &LanguageIdentifier, closure: C) -> OUT
-    where
-        C: Fn(&mut dyn Iterator<Item = &ast::Message<&str>>) -> OUT,
-    {
-        let inner = self.inner.load();
-        let config_lock = inner.language_config.read();
-
-        let mut iter = config_lock
-            .language_bundles
-            .iter()
-            .flat_map(|language_bundles| language_bundles.iter())
-            .filter(|language_bundle| &language_bundle.language == language)
-            .flat_map(|language_bundle| {
-                language_bundle
-                    .resource
-                    .entries()
-                    .filter_map(|entry| match entry {
-                        ast::Entry::Message(message) => Some(message),
-                        _ => None,
-                    })
-            });
-
-        (closure)(&mut iter)
-    }
-
-    /// Set whether the underlying Fluent logic should insert Unicode
-    /// Directionality Isolation Marks around placeables.
-    ///
-    /// See [`fluent::bundle::FluentBundleBase::set_use_isolating`] for more
-    /// information.
-    ///
-    /// **Note:** This function will have no effect if
-    /// [`LanguageLoader::load_languages`] has not been called first.
-    ///
-    /// Default: `true`.
-    pub fn set_use_isolating(&self, value: bool) {
-        self.with_bundles_mut(|bundle| bundle.set_use_isolating(value));
-    }
-
-    /// Apply some configuration to each budle in this loader.
-    ///
-    /// **Note:** This function will have no effect if
-    /// [`LanguageLoader::load_languages`] has not been called first.
-    pub fn with_bundles_mut<F>(&self, f: F)
-    where
-        F: Fn(&mut FluentBundle<Arc<FluentResource>, IntlLangMemoizer>),
-    {
-        for bundle in self
-            .inner
-            .load()
-            .language_config
-            .write()
-            .language_bundles
-            .iter_mut()
-            .flat_map(|bundles| bundles.iter_mut())
-        {
-            f(&mut bundle.bundle);
-        }
-    }
-
-    /// Create a new loader with a subset of currently loaded languages.
-    /// This is a rather cheap operation and does not require any
-    /// extensive copy operations. Cheap does not mean free so you
-    /// should not call this message repeatedly in order to translate
-    /// multiple strings for the same language.
-    pub fn select_languages<LI: AsRef<LanguageIdentifier>>(
-        &self,
-        languages: &[LI],
-    ) -> FluentLanguageLoader {
-        let inner = self.inner.load();
-        let config_lock = inner.language_config.read();
-        let fallback_language: Option<&unic_langid::LanguageIdentifier> = if languages
-            .iter()
-            .any(|language| language.as_ref() == &self.fallback_language)
-        {
-            None
-        } else {
-            Some(&self.fallback_language)
-        };
-
-        let indices = languages
-            .iter()
-            .map(|lang| lang.as_ref())
-            .chain(fallback_language)
-            .filter_map(|lang| config_lock.language_map.get(lang.as_ref()))
-            .cloned()
-            .collect();
-        FluentLanguageLoader {
-            inner: ArcSwap::new(Arc::new(FluentLanguageLoaderInner {
-                current_languages: CurrentLanguages {
-                    languages: languages.iter().map(|lang| lang.as_ref().clone()).collect(),
-                    indices,
-                },
-                language_config: self.inner.load().language_config.clone(),
-            })),
-            domain: self.domain.clone(),
-            fallback_language: self.fallback_language.clone(),
-        }
-    }
-
-    /// Select the requested `languages` from the currently loaded languages using the supplied
-    /// [`NegotiationStrategy`].
-    pub fn select_languages_negotiate<LI: AsRef<LanguageIdentifier>>(
+    fn format<'args>(
         &self,
-        languages: &[LI],
-        strategy: NegotiationStrategy,
-    ) -> FluentLanguageLoader {
-        let available_languages = &self.inner.load().current_languages.languages;
-        let negotiated_languages = fluent_langneg::negotiate_languages(
-            languages,
-            available_languages,
-            Some(self.fallback_language()),
-            strategy,
-        );
-
-        self.select_languages(&negotiated_languages)
+        message_id: &str,
+        attribute_id: Option<&str>,
+        args: Option<&'args FluentArgs<'args>>,
+    ) -> Option<String> {
+        let current_language = self.current_language();
+        self.inner.bundles.iter().find_map(|language_bundle| {
+            language_bundle.format(message_id, attribute_id, args, current_language)
+        })
     }
 }
 
@@ -481,9 +3151,24 @@ impl LanguageLoader for FluentLanguageLoader {
         format!("{}.ftl", self.domain())
     }
 
+    /// The template for the paths of the language files, see
+    /// [FluentLanguageLoader::set_path_template()].
+    fn path_template(&self) -> PathTemplate {
+        self.path_template
+            .lock()
+            .clone()
+            .unwrap_or_else(|| PathTemplate::with_file_name(&self.language_file_name()))
+    }
+
+    /// The aliases for languages, see
+    /// [FluentLanguageLoader::set_language_aliases()].
+    fn language_aliases(&self) -> LanguageAliases {
+        self.language_aliases.lock().clone()
+    }
+
     /// Get the language which is currently selected for this loader.
     fn current_language(&self) -> unic_langid::LanguageIdentifier {
-        self.current_language_impl(&self.inner.load())
+        self.current_language_impl(&self.inner().load())
     }
 
     /// Load the languages `language_ids` using the resources packaged
@@ -498,6 +3183,34 @@ impl LanguageLoader for FluentLanguageLoader {
         i18n_assets: &dyn I18nAssets,
         language_ids: &[unic_langid::LanguageIdentifier],
     ) -> Result<(), I18nEmbedError> {
+        self.load_languages_impl(i18n_assets, language_ids, false)
+            .map(|_| ())
+    }
+
+    fn reload(&self, i18n_assets: &dyn I18nAssets) -> Result<(), I18nEmbedError> {
+        self.load_languages(
+            i18n_assets,
+            &self.inner().load().current_languages.languages.clone(),
+        )
+    }
+}
+
+impl FluentLanguageLoader {
+    /// Load the languages `language_ids`, see
+    /// [LanguageLoader::load_languages()], returning the problems
+    /// found in their fluent files. If `strict` is true, the
+    /// languages are not loaded when there are problems.
+    fn load_languages_impl(
+        &self,
+        i18n_assets: &dyn I18nAssets,
+        language_ids: &[unic_langid::LanguageIdentifier],
+        strict: bool,
+    ) -> Result<Vec<ResourceDiagnostic>, I18nEmbedError> {
+        let _span = diagnostic_span!(
+            "load_languages",
+            domain = %self.domain(),
+            languages = ?language_ids
+        );
         let mut language_ids = language_ids.iter().peekable();
         if language_ids.peek().is_none() {
             return Err(I18nEmbedError::RequestedLanguagesEmpty);
@@ -511,39 +3224,25 @@ impl LanguageLoader for FluentLanguageLoader {
         if !load_language_ids.contains(&self.fallback_language) {
             load_language_ids.push(self.fallback_language.clone());
         }
-        let language_bundles: Vec<Vec<_>> = load_language_ids.iter().map(|language| {
-            let (path, files) = self.language_files(language, i18n_assets);
-
-            if files.is_empty() {
-                log::debug!(target:"i18n_embed::fluent", "Unable to find language file: \"{0}\" for language: \"{1}\"", path, language);
-                if language == &self.fallback_language {
-                    return Err(I18nEmbedError::LanguageNotAvailable(path, language.clone()));
-                }
-            }
-            files.into_iter().map(|file| {
-                log::debug!(target:"i18n_embed::fluent", "Loaded language file: \"{0}\" for language: \"{1}\"", path, language);
-
-                let file_string = String::from_utf8(file.to_vec())
-                    .map_err(|err| I18nEmbedError::ErrorParsingFileUtf8(path.clone(), err))?
-                    // TODO: Workaround for https://github.com/kellpossible/cargo-i18n/issues/57
-                    // remove when https://github.com/projectfluent/fluent-rs/issues/213 is resolved.
-                    .replace("\u{000D}\n", "\n");
-
-                let resource = match FluentResource::try_new(file_string) {
-                    Ok(resource) => resource,
-                    Err((resource, errors)) => {
-                        errors.iter().for_each(|err| {
-                            log::error!(target: "i18n_embed::fluent", "Error while parsing fluent language file \"{0}\": \"{1:?}\".", path, err);
-                        });
-                        resource
-                    }
-                };
+        let pseudolocalization = self.pseudolocalization();
+        let mut diagnostics = Vec::new();
+        let language_bundles: Vec<Vec<_>> = load_language_ids
+            .iter()
+            .map(|language| {
+                self.load_language_bundles(
+                    language,
+                    i18n_assets,
+                    pseudolocalization,
+                    &mut diagnostics,
+                )
+            })
+            .collect::<Result<_, I18nEmbedError>>()?;
 
-                Ok(LanguageBundle::new(language.clone(), resource))
-            }).collect::<Result<Vec<_>, I18nEmbedError>>()
-        }).collect::<Result<_, I18nEmbedError>>()?;
+        if strict && !diagnostics.is_empty() {
+            return Err(I18nEmbedError::InvalidResources(diagnostics));
+        }
 
-        self.inner.swap(Arc::new(FluentLanguageLoaderInner {
+        self.inner().swap(Arc::new(FluentLanguageLoaderInner {
             current_languages: CurrentLanguages {
                 languages: language_ids,
                 indices: (0..load_language_ids.len()).collect(),
@@ -561,17 +3260,227 @@ impl LanguageLoader for FluentLanguageLoader {
                     .collect(),
                 language_bundles,
             })),
+            #[cfg(feature = "cache")]
+            message_cache: MessageCache::default(),
         }));
 
-        Ok(())
+        Ok(diagnostics)
     }
 
-    fn reload(&self, i18n_assets: &dyn I18nAssets) -> Result<(), I18nEmbedError> {
-        self.load_languages(
-            i18n_assets,
-            &self.inner.load().current_languages.languages.clone(),
+    /// Load the bundles of the `language` from its files in the
+    /// `i18n_assets`, in order of priority, appending the problems
+    /// found in them to `diagnostics`. There are no bundles if the
+    /// language is not available, which is an error for the
+    /// [LanguageLoader::fallback_language()].
+    fn load_language_bundles(
+        &self,
+        language: &LanguageIdentifier,
+        i18n_assets: &dyn I18nAssets,
+        pseudolocalization: Option<Pseudolocalization>,
+        diagnostics: &mut Vec<ResourceDiagnostic>,
+    ) -> Result<Vec<Arc<LanguageBundle>>, I18nEmbedError> {
+        let (path, files) = self.language_files(language, i18n_assets);
+        let files = files
+            .into_iter()
+            .map(|file| (path.clone(), None, file))
+            .chain(self.namespace_files(language, i18n_assets))
+            .collect::<Vec<_>>();
+
+        if files.is_empty() {
+            diagnostic!(debug, target: "i18n_embed::fluent", { path = path, language = language }, "Unable to find language file: \"{0}\" for language: \"{1}\"", path, language);
+            if language == &self.fallback_language {
+                return Err(I18nEmbedError::LanguageNotAvailable(path, language.clone()));
+            }
+        }
+        let terms = if files.is_empty() {
+            None
+        } else {
+            self.terms_resource(language, i18n_assets, diagnostics)?
+        };
+
+        files.into_iter().map(|(path, namespace, file)| {
+            diagnostic!(debug, target: "i18n_embed::fluent", { path = path, language = language }, "Loaded language file: \"{0}\" for language: \"{1}\"", path, language);
+
+            let mut errors = Vec::new();
+            let resource = self.parse_resource(language, &path, &file, &mut errors)?;
+            let mut bundle = LanguageBundle::new(language.clone(), namespace, resource, terms.clone(), pseudolocalization, &mut errors);
+            bundle.bundle.set_use_isolating(self.use_isolating());
+            diagnostics.extend(errors.into_iter().map(|error| ResourceDiagnostic {
+                language: language.clone(),
+                path: path.clone(),
+                error,
+            }));
+            Ok(Arc::new(bundle))
+        }).collect::<Result<Vec<_>, I18nEmbedError>>()
+    }
+
+    /// Load the shared terms of the `language` from its
+    /// [TERMS_FILE], if the assets contain one.
+    fn terms_resource(
+        &self,
+        language: &LanguageIdentifier,
+        i18n_assets: &dyn I18nAssets,
+        diagnostics: &mut Vec<ResourceDiagnostic>,
+    ) -> Result<Option<Arc<FluentResource>>, I18nEmbedError> {
+        let path = match self.path_template().sibling_path(
+            self.asset_language(language, i18n_assets),
+            self.domain(),
+            TERMS_FILE,
+        ) {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+        let file = match i18n_assets.get_files(&path).into_iter().next() {
+            Some(file) => file,
+            None => return Ok(None),
+        };
+        diagnostic!(debug, target: "i18n_embed::fluent", { path = path, language = language }, "Loaded terms file: \"{0}\" for language: \"{1}\"", path, language);
+
+        let mut errors = Vec::new();
+        let resource = self.parse_resource(language, &path, &file, &mut errors)?;
+        diagnostics.extend(errors.into_iter().map(|error| ResourceDiagnostic {
+            language: language.clone(),
+            path: path.clone(),
+            error,
+        }));
+        Ok(Some(Arc::new(resource)))
+    }
+
+    /// Parse the fluent `file` at the `path`, appending the syntax
+    /// errors to `errors`.
+    fn parse_resource(
+        &self,
+        language: &LanguageIdentifier,
+        path: &str,
+        file: &[u8],
+        errors: &mut Vec<FluentError>,
+    ) -> Result<FluentResource, I18nEmbedError> {
+        let file_string = String::from_utf8(file.to_vec())
+            .map_err(|err| I18nEmbedError::ErrorParsingFileUtf8(path.to_string(), err))?
+            // TODO: Workaround for https://github.com/kellpossible/cargo-i18n/issues/57
+            // remove when https://github.com/projectfluent/fluent-rs/issues/213 is resolved.
+            .replace("\u{000D}\n", "\n");
+
+        let file_string = if self.message_format_placeholders.load(Ordering::Relaxed) {
+            crate::message_format::convert_placeholders(path, &file_string)
+        } else {
+            file_string
+        };
+
+        Ok(match FluentResource::try_new(file_string) {
+            Ok(resource) => resource,
+            Err((resource, parser_errors)) => {
+                parser_errors.iter().for_each(|err| {
+                    diagnostic!(error, target: "i18n_embed::fluent", { path = path, language = language }, "Error while parsing fluent language file \"{0}\" for language \"{1}\": \"{2:?}\".", path, language, err);
+                });
+                errors.extend(parser_errors.into_iter().map(FluentError::ParserError));
+                resource
+            }
+        })
+    }
+}
+
+fn global_loaders() -> &'static RwLock<HashMap<String, &'static FluentLanguageLoader>> {
+    static GLOBAL_LOADERS: OnceLock<RwLock<HashMap<String, &'static FluentLanguageLoader>>> =
+        OnceLock::new();
+    GLOBAL_LOADERS.get_or_init(Default::default)
+}
+
+/// Register `loader` as the global loader for its
+/// [LanguageLoader::domain()], which is used by the `fl!()` macro
+/// when it is invoked without a loader. Returns the loader which was
+/// previously registered for the domain, if any.
+///
+/// See [init_global_loader!()](crate::init_global_loader!()) for a
+/// convenient way to create and register the loader for the current
+/// crate.
+pub fn set_global_loader(
+    loader: &'static FluentLanguageLoader,
+) -> Option<&'static FluentLanguageLoader> {
+    global_loaders()
+        .write()
+        .insert(loader.domain().to_string(), loader)
+}
+
+/// Get the global loader registered for the `domain` using
+/// [set_global_loader()], or `None` if there is none.
+pub fn try_global_loader(domain: &str) -> Option<&'static FluentLanguageLoader> {
+    global_loaders().read().get(domain).copied()
+}
+
+/// Get the global loader registered for the `domain` using
+/// [set_global_loader()].
+///
+/// # Panics
+///
+/// If no loader has been registered for the `domain`.
+pub fn global_loader(domain: &str) -> &'static FluentLanguageLoader {
+    try_global_loader(domain).unwrap_or_else(|| {
+        panic!(
+            "No global FluentLanguageLoader has been registered for the domain \"{}\", \
+            use init_global_loader!() or set_global_loader() to register one.",
+            domain
         )
+    })
+}
+
+/// Create a [FluentLanguageLoader] for the current crate using
+/// [fluent_language_loader!()], and register it as the global loader
+/// for its domain with [set_global_loader()], so that the `fl!()`
+/// macro can be used without specifying a loader. Evaluates to the
+/// `&'static FluentLanguageLoader`, which still needs to have its
+/// languages loaded.
+///
+/// The loader is stored in a `static` for each invocation of this
+/// macro, and only created the first time it is evaluated, so
+/// evaluating the same invocation again (e.g. in a function which is
+/// called more than once) registers and returns the same loader.
+///
+/// ⚠️ *This API requires the following crate features to be activated: `fluent-system`.*
+///
+/// ## Example
+///
+/// ```ignore
+/// let loader: &'static FluentLanguageLoader = i18n_embed::init_global_loader!();
+/// loader.load_fallback_language(&Localizations)?;
+///
+/// let message = fl!("hello-world");
+/// ```
+#[macro_export]
+macro_rules! init_global_loader {
+    () => {{
+        static LOADER: ::std::sync::OnceLock<$crate::fluent::FluentLanguageLoader> =
+            ::std::sync::OnceLock::new();
+        let loader: &'static $crate::fluent::FluentLanguageLoader =
+            LOADER.get_or_init(|| $crate::fluent::fluent_language_loader!());
+        $crate::fluent::set_global_loader(loader);
+        loader
+    }};
+}
+
+/// The `args` along with the variables of the region `context`, or
+/// `None` if there is no `context`, see
+/// [FluentLanguageLoader::with_region_context()].
+fn region_args<'args>(
+    context: Option<&RegionContext>,
+    args: Option<&'args FluentArgs<'args>>,
+    current_language: &LanguageIdentifier,
+) -> Option<FluentArgs<'args>> {
+    let context = context?;
+    let mut region_args = FluentArgs::new();
+    if let Some(region) = context.region_or(current_language) {
+        region_args.set("region", region.as_str().to_string());
+    }
+    if let Some(currency) = &context.currency {
+        region_args.set("currency", currency.clone());
+    }
+    if let Some(timezone) = &context.timezone {
+        region_args.set("timezone", timezone.clone());
+    }
+    for (name, value) in args.into_iter().flat_map(FluentArgs::iter) {
+        region_args.set(name.to_string(), value.clone());
     }
+    Some(region_args)
 }
 
 fn hash_map_to_fluent_args<'args, K, V>(map: HashMap<K, V>) -> Option<FluentArgs<'args>>
@@ -5,7 +5,9 @@
 //!
 //! ⚠️ *This module requires the following crate features to be activated: `gettext-system`.*
 
-use crate::{domain_from_module, I18nAssets, I18nEmbedError, LanguageLoader};
+use crate::{
+    domain_from_module, I18nAssets, I18nEmbedError, LanguageAliases, LanguageLoader, PathTemplate,
+};
 
 pub use i18n_embed_impl::gettext_language_loader;
 
@@ -20,8 +22,15 @@ use unic_langid::LanguageIdentifier;
 #[derive(Debug)]
 pub struct GettextLanguageLoader {
     current_language: RwLock<LanguageIdentifier>,
+    /// The language requested when languages were last loaded, which
+    /// is loaded again by [LanguageLoader::reload()], even if the
+    /// `current_language` is the fallback language because its `mo`
+    /// file was not available.
+    requested_language: RwLock<LanguageIdentifier>,
     module: &'static str,
     fallback_language: LanguageIdentifier,
+    path_template: RwLock<Option<PathTemplate>>,
+    language_aliases: RwLock<LanguageAliases>,
 }
 
 impl GettextLanguageLoader {
@@ -37,11 +46,97 @@ impl GettextLanguageLoader {
     pub fn new(module: &'static str, fallback_language: unic_langid::LanguageIdentifier) -> Self {
         Self {
             current_language: RwLock::new(fallback_language.clone()),
+            requested_language: RwLock::new(fallback_language.clone()),
             module,
             fallback_language,
+            path_template: RwLock::new(None),
+            language_aliases: RwLock::new(LanguageAliases::new()),
         }
     }
 
+    /// Locate the `mo` files within the assets using the
+    /// `path_template` (e.g. `locales/{language}.mo`), instead of the
+    /// default `{language}/{domain}.mo` layout if it is `None`. This
+    /// takes effect the next time languages are loaded.
+    pub fn set_path_template(&self, path_template: Option<PathTemplate>) {
+        *self.path_template.write() = path_template;
+    }
+
+    /// Serve requests for a language using the `mo` files of another
+    /// language, see [LanguageAliases]. This takes effect the next
+    /// time languages are loaded.
+    pub fn set_language_aliases(&self, language_aliases: LanguageAliases) {
+        *self.language_aliases.write() = language_aliases;
+    }
+
+    /// Translate the message `msgid` using the currently loaded
+    /// catalog, or return `msgid` unchanged if there is no translation
+    /// available.
+    ///
+    /// This is equivalent to the lookup performed by the `tr!` macro
+    /// for this loader's module, without formatting any arguments.
+    pub fn gettext(&self, msgid: &str) -> String {
+        tr::internal::with_translator(self.module, |translator| {
+            translator.translate(msgid, None).into_owned()
+        })
+    }
+
+    /// Translate the message `msgid` in the specified `context`, see
+    /// [GettextLanguageLoader::gettext()].
+    pub fn pgettext(&self, context: &str, msgid: &str) -> String {
+        tr::internal::with_translator(self.module, |translator| {
+            translator.translate(msgid, Some(context)).into_owned()
+        })
+    }
+
+    /// Translate the message `msgid`, selecting the plural form for
+    /// the number `n` using the `Plural-Forms` header of the currently
+    /// loaded catalog. If there is no translation available, `msgid`
+    /// is returned when `n == 1`, and `msgid_plural` otherwise.
+    ///
+    /// This is equivalent to the lookup performed by the `tr!` macro
+    /// with plurals (`tr!("singular" | "plural" % n)`) for this
+    /// loader's module, without formatting any arguments.
+    ///
+    /// **Note:** The plural expression evaluator in the `gettext` crate
+    /// does not respect the precedence of the ternary operator, so
+    /// conditions in the `Plural-Forms` header need to be enclosed in
+    /// parentheses, e.g. `plural=(n%10==1 && n%100!=11) ? 0 : ((n%10>=2
+    /// && n%10<=4 && (n%100<10 || n%100>=20)) ? 1 : 2);`.
+    pub fn ngettext(&self, msgid: &str, msgid_plural: &str, n: u64) -> String {
+        tr::internal::with_translator(self.module, |translator| {
+            translator
+                .ntranslate(n, msgid, msgid_plural, None)
+                .into_owned()
+        })
+    }
+
+    /// Translate the message `msgid` in the specified `context`,
+    /// selecting the plural form for the number `n`, see
+    /// [GettextLanguageLoader::ngettext()].
+    pub fn npgettext(&self, context: &str, msgid: &str, msgid_plural: &str, n: u64) -> String {
+        tr::internal::with_translator(self.module, |translator| {
+            translator
+                .ntranslate(n, msgid, msgid_plural, Some(context))
+                .into_owned()
+        })
+    }
+
+    /// The value of the `Plural-Forms` header of the currently loaded
+    /// catalog (e.g. `nplurals=2; plural=(n != 1);`), or `None` if the
+    /// catalog has no such header, or no catalog is loaded.
+    pub fn plural_forms(&self) -> Option<String> {
+        let metadata = self.gettext("");
+        metadata.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            if key.trim().eq_ignore_ascii_case("Plural-Forms") {
+                Some(value.trim().to_string())
+            } else {
+                None
+            }
+        })
+    }
+
     fn load_src_language(&self) {
         let catalog = gettext_system::Catalog::empty();
         tr::internal::set_translator(self.module, catalog);
@@ -66,6 +161,21 @@ impl LanguageLoader for GettextLanguageLoader {
         format!("{}.mo", self.domain())
     }
 
+    /// The template for the paths of the `mo` files, see
+    /// [GettextLanguageLoader::set_path_template()].
+    fn path_template(&self) -> PathTemplate {
+        self.path_template
+            .read()
+            .clone()
+            .unwrap_or_else(|| PathTemplate::with_file_name(&self.language_file_name()))
+    }
+
+    /// The aliases for languages, see
+    /// [GettextLanguageLoader::set_language_aliases()].
+    fn language_aliases(&self) -> LanguageAliases {
+        self.language_aliases.read().clone()
+    }
+
     /// Get the language which is currently loaded for this loader.
     fn current_language(&self) -> LanguageIdentifier {
         self.current_language.read().clone()
@@ -81,16 +191,26 @@ impl LanguageLoader for GettextLanguageLoader {
     /// **Note:** Gettext doesn't support loading multiple languages
     /// as multiple fallbacks. We only load the first of the requested
     /// languages, and the fallback is the src language.
+    ///
+    /// If the `mo` file of the language cannot be parsed, an
+    /// [I18nEmbedError::Gettext] error is returned, and the catalog
+    /// which was previously loaded is kept.
     #[allow(single_use_lifetimes)]
     fn load_languages(
         &self,
         i18n_assets: &dyn I18nAssets,
         language_ids: &[unic_langid::LanguageIdentifier],
     ) -> Result<(), I18nEmbedError> {
+        let _span = diagnostic_span!(
+            "load_languages",
+            domain = %self.domain(),
+            languages = ?language_ids
+        );
         let language_id = language_ids
             .iter()
             .next()
             .ok_or(I18nEmbedError::RequestedLanguagesEmpty)?;
+        *self.requested_language.write() = language_id.clone();
 
         if language_id == self.fallback_language() {
             self.load_src_language();
@@ -100,30 +220,42 @@ impl LanguageLoader for GettextLanguageLoader {
         let file = match files.as_slice() {
             [first_file] => first_file,
             [first_file, ..] => {
-                log::warn!(
+                diagnostic!(
+                    warn,
+                    target: "i18n_embed::gettext",
+                    { path = path, language = language_id },
                     "Gettext system does not yet support merging language files for {path:?}"
                 );
                 first_file
             }
             [] => {
-                log::error!(
-                    target:"i18n_embed::gettext", 
-                    "{} Setting current_language to fallback locale: \"{}\".", 
-                    I18nEmbedError::LanguageNotAvailable(path, language_id.clone()),
-                    self.fallback_language);
+                diagnostic!(
+                    error,
+                    target: "i18n_embed::gettext",
+                    { path = path, language = language_id },
+                    "{} Setting current_language to fallback locale: \"{}\".",
+                    I18nEmbedError::LanguageNotAvailable(path.clone(), language_id.clone()),
+                    self.fallback_language
+                );
                 self.load_src_language();
                 return Ok(());
             }
         };
 
-        let catalog = gettext_system::Catalog::parse(&**file).expect("could not parse the catalog");
+        let catalog = gettext_system::Catalog::parse(&**file)?;
         tr::internal::set_translator(self.module, catalog);
         *(self.current_language.write()) = language_id.clone();
 
         Ok(())
     }
 
+    /// Reload the language which was requested when languages were
+    /// last loaded, for example when its `mo` file is modified (see
+    /// [DefaultLocalizer::with_autoreload()](crate::DefaultLocalizer::with_autoreload())).
+    /// If the language was not available, and the fallback language
+    /// was loaded instead, it is loaded if it has become available.
     fn reload(&self, i18n_assets: &dyn I18nAssets) -> Result<(), I18nEmbedError> {
-        self.load_languages(i18n_assets, &[self.current_language()])
+        let requested_language = self.requested_language.read().clone();
+        self.load_languages(i18n_assets, &[requested_language])
     }
 }
@@ -0,0 +1,99 @@
+use std::iter::FromIterator;
+
+use unic_langid::LanguageIdentifier;
+
+/// Aliases for languages, used by a
+/// [LanguageLoader](crate::LanguageLoader) to serve a requested
+/// language using the files of another language which are available
+/// in the [I18nAssets](crate::I18nAssets). This allows assets
+/// produced by external tools with different naming conventions to be
+/// loaded without renaming them, for example:
+///
+/// + `no` served by the files of `nb`.
+/// + `zh-CN` served by the files of `zh-Hans`.
+///
+/// The alias is reported as available by
+/// [LanguageLoader::available_languages()](crate::LanguageLoader::available_languages())
+/// when the language it refers to is available, so that it can be
+/// selected during language negotiation.
+///
+/// ```
+/// use i18n_embed::LanguageAliases;
+///
+/// let mut aliases = LanguageAliases::new();
+/// aliases.insert("no".parse().unwrap(), "nb".parse().unwrap());
+/// assert_eq!(
+///     Some("nb".parse().unwrap()),
+///     aliases.resolve(&"no".parse().unwrap())
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LanguageAliases {
+    /// Aliases created in a `const` context, see
+    /// [LanguageAliases::new_const()].
+    static_aliases: &'static [(&'static str, &'static str)],
+    aliases: Vec<(LanguageIdentifier, LanguageIdentifier)>,
+}
+
+impl LanguageAliases {
+    /// Create a new empty set of aliases.
+    pub const fn new() -> Self {
+        Self {
+            static_aliases: &[],
+            aliases: Vec::new(),
+        }
+    }
+
+    /// Create a new set of aliases in a `const` context from pairs of
+    /// `(alias, language)` identifiers. Identifiers which fail to
+    /// parse are ignored, this is intended for aliases which have
+    /// already been validated (e.g. by the `static_loader!()` macro).
+    pub const fn new_const(aliases: &'static [(&'static str, &'static str)]) -> Self {
+        Self {
+            static_aliases: aliases,
+            aliases: Vec::new(),
+        }
+    }
+
+    /// Serve requests for the `alias` using the files of the
+    /// `language`, replacing any existing alias with the same
+    /// identifier.
+    pub fn insert(&mut self, alias: LanguageIdentifier, language: LanguageIdentifier) {
+        self.aliases.retain(|(existing, _)| existing != &alias);
+        self.aliases.push((alias, language));
+    }
+
+    /// Whether there are no aliases.
+    pub fn is_empty(&self) -> bool {
+        self.static_aliases.is_empty() && self.aliases.is_empty()
+    }
+
+    /// Iterate over the pairs of `(alias, language)`.
+    pub fn iter(&self) -> impl Iterator<Item = (LanguageIdentifier, LanguageIdentifier)> + '_ {
+        self.static_aliases
+            .iter()
+            .filter_map(|(alias, language)| Some((alias.parse().ok()?, language.parse().ok()?)))
+            .chain(self.aliases.iter().cloned())
+    }
+
+    /// The language whose files serve requests for the `alias`, or
+    /// `None` if it is not an alias.
+    pub fn resolve(&self, alias: &LanguageIdentifier) -> Option<LanguageIdentifier> {
+        self.iter()
+            .filter(|(existing, _)| existing == alias)
+            .map(|(_, language)| language)
+            .last()
+    }
+}
+
+impl FromIterator<(LanguageIdentifier, LanguageIdentifier)> for LanguageAliases {
+    fn from_iter<T: IntoIterator<Item = (LanguageIdentifier, LanguageIdentifier)>>(
+        iter: T,
+    ) -> Self {
+        let mut aliases = Self::new();
+        for (alias, language) in iter {
+            aliases.insert(alias, language);
+        }
+        aliases
+    }
+}
@@ -0,0 +1,388 @@
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+
+use sha2::{Digest, Sha256};
+use unic_langid::LanguageIdentifier;
+
+use crate::{assets::Subscribers, I18nAssets, I18nEmbedError, Watcher};
+
+/// The path of the manifest of the language packs in a
+/// [LanguagePackSource].
+pub const LANGUAGE_PACK_MANIFEST: &str = "SHA256SUMS";
+
+/// The path of the Ed25519 signature of the [LANGUAGE_PACK_MANIFEST]
+/// in a [LanguagePackSource], which is verified by a
+/// [LanguagePackManager] with a public key (see
+/// [LanguagePackManager::with_public_key()]).
+///
+/// ⚠️ *This API requires the following crate features to be activated: `language-pack-signatures`.*
+#[cfg(feature = "language-pack-signatures")]
+pub const LANGUAGE_PACK_SIGNATURE: &str = "SHA256SUMS.sig";
+
+/// A source of downloadable language packs, such as a HTTP server.
+///
+/// The source contains a [manifest](LanguagePackManifest) at
+/// [LANGUAGE_PACK_MANIFEST], and the files which it lists, using
+/// the same layout as the other [I18nAssets] (e.g.
+/// `de-DE/my_crate.ftl`).
+pub trait LanguagePackSource {
+    /// Fetch the contents of the file at `path`, relative to the root
+    /// of the source.
+    fn fetch(&self, path: &str) -> Result<Vec<u8>, I18nEmbedError>;
+}
+
+/// A file listed in a [LanguagePackManifest].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguagePackFile {
+    /// The path of the file (e.g. `de-DE/my_crate.ftl`).
+    pub path: String,
+    /// The expected SHA-256 digest of the file, as lowercase hex.
+    pub sha256: String,
+}
+
+impl LanguagePackFile {
+    /// The language of the pack which this file belongs to, which is
+    /// the first component of its path.
+    pub fn language(&self) -> Result<LanguageIdentifier, I18nEmbedError> {
+        let language = self.path.split('/').next().unwrap_or_default();
+        language
+            .parse()
+            .map_err(|error| I18nEmbedError::ErrorParsingLocale(language.to_string(), error))
+    }
+
+    /// Verify that `contents` match the expected digest of this file.
+    pub fn verify(&self, contents: &[u8]) -> Result<(), I18nEmbedError> {
+        let actual = sha256_hex(contents);
+        if actual == self.sha256 {
+            Ok(())
+        } else {
+            Err(I18nEmbedError::LanguagePackChecksumMismatch(
+                self.path.clone(),
+                self.sha256.clone(),
+                actual,
+            ))
+        }
+    }
+}
+
+/// The manifest of the language packs available in a
+/// [LanguagePackSource], listing each of their files along with its
+/// SHA-256 digest.
+///
+/// The manifest uses the format of the output of the `sha256sum`
+/// command, so it can be created by running `sha256sum */*.ftl >
+/// SHA256SUMS` in the directory of the language packs:
+///
+/// ```text
+/// 3a6eb0790f39ac87c94f3856b2dd2c5d110e6811602261a9a923d3bb23adc8b7  de-DE/my_crate.ftl
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LanguagePackManifest {
+    /// The files of the language packs.
+    pub files: Vec<LanguagePackFile>,
+}
+
+impl LanguagePackManifest {
+    /// Parse a manifest from the `sha256sum` format. Empty lines are
+    /// ignored.
+    pub fn parse(manifest: &str) -> Result<Self, I18nEmbedError> {
+        let files = manifest
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let (sha256, path) = line
+                    .split_once(char::is_whitespace)
+                    .ok_or_else(|| I18nEmbedError::ErrorParsingLanguagePackManifest(line.into()))?;
+                // `sha256sum` marks files read in binary mode with `*`.
+                let path = path.trim_start().trim_start_matches('*');
+                let is_digest = sha256.len() == 64 && sha256.chars().all(|c| c.is_ascii_hexdigit());
+                if !is_digest || path.is_empty() {
+                    return Err(I18nEmbedError::ErrorParsingLanguagePackManifest(
+                        line.into(),
+                    ));
+                }
+                Ok(LanguagePackFile {
+                    path: path.to_string(),
+                    sha256: sha256.to_ascii_lowercase(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { files })
+    }
+
+    /// The languages of the packs listed in this manifest, in the
+    /// order they first appear.
+    pub fn languages(&self) -> Result<Vec<LanguageIdentifier>, I18nEmbedError> {
+        let mut languages = Vec::new();
+        for file in &self.files {
+            let language = file.language()?;
+            if !languages.contains(&language) {
+                languages.push(language);
+            }
+        }
+        Ok(languages)
+    }
+
+    /// Verify that the `signature` (the 64 bytes of an Ed25519
+    /// signature) of the contents of a `manifest` was made with the
+    /// private key of the `public_key` (the 32 bytes of an Ed25519
+    /// public key). This can be used by other [I18nAssets] which
+    /// download language packs to check the manifest before using its
+    /// digests.
+    ///
+    /// The signature can be created using `openssl pkeyutl -sign
+    /// -rawin -inkey private.pem -in SHA256SUMS -out SHA256SUMS.sig`.
+    ///
+    /// ⚠️ *This API requires the following crate features to be activated: `language-pack-signatures`.*
+    #[cfg(feature = "language-pack-signatures")]
+    pub fn verify_signature(
+        manifest: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<(), I18nEmbedError> {
+        ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, public_key)
+            .verify(manifest, signature)
+            .map_err(|_| I18nEmbedError::LanguagePackSignatureInvalid)
+    }
+
+    /// The files of the pack for the `language`.
+    pub fn files_for<'a>(
+        &'a self,
+        language: &'a LanguageIdentifier,
+    ) -> impl Iterator<Item = &'a LanguagePackFile> + 'a {
+        self.files
+            .iter()
+            .filter(move |file| is_language_path(&file.path, language))
+    }
+}
+
+fn sha256_hex(contents: &[u8]) -> String {
+    Sha256::digest(contents)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[derive(Default)]
+struct LanguagePackAssetsInner {
+    files: RwLock<BTreeMap<String, Vec<u8>>>,
+    subscribers: Arc<Subscribers>,
+}
+
+/// The [I18nAssets] of the language packs installed by a
+/// [LanguagePackManager], which are kept in memory. Clones share the
+/// same files.
+///
+/// Subscribers (see [I18nAssets::subscribe_changed()]) are notified
+/// whenever a language pack is installed or removed, so that loaders
+/// can reload them (e.g. using [crate::DefaultLocalizer::with_autoreload()]).
+/// This is typically combined with the assets embedded in the
+/// application using an [crate::AssetsMultiplexor].
+#[derive(Clone, Default)]
+pub struct LanguagePackAssets {
+    inner: Arc<LanguagePackAssetsInner>,
+}
+
+impl std::fmt::Debug for LanguagePackAssets {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LanguagePackAssets")
+            .field(
+                "files",
+                &self.inner.files.read().unwrap().keys().collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl LanguagePackAssets {
+    /// Construct new empty [LanguagePackAssets].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the files of the `language` with the `files` (path and
+    /// contents), and notify the subscribers.
+    fn replace_language(&self, language: &LanguageIdentifier, files: Vec<(String, Vec<u8>)>) {
+        {
+            let mut current_files = self.inner.files.write().unwrap();
+            current_files.retain(|path, _| !is_language_path(path, language));
+            current_files.extend(files);
+        }
+        self.inner.subscribers.notify();
+    }
+
+    /// The languages which have files in these assets.
+    pub fn languages(&self) -> Vec<LanguageIdentifier> {
+        let mut languages: Vec<LanguageIdentifier> = self
+            .inner
+            .files
+            .read()
+            .unwrap()
+            .keys()
+            .filter_map(|path| path.split('/').next()?.parse().ok())
+            .collect();
+        languages.dedup();
+        languages
+    }
+}
+
+fn is_language_path(path: &str, language: &LanguageIdentifier) -> bool {
+    path.split('/')
+        .next()
+        .and_then(|component| component.parse::<LanguageIdentifier>().ok())
+        .as_ref()
+        == Some(language)
+}
+
+impl I18nAssets for LanguagePackAssets {
+    fn get_files(&self, file_path: &str) -> Vec<Cow<'_, [u8]>> {
+        self.inner
+            .files
+            .read()
+            .unwrap()
+            .get(file_path)
+            .map(|contents| Cow::Owned(contents.clone()))
+            .into_iter()
+            .collect()
+    }
+
+    fn filenames_iter(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        let filenames: Vec<String> = self.inner.files.read().unwrap().keys().cloned().collect();
+        Box::new(filenames.into_iter())
+    }
+
+    fn subscribe_changed(
+        &self,
+        changed: Arc<dyn Fn() + Send + Sync + 'static>,
+    ) -> Result<Box<dyn Watcher + Send + Sync + 'static>, I18nEmbedError> {
+        Ok(self.inner.subscribers.subscribe(changed))
+    }
+}
+
+/// Downloads language packs from a [LanguagePackSource], verifies
+/// them against the digests in its [LanguagePackManifest], and
+/// installs them into [LanguagePackAssets]. With a public key (see
+/// [LanguagePackManager::with_public_key()]), the signature of the
+/// manifest is also verified, so that neither the manifest nor the
+/// packs can be tampered with.
+///
+/// ⚠️ *This type requires the following crate features to be activated: `language-packs`.*
+///
+/// ```ignore
+/// let manager = LanguagePackManager::new(source);
+/// let assets = AssetsMultiplexor::new([
+///     Box::new(manager.assets()) as Box<dyn I18nAssets + Send + Sync>,
+///     Box::new(Localizations),
+/// ]);
+/// manager.install(&"de-DE".parse()?)?;
+/// i18n_embed::select(&loader, &assets, &["de-DE".parse()?])?;
+/// ```
+#[derive(Debug)]
+pub struct LanguagePackManager<S> {
+    source: S,
+    assets: LanguagePackAssets,
+    #[cfg(feature = "language-pack-signatures")]
+    public_key: Option<Vec<u8>>,
+}
+
+impl<S: LanguagePackSource> LanguagePackManager<S> {
+    /// Construct a new [LanguagePackManager] which downloads language
+    /// packs from the `source`.
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            assets: LanguagePackAssets::new(),
+            #[cfg(feature = "language-pack-signatures")]
+            public_key: None,
+        }
+    }
+
+    /// Require the manifest of the source to be signed with the
+    /// private key of the `public_key` (the 32 bytes of an Ed25519
+    /// public key). The signature is downloaded from
+    /// [LANGUAGE_PACK_SIGNATURE] and verified (see
+    /// [LanguagePackManifest::verify_signature()]) each time the
+    /// manifest is downloaded, and the manifest is rejected if it is
+    /// missing or not valid.
+    ///
+    /// ⚠️ *This API requires the following crate features to be activated: `language-pack-signatures`.*
+    #[cfg(feature = "language-pack-signatures")]
+    pub fn with_public_key(mut self, public_key: impl Into<Vec<u8>>) -> Self {
+        self.public_key = Some(public_key.into());
+        self
+    }
+
+    /// The source of the language packs.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// The assets containing the installed language packs.
+    pub fn assets(&self) -> LanguagePackAssets {
+        self.assets.clone()
+    }
+
+    /// Download the manifest of the language packs from the source,
+    /// verifying its signature if there is a public key (see
+    /// [LanguagePackManager::with_public_key()]).
+    pub fn manifest(&self) -> Result<LanguagePackManifest, I18nEmbedError> {
+        let manifest = self.source.fetch(LANGUAGE_PACK_MANIFEST)?;
+        #[cfg(feature = "language-pack-signatures")]
+        if let Some(public_key) = &self.public_key {
+            let signature = self.source.fetch(LANGUAGE_PACK_SIGNATURE)?;
+            LanguagePackManifest::verify_signature(&manifest, &signature, public_key)?;
+        }
+        let manifest = String::from_utf8(manifest).map_err(|error| {
+            I18nEmbedError::ErrorParsingFileUtf8(LANGUAGE_PACK_MANIFEST.to_string(), error)
+        })?;
+        LanguagePackManifest::parse(&manifest)
+    }
+
+    /// The languages of the packs which are available to be
+    /// installed from the source.
+    pub fn available_languages(&self) -> Result<Vec<LanguageIdentifier>, I18nEmbedError> {
+        self.manifest()?.languages()
+    }
+
+    /// The languages of the packs which have been installed.
+    pub fn installed_languages(&self) -> Vec<LanguageIdentifier> {
+        self.assets.languages()
+    }
+
+    /// Download the pack for the `language` and verify each of its
+    /// files against the manifest. The pack is only installed
+    /// (replacing any previously installed version) if all of its
+    /// files are verified, after which the subscribers of the
+    /// [LanguagePackManager::assets()] are notified.
+    pub fn install(&self, language: &LanguageIdentifier) -> Result<(), I18nEmbedError> {
+        let manifest = self.manifest()?;
+        let mut files = Vec::new();
+        for file in manifest.files_for(language) {
+            let contents = self.source.fetch(&file.path)?;
+            file.verify(&contents)?;
+            files.push((file.path.clone(), contents));
+        }
+        if files.is_empty() {
+            return Err(I18nEmbedError::LanguagePackNotAvailable(language.clone()));
+        }
+
+        diagnostic!(
+            info,
+            target: "i18n_embed::language_pack",
+            { language = language },
+            "Installing the language pack for \"{language}\"."
+        );
+        self.assets.replace_language(language, files);
+        Ok(())
+    }
+
+    /// Remove the installed pack for the `language`, and notify the
+    /// subscribers of the [LanguagePackManager::assets()].
+    pub fn uninstall(&self, language: &LanguageIdentifier) {
+        self.assets.replace_language(language, Vec::new());
+    }
+}
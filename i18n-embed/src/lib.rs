@@ -33,6 +33,21 @@
 //!     system using the [tr macro](https://docs.rs/tr/0.1.3/tr/) and
 //!     the [gettext crate](https://docs.rs/gettext/0.4.0/gettext/)
 //!     via the `gettext::GettextLanguageLoader` in this crate.
+//! + `qt-system`
+//!   + Enable support for [Qt
+//!     Linguist](https://doc.qt.io/qt-6/qtlinguist-index.html) `.ts`
+//!     files via the `qt::QtLanguageLoader` in this crate.
+//! + `properties-system`
+//!   + Enable support for Java `.properties` resource bundles via the
+//!     `properties::PropertiesLanguageLoader` in this crate.
+//! + `csv-system`
+//!   + Enable support for translation tables in a single `.csv` file
+//!     (e.g. exported from a spreadsheet) via the
+//!     `csv::CsvLanguageLoader` in this crate.
+//! + `cache`
+//!   + Enables caching of the messages formatted by the
+//!     `fluent::FluentLanguageLoader`, see
+//!     `FluentLanguageLoader::clear_message_cache()`.
 //! + `desktop-requester`
 //!   + Enables a convenience implementation of
 //!     [LanguageRequester](LanguageRequester) trait called
@@ -47,6 +62,78 @@
 //!     [web-sys](https://crates.io/crates/web-sys) crate for
 //!     resolving the language being requested by the user's web
 //!     browser in a WASM context.
+//! + `icu-collator`
+//!   + Enables [LanguageLoader::collator()] and `collator()` for
+//!     sorting strings according to the rules of a language, using
+//!     the [icu_collator](https://crates.io/crates/icu_collator)
+//!     crate.
+//! + `language-packs`
+//!   + Enables `LanguagePackManager`, which downloads language packs
+//!     from a `LanguagePackSource` (e.g. a HTTP server), verifies
+//!     their SHA-256 digests against a manifest, and installs them
+//!     into assets which notify the loaders to reload them.
+//! + `language-pack-signatures`
+//!   + Enables `LanguagePackManager::with_public_key()`, which
+//!     verifies the Ed25519 signature of the language pack manifest
+//!     before it is used, using the [ring](https://crates.io/crates/ring)
+//!     crate, so that the language packs cannot be tampered with.
+//! + `archive-assets`
+//!   + Enables `ArchiveAssets`, an implementation of [I18nAssets]
+//!     which reads the localization files from a zip or `.tar.gz`
+//!     archive on disk, such as a language pack distributed as a
+//!     single file.
+//! + `compressed-assets`
+//!   + Enables `CompressedRustEmbedAssets`, a wrapper for `RustEmbed`
+//!     which embeds gzip compressed localization files to reduce the
+//!     size of binaries with many languages, and decompresses them
+//!     when they are loaded.
+//! + `tracing`
+//!   + Emits the diagnostics of the loaders (such as missing
+//!     messages and errors while loading and formatting them) as
+//!     [tracing](https://crates.io/crates/tracing) events with
+//!     structured fields instead of [log](https://crates.io/crates/log)
+//!     records, within spans for `load_languages()` and `select()`,
+//!     so that they can be correlated with the request which caused
+//!     them. When no `tracing` subscriber is set, the events are
+//!     still logged.
+//! + `localized-logger`
+//!   + Enables `LocalizedLogger`, a [log](https://crates.io/crates/log)
+//!     implementation which localizes log records with an event id
+//!     using the `fluent::FluentLanguageLoader`, for displaying them
+//!     to the user.
+//! + `axum`
+//!   + Enables the `axum::LocalizationLayer`, a
+//!     [tower](https://crates.io/crates/tower) middleware for web
+//!     servers built with [axum](https://crates.io/crates/axum),
+//!     which provides each request with a
+//!     `fluent::FluentLanguageLoader` for the languages in its
+//!     `Accept-Language` header.
+//! + `actix-web`
+//!   + Enables the `actix_web::RequestLoader` extractor for web
+//!     servers built with [actix-web](https://crates.io/crates/actix-web),
+//!     which provides a `fluent::FluentLanguageLoader` for the
+//!     languages requested by the `Accept-Language` header, a query
+//!     parameter or a cookie.
+//! + `yew`
+//!   + Enables the `web_frontend` module for WASM frontends built
+//!     with [Yew](https://crates.io/crates/yew), with the
+//!     `LocalizationProvider` component which selects the languages
+//!     requested by the browser, and the `use_localization()` hook
+//!     which re-renders components when another language is selected.
+//! + `egui`
+//!   + Enables the `egui` module for desktop applications built with
+//!     [egui](https://crates.io/crates/egui), with the
+//!     `LanguageSelector` widget for choosing between the available
+//!     languages of a [Localizer].
+//! + `async`
+//!   + Enables `AsyncI18nAssets`, for assets which are fetched
+//!     asynchronously (e.g. from a HTTP server), and
+//!     `load_languages_async()` and `select_async()`, which only
+//!     fetch the files of the languages being loaded, so that
+//!     services using an async runtime such as
+//!     [tokio](https://crates.io/crates/tokio) can load translations
+//!     without blocking its worker threads. Synchronous assets can be
+//!     used with the `SyncAssets` adapter.
 //!
 //! # Examples
 //!
@@ -84,6 +171,16 @@
 //! # The paths inside the assets directory should be structured like so:
 //! # `assets_dir/{language}/{domain}.ftl`
 //! assets_dir = "i18n"
+//!
+//! # (Optional) The template for the paths of the fluent files within
+//! # the assets directory, if they use another layout.
+//! # path_template = "locales/{language}.ftl"
+//!
+//! # (Optional) Serve requests for a language using the files of
+//! # another language, when the assets use different identifiers.
+//! # [language_aliases]
+//! # no = "nb"
+//! # zh-CN = "zh-Hans"
 //! ```
 //!
 //! Next, you want to create your localization resources, per language
@@ -91,7 +188,9 @@
 //! Language
 //! Identifier](https://unicode.org/reports/tr35/tr35.html#Unicode_language_identifier)
 //! standard, and will be parsed via the [unic_langid
-//! crate](https://docs.rs/unic-langid/0.9.0/unic_langid/):
+//! crate](https://docs.rs/unic-langid/0.9.0/unic_langid/). Directories
+//! which are not written in the canonical form (such as `en_US` or
+//! `EN-us`) are also found:
 //!
 //! ```txt
 //! my_crate/
@@ -421,9 +520,50 @@
     unreachable_pub
 )]
 
+#[macro_use]
+mod diagnostics;
+
+mod accept_language;
+#[cfg(feature = "archive-assets")]
+mod archive;
 mod assets;
+#[cfg(feature = "async")]
+mod async_assets;
+#[cfg(feature = "icu-collator")]
+mod collator;
+#[cfg(feature = "compressed-assets")]
+mod compressed;
+mod direction;
+mod language_aliases;
+#[cfg(feature = "language-packs")]
+mod language_pack;
+mod list;
+#[cfg(feature = "localized-logger")]
+mod logger;
+mod path_template;
+mod polling_assets;
+mod presentation;
+mod pseudo;
+mod region;
+mod registry;
 mod requester;
 mod util;
+mod verify;
+
+#[cfg(feature = "actix-web")]
+pub mod actix_web;
+
+#[cfg(feature = "axum")]
+pub mod axum;
+
+#[cfg(feature = "csv-system")]
+pub mod csv;
+
+#[cfg(feature = "yew")]
+pub mod web_frontend;
+
+#[cfg(feature = "egui")]
+pub mod egui;
 
 #[cfg(feature = "fluent-system")]
 pub mod fluent;
@@ -431,9 +571,68 @@ pub mod fluent;
 #[cfg(feature = "gettext-system")]
 pub mod gettext;
 
+#[cfg(feature = "cache")]
+mod message_cache;
+
+pub mod testing;
+
+#[cfg(feature = "fluent-system")]
+mod message_format;
+
+#[cfg(feature = "properties-system")]
+pub mod properties;
+
+#[cfg(feature = "qt-system")]
+pub mod qt;
+
+#[cfg(any(
+    feature = "properties-system",
+    feature = "qt-system",
+    feature = "csv-system"
+))]
+mod table;
+
+#[cfg(any(feature = "gettext-system", feature = "fluent-system"))]
+mod multi_domain;
+
+#[cfg(feature = "fluent-system")]
+mod relative_time;
+
+#[cfg(feature = "fluent-system")]
+mod template;
+
+pub use accept_language::parse_accept_language;
+#[cfg(feature = "archive-assets")]
+pub use archive::{ArchiveAssets, ArchiveFormat};
 pub use assets::*;
+#[cfg(feature = "async")]
+pub use async_assets::*;
+#[cfg(feature = "icu-collator")]
+pub use collator::*;
+#[cfg(feature = "compressed-assets")]
+pub use compressed::{CompressedRustEmbedAssets, COMPRESSED_ASSET_EXTENSION};
+pub use direction::TextDirection;
+pub use language_aliases::LanguageAliases;
+#[cfg(feature = "language-packs")]
+pub use language_pack::*;
+pub use list::*;
+#[cfg(feature = "localized-logger")]
+pub use logger::{LocalizedLogger, LocalizedRecord};
+#[cfg(any(feature = "gettext-system", feature = "fluent-system"))]
+pub use multi_domain::MultiDomainLoader;
+pub use path_template::PathTemplate;
+pub use polling_assets::PollingAssets;
+pub use presentation::*;
+pub use pseudo::{Pseudolocalization, PSEUDOLOCALIZATION_LANGUAGE};
+pub use region::RegionContext;
+pub use registry::{LocalizationRegistry, Registration};
+#[cfg(feature = "fluent-system")]
+pub use relative_time::RelativeTime;
 pub use requester::*;
+#[cfg(feature = "fluent-system")]
+pub use template::{LocalizedTemplate, RenderedTemplate};
 pub use util::*;
+pub use verify::LanguageVerification;
 
 #[cfg(doctest)]
 #[macro_use]
@@ -449,19 +648,17 @@ extern crate i18n_embed_impl;
 #[cfg(feature = "gettext-system")]
 extern crate gettext as gettext_system;
 
-use std::{
-    borrow::Cow,
-    fmt::Debug,
-    path::{Component, Path},
-    string::FromUtf8Error,
-};
+use std::{borrow::Cow, collections::HashSet, fmt::Debug, string::FromUtf8Error};
 
 use fluent_langneg::{negotiate_languages, NegotiationStrategy};
-use log::{debug, error};
+use log::debug;
 use thiserror::Error;
 
 pub use unic_langid;
 
+#[cfg(feature = "icu-collator")]
+pub use icu_collator;
+
 /// An error that occurs in this library.
 #[derive(Error, Debug)]
 #[allow(missing_docs)]
@@ -474,11 +671,18 @@ pub enum I18nEmbedError {
     RequestedLanguagesEmpty,
     #[error("The language file \"{0}\" for the language \"{1}\" is not available.")]
     LanguageNotAvailable(String, unic_langid::LanguageIdentifier),
+    #[error("The domain \"{0}\" has not been added.")]
+    DomainNotAvailable(String),
+    #[error("The path template \"{0}\" is invalid, it needs to contain the \"{{language}}\" placeholder exactly once, and no placeholders other than \"{{language}}\" and \"{{domain}}\".")]
+    InvalidPathTemplate(String),
     #[error("There are multiple errors: {}", error_vec_to_string(.0))]
     Multiple(Vec<I18nEmbedError>),
     #[cfg(feature = "gettext-system")]
     #[error(transparent)]
     Gettext(#[from] gettext_system::Error),
+    #[cfg(feature = "fluent-system")]
+    #[error("There are errors in the fluent files: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    InvalidResources(Vec<fluent::ResourceDiagnostic>),
     #[cfg(feature = "autoreload")]
     #[error(transparent)]
     Notify(#[from] assets::NotifyError),
@@ -488,6 +692,53 @@ pub enum I18nEmbedError {
     #[cfg(feature = "filesystem-assets")]
     #[error("The path {0:?} is not a directory")]
     PathIsNotDirectory(std::path::PathBuf),
+    #[cfg(feature = "archive-assets")]
+    #[error("Unable to read the archive {0:?}")]
+    ArchiveIo(std::path::PathBuf, #[source] std::io::Error),
+    #[cfg(feature = "archive-assets")]
+    #[error("The archive {0:?} is invalid: {1}")]
+    InvalidArchive(std::path::PathBuf, String),
+    #[cfg(feature = "fluent-system")]
+    #[error("The message \"{0}\" is not available.")]
+    MessageNotAvailable(String),
+    #[cfg(feature = "fluent-system")]
+    #[error("The template message \"{0}\" does not have the required \"{1}\" attribute.")]
+    TemplateAttributeNotAvailable(String, String),
+    #[cfg(feature = "fluent-system")]
+    #[error("The template message \"{0}\" is missing the arguments: {}", .1.join(", "))]
+    TemplateArgumentsMissing(String, Vec<String>),
+    #[cfg(feature = "csv-system")]
+    #[error("Error parsing the CSV file \"{0}\".")]
+    ErrorParsingCsv(String, #[source] ::csv::Error),
+    #[cfg(feature = "qt-system")]
+    #[error("Error parsing the Qt Linguist file \"{0}\".")]
+    ErrorParsingQtLinguist(String, #[source] roxmltree::Error),
+    #[cfg(feature = "language-packs")]
+    #[error("Unable to fetch the language pack file \"{0}\".")]
+    LanguagePackFetch(
+        String,
+        #[source] Box<dyn std::error::Error + Send + Sync + 'static>,
+    ),
+    #[cfg(feature = "language-packs")]
+    #[error("Error parsing the line \"{0}\" of the language pack manifest.")]
+    ErrorParsingLanguagePackManifest(String),
+    #[cfg(feature = "language-packs")]
+    #[error("The language pack file \"{0}\" has the SHA-256 digest {2}, expected {1}.")]
+    LanguagePackChecksumMismatch(String, String, String),
+    #[cfg(feature = "language-packs")]
+    #[error("The language pack for \"{0}\" is not available.")]
+    LanguagePackNotAvailable(unic_langid::LanguageIdentifier),
+    #[cfg(feature = "language-pack-signatures")]
+    #[error("The signature of the language pack manifest is not valid for the public key.")]
+    LanguagePackSignatureInvalid,
+    #[error("Unable to fetch the polled localization assets.")]
+    PollingAssetsFetch(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[cfg(feature = "icu-collator")]
+    #[error("Unable to create a collator for the language \"{0}\".")]
+    Collator(
+        unic_langid::LanguageIdentifier,
+        #[source] icu_collator::CollatorError,
+    ),
 }
 
 fn error_vec_to_string(errors: &[I18nEmbedError]) -> String {
@@ -524,6 +775,12 @@ pub trait Localizer {
             requested_languages,
         )
     }
+
+    /// The direction in which the text of the currently selected
+    /// language is written, see [LanguageLoader::text_direction()].
+    fn text_direction(&self) -> TextDirection {
+        self.language_loader().text_direction()
+    }
 }
 
 /// A simple default implemenation of the [Localizer](Localizer) trait.
@@ -570,7 +827,12 @@ impl<'a> DefaultLocalizer<'a> {
 }
 
 impl DefaultLocalizer<'static> {
-    /// Create a new [DefaultLocalizer](DefaultLocalizer).
+    /// Reload the languages of the [LanguageLoader] (see
+    /// [LanguageLoader::reload()]) whenever the assets notify that
+    /// they have changed (see [I18nAssets::subscribe_changed()]), for
+    /// example when the `ftl` or `mo` files are modified while the
+    /// application is running. Errors while reloading are logged,
+    /// and the languages which were loaded before are kept.
     pub fn with_autoreload(mut self) -> Result<Self, I18nEmbedError> {
         let assets = self.i18n_assets;
         let loader = self.language_loader;
@@ -599,6 +861,11 @@ pub fn select(
     i18n_assets: &dyn I18nAssets,
     requested_languages: &[unic_langid::LanguageIdentifier],
 ) -> Result<Vec<unic_langid::LanguageIdentifier>, I18nEmbedError> {
+    let _span = diagnostic_span!(
+        "select",
+        domain = %language_loader.domain(),
+        requested_languages = ?requested_languages
+    );
     log::info!(
         "Selecting translations for domain \"{0}\"",
         language_loader.domain()
@@ -649,6 +916,52 @@ pub trait LanguageLoader {
     fn domain(&self) -> &str;
     /// The language file name to use for this loader's domain.
     fn language_file_name(&self) -> String;
+    /// The template for the paths of this loader's language files
+    /// within the [I18nAssets]. By default this is
+    /// `{language}/{language_file_name}`, see
+    /// [PathTemplate::with_file_name()].
+    fn path_template(&self) -> PathTemplate {
+        PathTemplate::with_file_name(&self.language_file_name())
+    }
+    /// The aliases used to serve requests for a language using the
+    /// files of another language, see [LanguageAliases]. By default
+    /// there are none.
+    fn language_aliases(&self) -> LanguageAliases {
+        LanguageAliases::new()
+    }
+    /// The name of the `language_id` as it is written within the
+    /// paths of the `i18n_assets`. This is the language which the
+    /// `language_id` is an alias of (see
+    /// [LanguageLoader::language_aliases()]), in its canonical form
+    /// (e.g. `en-US`), unless the assets only contain files for it
+    /// written in another form (e.g. `en_US`).
+    fn asset_language(
+        &self,
+        language_id: &unic_langid::LanguageIdentifier,
+        i18n_assets: &dyn I18nAssets,
+    ) -> String {
+        let language_id = self
+            .language_aliases()
+            .resolve(language_id)
+            .unwrap_or_else(|| language_id.clone());
+        let path_template = self.path_template();
+        let canonical = language_id.to_string();
+        if !i18n_assets
+            .get_files(&path_template.path(&canonical, self.domain()))
+            .is_empty()
+        {
+            return canonical;
+        }
+
+        i18n_assets
+            .filenames_iter()
+            .find_map(|filename| {
+                let language = path_template.language(&filename, self.domain())?;
+                let parsed: unic_langid::LanguageIdentifier = language.parse().ok()?;
+                (parsed == language_id).then(|| language.to_string())
+            })
+            .unwrap_or(canonical)
+    }
     /// The computed path to the language files, and data contained within the files at that path
     /// itself if they exist. There can be multiple files at a given path, in order of preference
     /// from high to low.
@@ -657,8 +970,9 @@ pub trait LanguageLoader {
         language_id: &unic_langid::LanguageIdentifier,
         i18n_assets: &'a dyn I18nAssets,
     ) -> (String, Vec<Cow<'a, [u8]>>) {
-        let language_id_string = language_id.to_string();
-        let file_path = format!("{}/{}", language_id_string, self.language_file_name());
+        let file_path = self
+            .path_template()
+            .path(self.asset_language(language_id, i18n_assets), self.domain());
 
         log::debug!("Attempting to load language file: \"{}\"", &file_path);
 
@@ -671,66 +985,60 @@ pub trait LanguageLoader {
         &self,
         i18n_assets: &dyn I18nAssets,
     ) -> Result<Vec<unic_langid::LanguageIdentifier>, I18nEmbedError> {
+        let path_template = self.path_template();
         let mut language_strings: Vec<String> = i18n_assets
             .filenames_iter()
             .filter_map(|filename| {
-                let path: &Path = Path::new(&filename);
-
-                let components: Vec<Component<'_>> = path.components().collect();
-
-                let locale: Option<String> = match components.first() {
-                    Some(Component::Normal(s)) => {
-                        Some(s.to_str().expect("path should be valid utf-8").to_string())
-                    }
-                    _ => None,
-                };
-
-                let language_file_name: Option<String> =
-                    components.get(1).and_then(|component| match component {
-                        Component::Normal(s) => {
-                            Some(s.to_str().expect("path should be valid utf-8").to_string())
-                        }
-                        _ => None,
-                    });
-
-                match language_file_name {
-                    Some(language_file_name) => {
-                        debug!(
-                            "Searching for available languages, found language file: \"{0}\"",
-                            &filename
-                        );
-                        if language_file_name == self.language_file_name() {
-                            locale
-                        } else {
-                            None
-                        }
-                    }
-                    None => None,
-                }
+                // Either the language file (e.g.
+                // `{language}/{domain}.ftl`), or a namespace file
+                // within the language's directory (e.g.
+                // `{language}/{domain}/`).
+                let locale = path_template
+                    .language(&filename, self.domain())?
+                    .to_string();
+                debug!(
+                    "Searching for available languages, found language file: \"{0}\"",
+                    &filename
+                );
+                Some(locale)
             })
             .collect();
 
-        let fallback_locale = self.fallback_language().to_string();
-
-        // For systems such as gettext which have a locale in the
-        // source code, this language will not be found in the
-        // localization assets, and should be the fallback_locale, so
-        // it needs to be added manually here.
-        if !language_strings
-            .iter()
-            .any(|language| language == &fallback_locale)
-        {
-            language_strings.insert(0, fallback_locale);
-        }
+        // A language may have several namespace files in addition
+        // to its language file.
+        let mut found_languages = HashSet::new();
+        language_strings.retain(|language| found_languages.insert(language.clone()));
 
-        language_strings
+        let mut languages = language_strings
             .into_iter()
             .map(|language: String| {
                 language
                     .parse()
                     .map_err(|err| I18nEmbedError::ErrorParsingLocale(language, err))
             })
-            .collect()
+            .collect::<Result<Vec<unic_langid::LanguageIdentifier>, _>>()?;
+
+        // The same language may be written in several forms (e.g.
+        // `en-US` and `en_US`).
+        let mut found_languages = HashSet::new();
+        languages.retain(|language| found_languages.insert(language.clone()));
+
+        // For systems such as gettext which have a locale in the
+        // source code, this language will not be found in the
+        // localization assets, and should be the fallback_locale, so
+        // it needs to be added manually here.
+        let fallback_language = self.fallback_language();
+        if !languages.contains(fallback_language) {
+            languages.insert(0, fallback_language.clone());
+        }
+
+        for (alias, language) in self.language_aliases().iter() {
+            if languages.contains(&language) && !languages.contains(&alias) {
+                languages.push(alias);
+            }
+        }
+
+        Ok(languages)
     }
 
     /// Load all available languages with [`LanguageLoader::load_languages()`].
@@ -739,9 +1047,53 @@ pub trait LanguageLoader {
         self.load_languages(i18n_assets, &available_languages)
     }
 
+    /// Compare the languages available in the `i18n_assets` with the
+    /// `expected_languages`, reporting any which are missing or
+    /// unexpected, and logging a warning for each of them. This is
+    /// intended to be called when an application starts (e.g. in
+    /// debug builds) to catch packaging mistakes, such as forgetting
+    /// to include the files for a language.
+    ///
+    /// The [LanguageLoader::fallback_language()] is always considered
+    /// to be available, see [LanguageLoader::available_languages()].
+    fn verify_against(
+        &self,
+        i18n_assets: &dyn I18nAssets,
+        expected_languages: &[unic_langid::LanguageIdentifier],
+    ) -> Result<LanguageVerification, I18nEmbedError> {
+        let available_languages = self.available_languages(i18n_assets)?;
+        let verification = LanguageVerification::new(&available_languages, expected_languages);
+
+        for language in &verification.missing {
+            log::warn!(
+                target: "i18n_embed::verify",
+                "The expected language \"{0}\" is not available for the domain \"{1}\".",
+                language,
+                self.domain()
+            );
+        }
+        for language in &verification.unexpected {
+            log::warn!(
+                target: "i18n_embed::verify",
+                "The language \"{0}\" is available for the domain \"{1}\", but it was not expected.",
+                language,
+                self.domain()
+            );
+        }
+
+        Ok(verification)
+    }
+
     /// Get the language which is currently loaded for this loader.
     fn current_language(&self) -> unic_langid::LanguageIdentifier;
 
+    /// The direction in which the text of the
+    /// [LanguageLoader::current_language()] is written, so that user
+    /// interfaces can mirror their layout for right-to-left languages.
+    fn text_direction(&self) -> TextDirection {
+        TextDirection::of(&self.current_language())
+    }
+
     /// Reload the currently loaded languages.
     fn reload(&self, i18n_assets: &dyn I18nAssets) -> Result<(), I18nEmbedError>;
 
@@ -761,6 +1113,17 @@ pub trait LanguageLoader {
     fn load_fallback_language(&self, i18n_assets: &dyn I18nAssets) -> Result<(), I18nEmbedError> {
         self.load_languages(i18n_assets, &[self.fallback_language().clone()])
     }
+
+    /// Create an [icu_collator::Collator] with the default options
+    /// for the [LanguageLoader::current_language()], which can be
+    /// used to sort localized strings correctly for the language.
+    /// See [collator()] to specify options.
+    ///
+    /// ⚠️ *This API requires the following crate features to be activated: `icu-collator`.*
+    #[cfg(feature = "icu-collator")]
+    fn collator(&self) -> Result<icu_collator::Collator, I18nEmbedError> {
+        collator(&self.current_language(), Default::default())
+    }
 }
 
 /// Populate gettext database with strings for use with tests.
@@ -771,5 +1134,6 @@ mod gettext_test_string {
         tr::tr!("only ru");
         tr::tr!("only es");
         tr::tr!("only fr");
+        tr::tr!("{n} file" | "{n} files" % 1);
     }
 }
@@ -0,0 +1,123 @@
+use unic_langid::LanguageIdentifier;
+
+/// The type of list to format with [format_list()].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListStyle {
+    /// A conjunction list, e.g. "a, b, and c".
+    And,
+    /// A disjunction list, e.g. "a, b, or c".
+    Or,
+}
+
+/// The separators used to join list items, derived from the CLDR
+/// `listPatterns` for a language.
+struct ListPatterns {
+    /// Placed between two items of a list with exactly two items.
+    pair: &'static str,
+    /// Placed between the leading items of a list with more than two
+    /// items.
+    middle: &'static str,
+    /// Placed between the last two items of a list with more than two
+    /// items.
+    end: &'static str,
+}
+
+impl ListPatterns {
+    const fn new(pair: &'static str, middle: &'static str, end: &'static str) -> Self {
+        Self { pair, middle, end }
+    }
+
+    /// Patterns for languages where the final separator is identical
+    /// to the pair separator, and items are otherwise separated by a
+    /// comma.
+    const fn comma(pair: &'static str) -> Self {
+        Self::new(pair, ", ", pair)
+    }
+}
+
+/// Look up the CLDR list patterns for the specified `language` and
+/// `style`, falling back to English if the language is unknown.
+fn list_patterns(language: &LanguageIdentifier, style: ListStyle) -> ListPatterns {
+    use ListStyle::{And, Or};
+    match (language.language.as_str(), style) {
+        ("ar", And) => ListPatterns::new(" و", "، ", " و"),
+        ("ar", Or) => ListPatterns::new(" أو ", "، ", " أو "),
+        ("cs", And) | ("sk", And) => ListPatterns::comma(" a "),
+        ("cs", Or) => ListPatterns::comma(" nebo "),
+        ("sk", Or) => ListPatterns::comma(" alebo "),
+        ("da", And) | ("nb", And) | ("no", And) | ("nn", And) => ListPatterns::comma(" og "),
+        ("da", Or) | ("nb", Or) | ("no", Or) | ("nn", Or) => ListPatterns::comma(" eller "),
+        ("de", And) => ListPatterns::comma(" und "),
+        ("de", Or) => ListPatterns::comma(" oder "),
+        ("eo", And) => ListPatterns::comma(" kaj "),
+        ("eo", Or) => ListPatterns::comma(" aŭ "),
+        ("es", And) => ListPatterns::comma(" y "),
+        ("es", Or) => ListPatterns::comma(" o "),
+        ("fi", And) => ListPatterns::comma(" ja "),
+        ("fi", Or) => ListPatterns::comma(" tai "),
+        ("fr", And) => ListPatterns::comma(" et "),
+        ("fr", Or) => ListPatterns::comma(" ou "),
+        ("it", And) => ListPatterns::comma(" e "),
+        ("it", Or) => ListPatterns::comma(" o "),
+        ("ja", And) => ListPatterns::new("、", "、", "、"),
+        ("ja", Or) => ListPatterns::new("または", "、", "、または"),
+        ("ka", And) => ListPatterns::comma(" და "),
+        ("ka", Or) => ListPatterns::comma(" ან "),
+        ("ko", And) => ListPatterns::comma(" 및 "),
+        ("ko", Or) => ListPatterns::comma(" 또는 "),
+        ("nl", And) => ListPatterns::comma(" en "),
+        ("nl", Or) => ListPatterns::comma(" of "),
+        ("pl", And) => ListPatterns::comma(" i "),
+        ("pl", Or) => ListPatterns::comma(" lub "),
+        ("pt", And) => ListPatterns::comma(" e "),
+        ("pt", Or) => ListPatterns::comma(" ou "),
+        ("ru", And) => ListPatterns::comma(" и "),
+        ("ru", Or) => ListPatterns::comma(" или "),
+        ("sv", And) => ListPatterns::comma(" och "),
+        ("sv", Or) => ListPatterns::comma(" eller "),
+        ("tr", And) => ListPatterns::comma(" ve "),
+        ("tr", Or) => ListPatterns::comma(" veya "),
+        ("uk", And) => ListPatterns::comma(" і "),
+        ("uk", Or) => ListPatterns::comma(" або "),
+        ("zh", And) => ListPatterns::new("和", "、", "和"),
+        ("zh", Or) => ListPatterns::new("或", "、", "或"),
+        // English, which is also used for unknown languages.
+        (_, And) => ListPatterns::new(" and ", ", ", ", and "),
+        (_, Or) => ListPatterns::new(" or ", ", ", ", or "),
+    }
+}
+
+/// Join `items` into a single localized list for the specified
+/// `language`, using the [CLDR list
+/// patterns](https://unicode.org/reports/tr35/tr35-general.html#ListPatterns)
+/// for that language. For example, `["a", "b", "c"]` formatted with
+/// [ListStyle::And] becomes `"a, b, and c"` in English and `"a, b und
+/// c"` in German. Languages without built-in list patterns fall back
+/// to English.
+///
+/// The returned `String` can be passed directly as an argument to a
+/// fluent message.
+pub fn format_list<S: AsRef<str>>(
+    language: &LanguageIdentifier,
+    items: &[S],
+    style: ListStyle,
+) -> String {
+    let patterns = list_patterns(language, style);
+    match items {
+        [] => String::new(),
+        [item] => item.as_ref().to_string(),
+        [first, second] => format!("{}{}{}", first.as_ref(), patterns.pair, second.as_ref()),
+        [leading @ .., second_last, last] => {
+            let mut list = leading
+                .iter()
+                .map(AsRef::as_ref)
+                .collect::<Vec<&str>>()
+                .join(patterns.middle);
+            list.push_str(patterns.middle);
+            list.push_str(second_last.as_ref());
+            list.push_str(patterns.end);
+            list.push_str(last.as_ref());
+            list
+        }
+    }
+}
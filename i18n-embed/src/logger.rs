@@ -0,0 +1,187 @@
+use fluent::{FluentArgs, FluentValue};
+use log::{
+    kv::{self, VisitSource},
+    LevelFilter, Log, Metadata, Record,
+};
+
+use crate::{fluent::FluentLanguageLoader, LanguageLoader};
+
+/// A log record which has been localized by a [LocalizedLogger].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalizedRecord {
+    /// The level of the record.
+    pub level: log::Level,
+    /// The target of the record.
+    pub target: String,
+    /// The event id of the record, if it had one.
+    pub event: Option<String>,
+    /// The fluent message with the id of the `event`, formatted
+    /// using the other key-values of the record as arguments. If the
+    /// record has no event id, or the message is not available, this
+    /// is the original message of the record.
+    pub message: String,
+}
+
+/// A [Log] implementation which localizes the log records of an
+/// application for display to the user (e.g. in a log pane), while
+/// passing the original records on to another logger (e.g. one which
+/// writes to a file in English).
+///
+/// The records are mapped to fluent messages by their event id, which
+/// is the `event` key-value of the record. The other key-values of the
+/// record are the arguments for the message:
+///
+/// ```ignore
+/// // With the message `file-saved = Saved { $path }` in the loader.
+/// log::info!(event = "file-saved", path = "notes.txt"; "Saved notes.txt");
+/// ```
+///
+/// ⚠️ *This type requires the following crate features to be activated: `localized-logger`.*
+pub struct LocalizedLogger {
+    loader: &'static FluentLanguageLoader,
+    sink: Box<dyn Fn(LocalizedRecord) + Send + Sync + 'static>,
+    logger: Option<Box<dyn Log + 'static>>,
+    level: LevelFilter,
+    event_key: String,
+}
+
+impl std::fmt::Debug for LocalizedLogger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalizedLogger")
+            .field("domain", &self.loader.domain())
+            .field("level", &self.level)
+            .field("event_key", &self.event_key)
+            .finish()
+    }
+}
+
+impl LocalizedLogger {
+    /// Create a new [LocalizedLogger] which localizes the records
+    /// using the `loader`, passing them to the `sink`.
+    pub fn new(
+        loader: &'static FluentLanguageLoader,
+        sink: impl Fn(LocalizedRecord) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            loader,
+            sink: Box::new(sink),
+            logger: None,
+            level: LevelFilter::Info,
+            event_key: "event".to_string(),
+        }
+    }
+
+    /// Also pass the original records to the `logger`, which decides
+    /// for itself which records are enabled.
+    pub fn with_logger(mut self, logger: impl Log + 'static) -> Self {
+        self.logger = Some(Box::new(logger));
+        self
+    }
+
+    /// Set the maximum level of the records passed to the sink. The
+    /// default is [LevelFilter::Info].
+    pub fn with_level(mut self, level: LevelFilter) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Set the key of the key-value containing the event id of a
+    /// record. The default is `event`.
+    pub fn with_event_key(mut self, event_key: impl Into<String>) -> Self {
+        self.event_key = event_key.into();
+        self
+    }
+
+    /// Set this as the global logger, see [log::set_boxed_logger()].
+    pub fn init(self) -> Result<(), log::SetLoggerError> {
+        let max_level = self
+            .logger
+            .as_ref()
+            .map_or(self.level, |_| LevelFilter::Trace);
+        log::set_boxed_logger(Box::new(self))?;
+        log::set_max_level(max_level);
+        Ok(())
+    }
+
+    /// Localize the `record`.
+    pub fn localize(&self, record: &Record<'_>) -> LocalizedRecord {
+        let mut visitor = ArgsVisitor {
+            event_key: &self.event_key,
+            event: None,
+            args: FluentArgs::new(),
+        };
+        if let Err(error) = record.key_values().visit(&mut visitor) {
+            log::debug!("Unable to visit the key-values of a log record: {error}");
+        }
+
+        let message = match &visitor.event {
+            Some(event) if self.loader.has(event) => {
+                self.loader.get_args_fluent(event, Some(&visitor.args))
+            }
+            _ => record.args().to_string(),
+        };
+
+        LocalizedRecord {
+            level: record.level(),
+            target: record.target().to_string(),
+            event: visitor.event,
+            message,
+        }
+    }
+}
+
+impl Log for LocalizedLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level
+            || self
+                .logger
+                .as_ref()
+                .is_some_and(|logger| logger.enabled(metadata))
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if let Some(logger) = &self.logger {
+            if logger.enabled(record.metadata()) {
+                logger.log(record);
+            }
+        }
+        // Records logged while localizing (e.g. for missing messages)
+        // are only passed to the other logger.
+        if record.level() <= self.level && !record.target().starts_with("i18n_embed") {
+            (self.sink)(self.localize(record));
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(logger) = &self.logger {
+            logger.flush();
+        }
+    }
+}
+
+/// Collects the event id and the message arguments from the
+/// key-values of a record.
+struct ArgsVisitor<'a> {
+    event_key: &'a str,
+    event: Option<String>,
+    args: FluentArgs<'static>,
+}
+
+impl<'kvs> VisitSource<'kvs> for ArgsVisitor<'_> {
+    fn visit_pair(&mut self, key: kv::Key<'kvs>, value: kv::Value<'kvs>) -> Result<(), kv::Error> {
+        if key.as_str() == self.event_key {
+            self.event = Some(value.to_string());
+            return Ok(());
+        }
+
+        let value: FluentValue<'static> = if let Some(value) = value.to_i64() {
+            value.into()
+        } else if let Some(value) = value.to_f64() {
+            value.into()
+        } else {
+            value.to_string().into()
+        };
+        self.args.set(key.as_str().to_string(), value);
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+//! The cache of formatted messages used by the
+//! [FluentLanguageLoader](crate::fluent::FluentLanguageLoader) when
+//! the `cache` feature is enabled.
+
+use std::collections::HashMap;
+
+use fluent::{types::FluentNumberOptions, FluentArgs, FluentValue};
+use parking_lot::Mutex;
+use unic_langid::LanguageIdentifier;
+
+/// Messages formatted with more arguments than this are not cached.
+const MAX_CACHED_ARGS: usize = 4;
+
+/// The maximum number of formatted messages in a cache. The cache is
+/// cleared when it is full, which is simpler than evicting the least
+/// recently used messages, and rare for the static labels this is
+/// intended for.
+const MAX_CACHED_MESSAGES: usize = 4096;
+
+/// A cached argument value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum CachedArg {
+    String(String),
+    /// The bits of the value, and its formatting options.
+    Number(u64, FluentNumberOptions),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    message_id: String,
+    attribute_id: Option<String>,
+    args: Vec<(String, CachedArg)>,
+}
+
+impl CacheKey {
+    /// The key for a message formatted with the `args`, or `None` if
+    /// the message should not be cached because it has too many
+    /// arguments, or arguments which can't be compared (such as
+    /// custom types).
+    fn new(
+        message_id: &str,
+        attribute_id: Option<&str>,
+        args: Option<&FluentArgs<'_>>,
+    ) -> Option<Self> {
+        let args = match args {
+            Some(args) if args.iter().count() > MAX_CACHED_ARGS => return None,
+            Some(args) => args
+                .iter()
+                .map(|(name, value)| {
+                    let value = match value {
+                        FluentValue::String(value) => CachedArg::String(value.to_string()),
+                        FluentValue::Number(number) => {
+                            CachedArg::Number(number.value.to_bits(), number.options.clone())
+                        }
+                        _ => return None,
+                    };
+                    Some((name.to_string(), value))
+                })
+                .collect::<Option<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+
+        Some(Self {
+            message_id: message_id.to_string(),
+            attribute_id: attribute_id.map(ToString::to_string),
+            args,
+        })
+    }
+}
+
+/// Formatted messages, along with the language which served them.
+/// A cache belongs to a single selection of loaded languages, so the
+/// language is not part of the key.
+#[derive(Debug, Default)]
+pub(crate) struct MessageCache {
+    messages: Mutex<HashMap<CacheKey, (String, LanguageIdentifier)>>,
+}
+
+impl MessageCache {
+    /// Get the message formatted with the `args` from the cache, or
+    /// using `format` (which returns the formatted message and the
+    /// language which served it) if it isn't cached yet.
+    pub(crate) fn get_or_format(
+        &self,
+        message_id: &str,
+        attribute_id: Option<&str>,
+        args: Option<&FluentArgs<'_>>,
+        format: impl FnOnce() -> Option<(String, LanguageIdentifier)>,
+    ) -> Option<(String, LanguageIdentifier)> {
+        let key = match CacheKey::new(message_id, attribute_id, args) {
+            Some(key) => key,
+            None => return format(),
+        };
+        if let Some(cached) = self.messages.lock().get(&key) {
+            return Some(cached.clone());
+        }
+
+        // Messages which are missing are not cached, so that they are
+        // still logged.
+        let formatted = format()?;
+        let mut messages = self.messages.lock();
+        if messages.len() >= MAX_CACHED_MESSAGES {
+            messages.clear();
+        }
+        messages.insert(key, formatted.clone());
+        Some(formatted)
+    }
+
+    pub(crate) fn clear(&self) {
+        self.messages.lock().clear();
+    }
+}
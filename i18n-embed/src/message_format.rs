@@ -0,0 +1,117 @@
+//! Conversion of ICU MessageFormat (MF1) style placeholders into
+//! fluent variable references, see
+//! [FluentLanguageLoader::enable_message_format_placeholders()](crate::fluent::FluentLanguageLoader::enable_message_format_placeholders()).
+
+use std::collections::HashSet;
+
+/// Convert the MessageFormat style placeholders in the fluent
+/// `source` of the file at `path` into fluent variable references:
+///
+/// + `{name}` becomes `{ $name }`, unless `name` is the id of a
+///   message defined in the `source`, in which case it is left as a
+///   message reference.
+/// + `{name, number}` also becomes `{ $name }`, because fluent
+///   formats numeric arguments as numbers.
+///
+/// Placeholders which are already valid fluent (such as
+/// `{ $name }`, `{ -term }` or `{ "literal" }`) are left unchanged,
+/// as are MessageFormat `plural`, `select` and `selectordinal`
+/// placeholders, which have no direct equivalent and are reported
+/// with a warning.
+pub(crate) fn convert_placeholders(path: &str, source: &str) -> String {
+    let message_ids: HashSet<&str> = source
+        .lines()
+        .filter_map(|line| {
+            let (id, _) = line.split_once('=')?;
+            let id = id.trim_end();
+            (!line.starts_with(char::is_whitespace) && is_identifier(id)).then_some(id)
+        })
+        .collect();
+
+    let mut converted = String::with_capacity(source.len());
+    for (line_index, line) in source.split_inclusive('\n').enumerate() {
+        if line.trim_start().starts_with('#') {
+            converted.push_str(line);
+            continue;
+        }
+
+        let mut rest = line;
+        while let Some(start) = rest.find('{') {
+            converted.push_str(&rest[..start]);
+            let placeable = &rest[start..];
+            let end = match placeable.find('}') {
+                Some(end) => end,
+                None => {
+                    rest = placeable;
+                    break;
+                }
+            };
+            let content = &placeable[1..end];
+            if content.contains('{') {
+                // A nested MessageFormat placeholder, such as a
+                // plural, which can't be converted.
+                if let Some(kind) = complex_placeholder_kind(content) {
+                    log::warn!(
+                        target: "i18n_embed::fluent",
+                        "Unable to convert the MessageFormat `{0}` placeholder in \"{1}\" on line {2}, it needs to be rewritten using fluent syntax.",
+                        kind,
+                        path,
+                        line_index + 1
+                    );
+                }
+                converted.push('{');
+                rest = &placeable[1..];
+                continue;
+            }
+
+            match convert_placeholder(content.trim(), &message_ids) {
+                Some(replacement) => {
+                    log::debug!(
+                        target: "i18n_embed::fluent",
+                        "Converted the MessageFormat placeholder `{{{0}}}` in \"{1}\" on line {2} to `{3}`.",
+                        content,
+                        path,
+                        line_index + 1,
+                        replacement
+                    );
+                    converted.push_str(&replacement);
+                }
+                None => converted.push_str(&placeable[..=end]),
+            }
+            rest = &placeable[end + 1..];
+        }
+        converted.push_str(rest);
+    }
+    converted
+}
+
+/// The fluent replacement for the contents of a MessageFormat
+/// placeholder, or `None` if it should be left unchanged.
+fn convert_placeholder(content: &str, message_ids: &HashSet<&str>) -> Option<String> {
+    let mut parts = content.split(',').map(str::trim);
+    let name = parts.next()?;
+    if !is_identifier(name) {
+        return None;
+    }
+    match (parts.next(), parts.next()) {
+        (None, _) if message_ids.contains(name) => None,
+        (None, _) | (Some("number"), None) => Some(format!("{{ ${name} }}")),
+        _ => None,
+    }
+}
+
+/// The kind of a MessageFormat placeholder with nested messages
+/// (e.g. `count, plural, one {`), if `content` is the start of one.
+fn complex_placeholder_kind(content: &str) -> Option<&str> {
+    let mut parts = content.split(',').map(str::trim);
+    let name = parts.next()?;
+    let kind = parts.next()?;
+    (is_identifier(name) && ["plural", "select", "selectordinal"].contains(&kind)).then_some(kind)
+}
+
+/// Whether `s` is a valid fluent identifier.
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
@@ -0,0 +1,163 @@
+use std::{collections::HashMap, sync::Arc};
+
+use parking_lot::{Mutex, RwLock};
+use unic_langid::LanguageIdentifier;
+
+use crate::{select, I18nAssets, I18nEmbedError, LanguageLoader};
+
+/// A domain managed by a [MultiDomainLoader].
+struct Domain<L> {
+    loader: Arc<L>,
+    assets: Box<dyn I18nAssets + Send + Sync + 'static>,
+    /// Whether the languages have been loaded into the `loader`.
+    loaded: Mutex<bool>,
+}
+
+impl<L: LanguageLoader> Domain<L> {
+    fn load(&self, requested_languages: &[LanguageIdentifier]) -> Result<(), I18nEmbedError> {
+        if requested_languages.is_empty() {
+            self.loader.load_fallback_language(&*self.assets)
+        } else {
+            select(&*self.loader, &*self.assets, requested_languages).map(|_| ())
+        }
+    }
+}
+
+/// Manages the [LanguageLoader]s for several domains (e.g. the
+/// `core` domain of an application, and a domain for each of its
+/// plugins), which share the same language selection.
+///
+/// The languages for a domain are only loaded when it is first
+/// accessed using [MultiDomainLoader::domain()], so an application
+/// with many plugins doesn't need to load the localizations for the
+/// plugins which are never used.
+///
+/// ```ignore
+/// use i18n_embed::{fluent::FluentLanguageLoader, MultiDomainLoader};
+/// use rust_embed::RustEmbed;
+///
+/// #[derive(RustEmbed)]
+/// #[folder = "i18n/ftl"]
+/// struct Localizations;
+///
+/// let loaders = MultiDomainLoader::new();
+/// loaders.add_domain(
+///     FluentLanguageLoader::new("test", "en-US".parse().unwrap()),
+///     Localizations,
+/// );
+/// loaders.select(&["ru".parse().unwrap()]).unwrap();
+///
+/// let loader = loaders.domain("test").unwrap();
+/// assert_eq!("Привет Мир Локализация!", loader.get("hello-world"));
+/// ```
+pub struct MultiDomainLoader<L> {
+    requested_languages: RwLock<Vec<LanguageIdentifier>>,
+    domains: RwLock<HashMap<String, Domain<L>>>,
+}
+
+impl<L> std::fmt::Debug for MultiDomainLoader<L> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultiDomainLoader")
+            .field("requested_languages", &*self.requested_languages.read())
+            .field("domains", &self.domains.read().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<L> Default for MultiDomainLoader<L> {
+    fn default() -> Self {
+        Self {
+            requested_languages: RwLock::new(Vec::new()),
+            domains: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl<L: LanguageLoader> MultiDomainLoader<L> {
+    /// Create a new [MultiDomainLoader] without any domains. Until
+    /// languages are selected using [MultiDomainLoader::select()],
+    /// each domain loads its fallback language.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the `loader` for the domain [LanguageLoader::domain()],
+    /// with the `assets` containing its localizations. This replaces
+    /// any loader previously added for the same domain. The
+    /// languages are not loaded until the domain is accessed.
+    pub fn add_domain(&self, loader: L, assets: impl I18nAssets + Send + Sync + 'static) {
+        let domain = Domain {
+            loader: Arc::new(loader),
+            assets: Box::new(assets),
+            loaded: Mutex::new(false),
+        };
+        self.domains
+            .write()
+            .insert(domain.loader.domain().to_string(), domain);
+    }
+
+    /// The names of the domains which have been added, in no
+    /// particular order.
+    pub fn domains(&self) -> Vec<String> {
+        self.domains.read().keys().cloned().collect()
+    }
+
+    /// Whether the languages for the `domain` have been loaded.
+    pub fn is_loaded(&self, domain: &str) -> bool {
+        self.domains
+            .read()
+            .get(domain)
+            .is_some_and(|domain| *domain.loaded.lock())
+    }
+
+    /// Get the loader for the `domain`, loading the selected
+    /// languages into it if this is the first time it has been
+    /// accessed. Returns [I18nEmbedError::DomainNotAvailable] if no
+    /// loader has been added for the `domain`.
+    pub fn domain(&self, domain: &str) -> Result<Arc<L>, I18nEmbedError> {
+        let domains = self.domains.read();
+        let entry = domains
+            .get(domain)
+            .ok_or_else(|| I18nEmbedError::DomainNotAvailable(domain.to_string()))?;
+
+        let mut loaded = entry.loaded.lock();
+        if !*loaded {
+            log::debug!("Lazily loading the languages for domain \"{0}\"", domain);
+            let requested_languages = self.requested_languages.read().clone();
+            entry.load(&requested_languages)?;
+            *loaded = true;
+        }
+
+        Ok(entry.loader.clone())
+    }
+
+    /// The languages which were requested using
+    /// [MultiDomainLoader::select()].
+    pub fn requested_languages(&self) -> Vec<LanguageIdentifier> {
+        self.requested_languages.read().clone()
+    }
+
+    /// Select the languages to use for all the domains in order of
+    /// preference by `requested_languages`. The domains which have
+    /// already been loaded are loaded again using [select()], the
+    /// others will use these languages when they are first accessed.
+    pub fn select(&self, requested_languages: &[LanguageIdentifier]) -> Result<(), I18nEmbedError> {
+        *self.requested_languages.write() = requested_languages.to_vec();
+
+        let mut errors = Vec::new();
+        for domain in self.domains.read().values() {
+            let loaded = domain.loaded.lock();
+            if *loaded {
+                if let Err(error) = domain.load(requested_languages) {
+                    errors.push(error);
+                }
+            }
+        }
+
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            _ => Err(I18nEmbedError::Multiple(errors)),
+        }
+    }
+}
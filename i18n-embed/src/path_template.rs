@@ -0,0 +1,187 @@
+use std::{borrow::Cow, fmt::Display};
+
+use crate::I18nEmbedError;
+
+const LANGUAGE: &str = "{language}";
+const DOMAIN: &str = "{domain}";
+
+/// A template for the paths of the language files within the
+/// [I18nAssets](crate::I18nAssets), used by a
+/// [LanguageLoader](crate::LanguageLoader) to find the files for a
+/// language, and to determine which languages are available.
+///
+/// The template contains a `{language}` placeholder which is replaced
+/// with the language identifier (e.g. `en-US`), and optionally a
+/// `{domain}` placeholder which is replaced with the domain of the
+/// loader. The default layout is `{language}/{domain}.ftl` for the
+/// fluent system, and `{language}/{domain}.mo` for the gettext
+/// system, but another layout can be used so that projects don't need
+/// to reorganize their assets, for example:
+///
+/// + `{domain}/{language}.ftl`
+/// + `locales/{language}.ftl`
+///
+/// Additional files for a language (such as fluent namespaces) are
+/// located in the directory with the same path as the language file
+/// without its extension, e.g. `locales/en-US/settings.ftl` with the
+/// template `locales/{language}.ftl`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PathTemplate(Cow<'static, str>);
+
+impl PathTemplate {
+    /// Create a new `PathTemplate`, returning an error if the
+    /// `template` does not contain the `{language}` placeholder
+    /// exactly once, or contains a placeholder other than
+    /// `{language}` and `{domain}`.
+    ///
+    /// ```
+    /// use i18n_embed::PathTemplate;
+    ///
+    /// let template = PathTemplate::new("locales/{language}.ftl").unwrap();
+    /// assert_eq!(
+    ///     "locales/en-US.ftl",
+    ///     template.path("en-US", "my_crate")
+    /// );
+    /// assert!(PathTemplate::new("{domain}.ftl").is_err());
+    /// ```
+    pub fn new(template: impl Into<Cow<'static, str>>) -> Result<Self, I18nEmbedError> {
+        let template = template.into();
+        let placeholders_valid = template
+            .match_indices('{')
+            .all(|(i, _)| template[i..].starts_with(LANGUAGE) || template[i..].starts_with(DOMAIN));
+        if template.matches(LANGUAGE).count() != 1 || !placeholders_valid {
+            return Err(I18nEmbedError::InvalidPathTemplate(template.into_owned()));
+        }
+        Ok(Self(template))
+    }
+
+    /// Create a new `PathTemplate` in a `const` context, see
+    /// [PathTemplate::new()]. The `template` is not validated, this
+    /// is intended for templates which have already been validated
+    /// (e.g. by the `static_loader!()` macro).
+    pub const fn new_const(template: &'static str) -> Self {
+        Self(Cow::Borrowed(template))
+    }
+
+    /// The template `{language}/{file_name}`, which is the default
+    /// for a loader with the [LanguageLoader::language_file_name()](crate::LanguageLoader::language_file_name())
+    /// `file_name`.
+    pub fn with_file_name(file_name: &str) -> Self {
+        Self(Cow::Owned(format!("{LANGUAGE}/{file_name}")))
+    }
+
+    /// The template as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The path of the language file for the `language` (usually a
+    /// [LanguageIdentifier](unic_langid::LanguageIdentifier)), with
+    /// the `domain`.
+    pub fn path(&self, language: impl Display, domain: &str) -> String {
+        self.0
+            .replace(DOMAIN, domain)
+            .replace(LANGUAGE, &language.to_string())
+    }
+
+    /// The directory containing the additional files (such as fluent
+    /// namespaces) for the `language`, with the `domain`. This is the
+    /// [PathTemplate::path()] without its extension, or `None` if the
+    /// template has no extension.
+    pub fn directory(&self, language: impl Display, domain: &str) -> Option<String> {
+        Some(
+            self.stem()?
+                .replace(DOMAIN, domain)
+                .replace(LANGUAGE, &language.to_string()),
+        )
+    }
+
+    /// The path of the file with the `file_name` (such as the fluent
+    /// `_terms.ftl` file) in the same directory as the language file
+    /// for the `language`, with the `domain`. This is `None` if the
+    /// directory is not specific to the language, e.g. for the
+    /// template `locales/{language}.ftl`.
+    ///
+    /// ```
+    /// use i18n_embed::PathTemplate;
+    ///
+    /// let template = PathTemplate::new("{language}/{domain}.ftl").unwrap();
+    /// assert_eq!(
+    ///     Some("en-US/_terms.ftl".to_string()),
+    ///     template.sibling_path("en-US", "my_crate", "_terms.ftl")
+    /// );
+    /// let template = PathTemplate::new("{domain}/{language}.ftl").unwrap();
+    /// assert_eq!(None, template.sibling_path("en-US", "my_crate", "_terms.ftl"));
+    /// ```
+    pub fn sibling_path(
+        &self,
+        language: impl Display,
+        domain: &str,
+        file_name: &str,
+    ) -> Option<String> {
+        let directory = &self.0[..self.0.rfind('/')?];
+        if !directory.contains(LANGUAGE) {
+            return None;
+        }
+        Some(format!(
+            "{0}/{file_name}",
+            directory
+                .replace(DOMAIN, domain)
+                .replace(LANGUAGE, &language.to_string())
+        ))
+    }
+
+    /// The language of the file at `path` if it is a language file
+    /// for the `domain` (see [PathTemplate::path()]), or is within
+    /// the directory for a language (see [PathTemplate::directory()]).
+    /// The language is not parsed, so it may not be a valid language
+    /// identifier.
+    pub fn language<'p>(&self, path: &'p str, domain: &str) -> Option<&'p str> {
+        match_language(&self.0.replace(DOMAIN, domain), path, false).or_else(|| {
+            let stem = self.stem()?.replace(DOMAIN, domain);
+            match_language(&stem, path, true)
+        })
+    }
+
+    /// The template without the extension of its file name, if the
+    /// extension doesn't contain a placeholder.
+    fn stem(&self) -> Option<&str> {
+        let file_name_start = self.0.rfind('/').map_or(0, |i| i + 1);
+        let extension_start = file_name_start + self.0[file_name_start..].rfind('.')?;
+        if self.0[extension_start..].contains('{') {
+            return None;
+        }
+        Some(&self.0[..extension_start])
+    }
+}
+
+/// Match the `path` against the `template` (with the domain already
+/// substituted), returning the language. If `in_directory` is true,
+/// the `path` is matched against the files within the directory
+/// given by the template instead.
+fn match_language<'p>(template: &str, path: &'p str, in_directory: bool) -> Option<&'p str> {
+    let (prefix, suffix) = template.split_once(LANGUAGE)?;
+    let rest = path.strip_prefix(prefix)?;
+    // The language is a single path component.
+    let language_end = rest.find('/').unwrap_or(rest.len());
+    let end = (1..=language_end)
+        .filter(|&end| rest.is_char_boundary(end))
+        .find(|&end| {
+            let after = &rest[end..];
+            if in_directory {
+                after
+                    .strip_prefix(suffix)
+                    .and_then(|file| file.strip_prefix('/'))
+                    .is_some_and(|file| !file.is_empty())
+            } else {
+                after == suffix
+            }
+        })?;
+    Some(&rest[..end])
+}
+
+impl Display for PathTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
@@ -0,0 +1,194 @@
+use std::{
+    borrow::Cow,
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap},
+    hash::{Hash, Hasher},
+    sync::{mpsc, Arc, Mutex, RwLock, Weak},
+    time::Duration,
+};
+
+use crate::{assets::Subscribers, I18nAssets, I18nEmbedError, Watcher};
+
+/// [I18nAssets] fetched using a closure from a dynamic source of
+/// translations, such as a database (e.g. Postgres or Redis). The
+/// closure returns all of the files, a map of their paths (e.g.
+/// `en-US/my_crate.ftl`) to their contents.
+///
+/// The files are fetched when the assets are created, and whenever
+/// they are polled with [PollingAssets::poll()]. While there are
+/// subscribers (see [I18nAssets::subscribe_changed()]), the files are
+/// also polled in a background thread at the configured interval, and
+/// the subscribers are notified when the hash of their contents has
+/// changed, so that the loaders using these assets can reload them
+/// (e.g. using [crate::DefaultLocalizer::with_autoreload()]).
+///
+/// ```
+/// use std::{collections::HashMap, time::Duration};
+/// use i18n_embed::{I18nAssets, PollingAssets};
+///
+/// let assets = PollingAssets::new(
+///     || {
+///         // Query the translations from the database.
+///         let mut files = HashMap::new();
+///         files.insert("en-US/my_crate.ftl".to_string(), b"hello = Hello!".to_vec());
+///         Ok::<_, std::io::Error>(files)
+///     },
+///     Duration::from_secs(60),
+/// )
+/// .unwrap();
+///
+/// assert_eq!(b"hello = Hello!", &*assets.get_files("en-US/my_crate.ftl")[0]);
+/// ```
+pub struct PollingAssets<F> {
+    inner: Arc<PollingAssetsInner<F>>,
+    interval: Duration,
+}
+
+struct PollingAssetsInner<F> {
+    fetch: F,
+    files: RwLock<BTreeMap<String, Vec<u8>>>,
+    /// The hash of the `files`, once they have been fetched.
+    hash: Mutex<Option<u64>>,
+    subscribers: Arc<Subscribers>,
+}
+
+impl<F> std::fmt::Debug for PollingAssets<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PollingAssets")
+            .field(
+                "files",
+                &self.inner.files.read().unwrap().keys().collect::<Vec<_>>(),
+            )
+            .field("interval", &self.interval)
+            .finish()
+    }
+}
+
+impl<F, E> PollingAssets<F>
+where
+    F: Fn() -> Result<HashMap<String, Vec<u8>>, E> + Send + Sync + 'static,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    /// Construct new [PollingAssets] whose files are fetched using
+    /// `fetch`, and which are polled at the `interval` while there
+    /// are subscribers. Returns an error if the files can't be
+    /// fetched.
+    pub fn new(fetch: F, interval: Duration) -> Result<Self, I18nEmbedError> {
+        let assets = Self {
+            inner: Arc::new(PollingAssetsInner {
+                fetch,
+                files: RwLock::default(),
+                hash: Mutex::default(),
+                subscribers: Arc::default(),
+            }),
+            interval,
+        };
+        assets.poll()?;
+        Ok(assets)
+    }
+
+    /// The interval at which the files are polled while there are
+    /// subscribers.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Fetch the files now, and notify the subscribers if they have
+    /// changed. Returns whether the files have changed. When the
+    /// files can't be fetched, the previous files are kept.
+    pub fn poll(&self) -> Result<bool, I18nEmbedError> {
+        self.inner.poll()
+    }
+}
+
+impl<F, E> PollingAssetsInner<F>
+where
+    F: Fn() -> Result<HashMap<String, Vec<u8>>, E>,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    fn poll(&self) -> Result<bool, I18nEmbedError> {
+        let files: BTreeMap<String, Vec<u8>> = (self.fetch)()
+            .map_err(|error| I18nEmbedError::PollingAssetsFetch(error.into()))?
+            .into_iter()
+            .collect();
+        let mut hasher = DefaultHasher::new();
+        files.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        {
+            let mut current_hash = self.hash.lock().unwrap();
+            if *current_hash == Some(hash) {
+                return Ok(false);
+            }
+            *current_hash = Some(hash);
+            *self.files.write().unwrap() = files;
+        }
+        self.subscribers.notify();
+        Ok(true)
+    }
+}
+
+impl<F, E> I18nAssets for PollingAssets<F>
+where
+    F: Fn() -> Result<HashMap<String, Vec<u8>>, E> + Send + Sync + 'static,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    fn get_files(&self, file_path: &str) -> Vec<Cow<'_, [u8]>> {
+        self.inner
+            .files
+            .read()
+            .unwrap()
+            .get(file_path)
+            .map(|contents| Cow::Owned(contents.clone()))
+            .into_iter()
+            .collect()
+    }
+
+    fn filenames_iter(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        let filenames: Vec<String> = self.inner.files.read().unwrap().keys().cloned().collect();
+        Box::new(filenames.into_iter())
+    }
+
+    fn subscribe_changed(
+        &self,
+        changed: Arc<dyn Fn() + Send + Sync + 'static>,
+    ) -> Result<Box<dyn Watcher + Send + Sync + 'static>, I18nEmbedError> {
+        let subscription = self.inner.subscribers.subscribe(changed);
+
+        // The thread stops polling when the watcher (and with it the
+        // sender) is dropped, or when the assets are dropped.
+        let (stop, stopped) = mpsc::channel::<()>();
+        let inner: Weak<PollingAssetsInner<F>> = Arc::downgrade(&self.inner);
+        let interval = self.interval;
+        std::thread::spawn(move || {
+            while let Err(mpsc::RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                let inner = match inner.upgrade() {
+                    Some(inner) => inner,
+                    None => break,
+                };
+                if let Err(error) = inner.poll() {
+                    diagnostic!(
+                        warn,
+                        target: "i18n_embed::assets",
+                        {},
+                        "Unable to poll the localization assets: {}",
+                        error
+                    );
+                }
+            }
+        });
+
+        Ok(Box::new(PollingWatcher {
+            _subscription: subscription,
+            _stop: stop,
+        }))
+    }
+}
+
+/// Unsubscribes from the [PollingAssets] and stops polling them when
+/// dropped.
+struct PollingWatcher {
+    _subscription: Box<dyn Watcher + Send + Sync + 'static>,
+    _stop: mpsc::Sender<()>,
+}
+
+impl Watcher for PollingWatcher {}
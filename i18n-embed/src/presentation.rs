@@ -0,0 +1,174 @@
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+use unic_langid::LanguageIdentifier;
+
+/// Metadata for presenting a language to the user, for example as an
+/// option in a language picker, obtained using
+/// [locale_presentation()].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LocalePresentation {
+    /// An emoji flag for the language, e.g. "🇩🇪" for `de-DE`.
+    ///
+    /// Flags represent countries rather than languages, so this is a
+    /// heuristic: the region of the language identifier is used if it
+    /// has one, otherwise the region where the language is most
+    /// commonly spoken. Languages without a clear association with a
+    /// single region (such as Arabic or Esperanto) have no flag.
+    pub flag: Option<String>,
+    /// The name of the language in the language itself, e.g.
+    /// "Deutsch" for `de`.
+    pub native_name: Option<String>,
+    /// The name of the language in English, e.g. "German" for `de`.
+    pub english_name: Option<String>,
+}
+
+/// Built-in presentation data for a language: its primary language
+/// subtag, native name, English name, and the region used for its flag
+/// when the language identifier doesn't specify one.
+struct LanguageData {
+    language: &'static str,
+    native_name: &'static str,
+    english_name: &'static str,
+    default_region: Option<&'static str>,
+}
+
+const fn data(
+    language: &'static str,
+    native_name: &'static str,
+    english_name: &'static str,
+    default_region: Option<&'static str>,
+) -> LanguageData {
+    LanguageData {
+        language,
+        native_name,
+        english_name,
+        default_region,
+    }
+}
+
+/// Presentation data for common languages, sorted by language subtag.
+const LANGUAGES: &[LanguageData] = &[
+    data("ar", "العربية", "Arabic", None),
+    data("bg", "Български", "Bulgarian", Some("BG")),
+    data("ca", "Català", "Catalan", None),
+    data("cs", "Čeština", "Czech", Some("CZ")),
+    data("da", "Dansk", "Danish", Some("DK")),
+    data("de", "Deutsch", "German", Some("DE")),
+    data("el", "Ελληνικά", "Greek", Some("GR")),
+    data("en", "English", "English", None),
+    data("eo", "Esperanto", "Esperanto", None),
+    data("es", "Español", "Spanish", None),
+    data("et", "Eesti", "Estonian", Some("EE")),
+    data("fa", "فارسی", "Persian", Some("IR")),
+    data("fi", "Suomi", "Finnish", Some("FI")),
+    data("fr", "Français", "French", None),
+    data("he", "עברית", "Hebrew", Some("IL")),
+    data("hi", "हिन्दी", "Hindi", Some("IN")),
+    data("hr", "Hrvatski", "Croatian", Some("HR")),
+    data("hu", "Magyar", "Hungarian", Some("HU")),
+    data("id", "Bahasa Indonesia", "Indonesian", Some("ID")),
+    data("it", "Italiano", "Italian", Some("IT")),
+    data("ja", "日本語", "Japanese", Some("JP")),
+    data("ka", "ქართული", "Georgian", Some("GE")),
+    data("ko", "한국어", "Korean", Some("KR")),
+    data("lt", "Lietuvių", "Lithuanian", Some("LT")),
+    data("lv", "Latviešu", "Latvian", Some("LV")),
+    data("nb", "Norsk bokmål", "Norwegian Bokmål", Some("NO")),
+    data("nl", "Nederlands", "Dutch", None),
+    data("nn", "Norsk nynorsk", "Norwegian Nynorsk", Some("NO")),
+    data("pl", "Polski", "Polish", Some("PL")),
+    data("pt", "Português", "Portuguese", None),
+    data("ro", "Română", "Romanian", Some("RO")),
+    data("ru", "Русский", "Russian", None),
+    data("sk", "Slovenčina", "Slovak", Some("SK")),
+    data("sl", "Slovenščina", "Slovenian", Some("SI")),
+    data("sr", "Српски", "Serbian", Some("RS")),
+    data("sv", "Svenska", "Swedish", Some("SE")),
+    data("th", "ไทย", "Thai", Some("TH")),
+    data("tr", "Türkçe", "Turkish", Some("TR")),
+    data("uk", "Українська", "Ukrainian", Some("UA")),
+    data("vi", "Tiếng Việt", "Vietnamese", Some("VN")),
+    data("zh", "中文", "Chinese", None),
+];
+
+/// Presentation metadata registered using
+/// [override_locale_presentation()], which takes precedence over the
+/// built-in data.
+fn overrides() -> &'static RwLock<HashMap<LanguageIdentifier, LocalePresentation>> {
+    static OVERRIDES: OnceLock<RwLock<HashMap<LanguageIdentifier, LocalePresentation>>> =
+        OnceLock::new();
+    OVERRIDES.get_or_init(Default::default)
+}
+
+/// Obtain the metadata for presenting the `language` to the user, for
+/// example in a language picker.
+///
+/// The metadata is compiled into this crate for common languages, and
+/// can be replaced for a specific language identifier (e.g. to use
+/// "English (UK)" for `en-GB`) using [override_locale_presentation()].
+/// Any fields which are not available for the `language` are `None`.
+///
+/// ```
+/// use i18n_embed::{locale_presentation, unic_langid::LanguageIdentifier};
+///
+/// let de: LanguageIdentifier = "de-AT".parse().unwrap();
+/// let presentation = locale_presentation(&de);
+/// assert_eq!(Some("🇦🇹"), presentation.flag.as_deref());
+/// assert_eq!(Some("Deutsch"), presentation.native_name.as_deref());
+/// assert_eq!(Some("German"), presentation.english_name.as_deref());
+/// ```
+pub fn locale_presentation(language: &LanguageIdentifier) -> LocalePresentation {
+    if let Some(presentation) = overrides()
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .get(language)
+    {
+        return presentation.clone();
+    }
+
+    let data = LANGUAGES
+        .binary_search_by(|data| data.language.cmp(language.language.as_str()))
+        .ok()
+        .map(|index| &LANGUAGES[index]);
+
+    let region = language
+        .region
+        .as_ref()
+        .map(|region| region.as_str())
+        .or_else(|| data.and_then(|data| data.default_region));
+
+    LocalePresentation {
+        flag: region.and_then(flag_emoji),
+        native_name: data.map(|data| data.native_name.to_string()),
+        english_name: data.map(|data| data.english_name.to_string()),
+    }
+}
+
+/// Replace the metadata returned by [locale_presentation()] for the
+/// `language`, for example with names which are loaded from a
+/// manifest of the languages supported by an application. The
+/// override only applies to this exact language identifier.
+pub fn override_locale_presentation(
+    language: LanguageIdentifier,
+    presentation: LocalePresentation,
+) {
+    overrides()
+        .write()
+        .unwrap_or_else(|err| err.into_inner())
+        .insert(language, presentation);
+}
+
+/// Convert a two letter region code into the emoji flag made from the
+/// corresponding pair of regional indicator symbols. Numeric region
+/// codes (such as `419` for Latin America) have no flag.
+fn flag_emoji(region: &str) -> Option<String> {
+    if region.len() != 2 || !region.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return None;
+    }
+    region
+        .bytes()
+        .map(|b| char::from_u32(0x1F1E6 + u32::from(b.to_ascii_uppercase() - b'A')))
+        .collect()
+}
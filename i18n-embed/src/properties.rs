@@ -0,0 +1,253 @@
+//! This module contains the types and functions to interact with Java
+//! [`.properties`](https://docs.oracle.com/javase/8/docs/api/java/util/Properties.html#load-java.io.Reader-)
+//! resource bundles, for products which share their translations with
+//! Java applications.
+//!
+//! Most important is the [PropertiesLanguageLoader].
+//!
+//! ⚠️ *This module requires the following crate features to be activated: `properties-system`.*
+
+use std::{collections::HashMap, fmt::Display};
+
+use unic_langid::LanguageIdentifier;
+
+use crate::{table::LanguageTables, I18nAssets, I18nEmbedError, LanguageLoader};
+
+/// [LanguageLoader] implementation for Java `.properties` files. The
+/// files are expected at `{language}/{domain}.properties` in the
+/// assets, encoded using UTF-8 (the default for resource bundles
+/// since Java 9). The file for the fallback language contains the
+/// messages of the base bundle, and is required.
+///
+/// ```ignore
+/// let loader = PropertiesLanguageLoader::new("app", "en".parse().unwrap());
+/// loader.load_languages(&Localizations, &["de".parse().unwrap()])?;
+/// assert_eq!("Datei öffnen", loader.get("menu.file.open"));
+/// ```
+///
+/// ⚠️ *This API requires the following crate features to be activated: `properties-system`.*
+#[derive(Debug)]
+pub struct PropertiesLanguageLoader {
+    tables: LanguageTables<String, String>,
+    domain: String,
+    fallback_language: LanguageIdentifier,
+}
+
+impl PropertiesLanguageLoader {
+    /// Create a new `PropertiesLanguageLoader` for the `domain`.
+    pub fn new(domain: impl Into<String>, fallback_language: LanguageIdentifier) -> Self {
+        Self {
+            tables: LanguageTables::new(&fallback_language),
+            domain: domain.into(),
+            fallback_language,
+        }
+    }
+
+    /// Get the message with the `key` from the first of the loaded
+    /// languages which has it. If the message is not available in
+    /// any of them, an error is logged and the `key` is returned.
+    pub fn get(&self, key: &str) -> String {
+        self.tables
+            .lookup(key, |_, value| value.clone())
+            .unwrap_or_else(|| {
+                log::error!(
+                    target: "i18n_embed::properties",
+                    "Unable to find the message \"{0}\" in the \"{1}\" domain",
+                    key,
+                    self.domain
+                );
+                key.to_string()
+            })
+    }
+
+    /// Get the message with the `key`, see
+    /// [PropertiesLanguageLoader::get()], replacing the placeholders
+    /// `{0}`, `{1}`, ... with the `args` as Java's `MessageFormat`
+    /// does. Text within single quotes is not formatted, and `''` is a
+    /// single quote. Format types (such as `{0,number}`) are ignored,
+    /// and the arguments formatted using [Display].
+    pub fn get_args(&self, key: &str, args: &[&dyn Display]) -> String {
+        format_message(&self.get(key), args)
+    }
+
+    /// Whether the message with the `key` is available in any of the
+    /// loaded languages.
+    pub fn has(&self, key: &str) -> bool {
+        self.tables.contains(key)
+    }
+}
+
+/// Format the `pattern` with the `args`, see
+/// [PropertiesLanguageLoader::get_args()].
+fn format_message(pattern: &str, args: &[&dyn Display]) -> String {
+    let mut formatted = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                formatted.push('\'');
+            }
+            '\'' => quoted = !quoted,
+            '{' if !quoted => {
+                let placeholder: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                let index = placeholder.split(',').next().unwrap_or_default().trim();
+                match index
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| args.get(index))
+                {
+                    Some(arg) => formatted.push_str(&arg.to_string()),
+                    None => {
+                        formatted.push('{');
+                        formatted.push_str(&placeholder);
+                        formatted.push('}');
+                    }
+                }
+            }
+            c => formatted.push(c),
+        }
+    }
+    formatted
+}
+
+/// Parse the key-value pairs of a `.properties` file, following the
+/// rules of Java's `Properties::load()`.
+fn parse_properties(source: &str) -> HashMap<String, String> {
+    let mut properties = HashMap::new();
+    let mut lines = source.lines();
+    while let Some(line) = lines.next() {
+        let line = line.trim_start();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+            continue;
+        }
+
+        // Join the lines ending with an unescaped backslash.
+        let mut logical_line = line.to_string();
+        while ends_with_continuation(&logical_line) {
+            logical_line.pop();
+            match lines.next() {
+                Some(next_line) => logical_line.push_str(next_line.trim_start()),
+                None => break,
+            }
+        }
+
+        let (key, value) = split_key_value(&logical_line);
+        properties.insert(unescape(key), unescape(value));
+    }
+    properties
+}
+
+/// Whether the `line` ends with an odd number of backslashes.
+fn ends_with_continuation(line: &str) -> bool {
+    line.chars().rev().take_while(|c| *c == '\\').count() % 2 == 1
+}
+
+/// Split the `line` into its key and value, which are separated by
+/// the first unescaped `=`, `:` or whitespace.
+fn split_key_value(line: &str) -> (&str, &str) {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '=' | ':' => return (&line[..i], line[i + 1..].trim_start()),
+            c if c.is_whitespace() => {
+                let value = line[i..].trim_start();
+                let value = value
+                    .strip_prefix(['=', ':'])
+                    .map_or(value, str::trim_start);
+                return (&line[..i], value);
+            }
+            _ => {}
+        }
+    }
+    (line, "")
+}
+
+/// Replace the escape sequences in `s`.
+fn unescape(s: &str) -> String {
+    let mut unescaped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('f') => unescaped.push('\u{000C}'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    Some(c) => unescaped.push(c),
+                    None => {
+                        log::warn!(
+                            target: "i18n_embed::properties",
+                            "Invalid unicode escape sequence \"\\u{0}\"",
+                            hex
+                        );
+                        unescaped.push_str(&hex);
+                    }
+                }
+            }
+            Some(c) => unescaped.push(c),
+            None => {}
+        }
+    }
+    unescaped
+}
+
+impl LanguageLoader for PropertiesLanguageLoader {
+    /// The fallback language, which is the language of the base
+    /// bundle.
+    fn fallback_language(&self) -> &LanguageIdentifier {
+        &self.fallback_language
+    }
+
+    /// The domain for the translation that this loader is associated with.
+    fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// The language file name to use for this loader's domain.
+    fn language_file_name(&self) -> String {
+        format!("{}.properties", self.domain)
+    }
+
+    /// Get the language which is currently loaded for this loader.
+    fn current_language(&self) -> LanguageIdentifier {
+        self.tables.current_language()
+    }
+
+    /// Load the languages `language_ids` using the resources packaged
+    /// in the `i18n_assets` in order of fallback preference. This
+    /// also sets the [LanguageLoader::current_language()] to the
+    /// first in the `language_ids` slice. You can use
+    /// [select()](super::select()) to determine which fallbacks are
+    /// actually available for an arbitrary slice of preferences.
+    fn load_languages(
+        &self,
+        i18n_assets: &dyn I18nAssets,
+        language_ids: &[LanguageIdentifier],
+    ) -> Result<(), I18nEmbedError> {
+        self.tables
+            .load(self, i18n_assets, language_ids, true, |path, file| {
+                let source = String::from_utf8(file.to_vec()).map_err(|error| {
+                    I18nEmbedError::ErrorParsingFileUtf8(path.to_string(), error)
+                })?;
+                Ok(parse_properties(&source))
+            })
+    }
+
+    fn reload(&self, i18n_assets: &dyn I18nAssets) -> Result<(), I18nEmbedError> {
+        let language_ids = self.tables.languages();
+        self.load_languages(i18n_assets, &language_ids)
+    }
+}
@@ -0,0 +1,105 @@
+use std::borrow::Cow;
+
+/// The identifier of the language conventionally used for
+/// pseudolocalized resources (`qps-ploc`), in its canonical form.
+pub const PSEUDOLOCALIZATION_LANGUAGE: &str = "qps-Ploc";
+
+/// The accented look-alikes of the ASCII letters `A` to `Z`, followed
+/// by `a` to `z`.
+const ACCENTED: [char; 52] = [
+    'Å', 'Ɓ', 'Ç', 'Ð', 'É', 'Ƒ', 'Ĝ', 'Ĥ', 'Î', 'Ĵ', 'Ķ', 'Ļ', 'Ṁ', 'Ñ', 'Ö', 'Þ', 'Ǫ', 'Ŕ', 'Š',
+    'Ţ', 'Û', 'Ṽ', 'Ŵ', 'Ẋ', 'Ý', 'Ž', 'å', 'ƀ', 'ç', 'ð', 'é', 'ƒ', 'ĝ', 'ĥ', 'î', 'ĵ', 'ķ', 'ļ',
+    'ṁ', 'ñ', 'ö', 'þ', 'ǫ', 'ŕ', 'š', 'ţ', 'û', 'ṽ', 'ŵ', 'ẋ', 'ý', 'ž',
+];
+
+/// Options for pseudolocalization, which transforms messages so that
+/// they remain readable, but look like they have been translated.
+/// This allows developers to spot text which hasn't been localized,
+/// and layouts which can't accommodate longer translations, without
+/// waiting for them to be translated. For example `Hello World!`
+/// becomes `[Ĥéļļö Ŵöŕļð!~~~~]` with the default options.
+///
+/// See `fluent::FluentLanguageLoader::set_pseudolocalization()`, and
+/// the `cargo i18n pseudo` command for generating pseudolocalized
+/// resources for the [PSEUDOLOCALIZATION_LANGUAGE].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pseudolocalization {
+    /// Replace ASCII letters with accented look-alikes, e.g. `Hello`
+    /// becomes `Ĥéļļö`.
+    pub accents: bool,
+    /// Lengthen each message with `~` characters by this percentage
+    /// of its length (rounded up), to simulate the expansion of
+    /// translated text.
+    pub expansion_percent: u32,
+    /// Surround each message with `[` and `]`, so that truncated or
+    /// concatenated messages can be spotted.
+    pub brackets: bool,
+}
+
+impl Default for Pseudolocalization {
+    fn default() -> Self {
+        Self {
+            accents: true,
+            expansion_percent: 30,
+            brackets: true,
+        }
+    }
+}
+
+impl Pseudolocalization {
+    /// Pseudolocalize the `text` of a message which doesn't contain
+    /// any placeables, see [Pseudolocalization::accent()] and
+    /// [Pseudolocalization::wrap()].
+    pub fn pseudolocalize(&self, text: &str) -> String {
+        self.wrap(&self.accent(text))
+    }
+
+    /// Replace the ASCII letters in the `text` with their accented
+    /// look-alikes if [Pseudolocalization::accents] is enabled.
+    pub fn accent<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.accents {
+            accent(text)
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+
+    /// Add the expansion padding and brackets to a `message` which
+    /// has already been formatted (with its placeables).
+    pub fn wrap(&self, message: &str) -> String {
+        let padding = self.padding(message.chars().count());
+        let mut wrapped = String::with_capacity(message.len() + padding.len() + 2);
+        if self.brackets {
+            wrapped.push('[');
+        }
+        wrapped.push_str(message);
+        wrapped.push_str(&padding);
+        if self.brackets {
+            wrapped.push(']');
+        }
+        wrapped
+    }
+
+    /// The expansion padding for a message with `length` characters.
+    pub fn padding(&self, length: usize) -> String {
+        let padding = (length as u64 * u64::from(self.expansion_percent)).div_ceil(100);
+        "~".repeat(padding as usize)
+    }
+}
+
+/// Replace the ASCII letters in the `text` with their accented
+/// look-alikes. This is a function rather than a closure so that it
+/// can be used as the transform of a fluent bundle, which is only
+/// applied to the text of messages, leaving their placeables intact.
+pub(crate) fn accent(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(|c| c.is_ascii_alphabetic()) {
+        return Cow::Borrowed(text);
+    }
+    text.chars()
+        .map(|c| match c {
+            'A'..='Z' => ACCENTED[c as usize - 'A' as usize],
+            'a'..='z' => ACCENTED[c as usize - 'a' as usize + 26],
+            c => c,
+        })
+        .collect()
+}
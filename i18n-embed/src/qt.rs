@@ -0,0 +1,299 @@
+//! This module contains the types and functions to interact with the
+//! [Qt Linguist](https://doc.qt.io/qt-6/qtlinguist-index.html)
+//! localization system, for products which share their translations
+//! with Qt applications.
+//!
+//! Most important is the [QtLanguageLoader].
+//!
+//! ⚠️ *This module requires the following crate features to be activated: `qt-system`.*
+
+use std::collections::HashMap;
+
+use intl_pluralrules::{PluralCategory, PluralRuleType, PluralRules};
+use parking_lot::RwLock;
+use unic_langid::LanguageIdentifier;
+
+use crate::{table::LanguageTables, I18nAssets, I18nEmbedError, LanguageLoader};
+
+/// Identifies a message in a Qt Linguist `.ts` file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct MessageKey {
+    context: String,
+    source: String,
+    disambiguation: Option<String>,
+}
+
+impl MessageKey {
+    fn new(context: &str, source: &str, disambiguation: Option<&str>) -> Self {
+        Self {
+            context: context.to_string(),
+            source: source.to_string(),
+            disambiguation: disambiguation.map(ToString::to_string),
+        }
+    }
+}
+
+/// [LanguageLoader] implementation for Qt Linguist `.ts` files. The
+/// files are expected at `{language}/{domain}.ts` in the assets. The
+/// messages of the fallback language are the source texts in the
+/// code, so it does not need a file.
+///
+/// Translations marked as `unfinished`, `obsolete` or `vanished` are
+/// ignored, as they are by Qt's `lrelease`.
+///
+/// ```ignore
+/// let loader = QtLanguageLoader::new("app", "en".parse().unwrap());
+/// loader.load_languages(&Localizations, &["de".parse().unwrap()])?;
+/// assert_eq!("Öffnen", loader.translate("MainWindow", "Open", None));
+/// ```
+///
+/// ⚠️ *This API requires the following crate features to be activated: `qt-system`.*
+#[derive(Debug)]
+pub struct QtLanguageLoader {
+    tables: LanguageTables<MessageKey, Vec<String>>,
+    domain: String,
+    fallback_language: LanguageIdentifier,
+    /// The plural categories of each language, see
+    /// [QtLanguageLoader::numerus_form_index()].
+    plural_categories: RwLock<HashMap<LanguageIdentifier, Vec<usize>>>,
+}
+
+impl QtLanguageLoader {
+    /// Create a new `QtLanguageLoader` for the `domain`.
+    pub fn new(domain: impl Into<String>, fallback_language: LanguageIdentifier) -> Self {
+        Self {
+            tables: LanguageTables::new(&fallback_language),
+            domain: domain.into(),
+            fallback_language,
+            plural_categories: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Translate the `source` text in the `context` (usually the
+    /// name of a class), optionally distinguished from other messages
+    /// with the same `source` text by the `disambiguation` comment.
+    /// This is equivalent to `QCoreApplication::translate()`.
+    ///
+    /// The translation is taken from the first of the loaded
+    /// languages which has one, or the `source` text is returned if
+    /// there is none.
+    pub fn translate(&self, context: &str, source: &str, disambiguation: Option<&str>) -> String {
+        let key = MessageKey::new(context, source, disambiguation);
+        self.tables
+            .lookup(&key, |_, forms| forms.first().cloned())
+            .flatten()
+            .unwrap_or_else(|| {
+                log::debug!(
+                    target: "i18n_embed::qt",
+                    "No translation for \"{0}\" in context \"{1}\"",
+                    source,
+                    context
+                );
+                source.to_string()
+            })
+    }
+
+    /// Translate the `source` text containing the number `n`, see
+    /// [QtLanguageLoader::translate()]. The numerus form is selected
+    /// for `n` using the plural rules of the language of the
+    /// translation, and each `%n` is replaced with `n`.
+    pub fn translate_n(
+        &self,
+        context: &str,
+        source: &str,
+        disambiguation: Option<&str>,
+        n: u64,
+    ) -> String {
+        let key = MessageKey::new(context, source, disambiguation);
+        let translation = self
+            .tables
+            .lookup(&key, |language, forms| {
+                let index = self.numerus_form_index(language, n);
+                forms.get(index).or_else(|| forms.last()).cloned()
+            })
+            .flatten()
+            .unwrap_or_else(|| source.to_string());
+        translation.replace("%n", &n.to_string())
+    }
+
+    /// Whether there is a translation for the `source` text in the
+    /// `context` in any of the loaded languages.
+    pub fn has(&self, context: &str, source: &str, disambiguation: Option<&str>) -> bool {
+        self.tables
+            .contains(&MessageKey::new(context, source, disambiguation))
+    }
+
+    /// The index of the numerus form for `n` in a translation for the
+    /// `language`. Qt orders the numerus forms of a language in the
+    /// same way as the CLDR plural categories (`zero`, `one`, `two`,
+    /// `few`, `many`, `other`), omitting those which are not used for
+    /// whole numbers in the language.
+    fn numerus_form_index(&self, language: &LanguageIdentifier, n: u64) -> usize {
+        let rules = PluralRules::create(language.clone(), PluralRuleType::CARDINAL).or_else(|_| {
+            let language_only = LanguageIdentifier::from_parts(language.language, None, None, &[]);
+            PluralRules::create(language_only, PluralRuleType::CARDINAL)
+        });
+        let rules = match rules {
+            Ok(rules) => rules,
+            Err(_) => return usize::from(n != 1),
+        };
+
+        if !self.plural_categories.read().contains_key(language) {
+            // Plural rules which depend on larger numbers are rare
+            // enough to not affect the order of the categories.
+            let mut categories: Vec<usize> = (0..=200u64)
+                .filter_map(|n| rules.select(n).ok().map(category_order))
+                .collect();
+            categories.sort_unstable();
+            categories.dedup();
+            self.plural_categories
+                .write()
+                .insert(language.clone(), categories);
+        }
+
+        let category = rules.select(n).map_or(5, category_order);
+        self.plural_categories
+            .read()
+            .get(language)
+            .and_then(|categories| categories.iter().position(|c| *c == category))
+            .unwrap_or(0)
+    }
+}
+
+fn category_order(category: PluralCategory) -> usize {
+    match category {
+        PluralCategory::ZERO => 0,
+        PluralCategory::ONE => 1,
+        PluralCategory::TWO => 2,
+        PluralCategory::FEW => 3,
+        PluralCategory::MANY => 4,
+        PluralCategory::OTHER => 5,
+    }
+}
+
+/// Parse the messages of the Qt Linguist `.ts` file at `path`.
+fn parse_ts_file(
+    path: &str,
+    file: &[u8],
+) -> Result<HashMap<MessageKey, Vec<String>>, I18nEmbedError> {
+    let source = String::from_utf8(file.to_vec())
+        .map_err(|error| I18nEmbedError::ErrorParsingFileUtf8(path.to_string(), error))?;
+    // The `.ts` files written by Qt Linguist have a `<!DOCTYPE TS>`.
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..roxmltree::ParsingOptions::default()
+    };
+    let document = roxmltree::Document::parse_with_options(&source, options)
+        .map_err(|error| I18nEmbedError::ErrorParsingQtLinguist(path.to_string(), error))?;
+
+    let mut messages = HashMap::new();
+    for context in document
+        .root_element()
+        .children()
+        .filter(|node| node.has_tag_name("context"))
+    {
+        let context_name = child_text(context, "name").unwrap_or_default();
+        for message in context
+            .children()
+            .filter(|node| node.has_tag_name("message"))
+        {
+            let source = match child_text(message, "source") {
+                Some(source) => source,
+                None => continue,
+            };
+            let translation = match message
+                .children()
+                .find(|node| node.has_tag_name("translation"))
+            {
+                Some(translation) => translation,
+                None => continue,
+            };
+            if matches!(
+                translation.attribute("type"),
+                Some("unfinished" | "obsolete" | "vanished")
+            ) {
+                continue;
+            }
+
+            let numerus_forms: Vec<String> = translation
+                .children()
+                .filter(|node| node.has_tag_name("numerusform"))
+                .map(|node| node_text(node))
+                .collect();
+            let forms = if numerus_forms.is_empty() {
+                vec![node_text(translation)]
+            } else {
+                numerus_forms
+            };
+            if forms.iter().all(String::is_empty) {
+                continue;
+            }
+
+            let key = MessageKey {
+                context: context_name.clone(),
+                source,
+                disambiguation: child_text(message, "comment"),
+            };
+            messages.insert(key, forms);
+        }
+    }
+    Ok(messages)
+}
+
+/// The text of the first child element of `node` with the `tag_name`.
+fn child_text(node: roxmltree::Node<'_, '_>, tag_name: &str) -> Option<String> {
+    node.children()
+        .find(|child| child.has_tag_name(tag_name))
+        .map(node_text)
+}
+
+/// The text of all the text nodes within the `node`.
+fn node_text(node: roxmltree::Node<'_, '_>) -> String {
+    node.descendants()
+        .filter(|descendant| descendant.is_text())
+        .filter_map(|descendant| descendant.text())
+        .collect()
+}
+
+impl LanguageLoader for QtLanguageLoader {
+    /// The fallback language, which is the language of the source
+    /// texts in the code.
+    fn fallback_language(&self) -> &LanguageIdentifier {
+        &self.fallback_language
+    }
+
+    /// The domain for the translation that this loader is associated with.
+    fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// The language file name to use for this loader's domain.
+    fn language_file_name(&self) -> String {
+        format!("{}.ts", self.domain)
+    }
+
+    /// Get the language which is currently loaded for this loader.
+    fn current_language(&self) -> LanguageIdentifier {
+        self.tables.current_language()
+    }
+
+    /// Load the languages `language_ids` using the resources packaged
+    /// in the `i18n_assets` in order of fallback preference. This
+    /// also sets the [LanguageLoader::current_language()] to the
+    /// first in the `language_ids` slice. You can use
+    /// [select()](super::select()) to determine which fallbacks are
+    /// actually available for an arbitrary slice of preferences.
+    fn load_languages(
+        &self,
+        i18n_assets: &dyn I18nAssets,
+        language_ids: &[LanguageIdentifier],
+    ) -> Result<(), I18nEmbedError> {
+        self.tables
+            .load(self, i18n_assets, language_ids, false, parse_ts_file)
+    }
+
+    fn reload(&self, i18n_assets: &dyn I18nAssets) -> Result<(), I18nEmbedError> {
+        let language_ids = self.tables.languages();
+        self.load_languages(i18n_assets, &language_ids)
+    }
+}
@@ -0,0 +1,59 @@
+use unic_langid::{subtags::Region, LanguageIdentifier};
+
+/// The region, currency and time zone to localize amounts and dates
+/// with, which may differ from those implied by the language (e.g. a
+/// user reading English in Switzerland, paying in euros). In a server
+/// this is usually specific to each request, so it is stored on the
+/// loader for the request's languages rather than in a thread-local:
+///
+/// ```ignore
+/// let context = RegionContext::new()
+///     .with_region("CH".parse()?)
+///     .with_currency("EUR");
+/// let loader = loader.select_languages(&request_languages).with_region_context(context);
+/// ```
+///
+/// The `fluent::FluentLanguageLoader` makes the context available to
+/// messages, and to the custom functions added to its bundles, as the
+/// `$region`, `$currency` and `$timezone` variables, see
+/// `FluentLanguageLoader::with_region_context()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RegionContext {
+    /// The region, e.g. `CH`.
+    pub region: Option<Region>,
+    /// The ISO 4217 code of the currency, e.g. `EUR`.
+    pub currency: Option<String>,
+    /// The IANA time zone, e.g. `Europe/Zurich`.
+    pub timezone: Option<String>,
+}
+
+impl RegionContext {
+    /// Create an empty `RegionContext`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the [RegionContext::region].
+    pub fn with_region(mut self, region: Region) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    /// Set the [RegionContext::currency].
+    pub fn with_currency(mut self, currency: impl Into<String>) -> Self {
+        self.currency = Some(currency.into());
+        self
+    }
+
+    /// Set the [RegionContext::timezone].
+    pub fn with_timezone(mut self, timezone: impl Into<String>) -> Self {
+        self.timezone = Some(timezone.into());
+        self
+    }
+
+    /// The [RegionContext::region], or otherwise the region of the
+    /// `language` (if it has one).
+    pub fn region_or(&self, language: &LanguageIdentifier) -> Option<Region> {
+        self.region.or(language.region)
+    }
+}
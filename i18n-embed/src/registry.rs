@@ -0,0 +1,202 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, OnceLock, RwLock, Weak},
+};
+
+use unic_langid::LanguageIdentifier;
+
+use crate::{I18nEmbedError, Localizer};
+
+type SharedLocalizer = Arc<dyn Localizer + Send + Sync + 'static>;
+
+#[derive(Default)]
+struct RegistryInner {
+    localizers: HashMap<usize, SharedLocalizer>,
+    next_id: usize,
+    /// The languages most recently selected for the registry, which
+    /// are selected for new localizers when they are registered.
+    requested_languages: Option<Vec<LanguageIdentifier>>,
+}
+
+/// A registry of [Localizer]s which can be added and removed while
+/// the application is running, such as those of dynamically loaded
+/// plugins. The registry can be added to the application's
+/// [LanguageRequester](crate::LanguageRequester) using
+/// [LanguageRequester::add_registry()](crate::LanguageRequester::add_registry()),
+/// which will then select the requested languages for all the
+/// localizers in the registry.
+///
+/// The registry is cheap to clone, and each clone refers to the same
+/// localizers. The application can either pass its registry to the
+/// plugins, or use the global registry [LocalizationRegistry::global()].
+/// Keep in mind that a plugin in a dynamic library which links its
+/// own copy of this crate will also have its own global registry.
+///
+/// ```ignore
+/// // In the application.
+/// let registry = LocalizationRegistry::global();
+/// let mut requester = DesktopLanguageRequester::new();
+/// requester.add_registry(registry.clone());
+/// requester.poll()?;
+///
+/// // In the plugin, when it is loaded. The current languages are
+/// // selected for the localizer immediately.
+/// let registration = registry.register(plugin::localizer())?;
+///
+/// // In the plugin, when it is unloaded.
+/// drop(registration);
+/// ```
+#[derive(Clone, Default)]
+pub struct LocalizationRegistry {
+    inner: Arc<RwLock<RegistryInner>>,
+}
+
+impl std::fmt::Debug for LocalizationRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = self.inner.read().expect("registry lock poisoned");
+        let domains: Vec<&str> = inner
+            .localizers
+            .values()
+            .map(|localizer| localizer.language_loader().domain())
+            .collect();
+        f.debug_struct("LocalizationRegistry")
+            .field("domains", &domains)
+            .field("requested_languages", &inner.requested_languages)
+            .finish()
+    }
+}
+
+impl LocalizationRegistry {
+    /// Create a new empty [LocalizationRegistry].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The global registry.
+    pub fn global() -> &'static LocalizationRegistry {
+        static GLOBAL_REGISTRY: OnceLock<LocalizationRegistry> = OnceLock::new();
+        GLOBAL_REGISTRY.get_or_init(Default::default)
+    }
+
+    /// Register the `localizer`, selecting the languages which were
+    /// most recently selected for this registry (if any). The
+    /// `localizer` remains registered until the returned
+    /// [Registration] is dropped.
+    pub fn register(&self, localizer: SharedLocalizer) -> Result<Registration, I18nEmbedError> {
+        let requested_languages = self
+            .inner
+            .read()
+            .expect("registry lock poisoned")
+            .requested_languages
+            .clone();
+        if let Some(requested_languages) = requested_languages {
+            localizer.select(&requested_languages)?;
+        }
+
+        let mut inner = self.inner.write().expect("registry lock poisoned");
+        let id = inner.next_id;
+        inner.next_id += 1;
+        log::debug!(
+            "Registering the localizer for domain \"{0}\"",
+            localizer.language_loader().domain()
+        );
+        inner.localizers.insert(id, localizer);
+
+        Ok(Registration {
+            registry: Arc::downgrade(&self.inner),
+            id,
+        })
+    }
+
+    /// The number of localizers currently registered.
+    pub fn len(&self) -> usize {
+        self.inner
+            .read()
+            .expect("registry lock poisoned")
+            .localizers
+            .len()
+    }
+
+    /// Whether there are no localizers currently registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn localizers(&self) -> Vec<SharedLocalizer> {
+        self.inner
+            .read()
+            .expect("registry lock poisoned")
+            .localizers
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// Call [Localizer::select()] on each of the registered
+    /// localizers with the `requested_languages`, which are also
+    /// selected for the localizers registered afterwards.
+    pub fn select(&self, requested_languages: &[LanguageIdentifier]) -> Result<(), I18nEmbedError> {
+        self.inner
+            .write()
+            .expect("registry lock poisoned")
+            .requested_languages = Some(requested_languages.to_vec());
+
+        let mut errors: Vec<I18nEmbedError> = self
+            .localizers()
+            .iter()
+            .filter_map(|localizer| localizer.select(requested_languages).err())
+            .collect();
+
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            _ => Err(I18nEmbedError::Multiple(errors)),
+        }
+    }
+
+    /// The languages reported to be available by the registered
+    /// localizers.
+    pub fn available_languages(&self) -> Result<Vec<LanguageIdentifier>, I18nEmbedError> {
+        let mut available_languages = HashSet::new();
+        for localizer in self.localizers() {
+            available_languages.extend(localizer.available_languages()?);
+        }
+        Ok(available_languages.into_iter().collect())
+    }
+
+    /// The languages currently loaded by the registered localizers,
+    /// keyed by the [LanguageLoader::domain()](crate::LanguageLoader::domain()).
+    pub fn current_languages(&self) -> HashMap<String, LanguageIdentifier> {
+        self.localizers()
+            .iter()
+            .map(|localizer| {
+                let loader = localizer.language_loader();
+                (loader.domain().to_string(), loader.current_language())
+            })
+            .collect()
+    }
+}
+
+/// The registration of a [Localizer] in a [LocalizationRegistry],
+/// created by [LocalizationRegistry::register()]. The localizer is
+/// removed from the registry when this is dropped.
+#[must_use = "the localizer is removed from the registry when the registration is dropped"]
+#[derive(Debug)]
+pub struct Registration {
+    registry: Weak<RwLock<RegistryInner>>,
+    id: usize,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        if let Some(registry) = self.registry.upgrade() {
+            let mut inner = registry.write().unwrap_or_else(|error| error.into_inner());
+            if let Some(localizer) = inner.localizers.remove(&self.id) {
+                log::debug!(
+                    "Removed the localizer for domain \"{0}\" from the registry",
+                    localizer.language_loader().domain()
+                );
+            }
+        }
+    }
+}
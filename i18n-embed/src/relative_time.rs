@@ -0,0 +1,127 @@
+use fluent::{bundle::FluentBundle, FluentArgs, FluentResource};
+use intl_memoizer::concurrent::IntlLangMemoizer;
+use std::{sync::OnceLock, time::Duration};
+use unic_langid::LanguageIdentifier;
+
+/// A point in time relative to now, formatted using
+/// [FluentLanguageLoader::format_relative_time()](crate::fluent::FluentLanguageLoader::format_relative_time()).
+///
+/// ⚠️ *This API requires the following crate features to be activated: `fluent-system`.*
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelativeTime {
+    /// A time which occurred the specified duration ago, e.g. "3
+    /// minutes ago".
+    Past(Duration),
+    /// A time which will occur after the specified duration, e.g. "in
+    /// 3 minutes".
+    Future(Duration),
+}
+
+impl From<Duration> for RelativeTime {
+    fn from(duration: Duration) -> Self {
+        Self::Past(duration)
+    }
+}
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+const MONTH: u64 = 30 * DAY;
+const YEAR: u64 = 365 * DAY;
+
+impl RelativeTime {
+    /// The id of the message used to format this relative time, and
+    /// the value for its `$count` argument.
+    pub(crate) fn message(&self) -> (String, u64) {
+        let (duration, past) = match self {
+            Self::Past(duration) => (duration, true),
+            Self::Future(duration) => (duration, false),
+        };
+        let seconds = duration.as_secs();
+        let (unit, count) = match seconds {
+            0 => return ("relative-time-now".to_string(), 0),
+            s if s < MINUTE => ("seconds", s),
+            s if s < HOUR => ("minutes", s / MINUTE),
+            s if s < DAY => ("hours", s / HOUR),
+            s if s < MONTH => ("days", s / DAY),
+            s if s < YEAR => ("months", s / MONTH),
+            s => ("years", s / YEAR),
+        };
+        let message_id = if past {
+            format!("relative-time-{unit}-ago")
+        } else {
+            format!("relative-time-in-{unit}")
+        };
+        (message_id, count)
+    }
+}
+
+/// Languages with built-in relative time messages. English is the
+/// first, and is used for any other language.
+const BUILTIN_RESOURCES: &[(&str, &str)] = &[
+    ("en", include_str!("relative_time/en.ftl")),
+    ("de", include_str!("relative_time/de.ftl")),
+    ("es", include_str!("relative_time/es.ftl")),
+    ("fr", include_str!("relative_time/fr.ftl")),
+    ("ru", include_str!("relative_time/ru.ftl")),
+];
+
+type BuiltinBundle = FluentBundle<FluentResource, IntlLangMemoizer>;
+
+fn builtin_bundles() -> &'static [(LanguageIdentifier, BuiltinBundle)] {
+    static BUNDLES: OnceLock<Vec<(LanguageIdentifier, BuiltinBundle)>> = OnceLock::new();
+    BUNDLES.get_or_init(|| {
+        BUILTIN_RESOURCES
+            .iter()
+            .map(|(language, source)| {
+                let language: LanguageIdentifier = language
+                    .parse()
+                    .expect("built-in language identifiers should be valid");
+                let resource = FluentResource::try_new(source.to_string())
+                    .expect("built-in relative time resources should be valid");
+                let mut bundle = FluentBundle::new_concurrent(vec![language.clone()]);
+                bundle.set_use_isolating(false);
+                bundle
+                    .add_resource(resource)
+                    .expect("built-in relative time resources should not conflict");
+                (language, bundle)
+            })
+            .collect()
+    })
+}
+
+/// Format the built-in message `message_id` for the first of the
+/// `languages` which has built-in relative time messages.
+pub(crate) fn format_builtin(
+    languages: &[LanguageIdentifier],
+    message_id: &str,
+    args: &FluentArgs<'_>,
+) -> String {
+    let bundles = builtin_bundles();
+    let bundle = languages
+        .iter()
+        .find_map(|requested| {
+            bundles
+                .iter()
+                .find(|(language, _)| language.language == requested.language)
+        })
+        .unwrap_or(&bundles[0]);
+
+    let (language, bundle) = bundle;
+    let pattern = bundle
+        .get_message(message_id)
+        .and_then(|message| message.value())
+        .expect("built-in relative time resources should contain all messages");
+    let mut errors = Vec::new();
+    let value = bundle.format_pattern(pattern, Some(args), &mut errors);
+    if !errors.is_empty() {
+        diagnostic!(
+            error,
+            target: "i18n_embed::relative_time",
+            { language = language, message_id = message_id },
+            "Failed to format a message for language \"{}\" and id \"{}\".\nErrors\n{:?}.",
+            language, message_id, errors
+        )
+    }
+    value.into()
+}
@@ -0,0 +1,49 @@
+relative-time-now = jetzt
+relative-time-seconds-ago = { $count ->
+    [one] vor { $count } Sekunde
+   *[other] vor { $count } Sekunden
+}
+relative-time-in-seconds = { $count ->
+    [one] in { $count } Sekunde
+   *[other] in { $count } Sekunden
+}
+relative-time-minutes-ago = { $count ->
+    [one] vor { $count } Minute
+   *[other] vor { $count } Minuten
+}
+relative-time-in-minutes = { $count ->
+    [one] in { $count } Minute
+   *[other] in { $count } Minuten
+}
+relative-time-hours-ago = { $count ->
+    [one] vor { $count } Stunde
+   *[other] vor { $count } Stunden
+}
+relative-time-in-hours = { $count ->
+    [one] in { $count } Stunde
+   *[other] in { $count } Stunden
+}
+relative-time-days-ago = { $count ->
+    [one] vor { $count } Tag
+   *[other] vor { $count } Tagen
+}
+relative-time-in-days = { $count ->
+    [one] in { $count } Tag
+   *[other] in { $count } Tagen
+}
+relative-time-months-ago = { $count ->
+    [one] vor { $count } Monat
+   *[other] vor { $count } Monaten
+}
+relative-time-in-months = { $count ->
+    [one] in { $count } Monat
+   *[other] in { $count } Monaten
+}
+relative-time-years-ago = { $count ->
+    [one] vor { $count } Jahr
+   *[other] vor { $count } Jahren
+}
+relative-time-in-years = { $count ->
+    [one] in { $count } Jahr
+   *[other] in { $count } Jahren
+}
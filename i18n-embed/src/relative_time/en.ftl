@@ -0,0 +1,49 @@
+relative-time-now = now
+relative-time-seconds-ago = { $count ->
+    [one] { $count } second ago
+   *[other] { $count } seconds ago
+}
+relative-time-in-seconds = { $count ->
+    [one] in { $count } second
+   *[other] in { $count } seconds
+}
+relative-time-minutes-ago = { $count ->
+    [one] { $count } minute ago
+   *[other] { $count } minutes ago
+}
+relative-time-in-minutes = { $count ->
+    [one] in { $count } minute
+   *[other] in { $count } minutes
+}
+relative-time-hours-ago = { $count ->
+    [one] { $count } hour ago
+   *[other] { $count } hours ago
+}
+relative-time-in-hours = { $count ->
+    [one] in { $count } hour
+   *[other] in { $count } hours
+}
+relative-time-days-ago = { $count ->
+    [one] { $count } day ago
+   *[other] { $count } days ago
+}
+relative-time-in-days = { $count ->
+    [one] in { $count } day
+   *[other] in { $count } days
+}
+relative-time-months-ago = { $count ->
+    [one] { $count } month ago
+   *[other] { $count } months ago
+}
+relative-time-in-months = { $count ->
+    [one] in { $count } month
+   *[other] in { $count } months
+}
+relative-time-years-ago = { $count ->
+    [one] { $count } year ago
+   *[other] { $count } years ago
+}
+relative-time-in-years = { $count ->
+    [one] in { $count } year
+   *[other] in { $count } years
+}
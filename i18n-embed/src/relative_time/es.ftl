@@ -0,0 +1,49 @@
+relative-time-now = ahora
+relative-time-seconds-ago = { $count ->
+    [one] hace { $count } segundo
+   *[other] hace { $count } segundos
+}
+relative-time-in-seconds = { $count ->
+    [one] dentro de { $count } segundo
+   *[other] dentro de { $count } segundos
+}
+relative-time-minutes-ago = { $count ->
+    [one] hace { $count } minuto
+   *[other] hace { $count } minutos
+}
+relative-time-in-minutes = { $count ->
+    [one] dentro de { $count } minuto
+   *[other] dentro de { $count } minutos
+}
+relative-time-hours-ago = { $count ->
+    [one] hace { $count } hora
+   *[other] hace { $count } horas
+}
+relative-time-in-hours = { $count ->
+    [one] dentro de { $count } hora
+   *[other] dentro de { $count } horas
+}
+relative-time-days-ago = { $count ->
+    [one] hace { $count } día
+   *[other] hace { $count } días
+}
+relative-time-in-days = { $count ->
+    [one] dentro de { $count } día
+   *[other] dentro de { $count } días
+}
+relative-time-months-ago = { $count ->
+    [one] hace { $count } mes
+   *[other] hace { $count } meses
+}
+relative-time-in-months = { $count ->
+    [one] dentro de { $count } mes
+   *[other] dentro de { $count } meses
+}
+relative-time-years-ago = { $count ->
+    [one] hace { $count } año
+   *[other] hace { $count } años
+}
+relative-time-in-years = { $count ->
+    [one] dentro de { $count } año
+   *[other] dentro de { $count } años
+}
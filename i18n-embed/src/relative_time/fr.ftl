@@ -0,0 +1,49 @@
+relative-time-now = maintenant
+relative-time-seconds-ago = { $count ->
+    [one] il y a { $count } seconde
+   *[other] il y a { $count } secondes
+}
+relative-time-in-seconds = { $count ->
+    [one] dans { $count } seconde
+   *[other] dans { $count } secondes
+}
+relative-time-minutes-ago = { $count ->
+    [one] il y a { $count } minute
+   *[other] il y a { $count } minutes
+}
+relative-time-in-minutes = { $count ->
+    [one] dans { $count } minute
+   *[other] dans { $count } minutes
+}
+relative-time-hours-ago = { $count ->
+    [one] il y a { $count } heure
+   *[other] il y a { $count } heures
+}
+relative-time-in-hours = { $count ->
+    [one] dans { $count } heure
+   *[other] dans { $count } heures
+}
+relative-time-days-ago = { $count ->
+    [one] il y a { $count } jour
+   *[other] il y a { $count } jours
+}
+relative-time-in-days = { $count ->
+    [one] dans { $count } jour
+   *[other] dans { $count } jours
+}
+relative-time-months-ago = { $count ->
+    [one] il y a { $count } mois
+   *[other] il y a { $count } mois
+}
+relative-time-in-months = { $count ->
+    [one] dans { $count } mois
+   *[other] dans { $count } mois
+}
+relative-time-years-ago = { $count ->
+    [one] il y a { $count } an
+   *[other] il y a { $count } ans
+}
+relative-time-in-years = { $count ->
+    [one] dans { $count } an
+   *[other] dans { $count } ans
+}
@@ -0,0 +1,73 @@
+relative-time-now = сейчас
+relative-time-seconds-ago = { $count ->
+    [one] { $count } секунду назад
+    [few] { $count } секунды назад
+    [many] { $count } секунд назад
+   *[other] { $count } секунды назад
+}
+relative-time-in-seconds = { $count ->
+    [one] через { $count } секунду
+    [few] через { $count } секунды
+    [many] через { $count } секунд
+   *[other] через { $count } секунды
+}
+relative-time-minutes-ago = { $count ->
+    [one] { $count } минуту назад
+    [few] { $count } минуты назад
+    [many] { $count } минут назад
+   *[other] { $count } минуты назад
+}
+relative-time-in-minutes = { $count ->
+    [one] через { $count } минуту
+    [few] через { $count } минуты
+    [many] через { $count } минут
+   *[other] через { $count } минуты
+}
+relative-time-hours-ago = { $count ->
+    [one] { $count } час назад
+    [few] { $count } часа назад
+    [many] { $count } часов назад
+   *[other] { $count } часа назад
+}
+relative-time-in-hours = { $count ->
+    [one] через { $count } час
+    [few] через { $count } часа
+    [many] через { $count } часов
+   *[other] через { $count } часа
+}
+relative-time-days-ago = { $count ->
+    [one] { $count } день назад
+    [few] { $count } дня назад
+    [many] { $count } дней назад
+   *[other] { $count } дня назад
+}
+relative-time-in-days = { $count ->
+    [one] через { $count } день
+    [few] через { $count } дня
+    [many] через { $count } дней
+   *[other] через { $count } дня
+}
+relative-time-months-ago = { $count ->
+    [one] { $count } месяц назад
+    [few] { $count } месяца назад
+    [many] { $count } месяцев назад
+   *[other] { $count } месяца назад
+}
+relative-time-in-months = { $count ->
+    [one] через { $count } месяц
+    [few] через { $count } месяца
+    [many] через { $count } месяцев
+   *[other] через { $count } месяца
+}
+relative-time-years-ago = { $count ->
+    [one] { $count } год назад
+    [few] { $count } года назад
+    [many] { $count } лет назад
+   *[other] { $count } года назад
+}
+relative-time-in-years = { $count ->
+    [one] через { $count } год
+    [few] через { $count } года
+    [many] через { $count } лет
+   *[other] через { $count } года
+}
@@ -1,4 +1,4 @@
-use crate::{I18nEmbedError, Localizer};
+use crate::{I18nEmbedError, LocalizationRegistry, Localizer};
 use std::{collections::HashMap, sync::Weak};
 
 /// A trait used by [I18nAssets](crate::I18nAssets) to ascertain which
@@ -27,6 +27,12 @@ pub trait LanguageRequester<'a> {
     /// you are adding here, you may want to manually call
     /// [#poll()](#poll()) after adding the listener/s.
     fn add_listener_ref(&mut self, listener: &'a dyn Localizer);
+    /// Add a [LocalizationRegistry] to this `LanguageRequester`. When
+    /// the system reports that the currently requested languages has
+    /// changed, [LocalizationRegistry::select()] will be called, which
+    /// calls [Localizer#select()](Localizer#select()) on each of the
+    /// localizers currently in the registry.
+    fn add_registry(&mut self, registry: LocalizationRegistry);
     /// Poll the system's currently selected language, and call
     /// [Localizer#select()](Localizer#select()) on each of the
     /// listeners.
@@ -58,6 +64,7 @@ pub trait LanguageRequester<'a> {
 pub struct LanguageRequesterImpl<'a> {
     arc_listeners: Vec<Weak<dyn Localizer>>,
     ref_listeners: Vec<&'a dyn Localizer>,
+    registries: Vec<LocalizationRegistry>,
     language_override: Option<unic_langid::LanguageIdentifier>,
 }
 
@@ -67,6 +74,7 @@ impl<'a> LanguageRequesterImpl<'a> {
         LanguageRequesterImpl {
             arc_listeners: Vec::new(),
             ref_listeners: Vec::new(),
+            registries: Vec::new(),
             language_override: None,
         }
     }
@@ -94,6 +102,12 @@ impl<'a> LanguageRequesterImpl<'a> {
         self.ref_listeners.push(listener);
     }
 
+    /// Add a [LocalizationRegistry], whose localizers listen to
+    /// changes to the current language.
+    pub fn add_registry(&mut self, registry: LocalizationRegistry) {
+        self.registries.push(registry);
+    }
+
     /// With the provided `requested_languages` call
     /// [Localizer#select()](Localizer#select()) on each of the
     /// listeners.
@@ -121,6 +135,12 @@ impl<'a> LanguageRequesterImpl<'a> {
             }
         }
 
+        for registry in &self.registries {
+            if let Err(error) = registry.select(&requested_languages) {
+                errors.push(error);
+            }
+        }
+
         if errors.is_empty() {
             Ok(())
         } else if errors.len() == 1 {
@@ -177,6 +197,10 @@ impl<'a> LanguageRequesterImpl<'a> {
                 })
         }
 
+        for registry in &self.registries {
+            available_languages.extend(registry.available_languages()?);
+        }
+
         Ok(available_languages.into_iter().collect())
     }
 
@@ -191,6 +215,10 @@ impl<'a> LanguageRequesterImpl<'a> {
             }
         }
 
+        for registry in &self.registries {
+            current_languages.extend(registry.current_languages());
+        }
+
         current_languages
     }
 }
@@ -214,8 +242,8 @@ impl std::fmt::Debug for LanguageRequesterImpl<'_> {
             .join(", ");
         write!(
             f,
-            "LanguageRequesterImpl(listeners: {}, language_override: {:?})",
-            listeners_debug, self.language_override,
+            "LanguageRequesterImpl(listeners: {}, registries: {:?}, language_override: {:?})",
+            listeners_debug, self.registries, self.language_override,
         )
     }
 }
@@ -246,6 +274,10 @@ impl<'a> LanguageRequester<'a> for DesktopLanguageRequester<'a> {
         self.implementation.add_listener_ref(listener)
     }
 
+    fn add_registry(&mut self, registry: LocalizationRegistry) {
+        self.implementation.add_registry(registry)
+    }
+
     fn set_language_override(
         &mut self,
         language_override: Option<unic_langid::LanguageIdentifier>,
@@ -364,6 +396,10 @@ impl<'a> LanguageRequester<'a> for WebLanguageRequester<'a> {
         self.implementation.add_listener_ref(listener)
     }
 
+    fn add_registry(&mut self, registry: LocalizationRegistry) {
+        self.implementation.add_registry(registry)
+    }
+
     fn poll(&mut self) -> Result<(), I18nEmbedError> {
         self.implementation.poll(self.requested_languages())
     }
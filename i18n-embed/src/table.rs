@@ -0,0 +1,149 @@
+//! The loaded translations shared by the loaders for the localization
+//! systems with simple key-value resources, such as `.properties`,
+//! `.csv` and Qt Linguist `.ts` files.
+
+use std::{borrow::Borrow, collections::HashMap, hash::Hash};
+
+use parking_lot::RwLock;
+use unic_langid::LanguageIdentifier;
+
+#[cfg(any(feature = "properties-system", feature = "qt-system"))]
+use crate::{I18nAssets, I18nEmbedError, LanguageLoader};
+
+/// The translations loaded for a single language.
+#[derive(Debug)]
+struct LanguageTable<K, V> {
+    language: LanguageIdentifier,
+    entries: HashMap<K, V>,
+}
+
+/// The translations loaded for each of the selected languages, in
+/// order of fallback preference.
+#[derive(Debug)]
+pub(crate) struct LanguageTables<K, V> {
+    tables: RwLock<Vec<LanguageTable<K, V>>>,
+    /// The languages which were requested to be loaded, followed by
+    /// the fallback language.
+    languages: RwLock<Vec<LanguageIdentifier>>,
+}
+
+impl<K: Eq + Hash, V> LanguageTables<K, V> {
+    pub(crate) fn new(fallback_language: &LanguageIdentifier) -> Self {
+        Self {
+            tables: RwLock::new(Vec::new()),
+            languages: RwLock::new(vec![fallback_language.clone()]),
+        }
+    }
+
+    pub(crate) fn current_language(&self) -> LanguageIdentifier {
+        self.languages.read()[0].clone()
+    }
+
+    /// The languages which were requested to be loaded, followed by
+    /// the fallback language, in order of fallback preference.
+    pub(crate) fn languages(&self) -> Vec<LanguageIdentifier> {
+        self.languages.read().clone()
+    }
+
+    /// Load the `language_ids` followed by the fallback language of
+    /// the `loader`, parsing each of the language files using
+    /// `parse`. The entries of files earlier in the list of language
+    /// files take precedence.
+    ///
+    /// When `fallback_required` is `true`, it is an error if there
+    /// are no files for the fallback language, otherwise the fallback
+    /// language is expected to be in the source code.
+    #[cfg(any(feature = "properties-system", feature = "qt-system"))]
+    pub(crate) fn load(
+        &self,
+        loader: &dyn LanguageLoader,
+        i18n_assets: &dyn I18nAssets,
+        language_ids: &[LanguageIdentifier],
+        fallback_required: bool,
+        parse: impl Fn(&str, &[u8]) -> Result<HashMap<K, V>, I18nEmbedError>,
+    ) -> Result<(), I18nEmbedError> {
+        if language_ids.is_empty() {
+            return Err(I18nEmbedError::RequestedLanguagesEmpty);
+        }
+
+        let mut load_language_ids = language_ids.to_vec();
+        if !load_language_ids.contains(loader.fallback_language()) {
+            load_language_ids.push(loader.fallback_language().clone());
+        }
+
+        let mut tables = Vec::new();
+        for language in &load_language_ids {
+            let (path, files) = loader.language_files(language, i18n_assets);
+            if files.is_empty() {
+                log::debug!(
+                    "Unable to find language file: \"{0}\" for language: \"{1}\"",
+                    path,
+                    language
+                );
+                if fallback_required && language == loader.fallback_language() {
+                    return Err(I18nEmbedError::LanguageNotAvailable(path, language.clone()));
+                }
+                continue;
+            }
+
+            let mut entries = HashMap::new();
+            for file in files {
+                for (key, value) in parse(&path, &file)? {
+                    entries.entry(key).or_insert(value);
+                }
+            }
+            log::debug!(
+                "Loaded language file: \"{0}\" for language: \"{1}\"",
+                path,
+                language
+            );
+            tables.push((language.clone(), entries));
+        }
+
+        self.set(load_language_ids, tables);
+        Ok(())
+    }
+
+    /// Replace the loaded `languages` (the requested languages
+    /// followed by the fallback language) and their `tables`, in
+    /// order of fallback preference.
+    pub(crate) fn set(
+        &self,
+        languages: Vec<LanguageIdentifier>,
+        tables: Vec<(LanguageIdentifier, HashMap<K, V>)>,
+    ) {
+        *self.tables.write() = tables
+            .into_iter()
+            .map(|(language, entries)| LanguageTable { language, entries })
+            .collect();
+        *self.languages.write() = languages;
+    }
+
+    /// Look up the `key` in each of the loaded languages in order of
+    /// fallback preference, calling `f` with the first language which
+    /// has the `key`, and its value.
+    pub(crate) fn lookup<Q, R>(
+        &self,
+        key: &Q,
+        f: impl FnOnce(&LanguageIdentifier, &V) -> R,
+    ) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.tables
+            .read()
+            .iter()
+            .find_map(|table| table.entries.get(key).map(|value| (&table.language, value)))
+            .map(|(language, value)| f(language, value))
+    }
+
+    /// Whether the `key` is available in any of the loaded languages.
+    pub(crate) fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.lookup(key, |_, _| ()).is_some()
+    }
+}
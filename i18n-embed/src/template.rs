@@ -0,0 +1,180 @@
+use std::{borrow::Cow, collections::BTreeSet};
+
+use fluent::FluentArgs;
+use fluent_syntax::ast::{CallArguments, Expression, InlineExpression, Pattern, PatternElement};
+
+use crate::{fluent::FluentLanguageLoader, I18nEmbedError};
+
+/// The id of the attribute containing the subject of a template.
+const SUBJECT_ATTRIBUTE: &str = "subject";
+/// The id of the attribute containing the body of a template.
+const BODY_ATTRIBUTE: &str = "body";
+
+/// A helper for localizing an email or notification, which by
+/// convention is a fluent message with a `.subject` and a `.body`
+/// attribute:
+///
+/// ```fluent
+/// welcome-email =
+///     .subject = Welcome, { $name }!
+///     .body = Hi { $name }, thanks for signing up.
+/// ```
+///
+/// Created using [FluentLanguageLoader::template()]. The arguments
+/// referenced by the subject and body are checked before the
+/// template is rendered, so a missing argument is reported as an
+/// error instead of appearing in the output.
+///
+/// ⚠️ *This API requires the following crate features to be activated: `fluent-system`.*
+#[derive(Debug, Clone)]
+pub struct LocalizedTemplate<'a> {
+    loader: &'a FluentLanguageLoader,
+    message_id: Cow<'a, str>,
+}
+
+/// The subject and body of a [LocalizedTemplate], rendered using
+/// [LocalizedTemplate::render()].
+///
+/// ⚠️ *This API requires the following crate features to be activated: `fluent-system`.*
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedTemplate {
+    /// The rendered `.subject` attribute.
+    pub subject: String,
+    /// The rendered `.body` attribute.
+    pub body: String,
+}
+
+impl<'a> LocalizedTemplate<'a> {
+    pub(crate) fn new(loader: &'a FluentLanguageLoader, message_id: Cow<'a, str>) -> Self {
+        Self { loader, message_id }
+    }
+
+    /// The id of the message containing this template.
+    pub fn message_id(&self) -> &str {
+        &self.message_id
+    }
+
+    /// The names of the arguments referenced by the subject and the
+    /// body of this template, in alphabetical order.
+    ///
+    /// Returns an error if the message, or either of its `.subject`
+    /// and `.body` attributes is not available.
+    pub fn expected_args(&self) -> Result<BTreeSet<String>, I18nEmbedError> {
+        self.loader
+            .with_fluent_message(&self.message_id, |message| {
+                let mut args = Vec::new();
+                for attribute_id in [SUBJECT_ATTRIBUTE, BODY_ATTRIBUTE] {
+                    let attribute = message.get_attribute(attribute_id).ok_or_else(|| {
+                        I18nEmbedError::TemplateAttributeNotAvailable(
+                            self.message_id.to_string(),
+                            attribute_id.to_string(),
+                        )
+                    })?;
+                    args_from_pattern(attribute.value(), &mut args);
+                }
+                Ok(args.into_iter().map(ToString::to_string).collect())
+            })
+            .unwrap_or_else(|| {
+                Err(I18nEmbedError::MessageNotAvailable(
+                    self.message_id.to_string(),
+                ))
+            })
+    }
+
+    /// Check that the `args` contain all the arguments referenced by
+    /// the subject and the body of this template. Additional
+    /// arguments are permitted.
+    pub fn check_args(&self, args: Option<&FluentArgs<'_>>) -> Result<(), I18nEmbedError> {
+        let missing: Vec<String> = self
+            .expected_args()?
+            .into_iter()
+            .filter(|name| {
+                !args.is_some_and(|args| args.iter().any(|(arg_name, _)| arg_name == name.as_str()))
+            })
+            .collect();
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(I18nEmbedError::TemplateArgumentsMissing(
+                self.message_id.to_string(),
+                missing,
+            ))
+        }
+    }
+
+    /// Render the subject and the body of this template with the
+    /// specified `args`, after checking them using
+    /// [LocalizedTemplate::check_args()].
+    pub fn render<'args>(
+        &self,
+        args: Option<&'args FluentArgs<'args>>,
+    ) -> Result<RenderedTemplate, I18nEmbedError> {
+        self.check_args(args)?;
+        Ok(RenderedTemplate {
+            subject: self
+                .loader
+                .get_attr_args_fluent(&self.message_id, SUBJECT_ATTRIBUTE, args),
+            body: self
+                .loader
+                .get_attr_args_fluent(&self.message_id, BODY_ATTRIBUTE, args),
+        })
+    }
+}
+
+fn args_from_pattern<'s>(pattern: &Pattern<&'s str>, args: &mut Vec<&'s str>) {
+    pattern.elements.iter().for_each(|element| {
+        if let PatternElement::Placeable { expression } = element {
+            args_from_expression(expression, args)
+        }
+    });
+}
+
+fn args_from_expression<'s>(expr: &Expression<&'s str>, args: &mut Vec<&'s str>) {
+    match expr {
+        Expression::Inline(inline_expr) => {
+            args_from_inline_expression(inline_expr, args);
+        }
+        Expression::Select { selector, variants } => {
+            args_from_inline_expression(selector, args);
+
+            variants.iter().for_each(|variant| {
+                args_from_pattern(&variant.value, args);
+            })
+        }
+    }
+}
+
+fn args_from_inline_expression<'s>(
+    inline_expr: &InlineExpression<&'s str>,
+    args: &mut Vec<&'s str>,
+) {
+    match inline_expr {
+        InlineExpression::FunctionReference {
+            id: _,
+            arguments: call_args,
+        } => {
+            args_from_call_arguments(call_args, args);
+        }
+        InlineExpression::TermReference {
+            id: _,
+            attribute: _,
+            arguments: Some(call_args),
+        } => {
+            args_from_call_arguments(call_args, args);
+        }
+        InlineExpression::VariableReference { id } => args.push(id.name),
+        InlineExpression::Placeable { expression } => args_from_expression(expression, args),
+        _ => {}
+    }
+}
+
+fn args_from_call_arguments<'s>(call_args: &CallArguments<&'s str>, args: &mut Vec<&'s str>) {
+    call_args.positional.iter().for_each(|expr| {
+        args_from_inline_expression(expr, args);
+    });
+
+    call_args.named.iter().for_each(|named_arg| {
+        args_from_inline_expression(&named_arg.value, args);
+    })
+}
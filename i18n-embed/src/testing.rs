@@ -0,0 +1,17 @@
+//! Utilities for testing code which uses the localization loaders,
+//! such as [InMemoryAssets] which provides the localization files
+//! without embedding them.
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use i18n_embed::{testing::InMemoryAssets, I18nAssets};
+//!
+//! let mut files = HashMap::new();
+//! files.insert("en-US/my_crate.ftl".to_string(), "hello = Hello!".to_string());
+//! let assets = InMemoryAssets::from(files);
+//!
+//! assert_eq!(vec!["en-US/my_crate.ftl"], assets.filenames_iter().collect::<Vec<_>>());
+//! assert_eq!(b"hello = Hello!", &*assets.get_files("en-US/my_crate.ftl")[0]);
+//! ```
+
+pub use crate::InMemoryAssets;
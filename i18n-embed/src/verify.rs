@@ -0,0 +1,66 @@
+use std::fmt::Display;
+
+use unic_langid::LanguageIdentifier;
+
+/// A report comparing the languages available in some
+/// [I18nAssets](crate::I18nAssets) with the languages an application
+/// expects to be available, produced by
+/// [LanguageLoader::verify_against()](crate::LanguageLoader::verify_against()).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LanguageVerification {
+    /// Languages which were expected, but have no language files
+    /// available.
+    pub missing: Vec<LanguageIdentifier>,
+    /// Languages which have language files available, but were not
+    /// expected.
+    pub unexpected: Vec<LanguageIdentifier>,
+}
+
+impl LanguageVerification {
+    /// Compare the `available` languages with the `expected` ones.
+    pub(crate) fn new(available: &[LanguageIdentifier], expected: &[LanguageIdentifier]) -> Self {
+        Self {
+            missing: expected
+                .iter()
+                .filter(|language| !available.contains(language))
+                .cloned()
+                .collect(),
+            unexpected: available
+                .iter()
+                .filter(|language| !expected.contains(language))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Returns `true` if there are no missing or unexpected languages.
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.unexpected.is_empty()
+    }
+}
+
+impl Display for LanguageVerification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let join = |languages: &[LanguageIdentifier]| {
+            languages
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        if self.is_ok() {
+            return write!(f, "All of the expected languages are available.");
+        }
+        if !self.missing.is_empty() {
+            write!(f, "Missing languages: {}.", join(&self.missing))?;
+            if !self.unexpected.is_empty() {
+                write!(f, " ")?;
+            }
+        }
+        if !self.unexpected.is_empty() {
+            write!(f, "Unexpected languages: {}.", join(&self.unexpected))?;
+        }
+        Ok(())
+    }
+}
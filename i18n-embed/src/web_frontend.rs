@@ -0,0 +1,194 @@
+//! This module contains the integration with
+//! [Yew](https://yew.rs), for localizing WASM frontend
+//! applications.
+//!
+//! The [LocalizationProvider] component selects the languages
+//! requested by the browser (using the [WebLanguageRequester]), and
+//! provides the [Localization] to its children as a context, which
+//! components obtain using the [use_localization()] hook. The
+//! components which use it are re-rendered when another language is
+//! selected using [Localization::select()]:
+//!
+//! ```ignore
+//! use i18n_embed::web_frontend::{use_localization, LocalizationProvider};
+//! use i18n_embed_fl::fl;
+//! use yew::prelude::*;
+//!
+//! static LOADER: FluentLanguageLoader = static_loader!();
+//! static LOCALIZATIONS: &(dyn I18nAssets + Send + Sync) = &Localizations;
+//!
+//! #[function_component]
+//! fn Greeting() -> Html {
+//!     let localization = use_localization();
+//!     let select_french = {
+//!         let localization = localization.clone();
+//!         Callback::from(move |_| {
+//!             localization.select(&[langid!("fr")]).unwrap();
+//!         })
+//!     };
+//!     html! {
+//!         <button onclick={select_french}>{ fl!(localization, "hello-world") }</button>
+//!     }
+//! }
+//!
+//! #[function_component]
+//! fn App() -> Html {
+//!     html! {
+//!         <LocalizationProvider loader={&LOADER} i18n_assets={LOCALIZATIONS}>
+//!             <Greeting />
+//!         </LocalizationProvider>
+//!     }
+//! }
+//! ```
+//!
+//! ⚠️ *This module requires the following crate features to be activated: `yew`.*
+
+use std::{fmt::Debug, ops::Deref};
+
+use unic_langid::LanguageIdentifier;
+use yew::{
+    function_component, hook, html, use_context, use_state, ContextProvider, Html, Properties,
+    UseStateSetter,
+};
+
+use crate::{fluent::FluentLanguageLoader, I18nAssets, I18nEmbedError, WebLanguageRequester};
+
+/// The localization of an application, provided to its components by
+/// the [LocalizationProvider], see the [module documentation](self).
+/// It dereferences to the [FluentLanguageLoader], so it can be used
+/// with the `fl!()` macro.
+#[derive(Clone)]
+pub struct Localization {
+    loader: &'static FluentLanguageLoader,
+    i18n_assets: &'static (dyn I18nAssets + Send + Sync),
+    language: LanguageIdentifier,
+    set_language: UseStateSetter<LanguageIdentifier>,
+}
+
+impl Localization {
+    /// The loader of the application.
+    pub fn loader(&self) -> &'static FluentLanguageLoader {
+        self.loader
+    }
+
+    /// The language which is currently selected.
+    pub fn language(&self) -> &LanguageIdentifier {
+        &self.language
+    }
+
+    /// The languages which are available to be selected, see
+    /// [LanguageLoader::available_languages()](crate::LanguageLoader::available_languages()).
+    pub fn available_languages(&self) -> Result<Vec<LanguageIdentifier>, I18nEmbedError> {
+        crate::LanguageLoader::available_languages(self.loader, self.i18n_assets)
+    }
+
+    /// Select the `requested_languages` (see [select()](crate::select())),
+    /// and re-render the components which use this localization.
+    pub fn select(
+        &self,
+        requested_languages: &[LanguageIdentifier],
+    ) -> Result<Vec<LanguageIdentifier>, I18nEmbedError> {
+        let languages = crate::select(self.loader, self.i18n_assets, requested_languages)?;
+        self.set_language
+            .set(crate::LanguageLoader::current_language(self.loader));
+        Ok(languages)
+    }
+}
+
+impl PartialEq for Localization {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.loader, other.loader)
+            && self.language == other.language
+            && self.set_language == other.set_language
+    }
+}
+
+impl Debug for Localization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Localization")
+            .field("loader", &self.loader)
+            .field("language", &self.language)
+            .finish()
+    }
+}
+
+impl Deref for Localization {
+    type Target = FluentLanguageLoader;
+
+    fn deref(&self) -> &Self::Target {
+        self.loader
+    }
+}
+
+/// The properties of the [LocalizationProvider].
+#[derive(Properties)]
+pub struct LocalizationProviderProps {
+    /// The loader of the application.
+    pub loader: &'static FluentLanguageLoader,
+    /// The assets containing the languages of the `loader`.
+    pub i18n_assets: &'static (dyn I18nAssets + Send + Sync),
+    /// The languages to select when the provider is created, instead
+    /// of the languages requested by the browser.
+    #[prop_or_default]
+    pub requested_languages: Option<Vec<LanguageIdentifier>>,
+    /// The components which can use the [Localization].
+    #[prop_or_default]
+    pub children: Html,
+}
+
+impl PartialEq for LocalizationProviderProps {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.loader, other.loader)
+            && self.requested_languages == other.requested_languages
+            && self.children == other.children
+    }
+}
+
+impl Debug for LocalizationProviderProps {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalizationProviderProps")
+            .field("loader", &self.loader)
+            .field("requested_languages", &self.requested_languages)
+            .finish()
+    }
+}
+
+/// A component which provides the [Localization] to its children,
+/// after selecting the languages requested by the browser, or the
+/// [LocalizationProviderProps::requested_languages].
+#[function_component(LocalizationProvider)]
+pub fn localization_provider(props: &LocalizationProviderProps) -> Html {
+    let language = use_state(|| {
+        let requested_languages = props
+            .requested_languages
+            .clone()
+            .unwrap_or_else(WebLanguageRequester::requested_languages);
+        if let Err(error) = crate::select(props.loader, props.i18n_assets, &requested_languages) {
+            log::error!("Unable to select the requested languages: {}", error);
+        }
+        crate::LanguageLoader::current_language(props.loader)
+    });
+    let localization = Localization {
+        loader: props.loader,
+        i18n_assets: props.i18n_assets,
+        language: (*language).clone(),
+        set_language: language.setter(),
+    };
+
+    html! {
+        <ContextProvider<Localization> context={localization}>
+            { props.children.clone() }
+        </ContextProvider<Localization>>
+    }
+}
+
+/// Obtain the [Localization] provided by the enclosing
+/// [LocalizationProvider]. The component is re-rendered when another
+/// language is selected.
+///
+/// Panics if the component is not within a [LocalizationProvider].
+#[hook]
+pub fn use_localization() -> Localization {
+    use_context::<Localization>()
+        .expect("use_localization() needs to be used within a LocalizationProvider")
+}
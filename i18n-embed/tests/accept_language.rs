@@ -0,0 +1,37 @@
+use i18n_embed::parse_accept_language;
+
+fn parse(header: &str) -> Vec<String> {
+    parse_accept_language(header)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn orders_by_quality() {
+    assert_eq!(vec!["de-DE", "de", "en"], parse("de-DE,de;q=0.9,en;q=0.8"));
+    assert_eq!(vec!["fr", "en", "de"], parse("en;q=0.5, de;q=0.1, fr"));
+    // Languages with the same quality keep the order of the header.
+    assert_eq!(
+        vec!["ja", "ko", "zh"],
+        parse("ja;q=0.5, ko;q=0.5, zh;q=0.5")
+    );
+}
+
+#[test]
+fn skips_invalid_entries() {
+    assert_eq!(vec!["en-US"], parse("en-US, *;q=0.5"));
+    assert_eq!(vec!["en"], parse("de;q=0, en;q=0.1"));
+    assert_eq!(vec!["en"], parse("not a language, de;q=2, fr;q=x, en"));
+    assert_eq!(vec!["en"], parse(" , ;q=0.5, en;q=1.000"));
+    assert_eq!(vec!["en-US", "de"], parse("en-US, de;q=0.9, en-US;q=0.8"));
+    assert!(parse("").is_empty());
+}
+
+#[test]
+fn normalizes_tags() {
+    assert_eq!(
+        vec!["en-US", "zh-Hant-TW"],
+        parse("EN_us, zh-hant-tw;Q=0.9")
+    );
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "actix-web")]
+
+use actix_web::{cookie::Cookie, dev::Payload, test::TestRequest, web, FromRequest};
+use i18n_embed::{
+    actix_web::{LocalizationConfig, RequestLoader},
+    fluent::FluentLanguageLoader,
+    LanguageLoader,
+};
+use rust_embed::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ftl"]
+struct Localizations;
+
+fn config() -> LocalizationConfig {
+    let loader = FluentLanguageLoader::new("test", "en-US".parse().unwrap());
+    loader.load_available_languages(&Localizations).unwrap();
+    LocalizationConfig::new(loader)
+        .with_query_parameter("lang")
+        .with_cookie("lang")
+}
+
+fn extract(request: TestRequest) -> Result<RequestLoader, actix_web::Error> {
+    RequestLoader::from_request(&request.to_http_request(), &mut Payload::None).into_inner()
+}
+
+#[test]
+fn negotiates_accept_language() {
+    let loader = extract(
+        TestRequest::default()
+            .app_data(config())
+            .insert_header(("Accept-Language", "ru-RU, en;q=0.5")),
+    )
+    .unwrap();
+    assert_eq!("ru", loader.current_language().to_string());
+    assert_eq!("Привет Мир Локализация!", loader.get("hello-world"));
+
+    // The config may also be wrapped in `web::Data`.
+    let loader = extract(TestRequest::default().app_data(web::Data::new(config()))).unwrap();
+    assert_eq!("en-US", loader.current_language().to_string());
+}
+
+#[test]
+fn overrides() {
+    let loader = extract(
+        TestRequest::with_uri("/?lang=en-GB")
+            .app_data(config())
+            .insert_header(("Accept-Language", "ru"))
+            .cookie(Cookie::new("lang", "ru")),
+    )
+    .unwrap();
+    assert_eq!("en-GB", loader.current_language().to_string());
+    assert_eq!(
+        vec!["en-GB", "ru", "ru"],
+        loader
+            .requested_languages()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    );
+
+    let loader = extract(
+        TestRequest::default()
+            .app_data(config())
+            .insert_header(("Accept-Language", "en-GB"))
+            .cookie(Cookie::new("lang", "ru")),
+    )
+    .unwrap();
+    assert_eq!("ru", loader.current_language().to_string());
+}
+
+#[test]
+fn missing_config() {
+    assert!(extract(TestRequest::default()).is_err());
+}
@@ -0,0 +1,103 @@
+#![cfg(all(feature = "archive-assets", feature = "fluent-system"))]
+
+use std::path::PathBuf;
+
+use i18n_embed::{
+    fluent::FluentLanguageLoader, ArchiveAssets, ArchiveFormat, I18nAssets, I18nEmbedError,
+    LanguageLoader,
+};
+use unic_langid::LanguageIdentifier;
+
+const LONG_NAME: &str = "fr/long-long-long-long-long-long-long-long-long-long-long-long-long-long-long-long-long-long-long-long-long-long-long-long-name.ftl";
+
+fn archive_path(file_name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("i18n")
+        .join("archive")
+        .join(file_name)
+}
+
+fn check_archive(assets: &ArchiveAssets) {
+    let mut filenames: Vec<String> = assets.filenames_iter().collect();
+    filenames.sort();
+    pretty_assertions::assert_eq!(
+        vec![
+            "de-DE/test.ftl".to_string(),
+            LONG_NAME.to_string(),
+            "fr/test.ftl".to_string(),
+        ],
+        filenames
+    );
+
+    let files = assets.get_files("de-DE/test.ftl");
+    assert_eq!(1, files.len());
+    assert_eq!(
+        "hello-world = Hallo Welt!\nbye = Tschüss!\n".as_bytes(),
+        &*files[0]
+    );
+    assert_eq!(
+        "hello-world = Bonjour le monde !\n".as_bytes(),
+        &*assets.get_files("fr/test.ftl")[0]
+    );
+    assert_eq!("long = Long\n".as_bytes(), &*assets.get_files(LONG_NAME)[0]);
+    assert!(assets.get_files("es/test.ftl").is_empty());
+    assert!(assets.get_files("fr").is_empty());
+}
+
+#[test]
+fn zip() {
+    let assets = ArchiveAssets::open(archive_path("language-pack.zip")).unwrap();
+    assert_eq!(ArchiveFormat::Zip, assets.format());
+    check_archive(&assets);
+}
+
+#[test]
+fn tar_gz() {
+    let assets = ArchiveAssets::open(archive_path("language-pack.tar.gz")).unwrap();
+    assert_eq!(ArchiveFormat::TarGz, assets.format());
+    check_archive(&assets);
+}
+
+#[test]
+fn load_languages() {
+    let assets = ArchiveAssets::open(archive_path("language-pack.zip")).unwrap();
+    let de: LanguageIdentifier = "de-DE".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", de.clone());
+    loader.load_languages(&assets, &[de]).unwrap();
+    assert_eq!("Hallo Welt!", loader.get("hello-world"));
+}
+
+#[test]
+fn invalid_archive() {
+    let path = archive_path("../mo/README.md");
+    assert!(matches!(
+        ArchiveAssets::open(path.clone()),
+        Err(I18nEmbedError::InvalidArchive(..))
+    ));
+    assert!(matches!(
+        ArchiveAssets::open_with_format(path, ArchiveFormat::Zip),
+        Err(I18nEmbedError::InvalidArchive(..))
+    ));
+    assert!(matches!(
+        ArchiveAssets::open(archive_path("missing.zip")),
+        Err(I18nEmbedError::ArchiveIo(..))
+    ));
+}
+
+#[test]
+fn corrupted_entry() {
+    let mut contents = std::fs::read(archive_path("language-pack.zip")).unwrap();
+    // Change a byte of the stored contents of `fr/test.ftl`.
+    let stored = contents
+        .windows(7)
+        .position(|window| window == b"Bonjour")
+        .unwrap();
+    contents[stored] = b'b';
+    let path =
+        std::env::temp_dir().join(format!("i18n-embed-corrupted-{}.zip", std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+
+    let assets = ArchiveAssets::open(&path).unwrap();
+    assert!(assets.get_files("fr/test.ftl").is_empty());
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,73 @@
+#![cfg(all(feature = "async", feature = "fluent-system"))]
+
+use std::{borrow::Cow, sync::Mutex};
+
+use async_trait::async_trait;
+use i18n_embed::{
+    fluent::FluentLanguageLoader, load_languages_async, select_async, AsyncI18nAssets, I18nAssets,
+    LanguageLoader, SyncAssets,
+};
+use rust_embed::RustEmbed;
+use unic_langid::LanguageIdentifier;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ftl"]
+struct Localizations;
+
+/// Assets which record the paths of the files which were fetched.
+#[derive(Default)]
+struct RecordingAssets {
+    fetched: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl AsyncI18nAssets for RecordingAssets {
+    async fn get_files(&self, file_path: &str) -> Vec<Cow<'_, [u8]>> {
+        self.fetched.lock().unwrap().push(file_path.to_string());
+        Localizations.get_files(file_path)
+    }
+
+    async fn filenames(&self) -> Vec<String> {
+        Localizations.filenames_iter().collect()
+    }
+}
+
+#[tokio::test]
+async fn load_languages() {
+    let loader = FluentLanguageLoader::new("test", "en-US".parse().unwrap());
+    let ru: LanguageIdentifier = "ru".parse().unwrap();
+    let assets = RecordingAssets::default();
+    load_languages_async(&loader, &assets, std::slice::from_ref(&ru))
+        .await
+        .unwrap();
+
+    assert_eq!(ru, loader.current_language());
+    assert_eq!("Привет Мир Локализация!", loader.get("hello-world"));
+    assert_eq!("only US", loader.get("only-us"));
+
+    let fetched = assets.fetched.lock().unwrap();
+    assert!(fetched.iter().any(|path| path == "ru/test.ftl"));
+    assert!(fetched.iter().any(|path| path == "en-US/test.ftl"));
+    assert!(
+        !fetched.iter().any(|path| path.starts_with("en-GB/")),
+        "{:?}",
+        fetched
+    );
+}
+
+#[tokio::test]
+async fn select() {
+    let loader = FluentLanguageLoader::new("test", "en-US".parse().unwrap());
+    let en_gb: LanguageIdentifier = "en-GB".parse().unwrap();
+    let languages = select_async(
+        &loader,
+        &SyncAssets(Localizations),
+        std::slice::from_ref(&en_gb),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(en_gb, languages[0]);
+    assert_eq!(en_gb, loader.current_language());
+    assert_eq!("Hello World Localisation!", loader.get("hello-world"));
+}
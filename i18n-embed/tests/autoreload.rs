@@ -0,0 +1,99 @@
+#![cfg(feature = "gettext-system")]
+
+mod common;
+
+use common::setup;
+use i18n_embed::{gettext::GettextLanguageLoader, LanguageLoader};
+use rust_embed::RustEmbed;
+use tr::internal::with_translator;
+use unic_langid::LanguageIdentifier;
+
+/// Custom version of the tr! macro function, without the runtime
+/// formatting, with the module set to `i18n_embed` where the
+/// strings were originally extracted from.
+fn tr(msgid: &str) -> String {
+    with_translator("i18n_embed", |t| t.translate(msgid, None).to_string())
+}
+
+#[derive(RustEmbed)]
+#[folder = "i18n/mo"]
+struct Localizations;
+
+/// Assets with a `ru/i18n_embed.mo` file which can be modified,
+/// notifying the subscriber of the changes.
+#[derive(Default)]
+struct EditableAssets {
+    mo: std::sync::RwLock<Option<Vec<u8>>>,
+    changed: std::sync::Mutex<Option<std::sync::Arc<dyn Fn() + Send + Sync>>>,
+}
+
+impl EditableAssets {
+    fn set_mo(&self, mo: Option<Vec<u8>>) {
+        *self.mo.write().unwrap() = mo;
+        if let Some(changed) = &*self.changed.lock().unwrap() {
+            changed();
+        }
+    }
+}
+
+impl i18n_embed::I18nAssets for EditableAssets {
+    fn get_files(&self, file_path: &str) -> Vec<std::borrow::Cow<'_, [u8]>> {
+        match &*self.mo.read().unwrap() {
+            Some(mo) if file_path == "ru/i18n_embed.mo" => vec![mo.clone().into()],
+            _ => Vec::new(),
+        }
+    }
+
+    fn filenames_iter(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new(std::iter::once("ru/i18n_embed.mo".to_string()))
+    }
+
+    fn subscribe_changed(
+        &self,
+        changed: std::sync::Arc<dyn Fn() + Send + Sync + 'static>,
+    ) -> Result<Box<dyn i18n_embed::Watcher + Send + Sync + 'static>, i18n_embed::I18nEmbedError>
+    {
+        *self.changed.lock().unwrap() = Some(changed);
+        Ok(Box::new(()))
+    }
+}
+
+#[test]
+fn autoreload() {
+    setup();
+
+    let ru_mo = Localizations::get("ru/i18n_embed.mo")
+        .unwrap()
+        .data
+        .to_vec();
+    let assets: &'static EditableAssets = Box::leak(Box::default());
+    assets.set_mo(Some(ru_mo.clone()));
+    let loader: &'static GettextLanguageLoader = Box::leak(Box::new(GettextLanguageLoader::new(
+        "i18n_embed",
+        "en".parse().unwrap(),
+    )));
+    let _localizer = i18n_embed::DefaultLocalizer::new(loader, assets)
+        .with_autoreload()
+        .unwrap();
+
+    let ru: LanguageIdentifier = "ru".parse().unwrap();
+    loader
+        .load_languages(assets, std::slice::from_ref(&ru))
+        .unwrap();
+    pretty_assertions::assert_eq!("только ру", tr("only ru"));
+
+    // A file which can't be parsed (e.g. while it is being written)
+    // doesn't replace the loaded catalog.
+    assets.set_mo(Some(b"not a catalog".to_vec()));
+    pretty_assertions::assert_eq!(ru, loader.current_language());
+    pretty_assertions::assert_eq!("только ру", tr("only ru"));
+
+    // The requested language is loaded again once it becomes
+    // available.
+    assets.set_mo(None);
+    pretty_assertions::assert_eq!("en", loader.current_language().to_string());
+    pretty_assertions::assert_eq!("only ru", tr("only ru"));
+    assets.set_mo(Some(ru_mo));
+    pretty_assertions::assert_eq!(ru, loader.current_language());
+    pretty_assertions::assert_eq!("только ру", tr("only ru"));
+}
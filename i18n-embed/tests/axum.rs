@@ -0,0 +1,79 @@
+#![cfg(feature = "axum")]
+
+use std::{
+    convert::Infallible,
+    future::{ready, Ready},
+    task::{Context, Poll},
+};
+
+use http::{header::ACCEPT_LANGUAGE, Request};
+use i18n_embed::{
+    axum::{LocalizationLayer, RequestLoader},
+    fluent::FluentLanguageLoader,
+    LanguageLoader,
+};
+use rust_embed::RustEmbed;
+use tower_layer::Layer;
+use tower_service::Service;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ftl"]
+struct Localizations;
+
+/// A service which responds with the `hello-world` message and the
+/// current language of the [RequestLoader].
+struct Hello;
+
+impl Service<Request<()>> for Hello {
+    type Response = (String, String);
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<()>) -> Self::Future {
+        let loader = request
+            .extensions()
+            .get::<RequestLoader>()
+            .expect("expected the request to have a RequestLoader");
+        ready(Ok((
+            loader.current_language().to_string(),
+            loader.get("hello-world"),
+        )))
+    }
+}
+
+fn hello(accept_language: Option<&str>) -> (String, String) {
+    let loader = FluentLanguageLoader::new("test", "en-US".parse().unwrap());
+    loader.load_available_languages(&Localizations).unwrap();
+    let mut service = LocalizationLayer::new(loader).layer(Hello);
+
+    let mut request = Request::builder();
+    if let Some(accept_language) = accept_language {
+        request = request.header(ACCEPT_LANGUAGE, accept_language);
+    }
+    match service.call(request.body(()).unwrap()).into_inner() {
+        Ok(response) => response,
+        Err(infallible) => match infallible {},
+    }
+}
+
+#[test]
+fn negotiates_accept_language() {
+    assert_eq!(
+        ("ru".to_string(), "Привет Мир Локализация!".to_string()),
+        hello(Some("ru-RU, en;q=0.5"))
+    );
+    assert_eq!("en-GB", hello(Some("en-GB")).0);
+}
+
+#[test]
+fn falls_back() {
+    assert_eq!(
+        ("en-US".to_string(), "Hello World Localization!".to_string()),
+        hello(Some("ja-JP"))
+    );
+    assert_eq!("en-US", hello(None).0);
+}
@@ -0,0 +1,33 @@
+#![cfg(all(feature = "icu-collator", feature = "fluent-system"))]
+
+use i18n_embed::{
+    collator, fluent::FluentLanguageLoader, icu_collator::CollatorOptions, LanguageLoader,
+};
+use unic_langid::LanguageIdentifier;
+
+fn sorted(language: &str, mut words: Vec<&'static str>) -> Vec<&'static str> {
+    let language: LanguageIdentifier = language.parse().unwrap();
+    let collator = collator(&language, CollatorOptions::new()).unwrap();
+    words.sort_by(|a, b| collator.compare(a, b));
+    words
+}
+
+#[test]
+fn collator_swedish() {
+    let words = vec!["ö", "z", "å", "a", "ä"];
+    assert_eq!(vec!["a", "z", "å", "ä", "ö"], sorted("sv", words));
+}
+
+#[test]
+fn collator_english() {
+    let words = vec!["ö", "z", "å", "a", "ä"];
+    assert_eq!(vec!["a", "å", "ä", "ö", "z"], sorted("en-US", words));
+}
+
+#[test]
+fn loader_collator() {
+    let sv: LanguageIdentifier = "sv".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", sv);
+    let collator = loader.collator().unwrap();
+    assert_eq!(std::cmp::Ordering::Greater, collator.compare("ä", "z"));
+}
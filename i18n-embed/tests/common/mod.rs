@@ -0,0 +1,23 @@
+#![allow(dead_code)]
+
+/// Initialize the logger, so that the log output is shown for failing tests.
+pub fn setup() {
+    let _ = env_logger::try_init();
+}
+
+/// Assets containing the pairs of file paths and contents.
+pub struct FileAssets(pub &'static [(&'static str, &'static str)]);
+
+impl i18n_embed::I18nAssets for FileAssets {
+    fn get_files(&self, file_path: &str) -> Vec<std::borrow::Cow<'_, [u8]>> {
+        self.0
+            .iter()
+            .filter(|(path, _)| *path == file_path)
+            .map(|(_, contents)| std::borrow::Cow::Borrowed(contents.as_bytes()))
+            .collect()
+    }
+
+    fn filenames_iter(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new(self.0.iter().map(|(path, _)| path.to_string()))
+    }
+}
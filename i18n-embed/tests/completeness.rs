@@ -0,0 +1,70 @@
+#![cfg(feature = "fluent-system")]
+
+mod common;
+
+use common::{setup, FileAssets};
+use i18n_embed::{fluent::FluentLanguageLoader, LanguageLoader};
+use rust_embed::RustEmbed;
+use unic_langid::LanguageIdentifier;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ftl"]
+struct Localizations;
+
+#[test]
+fn verify_against() {
+    setup();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let en_gb: LanguageIdentifier = "en-GB".parse().unwrap();
+    let ru: LanguageIdentifier = "ru".parse().unwrap();
+    let de: LanguageIdentifier = "de".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+
+    let verification = loader
+        .verify_against(&Localizations, &[en_us.clone(), en_gb.clone(), de.clone()])
+        .unwrap();
+    assert!(!verification.is_ok());
+    pretty_assertions::assert_eq!(vec![de], verification.missing);
+    pretty_assertions::assert_eq!(vec![ru.clone()], verification.unexpected);
+    pretty_assertions::assert_eq!(
+        "Missing languages: de. Unexpected languages: ru.",
+        verification.to_string()
+    );
+
+    let verification = loader
+        .verify_against(&Localizations, &[en_us, en_gb, ru])
+        .unwrap();
+    assert!(verification.is_ok());
+}
+
+#[test]
+fn completeness() {
+    setup();
+    const ASSETS: FileAssets = FileAssets(&[
+        (
+            "en/test.ftl",
+            "hello = Hello\ngoodbye = Goodbye\nyes = Yes\nno = No\n-brand = App\n",
+        ),
+        ("en/test/settings.ftl", "title = Settings\n"),
+        (
+            "es/test.ftl",
+            "hello = Hola\ngoodbye = Adiós\nyes = Sí\nold = Viejo\n",
+        ),
+        ("fr/test.ftl", "hello = Bonjour\n"),
+        ("fr/test/settings.ftl", "title = Paramètres\n"),
+    ]);
+    let en: LanguageIdentifier = "en".parse().unwrap();
+    let es: LanguageIdentifier = "es".parse().unwrap();
+    let fr: LanguageIdentifier = "fr".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en.clone());
+    loader
+        .load_languages(&ASSETS, &[es.clone(), fr.clone()])
+        .unwrap();
+
+    let completeness = loader.completeness();
+    assert_eq!(3, completeness.len());
+    assert_eq!(Some(&1.0), completeness.get(&en));
+    // Messages which are not in the fallback language don't count.
+    assert_eq!(Some(&0.6), completeness.get(&es));
+    assert_eq!(Some(&0.4), completeness.get(&fr));
+}
@@ -0,0 +1,38 @@
+#![cfg(all(feature = "compressed-assets", feature = "fluent-system"))]
+
+use i18n_embed::{
+    fluent::FluentLanguageLoader, CompressedRustEmbedAssets, I18nAssets, LanguageLoader,
+};
+use rust_embed::RustEmbed;
+use unic_langid::LanguageIdentifier;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/compressed"]
+struct Localizations;
+
+#[test]
+fn get_files() {
+    let assets = CompressedRustEmbedAssets::<Localizations>::new();
+    let filenames: Vec<String> = assets.filenames_iter().collect();
+    assert_eq!(vec!["de-DE/test.ftl", "fr/test.ftl"], filenames);
+
+    assert_eq!(
+        b"hello-world = Hallo Welt!\n",
+        &*assets.get_files("de-DE/test.ftl")[0]
+    );
+    assert_eq!(
+        b"hello-world = Bonjour le monde !\n",
+        &*assets.get_files("fr/test.ftl")[0]
+    );
+    assert!(assets.get_files("es/test.ftl").is_empty());
+}
+
+#[test]
+fn load_languages() {
+    let assets = CompressedRustEmbedAssets::<Localizations>::new();
+    let de: LanguageIdentifier = "de-DE".parse().unwrap();
+    let fr: LanguageIdentifier = "fr".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", fr.clone());
+    loader.load_languages(&assets, &[de, fr]).unwrap();
+    assert_eq!("Hallo Welt!", loader.get("hello-world"));
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "csv-system")]
+
+mod common;
+
+use common::setup;
+use i18n_embed::{csv::CsvLanguageLoader, I18nAssets, LanguageLoader};
+use rust_embed::RustEmbed;
+use std::{borrow::Cow, collections::HashMap, sync::RwLock};
+use unic_langid::LanguageIdentifier;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/csv"]
+struct Localizations;
+
+#[test]
+fn available_languages() {
+    setup();
+    let loader = CsvLanguageLoader::new("test", "en".parse().unwrap());
+    let available_languages: Vec<String> = loader
+        .available_languages(&Localizations)
+        .unwrap()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    pretty_assertions::assert_eq!(vec!["en", "de", "de-AT"], available_languages);
+}
+
+#[test]
+fn fallback_chain() {
+    setup();
+    let loader = CsvLanguageLoader::new("test", "en".parse().unwrap());
+    let de_at: LanguageIdentifier = "de-AT".parse().unwrap();
+    let de: LanguageIdentifier = "de".parse().unwrap();
+    loader
+        .load_languages(&Localizations, &[de_at.clone(), de])
+        .unwrap();
+
+    pretty_assertions::assert_eq!(de_at, loader.current_language());
+    let args = HashMap::from([("name", "Welt")]);
+    pretty_assertions::assert_eq!("Servus Welt!", loader.get_args("greeting", &args));
+    pretty_assertions::assert_eq!("Öffnen oder importieren", loader.get("menu.open"));
+    pretty_assertions::assert_eq!("Only in English", loader.get("only.en"));
+    assert!(!loader.has("# notes"));
+    pretty_assertions::assert_eq!("missing", loader.get("missing"));
+
+    loader.load_fallback_language(&Localizations).unwrap();
+    pretty_assertions::assert_eq!("Open, or import", loader.get("menu.open"));
+    pretty_assertions::assert_eq!("Hello {name}!", loader.get("greeting"));
+}
+
+/// Assets containing a single CSV file which can be modified, as
+/// if it had been edited on the disk.
+struct EditableAssets(RwLock<String>);
+
+impl I18nAssets for EditableAssets {
+    fn get_files(&self, file_path: &str) -> Vec<Cow<'_, [u8]>> {
+        if file_path == "test.csv" {
+            vec![Cow::Owned(self.0.read().unwrap().as_bytes().to_vec())]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn filenames_iter(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new(std::iter::once("test.csv".to_string()))
+    }
+}
+
+#[test]
+fn reload() {
+    setup();
+    let assets = EditableAssets(RwLock::new("key,en,fr\nhello,Hello,\n".to_string()));
+    let loader = CsvLanguageLoader::new("test", "en".parse().unwrap());
+    loader
+        .load_languages(&assets, &["fr".parse().unwrap()])
+        .unwrap();
+    pretty_assertions::assert_eq!("Hello", loader.get("hello"));
+
+    *assets.0.write().unwrap() = "key,en,fr\nhello,Hello,Bonjour\n".to_string();
+    loader.reload(&assets).unwrap();
+    pretty_assertions::assert_eq!("fr", loader.current_language().to_string());
+    pretty_assertions::assert_eq!("Bonjour", loader.get("hello"));
+}
+
+#[test]
+fn fallback_required() {
+    setup();
+    let loader = CsvLanguageLoader::new("test", "fr".parse().unwrap());
+    let error = loader
+        .load_languages(&Localizations, &["de".parse().unwrap()])
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        i18n_embed::I18nEmbedError::LanguageNotAvailable(..)
+    ));
+}
@@ -0,0 +1,244 @@
+#![cfg(feature = "fluent-system")]
+
+mod common;
+
+use common::setup;
+use i18n_embed::{
+    fluent::{FluentLanguageLoader, LogPolicy, LookupStats, MissingMessage},
+    LanguageLoader,
+};
+use rust_embed::RustEmbed;
+use unic_langid::LanguageIdentifier;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ftl"]
+struct Localizations;
+
+#[test]
+fn lookup_stats() {
+    setup();
+    let ru: LanguageIdentifier = "ru".parse().unwrap();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    loader
+        .load_languages(&Localizations, &[ru.clone(), en_us.clone()])
+        .unwrap();
+
+    loader.get("only-us");
+    assert_eq!(LookupStats::default(), loader.lookup_stats());
+
+    loader.enable_lookup_tracing(true);
+    loader.get("only-ru");
+    loader.get("only-us");
+    loader.get("only-us");
+    loader.get("does-not-exist");
+    loader.get_attr("hello-world", "does-not-exist");
+    loader
+        .select_languages(std::slice::from_ref(&en_us))
+        .get("only-gb");
+
+    let stats = loader.lookup_stats();
+    assert_eq!(Some(&1), stats.served["only-ru"].get(&ru));
+    assert_eq!(Some(&2), stats.served["only-us"].get(&en_us));
+    assert_eq!(Some(&1), stats.served["only-gb"].get(&en_us));
+    assert_eq!(Some(&1), stats.missing.get("does-not-exist"));
+    assert_eq!(Some(&1), stats.missing.get("hello-world.does-not-exist"));
+
+    let mut fallbacks: Vec<_> = stats.fallbacks(&ru).collect();
+    fallbacks.sort();
+    assert_eq!(
+        vec![("only-gb", &en_us, 1), ("only-us", &en_us, 2)],
+        fallbacks
+    );
+
+    loader.reset_lookup_stats();
+    assert_eq!(LookupStats::default(), loader.lookup_stats());
+}
+
+#[test]
+fn log_policy() {
+    setup();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    loader
+        .load_languages(&Localizations, std::slice::from_ref(&en_us))
+        .unwrap();
+    assert_eq!(LogPolicy::All, loader.log_policy());
+
+    let policy = LogPolicy::Sample(std::num::NonZeroU32::new(100).unwrap());
+    loader.set_log_policy(policy);
+    assert_eq!(policy, loader.log_policy());
+    // The policy is shared with loaders created from this one.
+    let selected = loader.select_languages(std::slice::from_ref(&en_us));
+    assert_eq!(policy, selected.log_policy());
+
+    loader.set_log_policy(LogPolicy::Silent);
+    assert_eq!(LogPolicy::Silent, selected.log_policy());
+    assert_eq!(
+        "No localization for id: \"does-not-exist\"",
+        selected.get("does-not-exist")
+    );
+    assert_eq!(
+        "No localization for id: \"does-not-exist\"",
+        loader.snapshot().get("does-not-exist")
+    );
+}
+
+#[test]
+fn missing_message_counts() {
+    setup();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    loader
+        .load_languages(&Localizations, std::slice::from_ref(&en_us))
+        .unwrap();
+    loader.set_log_policy(LogPolicy::Deduplicate { interval: None });
+
+    for _ in 0..3 {
+        loader.get("does-not-exist");
+    }
+    loader.snapshot().get_attr("hello-world", "does-not-exist");
+    let counts = loader.missing_message_counts();
+    assert_eq!(2, counts.len());
+    assert_eq!(Some(&3), counts["does-not-exist"].get(&en_us));
+    assert_eq!(Some(&1), counts["hello-world.does-not-exist"].get(&en_us));
+
+    // Changing the policy keeps the counts.
+    loader.set_log_policy(LogPolicy::All);
+    assert_eq!(counts, loader.missing_message_counts());
+
+    loader.reset_missing_message_counts();
+    assert!(loader.missing_message_counts().is_empty());
+}
+
+#[test]
+fn missing_message_handler() {
+    setup();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    loader
+        .load_languages(&Localizations, std::slice::from_ref(&en_us))
+        .unwrap();
+
+    loader.set_missing_message_handler(|missing: &MissingMessage<'_>| {
+        assert_eq!("test", missing.domain);
+        match missing.attribute_id {
+            Some(attribute_id) => format!("{}.{attribute_id}", missing.message_id),
+            None => missing.message_id.to_string(),
+        }
+    });
+    assert_eq!("does-not-exist", loader.get("does-not-exist"));
+    assert_eq!("does-not-exist", loader.get_cow("does-not-exist"));
+    assert_eq!(
+        "hello-world.does-not-exist",
+        loader.get_attr("hello-world", "does-not-exist")
+    );
+    // Found messages are unaffected.
+    assert_eq!("Hello World Localization!", loader.get("hello-world"));
+
+    // The handler is shared with loaders created from this one,
+    // and its snapshots.
+    let selected = loader.select_languages(std::slice::from_ref(&en_us));
+    assert_eq!("does-not-exist", selected.get("does-not-exist"));
+    assert_eq!(
+        "hello-world.does-not-exist",
+        loader.snapshot().get_attr("hello-world", "does-not-exist")
+    );
+
+    // Missing messages are still counted.
+    assert_eq!(
+        Some(&3),
+        loader.missing_message_counts()["does-not-exist"].get(&en_us)
+    );
+
+    loader.set_missing_message_handler(|_: &MissingMessage<'_>| String::new());
+    assert_eq!("", selected.get("does-not-exist"));
+
+    loader.reset_missing_message_handler();
+    assert_eq!(
+        "No localization for id: \"does-not-exist\"",
+        loader.get("does-not-exist")
+    );
+}
+
+#[test]
+#[should_panic(expected = "No localization for id: \"does-not-exist\"")]
+fn missing_message_handler_panic() {
+    setup();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    loader.load_languages(&Localizations, &[en_us]).unwrap();
+    loader.set_missing_message_handler(|missing: &MissingMessage<'_>| -> String {
+        panic!("{}", missing.placeholder())
+    });
+    loader.get("does-not-exist");
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn tracing_diagnostics() {
+    use std::sync::{Arc, Mutex};
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
+
+    /// Records the names of the spans, and the fields of the events.
+    #[derive(Default)]
+    struct Recorder {
+        spans: Mutex<Vec<String>>,
+        events: Mutex<Vec<Vec<(String, String)>>>,
+    }
+
+    struct FieldVisitor<'a>(&'a mut Vec<(String, String)>);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .push((field.name().to_string(), format!("{value:?}")));
+        }
+    }
+
+    struct RecordingSubscriber(Arc<Recorder>);
+
+    impl Subscriber for RecordingSubscriber {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+            let mut spans = self.0.spans.lock().unwrap();
+            spans.push(span.metadata().name().to_string());
+            span::Id::from_u64(spans.len() as u64)
+        }
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Vec::new();
+            event.record(&mut FieldVisitor(&mut fields));
+            self.0.events.lock().unwrap().push(fields);
+        }
+        fn enter(&self, _: &span::Id) {}
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    let recorder = Arc::new(Recorder::default());
+    tracing::subscriber::with_default(RecordingSubscriber(recorder.clone()), || {
+        let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+        let loader = FluentLanguageLoader::new("test", en_us.clone());
+        loader.load_languages(&Localizations, &[en_us]).unwrap();
+        loader.get("does-not-exist");
+    });
+
+    assert!(recorder
+        .spans
+        .lock()
+        .unwrap()
+        .contains(&"load_languages".to_string()));
+    let events = recorder.events.lock().unwrap();
+    let missing = events
+        .iter()
+        .find(|fields| fields.contains(&("message_id".to_string(), "does-not-exist".to_string())))
+        .expect("missing message event");
+    assert!(missing.contains(&("domain".to_string(), "test".to_string())));
+    assert!(missing.contains(&("language".to_string(), "en-US".to_string())));
+}
@@ -0,0 +1,89 @@
+#![cfg(all(feature = "egui", feature = "fluent-system"))]
+
+use egui::{
+    epaint::Shape, CentralPanel, Context, Event, Modifiers, PointerButton, Pos2, RawInput, Response,
+};
+use i18n_embed::{
+    egui::LanguageSelector, fluent::FluentLanguageLoader, DefaultLocalizer, LanguageLoader,
+    Localizer,
+};
+use rust_embed::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ftl"]
+struct Localizations;
+
+/// Run a frame of the `ctx` showing a [LanguageSelector], returning
+/// its response and the texts which were painted with their
+/// positions.
+fn run(
+    ctx: &Context,
+    localizer: &DefaultLocalizer<'_>,
+    events: Vec<Event>,
+) -> (Response, Vec<(String, Pos2)>) {
+    let mut response = None;
+    let output = ctx.run(
+        RawInput {
+            events,
+            ..RawInput::default()
+        },
+        |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                response = Some(ui.add(LanguageSelector::new(localizer)));
+            });
+        },
+    );
+
+    let mut painted = Vec::new();
+    for shape in &output.shapes {
+        painted_texts(&shape.shape, &mut painted);
+    }
+    (response.unwrap(), painted)
+}
+
+/// Collect the texts painted by the `shape`, with the positions of
+/// their centers.
+fn painted_texts(shape: &Shape, texts: &mut Vec<(String, Pos2)>) {
+    match shape {
+        Shape::Vec(shapes) => shapes.iter().for_each(|shape| painted_texts(shape, texts)),
+        Shape::Text(text) => texts.push((
+            text.galley.text().to_string(),
+            text.pos + text.galley.rect.center().to_vec2(),
+        )),
+        _ => {}
+    }
+}
+
+fn click(position: Pos2) -> Vec<Event> {
+    let button = |pressed| Event::PointerButton {
+        pos: position,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Modifiers::default(),
+    };
+    vec![Event::PointerMoved(position), button(true), button(false)]
+}
+
+#[test]
+fn select_language() {
+    let loader = FluentLanguageLoader::new("test", "en-US".parse().unwrap());
+    let localizer = DefaultLocalizer::new(&loader, &Localizations);
+    localizer.select(&["en-US".parse().unwrap()]).unwrap();
+
+    let ctx = Context::default();
+    let (response, texts) = run(&ctx, &localizer, Vec::new());
+    assert!(texts.iter().any(|(text, _)| text == "English (en-US)"));
+
+    // Open the drop-down menu.
+    let (response, _) = run(&ctx, &localizer, click(response.rect.center()));
+    assert!(!response.changed());
+    let (_, texts) = run(&ctx, &localizer, Vec::new());
+    let (_, position) = texts
+        .iter()
+        .find(|(text, _)| text == "Русский")
+        .expect("the menu should contain the available languages");
+
+    let (response, _) = run(&ctx, &localizer, click(*position));
+    assert!(response.changed());
+    assert_eq!("ru", loader.current_language().to_string());
+}
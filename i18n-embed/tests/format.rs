@@ -0,0 +1,121 @@
+#![cfg(feature = "fluent-system")]
+
+mod common;
+
+use ::fluent::{FluentArgs, FluentValue};
+use common::setup;
+use i18n_embed::{fluent::FluentLanguageLoader, I18nEmbedError, LanguageLoader};
+use rust_embed::RustEmbed;
+use unic_langid::LanguageIdentifier;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ftl"]
+struct Localizations;
+
+#[test]
+fn format_all() {
+    setup();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let ru: LanguageIdentifier = "ru".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    loader
+        .load_languages(&Localizations, std::slice::from_ref(&en_us))
+        .unwrap();
+    loader.set_use_isolating(false);
+
+    let messages = loader.format_all(&en_us, |message_id| {
+        if message_id == "only-gb-args" {
+            let mut args = FluentArgs::new();
+            args.set("userName", "Tanya");
+            Some(args)
+        } else {
+            None
+        }
+    });
+    let messages: std::collections::HashMap<String, String> = messages.into_iter().collect();
+    assert_eq!(
+        Some("Hello World Localization!"),
+        messages.get("hello-world").map(String::as_str)
+    );
+    assert_eq!(
+        Some("Hello Tanya! (US Version)"),
+        messages.get("only-gb-args").map(String::as_str)
+    );
+    // Messages in namespaces are included.
+    assert_eq!(
+        Some("Settings"),
+        messages.get("settings.title").map(String::as_str)
+    );
+
+    assert!(loader.format_all(&ru, |_| None).is_empty());
+}
+
+#[test]
+fn template() {
+    setup();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    loader.load_languages(&Localizations, &[en_us]).unwrap();
+    loader.set_use_isolating(false);
+
+    let template = loader.template("welcome-email");
+    assert_eq!(
+        vec!["count".to_string(), "name".to_string()],
+        template
+            .expected_args()
+            .unwrap()
+            .into_iter()
+            .collect::<Vec<_>>()
+    );
+
+    let mut args = FluentArgs::new();
+    args.set("name", "Tanya");
+    match template.render(Some(&args)) {
+        Err(I18nEmbedError::TemplateArgumentsMissing(message_id, missing)) => {
+            assert_eq!("welcome-email", message_id);
+            assert_eq!(vec!["count".to_string()], missing);
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    args.set("count", 2);
+    let rendered = template.render(Some(&args)).unwrap();
+    assert_eq!("Welcome, Tanya!", rendered.subject);
+    assert_eq!("Hi Tanya, you have 2 new messages.", rendered.body);
+
+    assert!(matches!(
+        loader.template("with-attr").render(None),
+        Err(I18nEmbedError::TemplateAttributeNotAvailable(_, _))
+    ));
+    assert!(matches!(
+        loader.template("non-existent-message").render(None),
+        Err(I18nEmbedError::MessageNotAvailable(_))
+    ));
+}
+
+#[test]
+fn message_format_placeholders() {
+    setup();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader = FluentLanguageLoader::new("legacy", en_us.clone());
+    loader.enable_message_format_placeholders(true);
+    loader.load_languages(&Localizations, &[en_us]).unwrap();
+    loader.set_use_isolating(false);
+
+    let args = maplit::hashmap! {
+        "name" => FluentValue::from("Tanya"),
+        "amount" => FluentValue::from(2.5),
+    };
+    assert_eq!("Hello Tanya!", loader.get_args("greeting", args.clone()));
+    assert_eq!("About Example", loader.get("about"));
+    assert_eq!("Total: 2.5", loader.get_args("total", args.clone()));
+    assert_eq!("Hello Tanya!", loader.get_args("fluent", args.clone()));
+    // MessageFormat plurals can't be converted.
+    assert!(!loader.has("items"));
+
+    // Without the conversion, the placeholder is a message reference.
+    loader.enable_message_format_placeholders(false);
+    loader.reload(&Localizations).unwrap();
+    loader.set_use_isolating(false);
+    assert_eq!("Hello {name}!", loader.get_args("greeting", args));
+}
@@ -0,0 +1,73 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use i18n_embed::{I18nAssets, InMemoryAssets};
+
+#[test]
+fn notifies_changes() {
+    let assets = InMemoryAssets::new();
+    let changed = Arc::new(AtomicUsize::new(0));
+    let watcher = {
+        let changed = changed.clone();
+        assets
+            .subscribe_changed(Arc::new(move || {
+                changed.fetch_add(1, Ordering::SeqCst);
+            }))
+            .unwrap()
+    };
+
+    assets.insert("fr/test.ftl", "hello-world = Bonjour !\n");
+    assert_eq!(1, changed.load(Ordering::SeqCst));
+    // Clones share the same files.
+    let clone = assets.clone();
+    assert_eq!(
+        b"hello-world = Bonjour !\n",
+        &*clone.get_files("fr/test.ftl")[0]
+    );
+
+    clone.update(|files| {
+        files.insert(
+            "de-DE/test.ftl".to_string(),
+            b"hello-world = Hallo!\n".to_vec(),
+        );
+        files.remove("fr/test.ftl");
+    });
+    assert_eq!(2, changed.load(Ordering::SeqCst));
+    assert_eq!(
+        vec!["de-DE/test.ftl"],
+        assets.filenames_iter().collect::<Vec<_>>()
+    );
+
+    // Removing a missing file doesn't notify the subscribers.
+    assert!(!assets.remove("fr/test.ftl"));
+    assert_eq!(2, changed.load(Ordering::SeqCst));
+
+    // Unsubscribed when the watcher is dropped.
+    drop(watcher);
+    assert!(assets.remove("de-DE/test.ftl"));
+    assert!(assets.get_files("de-DE/test.ftl").is_empty());
+    assert_eq!(2, changed.load(Ordering::SeqCst));
+}
+
+#[cfg(feature = "fluent-system")]
+#[test]
+fn autoreload() {
+    use i18n_embed::{fluent::FluentLanguageLoader, DefaultLocalizer, LanguageLoader};
+    use unic_langid::LanguageIdentifier;
+
+    let assets: &'static InMemoryAssets = Box::leak(Box::default());
+    assets.insert("en/test.ftl", "hello-world = Hello World!\n");
+    let en: LanguageIdentifier = "en".parse().unwrap();
+    let loader: &'static FluentLanguageLoader =
+        Box::leak(Box::new(FluentLanguageLoader::new("test", en.clone())));
+    loader.load_languages(assets, &[en]).unwrap();
+    let _localizer = DefaultLocalizer::new(loader, assets)
+        .with_autoreload()
+        .unwrap();
+    assert_eq!("Hello World!", loader.get("hello-world"));
+
+    assets.insert("en/test.ftl", "hello-world = Hello, downloaded World!\n");
+    assert_eq!("Hello, downloaded World!", loader.get("hello-world"));
+}
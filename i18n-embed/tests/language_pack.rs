@@ -0,0 +1,144 @@
+#![cfg(feature = "language-packs")]
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use i18n_embed::{
+    I18nAssets, I18nEmbedError, LanguagePackManager, LanguagePackManifest, LanguagePackSource,
+    LANGUAGE_PACK_MANIFEST,
+};
+use unic_langid::LanguageIdentifier;
+
+// The SHA-256 digest of "greeting = Hallo!\n".
+const DE_DIGEST: &str = "32cd119fbf3576ad51c4bff780cc1495449ea825a449b75c9838d98028ca31fa";
+
+struct MapSource(HashMap<&'static str, Vec<u8>>);
+
+impl LanguagePackSource for MapSource {
+    fn fetch(&self, path: &str) -> Result<Vec<u8>, I18nEmbedError> {
+        self.0
+            .get(path)
+            .cloned()
+            .ok_or_else(|| I18nEmbedError::LanguagePackFetch(path.to_string(), "not found".into()))
+    }
+}
+
+#[test]
+fn parse_manifest() {
+    let manifest = LanguagePackManifest::parse(&format!(
+        "{DE_DIGEST}  de/test.ftl\n\n{DE_DIGEST} *de/test/extra.ftl\n{DE_DIGEST}  fr/test.ftl\n"
+    ))
+    .unwrap();
+    let de: LanguageIdentifier = "de".parse().unwrap();
+    let fr: LanguageIdentifier = "fr".parse().unwrap();
+    assert_eq!(vec![de.clone(), fr], manifest.languages().unwrap());
+    let de_files: Vec<&str> = manifest
+        .files_for(&de)
+        .map(|file| file.path.as_str())
+        .collect();
+    assert_eq!(vec!["de/test.ftl", "de/test/extra.ftl"], de_files);
+
+    assert!(matches!(
+        LanguagePackManifest::parse("not-a-digest  de/test.ftl"),
+        Err(I18nEmbedError::ErrorParsingLanguagePackManifest(_))
+    ));
+}
+
+#[test]
+fn install_verifies_digests() {
+    let mut files = HashMap::new();
+    files.insert(
+        LANGUAGE_PACK_MANIFEST,
+        format!("{DE_DIGEST}  de/test.ftl\n").into_bytes(),
+    );
+    files.insert("de/test.ftl", b"greeting = Hallo!\n".to_vec());
+    let manager = LanguagePackManager::new(MapSource(files.clone()));
+
+    let assets = manager.assets();
+    let changed = Arc::new(AtomicUsize::new(0));
+    let watcher = {
+        let changed = changed.clone();
+        assets
+            .subscribe_changed(Arc::new(move || {
+                changed.fetch_add(1, Ordering::SeqCst);
+            }))
+            .unwrap()
+    };
+
+    let de: LanguageIdentifier = "de".parse().unwrap();
+    manager.install(&de).unwrap();
+    assert_eq!(vec![de.clone()], manager.installed_languages());
+    assert_eq!(
+        vec![b"greeting = Hallo!\n".to_vec()],
+        assets
+            .get_files("de/test.ftl")
+            .into_iter()
+            .map(|file| file.into_owned())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(1, changed.load(Ordering::SeqCst));
+
+    files.insert("de/test.ftl", b"greeting = Pwned!\n".to_vec());
+    let tampered = LanguagePackManager::new(MapSource(files));
+    assert!(matches!(
+        tampered.install(&de),
+        Err(I18nEmbedError::LanguagePackChecksumMismatch(..))
+    ));
+    assert!(tampered.installed_languages().is_empty());
+
+    // Unsubscribed when the watcher is dropped.
+    drop(watcher);
+    manager.uninstall(&de);
+    assert!(manager.installed_languages().is_empty());
+    assert_eq!(1, changed.load(Ordering::SeqCst));
+}
+
+#[cfg(feature = "language-pack-signatures")]
+#[test]
+fn manifest_signature() {
+    use i18n_embed::LANGUAGE_PACK_SIGNATURE;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    let key_pair = Ed25519KeyPair::from_seed_unchecked(&[7; 32]).unwrap();
+    let public_key = key_pair.public_key().as_ref().to_vec();
+    let manifest = format!("{DE_DIGEST}  de/test.ftl\n").into_bytes();
+
+    let mut files = HashMap::new();
+    files.insert(LANGUAGE_PACK_MANIFEST, manifest.clone());
+    files.insert("de/test.ftl", b"greeting = Hallo!\n".to_vec());
+    let de: LanguageIdentifier = "de".parse().unwrap();
+
+    // The signature is required.
+    let unsigned =
+        LanguagePackManager::new(MapSource(files.clone())).with_public_key(public_key.clone());
+    assert!(matches!(
+        unsigned.install(&de),
+        Err(I18nEmbedError::LanguagePackFetch(ref path, _)) if path == LANGUAGE_PACK_SIGNATURE
+    ));
+
+    files.insert(
+        LANGUAGE_PACK_SIGNATURE,
+        key_pair.sign(&manifest).as_ref().to_vec(),
+    );
+    let signed =
+        LanguagePackManager::new(MapSource(files.clone())).with_public_key(public_key.clone());
+    signed.install(&de).unwrap();
+    assert_eq!(vec![de.clone()], signed.installed_languages());
+
+    // A manifest which was changed after it was signed is rejected.
+    files.insert(
+        LANGUAGE_PACK_MANIFEST,
+        format!("{DE_DIGEST}  de/test.ftl\n{DE_DIGEST}  fr/test.ftl\n").into_bytes(),
+    );
+    let tampered = LanguagePackManager::new(MapSource(files)).with_public_key(public_key);
+    assert!(matches!(
+        tampered.install(&de),
+        Err(I18nEmbedError::LanguagePackSignatureInvalid)
+    ));
+    assert!(tampered.installed_languages().is_empty());
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "fluent-system")]
+
+mod common;
+
+use common::{setup, FileAssets};
+use i18n_embed::fluent::{FluentLanguageLoader, LazyFluentLanguageLoader};
+use unic_langid::LanguageIdentifier;
+
+#[test]
+fn lazy_loader() {
+    setup();
+    const ASSETS: FileAssets = FileAssets(&[
+        ("en/test.ftl", "hello = Hello\ngoodbye = Goodbye\n"),
+        ("de/test.ftl", "hello = Hallo\n"),
+        ("es/test.ftl", "hello = Hola\n"),
+        ("fr/test.ftl", "hello = Bonjour\n"),
+    ]);
+    let en: LanguageIdentifier = "en".parse().unwrap();
+    let de: LanguageIdentifier = "de".parse().unwrap();
+    let es: LanguageIdentifier = "es".parse().unwrap();
+    let fr: LanguageIdentifier = "fr".parse().unwrap();
+    let it: LanguageIdentifier = "it".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en.clone());
+    loader.set_use_isolating(false);
+    let lazy = LazyFluentLanguageLoader::new(loader, ASSETS)
+        .unwrap()
+        .with_capacity(std::num::NonZeroUsize::new(2).unwrap());
+    assert!(lazy.loaded_languages().is_empty());
+
+    assert_eq!("Hello", lazy.get(&en, "hello"));
+    assert!(lazy.loaded_languages().is_empty());
+    assert_eq!("Hallo", lazy.get(&de, "hello"));
+    assert_eq!("Goodbye", lazy.get(&de, "goodbye"));
+    assert_eq!("Hola", lazy.get(&es, "hello"));
+    assert_eq!(vec![de.clone(), es.clone()], lazy.loaded_languages());
+
+    // The least recently used language is evicted.
+    assert_eq!("Hallo", lazy.get(&de, "hello"));
+    assert_eq!("Bonjour", lazy.get(&fr, "hello"));
+    assert_eq!(vec![de.clone(), fr.clone()], lazy.loaded_languages());
+
+    // Languages which are not available use the fallback language.
+    assert_eq!("Hello", lazy.get(&it, "hello"));
+    assert!(lazy.has(&it, "goodbye"));
+    assert_eq!(vec![fr.clone(), it.clone()], lazy.loaded_languages());
+
+    let scope = lazy.scope(&[es.clone(), de.clone()]).unwrap();
+    assert_eq!("Hola", scope.get("hello"));
+    assert_eq!("Goodbye", scope.get("goodbye"));
+    let scope = lazy.scope(&[en.clone(), de.clone()]).unwrap();
+    assert_eq!("Hello", scope.get("hello"));
+    assert_eq!(vec![en.clone(), de.clone()], scope.languages());
+    // Only the languages which are available are selected.
+    let scope = lazy.scope(&[it.clone(), de.clone()]).unwrap();
+    assert_eq!(vec![de.clone(), en.clone()], scope.languages());
+    assert_eq!(de, scope.current_language());
+
+    assert!(lazy.evict(&de));
+    assert!(!lazy.evict(&de));
+    lazy.reload().unwrap();
+    assert!(lazy.loaded_languages().is_empty());
+    assert_eq!("Hallo", lazy.get(&de, "hello"));
+}
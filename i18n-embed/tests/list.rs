@@ -0,0 +1,73 @@
+use i18n_embed::{format_list, ListStyle};
+use unic_langid::LanguageIdentifier;
+
+#[test]
+fn format_list_en() {
+    let en: LanguageIdentifier = "en-US".parse().unwrap();
+    assert_eq!("", format_list::<&str>(&en, &[], ListStyle::And));
+    assert_eq!("a", format_list(&en, &["a"], ListStyle::And));
+    assert_eq!("a and b", format_list(&en, &["a", "b"], ListStyle::And));
+    assert_eq!(
+        "a, b, and c",
+        format_list(&en, &["a", "b", "c"], ListStyle::And)
+    );
+    assert_eq!(
+        "a, b, c, or d",
+        format_list(&en, &["a", "b", "c", "d"], ListStyle::Or)
+    );
+}
+
+#[test]
+fn format_list_de() {
+    let de: LanguageIdentifier = "de-AT".parse().unwrap();
+    assert_eq!("a und b", format_list(&de, &["a", "b"], ListStyle::And));
+    assert_eq!(
+        "a, b und c",
+        format_list(&de, &["a", "b", "c"], ListStyle::And)
+    );
+    assert_eq!(
+        "a, b oder c",
+        format_list(&de, &["a", "b", "c"], ListStyle::Or)
+    );
+}
+
+#[test]
+fn format_list_zh() {
+    let zh: LanguageIdentifier = "zh-CN".parse().unwrap();
+    assert_eq!(
+        "甲、乙和丙",
+        format_list(&zh, &["甲", "乙", "丙"], ListStyle::And)
+    );
+}
+
+#[test]
+fn format_list_unknown_language() {
+    let xx: LanguageIdentifier = "xx".parse().unwrap();
+    let items = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+    assert_eq!("a, b, and c", format_list(&xx, &items, ListStyle::And));
+}
+
+#[cfg(feature = "fluent-system")]
+#[test]
+fn format_list_fluent_arg() {
+    use i18n_embed::{fluent::FluentLanguageLoader, LanguageLoader};
+    use rust_embed::RustEmbed;
+
+    #[derive(RustEmbed)]
+    #[folder = "i18n/ftl"]
+    struct Localizations;
+
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    loader.load_languages(&Localizations, &[en_us]).unwrap();
+    loader.set_use_isolating(false);
+
+    let things = loader.format_list(&["apples", "pears", "plums"], ListStyle::And);
+    let args = maplit::hashmap! {
+        "thing" => things
+    };
+    assert_eq!(
+        "inject a apples, pears, and plums here",
+        loader.get_args("isolation-chars", args)
+    );
+}
@@ -1,13 +1,15 @@
-#[cfg(any(feature = "fluent-system", feature = "gettext-system"))]
-fn setup() {
-    let _ = env_logger::try_init();
-}
+#![allow(clippy::cloned_ref_to_slice_refs)]
+
+mod common;
 
 #[cfg(feature = "fluent-system")]
 mod fluent {
-    use super::setup;
+    use crate::common::{setup, FileAssets};
     use fluent_langneg::NegotiationStrategy;
-    use i18n_embed::{fluent::FluentLanguageLoader, LanguageLoader};
+    use i18n_embed::{
+        fluent::{FluentLanguageLoader, MissingMessage},
+        I18nEmbedError, LanguageLoader, TextDirection,
+    };
     use rust_embed::RustEmbed;
     use unic_langid::LanguageIdentifier;
 
@@ -69,6 +71,75 @@ mod fluent {
         );
     }
 
+    #[test]
+    fn get_or_fallback_skips_intermediate_languages() {
+        setup();
+        let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+        let en_gb: LanguageIdentifier = "en-GB".parse().unwrap();
+        let ru: LanguageIdentifier = "ru".parse().unwrap();
+        let de: LanguageIdentifier = "de".parse().unwrap();
+
+        let loader = FluentLanguageLoader::new("test", en_us.clone());
+        loader.load_available_languages(&Localizations).unwrap();
+        loader.set_missing_message_handler(|missing: &MissingMessage<'_>| {
+            format!("missing {0} {1}", missing.language, missing.message_id)
+        });
+
+        pretty_assertions::assert_eq!(
+            "only GB US (GB)",
+            loader.get_or_fallback(&en_gb, "only-gb-us")
+        );
+        pretty_assertions::assert_eq!(
+            "Привет Мир Локализация!",
+            loader.get_or_fallback(&ru, "hello-world")
+        );
+        // en-GB is loaded, but it is not the fallback language.
+        pretty_assertions::assert_eq!("only GB US (US)", loader.get_or_fallback(&ru, "only-gb-us"));
+        pretty_assertions::assert_eq!(
+            "missing ru no-such-message",
+            loader.get_or_fallback(&ru, "no-such-message")
+        );
+        // A language which isn't loaded uses the fallback language.
+        pretty_assertions::assert_eq!("only US", loader.get_or_fallback(&de, "only-us"));
+
+        pretty_assertions::assert_eq!(
+            "World (US version)!",
+            loader.get_or_fallback_attr(&ru, "with-attr", "attr")
+        );
+        pretty_assertions::assert_eq!(
+            "Привет \u{2068}Tanya\u{2069}!",
+            loader.get_or_fallback_args(
+                &ru,
+                "only-ru-args",
+                maplit::hashmap! { "userName" => "Tanya" }
+            )
+        );
+    }
+
+    #[test]
+    fn load_available_languages_filtered() {
+        setup();
+        let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+        let en_gb: LanguageIdentifier = "en-GB".parse().unwrap();
+        let ru: LanguageIdentifier = "ru".parse().unwrap();
+
+        let loader = FluentLanguageLoader::new("test", en_us.clone());
+        let languages = loader
+            .load_available_languages_filtered(&Localizations, |language| language != &ru)
+            .unwrap();
+        pretty_assertions::assert_eq!(vec![en_gb, en_us.clone()], languages);
+        pretty_assertions::assert_eq!(languages, loader.current_languages());
+        pretty_assertions::assert_eq!("only GB", loader.get("only-gb"));
+
+        // The fallback language is loaded even when it is filtered out.
+        let languages = loader
+            .load_available_languages_filtered(&Localizations, |language| language == &ru)
+            .unwrap();
+        pretty_assertions::assert_eq!(vec![en_us, ru.clone()], languages);
+        pretty_assertions::assert_eq!("only US", loader.get("only-us"));
+        pretty_assertions::assert_eq!("только русский", loader.get_or_fallback(&ru, "only-ru"));
+    }
+
     #[test]
     fn attr() {
         setup();
@@ -122,6 +193,37 @@ mod fluent {
         assert_eq!("inject a thing here", msg);
     }
 
+    #[test]
+    fn bidirectional_isolation_persisted() {
+        setup();
+        let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+        let loader = FluentLanguageLoader::new("test", en_us.clone());
+        assert!(loader.use_isolating());
+        // The setting is applied to the languages loaded afterwards.
+        loader.set_use_isolating(false);
+        assert!(!loader.use_isolating());
+        loader.load_languages(&Localizations, &[en_us]).unwrap();
+        let args = || maplit::hashmap! { "thing" => "thing" };
+        assert_eq!(
+            "inject a thing here",
+            loader.get_args("isolation-chars", args())
+        );
+
+        loader.reload(&Localizations).unwrap();
+        assert_eq!(
+            "inject a thing here",
+            loader.get_args("isolation-chars", args())
+        );
+        let selected = loader.select_languages(&["ru".parse::<LanguageIdentifier>().unwrap()]);
+        assert!(!selected.use_isolating());
+
+        loader.set_use_isolating(true);
+        assert_eq!(
+            "inject a \u{2068}thing\u{2069} here",
+            loader.get_args("isolation-chars", args())
+        );
+    }
+
     #[test]
     fn bidirectional_isolation_on() {
         setup();
@@ -167,6 +269,41 @@ mod fluent {
         );
     }
 
+    #[test]
+    fn get_cow() {
+        use std::borrow::Cow;
+
+        setup();
+        let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+        let loader = FluentLanguageLoader::new("test", en_us.clone());
+        loader.load_languages(&Localizations, &[en_us]).unwrap();
+
+        let hello = loader.get_cow("hello-world");
+        assert!(matches!(hello.as_cow(), Cow::Borrowed(_)));
+        pretty_assertions::assert_eq!("Hello World Localization!", hello);
+        // The text is borrowed from the loaded resource.
+        assert!(std::ptr::eq(
+            hello.as_ref(),
+            loader.get_cow("hello-world").as_ref()
+        ));
+
+        let multi_line = loader.get_cow("multi-line");
+        pretty_assertions::assert_eq!(loader.get("multi-line"), multi_line);
+
+        // Messages with placeables are formatted.
+        let args = loader.get_cow("multi-line-args");
+        assert!(matches!(args.as_cow(), Cow::Owned(_)));
+        pretty_assertions::assert_eq!(loader.get("multi-line-args"), args);
+
+        // The borrowed text outlives the languages it was loaded from.
+        let ru: LanguageIdentifier = "ru".parse().unwrap();
+        loader.load_languages(&Localizations, &[ru]).unwrap();
+        pretty_assertions::assert_eq!("Hello World Localization!", hello);
+        pretty_assertions::assert_eq!("Hello World Localization!", hello.into_owned());
+
+        pretty_assertions::assert_eq!(loader.get("missing"), loader.get_cow("missing"));
+    }
+
     #[test]
     fn multiline_arguments_lf() {
         setup();
@@ -310,6 +447,35 @@ mod fluent {
         assert_eq!("Hello \u{2068}username\u{2069}!", msg);
     }
 
+    #[test]
+    fn scope() {
+        setup();
+        let ru: LanguageIdentifier = "ru".parse().unwrap();
+        let en_gb: LanguageIdentifier = "en-GB".parse().unwrap();
+        let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+        let loader = FluentLanguageLoader::new("test", en_us);
+
+        loader
+            .load_languages(&Localizations, &[ru.clone(), en_gb.clone()])
+            .unwrap();
+        let loaded_languages = loader.current_languages();
+
+        let ru_scope = loader.scope(std::slice::from_ref(&ru));
+        let gb_scope = loader.scope(&[ru.clone(), en_gb.clone()]);
+        assert_eq!(vec![ru.clone()], ru_scope.languages());
+        assert_eq!(vec![ru.clone(), en_gb], gb_scope.languages());
+
+        assert_eq!("only GB (US Version)", ru_scope.get("only-gb"));
+        assert_eq!("only GB", gb_scope.get("only-gb"));
+        assert_eq!("only US", gb_scope.get("only-us"));
+        assert_eq!(ru, ru_scope.current_language());
+        assert!(gb_scope.has("only-gb"));
+        assert!(!gb_scope.has("non-existent-message"));
+
+        // The original loader's language selection is unaffected.
+        assert_eq!(loaded_languages, loader.current_languages());
+    }
+
     #[test]
     fn select_languages_negotiate() {
         setup();
@@ -325,11 +491,131 @@ mod fluent {
             .get("only-gb-us");
         assert_eq!("only GB US (GB)", msg);
     }
+
+    #[test]
+    fn language_aliases() {
+        setup();
+        // Assets named using the conventions of another tool.
+        let assets = FileAssets(&[
+            ("en_US/test.ftl", "hello-world = Hello World!\n"),
+            ("nb/test.ftl", "hello-world = Hei verden!\n"),
+            ("zh-Hans/test.ftl", "hello-world = 你好世界！\n"),
+            ("zh-Hans/test/settings.ftl", "title = 设置\n"),
+        ]);
+        let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+        let nb: LanguageIdentifier = "nb".parse().unwrap();
+        let no: LanguageIdentifier = "no".parse().unwrap();
+        let zh_hans: LanguageIdentifier = "zh-Hans".parse().unwrap();
+        let zh_cn: LanguageIdentifier = "zh-CN".parse().unwrap();
+        let loader = FluentLanguageLoader::new("test", en_us.clone());
+        loader.set_use_isolating(false);
+
+        // `en_US` is found without an alias.
+        loader.load_fallback_language(&assets).unwrap();
+        pretty_assertions::assert_eq!("Hello World!", loader.get("hello-world"));
+        pretty_assertions::assert_eq!(
+            vec![en_us.clone(), nb.clone(), zh_hans.clone()],
+            loader.available_languages(&assets).unwrap()
+        );
+
+        loader.set_language_aliases(
+            vec![(no.clone(), nb.clone()), (zh_cn.clone(), zh_hans.clone())]
+                .into_iter()
+                .collect(),
+        );
+        pretty_assertions::assert_eq!(
+            vec![en_us.clone(), nb, zh_hans, no.clone(), zh_cn.clone()],
+            loader.available_languages(&assets).unwrap()
+        );
+
+        loader
+            .load_languages(&assets, &[zh_cn, en_us.clone()])
+            .unwrap();
+        pretty_assertions::assert_eq!("你好世界！", loader.get("hello-world"));
+        pretty_assertions::assert_eq!("设置", loader.get("settings.title"));
+        loader.load_languages(&assets, &[no, en_us]).unwrap();
+        pretty_assertions::assert_eq!("Hei verden!", loader.get("hello-world"));
+    }
+
+    #[test]
+    fn text_direction() {
+        setup();
+        let assets = FileAssets(&[
+            ("en-US/test.ftl", "hello-world = Hello World!\n"),
+            ("ar/test.ftl", "hello-world = مرحبا بالعالم!\n"),
+        ]);
+        let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+        let ar: LanguageIdentifier = "ar".parse().unwrap();
+        let loader = FluentLanguageLoader::new("test", en_us.clone());
+        loader.load_fallback_language(&assets).unwrap();
+        pretty_assertions::assert_eq!(TextDirection::LeftToRight, loader.text_direction());
+
+        loader.load_languages(&assets, &[ar, en_us]).unwrap();
+        pretty_assertions::assert_eq!(TextDirection::RightToLeft, loader.text_direction());
+        assert_eq!("rtl", loader.text_direction().html_dir());
+    }
+
+    struct BrokenAssets;
+
+    impl i18n_embed::I18nAssets for BrokenAssets {
+        fn get_files(&self, file_path: &str) -> Vec<std::borrow::Cow<'_, [u8]>> {
+            let file: &[u8] = match file_path {
+                "en-US/test.ftl" => b"hello = Hello\n",
+                "fr/test.ftl" => b"hello = Bonjour\nhello = Salut\nbroken = {\n",
+                _ => return Vec::new(),
+            };
+            vec![file.into()]
+        }
+
+        fn filenames_iter(&self) -> Box<dyn Iterator<Item = String> + '_> {
+            Box::new(vec!["en-US/test.ftl".to_string(), "fr/test.ftl".to_string()].into_iter())
+        }
+    }
+
+    #[test]
+    fn load_languages_strict() {
+        setup();
+        let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+        let fr: LanguageIdentifier = "fr".parse().unwrap();
+        let loader = FluentLanguageLoader::new("test", en_us.clone());
+        loader
+            .load_languages_strict(&BrokenAssets, std::slice::from_ref(&en_us))
+            .unwrap();
+
+        let diagnostics =
+            match loader.load_languages_strict(&BrokenAssets, std::slice::from_ref(&fr)) {
+                Err(I18nEmbedError::InvalidResources(diagnostics)) => diagnostics,
+                other => panic!("expected invalid resources, got {:?}", other),
+            };
+        // The languages which were previously loaded are kept.
+        pretty_assertions::assert_eq!(vec![en_us], loader.current_languages());
+        pretty_assertions::assert_eq!(2, diagnostics.len());
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.language == fr && diagnostic.path == "fr/test.ftl"));
+        assert!(matches!(
+            diagnostics[0].error,
+            ::fluent::FluentError::ParserError(_)
+        ));
+        assert!(matches!(
+            &diagnostics[1].error,
+            ::fluent::FluentError::Overriding { id, .. } if id == "hello"
+        ));
+
+        // The valid messages are still loaded when the problems are
+        // only reported.
+        let diagnostics = loader
+            .load_languages_with_diagnostics(&BrokenAssets, std::slice::from_ref(&fr))
+            .unwrap();
+        pretty_assertions::assert_eq!(2, diagnostics.len());
+        pretty_assertions::assert_eq!(vec![fr], loader.current_languages());
+        pretty_assertions::assert_eq!("Bonjour", loader.get("hello"));
+    }
 }
 
 #[cfg(feature = "gettext-system")]
 mod gettext {
-    use super::setup;
+    use crate::common::setup;
     use i18n_embed::{gettext::GettextLanguageLoader, LanguageLoader};
     use rust_embed::RustEmbed;
     use serial_test::serial;
@@ -381,4 +667,60 @@ mod gettext {
         pretty_assertions::assert_eq!("только ру", tr("only ru"));
         pretty_assertions::assert_eq!("only en", tr("only en"));
     }
+
+    #[test]
+    #[serial]
+    fn ngettext() {
+        setup();
+
+        let loader = GettextLanguageLoader::new("i18n_embed", "en".parse().unwrap());
+
+        let ru: LanguageIdentifier = "ru".parse().unwrap();
+        let en: LanguageIdentifier = "en".parse().unwrap();
+
+        loader.load_languages(&Localizations, &[ru]).unwrap();
+        pretty_assertions::assert_eq!(
+            Some(
+                "nplurals=3; plural=(n%10==1 && n%100!=11) ? 0 : ((n%10>=2 && \
+                n%10<=4 && (n%100<10 || n%100>=20)) ? 1 : 2);"
+            ),
+            loader.plural_forms().as_deref()
+        );
+        pretty_assertions::assert_eq!("только ру", loader.gettext("only ru"));
+        pretty_assertions::assert_eq!("{n} файл", loader.ngettext("{n} file", "{n} files", 21));
+        pretty_assertions::assert_eq!("{n} файла", loader.ngettext("{n} file", "{n} files", 3));
+        pretty_assertions::assert_eq!("{n} файлов", loader.ngettext("{n} file", "{n} files", 11));
+
+        loader.load_languages(&Localizations, &[en]).unwrap();
+        pretty_assertions::assert_eq!(None, loader.plural_forms());
+        pretty_assertions::assert_eq!("{n} file", loader.ngettext("{n} file", "{n} files", 1));
+        pretty_assertions::assert_eq!("{n} files", loader.ngettext("{n} file", "{n} files", 3));
+    }
+
+    #[test]
+    #[serial]
+    fn pgettext() {
+        setup();
+
+        let loader = GettextLanguageLoader::new("i18n_embed", "en".parse().unwrap());
+
+        let ru: LanguageIdentifier = "ru".parse().unwrap();
+        let en: LanguageIdentifier = "en".parse().unwrap();
+
+        loader.load_languages(&Localizations, &[ru]).unwrap();
+        pretty_assertions::assert_eq!("Открыть", loader.pgettext("verb", "Open"));
+        pretty_assertions::assert_eq!("Открытый", loader.pgettext("adjective", "Open"));
+        // The messages with a context are distinct from the message without one.
+        pretty_assertions::assert_eq!("Open", loader.gettext("Open"));
+        pretty_assertions::assert_eq!("Open", loader.pgettext("noun", "Open"));
+        pretty_assertions::assert_eq!(
+            "Открыть",
+            with_translator("i18n_embed", |t| t
+                .translate("Open", Some("verb"))
+                .to_string())
+        );
+
+        loader.load_languages(&Localizations, &[en]).unwrap();
+        pretty_assertions::assert_eq!("Open", loader.pgettext("verb", "Open"));
+    }
 }
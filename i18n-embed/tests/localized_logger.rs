@@ -0,0 +1,65 @@
+#![cfg(all(feature = "fluent-system", feature = "localized-logger"))]
+
+mod common;
+
+use common::setup;
+use i18n_embed::{fluent::FluentLanguageLoader, LanguageLoader};
+use rust_embed::RustEmbed;
+use unic_langid::LanguageIdentifier;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ftl"]
+struct Localizations;
+
+#[test]
+fn localized_logger() {
+    use i18n_embed::{LocalizedLogger, LocalizedRecord};
+    use std::sync::{Arc, Mutex};
+
+    setup();
+    let ru: LanguageIdentifier = "ru".parse().unwrap();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader: &'static FluentLanguageLoader =
+        Box::leak(Box::new(FluentLanguageLoader::new("test", en_us)));
+    loader.load_languages(&Localizations, &[ru]).unwrap();
+    loader.set_use_isolating(false);
+
+    let pane: Arc<Mutex<Vec<LocalizedRecord>>> = Arc::default();
+    let sink_pane = pane.clone();
+    let logger = LocalizedLogger::new(loader, move |record| sink_pane.lock().unwrap().push(record));
+
+    let key_values = [("event", "only-ru-args"), ("userName", "Tanya")];
+    let record = log::Record::builder()
+        .level(log::Level::Info)
+        .target("app")
+        .args(format_args!("Hello Tanya!"))
+        .key_values(&key_values)
+        .build();
+    pretty_assertions::assert_eq!(
+        LocalizedRecord {
+            level: log::Level::Info,
+            target: "app".to_string(),
+            event: Some("only-ru-args".to_string()),
+            message: "Привет Tanya!".to_string(),
+        },
+        logger.localize(&record)
+    );
+
+    let key_values = [("event", "does-not-exist")];
+    let record = log::Record::builder()
+        .level(log::Level::Warn)
+        .args(format_args!("Something happened"))
+        .key_values(&key_values)
+        .build();
+    log::Log::log(&logger, &record);
+    let record = log::Record::builder()
+        .level(log::Level::Debug)
+        .args(format_args!("Not for the user"))
+        .build();
+    log::Log::log(&logger, &record);
+
+    let pane = pane.lock().unwrap();
+    pretty_assertions::assert_eq!(1, pane.len());
+    pretty_assertions::assert_eq!(Some("does-not-exist"), pane[0].event.as_deref());
+    pretty_assertions::assert_eq!("Something happened", pane[0].message);
+}
@@ -0,0 +1,70 @@
+#![cfg(all(feature = "fluent-system", feature = "cache"))]
+
+mod common;
+
+use ::fluent::FluentArgs;
+use common::setup;
+use i18n_embed::{fluent::FluentLanguageLoader, LanguageLoader};
+use rust_embed::RustEmbed;
+use unic_langid::LanguageIdentifier;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ftl"]
+struct Localizations;
+
+#[test]
+fn message_cache() {
+    setup();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let ru: LanguageIdentifier = "ru".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    loader
+        .load_languages(&Localizations, std::slice::from_ref(&en_us))
+        .unwrap();
+    loader.enable_lookup_tracing(true);
+
+    let thing = |thing: &str| {
+        let mut args = FluentArgs::new();
+        args.set("thing", thing.to_string());
+        args
+    };
+    pretty_assertions::assert_eq!(
+        "inject a \u{2068}thing\u{2069} here",
+        loader.get_args_fluent("isolation-chars", Some(&thing("thing")))
+    );
+    pretty_assertions::assert_eq!(
+        "inject a \u{2068}thing\u{2069} here",
+        loader.get_args_fluent("isolation-chars", Some(&thing("thing")))
+    );
+    // The arguments are part of the key.
+    pretty_assertions::assert_eq!(
+        "inject a \u{2068}other\u{2069} here",
+        loader.get_args_fluent("isolation-chars", Some(&thing("other")))
+    );
+    // Lookups served by the cache are still traced.
+    pretty_assertions::assert_eq!(
+        Some(&3),
+        loader.lookup_stats().served["isolation-chars"].get(&en_us)
+    );
+
+    // Configuring the bundles clears the cache.
+    loader.set_use_isolating(false);
+    pretty_assertions::assert_eq!(
+        "inject a thing here",
+        loader.get_args_fluent("isolation-chars", Some(&thing("thing")))
+    );
+
+    // Loading languages replaces the cache.
+    pretty_assertions::assert_eq!("Hello World Localization!", loader.get("hello-world"));
+    loader.load_languages(&Localizations, &[ru]).unwrap();
+    pretty_assertions::assert_eq!("Привет Мир Локализация!", loader.get("hello-world"));
+    pretty_assertions::assert_eq!("Привет Мир Локализация!", loader.get_cow("hello-world"));
+
+    // Missing messages are not cached.
+    loader.get("missing");
+    loader.get("missing");
+    pretty_assertions::assert_eq!(Some(&2), loader.lookup_stats().missing.get("missing"));
+
+    loader.clear_message_cache();
+    pretty_assertions::assert_eq!("Привет Мир Локализация!", loader.get("hello-world"));
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "fluent-system")]
+
+mod common;
+
+use common::setup;
+use i18n_embed::{fluent::FluentLanguageLoader, I18nEmbedError, LanguageLoader, MultiDomainLoader};
+use rust_embed::RustEmbed;
+use unic_langid::LanguageIdentifier;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ftl"]
+struct Localizations;
+
+#[test]
+fn multi_domain_loader() {
+    setup();
+    let ru: LanguageIdentifier = "ru".parse().unwrap();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loaders = MultiDomainLoader::new();
+    loaders.add_domain(
+        FluentLanguageLoader::new("test", en_us.clone()),
+        Localizations,
+    );
+    loaders.add_domain(
+        FluentLanguageLoader::new("legacy", en_us.clone()),
+        Localizations,
+    );
+
+    let mut domains = loaders.domains();
+    domains.sort();
+    pretty_assertions::assert_eq!(vec!["legacy", "test"], domains);
+    assert!(matches!(
+        loaders.domain("plugin"),
+        Err(I18nEmbedError::DomainNotAvailable(domain)) if domain == "plugin"
+    ));
+
+    loaders.select(std::slice::from_ref(&ru)).unwrap();
+    assert!(!loaders.is_loaded("test"));
+    let test = loaders.domain("test").unwrap();
+    assert!(loaders.is_loaded("test"));
+    assert!(!loaders.is_loaded("legacy"));
+    pretty_assertions::assert_eq!(ru, test.current_language());
+    pretty_assertions::assert_eq!("только русский", test.get("only-ru"));
+
+    // Loaded domains follow the language selection.
+    loaders.select(std::slice::from_ref(&en_us)).unwrap();
+    pretty_assertions::assert_eq!(en_us, test.current_language());
+    assert!(!loaders.is_loaded("legacy"));
+
+    // `legacy` is only available in en-US.
+    let legacy = loaders.domain("legacy").unwrap();
+    pretty_assertions::assert_eq!(en_us, legacy.current_language());
+    pretty_assertions::assert_eq!("Example", legacy.get("app-name"));
+}
+
+#[test]
+fn localization_registry() {
+    use i18n_embed::{DefaultLocalizer, LanguageRequesterImpl, LocalizationRegistry};
+    use std::sync::Arc;
+
+    setup();
+    let ru: LanguageIdentifier = "ru".parse().unwrap();
+    let en_gb: LanguageIdentifier = "en-GB".parse().unwrap();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader: &'static FluentLanguageLoader =
+        Box::leak(Box::new(FluentLanguageLoader::new("test", en_us.clone())));
+
+    let registry = LocalizationRegistry::new();
+    let mut requester = LanguageRequesterImpl::new();
+    requester.add_registry(registry.clone());
+    requester.poll(vec![ru.clone()]).unwrap();
+    assert!(requester.current_languages().is_empty());
+
+    // The plugin is loaded after the languages were selected.
+    let registration = registry
+        .register(Arc::new(DefaultLocalizer::new(loader, &Localizations)))
+        .unwrap();
+    pretty_assertions::assert_eq!(ru, loader.current_language());
+    pretty_assertions::assert_eq!(1, registry.len());
+
+    requester.poll(vec![en_gb.clone()]).unwrap();
+    pretty_assertions::assert_eq!(en_gb, loader.current_language());
+    pretty_assertions::assert_eq!(Some(&en_gb), requester.current_languages().get("test"));
+    assert!(requester.available_languages().unwrap().contains(&ru));
+
+    // The plugin is unloaded.
+    drop(registration);
+    assert!(registry.is_empty());
+    requester.poll(vec![ru]).unwrap();
+    pretty_assertions::assert_eq!(en_gb, loader.current_language());
+    assert!(requester.current_languages().is_empty());
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "fluent-system")]
+
+mod common;
+
+use common::setup;
+use i18n_embed::{fluent::FluentLanguageLoader, LanguageLoader};
+
+/// Assets containing a single `en-US/test.ftl` file.
+struct SingleFileAssets(&'static str);
+
+impl i18n_embed::I18nAssets for SingleFileAssets {
+    fn get_files(&self, file_path: &str) -> Vec<std::borrow::Cow<'_, [u8]>> {
+        if file_path == "en-US/test.ftl" {
+            vec![self.0.as_bytes().into()]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn filenames_iter(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new(std::iter::once("en-US/test.ftl".to_string()))
+    }
+}
+
+#[test]
+fn multiplexor_merge_policy() {
+    use i18n_embed::{AssetsMultiplexor, I18nAssets, MergePolicy};
+    setup();
+
+    let load = |policy: MergePolicy| {
+        let assets = AssetsMultiplexor::new_with_policy(
+            vec![
+                (
+                    Box::new(SingleFileAssets("hello = Hello\nonly-base = Base\n"))
+                        as Box<dyn I18nAssets + Send + Sync>,
+                    0,
+                ),
+                (Box::new(SingleFileAssets("hello = Hi\n")), 10),
+            ],
+            policy,
+        );
+        let loader = FluentLanguageLoader::new("test", "en-US".parse().unwrap());
+        loader.load_fallback_language(&assets).unwrap();
+        loader
+    };
+
+    let loader = load(MergePolicy::MergeMessages);
+    pretty_assertions::assert_eq!("Hi", loader.get("hello"));
+    pretty_assertions::assert_eq!("Base", loader.get("only-base"));
+
+    let loader = load(MergePolicy::FirstWins);
+    pretty_assertions::assert_eq!("Hi", loader.get("hello"));
+    assert!(!loader.has("only-base"));
+
+    let loader = load(MergePolicy::LastWins);
+    pretty_assertions::assert_eq!("Hello", loader.get("hello"));
+    pretty_assertions::assert_eq!("Base", loader.get("only-base"));
+}
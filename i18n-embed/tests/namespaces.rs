@@ -0,0 +1,129 @@
+#![cfg(feature = "fluent-system")]
+
+mod common;
+
+use common::{setup, FileAssets};
+use i18n_embed::{fluent::FluentLanguageLoader, I18nEmbedError, LanguageLoader, PathTemplate};
+use rust_embed::RustEmbed;
+use unic_langid::LanguageIdentifier;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ftl"]
+struct Localizations;
+
+#[test]
+fn namespaces() {
+    setup();
+    let ru: LanguageIdentifier = "ru".parse().unwrap();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    loader.set_use_isolating(false);
+    loader
+        .load_languages(&Localizations, &[ru, en_us.clone()])
+        .unwrap();
+
+    pretty_assertions::assert_eq!("Настройки", loader.get("settings.title"));
+    pretty_assertions::assert_eq!("Hello from Settings!", loader.get("settings.greeting"));
+    pretty_assertions::assert_eq!("Volume", loader.get("settings.audio.volume"));
+    pretty_assertions::assert_eq!(
+        "Adjust the volume",
+        loader.get_attr("settings.audio.volume", "tooltip")
+    );
+    assert!(loader.has("settings.title"));
+    assert!(!loader.has("title"));
+    assert!(!loader.has("audio.volume"));
+
+    let message_ids = loader.message_ids(&en_us);
+    assert!(message_ids.contains(&"hello-world".to_string()));
+    assert!(message_ids.contains(&"settings.audio.volume".to_string()));
+
+    let available_languages = loader.available_languages(&Localizations).unwrap();
+    assert_eq!(
+        1,
+        available_languages
+            .iter()
+            .filter(|language| **language == en_us)
+            .count()
+    );
+}
+
+/// Assets with the `locales/{language}.ftl` layout.
+const LOCALES_ASSETS: FileAssets = FileAssets(&[
+    ("locales/en-US.ftl", "hello-world = Hello World!\n"),
+    ("locales/en-US/settings.ftl", "title = Settings\n"),
+    ("locales/fr.ftl", "hello-world = Bonjour le monde !\n"),
+    ("README.md", "# Locales\n"),
+]);
+
+#[test]
+fn path_template() {
+    setup();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let fr: LanguageIdentifier = "fr".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    loader.set_use_isolating(false);
+    assert!(loader.load_fallback_language(&LOCALES_ASSETS).is_err());
+
+    loader.set_path_template(Some(PathTemplate::new("locales/{language}.ftl").unwrap()));
+    assert_eq!(
+        vec![en_us.clone(), fr.clone()],
+        loader.available_languages(&LOCALES_ASSETS).unwrap()
+    );
+    loader
+        .load_languages(&LOCALES_ASSETS, &[fr, en_us])
+        .unwrap();
+    pretty_assertions::assert_eq!("Bonjour le monde !", loader.get("hello-world"));
+    pretty_assertions::assert_eq!("Settings", loader.get("settings.title"));
+
+    assert!(matches!(
+        PathTemplate::new("locales/{lang}.ftl"),
+        Err(I18nEmbedError::InvalidPathTemplate(_))
+    ));
+}
+
+/// Assets with terms shared by the files of each language.
+const TERMS_ASSETS: FileAssets = FileAssets(&[
+    ("en-US/_terms.ftl", "-brand = Acme\n-product = Rocket\n"),
+    (
+        "en-US/test.ftl",
+        "welcome = Welcome to { -brand }!\n-product = { -brand } Rocket\nabout = About { -product }\n",
+    ),
+    ("en-US/test/help.ftl", "contact = Contact { -brand }\n"),
+    ("en-US/other.ftl", "title = { -brand } Other\n"),
+    ("fr/test.ftl", "welcome = Bienvenue chez { -brand } !\n"),
+]);
+
+#[test]
+fn shared_terms() {
+    setup();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let fr: LanguageIdentifier = "fr".parse().unwrap();
+    assert_eq!(
+        vec![en_us.clone(), fr.clone()],
+        FluentLanguageLoader::new("test", en_us.clone())
+            .available_languages(&TERMS_ASSETS)
+            .unwrap()
+    );
+
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    let diagnostics = loader
+        .load_languages_with_diagnostics(&TERMS_ASSETS, std::slice::from_ref(&en_us))
+        .unwrap();
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    loader.set_use_isolating(false);
+    pretty_assertions::assert_eq!("Welcome to Acme!", loader.get("welcome"));
+    pretty_assertions::assert_eq!("Contact Acme", loader.get("help.contact"));
+    // The term defined in the file takes precedence.
+    pretty_assertions::assert_eq!("About Acme Rocket", loader.get("about"));
+
+    // The terms are shared by the domains.
+    let other = FluentLanguageLoader::new("other", en_us.clone());
+    other.load_languages(&TERMS_ASSETS, &[en_us]).unwrap();
+    other.set_use_isolating(false);
+    pretty_assertions::assert_eq!("Acme Other", other.get("title"));
+
+    // The terms are only available in their language.
+    loader.load_languages(&TERMS_ASSETS, &[fr]).unwrap();
+    loader.set_use_isolating(false);
+    pretty_assertions::assert_eq!("Bienvenue chez {-brand} !", loader.get("welcome"));
+}
@@ -0,0 +1,119 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use i18n_embed::{I18nAssets, I18nEmbedError, PollingAssets};
+
+type Files = HashMap<String, Vec<u8>>;
+
+/// A stand-in for a database table of the localization files.
+type Table = Arc<Mutex<Result<Files, String>>>;
+
+fn polling_assets(
+    table: &Table,
+    interval: Duration,
+) -> Result<PollingAssets<impl Fn() -> Result<Files, String> + Send + Sync>, I18nEmbedError> {
+    let table = table.clone();
+    PollingAssets::new(move || table.lock().unwrap().clone(), interval)
+}
+
+fn table(files: &[(&str, &str)]) -> Table {
+    Arc::new(Mutex::new(Ok(files
+        .iter()
+        .map(|(path, contents)| (path.to_string(), contents.as_bytes().to_vec()))
+        .collect())))
+}
+
+#[test]
+fn poll() {
+    let table = table(&[("fr/test.ftl", "hello-world = Bonjour !\n")]);
+    let assets = polling_assets(&table, Duration::from_secs(3600)).unwrap();
+    assert_eq!(
+        vec!["fr/test.ftl"],
+        assets.filenames_iter().collect::<Vec<_>>()
+    );
+
+    let changed = Arc::new(AtomicUsize::new(0));
+    let _watcher = {
+        let changed = changed.clone();
+        assets
+            .subscribe_changed(Arc::new(move || {
+                changed.fetch_add(1, Ordering::SeqCst);
+            }))
+            .unwrap()
+    };
+
+    // The subscribers are only notified when the files have changed.
+    assert!(!assets.poll().unwrap());
+    assert_eq!(0, changed.load(Ordering::SeqCst));
+
+    table.lock().unwrap().as_mut().unwrap().insert(
+        "de-DE/test.ftl".to_string(),
+        b"hello-world = Hallo Welt!\n".to_vec(),
+    );
+    assert!(assets.poll().unwrap());
+    assert_eq!(1, changed.load(Ordering::SeqCst));
+    assert_eq!(
+        b"hello-world = Hallo Welt!\n",
+        &*assets.get_files("de-DE/test.ftl")[0]
+    );
+
+    // The previous files are kept when they can't be fetched.
+    *table.lock().unwrap() = Err("connection refused".to_string());
+    assert!(matches!(
+        assets.poll(),
+        Err(I18nEmbedError::PollingAssetsFetch(_))
+    ));
+    assert_eq!(1, changed.load(Ordering::SeqCst));
+    assert_eq!(2, assets.filenames_iter().count());
+}
+
+#[test]
+fn fetch_error() {
+    let table: Table = Arc::new(Mutex::new(Err("connection refused".to_string())));
+    assert!(matches!(
+        polling_assets(&table, Duration::from_secs(3600)),
+        Err(I18nEmbedError::PollingAssetsFetch(_))
+    ));
+}
+
+#[test]
+fn polls_while_subscribed() {
+    let table = table(&[("fr/test.ftl", "hello-world = Bonjour !\n")]);
+    let assets = polling_assets(&table, Duration::from_millis(10)).unwrap();
+
+    let changed = Arc::new(AtomicUsize::new(0));
+    let watcher = {
+        let changed = changed.clone();
+        assets
+            .subscribe_changed(Arc::new(move || {
+                changed.fetch_add(1, Ordering::SeqCst);
+            }))
+            .unwrap()
+    };
+
+    table.lock().unwrap().as_mut().unwrap().insert(
+        "fr/test.ftl".to_string(),
+        b"hello-world = Salut !\n".to_vec(),
+    );
+    let start = Instant::now();
+    while changed.load(Ordering::SeqCst) == 0 {
+        assert!(start.elapsed() < Duration::from_secs(10));
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(
+        b"hello-world = Salut !\n",
+        &*assets.get_files("fr/test.ftl")[0]
+    );
+
+    // The files are no longer polled once the watcher is dropped.
+    drop(watcher);
+    table.lock().unwrap().as_mut().unwrap().clear();
+    std::thread::sleep(Duration::from_millis(100));
+    assert_eq!(1, assets.filenames_iter().count());
+}
@@ -0,0 +1,55 @@
+use i18n_embed::{locale_presentation, override_locale_presentation, LocalePresentation};
+use unic_langid::LanguageIdentifier;
+
+#[test]
+fn locale_presentation_built_in() {
+    let fr_ca: LanguageIdentifier = "fr-CA".parse().unwrap();
+    let presentation = locale_presentation(&fr_ca);
+    assert_eq!(Some("🇨🇦"), presentation.flag.as_deref());
+    assert_eq!(Some("Français"), presentation.native_name.as_deref());
+    assert_eq!(Some("French"), presentation.english_name.as_deref());
+
+    // Japanese is associated with Japan when no region is specified.
+    let ja: LanguageIdentifier = "ja".parse().unwrap();
+    assert_eq!(Some("🇯🇵"), locale_presentation(&ja).flag.as_deref());
+
+    // English is spoken in too many regions to pick a flag.
+    let en: LanguageIdentifier = "en".parse().unwrap();
+    assert_eq!(None, locale_presentation(&en).flag);
+
+    // Numeric regions have no flag.
+    let es_419: LanguageIdentifier = "es-419".parse().unwrap();
+    assert_eq!(None, locale_presentation(&es_419).flag);
+}
+
+#[test]
+fn locale_presentation_unknown() {
+    let unknown: LanguageIdentifier = "xx-NZ".parse().unwrap();
+    assert_eq!(
+        LocalePresentation {
+            flag: Some("🇳🇿".to_string()),
+            native_name: None,
+            english_name: None,
+        },
+        locale_presentation(&unknown)
+    );
+}
+
+#[test]
+fn locale_presentation_override() {
+    let en_gb: LanguageIdentifier = "en-GB".parse().unwrap();
+    let presentation = LocalePresentation {
+        flag: Some("🇬🇧".to_string()),
+        native_name: Some("English (UK)".to_string()),
+        english_name: Some("English (United Kingdom)".to_string()),
+    };
+    override_locale_presentation(en_gb.clone(), presentation.clone());
+    assert_eq!(presentation, locale_presentation(&en_gb));
+
+    // Other English variants still use the built-in data.
+    let en_au: LanguageIdentifier = "en-AU".parse().unwrap();
+    assert_eq!(
+        Some("English"),
+        locale_presentation(&en_au).native_name.as_deref()
+    );
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "properties-system")]
+
+mod common;
+
+use common::setup;
+use i18n_embed::{properties::PropertiesLanguageLoader, I18nEmbedError, LanguageLoader};
+use rust_embed::RustEmbed;
+use unic_langid::LanguageIdentifier;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/properties"]
+struct Localizations;
+
+#[test]
+fn available_languages() {
+    setup();
+    let loader = PropertiesLanguageLoader::new("test", "en".parse().unwrap());
+    let mut available_languages: Vec<String> = loader
+        .available_languages(&Localizations)
+        .unwrap()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    available_languages.sort();
+    pretty_assertions::assert_eq!(vec!["de", "de-AT", "en"], available_languages);
+}
+
+#[test]
+fn parse_base_bundle() {
+    setup();
+    let loader = PropertiesLanguageLoader::new("test", "en".parse().unwrap());
+    loader.load_fallback_language(&Localizations).unwrap();
+
+    pretty_assertions::assert_eq!("Open file", loader.get("menu.file.open"));
+    pretty_assertions::assert_eq!("This message spans two lines", loader.get("multi.line"));
+    pretty_assertions::assert_eq!("tab\there", loader.get("escaped key"));
+    pretty_assertions::assert_eq!("Hello World!", loader.get_args("greeting", &[&"World"]));
+    assert!(!loader.has("missing"));
+    pretty_assertions::assert_eq!("missing", loader.get("missing"));
+}
+
+#[test]
+fn fallback_chain() {
+    setup();
+    let loader = PropertiesLanguageLoader::new("test", "en".parse().unwrap());
+    let de_at: LanguageIdentifier = "de-AT".parse().unwrap();
+    let de: LanguageIdentifier = "de".parse().unwrap();
+    loader
+        .load_languages(&Localizations, &[de_at.clone(), de])
+        .unwrap();
+
+    pretty_assertions::assert_eq!(de_at, loader.current_language());
+    pretty_assertions::assert_eq!("Servus Welt!", loader.get_args("greeting", &[&"Welt"]));
+    pretty_assertions::assert_eq!("Datei öffnen", loader.get("menu.file.open"));
+    pretty_assertions::assert_eq!("Only in English", loader.get("only.en"));
+    pretty_assertions::assert_eq!(
+        "Das '3' ist {nicht} formatiert",
+        loader.get_args("quoted", &[&3])
+    );
+}
+
+#[test]
+fn fallback_required() {
+    setup();
+    let loader = PropertiesLanguageLoader::new("test", "fr".parse().unwrap());
+    let error = loader
+        .load_languages(&Localizations, &["de".parse().unwrap()])
+        .unwrap_err();
+    assert!(matches!(error, I18nEmbedError::LanguageNotAvailable(..)));
+}
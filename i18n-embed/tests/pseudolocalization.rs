@@ -0,0 +1,57 @@
+#![cfg(feature = "fluent-system")]
+
+mod common;
+
+use common::setup;
+use i18n_embed::{fluent::FluentLanguageLoader, LanguageLoader, Pseudolocalization};
+use rust_embed::RustEmbed;
+use unic_langid::LanguageIdentifier;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ftl"]
+struct Localizations;
+
+#[test]
+fn pseudolocalization() {
+    setup();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    loader
+        .load_languages(&Localizations, std::slice::from_ref(&en_us))
+        .unwrap();
+    loader.set_use_isolating(false);
+    assert_eq!(None, loader.pseudolocalization());
+
+    loader.set_pseudolocalization(Some(Pseudolocalization::default()));
+    assert_eq!("[Ĥéļļö~~]", loader.get_cow("with-attr"));
+    assert_eq!(
+        "[Ĥéļļö Ŵöŕļð Ļöçåļîžåţîöñ!~~~~~~~~]",
+        loader.get("hello-world")
+    );
+    // The values of placeables are not accented.
+    let mut args = std::collections::HashMap::new();
+    args.insert("thing", "ASCII");
+    assert_eq!(
+        "[îñĵéçţ å ASCII ĥéŕé~~~~~~]",
+        loader.get_args("isolation-chars", args)
+    );
+    assert_eq!(
+        "[Ŵöŕļð (ÛŠ ṽéŕšîöñ)!~~~~~~]",
+        loader.get_attr("with-attr", "attr")
+    );
+    // Shared with loaders created from this one.
+    let selected = loader.select_languages(std::slice::from_ref(&en_us));
+    assert_eq!("[Ĥéļļö~~]", selected.get("with-attr"));
+
+    // Applied to languages loaded subsequently.
+    loader.set_pseudolocalization(Some(Pseudolocalization {
+        accents: false,
+        expansion_percent: 0,
+        brackets: true,
+    }));
+    loader.reload(&Localizations).unwrap();
+    assert_eq!("[Hello]", loader.get("with-attr"));
+
+    loader.set_pseudolocalization(None);
+    assert_eq!("Hello", loader.get("with-attr"));
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "qt-system")]
+
+mod common;
+
+use common::setup;
+use i18n_embed::{qt::QtLanguageLoader, LanguageLoader};
+use rust_embed::RustEmbed;
+use unic_langid::LanguageIdentifier;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ts"]
+struct Localizations;
+
+#[test]
+fn available_languages() {
+    setup();
+    let loader = QtLanguageLoader::new("test", "en".parse().unwrap());
+    let mut available_languages: Vec<String> = loader
+        .available_languages(&Localizations)
+        .unwrap()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    available_languages.sort();
+    pretty_assertions::assert_eq!(vec!["de", "en", "ru"], available_languages);
+}
+
+#[test]
+fn translate() {
+    setup();
+    let loader = QtLanguageLoader::new("test", "en".parse().unwrap());
+    let de: LanguageIdentifier = "de".parse().unwrap();
+    loader
+        .load_languages(&Localizations, std::slice::from_ref(&de))
+        .unwrap();
+
+    pretty_assertions::assert_eq!(de, loader.current_language());
+    pretty_assertions::assert_eq!("Öffnen", loader.translate("MainWindow", "Open", None));
+    pretty_assertions::assert_eq!(
+        "Offen",
+        loader.translate("MainWindow", "Open", Some("adjective"))
+    );
+    assert!(!loader.has("Dialog", "Open", None));
+    pretty_assertions::assert_eq!("Open", loader.translate("Dialog", "Open", None));
+    // Unfinished translations are not used.
+    pretty_assertions::assert_eq!("Save", loader.translate("MainWindow", "Save", None));
+
+    pretty_assertions::assert_eq!(
+        "1 Datei",
+        loader.translate_n("MainWindow", "%n file(s)", None, 1)
+    );
+    pretty_assertions::assert_eq!(
+        "3 Dateien",
+        loader.translate_n("MainWindow", "%n file(s)", None, 3)
+    );
+}
+
+#[test]
+fn numerus_forms_ru() {
+    setup();
+    let loader = QtLanguageLoader::new("test", "en".parse().unwrap());
+    let ru_ru: LanguageIdentifier = "ru-RU".parse().unwrap();
+    let ru: LanguageIdentifier = "ru".parse().unwrap();
+    // There is no file for ru-RU, so the translations fall back to ru.
+    loader.load_languages(&Localizations, &[ru_ru, ru]).unwrap();
+    pretty_assertions::assert_eq!("Открыть", loader.translate("MainWindow", "Open", None));
+    for (n, expected) in [
+        (1, "1 файл"),
+        (3, "3 файла"),
+        (5, "5 файлов"),
+        (21, "21 файл"),
+        (0, "0 файлов"),
+    ] {
+        pretty_assertions::assert_eq!(
+            expected,
+            loader.translate_n("MainWindow", "%n file(s)", None, n)
+        );
+    }
+}
+
+#[test]
+fn fallback_to_source() {
+    setup();
+    let loader = QtLanguageLoader::new("test", "en".parse().unwrap());
+    loader
+        .load_languages(&Localizations, &["fr".parse().unwrap()])
+        .unwrap();
+    pretty_assertions::assert_eq!("Open", loader.translate("MainWindow", "Open", None));
+    pretty_assertions::assert_eq!(
+        "2 file(s)",
+        loader.translate_n("MainWindow", "%n file(s)", None, 2)
+    );
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "fluent-system")]
+
+mod common;
+
+use ::fluent::FluentValue;
+use common::setup;
+use i18n_embed::{fluent::FluentLanguageLoader, LanguageLoader, RegionContext};
+use rust_embed::RustEmbed;
+use unic_langid::LanguageIdentifier;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ftl"]
+struct Localizations;
+
+#[test]
+fn region_context() {
+    setup();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    loader
+        .load_languages(&Localizations, std::slice::from_ref(&en_us))
+        .unwrap();
+    loader.set_use_isolating(false);
+    loader.with_bundles_mut(|bundle| {
+        bundle
+            .add_function("CURRENCY", |positional, _| {
+                match (positional.first(), positional.get(1)) {
+                    (Some(FluentValue::Number(amount)), Some(FluentValue::String(currency))) => {
+                        format!("{:.2} {}", amount.value, currency).into()
+                    }
+                    _ => FluentValue::Error,
+                }
+            })
+            .unwrap();
+    });
+    assert!(loader.region_context().is_none());
+
+    let scope = loader.scope(&[&en_us]).with_region_context(
+        RegionContext::new()
+            .with_region("CH".parse().unwrap())
+            .with_currency("CHF")
+            .with_timezone("Europe/Zurich"),
+    );
+    pretty_assertions::assert_eq!(
+        Some("CHF"),
+        scope.region_context().unwrap().currency.as_deref()
+    );
+    pretty_assertions::assert_eq!(
+        "Total: 12.50 CHF",
+        scope.get_args("total", maplit::hashmap! { "amount" => 12.5 })
+    );
+    // Arguments take precedence over the context.
+    pretty_assertions::assert_eq!(
+        "Total: 12.50 EUR",
+        scope.get_args(
+            "total",
+            maplit::hashmap! {
+                "amount" => FluentValue::from(12.5),
+                "currency" => FluentValue::from("EUR"),
+            }
+        )
+    );
+    pretty_assertions::assert_eq!("CH Europe/Zurich", scope.get("region-info"));
+    pretty_assertions::assert_eq!("CH Europe/Zurich", scope.get_cow("region-info"));
+
+    // The region defaults to the region of the language.
+    let scope = loader
+        .scope(&[&en_us])
+        .with_region_context(RegionContext::new().with_timezone("America/New_York"));
+    pretty_assertions::assert_eq!("US America/New_York", scope.get("region-info"));
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "fluent-system")]
+
+mod common;
+
+use common::setup;
+use i18n_embed::{fluent::FluentLanguageLoader, LanguageLoader, RelativeTime};
+use rust_embed::RustEmbed;
+use std::time::Duration;
+use unic_langid::LanguageIdentifier;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ftl"]
+struct Localizations;
+
+#[test]
+fn format_relative_time() {
+    setup();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    loader.load_languages(&Localizations, &[en_us]).unwrap();
+
+    assert_eq!("now", loader.format_relative_time(Duration::ZERO));
+    assert_eq!(
+        "1 second ago",
+        loader.format_relative_time(Duration::from_secs(1))
+    );
+    assert_eq!(
+        "3 minutes ago",
+        loader.format_relative_time(Duration::from_secs(3 * 60 + 59))
+    );
+    assert_eq!(
+        "in 2 days",
+        loader.format_relative_time(RelativeTime::Future(Duration::from_secs(2 * 24 * 60 * 60)))
+    );
+    assert_eq!(
+        "in 1 year",
+        loader.format_relative_time(RelativeTime::Future(Duration::from_secs(
+            400 * 24 * 60 * 60
+        )))
+    );
+}
+
+#[test]
+fn format_relative_time_builtin_language() {
+    setup();
+    let ru: LanguageIdentifier = "ru".parse().unwrap();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us);
+    loader.load_languages(&Localizations, &[ru]).unwrap();
+
+    assert_eq!(
+        "5 минут назад",
+        loader.format_relative_time(Duration::from_secs(5 * 60))
+    );
+    assert_eq!(
+        "через 2 часа",
+        loader.format_relative_time(RelativeTime::Future(Duration::from_secs(2 * 60 * 60)))
+    );
+}
+
+#[test]
+fn format_relative_time_override() {
+    setup();
+    let en_gb: LanguageIdentifier = "en-GB".parse().unwrap();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us);
+    loader.load_languages(&Localizations, &[en_gb]).unwrap();
+
+    assert_eq!("just now", loader.format_relative_time(Duration::ZERO));
+    assert_eq!(
+        "1 hour ago",
+        loader.format_relative_time(Duration::from_secs(60 * 60))
+    );
+}
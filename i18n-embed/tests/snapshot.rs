@@ -0,0 +1,141 @@
+#![cfg(feature = "fluent-system")]
+
+mod common;
+
+use ::fluent::FluentValue;
+use common::setup;
+use i18n_embed::{
+    fluent::{FluentLanguageLoader, LogPolicy},
+    LanguageLoader, PathTemplate,
+};
+use rust_embed::RustEmbed;
+use unic_langid::LanguageIdentifier;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ftl"]
+struct Localizations;
+
+#[test]
+fn snapshot() {
+    setup();
+    let ru: LanguageIdentifier = "ru".parse().unwrap();
+    let en_gb: LanguageIdentifier = "en-GB".parse().unwrap();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    loader
+        .load_languages(&Localizations, std::slice::from_ref(&en_gb))
+        .unwrap();
+
+    let snapshot = loader.snapshot();
+    assert_eq!(&en_gb, snapshot.current_language());
+    assert_eq!("only GB", snapshot.get("only-gb"));
+    assert_eq!("only US", snapshot.get("only-us"));
+    assert!(snapshot.has("only-us"));
+    assert!(!snapshot.has("non-existent-message"));
+
+    // The snapshot is unaffected by subsequently loaded languages.
+    loader.load_languages(&Localizations, &[ru]).unwrap();
+    assert_eq!("only GB (US Version)", loader.get("only-gb"));
+
+    let messages = std::thread::spawn({
+        let snapshot = snapshot.clone();
+        move || snapshot.get("only-gb")
+    })
+    .join()
+    .unwrap();
+    assert_eq!("only GB", messages);
+
+    // Configuring the loader doesn't modify the shared bundles.
+    loader.load_languages(&Localizations, &[en_us]).unwrap();
+    let snapshot = loader.snapshot();
+    loader.set_use_isolating(false);
+    let args = maplit::hashmap! {
+        "thing" => "thing"
+    };
+    assert_eq!(
+        "inject a \u{2068}thing\u{2069} here",
+        snapshot.get_args("isolation-chars", args.clone())
+    );
+    assert_eq!(
+        "inject a thing here",
+        loader.get_args("isolation-chars", args)
+    );
+}
+
+#[test]
+fn snapshot_keeps_bundle_configuration() {
+    setup();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    loader
+        .load_languages(&Localizations, std::slice::from_ref(&en_us))
+        .unwrap();
+    loader.set_use_isolating(false);
+    loader.with_bundles_mut(|bundle| {
+        bundle
+            .add_function("CURRENCY", |positional, _| match positional {
+                [FluentValue::Number(amount), FluentValue::String(currency)] => {
+                    format!("{:.2} {}", amount.value, currency).into()
+                }
+                _ => FluentValue::Error,
+            })
+            .unwrap();
+    });
+
+    // The bundles shared with the snapshot are re-created.
+    let snapshot = loader.snapshot();
+    loader.with_bundles_mut(|bundle| bundle.set_use_isolating(false));
+
+    let args = maplit::hashmap! {
+        "amount" => FluentValue::from(12.5),
+        "currency" => FluentValue::from("CHF"),
+    };
+    assert_eq!("Total: 12.50 CHF", loader.get_args("total", args.clone()));
+    assert_eq!("Total: 12.50 CHF", snapshot.get_args("total", args));
+}
+
+#[test]
+fn clone_empty() {
+    use i18n_embed::testing::InMemoryAssets;
+
+    setup();
+    let assets: InMemoryAssets = maplit::hashmap! {
+        "locales/en-US.ftl".to_string() => "hello = Hello { $name }!\n".to_string(),
+        "locales/fr.ftl".to_string() => "hello = Bonjour { $name } !\n".to_string(),
+    }
+    .into();
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    let fr: LanguageIdentifier = "fr".parse().unwrap();
+    let loader = FluentLanguageLoader::new("test", en_us.clone());
+    loader.set_path_template(Some(PathTemplate::new("locales/{language}.ftl").unwrap()));
+    loader.set_use_isolating(false);
+    loader.set_log_policy(LogPolicy::Silent);
+    loader.load_fallback_language(&assets).unwrap();
+
+    let empty = loader.clone_empty();
+    pretty_assertions::assert_eq!("test", empty.domain());
+    pretty_assertions::assert_eq!(&en_us, empty.fallback_language());
+    assert!(!empty.has("hello"));
+    empty.load_fallback_language(&assets).unwrap();
+    pretty_assertions::assert_eq!(
+        "Hello Bob!",
+        empty.get_args("hello", maplit::hashmap! { "name" => "Bob" })
+    );
+
+    // The state is not shared.
+    let _ = empty.get("missing");
+    assert!(loader.missing_message_counts().is_empty());
+    pretty_assertions::assert_eq!(LogPolicy::All, empty.log_policy());
+
+    let french = loader.with_overridden_fallback(fr.clone());
+    pretty_assertions::assert_eq!(&fr, french.fallback_language());
+    french.load_fallback_language(&assets).unwrap();
+    pretty_assertions::assert_eq!(
+        "Bonjour Bob !",
+        french.get_args("hello", maplit::hashmap! { "name" => "Bob" })
+    );
+    pretty_assertions::assert_eq!(
+        "Hello Bob!",
+        loader.get_args("hello", maplit::hashmap! { "name" => "Bob" })
+    );
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "fluent-system")]
+
+mod common;
+
+use common::setup;
+use i18n_embed::{fluent::FluentLanguageLoader, LanguageLoader};
+use rust_embed::RustEmbed;
+use unic_langid::LanguageIdentifier;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ftl"]
+struct Localizations;
+
+#[test]
+fn loader_macro_params() {
+    use i18n_embed::fluent::{fluent_language_loader, static_loader};
+
+    static LOADER: FluentLanguageLoader = static_loader!(domain = "test");
+
+    // The configuration is used when there is an `i18n.toml`.
+    let loader: FluentLanguageLoader = fluent_language_loader!(default_fallback = "de");
+    pretty_assertions::assert_eq!("en", loader.fallback_language().to_string());
+    pretty_assertions::assert_eq!("test", LOADER.domain());
+    pretty_assertions::assert_eq!("en", LOADER.fallback_language().to_string());
+    assert!(loader.use_isolating());
+    assert!(LOADER.use_isolating());
+
+    let loader: FluentLanguageLoader =
+        fluent_language_loader!(domain = "test", fallback_language = "en-US");
+    pretty_assertions::assert_eq!("test", loader.domain());
+    pretty_assertions::assert_eq!("en-US", loader.fallback_language().to_string());
+
+    setup();
+    loader
+        .load_languages(&Localizations, &[loader.fallback_language().clone()])
+        .unwrap();
+    pretty_assertions::assert_eq!("Hello World Localization!", loader.get("hello-world"));
+}
+
+#[test]
+fn static_loader() {
+    use i18n_embed::fluent::static_loader;
+
+    static LOADER: FluentLanguageLoader = static_loader!();
+    static CONST_LOADER: FluentLanguageLoader = FluentLanguageLoader::new_const("test", {
+        // Equivalent to `unic_langid::langid!("en-US")`.
+        let en_us: LanguageIdentifier = unsafe {
+            LanguageIdentifier::from_raw_parts_unchecked(
+                unic_langid::subtags::Language::from_raw_unchecked(u64::from_le_bytes(
+                    *b"en\0\0\0\0\0\0",
+                )),
+                None,
+                Some(unic_langid::subtags::Region::from_raw_unchecked(
+                    u32::from_le_bytes(*b"US\0\0"),
+                )),
+                None,
+            )
+        };
+        en_us
+    });
+
+    setup();
+    pretty_assertions::assert_eq!("en", LOADER.fallback_language().to_string());
+    pretty_assertions::assert_eq!("en", LOADER.current_language().to_string());
+
+    let en_us: LanguageIdentifier = "en-US".parse().unwrap();
+    pretty_assertions::assert_eq!(&en_us, CONST_LOADER.fallback_language());
+    CONST_LOADER
+        .load_languages(&Localizations, &[en_us])
+        .unwrap();
+    pretty_assertions::assert_eq!("Hello World Localization!", CONST_LOADER.get("hello-world"));
+}
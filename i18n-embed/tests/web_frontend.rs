@@ -0,0 +1,54 @@
+#![cfg(feature = "yew")]
+
+use i18n_embed::{
+    fluent::FluentLanguageLoader,
+    web_frontend::{use_localization, LocalizationProvider},
+    I18nAssets, LanguageLoader,
+};
+use rust_embed::RustEmbed;
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+use yew::{function_component, html, Html, LocalServerRenderer};
+
+#[derive(RustEmbed)]
+#[folder = "i18n/ftl"]
+struct Localizations;
+
+static LOCALIZATIONS: &(dyn I18nAssets + Send + Sync) = &Localizations;
+
+fn loader() -> &'static FluentLanguageLoader {
+    static LOADER: OnceLock<FluentLanguageLoader> = OnceLock::new();
+    LOADER.get_or_init(|| FluentLanguageLoader::new("test", "en-US".parse().unwrap()))
+}
+
+#[function_component]
+fn Greeting() -> Html {
+    let localization = use_localization();
+    html! {
+        <p lang={localization.language().to_string()}>{ localization.get("hello-world") }</p>
+    }
+}
+
+#[function_component]
+fn App() -> Html {
+    let requested_languages: Vec<LanguageIdentifier> = vec!["ru".parse().unwrap()];
+    html! {
+        <LocalizationProvider
+            loader={loader()}
+            i18n_assets={LOCALIZATIONS}
+            requested_languages={requested_languages}
+        >
+            <Greeting />
+        </LocalizationProvider>
+    }
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn provider_selects_requested_languages() {
+    let html = LocalServerRenderer::<App>::new()
+        .hydratable(false)
+        .render()
+        .await;
+    assert_eq!(r#"<p lang="ru">Привет Мир Локализация!</p>"#, html);
+    assert_eq!("ru", loader().current_language().to_string());
+}
@@ -1,6 +1,226 @@
+//! The `cargo i18n` command as a library, so that build orchestration
+//! (e.g. an `xtask`) can localize crates in-process instead of
+//! running the `cargo i18n` binary.
+//!
+//! ```no_run
+//! let report = cargo_i18n::run_with_options(&cargo_i18n::Options {
+//!     path: "my-crate".into(),
+//!     ..Default::default()
+//! })
+//! .unwrap();
+//!
+//! for path in &report.files_written {
+//!     println!("{}", path.display());
+//! }
+//! ```
+
 #[cfg(doctest)]
 #[macro_use]
 extern crate doc_comment;
 
 #[cfg(doctest)]
 doctest!("../README.md");
+
+use anyhow::{anyhow, Result};
+use i18n_build::{
+    run_with_summary, run_workspace_with_summary,
+    summary::RunSummary,
+    system::{self, ValidationIssue},
+};
+use i18n_config::{Crate, I18nConfigError, WhyNotCrate, Workspace};
+use std::{
+    collections::BTreeMap,
+    fs,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+};
+use tr::tr;
+use walkdir::WalkDir;
+
+/// The options for [run_with_options()], which mirror the command
+/// line arguments of `cargo i18n`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// The path to the crate (or workspace) to localize, like the
+    /// `--path` argument. By default this is the current directory.
+    pub path: PathBuf,
+    /// The name of the i18n config file, relative to the crate, like
+    /// the `--config-file-name` argument. By default this is
+    /// `i18n.toml`.
+    pub config_file_name: PathBuf,
+    /// Localize each of the members of the workspace at
+    /// [Options::path] which have an i18n config, like the
+    /// `--workspace` flag. A workspace without a root package is
+    /// always localized this way.
+    pub workspace: bool,
+    /// The maximum number of languages to update in parallel, like
+    /// the `--jobs` argument. By default this is the number of
+    /// available CPUs.
+    pub jobs: Option<NonZeroUsize>,
+    /// Validate the localization resources of each of the crates
+    /// after they have been localized, like `cargo i18n validate`,
+    /// and add the problems which were found to
+    /// [Report::warnings].
+    pub validate: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("."),
+            config_file_name: PathBuf::from("i18n.toml"),
+            workspace: false,
+            jobs: None,
+            validate: true,
+        }
+    }
+}
+
+/// The result of [run_with_options()].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    /// The summary of the crates which were localized, which is
+    /// printed by `cargo i18n`.
+    pub summary: RunSummary,
+    /// The localization resources which were created or modified,
+    /// sorted by their path.
+    pub files_written: Vec<PathBuf>,
+    /// The problems found by validating the localization resources
+    /// when [Options::validate] is enabled. These don't fail the
+    /// run, it is up to the caller to decide what to do with them.
+    pub warnings: Vec<ValidationIssue>,
+}
+
+/// Localize the crate (or the members of the workspace) at
+/// [Options::path], the same as running `cargo i18n` without a
+/// subcommand.
+pub fn run_with_options(options: &Options) -> Result<Report> {
+    let path = &options.path;
+    let config_file_path = &options.config_file_name;
+    let jobs = options.jobs.unwrap_or_else(i18n_build::util::default_jobs);
+
+    i18n_build::util::check_path_exists(path)?;
+
+    let crt = match Crate::from(path.clone(), None, config_file_path.clone()) {
+        Ok(crt) => Some(crt),
+        Err(I18nConfigError::NotACrate(_, WhyNotCrate::Workspace)) => None,
+        Err(err) => return Err(err.into()),
+    };
+
+    // The crates which are localized, to be validated afterwards.
+    let mut crate_paths = Vec::new();
+    let (before, summary) = match crt {
+        Some(crt) if !options.workspace => {
+            // The config may be in the `[package.metadata.i18n]`
+            // table of the `Cargo.toml` instead.
+            if crt.i18n_config.is_none() {
+                i18n_build::util::check_path_exists(path.join(config_file_path))?;
+            }
+            // The parent crates may also be written to.
+            crate_paths.push(crt.path.clone());
+            let mut parent = crt.find_parent();
+            while let Some(crt) = parent {
+                crate_paths.push(crt.path.clone());
+                parent = crt.find_parent();
+            }
+            let before = snapshot(crate_paths.last().expect("expected the crate's path"))?;
+            let summary = run_with_summary(crt, jobs)?;
+            (before, summary)
+        }
+        _ => {
+            let workspace = Workspace::from_cargo_metadata(path)?;
+            crate_paths.extend(
+                workspace
+                    .member_crates(config_file_path)?
+                    .into_iter()
+                    .map(|crt| crt.path),
+            );
+            let before = snapshot(&workspace.root)?;
+            let summary = run_workspace_with_summary(&workspace, config_file_path, jobs)?;
+            (before, summary)
+        }
+    };
+
+    let files_written = files_written(&before)?;
+
+    let mut warnings = Vec::new();
+    if options.validate {
+        for crate_path in crate_paths {
+            let crt = Crate::from(crate_path, None, config_file_path.clone())?;
+            warnings.extend(system::validate(&crt)?);
+        }
+    }
+
+    Ok(Report {
+        summary,
+        files_written,
+        warnings,
+    })
+}
+
+/// The contents of the localization resources beneath a directory,
+/// used to find which of them a run has written.
+struct Snapshot {
+    root: PathBuf,
+    files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+/// Read each of the localization resources (the files with an
+/// extension used by one of the registered localization systems)
+/// beneath the `root` directory, skipping the `target` directory and
+/// hidden directories.
+fn snapshot(root: &Path) -> Result<Snapshot> {
+    let extensions: Vec<String> = system::systems()
+        .iter()
+        .flat_map(|system| {
+            system
+                .resource_extensions()
+                .iter()
+                .map(|extension| extension.to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let mut files = BTreeMap::new();
+    let entries = WalkDir::new(root).into_iter().filter_entry(|entry| {
+        let name = entry.file_name().to_string_lossy();
+        entry.depth() == 0
+            || !(entry.file_type().is_dir() && (name == "target" || name.starts_with('.')))
+    });
+    for entry in entries {
+        let entry = entry.map_err(|err| {
+            anyhow!(tr!(
+                // {0} is the path to the directory, {1} is the error.
+                "Error walking the directory \"{0}\": {1}",
+                root.to_string_lossy(),
+                err
+            ))
+        })?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let is_resource = entry
+            .path()
+            .extension()
+            .is_some_and(|extension| extensions.iter().any(|e| extension == e.as_str()));
+        if is_resource {
+            files.insert(entry.path().to_path_buf(), fs::read(entry.path())?);
+        }
+    }
+    Ok(Snapshot {
+        root: root.to_path_buf(),
+        files,
+    })
+}
+
+/// The localization resources which have been created or modified
+/// since the `before` snapshot was taken.
+fn files_written(before: &Snapshot) -> Result<Vec<PathBuf>> {
+    let after = snapshot(&before.root)?;
+    Ok(after
+        .files
+        .into_iter()
+        .filter(|(path, contents)| before.files.get(path) != Some(contents))
+        .map(|(path, _)| path)
+        .collect())
+}
@@ -1,6 +1,12 @@
 use anyhow::Result;
-use clap::{builder::PossibleValuesParser, crate_authors, crate_version, Arg, Command};
-use i18n_build::run;
+use clap::{builder::PossibleValuesParser, crate_authors, crate_version, Arg, ArgAction, Command};
+use i18n_build::{
+    add_language::add_language,
+    error::CommandError,
+    init::{init, InitOptions, LocalizationSystem},
+    progress::Progress,
+    system::{Severity, ValidationIssue},
+};
 use i18n_config::Crate;
 use i18n_embed::{
     gettext::{gettext_language_loader, GettextLanguageLoader},
@@ -8,8 +14,13 @@ use i18n_embed::{
 };
 use rust_embed::RustEmbed;
 use std::{
+    io::{BufRead, IsTerminal, Write},
+    num::NonZeroUsize,
     path::{Path, PathBuf},
-    sync::{Arc, OnceLock},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock,
+    },
 };
 use tr::tr;
 use unic_langid::LanguageIdentifier;
@@ -66,18 +77,52 @@ using your system settings (as described at
 https://github.com/rust-locale/locale_config#supported-systems ) \
 however you can override it using the -l, --language option.
 
-Logging for this command is available using the \"env_logger\" crate. \
-You can enable debug logging using \"RUST_LOG=debug cargo i18n\".",
+The progress of localizing the crates is shown while it runs, unless \
+the -q, --quiet option is used. Logging for this command is available \
+using the -v, --verbose option (-vv for debug logging), or using the \
+\"env_logger\" crate, e.g. \"RUST_LOG=debug cargo i18n\".",
         short_about()
     )
 }
 
+/// Whether the problems reported by the gettext tools are printed as
+/// JSON (using `--message-format json`), see [print_json_diagnostics()].
+static JSON_MESSAGE_FORMAT: AtomicBool = AtomicBool::new(false);
+
 fn main() -> Result<()> {
-    env_logger::init();
+    let result = run();
+    if let Err(err) = &result {
+        if JSON_MESSAGE_FORMAT.load(Ordering::Relaxed) {
+            print_json_diagnostics(err);
+        }
+    }
+    result
+}
+
+/// Print the problems with specific files reported by the gettext
+/// tools which caused the `err` (if any) to stdout as JSON, one per
+/// line, for integration with editors and IDEs.
+fn print_json_diagnostics(err: &anyhow::Error) {
+    let command_error = match err
+        .chain()
+        .find_map(|err| err.downcast_ref::<CommandError>())
+    {
+        Some(command_error) => command_error,
+        None => return,
+    };
+    for diagnostic in &command_error.diagnostics {
+        match serde_json::to_string(diagnostic) {
+            Ok(json) => println!("{json}"),
+            Err(err) => log::error!("Unable to serialize the diagnostic: {0}", err),
+        }
+    }
+}
+
+fn run() -> Result<()> {
     let mut language_requester = DesktopLanguageRequester::new();
 
     let cargo_i18n_localizer: DefaultLocalizer<'static> =
-        DefaultLocalizer::new(&*language_loader(), &TRANSLATIONS);
+        DefaultLocalizer::new(language_loader(), &TRANSLATIONS);
 
     let cargo_i18n_localizer_rc: Arc<dyn Localizer> = Arc::new(cargo_i18n_localizer);
     let i18n_build_localizer_rc: Arc<dyn Localizer> = Arc::new(i18n_build::localizer());
@@ -116,6 +161,7 @@ fn main() -> Result<()> {
                     )
                 .long("path")
                 .num_args(1)
+                .value_parser(clap::value_parser!(PathBuf))
             )
             .arg(Arg::new("config-file-name")
                 .help(
@@ -141,10 +187,453 @@ fn main() -> Result<()> {
                 .default_value(fallback_locale)
                 .value_parser(PossibleValuesParser::new(available_languages_slice))
             )
+            .arg(Arg::new("jobs")
+                .help(
+                    tr!(
+                        // The help message for the `-j`, `--jobs` command line argument.
+                        "The maximum number of string extraction commands to run in parallel. Defaults to the number of available CPUs."
+                    )
+                )
+                .long("jobs")
+                .short('j')
+                .num_args(1)
+                .value_parser(clap::value_parser!(NonZeroUsize))
+            )
+            .arg(Arg::new("workspace")
+                .help(
+                    tr!(
+                        // The help message for the `--workspace` command line argument.
+                        "Localize all the members of the workspace which have an i18n config file, or which inherit the workspace's config using \"i18n = { workspace = true }\" in their \"[package.metadata]\". This is the default when the path is the root of a virtual workspace."
+                    )
+                )
+                .long("workspace")
+                .action(ArgAction::SetTrue)
+            )
+            .arg(Arg::new("format")
+                .help(
+                    tr!(
+                        // The help message for the `--format` command line argument.
+                        "The format of the summary printed when the localization is complete. With \"json\", the summary (including the number of translated, fuzzy and untranslated messages of each language) is printed to stdout as a JSON object."
+                    )
+                )
+                .long("format")
+                .num_args(1)
+                .default_value("text")
+                .value_parser(PossibleValuesParser::new(["text", "json"]))
+            )
+            .arg(Arg::new("quiet")
+                .help(
+                    tr!(
+                        // The help message for the `-q`, `--quiet` command line argument.
+                        "Don't show the progress, the summary or log messages other than errors."
+                    )
+                )
+                .long("quiet")
+                .short('q')
+                .global(true)
+                .action(ArgAction::SetTrue)
+                .conflicts_with("verbose")
+            )
+            .arg(Arg::new("verbose")
+                .help(
+                    tr!(
+                        // The help message for the `-v`, `--verbose` command line argument.
+                        "Show the log messages describing each step instead of the progress, use -vv to also show the debug messages (such as the commands which are run)."
+                    )
+                )
+                .long("verbose")
+                .short('v')
+                .global(true)
+                .action(ArgAction::Count)
+            )
+            .arg(Arg::new("message-format")
+                .help(
+                    tr!(
+                        // The help message for the `--message-format` command line argument.
+                        "The format of the problems with specific files reported by the gettext tools when they fail. With \"json\", each problem is also printed to stdout as a JSON object on its own line, for integration with editors and IDEs."
+                    )
+                )
+                .long("message-format")
+                .num_args(1)
+                .default_value("human")
+                .value_parser(PossibleValuesParser::new(["human", "json"]))
+            )
+            .subcommand(Command::new("init")
+                .about(
+                    tr!(
+                        // The help message for the `cargo i18n init` subcommand.
+                        "Set up the localization of a crate, creating the i18n config file, the localization assets directory, and optionally adding the required dependencies to \"Cargo.toml\". Options which are not specified are prompted for when running in a terminal."
+                    )
+                )
+                .arg(Arg::new("system")
+                    .help(
+                        tr!(
+                            // The help message for the `--system` command line argument of `cargo i18n init`.
+                            "The localization system to use."
+                        )
+                    )
+                    .long("system")
+                    .num_args(1)
+                    .value_parser(PossibleValuesParser::new(["fluent", "gettext"]))
+                )
+                .arg(Arg::new("fallback-language")
+                    .help(
+                        tr!(
+                            // The help message for the `--fallback-language` command line argument of `cargo i18n init`.
+                            "The language used in the source code, and which all messages must be available in."
+                        )
+                    )
+                    .long("fallback-language")
+                    .num_args(1)
+                )
+                .arg(Arg::new("target-languages")
+                    .help(
+                        tr!(
+                            // The help message for the `--target-languages` command line argument of `cargo i18n init`.
+                            "A comma separated list of the languages to translate into, when using the gettext system."
+                        )
+                    )
+                    .long("target-languages")
+                    .num_args(1)
+                    .value_delimiter(',')
+                )
+                .arg(Arg::new("patch-cargo-toml")
+                    .help(
+                        tr!(
+                            // The help message for the `--patch-cargo-toml` command line argument of `cargo i18n init`.
+                            "Add the dependencies required to embed the localizations to \"Cargo.toml\"."
+                        )
+                    )
+                    .long("patch-cargo-toml")
+                    .action(ArgAction::SetTrue)
+                )
+            )
+            .subcommand(Command::new("add-language")
+                .about(
+                    tr!(
+                        // The help message for the `cargo i18n add-language` subcommand.
+                        "Add a new language to a crate. For the fluent system, this creates the language's files pre-populated with the fallback language's messages commented out. For the gettext system, this adds the language to the \"target_languages\" in the i18n config file, and creates its po file."
+                    )
+                )
+                .arg(Arg::new("language")
+                    .help(
+                        tr!(
+                            // The help message for the language argument of `cargo i18n add-language`.
+                            "The identifier of the language to add, e.g. \"de-AT\"."
+                        )
+                    )
+                    .required(true)
+                    .num_args(1)
+                )
+            )
+            .subcommands(translate_command())
+            .subcommand(Command::new("migrate")
+                .about(
+                    tr!(
+                        // The help message for the `cargo i18n migrate` subcommand.
+                        "Migrate a crate to another localization system, keeping its existing translations. Currently only migrating from gettext to fluent is supported: the po files are converted into ftl files, and a \"[fluent]\" section is added to the i18n config file."
+                    )
+                )
+                .arg(Arg::new("to")
+                    .help(
+                        tr!(
+                            // The help message for the `--to` command line argument of `cargo i18n migrate`.
+                            "The localization system to migrate to."
+                        )
+                    )
+                    .long("to")
+                    .required(true)
+                    .num_args(1)
+                    .value_parser(PossibleValuesParser::new(["fluent"]))
+                )
+                .arg(Arg::new("assets-dir")
+                    .help(
+                        tr!(
+                            // The help message for the `--assets-dir` command line argument of `cargo i18n migrate`.
+                            "The directory (relative to the crate) to create the ftl files in, if the crate doesn't already have a \"[fluent]\" config."
+                        )
+                    )
+                    .long("assets-dir")
+                    .num_args(1)
+                    .default_value("i18n")
+                    .value_parser(clap::value_parser!(PathBuf))
+                )
+                .arg(Arg::new("patch")
+                    .help(
+                        tr!(
+                            // The help message for the `--patch` command line argument of `cargo i18n migrate`.
+                            "Write a patch to this file which replaces the tr!() calls in the crate's source code with fl!() calls. The source code is not modified, the patch can be reviewed and then applied using \"patch -p1\" in the crate's directory."
+                        )
+                    )
+                    .long("patch")
+                    .num_args(1)
+                    .value_parser(clap::value_parser!(PathBuf))
+                )
+                .arg(Arg::new("loader")
+                    .help(
+                        tr!(
+                            // The help message for the `--loader` command line argument of `cargo i18n migrate`.
+                            "The expression for the language loader used in the fl!() calls of the patch."
+                        )
+                    )
+                    .long("loader")
+                    .num_args(1)
+                    .default_value("LANGUAGE_LOADER")
+                )
+            )
+            .subcommand(Command::new("export")
+                .about(
+                    tr!(
+                        // The help message for the `cargo i18n export` subcommand.
+                        "Export the messages of a crate and their translations, for exchanging them with translators. One file is written for each language, named \"{module}.{language}.xlf\"."
+                    )
+                )
+                .arg(Arg::new("format")
+                    .help(
+                        tr!(
+                            // The help message for the `--format` command line argument of `cargo i18n export`.
+                            "The format of the exported files."
+                        )
+                    )
+                    .long("format")
+                    .num_args(1)
+                    .default_value("xliff")
+                    .value_parser(PossibleValuesParser::new(["xliff"]))
+                )
+                .arg(Arg::new("xliff-version")
+                    .help(
+                        tr!(
+                            // The help message for the `--xliff-version` command line argument of `cargo i18n export`.
+                            "The version of the XLIFF standard to use."
+                        )
+                    )
+                    .long("xliff-version")
+                    .num_args(1)
+                    .default_value("1.2")
+                    .value_parser(PossibleValuesParser::new(["1.2", "2.0"]))
+                )
+                .arg(Arg::new("output-dir")
+                    .help(
+                        tr!(
+                            // The help message for the `--output-dir` command line argument of `cargo i18n export`.
+                            "The directory to write the exported files to."
+                        )
+                    )
+                    .long("output-dir")
+                    .short('o')
+                    .num_args(1)
+                    .default_value(".")
+                    .value_parser(clap::value_parser!(PathBuf))
+                )
+                .arg(Arg::new("languages")
+                    .help(
+                        tr!(
+                            // The help message for the languages argument of `cargo i18n export`.
+                            "The identifiers of the languages to export, e.g. \"de-AT\". By default all the languages of the crate are exported."
+                        )
+                    )
+                    .num_args(0..)
+                    .value_parser(clap::value_parser!(LanguageIdentifier))
+                )
+            )
+            .subcommand(Command::new("import")
+                .about(
+                    tr!(
+                        // The help message for the `cargo i18n import` subcommand.
+                        "Import the translations in XLIFF files (created by \"cargo i18n export\") into the localizations of a crate."
+                    )
+                )
+                .arg(Arg::new("files")
+                    .help(
+                        tr!(
+                            // The help message for the files argument of `cargo i18n import`.
+                            "The XLIFF files to import."
+                        )
+                    )
+                    .required(true)
+                    .num_args(1..)
+                    .value_parser(clap::value_parser!(PathBuf))
+                )
+            )
+            .subcommand(Command::new("doctor")
+                .about(
+                    tr!(
+                        // The help message for the `cargo i18n doctor` subcommand.
+                        "Check that the gettext tools (\"xtr\", \"msgcat\", \"msginit\", \"msgmerge\" and \"msgfmt\") required by a crate are installed, and print their versions. The executables can be configured in the \"[gettext.tools]\" table of the i18n config file, or using the \"CARGO_I18N_XTR\", \"CARGO_I18N_MSGCAT\", \"CARGO_I18N_MSGINIT\", \"CARGO_I18N_MSGMERGE\" and \"CARGO_I18N_MSGFMT\" environment variables."
+                    )
+                )
+            )
+            .subcommand(Command::new("validate")
+                .about(
+                    tr!(
+                        // The help message for the `cargo i18n validate` subcommand.
+                        "Check the localization resources of a crate for problems, using each of the localization systems it is configured to use."
+                    )
+                )
+            )
+            .subcommand(Command::new("lint")
+                .about(
+                    tr!(
+                        // The help message for the `cargo i18n lint` subcommand.
+                        "Check the fluent files of a crate for common mistakes in translations, such as variables which are not in the fallback language, translated protected terms, double spaces, trailing whitespace, and HTML tags or format specifiers which differ from the fallback language. The severity of each lint can be configured in the \"[fluent.lints]\" table of the i18n config file."
+                    )
+                )
+            )
+            .subcommand(Command::new("lint-glossary")
+                .about(
+                    tr!(
+                        // The help message for the `cargo i18n lint-glossary` subcommand.
+                        "Check that the fluent and gettext translations of a crate use the approved translations of the terms in the glossary file specified by \"glossary\" in the i18n config file, and not their disallowed variants."
+                    )
+                )
+            )
+            .subcommand(Command::new("codegen")
+                .about(
+                    tr!(
+                        // The help message for the `cargo i18n codegen` subcommand.
+                        "Generate a Rust module with a function for each message in the fluent files of the fallback language, e.g. \"messages::greeting(name)\" for the message \"greeting\" with the variable \"$name\", and constants for the ids of the messages and their attributes. The functions use the global loader of the crate, see \"init_global_loader!()\"."
+                    )
+                )
+                .arg(Arg::new("output")
+                    .help(
+                        tr!(
+                            // The help message for the `--output` command line argument of `cargo i18n codegen`.
+                            "The path of the generated module, relative to the crate."
+                        )
+                    )
+                    .long("output")
+                    .num_args(1)
+                    .default_value(i18n_build::codegen::DEFAULT_OUTPUT)
+                    .value_parser(clap::value_parser!(PathBuf))
+                )
+                .arg(Arg::new("check")
+                    .help(
+                        tr!(
+                            // The help message for the `--check` command line argument of `cargo i18n codegen`.
+                            "Don't write the module, instead fail if it is not up to date with the fluent files (e.g. in continuous integration)."
+                        )
+                    )
+                    .long("check")
+                    .action(ArgAction::SetTrue)
+                )
+            )
+            .subcommand(Command::new("where")
+                .about(
+                    tr!(
+                        // The help message for the `cargo i18n where` subcommand.
+                        "Print the locations of the fl!() calls in a crate's source code which use a fluent message, to help understand the context of the message."
+                    )
+                )
+                .arg(Arg::new("message-id")
+                    .help(
+                        tr!(
+                            // The help message for the message id argument of `cargo i18n where`.
+                            "The id of the fluent message, e.g. \"hello-world\"."
+                        )
+                    )
+                    .required(true)
+                    .num_args(1)
+                )
+            )
+            .subcommand(Command::new("report")
+                .about(
+                    tr!(
+                        // The help message for the `cargo i18n report` subcommand.
+                        "Create a report of the translation status of a crate's languages, with a table of the coverage of each language and the lists of their missing messages, suitable for committing to the repository or publishing in continuous integration."
+                    )
+                )
+                .arg(Arg::new("format")
+                    .help(
+                        tr!(
+                            // The help message for the `--format` command line argument of `cargo i18n report`.
+                            "The format of the report."
+                        )
+                    )
+                    .long("format")
+                    .num_args(1)
+                    .default_value("markdown")
+                    .value_parser(PossibleValuesParser::new(["html", "markdown"]))
+                )
+                .arg(Arg::new("output")
+                    .help(
+                        tr!(
+                            // The help message for the `--output` command line argument of `cargo i18n report`.
+                            "The path of the file to write the report to, instead of printing it."
+                        )
+                    )
+                    .long("output")
+                    .short('o')
+                    .num_args(1)
+                    .value_parser(clap::value_parser!(PathBuf))
+                )
+                .arg(Arg::new("badge")
+                    .help(
+                        tr!(
+                            // The help message for the `--badge` command line argument of `cargo i18n report`.
+                            "Also write an SVG badge showing the overall translation coverage to this path, for the crate's README."
+                        )
+                    )
+                    .long("badge")
+                    .num_args(1)
+                    .value_parser(clap::value_parser!(PathBuf))
+                )
+            )
+            .subcommand(Command::new("pseudo")
+                .about(
+                    tr!(
+                        // The help message for the `cargo i18n pseudo` subcommand.
+                        "Generate pseudolocalized resources for the \"qps-Ploc\" language from the fallback language, for testing that the user interface is localized and can accommodate longer translations. For the gettext system, \"qps-Ploc\" needs to be added to the \"target_languages\" in the i18n config file for its po file to be compiled."
+                    )
+                )
+                .arg(Arg::new("expansion")
+                    .help(
+                        tr!(
+                            // The help message for the `--expansion` command line argument of `cargo i18n pseudo`.
+                            "Lengthen each message by this percentage of its length, to simulate the expansion of translated text."
+                        )
+                    )
+                    .long("expansion")
+                    .num_args(1)
+                    .default_value("30")
+                    .value_parser(clap::value_parser!(u32))
+                )
+                .arg(Arg::new("no-accents")
+                    .help(
+                        tr!(
+                            // The help message for the `--no-accents` command line argument of `cargo i18n pseudo`.
+                            "Don't replace the letters in the messages with accented look-alikes."
+                        )
+                    )
+                    .long("no-accents")
+                    .action(ArgAction::SetTrue)
+                )
+                .arg(Arg::new("no-brackets")
+                    .help(
+                        tr!(
+                            // The help message for the `--no-brackets` command line argument of `cargo i18n pseudo`.
+                            "Don't surround the messages with brackets."
+                        )
+                    )
+                    .long("no-brackets")
+                    .action(ArgAction::SetTrue)
+                )
+            )
         )
         .get_matches();
 
-    if let Some(i18n_matches) = matches.subcommand_matches("i18n") {
+    let i18n_matches = matches.subcommand_matches("i18n");
+    let quiet = i18n_matches.is_some_and(|i18n_matches| i18n_matches.get_flag("quiet"));
+    let verbose = i18n_matches.map_or(0, |i18n_matches| i18n_matches.get_count("verbose"));
+    init_logger(quiet, verbose);
+
+    if let Some(i18n_matches) = i18n_matches {
+        JSON_MESSAGE_FORMAT.store(
+            i18n_matches
+                .get_one::<String>("message-format")
+                .is_some_and(|message_format| message_format == "json"),
+            Ordering::Relaxed,
+        );
+
         let config_file_name: &String = i18n_matches
             .get_one("config-file-name")
             .expect("expected a default config file name to be present");
@@ -165,11 +654,616 @@ fn main() -> Result<()> {
         let config_file_path = Path::new(config_file_name).to_path_buf();
 
         i18n_build::util::check_path_exists(&path)?;
-        i18n_build::util::check_path_exists(path.join(&config_file_path))?;
 
-        let crt: Crate = Crate::from(path, None, config_file_path)?;
-        run(crt)?;
+        if let Some(init_matches) = i18n_matches.subcommand_matches("init") {
+            return run_init(&path, &config_file_path, init_matches);
+        }
+
+        if let Some(add_language_matches) = i18n_matches.subcommand_matches("add-language") {
+            let language: &String = add_language_matches
+                .get_one("language")
+                .expect("expected the required language to be present");
+            let crt = Crate::from(path, None, config_file_path)?;
+            for changed_path in add_language(&crt, language)? {
+                println!(
+                    "{}",
+                    tr!(
+                        // {0} is the path of a file created or modified by `cargo i18n add-language`.
+                        "Created or updated \"{0}\"",
+                        changed_path.to_string_lossy()
+                    )
+                );
+            }
+            return Ok(());
+        }
+
+        #[cfg(feature = "translate")]
+        if let Some(translate_matches) = i18n_matches.subcommand_matches("translate") {
+            return run_translate(path, config_file_path, translate_matches);
+        }
+
+        if let Some(migrate_matches) = i18n_matches.subcommand_matches("migrate") {
+            return run_migrate(path, config_file_path, migrate_matches);
+        }
+
+        if let Some(export_matches) = i18n_matches.subcommand_matches("export") {
+            return run_export(path, config_file_path, export_matches);
+        }
+
+        if let Some(import_matches) = i18n_matches.subcommand_matches("import") {
+            return run_import(path, config_file_path, import_matches);
+        }
+
+        if i18n_matches.subcommand_matches("doctor").is_some() {
+            return run_doctor(path, config_file_path);
+        }
+
+        if i18n_matches.subcommand_matches("validate").is_some() {
+            return run_validate(path, config_file_path);
+        }
+
+        if i18n_matches.subcommand_matches("lint").is_some() {
+            return run_lint(path, config_file_path);
+        }
+
+        if i18n_matches.subcommand_matches("lint-glossary").is_some() {
+            return run_lint_glossary(path, config_file_path);
+        }
+
+        if let Some(codegen_matches) = i18n_matches.subcommand_matches("codegen") {
+            return run_codegen(path, config_file_path, codegen_matches);
+        }
+
+        if let Some(pseudo_matches) = i18n_matches.subcommand_matches("pseudo") {
+            return run_pseudo(path, config_file_path, pseudo_matches);
+        }
+
+        if let Some(where_matches) = i18n_matches.subcommand_matches("where") {
+            return run_where(path, config_file_path, where_matches);
+        }
+
+        if let Some(report_matches) = i18n_matches.subcommand_matches("report") {
+            return run_report(path, config_file_path, report_matches);
+        }
+
+        let options = cargo_i18n::Options {
+            path,
+            config_file_name: config_file_path,
+            workspace: i18n_matches.get_flag("workspace"),
+            jobs: i18n_matches.get_one::<NonZeroUsize>("jobs").copied(),
+            validate: false,
+        };
+
+        let show_progress = !quiet && verbose == 0;
+        if show_progress {
+            i18n_build::progress::set_progress_handler(Some(progress_handler()));
+        }
+        let report = cargo_i18n::run_with_options(&options);
+        if show_progress {
+            i18n_build::progress::set_progress_handler(None);
+            clear_progress();
+        }
+        let report = report?;
+        if !quiet {
+            if i18n_matches
+                .get_one::<String>("format")
+                .is_some_and(|format| format == "json")
+            {
+                println!("{}", serde_json::to_string_pretty(&report.summary)?);
+            } else {
+                println!("{}", report.summary);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Initialize the logger: only errors are logged when `quiet`,
+/// otherwise the `verbose` level (the number of times the option was
+/// used) enables info or debug logging. The `RUST_LOG` environment
+/// variable takes precedence.
+fn init_logger(quiet: bool, verbose: u8) {
+    let level = match (quiet, verbose) {
+        (true, _) | (false, 0) => "error",
+        (false, 1) => "info",
+        (false, _) => "debug",
+    };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(level)).init();
+}
+
+/// A handler which shows the progress of localizing the crates on
+/// stderr: on a single line which is updated with each step when it
+/// is a terminal, otherwise each crate is printed on its own line.
+fn progress_handler() -> Box<dyn Fn(&Progress) + Send + Sync> {
+    let terminal = std::io::stderr().is_terminal();
+    Box::new(move |progress| {
+        let mut stderr = std::io::stderr().lock();
+        // Failing to show the progress shouldn't stop the build.
+        let _ = if terminal {
+            write!(
+                stderr,
+                "\r\x1b[2K{0} {1}",
+                tr!(
+                    // Shown before the crate and step currently being localized, e.g. "Localizing my_crate: msgmerge [fr]".
+                    "Localizing"
+                ),
+                progress
+            )
+            .and_then(|_| stderr.flush())
+        } else if let Progress::Crate { .. } = progress {
+            writeln!(stderr, "{0} {1}", tr!("Localizing"), progress)
+        } else {
+            Ok(())
+        };
+    })
+}
+
+/// Clear the progress shown by the [progress_handler()], if it is
+/// shown on a terminal.
+fn clear_progress() {
+    if std::io::stderr().is_terminal() {
+        eprint!("\r\x1b[2K");
+    }
+}
+
+/// The `cargo i18n translate` subcommand, which is only available
+/// when the `translate` feature is enabled.
+fn translate_command() -> Option<Command> {
+    if cfg!(not(feature = "translate")) {
+        return None;
+    }
+    Some(Command::new("translate")
+        .about(
+            tr!(
+                // The help message for the `cargo i18n translate` subcommand.
+                "Add machine translated drafts of the messages which are missing from a crate's languages, using the service configured in the \"[translate]\" section of the i18n config file. For the fluent system, the drafts are appended to each language's ftl files preceded by a comment. For the gettext system, the untranslated entries in each po file are filled in and marked as fuzzy."
+            )
+        )
+        .arg(Arg::new("languages")
+            .help(
+                tr!(
+                    // The help message for the languages argument of `cargo i18n translate`.
+                    "The identifiers of the languages to translate, e.g. \"de-AT\". By default all the languages of the crate are translated."
+                )
+            )
+            .num_args(0..)
+            .value_parser(clap::value_parser!(LanguageIdentifier))
+        ))
+}
+
+/// Run the `cargo i18n translate` subcommand for the crate at `path`.
+#[cfg(feature = "translate")]
+fn run_translate(
+    path: PathBuf,
+    config_file_path: PathBuf,
+    matches: &clap::ArgMatches,
+) -> Result<()> {
+    use i18n_build::translate::{translate, HttpTranslator};
+
+    let languages: Vec<LanguageIdentifier> = matches
+        .get_many::<LanguageIdentifier>("languages")
+        .map(|languages| languages.cloned().collect())
+        .unwrap_or_default();
+    let crt = Crate::from(path, None, config_file_path)?;
+    let translator = HttpTranslator::from_config(crt.translate_config_or_err()?)?;
+    for changed_path in translate(&crt, &translator, &languages)? {
+        println!(
+            "{}",
+            tr!(
+                // {0} is the path of a file created or modified by `cargo i18n translate`.
+                "Created or updated \"{0}\"",
+                changed_path.to_string_lossy()
+            )
+        );
+    }
+    Ok(())
+}
+
+/// Run the `cargo i18n migrate` subcommand for the crate at `path`.
+fn run_migrate(path: PathBuf, config_file_path: PathBuf, matches: &clap::ArgMatches) -> Result<()> {
+    use i18n_build::migrate::{migrate_to_fluent, rewrite_tr_calls, MigrateOptions};
+
+    let crt = Crate::from(path, None, config_file_path)?;
+    let options = MigrateOptions {
+        assets_dir: matches
+            .get_one::<PathBuf>("assets-dir")
+            .expect("expected a default assets directory to be present")
+            .clone(),
+    };
+    let migration = migrate_to_fluent(&crt, &options)?;
+    let mut changed = migration.changed.clone();
+
+    if let Some(patch_path) = matches.get_one::<PathBuf>("patch") {
+        let loader: &String = matches
+            .get_one("loader")
+            .expect("expected a default loader to be present");
+        let patch = rewrite_tr_calls(&crt, &migration, loader)?;
+        std::fs::write(patch_path, patch)
+            .map_err(|err| i18n_build::error::PathError::cannot_create_file(patch_path, err))?;
+        changed.push(patch_path.clone());
     }
 
+    for changed_path in changed {
+        println!(
+            "{}",
+            tr!(
+                // {0} is the path of a file created or modified by `cargo i18n migrate`.
+                "Created or updated \"{0}\"",
+                changed_path.to_string_lossy()
+            )
+        );
+    }
     Ok(())
 }
+
+/// Run the `cargo i18n export` subcommand for the crate at `path`.
+fn run_export(path: PathBuf, config_file_path: PathBuf, matches: &clap::ArgMatches) -> Result<()> {
+    use i18n_build::xliff::{export, export_languages, XliffVersion};
+
+    let version: XliffVersion = matches
+        .get_one::<String>("xliff-version")
+        .expect("expected a default XLIFF version to be present")
+        .parse()?;
+    let output_dir = matches
+        .get_one::<PathBuf>("output-dir")
+        .expect("expected a default output directory to be present");
+    let crt = Crate::from(path, None, config_file_path)?;
+    let languages: Vec<LanguageIdentifier> =
+        match matches.get_many::<LanguageIdentifier>("languages") {
+            Some(languages) => languages.cloned().collect(),
+            None => export_languages(&crt)?,
+        };
+
+    i18n_build::util::create_dir_all_if_not_exists(output_dir)?;
+    for language in &languages {
+        let document = export(&crt, language, version)?;
+        let output_path = output_dir.join(format!("{0}.{1}.xlf", crt.module_name(), language));
+        std::fs::write(&output_path, document.to_xml())
+            .map_err(|err| i18n_build::error::PathError::cannot_create_file(&output_path, err))?;
+        println!(
+            "{}",
+            tr!(
+                // {0} is the path of a file created or modified by `cargo i18n export`.
+                "Created or updated \"{0}\"",
+                output_path.to_string_lossy()
+            )
+        );
+    }
+    Ok(())
+}
+
+/// Run the `cargo i18n import` subcommand for the crate at `path`.
+fn run_import(path: PathBuf, config_file_path: PathBuf, matches: &clap::ArgMatches) -> Result<()> {
+    use anyhow::Context;
+    use i18n_build::xliff::{import, XliffDocument};
+
+    let crt = Crate::from(path, None, config_file_path)?;
+    for file in matches
+        .get_many::<PathBuf>("files")
+        .expect("expected the required files to be present")
+    {
+        let xml = std::fs::read_to_string(file).with_context(|| {
+            tr!(
+                // {0} is the path to the XLIFF file being imported.
+                "Unable to read \"{0}\".",
+                file.to_string_lossy()
+            )
+        })?;
+        let document = XliffDocument::from_xml(&xml).with_context(|| {
+            tr!(
+                // {0} is the path to the XLIFF file being imported.
+                "Unable to import \"{0}\".",
+                file.to_string_lossy()
+            )
+        })?;
+        for changed_path in import(&crt, &document)? {
+            println!(
+                "{}",
+                tr!(
+                    // {0} is the path of a file created or modified by `cargo i18n import`.
+                    "Created or updated \"{0}\"",
+                    changed_path.to_string_lossy()
+                )
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Run the `cargo i18n validate` subcommand for the crate at `path`.
+fn run_validate(path: PathBuf, config_file_path: PathBuf) -> Result<()> {
+    let crt = Crate::from(path, None, config_file_path)?;
+    report_issues(&i18n_build::system::validate(&crt)?)
+}
+
+/// Run the `cargo i18n lint` subcommand for the crate at `path`.
+fn run_lint(path: PathBuf, config_file_path: PathBuf) -> Result<()> {
+    let crt = Crate::from(path, None, config_file_path)?;
+    report_issues(&i18n_build::lint::lint(&crt)?)
+}
+
+/// Run the `cargo i18n doctor` subcommand for the crate at `path`,
+/// or for the default tools if there is no crate (e.g. in a
+/// workspace).
+fn run_doctor(path: PathBuf, config_file_path: PathBuf) -> Result<()> {
+    let crt = Crate::from(path, None, config_file_path).ok();
+    let statuses = i18n_build::doctor::check_tools(crt.as_ref());
+    for status in &statuses {
+        println!("{status}");
+    }
+    let missing: Vec<&str> = statuses
+        .iter()
+        .filter(|status| status.is_missing())
+        .map(|status| status.name)
+        .collect();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(tr!(
+            // {0} is a list of the names of the tools which are missing.
+            "Some of the required tools are missing: {0}",
+            missing.join(", ")
+        )))
+    }
+}
+
+/// Run the `cargo i18n lint-glossary` subcommand for the crate at
+/// `path`.
+fn run_lint_glossary(path: PathBuf, config_file_path: PathBuf) -> Result<()> {
+    let crt = Crate::from(path, None, config_file_path)?;
+    report_issues(&i18n_build::glossary::lint_glossary(&crt)?)
+}
+
+/// Print the `issues` found by `cargo i18n validate`, `cargo i18n
+/// lint` or `cargo i18n lint-glossary`, failing if any of them are
+/// errors.
+fn report_issues(issues: &[ValidationIssue]) -> Result<()> {
+    for issue in issues {
+        eprintln!("{issue}");
+    }
+    // Warnings are reported, but don't fail the validation.
+    let errors = issues
+        .iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .count();
+    if errors == 0 {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(tr!(
+            // {0} is the number of problems found by `cargo i18n validate`, `cargo i18n lint` or `cargo i18n lint-glossary`.
+            "Found {0} problems with the localization resources.",
+            errors
+        )))
+    }
+}
+
+/// Run the `cargo i18n codegen` subcommand for the crate at `path`.
+fn run_codegen(path: PathBuf, config_file_path: PathBuf, matches: &clap::ArgMatches) -> Result<()> {
+    let crt = Crate::from(path, None, config_file_path)?;
+    let output = crt.path.join(
+        matches
+            .get_one::<PathBuf>("output")
+            .expect("expected a default output to be present"),
+    );
+
+    if matches.get_flag("check") {
+        let source = i18n_build::codegen::generate(&crt)?;
+        if std::fs::read_to_string(&output).is_ok_and(|existing| existing == source) {
+            return Ok(());
+        }
+        return Err(anyhow::anyhow!(tr!(
+            // {0} is the path of the module generated by `cargo i18n codegen`.
+            "\"{0}\" is not up to date with the fluent files, run \"cargo i18n codegen\" to update it.",
+            output.to_string_lossy()
+        )));
+    }
+
+    if i18n_build::codegen::write(&crt, &output)? {
+        println!(
+            "{}",
+            tr!(
+                // {0} is the path of a file created or modified by `cargo i18n codegen`.
+                "Created or updated \"{0}\"",
+                output.to_string_lossy()
+            )
+        );
+    }
+    Ok(())
+}
+
+/// Run the `cargo i18n pseudo` subcommand for the crate at `path`.
+fn run_pseudo(path: PathBuf, config_file_path: PathBuf, matches: &clap::ArgMatches) -> Result<()> {
+    use i18n_embed::Pseudolocalization;
+
+    let crt = Crate::from(path, None, config_file_path)?;
+    let pseudolocalization = Pseudolocalization {
+        accents: !matches.get_flag("no-accents"),
+        expansion_percent: *matches
+            .get_one::<u32>("expansion")
+            .expect("expected a default expansion to be present"),
+        brackets: !matches.get_flag("no-brackets"),
+    };
+    for changed_path in i18n_build::pseudo::pseudolocalize(&crt, &pseudolocalization)? {
+        println!(
+            "{}",
+            tr!(
+                // {0} is the path of a file created or modified by `cargo i18n pseudo`.
+                "Created or updated \"{0}\"",
+                changed_path.to_string_lossy()
+            )
+        );
+    }
+    Ok(())
+}
+
+/// Run the `cargo i18n where` subcommand for the crate at `path`.
+fn run_where(path: PathBuf, config_file_path: PathBuf, matches: &clap::ArgMatches) -> Result<()> {
+    let message_id: &String = matches
+        .get_one("message-id")
+        .expect("expected the required message id to be present");
+    let crt = Crate::from(path, None, config_file_path)?;
+    let usages = i18n_build::usage::message_usages(&crt)?;
+    let locations = usages.get(message_id).ok_or_else(|| {
+        anyhow::anyhow!(tr!(
+            // {0} is the id of the fluent message.
+            "The message \"{0}\" is not used by any fl!() calls in the crate.",
+            message_id
+        ))
+    })?;
+    for location in locations {
+        println!("{location}");
+    }
+    Ok(())
+}
+
+/// Run the `cargo i18n report` subcommand for the crate at `path`.
+fn run_report(path: PathBuf, config_file_path: PathBuf, matches: &clap::ArgMatches) -> Result<()> {
+    use i18n_build::report::{badge, report, ReportFormat};
+
+    let format: ReportFormat = matches
+        .get_one::<String>("format")
+        .expect("expected a default report format to be present")
+        .parse()?;
+    let crt = Crate::from(path, None, config_file_path)?;
+    let crate_report = report(&crt)?;
+    let rendered = crate_report.render(format);
+
+    match matches.get_one::<PathBuf>("output") {
+        Some(output_path) => {
+            std::fs::write(output_path, rendered).map_err(|err| {
+                i18n_build::error::PathError::cannot_create_file(output_path, err)
+            })?;
+            println!(
+                "{}",
+                tr!(
+                    // {0} is the path of a file created or modified by `cargo i18n report`.
+                    "Created or updated \"{0}\"",
+                    output_path.to_string_lossy()
+                )
+            );
+        }
+        None => print!("{rendered}"),
+    }
+
+    if let Some(badge_path) = matches.get_one::<PathBuf>("badge") {
+        let svg = badge(
+            &tr!(
+                // The label of the badge created by `cargo i18n report --badge`.
+                "translated"
+            ),
+            crate_report.coverage(),
+        );
+        std::fs::write(badge_path, svg)
+            .map_err(|err| i18n_build::error::PathError::cannot_create_file(badge_path, err))?;
+        let created = tr!(
+            // {0} is the path of a file created or modified by `cargo i18n report`.
+            "Created or updated \"{0}\"",
+            badge_path.to_string_lossy()
+        );
+        // Keep the report which was printed to stdout intact.
+        if matches.contains_id("output") {
+            println!("{created}");
+        } else {
+            eprintln!("{created}");
+        }
+    }
+    Ok(())
+}
+
+/// Run the `cargo i18n init` subcommand for the crate at `path`.
+fn run_init(path: &Path, config_file_path: &Path, matches: &clap::ArgMatches) -> Result<()> {
+    let interactive = std::io::stdin().is_terminal();
+    let default_options = InitOptions::default();
+
+    let system: LocalizationSystem = match matches.get_one::<String>("system") {
+        Some(system) => system.parse()?,
+        None if interactive => prompt(
+            &tr!(
+                // Prompt for the localization system when running `cargo i18n init`.
+                "Localization system (fluent or gettext)"
+            ),
+            &default_options.system.to_string(),
+        )?
+        .parse()?,
+        None => default_options.system,
+    };
+
+    let fallback_language = match matches.get_one::<String>("fallback-language") {
+        Some(language) => language.clone(),
+        None if interactive => prompt(
+            &tr!(
+                // Prompt for the fallback language when running `cargo i18n init`.
+                "Fallback language"
+            ),
+            &default_options.fallback_language,
+        )?,
+        None => default_options.fallback_language,
+    };
+
+    let target_languages: Vec<String> = match matches.get_many::<String>("target-languages") {
+        Some(languages) => languages.cloned().collect(),
+        None if interactive && system == LocalizationSystem::Gettext => prompt(
+            &tr!(
+                // Prompt for the comma separated target languages when running `cargo i18n init`.
+                "Languages to translate into (comma separated)"
+            ),
+            "",
+        )?
+        .split(',')
+        .map(str::trim)
+        .filter(|language| !language.is_empty())
+        .map(ToString::to_string)
+        .collect(),
+        None => default_options.target_languages,
+    };
+
+    let patch_cargo_toml = matches.get_flag("patch-cargo-toml")
+        || (interactive
+            && matches!(
+                prompt(
+                    &tr!(
+                        // Prompt for whether to add dependencies to Cargo.toml when running `cargo i18n init`.
+                        "Add the required dependencies to Cargo.toml? (y/n)"
+                    ),
+                    "n",
+                )?
+                .as_str(),
+                "y" | "Y" | "yes"
+            ));
+
+    let options = InitOptions {
+        system,
+        fallback_language,
+        target_languages,
+        patch_cargo_toml,
+    };
+
+    for changed_path in init(path, config_file_path, &options)? {
+        println!(
+            "{}",
+            tr!(
+                // {0} is the path of a file or directory created or modified by `cargo i18n init`.
+                "Created or updated \"{0}\"",
+                changed_path.to_string_lossy()
+            )
+        );
+    }
+
+    Ok(())
+}
+
+/// Prompt the user for a value on the terminal, returning `default`
+/// if they don't enter anything.
+fn prompt(message: &str, default: &str) -> Result<String> {
+    print!("{message} [{default}]: ");
+    std::io::stdout().flush()?;
+    let mut input = String::new();
+    std::io::stdin().lock().read_line(&mut input)?;
+    let input = input.trim();
+    Ok(if input.is_empty() {
+        default.to_string()
+    } else {
+        input.to_string()
+    })
+}
@@ -0,0 +1,62 @@
+use cargo_i18n::{run_with_options, Options};
+use i18n_build::system::Severity;
+use std::num::NonZeroUsize;
+
+/// Running `cargo i18n` in-process reports the files which were
+/// written, and the problems found by validating the crate.
+#[test]
+fn files_written_and_warnings() {
+    let crate_path = std::env::temp_dir().join("cargo-i18n-run-with-options");
+    let _ = std::fs::remove_dir_all(&crate_path);
+    std::fs::create_dir_all(crate_path.join("src")).unwrap();
+    std::fs::create_dir_all(crate_path.join("i18n/en")).unwrap();
+    std::fs::create_dir_all(crate_path.join("i18n/de")).unwrap();
+    std::fs::write(
+        crate_path.join("Cargo.toml"),
+        "[package]\nname = \"run-with-options\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        "fallback_language = \"en\"\n\n\
+        [fluent]\nassets_dir = \"i18n\"\nsource_comments = \"i18n:\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        crate_path.join("src/main.rs"),
+        "fn main() {\n\
+         \x20   // i18n: Shown when the application starts.\n\
+         \x20   println!(\"{}\", fl!(LOADER, \"hello-world\"));\n\
+         }\n",
+    )
+    .unwrap();
+    let en_path = crate_path.join("i18n/en/run_with_options.ftl");
+    std::fs::write(&en_path, "hello-world = Hello World!\nbye = Bye!\n").unwrap();
+    let de_path = crate_path.join("i18n/de/run_with_options.ftl");
+    std::fs::write(&de_path, "hello-world = Hallo Welt!\n").unwrap();
+
+    let options = Options {
+        path: crate_path.clone(),
+        jobs: NonZeroUsize::new(1),
+        ..Options::default()
+    };
+    let report = run_with_options(&options).unwrap();
+    assert_eq!(vec![en_path.clone()], report.files_written);
+    assert_eq!(1, report.warnings.len());
+    assert_eq!(Some(de_path), report.warnings[0].path);
+    assert_eq!(Severity::Warning, report.warnings[0].severity);
+    assert!(std::fs::read_to_string(&en_path)
+        .unwrap()
+        .starts_with("# Shown when the application starts.\nhello-world"));
+
+    // Nothing is written when the resources are up to date.
+    let report = run_with_options(&Options {
+        validate: false,
+        ..options
+    })
+    .unwrap();
+    assert!(report.files_written.is_empty());
+    assert!(report.warnings.is_empty());
+
+    std::fs::remove_dir_all(&crate_path).unwrap();
+}
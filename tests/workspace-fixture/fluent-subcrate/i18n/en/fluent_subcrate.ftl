@@ -0,0 +1,2 @@
+hello-world = Hello World!
+greeting = Hello {$name}!
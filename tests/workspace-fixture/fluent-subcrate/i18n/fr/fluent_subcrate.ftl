@@ -0,0 +1,2 @@
+hello-world = Bonjour le monde!
+greeting = Bonjour {$name}!
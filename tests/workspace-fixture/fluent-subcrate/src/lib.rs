@@ -0,0 +1,36 @@
+//! A subcrate of the workspace fixture which is localized using the
+//! fluent system, with its own `i18n.toml`.
+
+use i18n_embed::{
+    fluent::{fluent_language_loader, FluentLanguageLoader},
+    DefaultLocalizer, LanguageLoader,
+};
+use once_cell::sync::Lazy;
+use rust_embed::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "i18n/"]
+struct Localizations;
+
+static LANGUAGE_LOADER: Lazy<FluentLanguageLoader> = Lazy::new(|| {
+    let loader: FluentLanguageLoader = fluent_language_loader!();
+    loader
+        .load_fallback_language(&Localizations)
+        .expect("Error while loading fallback language");
+    loader
+});
+
+/// Get the hello world statement in the currently selected language.
+pub fn hello_world() -> String {
+    i18n_embed_fl::fl!(LANGUAGE_LOADER, "hello-world").into()
+}
+
+/// Greet `name` in the currently selected language.
+pub fn greeting(name: &str) -> String {
+    i18n_embed_fl::fl!(LANGUAGE_LOADER, "greeting", name = name)
+}
+
+/// Get the `Localizer` to be used for localizing this subcrate.
+pub fn localizer() -> DefaultLocalizer<'static> {
+    DefaultLocalizer::new(&*LANGUAGE_LOADER, &Localizations)
+}
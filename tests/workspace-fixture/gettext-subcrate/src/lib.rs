@@ -0,0 +1,10 @@
+//! A subcrate of the workspace fixture which is localized using the
+//! gettext system, with its strings extracted into the parent crate's
+//! pot file.
+
+use tr::tr;
+
+/// Get the goodbye statement in the currently selected language.
+pub fn goodbye() -> String {
+    tr!("Goodbye!")
+}
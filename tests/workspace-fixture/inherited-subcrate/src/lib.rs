@@ -0,0 +1,3 @@
+//! A subcrate of the workspace fixture which has no `i18n.toml` of
+//! its own, and instead inherits the config of the root crate which
+//! lists it in its `subcrates`.
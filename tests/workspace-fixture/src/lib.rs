@@ -0,0 +1,7 @@
+//! A fixture crate combining a fluent subcrate which is localized
+//! independently, and a gettext subcrate which extracts its strings
+//! into this crate's pot file. It is used to test how `cargo i18n`
+//! and the `fl!()` macro resolve the configuration of each crate.
+
+pub use fluent_subcrate::{greeting, hello_world};
+pub use gettext_subcrate::goodbye;
@@ -0,0 +1,342 @@
+use i18n_config::{Crate, Workspace};
+use i18n_embed::Localizer;
+use std::path::{Path, PathBuf};
+
+fn fixture_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn canonical_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap()
+}
+
+/// The root crate uses its own gettext config.
+#[test]
+fn root_active_config() {
+    let root = Crate::from(fixture_path(), None, "i18n.toml").unwrap();
+    let (config_crate, config) = root.active_config().unwrap().unwrap();
+
+    assert_eq!("workspace-fixture", config_crate.name);
+    assert_eq!(
+        vec![
+            PathBuf::from("fluent-subcrate"),
+            PathBuf::from("gettext-subcrate"),
+            PathBuf::from("inherited-subcrate"),
+        ],
+        config.subcrates
+    );
+    assert!(config.gettext.as_ref().unwrap().collate_extracted_subcrates);
+    assert!(!root.collated_subcrate());
+}
+
+/// The fluent subcrate has its own config, so it is localized
+/// independently of the root crate.
+#[test]
+fn fluent_subcrate_active_config() {
+    let root = Crate::from(fixture_path(), None, "i18n.toml").unwrap();
+    let subcrate = Crate::from(
+        fixture_path().join("fluent-subcrate"),
+        Some(&root),
+        "i18n.toml",
+    )
+    .unwrap();
+    let (config_crate, config) = subcrate.active_config().unwrap().unwrap();
+
+    assert_eq!("fluent-subcrate", config_crate.name);
+    assert_eq!(
+        PathBuf::from("i18n"),
+        config.fluent.as_ref().unwrap().assets_dir
+    );
+    assert!(!subcrate.collated_subcrate());
+}
+
+/// The gettext subcrate extracts its strings to the root crate, so
+/// it uses the root crate's config.
+#[test]
+fn gettext_subcrate_active_config() {
+    let root = Crate::from(fixture_path(), None, "i18n.toml").unwrap();
+    let subcrate = Crate::from(
+        fixture_path().join("gettext-subcrate"),
+        Some(&root),
+        "i18n.toml",
+    )
+    .unwrap();
+    let (config_crate, _config) = subcrate.active_config().unwrap().unwrap();
+
+    assert_eq!("workspace-fixture", config_crate.name);
+    assert!(subcrate.collated_subcrate());
+}
+
+/// A subcrate without a known parent can discover the root crate
+/// which lists it in its `subcrates`.
+#[test]
+fn find_parent() {
+    for subcrate_name in ["fluent-subcrate", "gettext-subcrate"] {
+        let subcrate = Crate::from(fixture_path().join(subcrate_name), None, "i18n.toml").unwrap();
+        let parent = subcrate.find_parent().unwrap();
+
+        assert_eq!("workspace-fixture", parent.name);
+        assert_eq!(
+            canonical_path(&fixture_path()),
+            canonical_path(&parent.path)
+        );
+    }
+}
+
+/// The messages checked by the `fl!()` macro in the fluent subcrate
+/// are available at runtime.
+#[test]
+fn fluent_subcrate_fl() {
+    assert_eq!("Hello World!", workspace_fixture::hello_world());
+
+    let localizer = fluent_subcrate::localizer();
+    let fr: i18n_embed::unic_langid::LanguageIdentifier = "fr".parse().unwrap();
+    localizer.select(&[fr]).unwrap();
+    assert_eq!(
+        "Bonjour \u{2068}Luke\u{2069}!",
+        workspace_fixture::greeting("Luke")
+    );
+
+    let en: i18n_embed::unic_langid::LanguageIdentifier = "en".parse().unwrap();
+    localizer.select(&[en]).unwrap();
+}
+
+/// Without a loaded catalog the gettext subcrate returns the source
+/// strings.
+#[test]
+fn gettext_subcrate_source_strings() {
+    assert_eq!("Goodbye!", workspace_fixture::goodbye());
+}
+
+/// Workspace discovery finds the fixture crates, and only localizes
+/// the root crate directly, because the other two are its subcrates.
+#[test]
+fn workspace_member_crates() {
+    let workspace = Workspace::from_cargo_metadata(fixture_path()).unwrap();
+    let member_names: Vec<&str> = workspace
+        .members
+        .iter()
+        .map(|member| member.name.as_str())
+        .collect();
+    assert!(member_names.contains(&"workspace-fixture"));
+    assert!(member_names.contains(&"fluent-subcrate"));
+    assert!(member_names.contains(&"gettext-subcrate"));
+
+    let crate_names: Vec<String> = workspace
+        .member_crates("i18n.toml")
+        .unwrap()
+        .into_iter()
+        .map(|crt| crt.name)
+        .collect();
+    assert!(crate_names.contains(&"workspace-fixture".to_string()));
+    assert!(!crate_names.contains(&"fluent-subcrate".to_string()));
+    assert!(!crate_names.contains(&"gettext-subcrate".to_string()));
+}
+
+/// Members can inherit the `[workspace.metadata.i18n]` config.
+#[test]
+fn workspace_metadata_config() {
+    let fixture_path = fixture_path();
+    let manifest_path = |member: &str| fixture_path.join(member).join("Cargo.toml");
+    let metadata = serde_json::json!({
+        "packages": [
+            {
+                "id": "fluent-subcrate",
+                "name": "fluent-subcrate",
+                "manifest_path": manifest_path("fluent-subcrate"),
+                "metadata": null,
+            },
+            {
+                "id": "workspace-fixture",
+                "name": "workspace-fixture",
+                "manifest_path": manifest_path(""),
+                "metadata": { "i18n": { "workspace": true } },
+            },
+        ],
+        "workspace_members": ["fluent-subcrate", "workspace-fixture"],
+        "workspace_root": fixture_path,
+        "metadata": {
+            "i18n": {
+                "fallback_language": "en",
+                "fluent": { "assets_dir": "i18n" },
+            },
+        },
+    });
+
+    let workspace =
+        Workspace::from_metadata_json(manifest_path(""), metadata.to_string().as_bytes()).unwrap();
+    assert!(!workspace.members[0].inherits_workspace_config);
+    assert!(workspace.members[1].inherits_workspace_config);
+
+    let config = workspace.i18n_config.as_ref().unwrap();
+    assert_eq!(
+        PathBuf::from("i18n"),
+        config.fluent.as_ref().unwrap().assets_dir
+    );
+
+    // Using a config file name which doesn't exist, so that only the
+    // member inheriting the workspace config is localized.
+    let crates = workspace.member_crates("missing.toml").unwrap();
+    assert_eq!(1, crates.len());
+    assert_eq!("workspace-fixture", crates[0].name);
+    assert!(crates[0].i18n_config.as_ref().unwrap().fluent.is_some());
+}
+
+/// Subcrates with their own `i18n.toml` can be discovered instead of
+/// being listed in `subcrates`.
+#[test]
+fn auto_discover_subcrates() {
+    let mut root = Crate::from(fixture_path(), None, "i18n.toml").unwrap();
+    let config = root.i18n_config.as_mut().unwrap();
+    config.subcrates.clear();
+    assert!(root.subcrate_paths().is_empty());
+
+    let config = root.i18n_config.as_mut().unwrap();
+    config.auto_discover_subcrates = true;
+    assert_eq!(
+        vec![
+            PathBuf::from("fluent-subcrate"),
+            PathBuf::from("gettext-subcrate")
+        ],
+        root.subcrate_paths()
+    );
+
+    // Listed subcrates are not duplicated.
+    let config = root.i18n_config.as_mut().unwrap();
+    config.subcrates = vec![PathBuf::from("gettext-subcrate")];
+    assert_eq!(
+        vec![
+            PathBuf::from("gettext-subcrate"),
+            PathBuf::from("fluent-subcrate")
+        ],
+        root.subcrate_paths()
+    );
+}
+
+/// A subcrate without its own `i18n.toml` inherits the config of the
+/// parent which lists it in its `subcrates`, while a subcrate with its
+/// own `i18n.toml` uses that instead.
+#[test]
+fn locate_inherited_crate_paths() {
+    // This is the only test which depends on `CARGO_MANIFEST_DIR` at
+    // runtime.
+    let subcrate_path = fixture_path().join("inherited-subcrate");
+    std::env::set_var("CARGO_MANIFEST_DIR", &subcrate_path);
+    let crate_paths = i18n_config::locate_crate_paths().unwrap();
+    assert_eq!(subcrate_path, crate_paths.crate_dir);
+    assert_eq!(
+        canonical_path(&fixture_path().join("i18n.toml")),
+        canonical_path(&crate_paths.i18n_config_file)
+    );
+    assert_eq!(
+        canonical_path(&fixture_path()),
+        canonical_path(&crate_paths.i18n_config_crate_dir)
+    );
+    assert_eq!(
+        Some("workspace-fixture"),
+        crate_paths.i18n_config_crate_name.as_deref()
+    );
+
+    let subcrate_path = fixture_path().join("fluent-subcrate");
+    std::env::set_var("CARGO_MANIFEST_DIR", &subcrate_path);
+    let crate_paths = i18n_config::locate_crate_paths().unwrap();
+    assert_eq!(
+        subcrate_path.join("i18n.toml"),
+        crate_paths.i18n_config_file
+    );
+    assert_eq!(subcrate_path, crate_paths.i18n_config_crate_dir);
+    assert_eq!(None, crate_paths.i18n_config_crate_name);
+
+    std::env::set_var("CARGO_MANIFEST_DIR", fixture_path());
+}
+
+/// The config can be specified in the `[package.metadata.i18n]` table
+/// of the `Cargo.toml`, which is used when there is no `i18n.toml`.
+#[test]
+fn cargo_toml_config() {
+    use i18n_config::I18nConfig;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let crate_path = tmp.path().to_path_buf();
+    let cargo_path = crate_path.join("Cargo.toml");
+    std::fs::write(
+        &cargo_path,
+        "[package]\nname = \"cargo-toml-config\"\nversion = \"0.1.0\"\n\n\
+        [package.metadata.i18n]\nfallback_language = \"en\"\n\n\
+        [package.metadata.i18n.fluent]\nassets_dir = \"locales\"\n",
+    )
+    .unwrap();
+
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    let config = crt.config_or_err().unwrap();
+    assert_eq!("en", config.fallback_language.to_string());
+    assert_eq!(
+        PathBuf::from("locales"),
+        config.fluent.as_ref().unwrap().assets_dir
+    );
+    let config = I18nConfig::from_file(&cargo_path).unwrap();
+    assert_eq!("en", config.fallback_language.to_string());
+
+    // The `i18n.toml` takes precedence.
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        "fallback_language = \"fr\"\n\n[fluent]\nassets_dir = \"i18n\"\n",
+    )
+    .unwrap();
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    assert_eq!(
+        "fr",
+        crt.config_or_err().unwrap().fallback_language.to_string()
+    );
+
+    // Inheriting the workspace's config is not a config of its own.
+    std::fs::remove_file(crate_path.join("i18n.toml")).unwrap();
+    std::fs::write(
+        &cargo_path,
+        "[package]\nname = \"cargo-toml-config\"\nversion = \"0.1.0\"\n\n\
+        [package.metadata.i18n]\nworkspace = true\n",
+    )
+    .unwrap();
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    assert!(crt.i18n_config.is_none());
+    assert!(I18nConfig::from_cargo_toml(&cargo_path).unwrap().is_none());
+    assert!(I18nConfig::from_file(&cargo_path).is_err());
+}
+
+/// `use_isolating` in the `[fluent]` section is `true` by default.
+#[test]
+fn fluent_use_isolating_config() {
+    let tmp = tempfile::tempdir().unwrap();
+    let crate_path = tmp.path().to_path_buf();
+    std::fs::write(
+        crate_path.join("Cargo.toml"),
+        "[package]\nname = \"use-isolating\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    let config = "fallback_language = \"en\"\n\n[fluent]\nassets_dir = \"i18n\"\n";
+    std::fs::write(crate_path.join("i18n.toml"), config).unwrap();
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    assert!(
+        crt.config_or_err()
+            .unwrap()
+            .fluent
+            .as_ref()
+            .unwrap()
+            .use_isolating
+    );
+
+    std::fs::write(
+        crate_path.join("i18n.toml"),
+        format!("{config}use_isolating = false\n"),
+    )
+    .unwrap();
+    let crt = Crate::from(crate_path.clone(), None, "i18n.toml").unwrap();
+    assert!(
+        !crt.config_or_err()
+            .unwrap()
+            .fluent
+            .as_ref()
+            .unwrap()
+            .use_isolating
+    );
+}